@@ -402,6 +402,7 @@ impl Agent {
         let start = Instant::now();
 
         let result = if let Some(tool) = self.tools.iter().find(|t| t.name() == call.name) {
+            let _permit = crate::tools::concurrency::acquire(&call.name).await;
             match tool.execute(call.arguments.clone()).await {
                 Ok(r) => {
                     self.observer.record_event(&ObserverEvent::ToolCall {
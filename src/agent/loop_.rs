@@ -1,13 +1,16 @@
-use crate::approval::{ApprovalManager, ApprovalRequest, ApprovalResponse};
-use crate::config::Config;
+use crate::approval::{attach, ApprovalManager, ApprovalRequest, ApprovalResponse};
+use crate::config::{Config, ContentInjectionConfig};
+use crate::cost::usage::{estimate_tokens, ActivityKind, UsageEvent, UsageScope, USAGE_SCOPE};
 use crate::memory::{self, Memory, MemoryCategory};
 use crate::multimodal;
-use crate::observability::{self, runtime_trace, Observer, ObserverEvent};
+use crate::observability::{self, runtime_trace, turn, turn_report, Observer, ObserverEvent};
 use crate::providers::{
-    self, ChatMessage, ChatRequest, Provider, ProviderCapabilityError, ToolCall,
+    self, ChatMessage, ChatRequest, ChatResponse, Provider, ProviderCapabilityError,
+    ResponseStream, StreamOptions, ToolCall,
 };
 use crate::runtime;
-use crate::security::SecurityPolicy;
+use crate::security::injection_guard::guard_tool_output;
+use crate::security::{ArgumentScan, LeakDetector, LeakResult, SecurityPolicy, ToolAnomalyMonitor};
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
@@ -25,6 +28,7 @@ use std::io::Write as _;
 use std::sync::{Arc, LazyLock};
 use std::time::{Duration, Instant};
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 use uuid::Uuid;
 
 mod context;
@@ -32,16 +36,19 @@ pub(crate) mod detection;
 mod execution;
 mod history;
 mod parsing;
+pub mod planning;
 
-use context::{build_context, build_hardware_context};
+pub(crate) use context::build_context;
+use context::build_hardware_context;
 use detection::{DetectionVerdict, LoopDetectionConfig, LoopDetector};
 use execution::{
     execute_tools_parallel, execute_tools_sequential, should_execute_tools_in_parallel,
     ToolExecutionOutcome,
 };
+pub(crate) use history::summarize_messages;
 #[cfg(test)]
 use history::{apply_compaction_summary, build_compaction_transcript};
-use history::{auto_compact_history, trim_history};
+use history::{auto_compact_history, context_budget, fit_context_window, trim_history};
 #[allow(unused_imports)]
 use parsing::{
     default_param_for_tool, detect_tool_call_parse_issue, extract_json_values, map_tool_name_alias,
@@ -49,10 +56,16 @@ use parsing::{
     parse_perl_style_tool_calls, parse_structured_tool_calls, parse_tool_call_value,
     parse_tool_calls, parse_tool_calls_from_json_value, tool_call_signature, ParsedToolCall,
 };
+use planning::{planned_call_output, PlanScope, PLAN_SCOPE};
 
 /// Minimum characters per chunk when relaying LLM text to a streaming draft.
 const STREAM_CHUNK_MIN_CHARS: usize = 80;
 
+/// Text that may open a prompt-guided tool call (XML tags, fenced blocks).
+/// Live streaming stops relaying at the first one so raw tool-call markup
+/// never reaches a draft; the parsed answer is relayed after the call.
+const STREAM_HOLD_BACK_MARKERS: [&str; 2] = ["<", "```"];
+
 /// Default maximum agentic tool-use iterations per user message to prevent runaway loops.
 /// Used as a safe fallback when `max_tool_iterations` is unset or configured as zero.
 const DEFAULT_MAX_TOOL_ITERATIONS: usize = 20;
@@ -80,13 +93,62 @@ fn should_treat_provider_as_vision_capable(provider_name: &str, provider: &dyn P
 const SLASH_COMMANDS: &[(&[&str], &str, &str)] = &[
     (&["/help"], "/help", "Show this help message"),
     (
-        &["/clear", "/new"],
+        &["/clear", "/new", "/session clear"],
         "/clear /new",
         "Clear conversation history",
     ),
+    (
+        &["/tools"],
+        "/tools",
+        "List the tools available to the agent",
+    ),
+    (
+        &["/model"],
+        "/model [name]",
+        "Show the model, or switch it for this session",
+    ),
+    (
+        &["/approve"],
+        "/approve [tool]",
+        "List tools still needing approval, or approve one for this session",
+    ),
     (&["/quit", "/exit"], "/quit /exit", "Exit interactive mode"),
 ];
 
+/// Interactive-mode history file, in the config directory.
+const CLI_HISTORY_FILE: &str = "chat_history";
+
+/// A slash command typed in interactive mode.
+#[derive(Debug, PartialEq, Eq)]
+enum ReplCommand {
+    Help,
+    Quit,
+    Clear,
+    Tools,
+    /// `/model` shows the active model; `/model <name>` switches to it.
+    Model(Option<String>),
+    /// `/approve` and `/approve pending` list tools that will still prompt.
+    PendingApprovals,
+    Approve(String),
+}
+
+/// Parse a slash command. Unknown `/...` input is not a command and goes to
+/// the model like any other message.
+fn parse_repl_command(input: &str) -> Option<ReplCommand> {
+    let mut parts = input.split_whitespace();
+    let command = match (parts.next()?, parts.next()) {
+        ("/help", None) => ReplCommand::Help,
+        ("/quit" | "/exit", None) => ReplCommand::Quit,
+        ("/clear" | "/new", None) | ("/session", Some("clear")) => ReplCommand::Clear,
+        ("/tools", None) => ReplCommand::Tools,
+        ("/model", arg) => ReplCommand::Model(arg.map(str::to_string)),
+        ("/approve", None | Some("pending")) => ReplCommand::PendingApprovals,
+        ("/approve", Some(tool)) => ReplCommand::Approve(tool.to_string()),
+        _ => return None,
+    };
+    parts.next().is_none().then_some(command)
+}
+
 struct SlashCommandCompleter;
 
 impl Completer for SlashCommandCompleter {
@@ -239,6 +301,47 @@ pub(crate) fn scrub_credentials(input: &str) -> String {
         .to_string()
 }
 
+/// Inbound guardrail: redact secrets embedded in tool-call arguments (e.g. a URL
+/// carrying a token, a shell command with an inline password) before the call is
+/// written to runtime traces, approval audit entries, or session history.
+/// Tools still execute with the original, unredacted arguments.
+pub(crate) fn redact_tool_arguments(args: &serde_json::Value) -> serde_json::Value {
+    match LeakDetector::new().scan_tool_arguments(args) {
+        ArgumentScan::Clean => args.clone(),
+        ArgumentScan::Detected { patterns, redacted } => {
+            tracing::warn!(
+                patterns = ?patterns,
+                "input guardrail: credential detected in tool call arguments"
+            );
+            redacted
+        }
+    }
+}
+
+/// Same as [`redact_tool_arguments`] for raw argument strings as returned by
+/// native tool-calling providers. Non-JSON payloads are scanned as plain text.
+fn redact_tool_arguments_str(raw: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(raw) {
+        Ok(args) => redact_tool_arguments(&args).to_string(),
+        Err(_) => redact_tool_call_text(raw),
+    }
+}
+
+/// Redact secrets from prompt-mode assistant text that embeds `<tool_call>`
+/// payloads before it is stored in session history.
+fn redact_tool_call_text(text: &str) -> String {
+    match LeakDetector::new().scan(text) {
+        LeakResult::Clean => text.to_string(),
+        LeakResult::Detected { patterns, redacted } => {
+            tracing::warn!(
+                patterns = ?patterns,
+                "input guardrail: credential detected in tool call payload"
+            );
+            redacted
+        }
+    }
+}
+
 /// Default trigger for auto-compaction when non-system message count exceeds this threshold.
 /// Prefer passing the config-driven value via `run_tool_call_loop`; this constant is only
 /// used when callers omit the parameter.
@@ -286,9 +389,41 @@ pub(crate) struct NonCliApprovalContext {
     pub prompt_tx: tokio::sync::mpsc::UnboundedSender<NonCliApprovalPrompt>,
 }
 
+/// Tool lifecycle notification for live front-ends (e.g. the gateway
+/// WebSocket). Arguments are redacted and output is credential-scrubbed.
+#[derive(Debug, Clone)]
+pub(crate) enum ToolLoopEvent {
+    Started {
+        id: Option<String>,
+        name: String,
+        arguments: serde_json::Value,
+    },
+    Finished {
+        id: Option<String>,
+        name: String,
+        success: bool,
+        output: String,
+        duration: Duration,
+    },
+}
+
+/// Longest tool output forwarded in a [`ToolLoopEvent::Finished`].
+const TOOL_EVENT_OUTPUT_MAX_CHARS: usize = 2000;
+
+tokio::task_local! {
+    /// Receives [`ToolLoopEvent`]s for tool calls made inside the scope.
+    pub(crate) static TOOL_EVENT_SINK: tokio::sync::mpsc::UnboundedSender<ToolLoopEvent>;
+}
+
+fn emit_tool_event(event: ToolLoopEvent) {
+    let _ = TOOL_EVENT_SINK.try_with(|sink| sink.send(event));
+}
+
 tokio::task_local! {
     static TOOL_LOOP_NON_CLI_APPROVAL_CONTEXT: Option<NonCliApprovalContext>;
     static LOOP_DETECTION_CONFIG: LoopDetectionConfig;
+    pub(crate) static CONTENT_INJECTION_CONFIG: ContentInjectionConfig;
+    pub(crate) static TOOL_ANOMALY_MONITOR: Arc<ToolAnomalyMonitor>;
 }
 
 /// Extract a short hint from tool call arguments for progress display.
@@ -467,7 +602,7 @@ fn build_native_assistant_history(
             serde_json::json!({
                 "id": tc.id,
                 "name": tc.name,
-                "arguments": tc.arguments,
+                "arguments": redact_tool_arguments_str(&tc.arguments),
             })
         })
         .collect();
@@ -504,7 +639,7 @@ fn build_native_assistant_history_from_parsed_calls(
             Some(serde_json::json!({
                 "id": tc.tool_call_id.clone()?,
                 "name": tc.name,
-                "arguments": serde_json::to_string(&tc.arguments).unwrap_or_else(|_| "{}".to_string()),
+                "arguments": serde_json::to_string(&redact_tool_arguments(&tc.arguments)).unwrap_or_else(|_| "{}".to_string()),
             }))
         })
         .collect::<Option<Vec<_>>>()?;
@@ -582,6 +717,179 @@ pub(crate) fn is_loop_detection_error(err: &anyhow::Error) -> bool {
     })
 }
 
+/// Bill one LLM call to the turn's usage scope, estimating the token counts
+/// the provider did not report.
+fn record_llm_usage(
+    scope: &UsageScope,
+    channel: &str,
+    principal: Option<&str>,
+    provider_name: &str,
+    model: &str,
+    request_messages: &[ChatMessage],
+    resp: &ChatResponse,
+) {
+    let reported_input = resp.usage.as_ref().and_then(|u| u.input_tokens);
+    let reported_output = resp.usage.as_ref().and_then(|u| u.output_tokens);
+    let event = UsageEvent {
+        session: &scope.session,
+        channel,
+        principal,
+        provider: provider_name,
+        model,
+        input_tokens: reported_input.unwrap_or_else(|| {
+            request_messages
+                .iter()
+                .map(|m| estimate_tokens(&m.content))
+                .sum()
+        }),
+        output_tokens: reported_output.unwrap_or_else(|| {
+            estimate_tokens(resp.text_or_empty())
+                + resp
+                    .tool_calls
+                    .iter()
+                    .map(|call| estimate_tokens(&call.arguments))
+                    .sum::<u64>()
+        }),
+        estimated: reported_input.is_none() || reported_output.is_none(),
+    };
+    if let Err(e) = scope.ledger.record(&event) {
+        tracing::warn!("Failed to record token usage: {e}");
+    }
+}
+
+/// Open the usage ledger for a CLI/daemon run when cost tracking is enabled.
+fn open_usage_scope(config: &Config, session: String) -> Option<UsageScope> {
+    if !config.cost.enabled {
+        return None;
+    }
+    match crate::cost::UsageLedger::open(&config.workspace_dir, &config.cost) {
+        Ok(ledger) => Some(UsageScope {
+            ledger: Arc::new(ledger),
+            session,
+        }),
+        Err(e) => {
+            tracing::warn!("Failed to open usage ledger: {e}");
+            None
+        }
+    }
+}
+
+/// Outcome of a live-streamed LLM call.
+struct StreamedResponse {
+    response: ChatResponse,
+    /// Some text was relayed to the draft.
+    relayed_any: bool,
+    /// All of the response text was relayed.
+    relayed_all: bool,
+}
+
+/// Stream one LLM call, relaying text deltas to `tx` as they arrive.
+///
+/// Returns `None` when the provider streamed no text or the stream failed;
+/// the caller then falls back to a regular call, which also retries.
+async fn stream_llm_response(
+    provider: &dyn Provider,
+    messages: &[ChatMessage],
+    model: &str,
+    temperature: f64,
+    tx: &tokio::sync::mpsc::Sender<String>,
+) -> Option<StreamedResponse> {
+    let mut stream = ResponseStream::new(provider.stream_chat_with_history(
+        messages,
+        model,
+        temperature,
+        StreamOptions::new(true),
+    ));
+    let mut text = String::new();
+    let mut relayed = 0;
+    let mut holding_back = false;
+    while let Some(delta) = stream.next_delta().await {
+        match delta {
+            Ok(delta) => text.push_str(&delta),
+            Err(e) => {
+                tracing::warn!("Streaming LLM call failed, retrying without streaming: {e}");
+                if relayed > 0 {
+                    let _ = tx.send(DRAFT_CLEAR_SENTINEL.to_string()).await;
+                }
+                return None;
+            }
+        }
+        if holding_back {
+            continue;
+        }
+        let end = match STREAM_HOLD_BACK_MARKERS
+            .iter()
+            .filter_map(|marker| text.find(marker))
+            .min()
+        {
+            Some(index) => {
+                holding_back = true;
+                index.max(relayed)
+            }
+            None => text.len(),
+        };
+        if end > relayed {
+            if relayed == 0 {
+                // Replace progress lines with the answer as it arrives.
+                let _ = tx.send(DRAFT_CLEAR_SENTINEL.to_string()).await;
+            }
+            let _ = tx.send(text[relayed..end].to_string()).await;
+            relayed = end;
+        }
+    }
+    if text.is_empty() {
+        return None;
+    }
+    Some(StreamedResponse {
+        response: ChatResponse {
+            text: Some(text),
+            tool_calls: Vec::new(),
+            usage: None,
+            reasoning_content: None,
+        },
+        relayed_any: relayed > 0,
+        relayed_all: !holding_back,
+    })
+}
+
+/// Print streamed reply text to stdout as it arrives.
+///
+/// Progress deltas are skipped. After a draft clear the answer is relayed
+/// again from the start; text already on screen is not printed twice.
+/// Resolves to the text printed for the last answer.
+fn spawn_cli_stream_printer(
+    mut rx: tokio::sync::mpsc::Receiver<String>,
+) -> tokio::task::JoinHandle<String> {
+    tokio::spawn(async move {
+        let mut printed = String::new();
+        let mut current = String::new();
+        while let Some(delta) = rx.recv().await {
+            if delta == DRAFT_CLEAR_SENTINEL {
+                current.clear();
+                continue;
+            }
+            if delta.starts_with(DRAFT_PROGRESS_SENTINEL) {
+                continue;
+            }
+            current.push_str(&delta);
+            if let Some(rest) = current.strip_prefix(printed.as_str()) {
+                if printed.is_empty() {
+                    println!();
+                }
+                print!("{rest}");
+            } else if printed.starts_with(current.as_str()) {
+                continue;
+            } else {
+                // A later answer replaced the text on screen.
+                print!("\n\n{current}");
+            }
+            printed.clone_from(&current);
+            let _ = std::io::stdout().flush();
+        }
+        printed
+    })
+}
+
 /// Execute a single turn of the agent loop: send messages, parse tool calls,
 /// execute tools, and loop until the LLM produces a final text response.
 /// When `silent` is true, suppresses stdout (for channel use).
@@ -597,6 +905,7 @@ pub(crate) async fn agent_turn(
     silent: bool,
     multimodal_config: &crate::config::MultimodalConfig,
     max_tool_iterations: usize,
+    on_delta: Option<tokio::sync::mpsc::Sender<String>>,
 ) -> Result<String> {
     run_tool_call_loop(
         provider,
@@ -612,7 +921,7 @@ pub(crate) async fn agent_turn(
         multimodal_config,
         max_tool_iterations,
         None,
-        None,
+        on_delta,
         None,
         &[],
     )
@@ -778,7 +1087,30 @@ pub(crate) async fn run_tool_call_loop(
         .map(|tool| tool.spec())
         .collect();
     let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
-    let turn_id = Uuid::new_v4().to_string();
+    // Native tool definitions must fit the provider's limits; prompt-guided
+    // tools are plain text in the system prompt and go out unchanged.
+    let shaped_tools = if use_native_tools {
+        let shaped = crate::providers::capabilities::shape_tools(
+            tool_specs.clone(),
+            &crate::providers::capabilities::lookup(provider_name, model),
+        );
+        if !shaped.dropped.is_empty() {
+            tracing::info!(
+                provider = provider_name,
+                model,
+                dropped = ?shaped.dropped,
+                "Tool list exceeds the provider's limit; dropping the last tools"
+            );
+        }
+        shaped
+    } else {
+        crate::providers::capabilities::ShapedTools::default()
+    };
+    let tool_spec_tokens = serde_json::to_string(&shaped_tools.specs).map_or(0, |json| {
+        crate::channels::session::tokens::estimate_text_tokens(model, &json)
+    });
+    let prompt_budget = context_budget(provider_name, model, tool_spec_tokens);
+    let turn_id = turn::current_turn_id().unwrap_or_else(turn::new_turn_id);
     let mut seen_tool_signatures: HashSet<(String, String)> = HashSet::new();
     let mut missing_tool_call_retry_used = false;
     let mut missing_tool_call_retry_prompt: Option<String> = None;
@@ -787,8 +1119,27 @@ pub(crate) async fn run_tool_call_loop(
         .unwrap_or_default();
     let mut loop_detector = LoopDetector::new(ld_config);
     let mut loop_detection_prompt: Option<String> = None;
-    let bypass_non_cli_approval_for_turn =
-        approval.is_some_and(|mgr| channel_name != "cli" && mgr.consume_non_cli_allow_all_once());
+    let injection_config = CONTENT_INJECTION_CONFIG
+        .try_with(Clone::clone)
+        .unwrap_or_default();
+    let mut untrusted_content_escalated = false;
+    let anomaly_monitor = TOOL_ANOMALY_MONITOR.try_with(Clone::clone).ok();
+    let usage_scope = USAGE_SCOPE.try_with(Clone::clone).ok().flatten();
+    let usage_principal = non_cli_approval_context
+        .as_ref()
+        .map(|ctx| format!("{channel_name}:{}", ctx.sender));
+    let anomaly_session = match non_cli_approval_context.as_ref() {
+        Some(ctx) => format!("{channel_name}:{}", ctx.sender),
+        None => match channel_reply_target.as_deref() {
+            Some(target) => format!("{channel_name}:{target}"),
+            None => channel_name.to_string(),
+        },
+    };
+    let plan_scope = PLAN_SCOPE.try_with(Clone::clone).ok().flatten();
+    let planning = matches!(plan_scope, Some(PlanScope::Recording(_)));
+    let bypass_non_cli_approval_for_turn = approval.is_some_and(|mgr| {
+        channel_name != "cli" && !planning && mgr.consume_non_cli_allow_all_once()
+    });
     if bypass_non_cli_approval_for_turn {
         runtime_trace::record_event(
             "approval_bypass_one_time_all_tools_consumed",
@@ -824,6 +1175,22 @@ pub(crate) async fn run_tool_call_loop(
             .into());
         }
 
+        // Compact before sending rather than losing the turn to a provider
+        // 400 once tool results have outgrown the context window.
+        if let Some(fit) = fit_context_window(history, provider, model, prompt_budget).await {
+            tracing::info!(
+                provider = provider_name,
+                model,
+                budget = prompt_budget,
+                before = fit.before,
+                after = fit.after,
+                summarized = fit.summarized,
+                dropped = fit.dropped,
+                truncated = fit.truncated,
+                "Compacted history to fit the context window"
+            );
+        }
+
         let prepared_messages =
             multimodal::prepare_messages_for_provider(history, multimodal_config).await?;
         let mut request_messages = prepared_messages.messages.clone();
@@ -844,6 +1211,10 @@ pub(crate) async fn run_tool_call_loop(
             let _ = tx.send(format!("{DRAFT_PROGRESS_SENTINEL}{phase}")).await;
         }
 
+        if let (Some(scope), Some(principal)) = (usage_scope.as_ref(), usage_principal.as_deref()) {
+            scope.ledger.check_principal_budget(principal)?;
+        }
+
         observer.record_event(&ObserverEvent::LlmRequest {
             provider: provider_name.to_string(),
             model: model.to_string(),
@@ -873,20 +1244,46 @@ pub(crate) async fn run_tool_call_loop(
         // Unified path via Provider::chat so provider-specific native tool logic
         // (OpenAI/Anthropic/OpenRouter/compatible adapters) is honored.
         let request_tools = if use_native_tools {
-            Some(tool_specs.as_slice())
+            Some(shaped_tools.specs.as_slice())
         } else {
             None
         };
 
-        let chat_future = provider.chat(
-            ChatRequest {
-                messages: &request_messages,
-                tools: request_tools,
-            },
-            model,
-            temperature,
-        );
+        // Stream the call live when a draft is listening. Native tool calls
+        // arrive as structured data, so those turns use the regular call.
+        let stream_to = on_delta
+            .as_ref()
+            .filter(|_| !use_native_tools && provider.supports_streaming());
+        let mut relayed_any = false;
+        let mut relayed_all = false;
+        let chat_future = async {
+            if let Some(tx) = stream_to {
+                if let Some(streamed) =
+                    stream_llm_response(provider, &request_messages, model, temperature, tx).await
+                {
+                    relayed_any = streamed.relayed_any;
+                    relayed_all = streamed.relayed_all;
+                    return Ok(streamed.response);
+                }
+            }
+            provider
+                .chat(
+                    ChatRequest {
+                        messages: &request_messages,
+                        tools: request_tools,
+                    },
+                    model,
+                    temperature,
+                )
+                .await
+        };
 
+        let chat_future = chat_future.instrument(tracing::info_span!(
+            "provider_request",
+            provider = provider_name,
+            model = model,
+            iteration = iteration + 1,
+        ));
         let chat_result = if let Some(token) = cancellation_token.as_ref() {
             tokio::select! {
                 () = token.cancelled() => return Err(ToolLoopCancelled.into()),
@@ -899,12 +1296,23 @@ pub(crate) async fn run_tool_call_loop(
         let (
             response_text,
             parsed_text,
-            tool_calls,
+            mut tool_calls,
             assistant_history_content,
             native_tool_calls,
             parse_issue_detected,
         ) = match chat_result {
             Ok(resp) => {
+                if let Some(scope) = usage_scope.as_ref() {
+                    record_llm_usage(
+                        scope,
+                        channel_name,
+                        usage_principal.as_deref(),
+                        provider_name,
+                        model,
+                        &request_messages,
+                        &resp,
+                    );
+                }
                 let (resp_input_tokens, resp_output_tokens) = resp
                     .usage
                     .as_ref()
@@ -920,6 +1328,11 @@ pub(crate) async fn run_tool_call_loop(
                     input_tokens: resp_input_tokens,
                     output_tokens: resp_output_tokens,
                 });
+                turn::record(|report| {
+                    report.llm_calls += 1;
+                    report.input_tokens += resp_input_tokens.unwrap_or_default();
+                    report.output_tokens += resp_output_tokens.unwrap_or_default();
+                });
 
                 let response_text = resp.text_or_empty().to_string();
                 // First try native structured tool calls (OpenAI-format).
@@ -986,9 +1399,11 @@ pub(crate) async fn run_tool_call_loop(
                             &calls,
                             reasoning_content.as_deref(),
                         )
-                        .unwrap_or_else(|| response_text.clone())
-                    } else {
+                        .unwrap_or_else(|| redact_tool_call_text(&response_text))
+                    } else if calls.is_empty() {
                         response_text.clone()
+                    } else {
+                        redact_tool_call_text(&response_text)
                     }
                 } else {
                     build_native_assistant_history(
@@ -1019,6 +1434,7 @@ pub(crate) async fn run_tool_call_loop(
                     input_tokens: None,
                     output_tokens: None,
                 });
+                turn::record(|report| report.llm_calls += 1);
                 runtime_trace::record_event(
                     "llm_response",
                     Some(channel_name),
@@ -1036,6 +1452,11 @@ pub(crate) async fn run_tool_call_loop(
             }
         };
 
+        // Calls to tools renamed for the provider go to the registered tool.
+        for call in &mut tool_calls {
+            call.name = shaped_tools.original_name(&call.name).to_string();
+        }
+
         let display_text = if parsed_text.is_empty() {
             response_text.clone()
         } else {
@@ -1110,9 +1531,11 @@ pub(crate) async fn run_tool_call_loop(
                 }),
             );
             // No tool calls — this is the final response.
-            // If a streaming sender is provided, relay the text in small chunks
-            // so the channel can progressively update the draft message.
-            if let Some(ref tx) = on_delta {
+            // If a streaming sender is provided and the answer was not already
+            // streamed live, relay the text in small chunks so the channel can
+            // progressively update the draft message.
+            let streamed_in_full = relayed_all && display_text == response_text;
+            if let Some(tx) = on_delta.as_ref().filter(|_| !streamed_in_full) {
                 // Clear accumulated progress lines before streaming the final answer.
                 let _ = tx.send(DRAFT_CLEAR_SENTINEL.to_string()).await;
                 // Split on whitespace boundaries, accumulating chunks of at least
@@ -1137,11 +1560,13 @@ pub(crate) async fn run_tool_call_loop(
                 }
             }
             history.push(ChatMessage::assistant(response_text.clone()));
+            turn::record(|report| report.set_response(&display_text));
             return Ok(display_text);
         }
 
-        // Print any text the LLM produced alongside tool calls (unless silent)
-        if !silent && !display_text.is_empty() {
+        // Print any text the LLM produced alongside tool calls (unless silent
+        // or already streamed)
+        if !silent && !relayed_any && !display_text.is_empty() {
             print!("{display_text}");
             let _ = std::io::stdout().flush();
         }
@@ -1155,7 +1580,8 @@ pub(crate) async fn run_tool_call_loop(
         let mut individual_results: Vec<(Option<String>, String)> = Vec::new();
         let mut ordered_results: Vec<Option<(String, Option<String>, ToolExecutionOutcome)>> =
             (0..tool_calls.len()).map(|_| None).collect();
-        let allow_parallel_execution = should_execute_tools_in_parallel(&tool_calls, approval);
+        let allow_parallel_execution =
+            should_execute_tools_in_parallel(&tool_calls, approval, channel_name);
         let mut executable_indices: Vec<usize> = Vec::new();
         let mut executable_calls: Vec<ParsedToolCall> = Vec::new();
 
@@ -1182,7 +1608,7 @@ pub(crate) async fn run_tool_call_loop(
                             serde_json::json!({
                                 "iteration": iteration + 1,
                                 "tool": call.name,
-                                "arguments": scrub_credentials(&tool_args.to_string()),
+                                "arguments": scrub_credentials(&redact_tool_arguments(&tool_args).to_string()),
                             }),
                         );
                         ordered_results[idx] = Some((
@@ -1224,7 +1650,7 @@ pub(crate) async fn run_tool_call_loop(
                     serde_json::json!({
                         "iteration": iteration + 1,
                         "tool": tool_name.clone(),
-                        "arguments": scrub_credentials(&tool_args.to_string()),
+                        "arguments": scrub_credentials(&redact_tool_arguments(&tool_args).to_string()),
                         "blocked_by_channel_policy": true,
                     }),
                 );
@@ -1241,55 +1667,205 @@ pub(crate) async fn run_tool_call_loop(
                 continue;
             }
 
+            // ── Planning mode: record instead of running ─────
+            if let Some(PlanScope::Recording(recorder)) = plan_scope.as_ref() {
+                let arguments = redact_tool_arguments(&tool_args);
+                let step = recorder.record(&tool_name, arguments.clone());
+                runtime_trace::record_event(
+                    "tool_call_planned",
+                    Some(channel_name),
+                    Some(provider_name),
+                    Some(model),
+                    Some(&turn_id),
+                    Some(true),
+                    None,
+                    serde_json::json!({
+                        "iteration": iteration + 1,
+                        "tool": tool_name.clone(),
+                        "step": step,
+                        "arguments": scrub_credentials(&arguments.to_string()),
+                    }),
+                );
+                ordered_results[idx] = Some((
+                    tool_name.clone(),
+                    call.tool_call_id.clone(),
+                    ToolExecutionOutcome {
+                        output: planned_call_output(step),
+                        success: true,
+                        error_reason: None,
+                        duration: Duration::ZERO,
+                    },
+                ));
+                continue;
+            }
+
+            // ── Behavioral anomaly monitor ───────────────────
+            let anomaly = anomaly_monitor
+                .as_ref()
+                .and_then(|monitor| monitor.observe(&anomaly_session, &tool_name, &tool_args));
+            if let Some(alert) = anomaly.as_ref() {
+                tracing::warn!(
+                    tool = %tool_name,
+                    session = %anomaly_session,
+                    kind = ?alert.kind,
+                    paused = alert.paused,
+                    "tool anomaly: {}",
+                    alert.message
+                );
+                runtime_trace::record_event(
+                    "tool_anomaly_detected",
+                    Some(channel_name),
+                    Some(provider_name),
+                    Some(model),
+                    Some(&turn_id),
+                    Some(!alert.paused),
+                    Some(&alert.message),
+                    serde_json::json!({
+                        "iteration": iteration + 1,
+                        "tool": tool_name.clone(),
+                        "kind": alert.kind,
+                        "paused": alert.paused,
+                    }),
+                );
+            }
+            let anomaly_paused = anomaly.as_ref().is_some_and(|alert| alert.paused);
+            let forced_approval_reason = if untrusted_content_escalated {
+                Some(
+                    "untrusted content with injection markers was read earlier in this turn"
+                        .to_string(),
+                )
+            } else if let Some(alert) = anomaly.as_ref().filter(|alert| alert.paused) {
+                Some(format!("tool-call anomaly detected ({})", alert.message))
+            } else {
+                None
+            };
+
             // ── Approval hook ────────────────────────────────
+            let approved_by_plan = matches!(
+                plan_scope.as_ref(),
+                Some(PlanScope::Approved(tools)) if tools.contains(&tool_name)
+            );
             if let Some(mgr) = approval {
-                if bypass_non_cli_approval_for_turn {
+                if (bypass_non_cli_approval_for_turn || approved_by_plan)
+                    && forced_approval_reason.is_none()
+                {
                     mgr.record_decision(
                         &tool_name,
                         &tool_args,
                         ApprovalResponse::Yes,
                         channel_name,
                     );
-                } else if mgr.needs_approval(&tool_name) {
+                } else if forced_approval_reason.is_some()
+                    || mgr.needs_approval(&tool_name, channel_name)
+                {
                     let request = ApprovalRequest {
                         tool_name: tool_name.clone(),
                         arguments: tool_args.clone(),
                     };
 
-                    let decision = if channel_name == "cli" {
-                        mgr.prompt_cli(&request)
-                    } else if let Some(ctx) = non_cli_approval_context.as_ref() {
-                        let pending = mgr.create_non_cli_pending_request(
-                            &tool_name,
-                            &ctx.sender,
-                            channel_name,
-                            &ctx.reply_target,
-                            Some(
-                                "interactive approval required for supervised non-cli tool execution"
-                                    .to_string(),
-                            ),
-                        );
+                    let decision = async {
+                        if channel_name == "cli" {
+                            if let Some(reason) = forced_approval_reason.as_deref() {
+                                eprintln!("\n⚠ Approval required: {reason}.");
+                            }
+                            mgr.prompt_cli(&request)
+                        } else if let Some(ctx) = non_cli_approval_context.as_ref() {
+                            let pending = mgr.create_non_cli_pending_request(
+                                &tool_name,
+                                &ctx.sender,
+                                channel_name,
+                                &ctx.reply_target,
+                                Some(match forced_approval_reason.as_deref() {
+                                    Some(reason) => format!("interactive approval required: {reason}"),
+                                    None => {
+                                        "interactive approval required for supervised non-cli tool execution"
+                                            .to_string()
+                                    }
+                                }),
+                            );
+
+                            let _ = ctx.prompt_tx.send(NonCliApprovalPrompt {
+                                request_id: pending.request_id.clone(),
+                                tool_name: tool_name.clone(),
+                                arguments: tool_args.clone(),
+                            });
+
+                            // An attached operator may answer before the channel does.
+                            let operator = attach::ask_operators(attach::AttachPrompt::new(
+                                &pending.request_id,
+                                &tool_name,
+                                &tool_args,
+                                channel_name,
+                            ));
+                            tokio::select! {
+                                decision = await_non_cli_approval_decision(
+                                    mgr,
+                                    &pending.request_id,
+                                    &ctx.sender,
+                                    channel_name,
+                                    &ctx.reply_target,
+                                    cancellation_token.as_ref(),
+                                ) => decision,
+                                Some(decision) = operator => {
+                                    let _ = mgr.reject_non_cli_pending_request(
+                                        &pending.request_id,
+                                        &ctx.sender,
+                                        channel_name,
+                                        &ctx.reply_target,
+                                    );
+                                    let _ =
+                                        mgr.take_non_cli_pending_resolution(&pending.request_id);
+                                    decision
+                                }
+                            }
+                        } else {
+                            let request_id =
+                                format!("apr-{}", &Uuid::new_v4().simple().to_string()[..8]);
+                            attach::ask_operators(attach::AttachPrompt::new(
+                                &request_id,
+                                &tool_name,
+                                &tool_args,
+                                channel_name,
+                            ))
+                            .await
+                            .unwrap_or(ApprovalResponse::No)
+                        }
+                    }
+                    .instrument(tracing::info_span!("approval", tool = %tool_name))
+                    .await;
 
-                        let _ = ctx.prompt_tx.send(NonCliApprovalPrompt {
-                            request_id: pending.request_id.clone(),
-                            tool_name: tool_name.clone(),
-                            arguments: tool_args.clone(),
+                    mgr.record_decision(&tool_name, &tool_args, decision, channel_name);
+                    let decision_label = match decision {
+                        ApprovalResponse::Yes => "yes",
+                        ApprovalResponse::No => "no",
+                        ApprovalResponse::Always => "always",
+                    };
+                    observer.record_event(&ObserverEvent::ApprovalDecision {
+                        tool: tool_name.clone(),
+                        channel: channel_name.to_string(),
+                        decision: decision_label.to_string(),
+                    });
+                    turn::record(|report| {
+                        report.approvals.push(turn_report::ApprovalRecord {
+                            tool: tool_name.clone(),
+                            decision: decision_label.to_string(),
                         });
-
-                        await_non_cli_approval_decision(
-                            mgr,
-                            &pending.request_id,
-                            &ctx.sender,
+                    });
+                    if let Some(scope) = usage_scope.as_ref() {
+                        if let Err(e) = scope.ledger.record_activity(
                             channel_name,
-                            &ctx.reply_target,
-                            cancellation_token.as_ref(),
-                        )
-                        .await
-                    } else {
-                        ApprovalResponse::No
-                    };
+                            ActivityKind::Approval,
+                            decision_label,
+                        ) {
+                            tracing::warn!("Failed to record approval activity: {e}");
+                        }
+                    }
 
-                    mgr.record_decision(&tool_name, &tool_args, decision, channel_name);
+                    if anomaly_paused && decision != ApprovalResponse::No {
+                        if let Some(monitor) = anomaly_monitor.as_ref() {
+                            monitor.resume(&anomaly_session);
+                        }
+                    }
 
                     if decision == ApprovalResponse::No {
                         let denied = "Denied by user.".to_string();
@@ -1304,7 +1880,7 @@ pub(crate) async fn run_tool_call_loop(
                             serde_json::json!({
                                 "iteration": iteration + 1,
                                 "tool": tool_name.clone(),
-                                "arguments": scrub_credentials(&tool_args.to_string()),
+                                "arguments": scrub_credentials(&redact_tool_arguments(&tool_args).to_string()),
                             }),
                         );
                         ordered_results[idx] = Some((
@@ -1320,6 +1896,35 @@ pub(crate) async fn run_tool_call_loop(
                         continue;
                     }
                 }
+            } else if let Some(reason) = forced_approval_reason.as_deref() {
+                let blocked = format!(
+                    "Blocked tool call '{tool_name}': {reason} and no approval channel is available."
+                );
+                runtime_trace::record_event(
+                    "tool_call_result",
+                    Some(channel_name),
+                    Some(provider_name),
+                    Some(model),
+                    Some(&turn_id),
+                    Some(false),
+                    Some(&blocked),
+                    serde_json::json!({
+                        "iteration": iteration + 1,
+                        "tool": tool_name.clone(),
+                        "arguments": scrub_credentials(&redact_tool_arguments(&tool_args).to_string()),
+                    }),
+                );
+                ordered_results[idx] = Some((
+                    tool_name.clone(),
+                    call.tool_call_id.clone(),
+                    ToolExecutionOutcome {
+                        output: blocked.clone(),
+                        success: false,
+                        error_reason: Some(blocked),
+                        duration: Duration::ZERO,
+                    },
+                ));
+                continue;
             }
 
             let signature = tool_call_signature(&tool_name, &tool_args);
@@ -1338,7 +1943,7 @@ pub(crate) async fn run_tool_call_loop(
                     serde_json::json!({
                         "iteration": iteration + 1,
                         "tool": tool_name.clone(),
-                        "arguments": scrub_credentials(&tool_args.to_string()),
+                        "arguments": scrub_credentials(&redact_tool_arguments(&tool_args).to_string()),
                         "deduplicated": true,
                     }),
                 );
@@ -1366,9 +1971,14 @@ pub(crate) async fn run_tool_call_loop(
                 serde_json::json!({
                     "iteration": iteration + 1,
                     "tool": tool_name.clone(),
-                    "arguments": scrub_credentials(&tool_args.to_string()),
+                    "arguments": scrub_credentials(&redact_tool_arguments(&tool_args).to_string()),
                 }),
             );
+            emit_tool_event(ToolLoopEvent::Started {
+                id: call.tool_call_id.clone(),
+                name: tool_name.clone(),
+                arguments: redact_tool_arguments(&tool_args),
+            });
 
             // ── Progress: tool start ────────────────────────────
             if let Some(ref tx) = on_delta {
@@ -1410,11 +2020,71 @@ pub(crate) async fn run_tool_call_loop(
             .await?
         };
 
-        for ((idx, call), outcome) in executable_indices
+        turn::record(|report| {
+            for (call, outcome) in executable_calls.iter().zip(&executed_outcomes) {
+                report.tools.push(turn_report::ToolInvocation {
+                    tool: call.name.clone(),
+                    duration_ms: u64::try_from(outcome.duration.as_millis()).unwrap_or(u64::MAX),
+                    success: outcome.success,
+                });
+            }
+        });
+        if let Some(scope) = usage_scope.as_ref() {
+            for (call, outcome) in executable_calls.iter().zip(&executed_outcomes) {
+                let failed = (!outcome.success).then_some(ActivityKind::ToolFailure);
+                for kind in std::iter::once(ActivityKind::Tool).chain(failed) {
+                    if let Err(e) = scope.ledger.record_activity(channel_name, kind, &call.name) {
+                        tracing::warn!("Failed to record tool activity: {e}");
+                    }
+                }
+            }
+        }
+        if let (Some(scope), Some(principal)) = (usage_scope.as_ref(), usage_principal.as_deref()) {
+            let count = u64::try_from(executable_calls.len()).unwrap_or(u64::MAX);
+            if let Err(e) = scope
+                .ledger
+                .record_tool_calls(channel_name, principal, count)
+            {
+                tracing::warn!("Failed to record tool call usage: {e}");
+            }
+        }
+
+        for ((idx, call), mut outcome) in executable_indices
             .iter()
             .zip(executable_calls.iter())
             .zip(executed_outcomes.into_iter())
         {
+            // ── Untrusted content: prompt-injection guard ────────
+            if outcome.success {
+                if let Some(guarded) =
+                    guard_tool_output(&injection_config, &call.name, &outcome.output)
+                {
+                    tracing::warn!(
+                        tool = %call.name,
+                        categories = ?guarded.categories,
+                        action = ?injection_config.action,
+                        "content guard: instruction-like payload detected in tool output"
+                    );
+                    runtime_trace::record_event(
+                        "untrusted_content_flagged",
+                        Some(channel_name),
+                        Some(provider_name),
+                        Some(model),
+                        Some(&turn_id),
+                        Some(true),
+                        None,
+                        serde_json::json!({
+                            "iteration": iteration + 1,
+                            "tool": call.name.clone(),
+                            "categories": guarded.categories,
+                            "escalate": guarded.escalate,
+                        }),
+                    );
+                    untrusted_content_escalated |= guarded.escalate;
+                    outcome.output = guarded.content;
+                }
+            }
+
             runtime_trace::record_event(
                 "tool_call_result",
                 Some(channel_name),
@@ -1430,6 +2100,16 @@ pub(crate) async fn run_tool_call_loop(
                     "output": scrub_credentials(&outcome.output),
                 }),
             );
+            emit_tool_event(ToolLoopEvent::Finished {
+                id: call.tool_call_id.clone(),
+                name: call.name.clone(),
+                success: outcome.success,
+                output: truncate_with_ellipsis(
+                    &scrub_credentials(&outcome.output),
+                    TOOL_EVENT_OUTPUT_MAX_CHARS,
+                ),
+                duration: outcome.duration,
+            });
 
             // ── Hook: after_tool_call (void) ─────────────────
             if let Some(hooks) = hooks {
@@ -1780,6 +2460,7 @@ pub async fn run(
         custom_provider_api_mode: config.provider_api.map(|mode| mode.as_compatible_mode()),
         max_tokens_override: None,
         model_support_vision: config.model_support_vision,
+        model_support_tools: config.model_support_tools,
     };
 
     let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
@@ -1792,6 +2473,17 @@ pub async fn run(
         &provider_runtime_options,
     )?;
 
+    // History compaction runs on the `summarize` route when one is configured.
+    let summary_model = if config
+        .model_routes
+        .iter()
+        .any(|route| route.hint == providers::router::SUMMARIZE_ROUTE_HINT)
+    {
+        format!("hint:{}", providers::router::SUMMARIZE_ROUTE_HINT)
+    } else {
+        model_name.to_string()
+    };
+
     observer.record_event(&ObserverEvent::AgentStart {
         provider: provider_name.to_string(),
         model: model_name.to_string(),
@@ -1806,6 +2498,7 @@ pub async fn run(
         .map(|dir| crate::rag::HardwareRag::load(&config.workspace_dir, dir.trim()))
         .and_then(Result::ok)
         .filter(|r: &crate::rag::HardwareRag| !r.is_empty());
+    let knowledge_base = crate::kb::KnowledgeBase::if_enabled(&config);
     if let Some(ref rag) = hardware_rag {
         tracing::info!(chunks = rag.len(), "Hardware RAG loaded");
     }
@@ -1969,6 +2662,10 @@ pub async fn run(
         None
     };
     let channel_name = if interactive { "cli" } else { "daemon" };
+    let tool_anomaly = Arc::new(ToolAnomalyMonitor::new(
+        config.security.tool_anomaly.clone(),
+    ));
+    let usage_scope = open_usage_scope(&config, format!("{channel_name}:{}", Uuid::new_v4()));
 
     // ── Execute ──────────────────────────────────────────────────
     let start = Instant::now();
@@ -1984,7 +2681,7 @@ pub async fn run(
                 .await;
         }
 
-        // Inject memory + hardware RAG context into user message
+        // Inject memory, knowledge base and hardware RAG context into user message
         let mem_context =
             build_context(mem.as_ref(), &msg, config.memory.min_relevance_score).await;
         let rag_limit = if config.agent.compact_context { 2 } else { 5 };
@@ -1992,7 +2689,11 @@ pub async fn run(
             .as_ref()
             .map(|r| build_hardware_context(r, &msg, &board_names, rag_limit))
             .unwrap_or_default();
-        let context = format!("{mem_context}{hw_context}");
+        let kb_context = match &knowledge_base {
+            Some(kb) => kb.context_for(&msg).await,
+            None => String::new(),
+        };
+        let context = format!("{mem_context}{kb_context}{hw_context}");
         let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
         let enriched = if context.is_empty() {
             format!("[{now}] {msg}")
@@ -2010,29 +2711,41 @@ pub async fn run(
             ping_pong_cycles: config.agent.loop_detection_ping_pong_cycles,
             failure_streak_threshold: config.agent.loop_detection_failure_streak,
         };
-        let response = LOOP_DETECTION_CONFIG
-            .scope(
-                ld_cfg,
-                run_tool_call_loop(
-                    provider.as_ref(),
-                    &mut history,
-                    &tools_registry,
-                    observer.as_ref(),
-                    provider_name,
-                    model_name,
-                    temperature,
-                    false,
-                    approval_manager.as_ref(),
-                    channel_name,
-                    &config.multimodal,
-                    config.agent.max_tool_iterations,
-                    None,
-                    None,
-                    None,
-                    &[],
+        let response = turn::in_turn(
+            channel_name,
+            None,
+            USAGE_SCOPE.scope(
+                usage_scope.clone(),
+                TOOL_ANOMALY_MONITOR.scope(
+                    tool_anomaly.clone(),
+                    CONTENT_INJECTION_CONFIG.scope(
+                        config.security.content_injection.clone(),
+                        LOOP_DETECTION_CONFIG.scope(
+                            ld_cfg,
+                            run_tool_call_loop(
+                                provider.as_ref(),
+                                &mut history,
+                                &tools_registry,
+                                observer.as_ref(),
+                                provider_name,
+                                model_name,
+                                temperature,
+                                false,
+                                approval_manager.as_ref(),
+                                channel_name,
+                                &config.multimodal,
+                                config.agent.max_tool_iterations,
+                                None,
+                                None,
+                                None,
+                                &[],
+                            ),
+                        ),
+                    ),
                 ),
-            )
-            .await?;
+            ),
+        )
+        .await?;
         final_output = response.clone();
         println!("{response}");
         observer.record_event(&ObserverEvent::TurnComplete);
@@ -2050,6 +2763,15 @@ pub async fn run(
                 .build(),
         )?;
         rl.set_helper(Some(SlashCommandCompleter));
+        let history_path = config
+            .config_path
+            .parent()
+            .map(|dir| dir.join(CLI_HISTORY_FILE));
+        if let Some(path) = &history_path {
+            // A missing file just means no history yet.
+            let _ = rl.load_history(path);
+        }
+        let mut active_model = model_name.to_string();
 
         loop {
             let input = match rl.readline("> ") {
@@ -2068,16 +2790,68 @@ pub async fn run(
                 continue;
             }
             rl.add_history_entry(&input)?;
-            match user_input.as_str() {
-                "/quit" | "/exit" => break,
-                "/help" => {
+            if let Some(path) = &history_path {
+                if let Err(e) = rl.save_history(path) {
+                    tracing::debug!("Failed to save CLI history: {e}");
+                }
+            }
+            match parse_repl_command(&user_input) {
+                Some(ReplCommand::Quit) => break,
+                Some(ReplCommand::Help) => {
                     println!("Available commands:");
-                    println!("  /help        Show this help message");
-                    println!("  /clear /new  Clear conversation history");
-                    println!("  /quit /exit  Exit interactive mode\n");
+                    for (_, label, description) in SLASH_COMMANDS {
+                        println!("  {label:<17}{description}");
+                    }
+                    println!();
+                    continue;
+                }
+                Some(ReplCommand::Tools) => {
+                    println!("{} tools available:", tools_registry.len());
+                    for tool in &tools_registry {
+                        println!("  {:<20}{}", tool.name(), tool.description());
+                    }
+                    println!();
+                    continue;
+                }
+                Some(ReplCommand::Model(None)) => {
+                    println!("Model: {active_model} (provider {provider_name})\n");
                     continue;
                 }
-                "/clear" | "/new" => {
+                Some(ReplCommand::Model(Some(model))) => {
+                    println!("Model switched to {model} for this session.\n");
+                    active_model = model;
+                    continue;
+                }
+                Some(ReplCommand::PendingApprovals) => {
+                    let Some(approvals) = approval_manager.as_ref() else {
+                        continue;
+                    };
+                    let pending: Vec<&str> = tools_registry
+                        .iter()
+                        .map(|tool| tool.name())
+                        .filter(|name| approvals.needs_approval(name, "cli"))
+                        .collect();
+                    if pending.is_empty() {
+                        println!("No tools need approval in this session.\n");
+                    } else {
+                        println!("Tools that will ask before running:");
+                        for name in pending {
+                            println!("  {name}");
+                        }
+                        println!("Use /approve <tool> to approve one for this session.\n");
+                    }
+                    continue;
+                }
+                Some(ReplCommand::Approve(tool)) => {
+                    if !tools_registry.iter().any(|t| t.name() == tool) {
+                        println!("Unknown tool: {tool}. Use /tools to list tools.\n");
+                    } else if let Some(approvals) = approval_manager.as_ref() {
+                        approvals.grant_session(&tool);
+                        println!("{tool} approved for this session.\n");
+                    }
+                    continue;
+                }
+                Some(ReplCommand::Clear) => {
                     println!(
                         "This will clear the current conversation and delete all session memory."
                     );
@@ -2091,6 +2865,9 @@ pub async fn run(
 
                     // Ensure prior prompts are not navigable after reset.
                     rl.clear_history()?;
+                    if let Some(path) = &history_path {
+                        let _ = rl.save_history(path);
+                    }
                     history.clear();
                     history.push(ChatMessage::system(&system_prompt));
                     // Clear conversation and daily memory
@@ -2110,7 +2887,7 @@ pub async fn run(
                     }
                     continue;
                 }
-                _ => {}
+                None => {}
             }
 
             // Auto-save conversation turns (skip short/trivial messages)
@@ -2121,7 +2898,7 @@ pub async fn run(
                     .await;
             }
 
-            // Inject memory + hardware RAG context into user message
+            // Inject memory, knowledge base and hardware RAG context into user message
             let mem_context =
                 build_context(mem.as_ref(), &user_input, config.memory.min_relevance_score).await;
             let rag_limit = if config.agent.compact_context { 2 } else { 5 };
@@ -2129,7 +2906,11 @@ pub async fn run(
                 .as_ref()
                 .map(|r| build_hardware_context(r, &user_input, &board_names, rag_limit))
                 .unwrap_or_default();
-            let context = format!("{mem_context}{hw_context}");
+            let kb_context = match &knowledge_base {
+                Some(kb) => kb.context_for(&user_input).await,
+                None => String::new(),
+            };
+            let context = format!("{mem_context}{kb_context}{hw_context}");
             let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
             let enriched = if context.is_empty() {
                 format!("[{now}] {user_input}")
@@ -2144,29 +2925,47 @@ pub async fn run(
                 ping_pong_cycles: config.agent.loop_detection_ping_pong_cycles,
                 failure_streak_threshold: config.agent.loop_detection_failure_streak,
             };
-            let response = match LOOP_DETECTION_CONFIG
-                .scope(
-                    ld_cfg,
-                    run_tool_call_loop(
-                        provider.as_ref(),
-                        &mut history,
-                        &tools_registry,
-                        observer.as_ref(),
-                        provider_name,
-                        model_name,
-                        temperature,
-                        false,
-                        approval_manager.as_ref(),
-                        channel_name,
-                        &config.multimodal,
-                        config.agent.max_tool_iterations,
-                        None,
-                        None,
-                        None,
-                        &[],
+            let (stream_tx, stream_printer) = if provider.supports_streaming() {
+                let (tx, rx) = tokio::sync::mpsc::channel::<String>(64);
+                (Some(tx), Some(spawn_cli_stream_printer(rx)))
+            } else {
+                (None, None)
+            };
+            let response = match turn::in_turn(
+                channel_name,
+                None,
+                USAGE_SCOPE.scope(
+                    usage_scope.clone(),
+                    TOOL_ANOMALY_MONITOR.scope(
+                        tool_anomaly.clone(),
+                        CONTENT_INJECTION_CONFIG.scope(
+                            config.security.content_injection.clone(),
+                            LOOP_DETECTION_CONFIG.scope(
+                                ld_cfg,
+                                run_tool_call_loop(
+                                    provider.as_ref(),
+                                    &mut history,
+                                    &tools_registry,
+                                    observer.as_ref(),
+                                    provider_name,
+                                    &active_model,
+                                    temperature,
+                                    false,
+                                    approval_manager.as_ref(),
+                                    channel_name,
+                                    &config.multimodal,
+                                    config.agent.max_tool_iterations,
+                                    None,
+                                    stream_tx,
+                                    None,
+                                    &[],
+                                ),
+                            ),
+                        ),
                     ),
-                )
-                .await
+                ),
+            )
+            .await
             {
                 Ok(resp) => resp,
                 Err(e) => {
@@ -2194,7 +2993,13 @@ pub async fn run(
                 }
             };
             final_output = response.clone();
-            if let Err(e) = crate::channels::Channel::send(
+            let streamed = match stream_printer {
+                Some(printer) => printer.await.unwrap_or_default(),
+                None => String::new(),
+            };
+            if !streamed.is_empty() && streamed.trim() == response.trim() {
+                println!("\n");
+            } else if let Err(e) = crate::channels::Channel::send(
                 &cli,
                 &crate::channels::traits::SendMessage::new(format!("\n{response}\n"), "user"),
             )
@@ -2208,7 +3013,7 @@ pub async fn run(
             if let Ok(compacted) = auto_compact_history(
                 &mut history,
                 provider.as_ref(),
-                model_name,
+                &summary_model,
                 config.agent.max_history_messages,
             )
             .await
@@ -2238,8 +3043,14 @@ pub async fn run(
 /// Process a single message through the full agent (with tools, peripherals, memory).
 /// Used by channels (Telegram, Discord, etc.) to enable hardware and tool use.
 pub async fn process_message(config: Config, message: &str) -> Result<String> {
-    let observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
+    process_message_with_options(config, message, ProcessMessageOptions::default()).await
+}
+
+/// Memory and the full tool registry (peripherals included) for a
+/// non-interactive turn or a direct `zeroclaw tool exec` call.
+pub(crate) async fn build_tool_registry(
+    config: &Config,
+) -> Result<(Vec<Box<dyn Tool>>, Arc<dyn Memory>)> {
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -2274,11 +3085,40 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         &config.workspace_dir,
         &config.agents,
         config.api_key.as_deref(),
-        &config,
+        config,
     );
     let peripheral_tools: Vec<Box<dyn Tool>> =
         crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
     tools_registry.extend(peripheral_tools);
+    Ok((tools_registry, mem))
+}
+
+/// Extra inputs for [`process_message_with_options`].
+#[derive(Default)]
+pub struct ProcessMessageOptions {
+    /// Earlier user/assistant turns replayed between the system prompt and
+    /// the new message.
+    pub history: Vec<ChatMessage>,
+    /// Usage-ledger session; defaults to a fresh `channel:<uuid>`.
+    pub session: Option<String>,
+    /// Receives reply text deltas (and draft sentinels) as the turn runs.
+    pub on_delta: Option<tokio::sync::mpsc::Sender<String>>,
+    /// Restrict the turn to these tool names; `None` keeps the full registry.
+    pub allowed_tools: Option<Vec<String>>,
+}
+
+/// [`process_message`] with prior conversation history and live streaming.
+pub async fn process_message_with_options(
+    config: Config,
+    message: &str,
+    options: ProcessMessageOptions,
+) -> Result<String> {
+    let observer: Arc<dyn Observer> =
+        Arc::from(observability::create_observer(&config.observability));
+    let (mut tools_registry, mem) = build_tool_registry(&config).await?;
+    if let Some(allowed) = &options.allowed_tools {
+        tools_registry.retain(|tool| allowed.iter().any(|name| name == tool.name()));
+    }
 
     let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
     let model_name = config
@@ -2296,6 +3136,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         custom_provider_api_mode: config.provider_api.map(|mode| mode.as_compatible_mode()),
         max_tokens_override: None,
         model_support_vision: config.model_support_vision,
+        model_support_tools: config.model_support_tools,
     };
     let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
         provider_name,
@@ -2315,6 +3156,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         .map(|dir| crate::rag::HardwareRag::load(&config.workspace_dir, dir.trim()))
         .and_then(Result::ok)
         .filter(|r: &crate::rag::HardwareRag| !r.is_empty());
+    let knowledge_base = crate::kb::KnowledgeBase::if_enabled(&config);
     let board_names: Vec<String> = config
         .peripherals
         .boards
@@ -2379,6 +3221,9 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
             "Query connected hardware for reported GPIO pins and LED pin. Use when user asks what pins are available.",
         ));
     }
+    if let Some(allowed) = &options.allowed_tools {
+        tool_descs.retain(|(name, _)| allowed.iter().any(|allowed| allowed == name));
+    }
     let bootstrap_max_chars = if config.agent.compact_context {
         Some(6000)
     } else {
@@ -2406,7 +3251,11 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         .as_ref()
         .map(|r| build_hardware_context(r, message, &board_names, rag_limit))
         .unwrap_or_default();
-    let context = format!("{mem_context}{hw_context}");
+    let kb_context = match &knowledge_base {
+        Some(kb) => kb.context_for(message).await,
+        None => String::new(),
+    };
+    let context = format!("{mem_context}{kb_context}{hw_context}");
     let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
     let enriched = if context.is_empty() {
         format!("[{now}] {message}")
@@ -2414,22 +3263,43 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         format!("{context}[{now}] {message}")
     };
 
-    let mut history = vec![
-        ChatMessage::system(&system_prompt),
-        ChatMessage::user(&enriched),
-    ];
+    let mut history = Vec::with_capacity(options.history.len() + 2);
+    history.push(ChatMessage::system(&system_prompt));
+    history.extend(options.history);
+    history.push(ChatMessage::user(&enriched));
 
-    agent_turn(
-        provider.as_ref(),
-        &mut history,
-        &tools_registry,
-        observer.as_ref(),
-        provider_name,
-        &model_name,
-        config.default_temperature,
-        true,
-        &config.multimodal,
-        config.agent.max_tool_iterations,
+    let tool_anomaly = Arc::new(ToolAnomalyMonitor::new(
+        config.security.tool_anomaly.clone(),
+    ));
+    let session = options
+        .session
+        .unwrap_or_else(|| format!("channel:{}", Uuid::new_v4()));
+    let usage_scope = open_usage_scope(&config, session);
+    turn::in_turn(
+        "channel",
+        None,
+        USAGE_SCOPE.scope(
+            usage_scope,
+            TOOL_ANOMALY_MONITOR.scope(
+                tool_anomaly,
+                CONTENT_INJECTION_CONFIG.scope(
+                    config.security.content_injection.clone(),
+                    agent_turn(
+                        provider.as_ref(),
+                        &mut history,
+                        &tools_registry,
+                        observer.as_ref(),
+                        provider_name,
+                        &model_name,
+                        config.default_temperature,
+                        true,
+                        &config.multimodal,
+                        config.agent.max_tool_iterations,
+                        options.on_delta,
+                    ),
+                ),
+            ),
+        ),
     )
     .await
 }
@@ -2444,6 +3314,36 @@ mod tests {
     use std::sync::{Arc, Mutex};
     use std::time::Duration;
 
+    #[test]
+    fn parse_repl_command_recognizes_slash_commands() {
+        assert_eq!(parse_repl_command("/help"), Some(ReplCommand::Help));
+        assert_eq!(parse_repl_command("/exit"), Some(ReplCommand::Quit));
+        assert_eq!(
+            parse_repl_command("/session clear"),
+            Some(ReplCommand::Clear)
+        );
+        assert_eq!(parse_repl_command("/new"), Some(ReplCommand::Clear));
+        assert_eq!(parse_repl_command("/tools"), Some(ReplCommand::Tools));
+        assert_eq!(parse_repl_command("/model"), Some(ReplCommand::Model(None)));
+        assert_eq!(
+            parse_repl_command("/model  gpt-4o "),
+            Some(ReplCommand::Model(Some("gpt-4o".into())))
+        );
+        assert_eq!(
+            parse_repl_command("/approve pending"),
+            Some(ReplCommand::PendingApprovals)
+        );
+        assert_eq!(
+            parse_repl_command("/approve shell"),
+            Some(ReplCommand::Approve("shell".into()))
+        );
+
+        // Anything else is a message for the model.
+        assert_eq!(parse_repl_command("/etc/hosts has a typo"), None);
+        assert_eq!(parse_repl_command("/session reset"), None);
+        assert_eq!(parse_repl_command("/tools please"), None);
+    }
+
     #[test]
     fn test_scrub_credentials() {
         let input = "API_KEY=sk-1234567890abcdef; token: 1234567890; password=\"secret123456\"";
@@ -2942,7 +3842,7 @@ mod tests {
             tool_call_id: None,
         }];
 
-        assert!(!should_execute_tools_in_parallel(&calls, None));
+        assert!(!should_execute_tools_in_parallel(&calls, None, "cli"));
     }
 
     #[test]
@@ -2964,7 +3864,8 @@ mod tests {
 
         assert!(!should_execute_tools_in_parallel(
             &calls,
-            Some(&approval_mgr)
+            Some(&approval_mgr),
+            "cli"
         ));
     }
 
@@ -2990,7 +3891,8 @@ mod tests {
 
         assert!(should_execute_tools_in_parallel(
             &calls,
-            Some(&approval_mgr)
+            Some(&approval_mgr),
+            "cli"
         ));
     }
 
@@ -4297,6 +5199,58 @@ Tail"#;
         assert!(history[3].content.contains("recent 2"));
     }
 
+    #[tokio::test]
+    async fn fit_context_window_summarizes_then_drops_oldest() {
+        let provider = NonVisionProvider {
+            calls: Arc::new(AtomicUsize::new(0)),
+        };
+        let mut history = vec![ChatMessage::system("sys")];
+        for i in 0..10 {
+            let text = format!("{i}{}", "x".repeat(399));
+            history.push(if i % 2 == 0 {
+                ChatMessage::user(text)
+            } else {
+                ChatMessage::assistant(text)
+            });
+        }
+
+        assert!(fit_context_window(&mut history, &provider, "gpt-4o", 5_000)
+            .await
+            .is_none());
+
+        let fit = fit_context_window(&mut history, &provider, "gpt-4o", 500)
+            .await
+            .expect("history exceeds the budget");
+        assert_eq!(fit.summarized, 4);
+        assert_eq!(fit.dropped, 3);
+        assert!(!fit.truncated);
+        assert!(fit.after <= 500);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(history[0].role, "system");
+        assert!(history.last().unwrap().content.starts_with('9'));
+    }
+
+    #[tokio::test]
+    async fn fit_context_window_truncates_oversized_latest_message() {
+        let provider = NonVisionProvider {
+            calls: Arc::new(AtomicUsize::new(0)),
+        };
+        let mut history = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::user("y".repeat(8_000)),
+        ];
+
+        let fit = fit_context_window(&mut history, &provider, "gpt-4o", 500)
+            .await
+            .expect("history exceeds the budget");
+        assert!(fit.truncated);
+        assert!(fit.after <= 500);
+        assert_eq!(history.len(), 2);
+        assert!(history[1]
+            .content
+            .ends_with("[… truncated to fit the context window]"));
+    }
+
     #[test]
     fn autosave_memory_key_has_prefix_and_uniqueness() {
         let key1 = autosave_memory_key("user_msg");
@@ -5231,6 +6185,21 @@ Let me check the result."#;
         assert!(parsed.get("reasoning_content").is_none());
     }
 
+    #[test]
+    fn build_native_assistant_history_redacts_secrets_in_arguments() {
+        let calls = vec![ToolCall {
+            id: "call_1".into(),
+            name: "shell".into(),
+            arguments: r#"{"command":"curl -H 'x-api-key: sk-ant-REDACTED' https://api.example.com"}"#.into(),
+        }];
+        let result = build_native_assistant_history("", &calls, None);
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(parsed["tool_calls"][0]["id"].as_str(), Some("call_1"));
+        let arguments = parsed["tool_calls"][0]["arguments"].as_str().unwrap();
+        assert!(!arguments.contains("sk-ant-"));
+        assert!(arguments.contains("[REDACTED"));
+    }
+
     #[test]
     fn build_native_assistant_history_from_parsed_calls_includes_reasoning_content() {
         let calls = vec![ParsedToolCall {
@@ -5263,4 +6232,69 @@ Let me check the result."#;
         assert_eq!(parsed["content"].as_str(), Some("answer"));
         assert!(parsed.get("reasoning_content").is_none());
     }
+
+    struct StreamingProvider {
+        chunks: Vec<&'static str>,
+    }
+
+    #[async_trait]
+    impl Provider for StreamingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("streaming provider tests must not fall back to chat");
+        }
+
+        fn supports_streaming(&self) -> bool {
+            true
+        }
+
+        fn stream_chat_with_history(
+            &self,
+            _messages: &[ChatMessage],
+            _model: &str,
+            _temperature: f64,
+            _options: StreamOptions,
+        ) -> futures_util::stream::BoxStream<
+            'static,
+            crate::providers::traits::StreamResult<crate::providers::traits::StreamChunk>,
+        > {
+            use futures_util::StreamExt;
+            let chunks: Vec<_> = self
+                .chunks
+                .iter()
+                .map(|chunk| Ok(crate::providers::traits::StreamChunk::delta(*chunk)))
+                .collect();
+            futures_util::stream::iter(chunks).boxed()
+        }
+    }
+
+    #[tokio::test]
+    async fn stream_llm_response_holds_back_tool_call_markup() {
+        let provider = StreamingProvider {
+            chunks: vec!["Sure, ", "checking. <tool", "_call>{}</tool_call>"],
+        };
+        let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+        let streamed =
+            stream_llm_response(&provider, &[ChatMessage::user("hi")], "model", 0.0, &tx)
+                .await
+                .unwrap();
+        drop(tx);
+
+        let mut relayed = Vec::new();
+        while let Some(delta) = rx.recv().await {
+            relayed.push(delta);
+        }
+        assert_eq!(relayed, vec![DRAFT_CLEAR_SENTINEL, "Sure, ", "checking. "]);
+        assert!(streamed.relayed_any);
+        assert!(!streamed.relayed_all);
+        assert_eq!(
+            streamed.response.text.as_deref(),
+            Some("Sure, checking. <tool_call>{}</tool_call>")
+        );
+    }
 }
@@ -6,6 +6,7 @@ use crate::tools::Tool;
 use anyhow::Result;
 use std::time::{Duration, Instant};
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
 fn find_tool<'a>(tools: &'a [Box<dyn Tool>], name: &str) -> Option<&'a dyn Tool> {
     tools.iter().find(|t| t.name() == name).map(|t| t.as_ref())
@@ -17,6 +18,7 @@ async fn execute_one_tool(
     observer: &dyn Observer,
     cancellation_token: Option<&CancellationToken>,
 ) -> Result<ToolExecutionOutcome> {
+    crate::observability::turn::note_tool(call_name);
     observer.record_event(&ObserverEvent::ToolCallStart {
         tool: call_name.to_string(),
     });
@@ -38,7 +40,11 @@ async fn execute_one_tool(
         });
     };
 
-    let tool_future = tool.execute(call_arguments);
+    let tool_future = async {
+        let _permit = crate::tools::concurrency::acquire(call_name).await;
+        tool.execute(call_arguments).await
+    }
+    .instrument(tracing::info_span!("tool_call", tool = %call_name));
     let tool_result = if let Some(token) = cancellation_token {
         tokio::select! {
             () = token.cancelled() => return Err(ToolLoopCancelled.into()),
@@ -65,10 +71,12 @@ async fn execute_one_tool(
                 })
             } else {
                 let reason = r.error.unwrap_or(r.output);
+                let scrubbed = scrub_credentials(&reason);
+                crate::tools::registry::record_error(call_name, &scrubbed);
                 Ok(ToolExecutionOutcome {
                     output: format!("Error: {reason}"),
                     success: false,
-                    error_reason: Some(scrub_credentials(&reason)),
+                    error_reason: Some(scrubbed),
                     duration,
                 })
             }
@@ -81,6 +89,7 @@ async fn execute_one_tool(
                 success: false,
             });
             let reason = format!("Error executing {call_name}: {e}");
+            crate::tools::registry::record_error(call_name, &scrub_credentials(&reason));
             Ok(ToolExecutionOutcome {
                 output: reason.clone(),
                 success: false,
@@ -101,13 +110,17 @@ pub(super) struct ToolExecutionOutcome {
 pub(super) fn should_execute_tools_in_parallel(
     tool_calls: &[ParsedToolCall],
     approval: Option<&ApprovalManager>,
+    channel_name: &str,
 ) -> bool {
     if tool_calls.len() <= 1 {
         return false;
     }
 
     if let Some(mgr) = approval {
-        if tool_calls.iter().any(|call| mgr.needs_approval(&call.name)) {
+        if tool_calls
+            .iter()
+            .any(|call| mgr.needs_approval(&call.name, channel_name))
+        {
             // Approval-gated calls must keep sequential handling so the caller can
             // enforce CLI prompt/deny policy consistently.
             return false;
@@ -1,3 +1,4 @@
+use crate::channels::session::tokens::{estimate_message_tokens, truncate_to_tokens};
 use crate::providers::{ChatMessage, Provider};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
@@ -12,6 +13,27 @@ const COMPACTION_MAX_SOURCE_CHARS: usize = 12_000;
 /// Max characters retained in stored compaction summary.
 const COMPACTION_MAX_SUMMARY_CHARS: usize = 2_000;
 
+/// Messages the context guard keeps verbatim when it summarizes.
+const CONTEXT_GUARD_KEEP_RECENT_MESSAGES: usize = 6;
+
+/// Tokens the context guard leaves free for the response, capped at a
+/// quarter of the window.
+const CONTEXT_GUARD_RESPONSE_RESERVE_TOKENS: usize = 4_096;
+
+/// What [`fit_context_window`] did to make a request fit.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct ContextFit {
+    /// Estimated prompt tokens before and after.
+    pub before: usize,
+    pub after: usize,
+    /// Messages folded into a compaction summary.
+    pub summarized: usize,
+    /// Oldest messages dropped.
+    pub dropped: usize,
+    /// Whether the largest remaining message had to be cut short.
+    pub truncated: bool,
+}
+
 /// Trim conversation history to prevent unbounded growth.
 /// Preserves the system prompt (first message if role=system) and the most recent messages.
 pub(super) fn trim_history(history: &mut Vec<ChatMessage>, max_history: usize) {
@@ -82,7 +104,28 @@ pub(super) async fn auto_compact_history(
 
     let compact_end = start + compact_count;
     let to_compact: Vec<ChatMessage> = history[start..compact_end].to_vec();
-    let transcript = build_compaction_transcript(&to_compact);
+    let summary = summarize_messages(provider, model, &to_compact)
+        .await
+        .unwrap_or_else(|_| {
+            // Fallback to deterministic local truncation when summarization fails.
+            truncate_with_ellipsis(
+                &build_compaction_transcript(&to_compact),
+                COMPACTION_MAX_SUMMARY_CHARS,
+            )
+        });
+    apply_compaction_summary(history, start, compact_end, &summary);
+
+    Ok(true)
+}
+
+/// Ask the provider for a short bullet summary of `messages`, capped at
+/// [`COMPACTION_MAX_SUMMARY_CHARS`].
+pub(crate) async fn summarize_messages(
+    provider: &dyn Provider,
+    model: &str,
+    messages: &[ChatMessage],
+) -> Result<String> {
+    let transcript = build_compaction_transcript(messages);
 
     let summarizer_system = "You are a conversation compaction engine. Summarize older chat history into concise context for future turns. Preserve: user preferences, commitments, decisions, unresolved tasks, key facts. Omit: filler, repeated chit-chat, verbose tool logs. Output plain text bullet points only.";
 
@@ -93,14 +136,100 @@ pub(super) async fn auto_compact_history(
 
     let summary_raw = provider
         .chat_with_system(Some(summarizer_system), &summarizer_user, model, 0.2)
-        .await
-        .unwrap_or_else(|_| {
-            // Fallback to deterministic local truncation when summarization fails.
-            truncate_with_ellipsis(&transcript, COMPACTION_MAX_SUMMARY_CHARS)
-        });
+        .await?;
 
-    let summary = truncate_with_ellipsis(&summary_raw, COMPACTION_MAX_SUMMARY_CHARS);
-    apply_compaction_summary(history, start, compact_end, &summary);
+    Ok(truncate_with_ellipsis(
+        &summary_raw,
+        COMPACTION_MAX_SUMMARY_CHARS,
+    ))
+}
 
-    Ok(true)
+/// Token budget for the prompt of a request to `model` on `provider`:
+/// the context window minus the response reserve and `fixed_tokens`
+/// (tool definitions sent beside the messages).
+pub(super) fn context_budget(provider: &str, model: &str, fixed_tokens: usize) -> usize {
+    let window = crate::providers::capabilities::context_window(provider, model);
+    window
+        .saturating_sub(CONTEXT_GUARD_RESPONSE_RESERVE_TOKENS.min(window / 4))
+        .saturating_sub(fixed_tokens)
+}
+
+fn estimate_history_tokens(model: &str, history: &[ChatMessage]) -> usize {
+    history
+        .iter()
+        .map(|msg| estimate_message_tokens(model, msg))
+        .sum()
+}
+
+/// First index at or after `idx` that does not start with tool results,
+/// which must stay behind the assistant message that requested them.
+fn skip_tool_results(history: &[ChatMessage], mut idx: usize) -> usize {
+    while idx + 1 < history.len() && history[idx].role == "tool" {
+        idx += 1;
+    }
+    idx
+}
+
+/// Pre-flight check before a model call: when the estimated prompt exceeds
+/// `budget`, summarize older messages, then drop the oldest, then cut the
+/// largest remaining message until it fits. The system prompt and the
+/// latest message are always kept. Returns `None` when nothing changed.
+pub(super) async fn fit_context_window(
+    history: &mut Vec<ChatMessage>,
+    provider: &dyn Provider,
+    model: &str,
+    budget: usize,
+) -> Option<ContextFit> {
+    let before = estimate_history_tokens(model, history);
+    if before <= budget {
+        return None;
+    }
+    let mut fit = ContextFit {
+        before,
+        ..ContextFit::default()
+    };
+    let start = usize::from(history.first().is_some_and(|m| m.role == "system"));
+
+    let keep_from = history
+        .len()
+        .saturating_sub(CONTEXT_GUARD_KEEP_RECENT_MESSAGES)
+        .max(start);
+    let compact_end = skip_tool_results(history, keep_from);
+    if compact_end > start + 1 {
+        let to_compact = history[start..compact_end].to_vec();
+        let summary = summarize_messages(provider, model, &to_compact)
+            .await
+            .unwrap_or_else(|_| {
+                truncate_with_ellipsis(
+                    &build_compaction_transcript(&to_compact),
+                    COMPACTION_MAX_SUMMARY_CHARS,
+                )
+            });
+        apply_compaction_summary(history, start, compact_end, &summary);
+        fit.summarized = compact_end - start;
+    }
+
+    let mut total = estimate_history_tokens(model, history);
+    while total > budget && history.len() > start + 1 {
+        let end = skip_tool_results(history, start + 1);
+        fit.dropped += end - start;
+        history.drain(start..end);
+        total = estimate_history_tokens(model, history);
+    }
+
+    if total > budget {
+        let largest =
+            (start..history.len()).max_by_key(|&idx| estimate_message_tokens(model, &history[idx]));
+        if let Some(idx) = largest {
+            let cost = estimate_message_tokens(model, &history[idx]);
+            let keep = cost.saturating_sub(total - budget + 16);
+            let cut = truncate_to_tokens(model, &history[idx].content, keep).to_string();
+            history[idx].content = format!("{cut}\n[… truncated to fit the context window]");
+            fit.truncated = true;
+            total = estimate_history_tokens(model, history);
+        }
+    }
+
+    fit.after = total;
+    Some(fit)
 }
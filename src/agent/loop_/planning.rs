@@ -0,0 +1,88 @@
+//! Planning mode for the tool-call loop.
+//!
+//! Inside a [`PlanScope::Recording`] scope the loop records every tool call
+//! the model makes as a plan step and answers it with a placeholder result
+//! instead of running it, so the model can lay out the whole plan in one
+//! turn. Inside a [`PlanScope::Approved`] scope the tools named by an
+//! approved plan run without per-call approval prompts.
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+tokio::task_local! {
+    /// Plan handling of the current turn; `None` runs tools normally.
+    pub(crate) static PLAN_SCOPE: Option<PlanScope>;
+}
+
+/// How the current turn treats tool calls.
+#[derive(Clone)]
+pub enum PlanScope {
+    /// Record tool calls as plan steps without executing them.
+    Recording(PlanRecorder),
+    /// Run the listed tools without approval prompts.
+    Approved(Arc<HashSet<String>>),
+}
+
+/// A tool call the model intends to make. Arguments are redacted.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlannedToolCall {
+    pub tool: String,
+    pub arguments: serde_json::Value,
+}
+
+/// Plan steps collected during a planning turn.
+#[derive(Clone, Default)]
+pub struct PlanRecorder(Arc<Mutex<Vec<PlannedToolCall>>>);
+
+impl PlanRecorder {
+    /// Record a step and return its 1-based number. A call identical to an
+    /// earlier step returns that step's number.
+    pub fn record(&self, tool: &str, arguments: serde_json::Value) -> usize {
+        let mut steps = self.0.lock();
+        if let Some(existing) = steps
+            .iter()
+            .position(|step| step.tool == tool && step.arguments == arguments)
+        {
+            return existing + 1;
+        }
+        steps.push(PlannedToolCall {
+            tool: tool.to_string(),
+            arguments,
+        });
+        steps.len()
+    }
+
+    /// Steps recorded so far, in order.
+    pub fn steps(&self) -> Vec<PlannedToolCall> {
+        self.0.lock().clone()
+    }
+}
+
+/// Tool result the model sees for a planned call.
+pub(crate) fn planned_call_output(step: usize) -> String {
+    format!(
+        "Planning mode: this call was not executed. It is step {step} of the plan. \
+         Continue with the remaining steps assuming it succeeds, then summarize the plan."
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorder_numbers_steps_and_merges_repeats() {
+        let recorder = PlanRecorder::default();
+        let ls = serde_json::json!({ "command": "ls" });
+        assert_eq!(recorder.record("shell", ls.clone()), 1);
+        assert_eq!(
+            recorder.record("file_read", serde_json::json!({ "path": "a" })),
+            2
+        );
+        assert_eq!(recorder.record("shell", ls), 1);
+        let tools: Vec<String> = recorder.steps().into_iter().map(|s| s.tool).collect();
+        assert_eq!(tools, vec!["shell", "file_read"]);
+    }
+}
@@ -0,0 +1,176 @@
+//! Daily activity digest.
+//!
+//! The cron scheduler calls [`send_due`] on every tick. Once the configured
+//! `[analytics].digest_hour` (UTC) has passed, the previous UTC day is
+//! summarized per channel from the usage ledger — turns, most used tools,
+//! approval decisions, failed tool calls and turns, LLM requests and cost —
+//! and sent to `[analytics].channel` / `to`. Days already sent are recorded
+//! in the ledger, so each digest goes out once. `zeroclaw usage digest`
+//! prints the same summary for any day.
+
+use crate::config::Config;
+use crate::cost::{ChannelActivity, UsageLedger};
+use anyhow::Result;
+use chrono::{NaiveDate, Timelike, Utc};
+use std::fmt::Write;
+
+/// Digest of `day` listing up to `top_tools` tools per channel.
+pub fn render(day: NaiveDate, channels: &[ChannelActivity], top_tools: usize) -> String {
+    let mut out = format!("Agent activity on {day} (UTC)");
+    if channels.is_empty() {
+        out.push_str("\n\nNo activity recorded.");
+        return out;
+    }
+
+    for activity in channels {
+        let failed_turns: u64 = activity.turn_failures.iter().map(|(_, n)| n).sum();
+        let _ = write!(out, "\n\n{}: {} turns", activity.channel, activity.turns);
+        if failed_turns > 0 {
+            let _ = write!(out, " ({failed_turns} failed)");
+        }
+        let _ = write!(
+            out,
+            ", {} LLM requests, ${:.4}",
+            activity.requests, activity.cost_usd
+        );
+
+        if !activity.tools.is_empty() {
+            let calls: u64 = activity.tools.iter().map(|(_, n)| n).sum();
+            let _ = write!(
+                out,
+                "\n  Tools ({calls} calls): {}",
+                counted(&activity.tools, top_tools)
+            );
+        }
+        if !activity.tool_failures.is_empty() {
+            let _ = write!(
+                out,
+                "\n  Failed tool calls: {}",
+                counted(&activity.tool_failures, top_tools)
+            );
+        }
+        if !activity.approvals.is_empty() {
+            let count = |decision: &str| -> u64 {
+                activity
+                    .approvals
+                    .iter()
+                    .filter(|(name, _)| name == decision)
+                    .map(|(_, n)| n)
+                    .sum()
+            };
+            let _ = write!(
+                out,
+                "\n  Approvals: {} approved, {} denied",
+                count("yes") + count("always"),
+                count("no")
+            );
+        }
+        if !activity.turn_failures.is_empty() {
+            let _ = write!(
+                out,
+                "\n  Failed turns: {}",
+                counted(&activity.turn_failures, usize::MAX)
+            );
+        }
+    }
+
+    let turns: u64 = channels.iter().map(|c| c.turns).sum();
+    let cost: f64 = channels.iter().map(|c| c.cost_usd).sum();
+    let _ = write!(out, "\n\nTotal: {turns} turns, ${cost:.4}");
+    out
+}
+
+/// `name ×count` pairs, the first `limit` of them.
+fn counted(items: &[(String, u64)], limit: usize) -> String {
+    let mut out = items
+        .iter()
+        .take(limit)
+        .map(|(name, n)| format!("{name} ×{n}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if items.len() > limit {
+        let _ = write!(out, ", …{} more", items.len() - limit);
+    }
+    out
+}
+
+/// Summary of `day` from the usage ledger.
+pub fn digest_for(config: &Config, day: NaiveDate) -> Result<String> {
+    let ledger = UsageLedger::open(&config.workspace_dir, &config.cost)?;
+    Ok(render(
+        day,
+        &ledger.channel_activity(day)?,
+        config.analytics.top_tools,
+    ))
+}
+
+/// Send yesterday's digest when it is due and has not gone out yet.
+pub async fn send_due(config: &Config) {
+    let analytics = &config.analytics;
+    if !analytics.digest_enabled || !config.cost.enabled {
+        return;
+    }
+    let (Some(channel), Some(to)) = (analytics.channel.as_deref(), analytics.to.as_deref()) else {
+        return;
+    };
+    let now = Utc::now();
+    if now.hour() < u32::from(analytics.digest_hour) {
+        return;
+    }
+    let Some(day) = now.date_naive().pred_opt() else {
+        return;
+    };
+    if let Err(e) = send_digest(config, channel, to, day).await {
+        tracing::warn!("Failed to send the activity digest for {day}: {e:#}");
+    }
+}
+
+async fn send_digest(config: &Config, channel: &str, to: &str, day: NaiveDate) -> Result<()> {
+    let ledger = UsageLedger::open(&config.workspace_dir, &config.cost)?;
+    if ledger.digest_sent(day)? {
+        return Ok(());
+    }
+    let message = render(
+        day,
+        &ledger.channel_activity(day)?,
+        config.analytics.top_tools,
+    );
+    crate::cron::scheduler::deliver_announcement(config, channel, to, &message).await?;
+    // Only after delivery succeeded, so a failed digest is retried.
+    ledger.mark_digest_sent(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_summarizes_each_channel() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        let channels = vec![ChannelActivity {
+            channel: "telegram".into(),
+            turns: 12,
+            tools: vec![
+                ("shell".into(), 7),
+                ("file_read".into(), 3),
+                ("web_search".into(), 1),
+            ],
+            tool_failures: vec![("shell".into(), 2)],
+            approvals: vec![("yes".into(), 4), ("no".into(), 1), ("always".into(), 1)],
+            turn_failures: vec![("timeout".into(), 1)],
+            requests: 30,
+            cost_usd: 0.5,
+        }];
+
+        let digest = render(day, &channels, 2);
+        assert!(digest.starts_with("Agent activity on 2026-10-15 (UTC)"));
+        assert!(digest.contains("telegram: 12 turns (1 failed), 30 LLM requests, $0.5000"));
+        assert!(digest.contains("Tools (11 calls): shell ×7, file_read ×3, …1 more"));
+        assert!(digest.contains("Failed tool calls: shell ×2"));
+        assert!(digest.contains("Approvals: 5 approved, 1 denied"));
+        assert!(digest.contains("Failed turns: timeout ×1"));
+        assert!(digest.ends_with("Total: 12 turns, $0.5000"));
+
+        assert!(render(day, &[], 5).ends_with("No activity recorded."));
+    }
+}
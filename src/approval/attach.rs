@@ -0,0 +1,468 @@
+//! Remote approval prompts for `zeroclaw attach`.
+//!
+//! A daemon running under systemd has no terminal to ask on. Operators
+//! attach from an interactive terminal, either over the local socket next
+//! to the config file (`attach.sock`, owner-only) or over the gateway's
+//! authenticated `GET /ws/attach`, and every approval the daemon raises is
+//! offered to them alongside the channel's own prompt. The first answer
+//! wins; the others are told the request was resolved.
+//!
+//! Protocol (newline-delimited JSON on the socket, text frames on the WS):
+//! ```text
+//! Server -> Client: {"type":"attached","pending":1}
+//! Server -> Client: {"type":"approval_required","request_id":"apr-1a2b3c4d","tool":"shell","summary":"command: ls","channel":"telegram"}
+//! Client -> Server: {"type":"approval","request_id":"apr-1a2b3c4d","decision":"yes"}
+//! Server -> Client: {"type":"approval_resolved","request_id":"apr-1a2b3c4d"}
+//! ```
+//!
+//! Unanswered prompts are withdrawn after five minutes.
+
+use super::{summarize_args, ApprovalResponse};
+use crate::config::Config;
+use crate::security::{ArgumentScan, LeakDetector};
+use anyhow::{bail, Context, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::LazyLock;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::{mpsc, oneshot};
+
+const PROMPT_TIMEOUT_SECS: u64 = 300;
+
+/// An approval offered to attached operators.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttachPrompt {
+    pub request_id: String,
+    pub tool: String,
+    /// Redacted one-line summary of the tool arguments.
+    pub summary: String,
+    pub channel: String,
+}
+
+impl AttachPrompt {
+    pub fn new(request_id: &str, tool: &str, arguments: &serde_json::Value, channel: &str) -> Self {
+        let arguments = match LeakDetector::new().scan_tool_arguments(arguments) {
+            ArgumentScan::Clean => arguments.clone(),
+            ArgumentScan::Detected { redacted, .. } => redacted,
+        };
+        Self {
+            request_id: request_id.to_string(),
+            tool: tool.to_string(),
+            summary: summarize_args(&arguments),
+            channel: channel.to_string(),
+        }
+    }
+}
+
+/// Messages exchanged with attached clients.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AttachMessage {
+    Attached {
+        pending: usize,
+    },
+    ApprovalRequired(AttachPrompt),
+    ApprovalResolved {
+        request_id: String,
+    },
+    Approval {
+        request_id: String,
+        decision: ApprovalResponse,
+    },
+    Error {
+        message: String,
+    },
+}
+
+struct Waiting {
+    prompt: AttachPrompt,
+    decision: oneshot::Sender<ApprovalResponse>,
+}
+
+#[derive(Default)]
+struct Relay {
+    next_client: AtomicU64,
+    clients: Mutex<HashMap<u64, mpsc::UnboundedSender<AttachMessage>>>,
+    waiting: Mutex<HashMap<String, Waiting>>,
+}
+
+static RELAY: LazyLock<Relay> = LazyLock::new(Relay::default);
+
+/// An attached operator. Dropping it detaches.
+pub struct Operator<'a> {
+    relay: &'a Relay,
+    id: u64,
+    rx: mpsc::UnboundedReceiver<AttachMessage>,
+}
+
+impl Operator<'_> {
+    /// Next message for this operator; `None` once the relay dropped it.
+    pub async fn next(&mut self) -> Option<AttachMessage> {
+        self.rx.recv().await
+    }
+}
+
+impl Drop for Operator<'_> {
+    fn drop(&mut self) {
+        self.relay.clients.lock().remove(&self.id);
+    }
+}
+
+/// Withdraws a prompt when its asker stops waiting, whatever the reason.
+struct WaitGuard<'a> {
+    relay: &'a Relay,
+    request_id: &'a str,
+}
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        // Answered prompts were already removed and announced.
+        if self.relay.waiting.lock().remove(self.request_id).is_some() {
+            self.relay.broadcast(&AttachMessage::ApprovalResolved {
+                request_id: self.request_id.to_string(),
+            });
+        }
+    }
+}
+
+impl Relay {
+    fn broadcast(&self, message: &AttachMessage) {
+        self.clients
+            .lock()
+            .retain(|_, client| client.send(message.clone()).is_ok());
+    }
+
+    fn attach(&self) -> Operator<'_> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let pending: Vec<AttachPrompt> = self
+            .waiting
+            .lock()
+            .values()
+            .map(|waiting| waiting.prompt.clone())
+            .collect();
+        let _ = tx.send(AttachMessage::Attached {
+            pending: pending.len(),
+        });
+        for prompt in pending {
+            let _ = tx.send(AttachMessage::ApprovalRequired(prompt));
+        }
+        let id = self.next_client.fetch_add(1, Ordering::Relaxed);
+        self.clients.lock().insert(id, tx);
+        Operator {
+            relay: self,
+            id,
+            rx,
+        }
+    }
+
+    fn has_operators(&self) -> bool {
+        !self.clients.lock().is_empty()
+    }
+
+    async fn ask(&self, prompt: AttachPrompt) -> Option<ApprovalResponse> {
+        if !self.has_operators() {
+            return None;
+        }
+        let (tx, rx) = oneshot::channel();
+        let request_id = prompt.request_id.clone();
+        self.waiting.lock().insert(
+            request_id.clone(),
+            Waiting {
+                prompt: prompt.clone(),
+                decision: tx,
+            },
+        );
+        let _guard = WaitGuard {
+            relay: self,
+            request_id: &request_id,
+        };
+        self.broadcast(&AttachMessage::ApprovalRequired(prompt));
+
+        tokio::time::timeout(std::time::Duration::from_secs(PROMPT_TIMEOUT_SECS), rx)
+            .await
+            .ok()
+            .and_then(Result::ok)
+    }
+
+    fn handle_client_message(&self, raw: &str) -> Option<AttachMessage> {
+        let error = |message: &str| {
+            Some(AttachMessage::Error {
+                message: message.to_string(),
+            })
+        };
+        match serde_json::from_str::<AttachMessage>(raw) {
+            Ok(AttachMessage::Approval {
+                request_id,
+                decision,
+            }) => {
+                let Some(waiting) = self.waiting.lock().remove(&request_id) else {
+                    return error("Unknown or already resolved approval request");
+                };
+                let _ = waiting.decision.send(decision);
+                self.broadcast(&AttachMessage::ApprovalResolved { request_id });
+                None
+            }
+            Ok(_) => error("Only approval messages are accepted"),
+            Err(_) => error("Invalid JSON"),
+        }
+    }
+}
+
+/// Register an operator. Prompts that are already waiting are replayed to
+/// it right after the `attached` greeting.
+pub fn attach() -> Operator<'static> {
+    RELAY.attach()
+}
+
+/// Offer `prompt` to attached operators and wait for the first answer.
+/// `None` when nobody is attached or nobody answered in time; callers race
+/// this against their own prompt and drop it when that one wins.
+pub async fn ask_operators(prompt: AttachPrompt) -> Option<ApprovalResponse> {
+    RELAY.ask(prompt).await
+}
+
+/// Apply a message received from an attached client. Returns a reply for
+/// that client when the message was not accepted.
+pub fn handle_client_message(raw: &str) -> Option<AttachMessage> {
+    RELAY.handle_client_message(raw)
+}
+
+/// Local attach socket, next to the config file.
+pub fn socket_path(config: &Config) -> PathBuf {
+    config
+        .config_path
+        .parent()
+        .map_or_else(|| PathBuf::from("."), PathBuf::from)
+        .join("attach.sock")
+}
+
+/// Accept operators on the local socket until the task is cancelled.
+#[cfg(unix)]
+pub async fn serve_local(config: Config) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = socket_path(&config);
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove stale {}", path.display()))?;
+    }
+    let listener = tokio::net::UnixListener::bind(&path)
+        .with_context(|| format!("Failed to bind {}", path.display()))?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+    tracing::info!("Attach socket listening on {}", path.display());
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(async move {
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            let mut operator = attach();
+            loop {
+                let outgoing = tokio::select! {
+                    line = lines.next_line() => match line {
+                        Ok(Some(line)) if line.trim().is_empty() => continue,
+                        Ok(Some(line)) => match handle_client_message(&line) {
+                            Some(reply) => reply,
+                            None => continue,
+                        },
+                        _ => break,
+                    },
+                    message = operator.next() => match message {
+                        Some(message) => message,
+                        None => break,
+                    },
+                };
+                let Ok(mut encoded) = serde_json::to_string(&outgoing) else {
+                    continue;
+                };
+                encoded.push('\n');
+                if writer.write_all(encoded.as_bytes()).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+// ── Client ───────────────────────────────────────────────────────
+
+/// Run `zeroclaw attach`: connect to the daemon and answer approval
+/// prompts from this terminal until the connection closes.
+pub async fn run_client(config: &Config, gateway: Option<&str>, token: Option<&str>) -> Result<()> {
+    match gateway {
+        Some(url) => run_ws_client(url, token).await,
+        None => run_local_client(config).await,
+    }
+}
+
+#[cfg(unix)]
+async fn run_local_client(config: &Config) -> Result<()> {
+    let path = socket_path(config);
+    let stream = tokio::net::UnixStream::connect(&path)
+        .await
+        .with_context(|| {
+            format!(
+                "Cannot connect to {}. Is `zeroclaw daemon` running with this config?",
+                path.display()
+            )
+        })?;
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        let Some(mut reply) = answer(&line).await? else {
+            continue;
+        };
+        reply.push('\n');
+        writer.write_all(reply.as_bytes()).await?;
+    }
+    println!("Daemon closed the connection.");
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn run_local_client(_config: &Config) -> Result<()> {
+    bail!("The local attach socket needs a Unix platform; use --gateway instead")
+}
+
+async fn run_ws_client(url: &str, token: Option<&str>) -> Result<()> {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Message};
+
+    let base = url.trim_end_matches('/');
+    let base = if let Some(rest) = base.strip_prefix("https://") {
+        format!("wss://{rest}")
+    } else if let Some(rest) = base.strip_prefix("http://") {
+        format!("ws://{rest}")
+    } else {
+        base.to_string()
+    };
+    let mut request = format!("{base}/ws/attach").into_client_request()?;
+    if let Some(token) = token {
+        request
+            .headers_mut()
+            .insert("Authorization", format!("Bearer {token}").parse()?);
+    }
+    let (socket, _) = tokio_tungstenite::connect_async(request)
+        .await
+        .with_context(|| format!("Cannot connect to {base}/ws/attach"))?;
+    let (mut sink, mut stream) = socket.split();
+    while let Some(message) = stream.next().await {
+        let text = match message? {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        if let Some(reply) = answer(text.as_str()).await? {
+            sink.send(Message::Text(reply.into())).await?;
+        }
+    }
+    println!("Gateway closed the connection.");
+    Ok(())
+}
+
+/// Print a server message; for approval prompts, ask on the terminal and
+/// return the encoded answer.
+async fn answer(raw: &str) -> Result<Option<String>> {
+    let message: AttachMessage =
+        serde_json::from_str(raw).context("Malformed message from the daemon")?;
+    match message {
+        AttachMessage::Attached { pending } => {
+            println!("🔗 Attached. Approval prompts will appear here ({pending} pending).");
+            Ok(None)
+        }
+        AttachMessage::ApprovalRequired(prompt) => {
+            let request_id = prompt.request_id.clone();
+            let decision = tokio::task::spawn_blocking(move || ask_terminal(&prompt)).await?;
+            Ok(Some(serde_json::to_string(&AttachMessage::Approval {
+                request_id,
+                decision,
+            })?))
+        }
+        AttachMessage::ApprovalResolved { request_id } => {
+            println!("   {request_id} resolved.");
+            Ok(None)
+        }
+        AttachMessage::Error { message } => {
+            eprintln!("⚠ {message}");
+            Ok(None)
+        }
+        AttachMessage::Approval { .. } => bail!("Unexpected approval message from the daemon"),
+    }
+}
+
+fn ask_terminal(prompt: &AttachPrompt) -> ApprovalResponse {
+    use std::io::{BufRead, Write};
+
+    println!();
+    println!(
+        "🔧 [{}] {} wants to execute: {}",
+        prompt.request_id, prompt.channel, prompt.tool
+    );
+    println!("   {}", prompt.summary);
+    print!("   [Y]es / [N]o / [A]lways for {}: ", prompt.tool);
+    let _ = std::io::stdout().flush();
+
+    let mut line = String::new();
+    if std::io::stdin().lock().read_line(&mut line).is_err() {
+        return ApprovalResponse::No;
+    }
+    match line.trim().to_ascii_lowercase().as_str() {
+        "y" | "yes" => ApprovalResponse::Yes,
+        "a" | "always" => ApprovalResponse::Always,
+        _ => ApprovalResponse::No,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn first_operator_answer_resolves_the_prompt() {
+        let relay = Relay::default();
+        let prompt = AttachPrompt::new(
+            "apr-test",
+            "shell",
+            &serde_json::json!({"command": "ls"}),
+            "telegram",
+        );
+        assert_eq!(relay.ask(prompt.clone()).await, None);
+
+        let mut first = relay.attach();
+        let mut second = relay.attach();
+        for operator in [&mut first, &mut second] {
+            assert_eq!(
+                operator.next().await,
+                Some(AttachMessage::Attached { pending: 0 })
+            );
+        }
+
+        let reply = r#"{"type":"approval","request_id":"apr-test","decision":"always"}"#;
+        let (decision, ()) = tokio::join!(relay.ask(prompt.clone()), async {
+            assert_eq!(
+                first.next().await,
+                Some(AttachMessage::ApprovalRequired(prompt.clone()))
+            );
+            assert!(relay.handle_client_message(reply).is_none());
+        });
+        assert_eq!(decision, Some(ApprovalResponse::Always));
+        assert!(matches!(
+            relay.handle_client_message(reply),
+            Some(AttachMessage::Error { .. })
+        ));
+
+        assert_eq!(
+            second.next().await,
+            Some(AttachMessage::ApprovalRequired(prompt))
+        );
+        assert_eq!(
+            second.next().await,
+            Some(AttachMessage::ApprovalResolved {
+                request_id: "apr-test".into()
+            })
+        );
+    }
+}
@@ -0,0 +1,287 @@
+//! Administration of the live channel runtime.
+//!
+//! The gateway's `/api/admin/*` endpoints call into this module to inspect
+//! and adjust the channel dispatcher running in the same process: list
+//! channels, force a config reload, review or revoke approval grants, and
+//! list or clear per-sender conversation history, and inspect the outbound
+//! reply queue. Each operation mirrors the matching in-chat runtime command
+//! (`/approvals`, `/unapprove`, `/tools`, `/new`) or CLI command
+//! (`zeroclaw channel outbox`).
+
+use super::outbox::{self, ChannelOutbox, OutboundEntry, OutboundStatus};
+use super::{
+    clear_sender_history, live_channels_registry, maybe_apply_runtime_config_update,
+    remove_non_cli_approval_from_config, runtime_config_path, runtime_config_store,
+    runtime_defaults_snapshot, session, snapshot_non_cli_excluded_tools, ChannelRuntimeContext,
+};
+use crate::approval::PendingNonCliApprovalRequest;
+use crate::tools::registry::ToolInfo;
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::sync::{Arc, Mutex, OnceLock};
+
+fn live_runtime_registry() -> &'static Mutex<Option<Arc<ChannelRuntimeContext>>> {
+    static REGISTRY: OnceLock<Mutex<Option<Arc<ChannelRuntimeContext>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(None))
+}
+
+pub(super) fn register_live_runtime(ctx: &Arc<ChannelRuntimeContext>) {
+    *live_runtime_registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = Some(Arc::clone(ctx));
+}
+
+pub(super) fn clear_live_runtime() {
+    live_runtime_registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take();
+}
+
+fn live_runtime() -> Result<Arc<ChannelRuntimeContext>> {
+    let Some(ctx) = live_runtime_registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+    else {
+        bail!("Channel runtime is not running in this process (start it with `zeroclaw daemon`)");
+    };
+    Ok(ctx)
+}
+
+/// Whether the channel runtime is running in this process.
+pub fn is_running() -> bool {
+    live_runtime().is_ok()
+}
+
+/// One connected channel.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelStatus {
+    pub name: String,
+    pub healthy: bool,
+}
+
+/// Connected channels with a fresh health check, sorted by name.
+pub async fn list_channels() -> Result<Vec<ChannelStatus>> {
+    live_runtime()?;
+    let channels: Vec<_> = live_channels_registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .cloned()
+        .collect();
+    let mut statuses = Vec::with_capacity(channels.len());
+    for channel in channels {
+        statuses.push(ChannelStatus {
+            name: channel.name().to_string(),
+            healthy: channel.health_check().await,
+        });
+    }
+    statuses.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(statuses)
+}
+
+/// Runtime defaults in effect after a reload.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReloadSummary {
+    pub config_path: Option<String>,
+    pub provider: String,
+    pub model: String,
+    pub temperature: f64,
+}
+
+/// Re-read `config.toml` and apply provider, model, and approval policy
+/// changes, even when the file looks unchanged.
+pub async fn reload_config() -> Result<ReloadSummary> {
+    let ctx = live_runtime()?;
+    let config_path = runtime_config_path(&ctx);
+    if let Some(path) = config_path.as_ref() {
+        if let Some(state) = runtime_config_store()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(path)
+        {
+            state.last_applied_stamp = None;
+        }
+    }
+    maybe_apply_runtime_config_update(&ctx).await?;
+
+    let defaults = runtime_defaults_snapshot(&ctx);
+    Ok(ReloadSummary {
+        config_path: config_path.map(|path| path.display().to_string()),
+        provider: defaults.default_provider,
+        model: defaults.model,
+        temperature: defaults.temperature,
+    })
+}
+
+/// Effective non-CLI approval state.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApprovalState {
+    pub auto_approve: Vec<String>,
+    pub always_ask: Vec<String>,
+    /// Tools approved for the rest of this runtime session.
+    pub session_grants: Vec<String>,
+    pub pending: Vec<PendingNonCliApprovalRequest>,
+}
+
+pub(super) fn approval_state(ctx: &ChannelRuntimeContext) -> ApprovalState {
+    let sorted = |set: std::collections::HashSet<String>| {
+        let mut items: Vec<String> = set.into_iter().collect();
+        items.sort();
+        items
+    };
+    let mgr = &ctx.approval_manager;
+    ApprovalState {
+        auto_approve: sorted(mgr.auto_approve_tools()),
+        always_ask: sorted(mgr.always_ask_tools()),
+        session_grants: sorted(mgr.non_cli_session_allowlist()),
+        pending: mgr.list_non_cli_pending_requests(None, None, None),
+    }
+}
+
+pub fn approvals() -> Result<ApprovalState> {
+    Ok(approval_state(&live_runtime()?))
+}
+
+/// Tools the channel agent can call, like `/tools` (without a sender's
+/// autonomy override or a channel's approval policy).
+pub fn tools() -> Result<Vec<ToolInfo>> {
+    let ctx = live_runtime()?;
+    Ok(crate::tools::registry::describe(
+        &ctx.tools_registry,
+        &ctx.approval_manager,
+        &snapshot_non_cli_excluded_tools(&ctx),
+        "",
+    ))
+}
+
+/// What `revoke_approval` removed.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApprovalRevocation {
+    pub tool: String,
+    pub session_grant_removed: bool,
+    pub runtime_grant_removed: bool,
+    pub pending_cleared: usize,
+    /// `Some(true)` when the tool was dropped from `autonomy.auto_approve`
+    /// in `config.toml`; `None` when no config file is known.
+    pub config_grant_removed: Option<bool>,
+}
+
+/// Revoke a tool's approval everywhere, like `/unapprove <tool>`.
+pub async fn revoke_approval(tool: &str) -> Result<ApprovalRevocation> {
+    let tool = tool.trim();
+    if tool.is_empty() {
+        bail!("Tool name must not be empty");
+    }
+    let ctx = live_runtime()?;
+    let mgr = &ctx.approval_manager;
+    let session_grant_removed = mgr.revoke_non_cli_session(tool);
+    let runtime_grant_removed = mgr.apply_persistent_runtime_revoke(tool);
+    let pending_cleared = mgr.clear_non_cli_pending_requests_for_tool(tool);
+    let config_grant_removed = remove_non_cli_approval_from_config(&ctx, tool)
+        .await?
+        .map(|(_, removed)| removed);
+    Ok(ApprovalRevocation {
+        tool: tool.to_string(),
+        session_grant_removed,
+        runtime_grant_removed,
+        pending_cleared,
+        config_grant_removed,
+    })
+}
+
+/// One sender's in-memory conversation.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub key: String,
+    pub messages: usize,
+    pub pinned: usize,
+    /// Title, tags and origin from the session store, when one is configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<session::SessionMetadata>,
+}
+
+pub(super) fn session_summaries(ctx: &ChannelRuntimeContext) -> Vec<SessionSummary> {
+    let pinned = ctx
+        .pinned_messages
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut summaries: Vec<SessionSummary> = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|(key, turns)| SessionSummary {
+            key: key.clone(),
+            messages: turns.len(),
+            pinned: pinned.get(key).map_or(0, Vec::len),
+            metadata: None,
+        })
+        .collect();
+    summaries.sort_by(|a, b| a.key.cmp(&b.key));
+    summaries
+}
+
+pub async fn sessions() -> Result<Vec<SessionSummary>> {
+    let ctx = live_runtime()?;
+    let mut summaries = session_summaries(&ctx);
+    if let Some(store) = ctx.session_store.as_ref() {
+        for summary in &mut summaries {
+            summary.metadata = session::load_metadata(store.as_ref(), &summary.key).await?;
+        }
+    }
+    Ok(summaries)
+}
+
+/// Forget one sender's history and pins, including the session store copy.
+/// Returns whether anything was held in memory for `key`.
+pub(super) async fn clear_session_for(ctx: &ChannelRuntimeContext, key: &str) -> Result<bool> {
+    let had_history = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains_key(key);
+    clear_sender_history(ctx, key);
+    let had_pins = ctx
+        .pinned_messages
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(key)
+        .is_some();
+    if let Some(store) = ctx.session_store.as_ref() {
+        store.remove(key).await?;
+        store.remove(&session::pins_key(key)).await?;
+        store.remove(&session::metadata_key(key)).await?;
+    }
+    Ok(had_history || had_pins)
+}
+
+pub async fn clear_session(key: &str) -> Result<bool> {
+    clear_session_for(&live_runtime()?, key).await
+}
+
+fn live_outbox() -> Result<Arc<ChannelOutbox>> {
+    live_runtime()?;
+    outbox::outbox().ok_or_else(|| {
+        anyhow::anyhow!("Outbound queue is disabled (`reliability.outbound_queue = false`)")
+    })
+}
+
+/// Recorded replies with `status` (all when `None`), newest first.
+pub async fn outbound(status: Option<OutboundStatus>) -> Result<Vec<OutboundEntry>> {
+    live_outbox()?.list(status, 200).await
+}
+
+/// Queue an undelivered reply for immediate delivery.
+pub async fn retry_outbound(id: i64) -> Result<OutboundEntry> {
+    match live_outbox()?.requeue(id).await? {
+        Some(entry) => Ok(entry),
+        None => bail!("No undelivered reply with id {id}"),
+    }
+}
+
+/// Drop an undelivered reply. Returns whether one was removed.
+pub async fn discard_outbound(id: i64) -> Result<bool> {
+    live_outbox()?.discard(id).await
+}
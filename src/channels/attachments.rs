@@ -0,0 +1,342 @@
+//! Shared on-disk store for files received through channels.
+//!
+//! Every channel saves inbound attachments under
+//! `<workspace>/attachments/<channel>/`. File names are sanitized, writes
+//! never follow symlinks or leave the workspace, and retention is enforced
+//! here for all channels at once: files older than `attachments.max_age_hours`
+//! are deleted, then the oldest files go until the store fits in
+//! `attachments.max_total_mb`. Collection runs after every save and
+//! periodically while channels run.
+
+use crate::config::AttachmentsConfig;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::fs;
+
+const ATTACHMENTS_REL_DIR: &str = "attachments";
+
+/// Age and size limits applied by [`AttachmentStore::gc`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AttachmentRetention {
+    pub max_age: Option<Duration>,
+    pub max_total_bytes: Option<u64>,
+}
+
+impl From<&AttachmentsConfig> for AttachmentRetention {
+    fn from(config: &AttachmentsConfig) -> Self {
+        Self {
+            max_age: (config.max_age_hours > 0)
+                .then(|| Duration::from_secs(config.max_age_hours.saturating_mul(3600))),
+            max_total_bytes: (config.max_total_mb > 0)
+                .then(|| config.max_total_mb.saturating_mul(1024 * 1024)),
+        }
+    }
+}
+
+static RETENTION: LazyLock<RwLock<AttachmentRetention>> =
+    LazyLock::new(|| RwLock::new(AttachmentRetention::from(&AttachmentsConfig::default())));
+
+/// Apply `[attachments]` to every store created afterwards.
+pub fn init_from_config(config: &AttachmentsConfig) {
+    *RETENTION.write().unwrap_or_else(|e| e.into_inner()) = AttachmentRetention::from(config);
+}
+
+/// What one collection pass deleted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcStats {
+    pub removed: usize,
+    pub freed_bytes: u64,
+}
+
+/// Attachment store rooted in a workspace.
+#[derive(Debug, Clone)]
+pub struct AttachmentStore {
+    workspace: PathBuf,
+    retention: AttachmentRetention,
+}
+
+impl AttachmentStore {
+    /// Store for `workspace` with the configured retention.
+    pub fn new(workspace: &Path) -> Self {
+        Self {
+            workspace: workspace.to_path_buf(),
+            retention: *RETENTION.read().unwrap_or_else(|e| e.into_inner()),
+        }
+    }
+
+    pub fn with_retention(mut self, retention: AttachmentRetention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Root directory of the store.
+    pub fn root(&self) -> PathBuf {
+        self.workspace.join(ATTACHMENTS_REL_DIR)
+    }
+
+    /// Directory holding `channel`'s files.
+    pub fn channel_dir(&self, channel: &str) -> PathBuf {
+        self.root().join(channel)
+    }
+
+    /// Path a new attachment named `file_name` from `channel` will be written
+    /// to. Creates the channel directory; rejects names that sanitize to
+    /// nothing, directories that resolve outside the workspace and existing
+    /// symlinks or non-files at the target.
+    pub async fn output_path(&self, channel: &str, file_name: &str) -> Result<PathBuf> {
+        let safe_name = sanitize_attachment_filename(file_name)
+            .ok_or_else(|| anyhow::anyhow!("invalid attachment filename: {file_name}"))?;
+        if sanitize_attachment_filename(channel).as_deref() != Some(channel) {
+            anyhow::bail!("invalid attachment channel name: {channel}");
+        }
+
+        fs::create_dir_all(&self.workspace).await?;
+        let workspace_root = fs::canonicalize(&self.workspace)
+            .await
+            .unwrap_or_else(|_| self.workspace.clone());
+
+        let save_dir = self.channel_dir(channel);
+        fs::create_dir_all(&save_dir).await?;
+        let resolved_save_dir = fs::canonicalize(&save_dir).await.with_context(|| {
+            format!(
+                "failed to resolve attachment save directory: {}",
+                save_dir.display()
+            )
+        })?;
+
+        if !resolved_save_dir.starts_with(&workspace_root) {
+            anyhow::bail!(
+                "attachment save directory escapes workspace: {}",
+                save_dir.display()
+            );
+        }
+
+        let output_path = resolved_save_dir.join(safe_name);
+        match fs::symlink_metadata(&output_path).await {
+            Ok(meta) => {
+                if meta.file_type().is_symlink() {
+                    anyhow::bail!(
+                        "refusing to write attachment through symlink: {}",
+                        output_path.display()
+                    );
+                }
+                if !meta.is_file() {
+                    anyhow::bail!(
+                        "attachment output path is not a regular file: {}",
+                        output_path.display()
+                    );
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        Ok(output_path)
+    }
+
+    /// Write `data` as `file_name` for `channel`, then enforce retention.
+    pub async fn save(&self, channel: &str, file_name: &str, data: &[u8]) -> Result<PathBuf> {
+        let path = self.output_path(channel, file_name).await?;
+        fs::write(&path, data)
+            .await
+            .with_context(|| format!("failed to save attachment to {}", path.display()))?;
+
+        let store = self.clone();
+        match tokio::task::spawn_blocking(move || store.gc()).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => tracing::warn!("Attachment garbage collection failed: {e:#}"),
+            Err(e) => tracing::warn!("Attachment garbage collection panicked: {e}"),
+        }
+        Ok(path)
+    }
+
+    /// Stored file `file_name` of `channel`, if it still exists.
+    pub fn resolve(&self, channel: &str, file_name: &str) -> Result<PathBuf> {
+        let safe_name = sanitize_attachment_filename(file_name)
+            .ok_or_else(|| anyhow::anyhow!("invalid attachment filename: {file_name}"))?;
+        let channel_dir = self
+            .channel_dir(channel)
+            .canonicalize()
+            .with_context(|| format!("no attachments stored for {channel}"))?;
+        let resolved = channel_dir
+            .join(safe_name)
+            .canonicalize()
+            .with_context(|| format!("attachment not found: {file_name}"))?;
+        if !resolved.starts_with(&channel_dir) || !resolved.is_file() {
+            anyhow::bail!("attachment not found: {file_name}");
+        }
+        Ok(resolved)
+    }
+
+    /// Delete expired files, then the oldest files until the store is within
+    /// its size cap. Symlinks are removed without being followed.
+    pub fn gc(&self) -> Result<GcStats> {
+        let mut stats = GcStats::default();
+        let root = self.root();
+        if !root.is_dir() {
+            return Ok(stats);
+        }
+
+        let now = SystemTime::now();
+        let mut files = Vec::new();
+        for channel_dir in std::fs::read_dir(&root)? {
+            let channel_dir = channel_dir?;
+            if !channel_dir.file_type()?.is_dir() {
+                continue;
+            }
+            for entry in std::fs::read_dir(channel_dir.path())? {
+                let entry = entry?;
+                let meta = entry.metadata()?;
+                if meta.is_dir() {
+                    continue;
+                }
+                let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                let expired = self.retention.max_age.is_some_and(|max_age| {
+                    now.duration_since(modified).unwrap_or_default() > max_age
+                });
+                if expired {
+                    remove(&entry.path(), meta.len(), &mut stats);
+                } else {
+                    files.push((entry.path(), meta.len(), modified));
+                }
+            }
+        }
+
+        if let Some(max_total) = self.retention.max_total_bytes {
+            let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+            files.sort_by_key(|(_, _, modified)| *modified);
+            for (path, len, _) in files {
+                if total <= max_total {
+                    break;
+                }
+                remove(&path, len, &mut stats);
+                total = total.saturating_sub(len);
+            }
+        }
+
+        if stats.removed > 0 {
+            tracing::info!(
+                removed = stats.removed,
+                freed_bytes = stats.freed_bytes,
+                "Collected channel attachments"
+            );
+        }
+        Ok(stats)
+    }
+}
+
+fn remove(path: &Path, len: u64, stats: &mut GcStats) {
+    match std::fs::remove_file(path) {
+        Ok(()) => {
+            stats.removed += 1;
+            stats.freed_bytes += len;
+        }
+        Err(e) => tracing::warn!("Failed to remove attachment {}: {e}", path.display()),
+    }
+}
+
+/// Collect `workspace`'s store every `interval` until `stop` is cancelled.
+pub async fn run_gc_loop(
+    workspace: PathBuf,
+    interval: Duration,
+    stop: tokio_util::sync::CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            () = stop.cancelled() => return,
+            _ = ticker.tick() => {}
+        }
+        let store = AttachmentStore::new(&workspace);
+        match tokio::task::spawn_blocking(move || store.gc()).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => tracing::warn!("Attachment garbage collection failed: {e:#}"),
+            Err(e) => tracing::warn!("Attachment garbage collection panicked: {e}"),
+        }
+    }
+}
+
+/// Base name of `file_name` with separators replaced, capped at 128
+/// characters. `None` when nothing usable is left.
+pub fn sanitize_attachment_filename(file_name: &str) -> Option<String> {
+    let basename = Path::new(file_name).file_name()?.to_str()?.trim();
+    if basename.is_empty() || basename == "." || basename == ".." {
+        return None;
+    }
+
+    let sanitized: String = basename
+        .replace(['/', '\\'], "_")
+        .chars()
+        .take(128)
+        .collect();
+    if sanitized.is_empty() || sanitized == "." || sanitized == ".." {
+        None
+    } else {
+        Some(sanitized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn age(path: &Path, secs_ago: u64) {
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(secs_ago))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn save_places_files_per_channel_and_resolves_them() {
+        let tmp = TempDir::new().unwrap();
+        let store = AttachmentStore::new(tmp.path()).with_retention(AttachmentRetention::default());
+
+        let path = store
+            .save("telegram", "../../etc/report.pdf", b"pdf")
+            .await
+            .unwrap();
+        assert!(path.ends_with("attachments/telegram/report.pdf"));
+        assert_eq!(store.resolve("telegram", "report.pdf").unwrap(), path);
+        assert!(store.resolve("discord", "report.pdf").is_err());
+        assert!(store.save("../telegram", "x.txt", b"x").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn gc_drops_expired_then_oldest_files_over_quota() {
+        let tmp = TempDir::new().unwrap();
+        let store = AttachmentStore::new(tmp.path()).with_retention(AttachmentRetention::default());
+        let expired = store
+            .save("telegram", "expired.bin", &[0; 10])
+            .await
+            .unwrap();
+        let oldest = store.save("discord", "oldest.bin", &[0; 10]).await.unwrap();
+        let newest = store
+            .save("telegram", "newest.bin", &[0; 10])
+            .await
+            .unwrap();
+        age(&expired, 7200);
+        age(&oldest, 600);
+        age(&newest, 60);
+
+        let stats = store
+            .with_retention(AttachmentRetention {
+                max_age: Some(Duration::from_secs(3600)),
+                max_total_bytes: Some(15),
+            })
+            .gc()
+            .unwrap();
+        assert_eq!(
+            stats,
+            GcStats {
+                removed: 2,
+                freed_bytes: 20
+            }
+        );
+        assert!(!expired.exists());
+        assert!(!oldest.exists());
+        assert!(newest.exists());
+    }
+}
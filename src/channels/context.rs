@@ -0,0 +1,209 @@
+//! Deterministic assembly of the per-turn channel system prompt.
+//!
+//! [`ContextBuilder`] composes the system prompt from named sections that
+//! always render in the same order — identity, tools, channel, memory,
+//! time — no matter when callers add them. Every section except identity
+//! has a token budget (`[session.context_budgets]`); an oversized section is
+//! cut at its budget with a marker instead of crowding history out of the
+//! context window. Identity carries the startup prompt and the prompt-cache
+//! boundary, so it is never cut.
+
+use super::session::tokens::{estimate_text_tokens, truncate_to_tokens};
+use crate::config::ContextBudgetsConfig;
+use chrono::{DateTime, TimeZone};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// Sections of the channel system prompt, in render order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ContextSection {
+    /// Startup prompt: identity files, skills, safety, shell policy.
+    Identity,
+    /// Tools available in this turn and the tool-call protocol.
+    Tools,
+    /// Delivery hints, execution visibility, and reply target.
+    Channel,
+    /// Pinned messages.
+    Memory,
+    /// Current date, time zone, and locale.
+    Time,
+}
+
+impl ContextSection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Identity => "identity",
+            Self::Tools => "tools",
+            Self::Channel => "channel",
+            Self::Memory => "memory",
+            Self::Time => "time",
+        }
+    }
+
+    /// Token budget for this section; `None` when unlimited.
+    fn budget(self, budgets: &ContextBudgetsConfig) -> Option<usize> {
+        let budget = match self {
+            Self::Identity => 0,
+            Self::Tools => budgets.tools,
+            Self::Channel => budgets.channel,
+            Self::Memory => budgets.memory,
+            Self::Time => budgets.time,
+        };
+        (budget > 0).then_some(budget)
+    }
+}
+
+/// Builder for the channel system prompt of one turn.
+pub struct ContextBuilder<'a> {
+    model: &'a str,
+    budgets: &'a ContextBudgetsConfig,
+    sections: BTreeMap<ContextSection, Vec<String>>,
+}
+
+impl<'a> ContextBuilder<'a> {
+    /// Builder estimating tokens for `model`.
+    pub fn new(model: &'a str, budgets: &'a ContextBudgetsConfig) -> Self {
+        Self {
+            model,
+            budgets,
+            sections: BTreeMap::new(),
+        }
+    }
+
+    /// Append `text` to `section`. Blank text is ignored; parts of one
+    /// section keep the order they were added in.
+    #[must_use]
+    pub fn section(mut self, section: ContextSection, text: &str) -> Self {
+        let text = text.trim();
+        if !text.is_empty() {
+            self.sections
+                .entry(section)
+                .or_default()
+                .push(text.to_string());
+        }
+        self
+    }
+
+    /// Set the time section from `now` and an optional locale.
+    #[must_use]
+    pub fn time<Tz>(self, now: &DateTime<Tz>, locale: Option<&str>) -> Self
+    where
+        Tz: TimeZone,
+        Tz::Offset: Display,
+    {
+        let mut text = format!(
+            "## Current Date & Time (this turn)\n\n{} ({})",
+            now.format("%Y-%m-%d %H:%M:%S"),
+            now.format("%Z")
+        );
+        if let Some(locale) = locale {
+            text.push_str("\nLocale: ");
+            text.push_str(locale);
+        }
+        self.section(ContextSection::Time, &text)
+    }
+
+    /// Render the prompt: sections in [`ContextSection`] order, separated
+    /// by blank lines, each cut to its budget.
+    pub fn build(&self) -> String {
+        let mut rendered = Vec::with_capacity(self.sections.len());
+        for (section, parts) in &self.sections {
+            let text = parts.join("\n\n");
+            rendered.push(match section.budget(self.budgets) {
+                Some(budget) => self.fit(*section, &text, budget),
+                None => text,
+            });
+        }
+        rendered.join("\n\n")
+    }
+
+    fn fit(&self, section: ContextSection, text: &str, budget: usize) -> String {
+        if estimate_text_tokens(self.model, text) <= budget {
+            return text.to_string();
+        }
+        tracing::debug!(
+            section = section.as_str(),
+            budget,
+            "System prompt section truncated to its token budget"
+        );
+        let marker = format!("\n[... {} section truncated]", section.as_str());
+        let keep = budget.saturating_sub(estimate_text_tokens(self.model, &marker));
+        format!(
+            "{}{marker}",
+            truncate_to_tokens(self.model, text, keep).trim_end()
+        )
+    }
+}
+
+/// Locale of the host from `LC_ALL`, `LC_MESSAGES` or `LANG`, without the
+/// encoding suffix. `C` and `POSIX` count as unset.
+pub fn host_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .map(|value| {
+            value
+                .split('.')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        })
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn fixed_now() -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339("2026-03-01T09:30:00+08:00").unwrap()
+    }
+
+    #[test]
+    fn sections_render_in_fixed_order() {
+        let budgets = ContextBudgetsConfig::default();
+        let prompt = ContextBuilder::new("gpt-4o", &budgets)
+            .time(&fixed_now(), Some("zh_CN"))
+            .section(
+                ContextSection::Memory,
+                "\n\n## Pinned Context\n\n1. Use metric units",
+            )
+            .section(ContextSection::Channel, "Reply briefly.")
+            .section(ContextSection::Tools, "## Tools\n\n- `shell`")
+            .section(ContextSection::Identity, "You are ZeroClaw.\n")
+            .section(ContextSection::Channel, "Channel context: telegram")
+            .section(ContextSection::Memory, "   ")
+            .build();
+
+        assert_eq!(
+            prompt,
+            "You are ZeroClaw.\n\n\
+             ## Tools\n\n- `shell`\n\n\
+             Reply briefly.\n\n\
+             Channel context: telegram\n\n\
+             ## Pinned Context\n\n1. Use metric units\n\n\
+             ## Current Date & Time (this turn)\n\n2026-03-01 09:30:00 (+08:00)\nLocale: zh_CN"
+        );
+    }
+
+    #[test]
+    fn oversized_sections_are_cut_except_identity() {
+        let budgets = ContextBudgetsConfig {
+            memory: 10,
+            ..ContextBudgetsConfig::default()
+        };
+        let identity = "identity ".repeat(200);
+        let pins = "pinned fact number one. ".repeat(20);
+        let prompt = ContextBuilder::new("gpt-4o", &budgets)
+            .section(ContextSection::Identity, &identity)
+            .section(ContextSection::Memory, &pins)
+            .build();
+
+        let (head, memory) = prompt.split_once("\n\n").unwrap();
+        assert_eq!(head, identity.trim());
+        assert!(memory.ends_with("[... memory section truncated]"));
+        assert!(estimate_text_tokens("gpt-4o", memory) <= 10);
+    }
+}
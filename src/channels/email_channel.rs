@@ -112,7 +112,44 @@ impl Default for EmailConfig {
     }
 }
 
-type ImapSession = Session<TlsStream<TcpStream>>;
+pub(crate) type ImapSession = Session<TlsStream<TcpStream>>;
+
+/// Open a TLS connection to `host:port` and log in.
+pub(crate) async fn connect_imap(
+    host: &str,
+    port: u16,
+    username: &str,
+    password: &str,
+) -> Result<ImapSession> {
+    let addr = format!("{host}:{port}");
+    debug!("Connecting to IMAP server at {}", addr);
+
+    // Connect TCP
+    let tcp = TcpStream::connect(&addr).await?;
+
+    // Establish TLS using rustls
+    let certs = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.into(),
+    };
+    let config = ClientConfig::builder()
+        .with_root_certificates(certs)
+        .with_no_client_auth();
+    let tls_stream: TlsConnector = Arc::new(config).into();
+    let sni: DnsName = host.to_string().try_into()?;
+    let stream = tls_stream.connect(sni.into(), tcp).await?;
+
+    // Create IMAP client
+    let client = async_imap::Client::new(stream);
+
+    // Login
+    let session = client
+        .login(username, password)
+        .await
+        .map_err(|(e, _)| anyhow!("IMAP login failed: {}", e))?;
+
+    debug!("IMAP login successful");
+    Ok(session)
+}
 
 /// Email channel — IMAP IDLE for instant push notifications, SMTP for outbound
 pub struct EmailChannel {
@@ -174,7 +211,7 @@ impl EmailChannel {
     }
 
     /// Extract the sender address from a parsed email
-    fn extract_sender(parsed: &mail_parser::Message) -> String {
+    pub(crate) fn extract_sender(parsed: &mail_parser::Message) -> String {
         parsed
             .from()
             .and_then(|addr| addr.first())
@@ -184,7 +221,7 @@ impl EmailChannel {
     }
 
     /// Extract readable text from a parsed email
-    fn extract_text(parsed: &mail_parser::Message) -> String {
+    pub(crate) fn extract_text(parsed: &mail_parser::Message) -> String {
         if let Some(text) = parsed.body_text(0) {
             return text.to_string();
         }
@@ -207,34 +244,13 @@ impl EmailChannel {
 
     /// Connect to IMAP server with TLS and authenticate
     async fn connect_imap(&self) -> Result<ImapSession> {
-        let addr = format!("{}:{}", self.config.imap_host, self.config.imap_port);
-        debug!("Connecting to IMAP server at {}", addr);
-
-        // Connect TCP
-        let tcp = TcpStream::connect(&addr).await?;
-
-        // Establish TLS using rustls
-        let certs = RootCertStore {
-            roots: webpki_roots::TLS_SERVER_ROOTS.into(),
-        };
-        let config = ClientConfig::builder()
-            .with_root_certificates(certs)
-            .with_no_client_auth();
-        let tls_stream: TlsConnector = Arc::new(config).into();
-        let sni: DnsName = self.config.imap_host.clone().try_into()?;
-        let stream = tls_stream.connect(sni.into(), tcp).await?;
-
-        // Create IMAP client
-        let client = async_imap::Client::new(stream);
-
-        // Login
-        let session = client
-            .login(&self.config.username, &self.config.password)
-            .await
-            .map_err(|(e, _)| anyhow!("IMAP login failed: {}", e))?;
-
-        debug!("IMAP login successful");
-        Ok(session)
+        connect_imap(
+            &self.config.imap_host,
+            self.config.imap_port,
+            &self.config.username,
+            &self.config.password,
+        )
+        .await
     }
 
     /// Fetch and process unseen messages from the selected mailbox
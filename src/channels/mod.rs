@@ -14,8 +14,11 @@
 //! To add a new channel, implement [`Channel`] in a new submodule and wire it into
 //! [`start_channels`]. See `AGENTS.md` §7.2 for the full change playbook.
 
+pub mod admin;
+pub mod attachments;
 pub mod clawdtalk;
 pub mod cli;
+pub mod context;
 pub mod dingtalk;
 pub mod discord;
 pub mod email_channel;
@@ -29,9 +32,16 @@ pub mod matrix;
 pub mod mattermost;
 pub mod nextcloud_talk;
 pub mod nostr;
+pub mod outbox;
+pub mod persona;
+pub mod plan;
 pub mod qq;
+pub mod queue;
+pub mod render;
+pub mod session;
 pub mod signal;
 pub mod slack;
+pub mod task_queue;
 pub mod telegram;
 pub mod traits;
 pub mod transcription;
@@ -58,6 +68,7 @@ pub use mattermost::MattermostChannel;
 pub use nextcloud_talk::NextcloudTalkChannel;
 pub use nostr::NostrChannel;
 pub use qq::QQChannel;
+pub use queue::QueueChannel;
 pub use signal::SignalChannel;
 pub use slack::SlackChannel;
 pub use telegram::TelegramChannel;
@@ -67,21 +78,27 @@ pub use whatsapp::WhatsAppChannel;
 #[cfg(feature = "whatsapp-web")]
 pub use whatsapp_web::WhatsAppWebChannel;
 
+use crate::agent::loop_::planning::PLAN_SCOPE;
 use crate::agent::loop_::{
     build_shell_policy_instructions, build_tool_instructions_from_specs,
     run_tool_call_loop_with_non_cli_approval_context, scrub_credentials, NonCliApprovalContext,
+    CONTENT_INJECTION_CONFIG, TOOL_ANOMALY_MONITOR,
 };
+use crate::approval::quarantine::QuarantineDecision;
 use crate::approval::{ApprovalManager, ApprovalResponse, PendingApprovalError};
 use crate::config::{Config, NonCliNaturalLanguageApprovalMode};
+use crate::cost::{UsageScope, USAGE_SCOPE};
 use crate::identity;
-use crate::memory::{self, Memory};
-use crate::observability::{self, runtime_trace, Observer};
+use crate::memory::{self, principal, Memory};
+use crate::observability::traits::ObserverMetric;
+use crate::observability::{self, runtime_trace, turn, Observer, ObserverEvent};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
-use crate::security::{LeakDetector, LeakResult, SecurityPolicy};
+use crate::security::{LeakDetector, LeakResult, SecurityPolicy, StreamScanner};
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::{Context, Result};
+use context::{ContextBuilder, ContextSection};
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
@@ -91,11 +108,14 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant, SystemTime};
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
 /// Per-sender conversation history for channel messages.
 type ConversationHistoryMap = Arc<Mutex<HashMap<String, Vec<ChatMessage>>>>;
-/// Maximum history messages to keep per sender.
-const MAX_CHANNEL_HISTORY: usize = 50;
+/// Per-sender pinned messages, kept outside history so trimming never drops them.
+type PinnedMessageMap = Arc<Mutex<HashMap<String, Vec<String>>>>;
+/// Prefix of the synthetic user turn that carries summarized older history.
+const CHANNEL_HISTORY_SUMMARY_PREFIX: &str = "[Earlier conversation summary]";
 /// Minimum user-message length (in chars) for auto-save to memory.
 /// Messages shorter than this (e.g. "ok", "thanks") are not stored,
 /// reducing noise in memory recall.
@@ -186,6 +206,9 @@ enum ChannelRuntimeCommand {
     ShowModel,
     SetModel(String),
     NewSession,
+    Pin(String),
+    Unpin(String),
+    ListPins,
     RequestAllToolsOnce,
     RequestToolApproval(String),
     ConfirmToolApproval(String),
@@ -195,6 +218,16 @@ enum ChannelRuntimeCommand {
     ApproveTool(String),
     UnapproveTool(String),
     ListApprovals,
+    ListTools,
+    ListWorkflows,
+    RunWorkflow(String),
+    ListQuarantined,
+    ReviewQuarantined(String, QuarantineDecision),
+    ShowHome,
+    ShowBudget,
+    OverrideBudget(String),
+    UndoLastTurn,
+    Persona(String),
 }
 
 const APPROVAL_ALL_TOOLS_ONCE_TOKEN: &str = "__all_tools_once__";
@@ -242,6 +275,8 @@ struct RuntimeAutonomyPolicy {
     non_cli_natural_language_approval_mode: NonCliNaturalLanguageApprovalMode,
     non_cli_natural_language_approval_mode_by_channel:
         HashMap<String, NonCliNaturalLanguageApprovalMode>,
+    level_overrides: HashMap<String, crate::security::AutonomyLevel>,
+    approval_policy_by_channel: HashMap<String, crate::config::ChannelApprovalPolicy>,
     perplexity_filter: crate::config::PerplexityFilterConfig,
 }
 
@@ -267,9 +302,14 @@ struct ChannelRuntimeContext {
     model: Arc<String>,
     temperature: f64,
     auto_save_memory: bool,
+    memory_principal_isolation: bool,
+    memory_access: crate::config::MemoryAccessConfig,
+    memory_fact_extraction: bool,
+    usage_ledger: Option<Arc<crate::cost::UsageLedger>>,
     max_tool_iterations: usize,
     min_relevance_score: f64,
     conversation_histories: ConversationHistoryMap,
+    pinned_messages: PinnedMessageMap,
     provider_cache: ProviderCacheMap,
     route_overrides: RouteSelectionMap,
     api_key: Option<String>,
@@ -284,7 +324,19 @@ struct ChannelRuntimeContext {
     non_cli_excluded_tools: Arc<Mutex<Vec<String>>>,
     query_classification: crate::config::QueryClassificationConfig,
     model_routes: Vec<crate::config::ModelRouteConfig>,
+    content_injection: crate::config::ContentInjectionConfig,
+    tool_anomaly: Arc<crate::security::ToolAnomalyMonitor>,
+    session_store: Option<Arc<dyn session::SessionStore>>,
+    session_config: crate::config::SessionConfig,
+    leak_guard: crate::config::LeakGuardConfig,
     approval_manager: Arc<ApprovalManager>,
+    /// Persistent record of accepted messages, used to resume turns a
+    /// restart interrupted. `None` when `reliability.resume_unfinished_tasks`
+    /// is off or the queue could not be opened.
+    task_queue: Option<Arc<task_queue::ChannelTaskQueue>>,
+    /// Ingested documents searched on every turn for context injection.
+    /// `None` unless `[kb].enabled` is set.
+    knowledge_base: Option<Arc<crate::kb::KnowledgeBase>>,
 }
 
 #[derive(Clone)]
@@ -629,13 +681,14 @@ fn split_internal_progress_delta(delta: &str) -> (bool, &str) {
     }
 }
 
-fn build_channel_system_prompt(
-    base_prompt: &str,
+/// Channel section of the system prompt: delivery hints, execution
+/// visibility, and the reply target.
+fn build_channel_context_prompt(
     channel_name: &str,
     reply_target: &str,
     expose_internal_tool_details: bool,
 ) -> String {
-    let mut prompt = base_prompt.to_string();
+    let mut prompt = String::new();
 
     if let Some(instructions) = channel_delivery_instructions(channel_name) {
         if prompt.is_empty() {
@@ -734,6 +787,9 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
     match base_command.as_str() {
         // History reset commands are safe for all channels.
         "/new" | "/clear" => Some(ChannelRuntimeCommand::NewSession),
+        "/pin" => Some(ChannelRuntimeCommand::Pin(tail)),
+        "/unpin" => Some(ChannelRuntimeCommand::Unpin(tail)),
+        "/pins" => Some(ChannelRuntimeCommand::ListPins),
         "/approve-all-once" => Some(ChannelRuntimeCommand::RequestAllToolsOnce),
         "/approve-request" => Some(ChannelRuntimeCommand::RequestToolApproval(tail)),
         "/approve-confirm" => Some(ChannelRuntimeCommand::ConfirmToolApproval(tail)),
@@ -743,6 +799,27 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
         "/approve" => Some(ChannelRuntimeCommand::ApproveTool(tail)),
         "/unapprove" => Some(ChannelRuntimeCommand::UnapproveTool(tail)),
         "/approvals" => Some(ChannelRuntimeCommand::ListApprovals),
+        "/tools" => Some(ChannelRuntimeCommand::ListTools),
+        "/workflows" => Some(ChannelRuntimeCommand::ListWorkflows),
+        "/workflow" => Some(ChannelRuntimeCommand::RunWorkflow(tail)),
+        "/quarantine" => Some(ChannelRuntimeCommand::ListQuarantined),
+        "/home" => Some(ChannelRuntimeCommand::ShowHome),
+        "/budget" => Some(ChannelRuntimeCommand::ShowBudget),
+        "/budget-override" => Some(ChannelRuntimeCommand::OverrideBudget(tail)),
+        "/undo" => Some(ChannelRuntimeCommand::UndoLastTurn),
+        "/persona" => Some(ChannelRuntimeCommand::Persona(tail)),
+        "/quarantine-release" => Some(ChannelRuntimeCommand::ReviewQuarantined(
+            tail,
+            QuarantineDecision::ApproveRedacted,
+        )),
+        "/quarantine-release-original" => Some(ChannelRuntimeCommand::ReviewQuarantined(
+            tail,
+            QuarantineDecision::ApproveOriginal,
+        )),
+        "/quarantine-discard" => Some(ChannelRuntimeCommand::ReviewQuarantined(
+            tail,
+            QuarantineDecision::Discard,
+        )),
         // Provider/model switching remains limited to channels with session routing.
         "/models" if supports_runtime_model_switch(channel_name) => {
             if let Some(provider) = args.first() {
@@ -884,6 +961,9 @@ fn is_approval_management_command(command: &ChannelRuntimeCommand) -> bool {
             | ChannelRuntimeCommand::ApproveTool(_)
             | ChannelRuntimeCommand::UnapproveTool(_)
             | ChannelRuntimeCommand::ListApprovals
+            | ChannelRuntimeCommand::ListQuarantined
+            | ChannelRuntimeCommand::ReviewQuarantined(..)
+            | ChannelRuntimeCommand::OverrideBudget(_)
     )
 }
 
@@ -954,6 +1034,8 @@ fn runtime_autonomy_policy_from_config(config: &Config) -> RuntimeAutonomyPolicy
             .autonomy
             .non_cli_natural_language_approval_mode_by_channel
             .clone(),
+        level_overrides: config.autonomy.level_overrides.clone(),
+        approval_policy_by_channel: config.autonomy.approval_policy_by_channel.clone(),
         perplexity_filter: config.security.perplexity_filter.clone(),
     }
 }
@@ -1088,6 +1170,14 @@ async fn load_runtime_defaults_from_config_file(
     let contents = tokio::fs::read_to_string(path)
         .await
         .with_context(|| format!("Failed to read {}", path.display()))?;
+    // Keep config.local.toml, profile and env overlays in effect. Skill
+    // pack defaults may not set any of the values read here.
+    let (contents, _) = crate::config::layers::apply_layers(
+        path.parent().unwrap_or_else(|| Path::new(".")),
+        contents,
+        crate::config::layers::active_profile().as_deref(),
+        toml::Table::new(),
+    )?;
     let mut parsed: Config =
         toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))?;
     parsed.config_path = path.to_path_buf();
@@ -1301,6 +1391,15 @@ async fn describe_non_cli_approvals(
         );
     }
 
+    if let Some(policy) = ctx.approval_manager.channel_approval_policy(channel) {
+        let _ = writeln!(
+            response,
+            "- `{channel}` overrides: auto_approve [{}], always_ask [{}]",
+            policy.auto_approve.join(", "),
+            policy.always_ask.join(", ")
+        );
+    }
+
     let mut session_grants = ctx
         .approval_manager
         .non_cli_session_allowlist()
@@ -1522,6 +1621,10 @@ async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Resul
         next_autonomy_policy.non_cli_natural_language_approval_mode,
         &next_autonomy_policy.non_cli_natural_language_approval_mode_by_channel,
     );
+    ctx.approval_manager
+        .replace_autonomy_overrides(&next_autonomy_policy.level_overrides);
+    ctx.approval_manager
+        .replace_channel_approval_policies(&next_autonomy_policy.approval_policy_by_channel);
     {
         let mut excluded = ctx
             .non_cli_excluded_tools
@@ -1564,6 +1667,33 @@ fn get_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str) -> Channel
         .unwrap_or_else(|| default_route_selection(ctx))
 }
 
+/// Route for an unclassified message: the sender's `/model` choice, else
+/// the active persona's model, else the default route.
+fn persona_route_selection(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+    active_persona: Option<&persona::ActivePersona>,
+) -> ChannelRouteSelection {
+    if let Some(route) = ctx
+        .route_overrides
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(sender_key)
+    {
+        return route.clone();
+    }
+    let mut route = default_route_selection(ctx);
+    if let Some(persona) = active_persona {
+        if let Some(model) = &persona.config.model {
+            if let Some(provider) = &persona.config.provider {
+                route.provider.clone_from(provider);
+            }
+            route.model.clone_from(model);
+        }
+    }
+    route
+}
+
 /// Classify a user message and return the appropriate route selection with logging.
 /// Returns None if classification is disabled or no rules match.
 fn classify_message_route(
@@ -1591,6 +1721,149 @@ fn classify_message_route(
     })
 }
 
+/// Provider and model for background tasks (history summarization, fact
+/// extraction): the `summarize` model route when configured, otherwise the
+/// sender's active provider and model.
+async fn background_task_route(
+    ctx: &ChannelRuntimeContext,
+    active_provider: &Arc<dyn Provider>,
+    active_model: &str,
+) -> (Arc<dyn Provider>, String) {
+    let Some(route) = ctx
+        .model_routes
+        .iter()
+        .find(|r| r.hint == providers::router::SUMMARIZE_ROUTE_HINT)
+    else {
+        return (Arc::clone(active_provider), active_model.to_string());
+    };
+    match get_or_create_provider(ctx, &route.provider).await {
+        Ok(provider) => (provider, route.model.clone()),
+        Err(e) => {
+            tracing::warn!(
+                provider = %route.provider,
+                "Summarize route provider unavailable, using active provider: {e}"
+            );
+            (Arc::clone(active_provider), active_model.to_string())
+        }
+    }
+}
+
+/// Switch `route` to the `budget` model route once the sender has used
+/// `[cost].downgrade_at_percent` of a budget, so the conversation continues
+/// on a cheaper model instead of stopping at the limit. Returns the reply
+/// footer noting the downgrade.
+fn apply_budget_downgrade(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    route: &mut ChannelRouteSelection,
+) -> Option<String> {
+    let ledger = ctx.usage_ledger.as_ref()?;
+    let budget_route = ctx
+        .model_routes
+        .iter()
+        .find(|r| r.hint == providers::router::BUDGET_ROUTE_HINT)?;
+    if budget_route.provider == route.provider && budget_route.model == route.model {
+        return None;
+    }
+    let principal = format!("{}:{}", msg.channel, msg.sender);
+    let used_percent = match ledger.budget_pressure(&msg.channel, &principal) {
+        Ok(pressure) => pressure?,
+        Err(e) => {
+            tracing::warn!("Failed to check budget pressure: {e}");
+            return None;
+        }
+    };
+
+    runtime_trace::record_event(
+        "channel_route_budget_downgrade",
+        Some(msg.channel.as_str()),
+        Some(budget_route.provider.as_str()),
+        Some(budget_route.model.as_str()),
+        None,
+        Some(true),
+        None,
+        serde_json::json!({
+            "sender": msg.sender,
+            "used_percent": used_percent,
+            "from_provider": route.provider,
+            "from_model": route.model,
+        }),
+    );
+    *route = ChannelRouteSelection {
+        provider: budget_route.provider.clone(),
+        model: budget_route.model.clone(),
+    };
+    Some(crate::i18n::t_with(
+        &msg.channel,
+        "reply.budget_downgrade",
+        &[
+            ("model", &route.model),
+            ("percent", &used_percent.to_string()),
+        ],
+    ))
+}
+
+/// Apply `[security.moderation]` to a final reply and record every finding
+/// in the runtime trace.
+async fn moderate_channel_reply(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    reply: String,
+) -> String {
+    let Some(moderator) = crate::security::moderation::moderator() else {
+        return reply;
+    };
+    let mut classifier = None;
+    if let Some(route) = moderator
+        .classifier_hint()
+        .and_then(|hint| ctx.model_routes.iter().find(|r| r.hint == hint))
+    {
+        match get_or_create_provider(ctx, &route.provider).await {
+            Ok(provider) => classifier = Some((provider, route.model.clone())),
+            Err(e) => tracing::warn!(
+                provider = %route.provider,
+                "Moderation classifier provider unavailable: {e}"
+            ),
+        }
+    }
+    let outcome = moderator
+        .moderate(
+            &reply,
+            classifier
+                .as_ref()
+                .map(|(provider, model)| (provider.as_ref(), model.as_str())),
+        )
+        .await;
+    let Some(action) = outcome.action() else {
+        return reply;
+    };
+    tracing::info!(
+        channel = %msg.channel,
+        sender = %msg.sender,
+        ?action,
+        "outgoing message flagged by moderation"
+    );
+    if outcome.modified() {
+        turn::record(|report| report.redactions.push("moderation".to_string()));
+    }
+    runtime_trace::record_event(
+        "channel_message_moderated",
+        Some(msg.channel.as_str()),
+        None,
+        None,
+        None,
+        Some(!outcome.modified()),
+        Some("outbound reply flagged by moderation"),
+        serde_json::json!({
+            "sender": msg.sender,
+            "action": action,
+            "findings": outcome.findings,
+            "original": scrub_credentials(&reply),
+        }),
+    );
+    outcome.text
+}
+
 fn set_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str, next: ChannelRouteSelection) {
     let default_route = default_route_selection(ctx);
     let mut routes = ctx
@@ -1651,13 +1924,103 @@ fn append_sender_turn(ctx: &ChannelRuntimeContext, sender_key: &str, turn: ChatM
         .conversation_histories
         .lock()
         .unwrap_or_else(|e| e.into_inner());
+    let cap = sender_history_cap(&ctx.session_config);
     let turns = histories.entry(sender_key.to_string()).or_default();
     turns.push(turn);
-    while turns.len() > MAX_CHANNEL_HISTORY {
+    while turns.len() > cap {
         turns.remove(0);
     }
 }
 
+/// Hard per-sender history cap. With summarization, history may grow one
+/// batch past `max_messages` so the summarizer gets a chance to run before
+/// anything is dropped.
+fn sender_history_cap(config: &crate::config::SessionConfig) -> usize {
+    match config.history_strategy {
+        crate::config::SessionHistoryStrategy::Truncate => config.max_messages,
+        crate::config::SessionHistoryStrategy::Summarize => {
+            config.max_messages.saturating_add(config.summarize_batch)
+        }
+    }
+}
+
+/// Fold the oldest `summarize_batch` turns into one summary once history
+/// exceeds `max_messages`. The summary is stored as a user turn because
+/// channel history must start with a user message; it merges with the next
+/// user turn when history is normalized. On failure the history is left
+/// as-is and the hard cap in [`append_sender_turn`] still applies.
+async fn summarize_sender_history(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+    provider: &dyn Provider,
+    model: &str,
+) -> bool {
+    let config = &ctx.session_config;
+    if config.history_strategy != crate::config::SessionHistoryStrategy::Summarize {
+        return false;
+    }
+
+    let snapshot = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(sender_key)
+        .cloned()
+        .unwrap_or_default();
+    if snapshot.len() <= config.max_messages {
+        return false;
+    }
+
+    // End the batch right before a user turn so the summary never splits a
+    // user/assistant exchange.
+    let mut batch_end = config.summarize_batch.min(snapshot.len() - 1);
+    while batch_end < snapshot.len() && snapshot[batch_end].role != "user" {
+        batch_end += 1;
+    }
+    if batch_end >= snapshot.len() {
+        return false;
+    }
+
+    let summary = match crate::agent::loop_::summarize_messages(
+        provider,
+        model,
+        &snapshot[..batch_end],
+    )
+    .await
+    {
+        Ok(summary) => summary,
+        Err(err) => {
+            tracing::warn!("Channel history summarization failed: {err}");
+            return false;
+        }
+    };
+
+    let mut histories = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let Some(turns) = histories.get_mut(sender_key) else {
+        return false;
+    };
+    // Another turn may have rewritten history while the summarizer ran.
+    let unchanged = turns.len() >= batch_end
+        && turns[..batch_end]
+            .iter()
+            .zip(&snapshot[..batch_end])
+            .all(|(a, b)| a.role == b.role && a.content == b.content);
+    if !unchanged {
+        return false;
+    }
+    turns.splice(
+        ..batch_end,
+        std::iter::once(ChatMessage::user(format!(
+            "{CHANNEL_HISTORY_SUMMARY_PREFIX}\n{}",
+            summary.trim()
+        ))),
+    );
+    true
+}
+
 fn rollback_orphan_user_turn(
     ctx: &ChannelRuntimeContext,
     sender_key: &str,
@@ -1685,6 +2048,248 @@ fn rollback_orphan_user_turn(
     true
 }
 
+fn sender_pins(ctx: &ChannelRuntimeContext, sender_key: &str) -> Vec<String> {
+    ctx.pinned_messages
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(sender_key)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Strip the `[<timestamp>] ` prefix added to stored user turns.
+fn strip_turn_timestamp(content: &str) -> &str {
+    content
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once("] "))
+        .map_or(content, |(_, text)| text)
+}
+
+/// Pin `text`, or the sender's previous user message when `text` is empty.
+fn pin_sender_message(ctx: &ChannelRuntimeContext, sender_key: &str, text: &str) -> String {
+    let max_pinned = ctx.session_config.max_pinned;
+    if max_pinned == 0 {
+        return "Pinning is disabled (`session.max_pinned = 0`).".to_string();
+    }
+
+    let text = if text.trim().is_empty() {
+        let previous = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(sender_key)
+            .and_then(|turns| turns.iter().rev().find(|turn| turn.role == "user"))
+            .map(|turn| strip_turn_timestamp(&turn.content).trim().to_string());
+        match previous {
+            Some(previous) if !previous.is_empty() => previous,
+            _ => return "Nothing to pin yet. Usage: `/pin <text>`".to_string(),
+        }
+    } else {
+        text.trim().to_string()
+    };
+
+    let mut pinned = ctx
+        .pinned_messages
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let pins = pinned.entry(sender_key.to_string()).or_default();
+    if pins.contains(&text) {
+        return "That message is already pinned.".to_string();
+    }
+    if pins.len() >= max_pinned {
+        return format!(
+            "Pin limit reached ({max_pinned}). Remove one with `/unpin <number>` first."
+        );
+    }
+    pins.push(text);
+    format!(
+        "Pinned ({}/{max_pinned}). It will stay in context until unpinned.",
+        pins.len()
+    )
+}
+
+/// Remove pin number `selector` (1-based), or every pin for `all`.
+fn unpin_sender_message(ctx: &ChannelRuntimeContext, sender_key: &str, selector: &str) -> String {
+    let mut pinned = ctx
+        .pinned_messages
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let Some(pins) = pinned.get_mut(sender_key).filter(|pins| !pins.is_empty()) else {
+        return "No pinned messages.".to_string();
+    };
+
+    let selector = selector.trim();
+    if selector.eq_ignore_ascii_case("all") {
+        let removed = pins.len();
+        pinned.remove(sender_key);
+        return format!("Removed {removed} pinned message(s).");
+    }
+    match selector.parse::<usize>() {
+        Ok(index) if (1..=pins.len()).contains(&index) => {
+            pins.remove(index - 1);
+            if pins.is_empty() {
+                pinned.remove(sender_key);
+            }
+            format!("Unpinned message {index}.")
+        }
+        _ => "Usage: `/unpin <number>` (see `/pins`) or `/unpin all`".to_string(),
+    }
+}
+
+/// System prompt section carrying the sender's pinned messages.
+fn build_pinned_context_prompt(pins: &[String]) -> String {
+    if pins.is_empty() {
+        return String::new();
+    }
+    let mut prompt = String::from(
+        "\n\n## Pinned Context\n\nThe user pinned these messages. Treat them as standing instructions and facts for the whole conversation:\n",
+    );
+    for (i, pin) in pins.iter().enumerate() {
+        let _ = write!(prompt, "\n{}. {pin}", i + 1);
+    }
+    prompt
+}
+
+/// Load the sender's pins from the session store, if one is configured.
+async fn hydrate_sender_pins(ctx: &ChannelRuntimeContext, sender_key: &str) {
+    let Some(store) = ctx.session_store.as_ref() else {
+        return;
+    };
+    match store.load(&session::pins_key(sender_key)).await {
+        Ok(stored) => {
+            let pins: Vec<String> = stored
+                .unwrap_or_default()
+                .into_iter()
+                .map(|turn| turn.content)
+                .take(ctx.session_config.max_pinned)
+                .collect();
+            let mut pinned = ctx
+                .pinned_messages
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if pins.is_empty() {
+                pinned.remove(sender_key);
+            } else {
+                pinned.insert(sender_key.to_string(), pins);
+            }
+        }
+        Err(err) => tracing::warn!(
+            store = store.name(),
+            "Failed to load pinned messages, using local copy: {err}"
+        ),
+    }
+}
+
+/// Write the sender's pins back to the session store.
+async fn persist_sender_pins(ctx: &ChannelRuntimeContext, sender_key: &str) {
+    let Some(store) = ctx.session_store.as_ref() else {
+        return;
+    };
+    let key = session::pins_key(sender_key);
+    let pins: Vec<ChatMessage> = sender_pins(ctx, sender_key)
+        .into_iter()
+        .map(ChatMessage::user)
+        .collect();
+    let result = if pins.is_empty() {
+        store.remove(&key).await
+    } else {
+        store.save(&key, &pins).await
+    };
+    if let Err(err) = result {
+        tracing::warn!(
+            store = store.name(),
+            "Failed to persist pinned messages: {err}"
+        );
+    }
+}
+
+/// Replace the local history for `sender_key` with the shared session store's
+/// copy, so a turn handled by another instance is visible here.
+async fn hydrate_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) {
+    let Some(store) = ctx.session_store.as_ref() else {
+        return;
+    };
+    match store.load(sender_key).await {
+        Ok(stored) => {
+            let mut histories = ctx
+                .conversation_histories
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            match stored {
+                Some(mut turns) if !turns.is_empty() => {
+                    let excess = turns
+                        .len()
+                        .saturating_sub(sender_history_cap(&ctx.session_config));
+                    turns.drain(..excess);
+                    histories.insert(sender_key.to_string(), turns);
+                }
+                _ => {
+                    histories.remove(sender_key);
+                }
+            }
+        }
+        Err(err) => tracing::warn!(
+            store = store.name(),
+            "Failed to load session history, using local copy: {err}"
+        ),
+    }
+}
+
+/// Write the local history for `sender_key` back to the shared session store.
+async fn persist_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) {
+    let Some(store) = ctx.session_store.as_ref() else {
+        return;
+    };
+    let turns = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(sender_key)
+        .cloned()
+        .unwrap_or_default();
+    let result = if turns.is_empty() {
+        match store.remove(sender_key).await {
+            Ok(()) => store.remove(&session::metadata_key(sender_key)).await,
+            Err(err) => Err(err),
+        }
+    } else {
+        store.save(sender_key, &turns).await
+    };
+    if let Err(err) = result {
+        tracing::warn!(
+            store = store.name(),
+            "Failed to persist session history: {err}"
+        );
+    }
+}
+
+/// Record the session's channel, principal and title (from its first
+/// message) in the session store.
+async fn record_session_metadata(
+    ctx: &ChannelRuntimeContext,
+    history_key: &str,
+    msg: &traits::ChannelMessage,
+) {
+    let Some(store) = ctx.session_store.as_ref() else {
+        return;
+    };
+    let refresh_ttl = ctx.session_config.ttl_secs > 0;
+    let result = session::touch_metadata(store.as_ref(), history_key, refresh_ttl, || {
+        session::SessionMetadata::new(
+            &msg.channel,
+            Some(format!("{}:{}", msg.channel, msg.sender)),
+            &msg.content,
+        )
+    })
+    .await;
+    if let Err(err) = result {
+        tracing::warn!(
+            store = store.name(),
+            "Failed to record session metadata: {err}"
+        );
+    }
+}
+
 fn should_skip_memory_context_entry(key: &str, content: &str) -> bool {
     if memory::is_assistant_autosave_key(key) {
         return true;
@@ -2063,7 +2668,42 @@ async fn handle_runtime_command_if_needed(
         }
         ChannelRuntimeCommand::NewSession => {
             clear_sender_history(ctx, &sender_key);
-            "Conversation history cleared. Starting fresh.".to_string()
+            if sender_pins(ctx, &sender_key).is_empty() {
+                "Conversation history cleared. Starting fresh.".to_string()
+            } else {
+                "Conversation history cleared. Starting fresh. Pinned messages were kept (`/unpin all` to remove them).".to_string()
+            }
+        }
+        ChannelRuntimeCommand::Pin(text) => {
+            let response = pin_sender_message(ctx, &sender_key, &text);
+            persist_sender_pins(ctx, &sender_key).await;
+            response
+        }
+        ChannelRuntimeCommand::Unpin(selector) => {
+            let response = unpin_sender_message(ctx, &sender_key, &selector);
+            persist_sender_pins(ctx, &sender_key).await;
+            response
+        }
+        ChannelRuntimeCommand::ListPins => {
+            let pins = sender_pins(ctx, &sender_key);
+            if pins.is_empty() {
+                "No pinned messages. Use `/pin <text>`, or `/pin` alone to pin your previous message.".to_string()
+            } else {
+                let mut response = format!(
+                    "Pinned messages ({}/{}):",
+                    pins.len(),
+                    ctx.session_config.max_pinned
+                );
+                for (i, pin) in pins.iter().enumerate() {
+                    let _ = write!(
+                        response,
+                        "\n{}. {}",
+                        i + 1,
+                        truncate_with_ellipsis(pin, 200)
+                    );
+                }
+                response
+            }
         }
         ChannelRuntimeCommand::RequestAllToolsOnce => {
             let req = ctx.approval_manager.create_non_cli_pending_request(
@@ -2117,7 +2757,10 @@ async fn handle_runtime_command_if_needed(
                 format!(
                     "Unknown tool `{tool_name}`.\nKnown tools (top 12): {preview}\nUse `/approve-request <tool-name>` with an exact tool name."
                 )
-            } else if !ctx.approval_manager.needs_approval(&tool_name) {
+            } else if !ctx
+                .approval_manager
+                .needs_approval(&tool_name, &msg.channel)
+            {
                 format!(
                     "`{tool_name}` is already approved in the current runtime policy. You can use it directly."
                 )
@@ -2391,29 +3034,568 @@ async fn handle_runtime_command_if_needed(
                 Err(err) => format!("Failed to read approval state: {err}"),
             }
         }
-    };
-
-    if let Err(err) = channel
-        .send(&SendMessage::new(response, &msg.reply_target).in_thread(msg.thread_ts.clone()))
-        .await
-    {
-        tracing::warn!(
-            "Failed to send runtime command response on {}: {err}",
-            channel.name()
+        ChannelRuntimeCommand::ListTools => {
+            let excluded = snapshot_non_cli_excluded_tools(ctx);
+            crate::tools::registry::render_for_chat(&crate::tools::registry::describe(
+                ctx.tools_registry.as_ref(),
+                ctx.approval_manager.as_ref(),
+                &excluded,
+                &msg.channel,
+            ))
+        }
+        ChannelRuntimeCommand::ListWorkflows => {
+            let workflows = crate::workflows::load_workflows(ctx.workspace_dir.as_path());
+            if workflows.is_empty() {
+                "No workflows defined. Add TOML files under `workflows/` in the workspace."
+                    .to_string()
+            } else {
+                let mut response = String::from("Workflows (run with `/workflow <name>`):");
+                for workflow in workflows {
+                    let _ = write!(response, "\n- `{}`", workflow.name);
+                    if let Some(description) = workflow.description.as_deref() {
+                        let _ = write!(response, " — {description}");
+                    }
+                }
+                response
+            }
+        }
+        ChannelRuntimeCommand::RunWorkflow(name) => run_workflow_command(ctx, &name).await,
+        ChannelRuntimeCommand::ListQuarantined => list_quarantined_command(),
+        ChannelRuntimeCommand::ReviewQuarantined(id, decision) => {
+            review_quarantined_command(ctx, msg, &id, decision).await
+        }
+        ChannelRuntimeCommand::ShowHome => {
+            if channel.supports_home_view() {
+                publish_home_view(ctx, channel, sender).await;
+                return true;
+            }
+            "This channel has no home view.".to_string()
+        }
+        ChannelRuntimeCommand::ShowBudget => show_budget_command(ctx, msg),
+        ChannelRuntimeCommand::OverrideBudget(scope) => override_budget_command(ctx, msg, &scope),
+        ChannelRuntimeCommand::UndoLastTurn => undo_last_turn_command(ctx, msg).await,
+        ChannelRuntimeCommand::Persona(arg) => persona::command(source_channel, &sender_key, &arg),
+    };
+
+    persist_sender_history(ctx, &sender_key).await;
+
+    if let Err(err) = channel
+        .send(&SendMessage::new(response, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+        .await
+    {
+        tracing::warn!(
+            "Failed to send runtime command response on {}: {err}",
+            channel.name()
         );
     }
+    publish_home_view(ctx, channel, sender).await;
+
+    true
+}
+
+/// Latest messages of a sender shown on the home view.
+const HOME_RECENT_TURNS: usize = 5;
+
+/// Republish `sender`'s home view on channels that have one (Slack App
+/// Home): daemon status, their recent messages and pending approvals.
+async fn publish_home_view(ctx: &ChannelRuntimeContext, channel: &Arc<dyn Channel>, sender: &str) {
+    if !channel.supports_home_view() {
+        return;
+    }
+    let health = crate::health::snapshot();
+    let history_key = format!("{}_{sender}", channel.name());
+    let recent_turns = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&history_key)
+        .map(|turns| {
+            turns
+                .iter()
+                .rev()
+                .filter(|turn| turn.role == "user")
+                .take(HOME_RECENT_TURNS)
+                .map(|turn| truncate_with_ellipsis(turn.content.trim(), 120))
+                .collect()
+        })
+        .unwrap_or_default();
+    let pending_approvals = ctx
+        .approval_manager
+        .list_non_cli_pending_requests(Some(sender), Some(channel.name()), None)
+        .into_iter()
+        .map(|req| traits::HomeApproval {
+            request_id: req.request_id,
+            tool_name: approval_target_label(&req.tool_name),
+            expires_at: req.expires_at,
+        })
+        .collect();
+    let view = traits::HomeView {
+        uptime_secs: health.uptime_seconds,
+        components: health
+            .components
+            .into_iter()
+            .map(|(name, component)| (name, component.status))
+            .collect(),
+        recent_turns,
+        pending_approvals,
+    };
+    if let Err(err) = channel.publish_home_view(sender, &view).await {
+        tracing::warn!(channel = %channel.name(), "Failed to publish home view: {err}");
+    }
+}
+
+/// `/undo`: roll back the file and memory changes of the sender's last turn.
+async fn undo_last_turn_command(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+) -> String {
+    let Some(journal) = crate::tools::undo::journal() else {
+        return "Undo is off. Set `[agent] undo_journal = true`.".to_string();
+    };
+    let principal = format!("{}:{}", msg.channel, msg.sender);
+    let turn_id = match journal.last_turn(Some(&principal)) {
+        Ok(Some(turn_id)) => turn_id,
+        Ok(None) => return "Nothing to undo.".to_string(),
+        Err(e) => return format!("Failed to read the undo journal: {e}"),
+    };
+    let report = match journal.undo_turn(&turn_id, Some(ctx.memory.as_ref())).await {
+        Ok(report) => report,
+        Err(e) => return format!("Undo failed: {e}"),
+    };
+    runtime_trace::record_event(
+        "channel_turn_undone",
+        Some(msg.channel.as_str()),
+        None,
+        None,
+        Some(&turn_id),
+        Some(true),
+        None,
+        serde_json::json!({ "sender": msg.sender, "actions": report }),
+    );
+    let mut response = String::from("Undid your last turn:");
+    for line in report {
+        let _ = write!(response, "\n- {line}");
+    }
+    response
+}
+
+/// `/budget`: the sender's usage today against their daily budgets.
+fn show_budget_command(ctx: &ChannelRuntimeContext, msg: &traits::ChannelMessage) -> String {
+    let Some(ledger) = ctx.usage_ledger.as_ref() else {
+        return "Usage budgets need cost tracking. Set `[cost] enabled = true`.".to_string();
+    };
+    let principal = format!("{}:{}", msg.channel, msg.sender);
+    let usage = match ledger.daily_usage(&msg.channel, Some(&principal)) {
+        Ok(usage) => usage,
+        Err(e) => return format!("Failed to read usage: {e}"),
+    };
+    let budget = ledger.principal_daily_budget(&principal);
+    let limit = |value: u64| {
+        if value == 0 {
+            "unlimited".to_string()
+        } else {
+            value.to_string()
+        }
+    };
+    let mut response = format!("Usage today (UTC) for `{principal}`:");
+    let _ = write!(
+        response,
+        "\n- turns: {} of {}\n- tool calls: {} of {}\n- tokens: {} of {}",
+        usage.turns,
+        limit(budget.max_turns),
+        usage.tool_calls,
+        limit(budget.max_tool_calls),
+        usage.tokens,
+        limit(budget.max_tokens)
+    );
+    match ledger.check_daily_budget(&msg.channel, &principal) {
+        Ok(Some(exhausted)) => {
+            let _ = write!(
+                response,
+                "\nBudget of `{}` is used up ({}: {} of {}).",
+                exhausted.scope, exhausted.metric, exhausted.used, exhausted.limit
+            );
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to check daily budget: {e}"),
+    }
+    response
+}
+
+/// `/budget-override <channel[:sender]>`: lift a daily budget until the end
+/// of the UTC day.
+fn override_budget_command(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    scope: &str,
+) -> String {
+    let Some(ledger) = ctx.usage_ledger.as_ref() else {
+        return "Usage budgets need cost tracking. Set `[cost] enabled = true`.".to_string();
+    };
+    let scope = scope.trim();
+    if scope.is_empty() {
+        return "Usage: `/budget-override <channel>` or `/budget-override <channel>:<sender>`."
+            .to_string();
+    }
+    if let Err(e) = ledger.override_daily_budget(scope, &msg.sender) {
+        return format!("Failed to override budget: {e}");
+    }
+    runtime_trace::record_event(
+        "channel_budget_overridden",
+        Some(msg.channel.as_str()),
+        None,
+        None,
+        None,
+        Some(true),
+        None,
+        serde_json::json!({
+            "scope": scope,
+            "granted_by": msg.sender,
+        }),
+    );
+    format!("Daily budget of `{scope}` lifted until 00:00 UTC.")
+}
+
+/// Resolve plan commands. `/plan <request>` turns into a planning turn for
+/// the request and `/plan-approve` into a turn running the sender's pending
+/// plan; `None` means the message was fully handled.
+async fn resolve_plan_turn(
+    ctx: &ChannelRuntimeContext,
+    mut msg: traits::ChannelMessage,
+    target_channel: Option<&Arc<dyn Channel>>,
+) -> Option<(traits::ChannelMessage, plan::PlanTurn)> {
+    let Some(command) = plan::PlanCommand::parse(&msg.content) else {
+        let turn = if plan::plan_mode() && msg.channel != "cli" {
+            plan::PlanTurn::plan()
+        } else {
+            plan::PlanTurn::Direct
+        };
+        return Some((msg, turn));
+    };
+
+    let history_key = conversation_history_key(&msg);
+    let reply = match command {
+        plan::PlanCommand::Plan(request) if !request.is_empty() => {
+            msg.content = request;
+            return Some((msg, plan::PlanTurn::plan()));
+        }
+        plan::PlanCommand::Plan(_) => {
+            "Usage: `/plan <request>` — get a checklist of the tool calls I would make, without running any.".to_string()
+        }
+        plan::PlanCommand::Approve
+            if !ctx
+                .approval_manager
+                .is_non_cli_approval_actor_allowed(&msg.channel, &msg.sender) =>
+        {
+            format!(
+                "Approving plans is limited to `[autonomy].non_cli_approval_approvers`; sender `{}` is not one of them.",
+                msg.sender
+            )
+        }
+        plan::PlanCommand::Approve => match plan::take(&history_key) {
+            Some(pending) => {
+                runtime_trace::record_event(
+                    "channel_plan_approved",
+                    Some(msg.channel.as_str()),
+                    None,
+                    None,
+                    None,
+                    Some(true),
+                    None,
+                    serde_json::json!({
+                        "sender": msg.sender,
+                        "steps": pending.steps,
+                    }),
+                );
+                msg.content = pending.request.clone();
+                return Some((msg, plan::PlanTurn::Execute(pending)));
+            }
+            None => "There is no pending plan. Use `/plan <request>` to make one.".to_string(),
+        },
+        plan::PlanCommand::Discard => match plan::take(&history_key) {
+            Some(_) => "Plan discarded.".to_string(),
+            None => "There is no pending plan.".to_string(),
+        },
+    };
+    if let Some(channel) = target_channel {
+        let _ = channel
+            .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+            .await;
+    }
+    None
+}
+
+/// Reply and return `true` when the sender or their channel has used up a
+/// daily budget; otherwise count the turn against it.
+async fn daily_budget_exhausted(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    target_channel: Option<&Arc<dyn Channel>>,
+) -> bool {
+    let Some(ledger) = ctx.usage_ledger.as_ref() else {
+        return false;
+    };
+    let principal = format!("{}:{}", msg.channel, msg.sender);
+    let exhausted = match ledger.check_daily_budget(&msg.channel, &principal) {
+        Ok(exhausted) => exhausted,
+        Err(e) => {
+            tracing::warn!("Failed to check daily budget: {e}");
+            None
+        }
+    };
+    let Some(exhausted) = exhausted else {
+        if let Err(e) = ledger.record_turn(&msg.channel, &principal) {
+            tracing::warn!("Failed to record turn usage: {e}");
+        }
+        return false;
+    };
 
+    runtime_trace::record_event(
+        "channel_message_budget_exhausted",
+        Some(msg.channel.as_str()),
+        None,
+        None,
+        None,
+        Some(false),
+        Some("daily usage budget exhausted"),
+        serde_json::json!({
+            "sender": msg.sender,
+            "message_id": msg.id,
+            "scope": exhausted.scope,
+            "metric": exhausted.metric,
+            "used": exhausted.used,
+            "limit": exhausted.limit,
+        }),
+    );
+    if let Some(channel) = target_channel {
+        let reply = crate::i18n::t_with(
+            &msg.channel,
+            "reply.budget_exhausted",
+            &[
+                ("scope", &exhausted.scope),
+                ("metric", exhausted.metric),
+                ("used", &exhausted.used.to_string()),
+                ("limit", &exhausted.limit.to_string()),
+            ],
+        );
+        let _ = channel
+            .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+            .await;
+    }
     true
 }
 
+/// `/quarantine`: replies held by the leak guard.
+fn list_quarantined_command() -> String {
+    let Some(queue) = crate::approval::quarantine::queue() else {
+        return "Reply quarantine is disabled. Enable `[security.leak_guard].quarantine` to hold replies with detected credentials for review.".to_string();
+    };
+    let rows = queue.list();
+    if rows.is_empty() {
+        return "No replies are waiting for leak review.".to_string();
+    }
+    let mut response = String::from("Replies held for leak review:");
+    for held in rows {
+        let _ = write!(
+            response,
+            "\n- {}: channel={}, requested_by={}, patterns={}, expires_at={}\n  {}",
+            held.id,
+            held.channel,
+            held.requested_by,
+            held.patterns.join(", "),
+            held.expires_at.to_rfc3339(),
+            truncate_with_ellipsis(&held.redacted, 200)
+        );
+    }
+    response
+}
+
+/// `/quarantine-release[-original] <id>` and `/quarantine-discard <id>`.
+/// Releasing the unredacted original requires an explicitly configured
+/// approver; with an empty `non_cli_approval_approvers` list anyone who may
+/// chat could otherwise release the credential they asked for.
+async fn review_quarantined_command(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    id: &str,
+    decision: QuarantineDecision,
+) -> String {
+    let Some(queue) = crate::approval::quarantine::queue() else {
+        return "Reply quarantine is disabled.".to_string();
+    };
+    let id = id.trim();
+    if id.is_empty() {
+        return "Usage: `/quarantine-release <id>`, `/quarantine-release-original <id>` or `/quarantine-discard <id>`. Use `/quarantine` to list held replies.".to_string();
+    }
+    if decision == QuarantineDecision::ApproveOriginal
+        && ctx.approval_manager.non_cli_approval_approvers().is_empty()
+    {
+        return "Releasing an unredacted reply requires `[autonomy].non_cli_approval_approvers` to name the approvers allowed to do it.".to_string();
+    }
+
+    let held = match queue.take(id) {
+        Ok(held) => held,
+        Err(PendingApprovalError::Expired) => {
+            return format!("Quarantined reply `{id}` has expired and was discarded.")
+        }
+        Err(_) => return format!("Quarantined reply `{id}` was not found."),
+    };
+    runtime_trace::record_event(
+        "channel_message_quarantine_reviewed",
+        Some(msg.channel.as_str()),
+        None,
+        None,
+        None,
+        Some(true),
+        Some(decision.label()),
+        serde_json::json!({
+            "quarantine_id": held.id,
+            "reviewer": msg.sender,
+            "requested_by": held.requested_by,
+            "decision": decision.label(),
+        }),
+    );
+
+    let Some(text) = held.release_text(decision) else {
+        return format!("Discarded quarantined reply `{id}`.");
+    };
+    let Some(channel) = ctx.channels_by_name.get(&held.channel).cloned() else {
+        return format!(
+            "Channel `{}` is no longer running; quarantined reply `{id}` could not be delivered.",
+            held.channel
+        );
+    };
+    let rendered = render::render_for_channel(channel.name(), text);
+    match outbox::deliver(
+        channel.as_ref(),
+        &SendMessage::new(rendered, &held.reply_target).in_thread(held.thread_ts.clone()),
+    )
+    .await
+    {
+        Ok(()) => format!(
+            "Released quarantined reply `{id}` ({}) to `{}` on {}.",
+            if decision == QuarantineDecision::ApproveOriginal {
+                "original"
+            } else {
+                "redacted"
+            },
+            held.requested_by,
+            held.channel
+        ),
+        Err(e) => format!("Failed to deliver quarantined reply `{id}`: {e}"),
+    }
+}
+
+/// `/workflow <name>`: runs through the `workflow_run` tool so the same
+/// exclusion and approval policy applies as when the agent calls it.
+async fn run_workflow_command(ctx: &ChannelRuntimeContext, name: &str) -> String {
+    const TOOL: &str = "workflow_run";
+    let name = name.trim();
+    if name.is_empty() {
+        return "Usage: `/workflow <name>`. Use `/workflows` to list workflows.".to_string();
+    }
+    let excluded = ctx
+        .non_cli_excluded_tools
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .any(|tool| tool == TOOL);
+    let Some(tool) = ctx
+        .tools_registry
+        .iter()
+        .find(|tool| tool.name() == TOOL)
+        .filter(|_| !excluded)
+    else {
+        return format!("`{TOOL}` is not available on this channel.");
+    };
+    if ctx.approval_manager.needs_approval(TOOL, &msg.channel) {
+        return format!(
+            "Running workflows from chat needs approval. Use `/approve {TOOL}` to allow it."
+        );
+    }
+
+    match tool.execute(serde_json::json!({ "name": name })).await {
+        Ok(result) if result.success => {
+            if result.output.trim().is_empty() {
+                format!("Workflow `{name}` finished.")
+            } else {
+                result.output
+            }
+        }
+        Ok(result) => result
+            .error
+            .unwrap_or_else(|| format!("Workflow `{name}` failed.")),
+        Err(err) => format!("Workflow `{name}` failed: {err}"),
+    }
+}
+
+/// Extract durable facts from the finished exchange in the background so
+/// the extra provider call never delays the reply.
+fn spawn_fact_extraction(
+    ctx: &ChannelRuntimeContext,
+    provider: Arc<dyn Provider>,
+    model: &str,
+    msg: &traits::ChannelMessage,
+    reply: &str,
+    memory_principal: Option<principal::MemoryPrincipal>,
+) {
+    let memory = Arc::clone(&ctx.memory);
+    let model = model.to_string();
+    let user_message = msg.content.clone();
+    let reply = reply.to_string();
+    let provenance = memory::extraction::FactProvenance {
+        channel: msg.channel.clone(),
+        sender: msg.sender.clone(),
+        timestamp: i64::try_from(msg.timestamp)
+            .ok()
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            .unwrap_or_else(chrono::Utc::now)
+            .to_rfc3339(),
+    };
+    tokio::spawn(async move {
+        let facts = match memory::extraction::extract_facts(
+            provider.as_ref(),
+            &model,
+            &user_message,
+            &reply,
+        )
+        .await
+        {
+            Ok(facts) if facts.is_empty() => return,
+            Ok(facts) => facts,
+            Err(err) => {
+                tracing::debug!(channel = %provenance.channel, "Fact extraction failed: {err}");
+                return;
+            }
+        };
+        match memory::extraction::store_facts(
+            memory.as_ref(),
+            &facts,
+            &provenance,
+            memory_principal.as_ref(),
+        )
+        .await
+        {
+            Ok(stored) => tracing::debug!(
+                channel = %provenance.channel,
+                extracted = facts.len(),
+                stored,
+                "Stored extracted facts"
+            ),
+            Err(err) => tracing::warn!("Failed to store extracted facts: {err}"),
+        }
+    });
+}
+
 async fn build_memory_context(
     mem: &dyn Memory,
     user_msg: &str,
     min_relevance_score: f64,
+    memory_principal: Option<&principal::MemoryPrincipal>,
 ) -> String {
     let mut context = String::new();
 
-    if let Ok(entries) = mem.recall(user_msg, 5, None).await {
+    if let Ok(entries) = principal::recall_for_principal(mem, user_msg, 5, memory_principal).await {
         let mut included = 0usize;
         let mut used_chars = 0usize;
 
@@ -2553,21 +3735,33 @@ fn extract_tool_context_summary(history: &[ChatMessage], start_index: usize) ->
 }
 
 pub(crate) fn sanitize_channel_response(response: &str, tools: &[Box<dyn Tool>]) -> String {
+    sanitize_channel_response_checked(response, tools).0
+}
+
+/// Strip tool-call tags and stray tool JSON from a reply, without the leak
+/// guard.
+fn strip_channel_response_artifacts(response: &str, tools: &[Box<dyn Tool>]) -> String {
     let without_tool_tags = strip_tool_call_tags(response);
     let known_tool_names: HashSet<String> = tools
         .iter()
         .map(|tool| tool.name().to_ascii_lowercase())
         .collect();
-    let sanitized = strip_isolated_tool_json_artifacts(&without_tool_tags, &known_tool_names);
+    strip_isolated_tool_json_artifacts(&without_tool_tags, &known_tool_names)
+}
+
+/// [`sanitize_channel_response`], also reporting whether the leak guard
+/// redacted anything.
+fn sanitize_channel_response_checked(response: &str, tools: &[Box<dyn Tool>]) -> (String, bool) {
+    let sanitized = strip_channel_response_artifacts(response, tools);
 
     match LeakDetector::new().scan(&sanitized) {
-        LeakResult::Clean => sanitized,
+        LeakResult::Clean => (sanitized, false),
         LeakResult::Detected { patterns, redacted } => {
             tracing::warn!(
                 patterns = ?patterns,
                 "output guardrail: credential leak detected in outbound channel response"
             );
-            redacted
+            (redacted, true)
         }
     }
 }
@@ -2877,6 +4071,10 @@ async fn process_channel_message(
             "content_preview": truncate_with_ellipsis(&msg.content, 160),
         }),
     );
+    ctx.observer.record_event(&ObserverEvent::ChannelMessage {
+        channel: msg.channel.clone(),
+        direction: "inbound".to_string(),
+    });
 
     // ── Hook: on_message_received (modifying) ────────────
     let msg = if let Some(hooks) = &ctx.hooks {
@@ -2898,6 +4096,11 @@ async fn process_channel_message(
     if handle_runtime_command_if_needed(ctx.as_ref(), &msg, target_channel.as_ref()).await {
         return;
     }
+    let Some((msg, plan_turn)) =
+        resolve_plan_turn(ctx.as_ref(), msg, target_channel.as_ref()).await
+    else {
+        return;
+    };
     if !msg.content.trim_start().starts_with('/') {
         let perplexity_cfg = runtime_perplexity_filter_snapshot(ctx.as_ref());
         if let Some(assessment) =
@@ -2943,19 +4146,42 @@ or tune thresholds in config.",
             return;
         }
     }
+    if daily_budget_exhausted(ctx.as_ref(), &msg, target_channel.as_ref()).await {
+        return;
+    }
+
+    // Show the processing indicator as soon as the turn starts, before
+    // provider setup, history hydration and memory recall. The guard stops
+    // it on every early return.
+    let typing_cancellation = target_channel.as_ref().map(|_| CancellationToken::new());
+    let typing_task = match (target_channel.as_ref(), typing_cancellation.as_ref()) {
+        (Some(channel), Some(token)) => Some(spawn_scoped_typing_task(
+            Arc::clone(channel),
+            msg.reply_target.clone(),
+            token.clone(),
+        )),
+        _ => None,
+    };
+    let _typing_guard = typing_cancellation
+        .clone()
+        .map(CancellationToken::drop_guard);
 
     let history_key = conversation_history_key(&msg);
-    // Try classification first, fall back to sender/default route
-    let route = classify_message_route(ctx.as_ref(), &msg.content)
-        .unwrap_or_else(|| get_route_selection(ctx.as_ref(), &history_key));
+    let active_persona = persona::active(&msg.channel, &history_key);
+    // Try classification first, fall back to sender/persona/default route
+    let mut route = classify_message_route(ctx.as_ref(), &msg.content).unwrap_or_else(|| {
+        persona_route_selection(ctx.as_ref(), &history_key, active_persona.as_ref())
+    });
+    let budget_downgrade = apply_budget_downgrade(ctx.as_ref(), &msg, &mut route);
     let runtime_defaults = runtime_defaults_snapshot(ctx.as_ref());
     let active_provider = match get_or_create_provider(ctx.as_ref(), &route.provider).await {
         Ok(provider) => provider,
         Err(err) => {
             let safe_err = providers::sanitize_api_error(&err.to_string());
-            let message = format!(
-                "⚠️ Failed to initialize provider `{}`. Please run `/models` to choose another provider.\nDetails: {safe_err}",
-                route.provider
+            let message = crate::i18n::t_with(
+                &msg.channel,
+                "reply.provider_unavailable",
+                &[("provider", &route.provider), ("details", &safe_err)],
             );
             if let Some(channel) = target_channel.as_ref() {
                 let _ = channel
@@ -2968,6 +4194,12 @@ or tune thresholds in config.",
             return;
         }
     };
+    let memory_principal = ctx.memory_principal_isolation.then(|| {
+        principal::MemoryPrincipal::new(&msg.channel, &msg.sender, ctx.memory_access.clone())
+    });
+    let memory_namespace = memory_principal
+        .as_ref()
+        .map(principal::MemoryPrincipal::namespace);
     if ctx.auto_save_memory && msg.content.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
         let autosave_key = conversation_memory_key(&msg);
         let _ = ctx
@@ -2976,7 +4208,7 @@ or tune thresholds in config.",
                 &autosave_key,
                 &msg.content,
                 crate::memory::MemoryCategory::Conversation,
-                None,
+                memory_namespace.as_deref(),
             )
             .await;
     }
@@ -2984,6 +4216,8 @@ or tune thresholds in config.",
     println!("  ⏳ Processing message...");
     let started_at = Instant::now();
 
+    hydrate_sender_history(ctx.as_ref(), &history_key).await;
+    hydrate_sender_pins(ctx.as_ref(), &history_key).await;
     let had_prior_history = ctx
         .conversation_histories
         .lock()
@@ -2993,8 +4227,17 @@ or tune thresholds in config.",
 
     // Inject per-message timestamp so the LLM always knows the current time,
     // even in multi-turn conversations where the system prompt may be stale.
-    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
-    let timestamped_content = format!("[{now}] {}", msg.content);
+    let principal_profile = crate::i18n::profile::turn_profile().unwrap_or_default();
+    let now = match principal_profile.effective_timezone() {
+        Some(tz) => chrono::Utc::now()
+            .with_timezone(&tz)
+            .format("%Y-%m-%d %H:%M:%S %Z")
+            .to_string(),
+        None => chrono::Local::now()
+            .format("%Y-%m-%d %H:%M:%S %Z")
+            .to_string(),
+    };
+    let timestamped_content = plan_turn.decorate(&format!("[{now}] {}", msg.content));
 
     // Preserve user turn before the LLM call so interrupted requests keep context.
     append_sender_turn(
@@ -3002,6 +4245,8 @@ or tune thresholds in config.",
         &history_key,
         ChatMessage::user(&timestamped_content),
     );
+    persist_sender_history(ctx.as_ref(), &history_key).await;
+    record_session_metadata(ctx.as_ref(), &history_key, &msg).await;
 
     // Build history from per-sender conversation cache.
     let prior_turns_raw = ctx
@@ -3015,34 +4260,115 @@ or tune thresholds in config.",
 
     // Only enrich with memory context when there is no prior conversation
     // history. Follow-up turns already include context from previous messages.
-    if !had_prior_history {
-        let memory_context =
-            build_memory_context(ctx.memory.as_ref(), &msg.content, ctx.min_relevance_score).await;
-        if let Some(last_turn) = prior_turns.last_mut() {
-            if last_turn.role == "user" && !memory_context.is_empty() {
-                last_turn.content = format!("{memory_context}{timestamped_content}");
-            }
+    // Knowledge base passages depend on the question itself, so they are
+    // looked up every turn; only the outgoing copy is enriched, never the
+    // cached history.
+    let memory_context = if had_prior_history {
+        String::new()
+    } else {
+        build_memory_context(
+            ctx.memory.as_ref(),
+            &msg.content,
+            ctx.min_relevance_score,
+            memory_principal.as_ref(),
+        )
+        .await
+    };
+    let kb_context = match &ctx.knowledge_base {
+        Some(kb) => kb.context_for(&msg.content).await,
+        None => String::new(),
+    };
+    let turn_context = format!("{memory_context}{kb_context}");
+    if let Some(last_turn) = prior_turns.last_mut() {
+        if last_turn.role == "user" && !turn_context.is_empty() {
+            last_turn.content = format!("{turn_context}{timestamped_content}");
         }
     }
 
     let expose_internal_tool_details =
         msg.channel == "cli" || should_expose_internal_tool_details(&msg.content);
-    let excluded_tools_snapshot = if msg.channel == "cli" {
+    let mut excluded_tools_snapshot = if msg.channel == "cli" {
         Vec::new()
     } else {
         snapshot_non_cli_excluded_tools(ctx.as_ref())
     };
-    let mut system_prompt = build_channel_system_prompt(
-        ctx.system_prompt.as_str(),
-        &msg.channel,
-        &msg.reply_target,
-        expose_internal_tool_details,
+    if let Some(persona) = &active_persona {
+        excluded_tools_snapshot
+            .extend(persona.excluded_tools(ctx.tools_registry.iter().map(|tool| tool.name())));
+    }
+    // The startup prompt is identical for every message; everything appended
+    // below varies per sender or turn. Mark the split so providers with
+    // prompt caching can reuse the prefix across the whole channel.
+    let base_prompt = if active_provider.supports_prompt_caching() {
+        format!(
+            "{}\n{}",
+            ctx.system_prompt.as_str(),
+            providers::PROMPT_CACHE_BOUNDARY
+        )
+    } else {
+        ctx.system_prompt.to_string()
+    };
+    let prompt_builder = ContextBuilder::new(&route.model, &ctx.session_config.context_budgets)
+        .section(ContextSection::Identity, &base_prompt)
+        .section(
+            ContextSection::Identity,
+            &active_persona
+                .as_ref()
+                .map(persona::ActivePersona::prompt)
+                .unwrap_or_default(),
+        )
+        .section(
+            ContextSection::Tools,
+            &build_runtime_tool_visibility_prompt(
+                ctx.tools_registry.as_ref(),
+                &excluded_tools_snapshot,
+                active_provider.supports_native_tools(),
+            ),
+        )
+        .section(
+            ContextSection::Channel,
+            &build_channel_context_prompt(
+                &msg.channel,
+                &msg.reply_target,
+                expose_internal_tool_details,
+            ),
+        )
+        .section(
+            ContextSection::Memory,
+            &build_pinned_context_prompt(&sender_pins(ctx.as_ref(), &history_key)),
+        );
+    let locale = principal_profile
+        .locale
+        .clone()
+        .or_else(context::host_locale);
+    let prompt_builder = match principal_profile.effective_timezone() {
+        Some(tz) => prompt_builder.time(&chrono::Utc::now().with_timezone(&tz), locale.as_deref()),
+        None => prompt_builder.time(&chrono::Local::now(), locale.as_deref()),
+    };
+    let system_prompt = prompt_builder
+        .section(
+            ContextSection::Time,
+            &crate::i18n::profile::prompt_section(
+                &principal_profile,
+                ctx.memory_principal_isolation,
+            ),
+        )
+        .build();
+    let trimmed_turns = session::tokens::trim_to_token_budget(
+        &mut prior_turns,
+        &system_prompt,
+        &route.provider,
+        &route.model,
+        &ctx.session_config,
     );
-    system_prompt.push_str(&build_runtime_tool_visibility_prompt(
-        ctx.tools_registry.as_ref(),
-        &excluded_tools_snapshot,
-        active_provider.supports_native_tools(),
-    ));
+    if trimmed_turns > 0 {
+        tracing::debug!(
+            sender = %msg.sender,
+            model = %route.model,
+            trimmed_turns,
+            "Trimmed channel history to fit the context window"
+        );
+    }
     let mut history = vec![ChatMessage::system(system_prompt)];
     history.extend(prior_turns);
     let use_streaming = target_channel
@@ -3094,11 +4420,18 @@ or tune thresholds in config.",
         let reply_target = msg.reply_target.clone();
         let draft_id = draft_id_ref.to_string();
         let suppress_internal_progress = !expose_internal_tool_details;
+        // Draft updates bypass `sanitize_channel_response`, so scan the stream
+        // incrementally; the finalized draft is sanitized as a whole later.
+        let mut leak_scanner =
+            StreamScanner::new(LeakDetector::new(), ctx.leak_guard.stream_overlap_chars);
+        let observer = Arc::clone(&ctx.observer);
+        let channel_name = msg.channel.clone();
         Some(tokio::spawn(async move {
             let mut accumulated = String::new();
             while let Some(delta) = rx.recv().await {
                 if delta == crate::agent::loop_::DRAFT_CLEAR_SENTINEL {
                     accumulated.clear();
+                    leak_scanner.reset();
                     continue;
                 }
                 let (is_internal_progress, visible_delta) = split_internal_progress_delta(&delta);
@@ -3106,7 +4439,11 @@ or tune thresholds in config.",
                     continue;
                 }
 
-                accumulated.push_str(visible_delta);
+                let released = leak_scanner.push(visible_delta);
+                if released.is_empty() {
+                    continue;
+                }
+                accumulated.push_str(&released);
                 if let Err(e) = channel
                     .update_draft(&reply_target, &draft_id, &accumulated)
                     .await
@@ -3114,6 +4451,16 @@ or tune thresholds in config.",
                     tracing::debug!("Draft update failed: {e}");
                 }
             }
+            if !leak_scanner.detected_patterns().is_empty() {
+                tracing::warn!(
+                    patterns = ?leak_scanner.detected_patterns(),
+                    "output guardrail: credential leak detected in streamed draft update"
+                );
+                observer.record_event(&ObserverEvent::LeakDetected {
+                    channel: channel_name,
+                    source: "draft".to_string(),
+                });
+            }
         }))
     } else {
         None
@@ -3129,16 +4476,6 @@ or tune thresholds in config.",
         }
     }
 
-    let typing_cancellation = target_channel.as_ref().map(|_| CancellationToken::new());
-    let typing_task = match (target_channel.as_ref(), typing_cancellation.as_ref()) {
-        (Some(channel), Some(token)) => Some(spawn_scoped_typing_task(
-            Arc::clone(channel),
-            msg.reply_target.clone(),
-            token.clone(),
-        )),
-        _ => None,
-    };
-
     // Record history length before tool loop so we can extract tool context after.
     let history_len_before_tools = history.len();
 
@@ -3155,28 +4492,58 @@ or tune thresholds in config.",
         None
     } else if let Some(channel_ref) = target_channel.as_ref() {
         let channel = Arc::clone(channel_ref);
+        let prompt_ctx = Arc::clone(&ctx);
+        let sender = msg.sender.clone();
         let reply_target = msg.reply_target.clone();
         let thread_ts = msg.thread_ts.clone();
-        Some(tokio::spawn(async move {
-            while let Some(prompt) = approval_prompt_rx.recv().await {
-                if let Err(err) = channel
-                    .send_approval_prompt(
-                        &reply_target,
-                        &prompt.request_id,
-                        &prompt.tool_name,
-                        &prompt.arguments,
-                        thread_ts.clone(),
-                    )
-                    .await
-                {
-                    tracing::warn!(
-                        channel = %channel.name(),
-                        request_id = %prompt.request_id,
-                        "Failed to send approval prompt: {err}"
-                    );
+        let turn_profile = crate::i18n::profile::turn_profile().unwrap_or_default();
+        Some(tokio::spawn(crate::i18n::profile::with_turn_profile(
+            turn_profile,
+            async move {
+                let mut sent_prompts = Vec::new();
+                while let Some(prompt) = approval_prompt_rx.recv().await {
+                    match channel
+                        .send_approval_prompt(
+                            &reply_target,
+                            &prompt.request_id,
+                            &prompt.tool_name,
+                            &prompt.arguments,
+                            thread_ts.clone(),
+                        )
+                        .await
+                    {
+                        Ok(Some(message)) => sent_prompts.push((prompt, message)),
+                        Ok(None) => {}
+                        Err(err) => {
+                            tracing::warn!(
+                                channel = %channel.name(),
+                                request_id = %prompt.request_id,
+                                "Failed to send approval prompt: {err}"
+                            );
+                        }
+                    }
+                    publish_home_view(&prompt_ctx, &channel, &sender).await;
                 }
-            }
-        }))
+                // Every request raised in the turn is settled once the tool
+                // loop returns; leave no stale buttons behind.
+                if channel.supports_message_edits() {
+                    for (prompt, message) in sent_prompts {
+                        let closed = crate::i18n::t_with(
+                            channel.name(),
+                            "approval.closed",
+                            &[("id", &prompt.request_id), ("tool", &prompt.tool_name)],
+                        );
+                        if let Err(err) = channel.edit_message(&message, &closed).await {
+                            tracing::debug!(
+                                channel = %channel.name(),
+                                request_id = %prompt.request_id,
+                                "Failed to close approval prompt: {err}"
+                            );
+                        }
+                    }
+                }
+            },
+        )))
     } else {
         None
     };
@@ -3190,32 +4557,54 @@ or tune thresholds in config.",
         })
     };
 
+    let usage_scope = ctx.usage_ledger.as_ref().map(|ledger| UsageScope {
+        ledger: Arc::clone(ledger),
+        session: history_key.clone(),
+    });
+
     let llm_result = tokio::select! {
         () = cancellation_token.cancelled() => LlmExecutionResult::Cancelled,
         result = tokio::time::timeout(
             Duration::from_secs(timeout_budget_secs),
-            run_tool_call_loop_with_non_cli_approval_context(
-                active_provider.as_ref(),
-                &mut history,
-                ctx.tools_registry.as_ref(),
-                ctx.observer.as_ref(),
-                route.provider.as_str(),
-                route.model.as_str(),
-                runtime_defaults.temperature,
-                true,
-                Some(ctx.approval_manager.as_ref()),
-                msg.channel.as_str(),
-                non_cli_approval_context,
-                &ctx.multimodal,
-                ctx.max_tool_iterations,
-                Some(cancellation_token.clone()),
-                delta_tx,
-                ctx.hooks.as_deref(),
-                &excluded_tools_snapshot,
+            principal::MEMORY_PRINCIPAL.scope(
+                memory_principal.clone(),
+                USAGE_SCOPE.scope(
+                    usage_scope,
+                    TOOL_ANOMALY_MONITOR.scope(
+                        ctx.tool_anomaly.clone(),
+                        CONTENT_INJECTION_CONFIG.scope(
+                            ctx.content_injection.clone(),
+                            PLAN_SCOPE.scope(
+                                plan_turn.scope(),
+                                run_tool_call_loop_with_non_cli_approval_context(
+                                    active_provider.as_ref(),
+                                    &mut history,
+                                    ctx.tools_registry.as_ref(),
+                                    ctx.observer.as_ref(),
+                                    route.provider.as_str(),
+                                    route.model.as_str(),
+                                    runtime_defaults.temperature,
+                                    true,
+                                    Some(ctx.approval_manager.as_ref()),
+                                    msg.channel.as_str(),
+                                    non_cli_approval_context,
+                                    &ctx.multimodal,
+                                    ctx.max_tool_iterations,
+                                    Some(cancellation_token.clone()),
+                                    delta_tx,
+                                    ctx.hooks.as_deref(),
+                                    &excluded_tools_snapshot,
+                                ),
+                            ),
+                        ),
+                    ),
+                ),
             ),
         ) => LlmExecutionResult::Completed(result),
     };
 
+    crate::tools::undo::claim_current_turn(&format!("{}:{}", msg.channel, msg.sender));
+
     drop(approval_prompt_tx);
     if let Some(handle) = approval_prompt_task {
         log_worker_join_result(handle.await);
@@ -3237,6 +4626,30 @@ or tune thresholds in config.",
         _ => "\u{26A0}\u{FE0F}",                                // ⚠️
     };
 
+    let failure = match &llm_result {
+        LlmExecutionResult::Completed(Err(_)) => Some("timeout"),
+        LlmExecutionResult::Completed(Ok(Err(e)))
+            if !crate::agent::loop_::is_tool_loop_cancelled(e)
+                && !cancellation_token.is_cancelled() =>
+        {
+            Some(if is_context_window_overflow_error(e) {
+                "context_window"
+            } else if is_tool_iteration_limit_error(e) {
+                "tool_limit"
+            } else {
+                "error"
+            })
+        }
+        _ => None,
+    };
+    if let (Some(reason), Some(ledger)) = (failure, ctx.usage_ledger.as_ref()) {
+        if let Err(e) =
+            ledger.record_activity(&msg.channel, crate::cost::ActivityKind::TurnFailure, reason)
+        {
+            tracing::warn!("Failed to record turn failure: {e}");
+        }
+    }
+
     match llm_result {
         LlmExecutionResult::Cancelled => {
             tracing::info!(
@@ -3266,8 +4679,10 @@ or tune thresholds in config.",
             }
         }
         LlmExecutionResult::Completed(Ok(Ok(response))) => {
+            let response = plan_turn.finish(&history_key, &msg.content, response);
+            let mut outbound_response = moderate_channel_reply(ctx.as_ref(), &msg, response).await;
+
             // ── Hook: on_message_sending (modifying) ─────────
-            let mut outbound_response = response;
             if let Some(hooks) = &ctx.hooks {
                 match hooks
                     .run_on_message_sending(
@@ -3324,15 +4739,55 @@ or tune thresholds in config.",
                 }
             }
 
-            let sanitized_response =
-                sanitize_channel_response(&outbound_response, ctx.tools_registry.as_ref());
-            let delivered_response = if sanitized_response.is_empty()
-                && !outbound_response.trim().is_empty()
-            {
-                "I encountered malformed tool-call output and could not produce a safe reply. Please try again.".to_string()
+            let (sanitized_response, leak_detected) =
+                sanitize_channel_response_checked(&outbound_response, ctx.tools_registry.as_ref());
+            if leak_detected {
+                ctx.observer.record_event(&ObserverEvent::LeakDetected {
+                    channel: msg.channel.clone(),
+                    source: "response".to_string(),
+                });
+                turn::record(|report| report.redactions.push("response".to_string()));
+            }
+            let delivered_response =
+                if sanitized_response.is_empty() && !outbound_response.trim().is_empty() {
+                    crate::i18n::t(&msg.channel, "reply.malformed_output")
+                } else {
+                    sanitized_response
+                };
+            turn::record(|report| report.set_response(&delivered_response));
+            let quarantined = if leak_detected {
+                crate::approval::quarantine::queue().and_then(|queue| {
+                    queue.hold(
+                        &msg.channel,
+                        &msg.reply_target,
+                        msg.thread_ts.clone(),
+                        &msg.sender,
+                        &strip_channel_response_artifacts(
+                            &outbound_response,
+                            ctx.tools_registry.as_ref(),
+                        ),
+                    )
+                })
             } else {
-                sanitized_response
+                None
             };
+            if let Some(held) = quarantined.as_ref() {
+                runtime_trace::record_event(
+                    "channel_message_quarantined",
+                    Some(msg.channel.as_str()),
+                    None,
+                    None,
+                    None,
+                    Some(true),
+                    Some("outbound reply held for leak review"),
+                    serde_json::json!({
+                        "quarantine_id": held.id,
+                        "sender": msg.sender,
+                        "patterns": held.patterns,
+                        "expires_at": held.expires_at.to_rfc3339(),
+                    }),
+                );
+            }
             runtime_trace::record_event(
                 "channel_message_outbound",
                 Some(msg.channel.as_str()),
@@ -3347,6 +4802,10 @@ or tune thresholds in config.",
                     "response": scrub_credentials(&delivered_response),
                 }),
             );
+            ctx.observer.record_event(&ObserverEvent::ChannelMessage {
+                channel: msg.channel.clone(),
+                direction: "outbound".to_string(),
+            });
 
             // Extract condensed tool-use context from the history messages
             // added during run_tool_call_loop, so the LLM retains awareness
@@ -3363,34 +4822,73 @@ or tune thresholds in config.",
                 &history_key,
                 ChatMessage::assistant(&history_response),
             );
+            if ctx.memory_fact_extraction
+                && msg.content.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS
+            {
+                let (task_provider, task_model) =
+                    background_task_route(ctx.as_ref(), &active_provider, &route.model).await;
+                spawn_fact_extraction(
+                    ctx.as_ref(),
+                    task_provider,
+                    &task_model,
+                    &msg,
+                    &delivered_response,
+                    memory_principal.clone(),
+                );
+            }
             println!(
                 "  🤖 Reply ({}ms): {}",
                 started_at.elapsed().as_millis(),
                 truncate_with_ellipsis(&delivered_response, 80)
             );
             if let Some(channel) = target_channel.as_ref() {
-                if let Some(ref draft_id) = draft_message_id {
-                    if let Err(e) = channel
-                        .finalize_draft(&msg.reply_target, draft_id, &delivered_response)
-                        .await
-                    {
-                        tracing::warn!("Failed to finalize draft: {e}; sending as new message");
-                        let _ = channel
-                            .send(
-                                &SendMessage::new(&delivered_response, &msg.reply_target)
+                let rendered = match quarantined.as_ref() {
+                    Some(held) => render::render_for_channel(
+                        channel.name(),
+                        &crate::i18n::t_with(
+                            &msg.channel,
+                            "reply.quarantined",
+                            &[("id", &held.id), ("expires", &held.expires_at.to_rfc3339())],
+                        ),
+                    ),
+                    None => match budget_downgrade.as_deref() {
+                        Some(footer) => render::render_for_channel(
+                            channel.name(),
+                            &format!("{delivered_response}\n\n{footer}"),
+                        ),
+                        None => render::render_for_channel(channel.name(), &delivered_response),
+                    },
+                };
+                record_turn_response(ctx.as_ref(), &msg, &rendered).await;
+                async {
+                    if let Some(ref draft_id) = draft_message_id {
+                        if let Err(e) = channel
+                            .finalize_draft(&msg.reply_target, draft_id, &rendered)
+                            .await
+                        {
+                            tracing::warn!("Failed to finalize draft: {e}; sending as new message");
+                            let _ = outbox::deliver(
+                                channel.as_ref(),
+                                &SendMessage::new(&rendered, &msg.reply_target)
                                     .in_thread(msg.thread_ts.clone()),
                             )
                             .await;
-                    }
-                } else if let Err(e) = channel
-                    .send(
-                        &SendMessage::new(delivered_response, &msg.reply_target)
+                        }
+                    } else if let Err(e) = outbox::deliver(
+                        channel.as_ref(),
+                        &SendMessage::new(&rendered, &msg.reply_target)
                             .in_thread(msg.thread_ts.clone()),
                     )
                     .await
-                {
-                    eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
+                    {
+                        eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
+                    }
                 }
+                .instrument(tracing::info_span!(
+                    "outbound_send",
+                    channel = channel.name()
+                ))
+                .await;
             }
         }
         LlmExecutionResult::Completed(Ok(Err(e))) => {
@@ -3423,11 +4921,14 @@ or tune thresholds in config.",
                 }
             } else if is_context_window_overflow_error(&e) {
                 let compacted = compact_sender_history(ctx.as_ref(), &history_key);
-                let error_text = if compacted {
-                    "⚠️ Context window exceeded for this conversation. I compacted recent history and kept the latest context. Please resend your last message."
-                } else {
-                    "⚠️ Context window exceeded for this conversation. Please resend your last message."
-                };
+                let error_text = crate::i18n::t(
+                    &msg.channel,
+                    if compacted {
+                        "reply.context_compacted"
+                    } else {
+                        "reply.context_exceeded"
+                    },
+                );
                 eprintln!(
                     "  ⚠️ Context window exceeded after {}ms; sender history compacted={}",
                     started_at.elapsed().as_millis(),
@@ -3450,7 +4951,7 @@ or tune thresholds in config.",
                 if let Some(channel) = target_channel.as_ref() {
                     if let Some(ref draft_id) = draft_message_id {
                         let _ = channel
-                            .finalize_draft(&msg.reply_target, draft_id, error_text)
+                            .finalize_draft(&msg.reply_target, draft_id, &error_text)
                             .await;
                     } else {
                         let _ = channel
@@ -3463,8 +4964,10 @@ or tune thresholds in config.",
                 }
             } else if is_tool_iteration_limit_error(&e) {
                 let limit = ctx.max_tool_iterations.max(1);
-                let pause_text = format!(
-                    "⚠️ Reached tool-iteration limit ({limit}) for this turn. Context and progress were preserved. Reply \"continue\" to resume, or increase `agent.max_tool_iterations`."
+                let pause_text = crate::i18n::t_with(
+                    &msg.channel,
+                    "reply.tool_limit",
+                    &[("limit", &limit.to_string())],
                 );
                 runtime_trace::record_event(
                     "channel_message_error",
@@ -3536,14 +5039,19 @@ or tune thresholds in config.",
                     );
                 }
                 if let Some(channel) = target_channel.as_ref() {
+                    let error_text = crate::i18n::t_with(
+                        &msg.channel,
+                        "reply.error",
+                        &[("error", &e.to_string())],
+                    );
                     if let Some(ref draft_id) = draft_message_id {
                         let _ = channel
-                            .finalize_draft(&msg.reply_target, draft_id, &format!("⚠️ Error: {e}"))
+                            .finalize_draft(&msg.reply_target, draft_id, &error_text)
                             .await;
                     } else {
                         let _ = channel
                             .send(
-                                &SendMessage::new(format!("⚠️ Error: {e}"), &msg.reply_target)
+                                &SendMessage::new(error_text, &msg.reply_target)
                                     .in_thread(msg.thread_ts.clone()),
                             )
                             .await;
@@ -3582,11 +5090,10 @@ or tune thresholds in config.",
                 ChatMessage::assistant("[Task timed out — not continuing this request]"),
             );
             if let Some(channel) = target_channel.as_ref() {
-                let error_text =
-                    "⚠️ Request timed out while waiting for the model. Please try again.";
+                let error_text = crate::i18n::t(&msg.channel, "reply.timeout");
                 if let Some(ref draft_id) = draft_message_id {
                     let _ = channel
-                        .finalize_draft(&msg.reply_target, draft_id, error_text)
+                        .finalize_draft(&msg.reply_target, draft_id, &error_text)
                         .await;
                 } else {
                     let _ = channel
@@ -3600,6 +5107,20 @@ or tune thresholds in config.",
         }
     }
 
+    let (summary_provider, summary_model) =
+        background_task_route(ctx.as_ref(), &active_provider, &route.model).await;
+    if summarize_sender_history(
+        ctx.as_ref(),
+        &history_key,
+        summary_provider.as_ref(),
+        &summary_model,
+    )
+    .await
+    {
+        tracing::debug!(sender = %msg.sender, "Summarized older channel history");
+    }
+    persist_sender_history(ctx.as_ref(), &history_key).await;
+
     // Swap 👀 → ✅ (or ⚠️ on error) to signal processing is complete
     if let Some(channel) = target_channel.as_ref() {
         let _ = channel
@@ -3611,10 +5132,16 @@ or tune thresholds in config.",
     }
 }
 
+/// How long in-flight turns get to react to cancellation once the shutdown
+/// grace period has elapsed.
+const SHUTDOWN_CANCEL_WAIT_SECS: u64 = 2;
+
 async fn run_message_dispatch_loop(
     mut rx: tokio::sync::mpsc::Receiver<traits::ChannelMessage>,
     ctx: Arc<ChannelRuntimeContext>,
     max_in_flight_messages: usize,
+    shutdown: CancellationToken,
+    shutdown_grace: Duration,
 ) {
     let semaphore = Arc::new(tokio::sync::Semaphore::new(max_in_flight_messages));
     let mut workers = tokio::task::JoinSet::new();
@@ -3623,22 +5150,61 @@ async fn run_message_dispatch_loop(
         InFlightSenderTaskState,
     >::new()));
     let task_sequence = Arc::new(AtomicU64::new(1));
-
-    while let Some(msg) = rx.recv().await {
-        let permit = match Arc::clone(&semaphore).acquire_owned().await {
-            Ok(permit) => permit,
-            Err(_) => break,
+    // Cancels every in-flight turn once the shutdown grace period runs out.
+    let cancel_in_flight = CancellationToken::new();
+
+    loop {
+        let msg = tokio::select! {
+            biased;
+            () = shutdown.cancelled() => break,
+            msg = rx.recv() => match msg {
+                Some(msg) => msg,
+                None => break,
+            },
+        };
+        if replay_completed_turn(&ctx, &msg).await {
+            continue;
+        }
+        let queued_task = match enqueue_channel_task(&ctx, &msg).await {
+            Some(task_queue::Enqueued::Task(id)) => Some(id),
+            Some(task_queue::Enqueued::InFlight) => {
+                tracing::info!(
+                    channel = %msg.channel,
+                    message_id = %msg.id,
+                    "Dropping duplicate delivery of a message whose turn is still in progress"
+                );
+                continue;
+            }
+            None => None,
+        };
+        let permit = tokio::select! {
+            biased;
+            () = shutdown.cancelled() => {
+                tracing::warn!(
+                    channel = %msg.channel,
+                    sender = %msg.sender,
+                    "Dropping queued message received during shutdown"
+                );
+                break;
+            }
+            permit = Arc::clone(&semaphore).acquire_owned() => match permit {
+                Ok(permit) => permit,
+                Err(_) => break,
+            },
         };
 
         let worker_ctx = Arc::clone(&ctx);
         let in_flight = Arc::clone(&in_flight_by_sender);
         let task_sequence = Arc::clone(&task_sequence);
+        let cancellation_token = cancel_in_flight.child_token();
+        let shutdown_cancel = cancel_in_flight.clone();
         workers.spawn(async move {
             let _permit = permit;
+            set_channel_task_status(&worker_ctx, queued_task, task_queue::TaskStatus::Running)
+                .await;
             let interrupt_enabled =
                 worker_ctx.interrupt_on_new_message && msg.channel == "telegram";
             let sender_scope_key = interruption_scope_key(&msg);
-            let cancellation_token = CancellationToken::new();
             let completion = Arc::new(InFlightTaskCompletion::new());
             let task_id = task_sequence.fetch_add(1, Ordering::Relaxed);
 
@@ -3666,7 +5232,38 @@ async fn run_message_dispatch_loop(
                 }
             }
 
-            process_channel_message(worker_ctx, msg, cancellation_token).await;
+            let (channel, message_id) = (msg.channel.clone(), msg.id.clone());
+            let sender = msg.sender.clone();
+            let autonomy_override = worker_ctx
+                .approval_manager
+                .autonomy_override_for(&msg.channel, &msg.sender);
+            let principal_profile = crate::i18n::profile::resolve(
+                worker_ctx.memory.as_ref(),
+                &msg.channel,
+                &msg.sender,
+                worker_ctx.memory_principal_isolation,
+            )
+            .await;
+            crate::security::with_turn_autonomy(
+                autonomy_override,
+                crate::i18n::profile::with_turn_profile(
+                    principal_profile,
+                    turn::in_turn(
+                        &channel,
+                        Some(&message_id),
+                        process_channel_message(Arc::clone(&worker_ctx), msg, cancellation_token),
+                    ),
+                ),
+            )
+            .await;
+            if let Some(target) = worker_ctx.channels_by_name.get(&channel) {
+                publish_home_view(&worker_ctx, target, &sender).await;
+            }
+            // A turn cut short by shutdown stays unfinished so the next start resumes it.
+            if !shutdown_cancel.is_cancelled() {
+                set_channel_task_status(&worker_ctx, queued_task, task_queue::TaskStatus::Done)
+                    .await;
+            }
 
             if interrupt_enabled {
                 let mut active = in_flight.lock().await;
@@ -3678,19 +5275,296 @@ async fn run_message_dispatch_loop(
                 }
             }
 
-            completion.mark_done();
-        });
+            completion.mark_done();
+        });
+
+        while let Some(result) = workers.try_join_next() {
+            log_worker_join_result(result);
+        }
+        ctx.observer
+            .record_metric(&ObserverMetric::QueueDepth(rx.len() as u64));
+        ctx.observer
+            .record_metric(&ObserverMetric::ActiveSessions(workers.len() as u64));
+    }
+
+    if shutdown.is_cancelled() {
+        drain_in_flight_messages(&ctx, &mut workers, &cancel_in_flight, shutdown_grace).await;
+        return;
+    }
+
+    while let Some(result) = workers.join_next().await {
+        log_worker_join_result(result);
+    }
+}
+
+/// Record an accepted message so a restart mid-turn can resume it. Runtime
+/// commands are answered without a model turn and are not worth replaying.
+async fn enqueue_channel_task(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+) -> Option<task_queue::Enqueued> {
+    let queue = ctx.task_queue.as_ref()?;
+    if parse_runtime_command(&msg.channel, &msg.content).is_some() {
+        return None;
+    }
+    match queue.enqueue(msg).await {
+        Ok(enqueued) => Some(enqueued),
+        Err(e) => {
+            tracing::warn!(channel = %msg.channel, "Failed to record channel task: {e}");
+            None
+        }
+    }
+}
+
+/// Answer a message whose turn already produced a reply (a redelivered
+/// webhook, or a restart between the tool calls and the send) with that
+/// reply instead of running the turn again. Returns whether it did.
+async fn replay_completed_turn(ctx: &ChannelRuntimeContext, msg: &traits::ChannelMessage) -> bool {
+    let Some(queue) = ctx.task_queue.as_ref() else {
+        return false;
+    };
+    let (id, response) = match queue.completed_response(msg).await {
+        Ok(Some(found)) => found,
+        Ok(None) => return false,
+        Err(e) => {
+            tracing::warn!(channel = %msg.channel, "Failed to look up channel task: {e}");
+            return false;
+        }
+    };
+    tracing::info!(
+        channel = %msg.channel,
+        message_id = %msg.id,
+        "Duplicate delivery of a completed turn; resending its stored reply"
+    );
+    runtime_trace::record_event(
+        "channel_message_replayed",
+        Some(msg.channel.as_str()),
+        None,
+        None,
+        None,
+        Some(true),
+        None,
+        serde_json::json!({
+            "sender": msg.sender,
+            "message_id": msg.id,
+        }),
+    );
+    if let Some(channel) = ctx.channels_by_name.get(&msg.channel) {
+        let reply = SendMessage::new(response, &msg.reply_target).in_thread(msg.thread_ts.clone());
+        if let Err(e) = outbox::deliver(channel.as_ref(), &reply).await {
+            tracing::warn!(channel = %msg.channel, "Failed to resend stored reply: {e}");
+        }
+    }
+    set_channel_task_status(ctx, Some(id), task_queue::TaskStatus::Done).await;
+    true
+}
+
+/// Store a turn's final reply before it is sent, so a redelivered message
+/// is answered with it instead of running its tools again.
+async fn record_turn_response(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    response: &str,
+) {
+    let Some(queue) = ctx.task_queue.as_ref() else {
+        return;
+    };
+    if let Err(e) = queue.record_response(msg, response).await {
+        tracing::warn!(channel = %msg.channel, "Failed to store turn response: {e}");
+    }
+}
+
+async fn set_channel_task_status(
+    ctx: &ChannelRuntimeContext,
+    id: Option<task_queue::TaskId>,
+    status: task_queue::TaskStatus,
+) {
+    let (Some(queue), Some(id)) = (ctx.task_queue.as_ref(), id) else {
+        return;
+    };
+    if let Err(e) = queue.set_status(id, status).await {
+        tracing::warn!("Failed to update channel task status: {e}");
+    }
+}
+
+/// Notice sent before a request interrupted by a restart is processed again.
+const RESUME_NOTICE: &str = "⏳ Resuming your request after a restart.";
+
+/// How often expired approval requests are announced.
+const APPROVAL_EXPIRY_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Feed tasks a previous process left unfinished back into the dispatch
+/// loop, telling each sender their request is being resumed.
+async fn resume_unfinished_tasks(
+    queue: Arc<task_queue::ChannelTaskQueue>,
+    channels_by_name: Arc<HashMap<String, Arc<dyn Channel>>>,
+    max_resumes: u32,
+    tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+) {
+    let tasks = match queue.take_unfinished(max_resumes).await {
+        Ok(tasks) => tasks,
+        Err(e) => {
+            tracing::warn!("Failed to load unfinished channel tasks: {e}");
+            return;
+        }
+    };
+    if !tasks.is_empty() {
+        tracing::info!("Resuming {} unfinished channel task(s)", tasks.len());
+    }
+
+    for task in tasks {
+        let msg = task.message;
+        let Some(channel) = channels_by_name.get(&msg.channel) else {
+            tracing::warn!(
+                channel = %msg.channel,
+                "Dropping unfinished task for a channel that is no longer configured"
+            );
+            if let Err(e) = queue
+                .set_status(task.id, task_queue::TaskStatus::Abandoned)
+                .await
+            {
+                tracing::warn!("Failed to update channel task status: {e}");
+            }
+            continue;
+        };
+        // A turn that finished before the restart is answered from its
+        // stored reply by the dispatch loop; only re-run turns get a notice.
+        if task.response.is_none() {
+            let notice =
+                SendMessage::new(RESUME_NOTICE, &msg.reply_target).in_thread(msg.thread_ts.clone());
+            if let Err(e) = channel.send(&notice).await {
+                tracing::warn!(channel = %msg.channel, "Failed to send resume notice: {e}");
+            }
+        }
+        if tx.send(msg).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Tell each chat when one of its approval requests expired without a
+/// decision, until `stop` fires.
+async fn run_approval_expiry_notifier(ctx: Arc<ChannelRuntimeContext>, stop: CancellationToken) {
+    let mut ticker = tokio::time::interval(APPROVAL_EXPIRY_POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            () = stop.cancelled() => return,
+            _ = ticker.tick() => {}
+        }
+        for req in ctx.approval_manager.take_expired_non_cli_requests() {
+            runtime_trace::record_event(
+                "approval_request_expired",
+                Some(req.requested_channel.as_str()),
+                None,
+                None,
+                None,
+                Some(false),
+                Some("approval request expired without a decision"),
+                serde_json::json!({
+                    "request_id": req.request_id,
+                    "tool": req.tool_name,
+                    "requested_by": req.requested_by,
+                }),
+            );
+            let Some(channel) = ctx.channels_by_name.get(&req.requested_channel) else {
+                continue;
+            };
+            let notice = crate::i18n::t_with(
+                &req.requested_channel,
+                "approval.expired",
+                &[("id", &req.request_id), ("tool", &req.tool_name)],
+            );
+            let message = SendMessage::new(notice, &req.requested_reply_target);
+            if let Err(e) = outbox::deliver(channel.as_ref(), &message).await {
+                tracing::warn!(
+                    channel = %req.requested_channel,
+                    request_id = %req.request_id,
+                    "Failed to announce expired approval request: {e}"
+                );
+            }
+        }
+    }
+}
+
+/// Let in-flight turns finish within `grace`, cancel the rest, then flush
+/// every sender's history and pins to the session store.
+async fn drain_in_flight_messages(
+    ctx: &ChannelRuntimeContext,
+    workers: &mut tokio::task::JoinSet<()>,
+    cancel_in_flight: &CancellationToken,
+    grace: Duration,
+) {
+    // Nobody is left to answer approval prompts; waiting turns fail closed.
+    let rejected = ctx.approval_manager.clear_non_cli_pending_requests();
+    if rejected > 0 {
+        tracing::info!("Rejected {rejected} pending approval request(s) for shutdown");
+    }
+    if !workers.is_empty() {
+        tracing::info!(
+            in_flight = workers.len(),
+            "Waiting for in-flight channel messages before shutdown"
+        );
+    }
 
-        while let Some(result) = workers.try_join_next() {
-            log_worker_join_result(result);
+    if tokio::time::timeout(grace, join_workers(workers))
+        .await
+        .is_err()
+    {
+        tracing::warn!(
+            remaining = workers.len(),
+            "Shutdown grace period elapsed; cancelling in-flight channel messages"
+        );
+        cancel_in_flight.cancel();
+        let cancel_wait = Duration::from_secs(SHUTDOWN_CANCEL_WAIT_SECS);
+        if tokio::time::timeout(cancel_wait, join_workers(workers))
+            .await
+            .is_err()
+        {
+            workers.abort_all();
+            join_workers(workers).await;
         }
     }
 
+    persist_all_sender_state(ctx).await;
+}
+
+async fn join_workers(workers: &mut tokio::task::JoinSet<()>) {
     while let Some(result) = workers.join_next().await {
         log_worker_join_result(result);
     }
 }
 
+/// Write every in-memory history and pin list to the session store.
+async fn persist_all_sender_state(ctx: &ChannelRuntimeContext) {
+    if ctx.session_store.is_none() {
+        return;
+    }
+    let history_keys: Vec<String> = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .keys()
+        .cloned()
+        .collect();
+    let pin_keys: Vec<String> = ctx
+        .pinned_messages
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .keys()
+        .cloned()
+        .collect();
+    for key in &history_keys {
+        persist_sender_history(ctx, key).await;
+    }
+    for key in &pin_keys {
+        persist_sender_pins(ctx, key).await;
+    }
+    tracing::info!(
+        sessions = history_keys.len(),
+        "Persisted channel session state for shutdown"
+    );
+}
+
 /// Load OpenClaw format bootstrap files into the prompt.
 fn load_openclaw_bootstrap_files(
     prompt: &mut String,
@@ -4210,11 +6084,73 @@ pub(crate) async fn handle_command(command: crate::ChannelCommands, config: &Con
         crate::ChannelCommands::BindTelegram { identity } => {
             bind_telegram_identity(config, &identity).await
         }
+        crate::ChannelCommands::Outbox {
+            status,
+            retry,
+            discard,
+        } => outbox_command(config, &status, retry, discard).await,
+    }
+}
+
+/// `zeroclaw channel outbox`: list, requeue or discard recorded replies.
+async fn outbox_command(
+    config: &Config,
+    status: &str,
+    retry: Option<i64>,
+    discard: Option<i64>,
+) -> Result<()> {
+    let queue = outbox::ChannelOutbox::open(&config.workspace_dir, &config.reliability)?;
+    if let Some(id) = retry {
+        let Some(entry) = queue.requeue(id).await? else {
+            anyhow::bail!("No undelivered reply with id {id}");
+        };
+        println!(
+            "Queued reply {id} to {} on {} for delivery; the running daemon sends it within a few seconds.",
+            entry.recipient, entry.channel
+        );
+        return Ok(());
+    }
+    if let Some(id) = discard {
+        if !queue.discard(id).await? {
+            anyhow::bail!("No undelivered reply with id {id}");
+        }
+        println!("Discarded reply {id}.");
+        return Ok(());
+    }
+
+    let filter = match status {
+        "all" => None,
+        other => Some(outbox::OutboundStatus::parse(other).ok_or_else(|| {
+            anyhow::anyhow!("Unknown status '{other}' (expected dead, pending, delivered or all)")
+        })?),
+    };
+    let entries = queue.list(filter, 100).await?;
+    if entries.is_empty() {
+        println!("No {status} replies in the outbound queue.");
+        return Ok(());
+    }
+    for entry in entries {
+        let created = chrono::DateTime::from_timestamp(entry.created_at, 0)
+            .map(|at| at.to_rfc3339())
+            .unwrap_or_default();
+        println!(
+            "#{} [{}] {} → {} ({} attempt(s), {created})",
+            entry.id,
+            entry.status.as_str(),
+            entry.channel,
+            entry.recipient,
+            entry.attempts
+        );
+        println!("    {}", truncate_with_ellipsis(&entry.content, 100));
+        if let Some(error) = entry.last_error.as_deref() {
+            println!("    last error: {error}");
+        }
     }
+    Ok(())
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ChannelHealthState {
+pub(crate) enum ChannelHealthState {
     Healthy,
     Unhealthy,
     Timeout,
@@ -4235,6 +6171,20 @@ struct ConfiguredChannel {
     channel: Arc<dyn Channel>,
 }
 
+/// Probe every configured channel's credentials concurrently, each bounded
+/// by a 10s timeout. Used by `zeroclaw doctor`.
+pub(crate) async fn probe_channel_health(
+    config: &Config,
+) -> Vec<(&'static str, ChannelHealthState)> {
+    let channels = collect_configured_channels(config, "health check");
+    futures_util::future::join_all(channels.into_iter().map(|configured| async move {
+        let result =
+            tokio::time::timeout(Duration::from_secs(10), configured.channel.health_check()).await;
+        (configured.display_name, classify_health_result(&result))
+    }))
+    .await
+}
+
 fn collect_configured_channels(
     config: &Config,
     matrix_skip_context: &str,
@@ -4295,7 +6245,9 @@ fn collect_configured_channels(
                 .with_group_reply_policy(
                     sl.effective_group_reply_mode().requires_mention(),
                     sl.group_reply_allowed_sender_ids(),
-                ),
+                )
+                .with_streaming(sl.stream_mode, sl.draft_update_interval_ms)
+                .with_app_home(sl.app_home),
             ),
         });
     }
@@ -4550,6 +6502,13 @@ fn collect_configured_channels(
         });
     }
 
+    if let Some(ref queue) = config.channels_config.queue {
+        channels.push(ConfiguredChannel {
+            display_name: "Queue",
+            channel: Arc::new(QueueChannel::new(queue.clone())),
+        });
+    }
+
     channels
 }
 
@@ -4642,10 +6601,18 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
 }
 
 /// Start all configured channels and route messages to the agent
-#[allow(clippy::too_many_lines)]
 pub async fn start_channels(config: Config) -> Result<()> {
+    run_channels(config, crate::daemon::shutdown_token()).await
+}
+
+/// Run the channel runtime until `stop` is cancelled. In-flight turns drain
+/// as on shutdown; the daemon uses this to rebuild channels after a config
+/// reload.
+#[allow(clippy::too_many_lines)]
+pub async fn run_channels(config: Config, stop: CancellationToken) -> Result<()> {
     // Ensure stale channel handles are never reused across restarts.
     clear_live_channels();
+    admin::clear_live_runtime();
 
     let provider_name = resolved_default_provider(&config);
     let provider_runtime_options = providers::ProviderRuntimeOptions {
@@ -4659,6 +6626,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         custom_provider_api_mode: config.provider_api.map(|mode| mode.as_compatible_mode()),
         max_tokens_override: None,
         model_support_vision: config.model_support_vision,
+        model_support_tools: config.model_support_tools,
     };
     let provider: Arc<dyn Provider> = Arc::from(
         create_resilient_provider_nonblocking(
@@ -4965,6 +6933,9 @@ pub async fn start_channels(config: Config) -> Result<()> {
             max_backoff_secs,
         ));
     }
+    // Resumed tasks re-enter through the bus; this sender is released once
+    // they have all been queued.
+    let resume_tx = tx.clone();
     drop(tx); // Drop our copy so rx closes when all channels stop
 
     let channels_by_name = Arc::new(
@@ -4988,6 +6959,32 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .as_ref()
         .is_some_and(|tg| tg.interrupt_on_new_message);
 
+    let usage_ledger = if config.cost.enabled {
+        match crate::cost::UsageLedger::open(&config.workspace_dir, &config.cost) {
+            Ok(ledger) => Some(Arc::new(ledger)),
+            Err(e) => {
+                tracing::warn!("Failed to open usage ledger: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let channel_task_queue = if config.reliability.resume_unfinished_tasks {
+        match task_queue::ChannelTaskQueue::open(&config.workspace_dir) {
+            Ok(queue) => Some(Arc::new(queue)),
+            Err(e) => {
+                tracing::warn!("Failed to open channel task queue: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    outbox::init_from_config(&config.reliability, &config.workspace_dir);
+
     let runtime_ctx = Arc::new(ChannelRuntimeContext {
         channels_by_name,
         provider: Arc::clone(&provider),
@@ -4999,9 +6996,14 @@ pub async fn start_channels(config: Config) -> Result<()> {
         model: Arc::new(model.clone()),
         temperature,
         auto_save_memory: config.memory.auto_save,
+        memory_principal_isolation: config.memory.principal_isolation,
+        memory_access: config.memory.access.clone(),
+        memory_fact_extraction: config.memory.fact_extraction,
+        usage_ledger,
         max_tool_iterations: config.agent.max_tool_iterations,
         min_relevance_score: config.memory.min_relevance_score,
         conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+        pinned_messages: Arc::new(Mutex::new(HashMap::new())),
         provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
         route_overrides: Arc::new(Mutex::new(HashMap::new())),
         api_key: config.api_key.clone(),
@@ -5026,6 +7028,13 @@ pub async fn start_channels(config: Config) -> Result<()> {
         )),
         query_classification: config.query_classification.clone(),
         model_routes: config.model_routes.clone(),
+        content_injection: config.security.content_injection.clone(),
+        tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+            config.security.tool_anomaly.clone(),
+        )),
+        session_store: session::create_session_store(&config.session, &config.workspace_dir)?,
+        session_config: config.session.clone(),
+        leak_guard: config.security.leak_guard.clone(),
         // WASM skill tools are sandboxed by the WASM engine and cannot access the
         // host filesystem, network, or shell. Pre-approve them so they are not
         // denied on non-CLI channels (which have no interactive stdin to prompt).
@@ -5039,9 +7048,75 @@ pub async fn start_channels(config: Config) -> Result<()> {
             }
             Arc::new(ApprovalManager::from_config(&autonomy))
         },
+        task_queue: channel_task_queue.clone(),
+        knowledge_base: crate::kb::KnowledgeBase::if_enabled(&config),
     });
 
-    run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
+    match channel_task_queue {
+        Some(queue) => {
+            tokio::spawn(resume_unfinished_tasks(
+                queue,
+                Arc::clone(&runtime_ctx.channels_by_name),
+                config.reliability.max_task_resumes,
+                resume_tx,
+            ));
+        }
+        None => drop(resume_tx),
+    }
+
+    if let Some(outbox) = outbox::outbox() {
+        tokio::spawn(outbox::run_retry_loop(
+            outbox,
+            Arc::clone(&runtime_ctx.channels_by_name),
+            stop.clone(),
+        ));
+    }
+
+    tokio::spawn(run_approval_expiry_notifier(
+        Arc::clone(&runtime_ctx),
+        stop.clone(),
+    ));
+
+    if config.attachments.gc_interval_minutes > 0 {
+        tokio::spawn(attachments::run_gc_loop(
+            config.workspace_dir.clone(),
+            Duration::from_secs(config.attachments.gc_interval_minutes.saturating_mul(60)),
+            stop.clone(),
+        ));
+    }
+
+    if let Some(store) = runtime_ctx.session_store.as_ref() {
+        if config.session.maintenance_interval_minutes > 0 {
+            tokio::spawn(session::run_maintenance_loop(
+                Arc::clone(store),
+                Arc::clone(&runtime_ctx.observer),
+                Duration::from_secs(
+                    config
+                        .session
+                        .maintenance_interval_minutes
+                        .saturating_mul(60),
+                ),
+                stop.clone(),
+            ));
+        }
+    }
+
+    admin::register_live_runtime(&runtime_ctx);
+    run_message_dispatch_loop(
+        rx,
+        runtime_ctx,
+        max_in_flight_messages,
+        stop.clone(),
+        Duration::from_secs(config.reliability.shutdown_grace_secs),
+    )
+    .await;
+
+    // Listeners never stop on their own; once stopped they are torn down here.
+    if stop.is_cancelled() {
+        for h in &handles {
+            h.abort();
+        }
+    }
 
     // Wait for all channel tasks
     for h in handles {
@@ -5049,6 +7124,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
     }
 
     clear_live_channels();
+    admin::clear_live_runtime();
 
     Ok(())
 }
@@ -5117,6 +7193,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_runtime_command_parses_workflow_commands() {
+        assert_eq!(
+            parse_runtime_command("telegram", "/workflows"),
+            Some(ChannelRuntimeCommand::ListWorkflows)
+        );
+        assert_eq!(
+            parse_runtime_command("slack", "/workflow standup-digest"),
+            Some(ChannelRuntimeCommand::RunWorkflow(
+                "standup-digest".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_runtime_command_parses_quarantine_commands() {
+        assert_eq!(
+            parse_runtime_command("slack", "/quarantine"),
+            Some(ChannelRuntimeCommand::ListQuarantined)
+        );
+        assert_eq!(
+            parse_runtime_command("slack", "/quarantine-release-original qtn-deadbeef"),
+            Some(ChannelRuntimeCommand::ReviewQuarantined(
+                "qtn-deadbeef".to_string(),
+                QuarantineDecision::ApproveOriginal
+            ))
+        );
+        assert!(is_approval_management_command(
+            &ChannelRuntimeCommand::ReviewQuarantined(
+                "qtn-deadbeef".to_string(),
+                QuarantineDecision::Discard
+            )
+        ));
+    }
+
+    #[test]
+    fn parse_runtime_command_parses_budget_commands() {
+        assert_eq!(
+            parse_runtime_command("telegram", "/budget"),
+            Some(ChannelRuntimeCommand::ShowBudget)
+        );
+        let command = parse_runtime_command("telegram", "/budget-override telegram:alice");
+        assert_eq!(
+            command,
+            Some(ChannelRuntimeCommand::OverrideBudget(
+                "telegram:alice".to_string()
+            ))
+        );
+        assert!(is_approval_management_command(&command.unwrap()));
+        assert!(!is_approval_management_command(
+            &ChannelRuntimeCommand::ShowBudget
+        ));
+    }
+
+    #[test]
+    fn parse_runtime_command_parses_persona() {
+        assert_eq!(
+            parse_runtime_command("telegram", "/persona reviewer"),
+            Some(ChannelRuntimeCommand::Persona("reviewer".to_string()))
+        );
+        assert_eq!(
+            parse_runtime_command("github", "/persona"),
+            Some(ChannelRuntimeCommand::Persona(String::new()))
+        );
+    }
+
+    #[test]
+    fn parse_runtime_command_parses_undo() {
+        let command = parse_runtime_command("discord", "/undo");
+        assert_eq!(command, Some(ChannelRuntimeCommand::UndoLastTurn));
+        assert!(!is_approval_management_command(&command.unwrap()));
+    }
+
     #[test]
     fn parse_runtime_command_allows_approval_commands_on_non_model_channels() {
         assert_eq!(
@@ -5163,6 +7312,22 @@ mod tests {
             parse_runtime_command("slack", "/approvals"),
             Some(ChannelRuntimeCommand::ListApprovals)
         );
+        assert_eq!(
+            parse_runtime_command("slack", "/tools"),
+            Some(ChannelRuntimeCommand::ListTools)
+        );
+        assert_eq!(
+            parse_runtime_command("slack", "/pin reply in French"),
+            Some(ChannelRuntimeCommand::Pin("reply in French".to_string()))
+        );
+        assert_eq!(
+            parse_runtime_command("slack", "/unpin 2"),
+            Some(ChannelRuntimeCommand::Unpin("2".to_string()))
+        );
+        assert_eq!(
+            parse_runtime_command("slack", "/pins"),
+            Some(ChannelRuntimeCommand::ListPins)
+        );
         assert_eq!(parse_runtime_command("slack", "/models"), None);
     }
 
@@ -5347,9 +7512,14 @@ mod tests {
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(histories)),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -5364,9 +7534,18 @@ mod tests {
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         };
 
         assert!(compact_sender_history(&ctx, &sender));
@@ -5401,9 +7580,14 @@ mod tests {
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -5418,9 +7602,18 @@ mod tests {
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         };
 
         append_sender_turn(&ctx, &sender, ChatMessage::user("hello"));
@@ -5458,9 +7651,14 @@ mod tests {
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(histories)),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -5475,9 +7673,18 @@ mod tests {
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         };
 
         assert!(rollback_orphan_user_turn(&ctx, &sender, "pending"));
@@ -5494,6 +7701,166 @@ mod tests {
         assert_eq!(turns[1].content, "ok");
     }
 
+    #[tokio::test]
+    async fn summarize_sender_history_folds_oldest_batch_at_user_boundary() {
+        let sender = "telegram_u4".to_string();
+        let ctx = ChannelRuntimeContext {
+            channels_by_name: Arc::new(HashMap::new()),
+            provider: Arc::new(DummyProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("system".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig {
+                max_messages: 6,
+                history_strategy: crate::config::SessionHistoryStrategy::Summarize,
+                summarize_batch: 3,
+                ..crate::config::SessionConfig::default()
+            },
+            leak_guard: crate::config::LeakGuardConfig::default(),
+            approval_manager: Arc::new(ApprovalManager::from_config(
+                &crate::config::AutonomyConfig::default(),
+            )),
+            task_queue: None,
+            knowledge_base: None,
+        };
+
+        for i in 0..4 {
+            append_sender_turn(&ctx, &sender, ChatMessage::user(format!("q{i}")));
+            append_sender_turn(&ctx, &sender, ChatMessage::assistant(format!("a{i}")));
+        }
+
+        assert!(summarize_sender_history(&ctx, &sender, &DummyProvider, "test-model").await);
+
+        let turns = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&sender)
+            .cloned()
+            .expect("sender history should exist");
+        // The batch of 3 is extended to 4 so the next kept turn is a user turn.
+        assert_eq!(turns.len(), 5);
+        assert_eq!(turns[0].role, "user");
+        assert!(turns[0].content.starts_with(CHANNEL_HISTORY_SUMMARY_PREFIX));
+        assert_eq!(turns[1].content, "q2");
+        assert_eq!(turns[4].content, "a3");
+
+        // Back under the limit, nothing more to summarize.
+        assert!(!summarize_sender_history(&ctx, &sender, &DummyProvider, "test-model").await);
+    }
+
+    #[test]
+    fn pinned_messages_survive_history_reset_and_respect_cap() {
+        let sender = "telegram_u5".to_string();
+        let ctx = ChannelRuntimeContext {
+            channels_by_name: Arc::new(HashMap::new()),
+            provider: Arc::new(DummyProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("system".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig {
+                max_pinned: 2,
+                ..crate::config::SessionConfig::default()
+            },
+            leak_guard: crate::config::LeakGuardConfig::default(),
+            approval_manager: Arc::new(ApprovalManager::from_config(
+                &crate::config::AutonomyConfig::default(),
+            )),
+            task_queue: None,
+            knowledge_base: None,
+        };
+
+        append_sender_turn(
+            &ctx,
+            &sender,
+            ChatMessage::user("[2026-01-01 10:00:00 UTC] deploy target is eu-west-1"),
+        );
+        assert!(pin_sender_message(&ctx, &sender, "").starts_with("Pinned (1/2)"));
+        assert!(pin_sender_message(&ctx, &sender, "reply in French").starts_with("Pinned (2/2)"));
+        assert!(pin_sender_message(&ctx, &sender, "one more").contains("limit reached"));
+        assert_eq!(
+            sender_pins(&ctx, &sender),
+            vec!["deploy target is eu-west-1", "reply in French"]
+        );
+
+        clear_sender_history(&ctx, &sender);
+        let prompt = build_pinned_context_prompt(&sender_pins(&ctx, &sender));
+        assert!(prompt.contains("1. deploy target is eu-west-1"));
+        assert!(prompt.contains("2. reply in French"));
+
+        assert_eq!(
+            unpin_sender_message(&ctx, &sender, "1"),
+            "Unpinned message 1."
+        );
+        assert_eq!(sender_pins(&ctx, &sender), vec!["reply in French"]);
+        assert!(unpin_sender_message(&ctx, &sender, "all").starts_with("Removed 1"));
+        assert!(build_pinned_context_prompt(&sender_pins(&ctx, &sender)).is_empty());
+    }
+
     struct DummyProvider;
 
     #[async_trait::async_trait]
@@ -6056,9 +8423,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -6073,9 +8445,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(vec!["mock_price".to_string()])),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -6114,6 +8495,91 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(sent[0].contains("response-1"));
     }
 
+    #[tokio::test]
+    async fn admin_lists_and_clears_sender_sessions() {
+        let provider: Arc<dyn Provider> = Arc::new(HistoryCaptureProvider::default());
+        let mut histories = HashMap::new();
+        histories.insert(
+            "telegram_alice".to_string(),
+            vec![ChatMessage::user("hi"), ChatMessage::assistant("hello")],
+        );
+        histories.insert("discord_bob".to_string(), vec![ChatMessage::user("ping")]);
+        let mut pins = HashMap::new();
+        pins.insert(
+            "telegram_alice".to_string(),
+            vec!["deploy on fridays".to_string()],
+        );
+
+        let runtime_ctx = ChannelRuntimeContext {
+            channels_by_name: Arc::new(HashMap::new()),
+            provider: Arc::clone(&provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(histories)),
+            pinned_messages: Arc::new(Mutex::new(pins)),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
+            approval_manager: Arc::new(ApprovalManager::from_config(
+                &crate::config::AutonomyConfig::default(),
+            )),
+            task_queue: None,
+            knowledge_base: None,
+        };
+
+        let summaries = admin::session_summaries(&runtime_ctx);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].key, "discord_bob");
+        assert_eq!(summaries[1].key, "telegram_alice");
+        assert_eq!(summaries[1].messages, 2);
+        assert_eq!(summaries[1].pinned, 1);
+
+        assert!(admin::clear_session_for(&runtime_ctx, "telegram_alice")
+            .await
+            .unwrap());
+        assert!(!admin::clear_session_for(&runtime_ctx, "telegram_alice")
+            .await
+            .unwrap());
+        assert!(sender_pins(&runtime_ctx, "telegram_alice").is_empty());
+        let remaining = admin::session_summaries(&runtime_ctx);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].key, "discord_bob");
+
+        let approvals = admin::approval_state(&runtime_ctx);
+        assert!(approvals.session_grants.is_empty());
+        assert!(approvals.pending.is_empty());
+    }
+
     #[tokio::test]
     async fn process_channel_message_executes_tool_calls_instead_of_sending_raw_json() {
         let channel_impl = Arc::new(RecordingChannel::default());
@@ -6133,9 +8599,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -6148,11 +8619,20 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -6197,9 +8677,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -6212,11 +8697,20 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -6275,9 +8769,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -6295,6 +8794,15 @@ BTC is currently around $65,000 based on latest tool output."#
             hooks: None,
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -6352,9 +8860,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -6372,6 +8885,15 @@ BTC is currently around $65,000 based on latest tool output."#
             hooks: None,
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -6421,9 +8943,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -6438,9 +8965,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -6485,9 +9021,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -6502,9 +9043,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -6558,9 +9108,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -6575,9 +9130,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -6659,9 +9223,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -6679,7 +9248,16 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(vec!["mock_price".to_string()])),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
+            task_queue: None,
+            knowledge_base: None,
         });
         assert_eq!(
             runtime_ctx
@@ -6721,7 +9299,9 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(runtime_ctx
             .approval_manager
             .is_non_cli_session_granted("mock_price"));
-        assert!(!runtime_ctx.approval_manager.needs_approval("mock_price"));
+        assert!(!runtime_ctx
+            .approval_manager
+            .needs_approval("mock_price", "telegram"));
         assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 0);
 
         let saved_raw = tokio::fs::read_to_string(&config_path)
@@ -6809,9 +9389,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -6829,7 +9414,16 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
+            task_queue: None,
+            knowledge_base: None,
         });
         assert_eq!(
             runtime_ctx
@@ -6860,7 +9454,9 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(!runtime_ctx
             .approval_manager
             .is_non_cli_session_granted("mock_price"));
-        assert!(runtime_ctx.approval_manager.needs_approval("mock_price"));
+        assert!(runtime_ctx
+            .approval_manager
+            .needs_approval("mock_price", "telegram"));
         assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 0);
 
         let saved_raw = tokio::fs::read_to_string(&config_path)
@@ -6919,9 +9515,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -6939,7 +9540,16 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager,
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -6964,7 +9574,9 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(!runtime_ctx
             .approval_manager
             .is_non_cli_session_granted("mock_price"));
-        assert!(runtime_ctx.approval_manager.needs_approval("mock_price"));
+        assert!(runtime_ctx
+            .approval_manager
+            .needs_approval("mock_price", "telegram"));
         assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 0);
 
         let saved_raw = tokio::fs::read_to_string(&config_path)
@@ -7024,9 +9636,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -7044,7 +9661,16 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(vec!["shell".to_string()])),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager,
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -7120,9 +9746,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -7140,7 +9771,16 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(vec!["mock_price".to_string()])),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -7199,7 +9839,9 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(runtime_ctx
             .approval_manager
             .is_non_cli_session_granted("mock_price"));
-        assert!(!runtime_ctx.approval_manager.needs_approval("mock_price"));
+        assert!(!runtime_ctx
+            .approval_manager
+            .needs_approval("mock_price", "telegram"));
         assert!(runtime_ctx
             .approval_manager
             .list_non_cli_pending_requests(Some("alice"), Some("telegram"), Some("chat-1"))
@@ -7268,9 +9910,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -7288,7 +9935,16 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -7413,9 +10069,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -7433,7 +10094,16 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -7458,7 +10128,9 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(runtime_ctx
             .approval_manager
             .is_non_cli_session_granted("mock_price"));
-        assert!(!runtime_ctx.approval_manager.needs_approval("mock_price"));
+        assert!(!runtime_ctx
+            .approval_manager
+            .needs_approval("mock_price", "telegram"));
         assert!(runtime_ctx
             .approval_manager
             .list_non_cli_pending_requests(Some("alice"), Some("telegram"), Some("chat-1"))
@@ -7528,9 +10200,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -7548,7 +10225,16 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -7576,7 +10262,9 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(!runtime_ctx
             .approval_manager
             .is_non_cli_session_granted("mock_price"));
-        assert!(runtime_ctx.approval_manager.needs_approval("mock_price"));
+        assert!(runtime_ctx
+            .approval_manager
+            .needs_approval("mock_price", "telegram"));
         assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 0);
     }
 
@@ -7623,9 +10311,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -7643,7 +10336,16 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -7673,7 +10375,9 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(!runtime_ctx
             .approval_manager
             .is_non_cli_session_granted("mock_price"));
-        assert!(runtime_ctx.approval_manager.needs_approval("mock_price"));
+        assert!(runtime_ctx
+            .approval_manager
+            .needs_approval("mock_price", "telegram"));
 
         process_channel_message(
             runtime_ctx.clone(),
@@ -7696,7 +10400,9 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(runtime_ctx
             .approval_manager
             .is_non_cli_session_granted("mock_price"));
-        assert!(!runtime_ctx.approval_manager.needs_approval("mock_price"));
+        assert!(!runtime_ctx
+            .approval_manager
+            .needs_approval("mock_price", "telegram"));
         assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 0);
 
         let saved_raw = tokio::fs::read_to_string(&config_path)
@@ -7740,9 +10446,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -7757,7 +10468,16 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -7855,9 +10575,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(route_overrides)),
             api_key: None,
@@ -7872,9 +10597,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -7931,9 +10665,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -7948,9 +10687,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -8020,9 +10768,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("startup-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -8040,9 +10793,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -8165,9 +10927,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("llama3.2".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: Some("http://127.0.0.1:11434".to_string()),
@@ -8185,9 +10952,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         maybe_apply_runtime_config_update(runtime_ctx.as_ref())
@@ -8271,9 +11047,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 12,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -8288,9 +11069,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -8336,9 +11126,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 3,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -8353,9 +11148,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -8513,9 +11317,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -8530,9 +11339,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -8561,7 +11379,14 @@ BTC is currently around $65,000 based on latest tool output."#
         drop(tx);
 
         let started = Instant::now();
-        run_message_dispatch_loop(rx, runtime_ctx, 2).await;
+        run_message_dispatch_loop(
+            rx,
+            runtime_ctx,
+            2,
+            CancellationToken::new(),
+            Duration::from_secs(30),
+        )
+        .await;
         let elapsed = started.elapsed();
 
         assert!(
@@ -8574,6 +11399,217 @@ BTC is currently around $65,000 based on latest tool output."#
         assert_eq!(sent_messages.len(), 2);
     }
 
+    #[tokio::test]
+    async fn message_dispatch_drains_in_flight_work_and_persists_sessions_on_shutdown() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let tmp = TempDir::new().unwrap();
+        let session_config = crate::config::SessionConfig::default();
+        let store: Arc<dyn session::SessionStore> =
+            Arc::new(session::SqliteSessionStore::new(tmp.path(), &session_config).unwrap());
+        // Held only in memory until the shutdown flush.
+        let mut histories = HashMap::new();
+        histories.insert(
+            "test-channel_carol".to_string(),
+            vec![
+                ChatMessage::user("earlier"),
+                ChatMessage::assistant("noted"),
+            ],
+        );
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::new(SlowProvider {
+                delay: Duration::from_millis(200),
+            }),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
+            max_tool_iterations: 10,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(histories)),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: Some(Arc::clone(&store)),
+            session_config,
+            leak_guard: crate::config::LeakGuardConfig::default(),
+            approval_manager: Arc::new(ApprovalManager::from_config(
+                &crate::config::AutonomyConfig::default(),
+            )),
+            task_queue: None,
+            knowledge_base: None,
+        });
+
+        // The sender stays open: only the shutdown token ends the loop.
+        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
+        tx.send(traits::ChannelMessage {
+            id: "1".to_string(),
+            sender: "alice".to_string(),
+            reply_target: "alice".to_string(),
+            content: "hello".to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 1,
+            thread_ts: None,
+        })
+        .await
+        .unwrap();
+
+        let shutdown = CancellationToken::new();
+        let dispatch = tokio::spawn(run_message_dispatch_loop(
+            rx,
+            runtime_ctx,
+            2,
+            shutdown.clone(),
+            Duration::from_secs(5),
+        ));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        shutdown.cancel();
+
+        tokio::time::timeout(Duration::from_secs(3), dispatch)
+            .await
+            .expect("dispatch loop should stop after shutdown")
+            .unwrap();
+
+        // The in-flight turn finished instead of being cut off.
+        assert_eq!(channel_impl.sent_messages.lock().await.len(), 1);
+        let carol = store.load("test-channel_carol").await.unwrap().unwrap();
+        assert_eq!(carol.len(), 2);
+        assert!(store.load("test-channel_alice").await.unwrap().is_some());
+        drop(tx);
+    }
+
+    #[tokio::test]
+    async fn message_dispatch_keeps_turns_cut_off_by_shutdown_for_resume() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+        let channels_by_name = Arc::new(channels_by_name);
+
+        let tmp = TempDir::new().unwrap();
+        let queue = Arc::new(task_queue::ChannelTaskQueue::open(tmp.path()).unwrap());
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::clone(&channels_by_name),
+            provider: Arc::new(SlowProvider {
+                delay: Duration::from_secs(10),
+            }),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
+            max_tool_iterations: 10,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
+            approval_manager: Arc::new(ApprovalManager::from_config(
+                &crate::config::AutonomyConfig::default(),
+            )),
+            task_queue: Some(Arc::clone(&queue)),
+            knowledge_base: None,
+        });
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
+        tx.send(traits::ChannelMessage {
+            id: "1".to_string(),
+            sender: "alice".to_string(),
+            reply_target: "alice".to_string(),
+            content: "long job".to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 1,
+            thread_ts: None,
+        })
+        .await
+        .unwrap();
+
+        // No grace period: the slow turn is cancelled as soon as shutdown starts.
+        let shutdown = CancellationToken::new();
+        let dispatch = tokio::spawn(run_message_dispatch_loop(
+            rx,
+            runtime_ctx,
+            2,
+            shutdown.clone(),
+            Duration::ZERO,
+        ));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        shutdown.cancel();
+        tokio::time::timeout(Duration::from_secs(5), dispatch)
+            .await
+            .expect("dispatch loop should stop after shutdown")
+            .unwrap();
+        drop(tx);
+
+        // Next start: the sender is told and the message re-enters the bus.
+        let (resume_tx, mut resume_rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
+        resume_unfinished_tasks(Arc::clone(&queue), channels_by_name, 2, resume_tx).await;
+        let resumed = resume_rx.recv().await.unwrap();
+        assert_eq!(resumed.content, "long job");
+        assert!(resume_rx.recv().await.is_none());
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert!(sent.last().unwrap().contains("Resuming your request"));
+    }
+
     #[tokio::test]
     async fn message_dispatch_interrupts_in_flight_telegram_request_and_preserves_context() {
         let channel_impl = Arc::new(TelegramRecordingChannel::default());
@@ -8598,9 +11634,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -8615,9 +11656,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -8647,7 +11697,14 @@ BTC is currently around $65,000 based on latest tool output."#
             .unwrap();
         });
 
-        run_message_dispatch_loop(rx, runtime_ctx, 4).await;
+        run_message_dispatch_loop(
+            rx,
+            runtime_ctx,
+            4,
+            CancellationToken::new(),
+            Duration::from_secs(30),
+        )
+        .await;
         send_task.await.unwrap();
 
         let sent_messages = channel_impl.sent_messages.lock().await;
@@ -8695,9 +11752,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -8712,9 +11774,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -8744,7 +11815,14 @@ BTC is currently around $65,000 based on latest tool output."#
             .unwrap();
         });
 
-        run_message_dispatch_loop(rx, runtime_ctx, 4).await;
+        run_message_dispatch_loop(
+            rx,
+            runtime_ctx,
+            4,
+            CancellationToken::new(),
+            Duration::from_secs(30),
+        )
+        .await;
         send_task.await.unwrap();
 
         let sent_messages = channel_impl.sent_messages.lock().await;
@@ -8774,9 +11852,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -8791,9 +11874,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -8838,9 +11930,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -8855,9 +11952,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -9333,7 +12439,7 @@ BTC is currently around $65,000 based on latest tool output."#
             .await
             .unwrap();
 
-        let context = build_memory_context(&mem, "age", 0.0).await;
+        let context = build_memory_context(&mem, "age", 0.0, None).await;
         assert!(context.contains("[Memory context]"));
         assert!(context.contains("Age is 45"));
     }
@@ -9359,9 +12465,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -9376,9 +12487,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -9449,9 +12569,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -9466,9 +12591,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -9539,9 +12673,14 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(histories)),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -9556,9 +12695,18 @@ BTC is currently around $65,000 based on latest tool output."#
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
@@ -9719,12 +12867,12 @@ Done reminder set for 1:38 AM."#;
     }
 
     #[test]
-    fn build_channel_system_prompt_includes_visibility_policy() {
-        let hidden = build_channel_system_prompt("base", "telegram", "chat", false);
+    fn build_channel_context_prompt_includes_visibility_policy() {
+        let hidden = build_channel_context_prompt("telegram", "chat", false);
         assert!(hidden.contains("run tools/functions in the background"));
         assert!(hidden.contains("Do not reveal raw tool names"));
 
-        let exposed = build_channel_system_prompt("base", "telegram", "chat", true);
+        let exposed = build_channel_context_prompt("telegram", "chat", true);
         assert!(exposed.contains("user explicitly requested command/tool details"));
     }
 
@@ -10262,9 +13410,14 @@ BTC is currently around $65,000 based on latest tool output."#;
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -10279,9 +13432,18 @@ BTC is currently around $65,000 based on latest tool output."#;
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         // Simulate a photo attachment message with [IMAGE:] marker.
@@ -10333,9 +13495,14 @@ BTC is currently around $65,000 based on latest tool output."#;
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_principal_isolation: false,
+            memory_access: crate::config::MemoryAccessConfig::default(),
+            memory_fact_extraction: false,
+            usage_ledger: None,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pinned_messages: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -10350,9 +13517,18 @@ BTC is currently around $65,000 based on latest tool output."#;
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            content_injection: crate::config::ContentInjectionConfig::default(),
+            tool_anomaly: Arc::new(crate::security::ToolAnomalyMonitor::new(
+                crate::config::ToolAnomalyConfig::default(),
+            )),
+            session_store: None,
+            session_config: crate::config::SessionConfig::default(),
+            leak_guard: crate::config::LeakGuardConfig::default(),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
+            task_queue: None,
+            knowledge_base: None,
         });
 
         process_channel_message(
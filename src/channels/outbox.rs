@@ -0,0 +1,585 @@
+//! Persistent outbound queue for agent replies.
+//!
+//! With `reliability.outbound_queue` on, every reply is written to
+//! `<workspace>/state/channel_outbox.db` before it is sent and marked
+//! delivered once the platform accepts it. A failed send stays pending and
+//! is retried in the background with exponential backoff
+//! (`outbound_retry_backoff_secs`, doubling up to an hour). After
+//! `outbound_max_attempts` failures the reply moves to the dead-letter list,
+//! where `zeroclaw channel outbox` and `/api/admin/outbox` show it and it
+//! can be queued again or discarded. Replies still pending when the daemon
+//! stops are sent after the next start.
+
+use super::traits::{Channel, SendMessage};
+use crate::config::ReliabilityConfig;
+use crate::db::{migrate, Migration, Schema, Step};
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Delivered rows are kept this long as receipts before being pruned.
+const DELIVERED_RETENTION_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Upper bound for the delay between two attempts.
+const MAX_RETRY_BACKOFF_SECS: u64 = 60 * 60;
+
+/// How often the retry loop looks for due replies.
+const RETRY_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Replies retried per poll.
+const RETRY_BATCH: usize = 20;
+
+/// Delivery state of an outbound reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboundStatus {
+    Pending,
+    Delivered,
+    Dead,
+}
+
+impl OutboundStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::Dead => "dead",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "pending" => Some(Self::Pending),
+            "delivered" => Some(Self::Delivered),
+            "dead" => Some(Self::Dead),
+            _ => None,
+        }
+    }
+}
+
+/// One recorded reply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutboundEntry {
+    pub id: i64,
+    pub channel: String,
+    pub recipient: String,
+    pub content: String,
+    pub thread_ts: Option<String>,
+    pub status: OutboundStatus,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    /// Unix seconds of the next retry while pending.
+    pub next_attempt_at: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl OutboundEntry {
+    fn message(&self) -> SendMessage {
+        SendMessage::new(&self.content, &self.recipient).in_thread(self.thread_ts.clone())
+    }
+}
+
+/// Tables of `state/channel_outbox.db`.
+pub static SCHEMA: Schema = Schema {
+    name: "channel_outbox",
+    path: "state/channel_outbox.db",
+    migrations: &[Migration {
+        version: 1,
+        description: "create channel_outbox",
+        step: Step::Sql(
+            "CREATE TABLE IF NOT EXISTS channel_outbox (
+                 id              INTEGER PRIMARY KEY AUTOINCREMENT,
+                 channel         TEXT NOT NULL,
+                 recipient       TEXT NOT NULL,
+                 content         TEXT NOT NULL,
+                 thread_ts       TEXT,
+                 status          TEXT NOT NULL,
+                 attempts        INTEGER NOT NULL DEFAULT 0,
+                 last_error      TEXT,
+                 next_attempt_at INTEGER NOT NULL,
+                 created_at      INTEGER NOT NULL,
+                 updated_at      INTEGER NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_channel_outbox_due
+                 ON channel_outbox(status, next_attempt_at);",
+        ),
+    }],
+};
+
+pub struct ChannelOutbox {
+    conn: Arc<Mutex<Connection>>,
+    max_attempts: u32,
+    backoff_secs: u64,
+}
+
+impl ChannelOutbox {
+    pub fn open(workspace_dir: &Path, config: &ReliabilityConfig) -> Result<Self> {
+        let db_path = Self::db_path(workspace_dir);
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        migrate(&conn, &SCHEMA)?;
+        conn.execute(
+            "DELETE FROM channel_outbox WHERE status = 'delivered' AND updated_at < ?1",
+            params![now_secs() - DELIVERED_RETENTION_SECS],
+        )?;
+
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            max_attempts: config.outbound_max_attempts.max(1),
+            backoff_secs: config.outbound_retry_backoff_secs.max(1),
+        })
+    }
+
+    pub fn db_path(workspace_dir: &Path) -> PathBuf {
+        SCHEMA.db_path(workspace_dir)
+    }
+
+    async fn with_conn<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T> + Send + 'static,
+    {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || f(&conn.lock())).await?
+    }
+
+    /// Record `message` as pending for `channel`. The caller sends it right
+    /// away; the retry loop only picks it up if no outcome is recorded within
+    /// one backoff period (the process died mid-send).
+    pub async fn enqueue(&self, channel: &str, message: &SendMessage) -> Result<i64> {
+        let channel = channel.to_string();
+        let message = message.clone();
+        let lease = i64::try_from(self.backoff_secs).unwrap_or(i64::MAX);
+        self.with_conn(move |conn| {
+            let now = now_secs();
+            conn.execute(
+                "INSERT INTO channel_outbox
+                     (channel, recipient, content, thread_ts, status, attempts,
+                      next_attempt_at, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, 'pending', 0, ?5, ?6, ?6)",
+                params![
+                    channel,
+                    message.recipient,
+                    message.content,
+                    message.thread_ts,
+                    now.saturating_add(lease),
+                    now
+                ],
+            )?;
+            Ok(conn.last_insert_rowid())
+        })
+        .await
+    }
+
+    pub async fn mark_delivered(&self, id: i64) -> Result<()> {
+        self.with_conn(move |conn| {
+            conn.execute(
+                "UPDATE channel_outbox
+                 SET status = 'delivered', attempts = attempts + 1, last_error = NULL,
+                     updated_at = ?1
+                 WHERE id = ?2",
+                params![now_secs(), id],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Count a failed attempt. The reply is scheduled for another attempt,
+    /// or moved to the dead-letter list once it has used up its attempts.
+    pub async fn record_failure(&self, id: i64, error: &str) -> Result<OutboundStatus> {
+        let error = crate::providers::sanitize_api_error(error);
+        let max_attempts = self.max_attempts;
+        let backoff_secs = self.backoff_secs;
+        self.with_conn(move |conn| {
+            let attempts: u32 = conn.query_row(
+                "SELECT attempts FROM channel_outbox WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )?;
+            let attempts = attempts.saturating_add(1);
+            let status = if attempts >= max_attempts {
+                OutboundStatus::Dead
+            } else {
+                OutboundStatus::Pending
+            };
+            let now = now_secs();
+            let delay = retry_delay_secs(backoff_secs, attempts);
+            conn.execute(
+                "UPDATE channel_outbox
+                 SET status = ?1, attempts = ?2, last_error = ?3, next_attempt_at = ?4,
+                     updated_at = ?5
+                 WHERE id = ?6",
+                params![
+                    status.as_str(),
+                    attempts,
+                    error,
+                    now.saturating_add(i64::try_from(delay).unwrap_or(i64::MAX)),
+                    now,
+                    id
+                ],
+            )?;
+            Ok(status)
+        })
+        .await
+    }
+
+    /// Pending replies whose next attempt is due, oldest first.
+    pub async fn due(&self, limit: usize) -> Result<Vec<OutboundEntry>> {
+        self.with_conn(move |conn| {
+            query_entries(
+                conn,
+                "WHERE status = 'pending' AND next_attempt_at <= ?1 ORDER BY id LIMIT ?2",
+                params![now_secs(), i64::try_from(limit).unwrap_or(i64::MAX)],
+            )
+        })
+        .await
+    }
+
+    /// Recorded replies with `status` (all when `None`), newest first.
+    pub async fn list(
+        &self,
+        status: Option<OutboundStatus>,
+        limit: usize,
+    ) -> Result<Vec<OutboundEntry>> {
+        self.with_conn(move |conn| {
+            let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+            match status {
+                Some(status) => query_entries(
+                    conn,
+                    "WHERE status = ?1 ORDER BY id DESC LIMIT ?2",
+                    params![status.as_str(), limit],
+                ),
+                None => query_entries(conn, "ORDER BY id DESC LIMIT ?1", params![limit]),
+            }
+        })
+        .await
+    }
+
+    /// Queue a dead or pending reply for an immediate attempt with a fresh
+    /// attempt count. Returns the entry, or `None` for an unknown or
+    /// already delivered id.
+    pub async fn requeue(&self, id: i64) -> Result<Option<OutboundEntry>> {
+        self.with_conn(move |conn| {
+            let now = now_secs();
+            let updated = conn.execute(
+                "UPDATE channel_outbox
+                 SET status = 'pending', attempts = 0, next_attempt_at = ?1, updated_at = ?1
+                 WHERE id = ?2 AND status != 'delivered'",
+                params![now, id],
+            )?;
+            if updated == 0 {
+                return Ok(None);
+            }
+            Ok(query_entries(conn, "WHERE id = ?1", params![id])?
+                .into_iter()
+                .next())
+        })
+        .await
+    }
+
+    /// Drop an undelivered reply. Returns whether one was removed.
+    pub async fn discard(&self, id: i64) -> Result<bool> {
+        self.with_conn(move |conn| {
+            let removed = conn.execute(
+                "DELETE FROM channel_outbox WHERE id = ?1 AND status != 'delivered'",
+                params![id],
+            )?;
+            Ok(removed > 0)
+        })
+        .await
+    }
+
+    /// Status of one reply, if it is still recorded.
+    pub async fn status(&self, id: i64) -> Result<Option<OutboundStatus>> {
+        self.with_conn(move |conn| {
+            let raw: Option<String> = conn
+                .query_row(
+                    "SELECT status FROM channel_outbox WHERE id = ?1",
+                    params![id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            Ok(raw.as_deref().and_then(OutboundStatus::parse))
+        })
+        .await
+    }
+
+    /// Send `message` now and record the outcome.
+    pub async fn deliver(&self, channel: &dyn Channel, message: &SendMessage) -> Result<()> {
+        let id = match self.enqueue(channel.name(), message).await {
+            Ok(id) => id,
+            Err(e) => {
+                tracing::warn!("Failed to record outbound reply: {e}");
+                return channel.send(message).await;
+            }
+        };
+        self.attempt(channel, id, message).await
+    }
+
+    async fn attempt(&self, channel: &dyn Channel, id: i64, message: &SendMessage) -> Result<()> {
+        match channel.send(message).await {
+            Ok(()) => {
+                if let Err(e) = self.mark_delivered(id).await {
+                    tracing::warn!(outbox_id = id, "Failed to record delivery receipt: {e}");
+                }
+                Ok(())
+            }
+            Err(send_error) => {
+                match self.record_failure(id, &send_error.to_string()).await {
+                    Ok(OutboundStatus::Dead) => tracing::error!(
+                        outbox_id = id,
+                        channel = %channel.name(),
+                        "Outbound reply moved to the dead-letter list: {send_error}"
+                    ),
+                    Ok(_) => tracing::warn!(
+                        outbox_id = id,
+                        channel = %channel.name(),
+                        "Outbound reply failed and will be retried: {send_error}"
+                    ),
+                    Err(e) => tracing::warn!(outbox_id = id, "Failed to record send failure: {e}"),
+                }
+                Err(send_error)
+            }
+        }
+    }
+
+    /// Attempt every due reply once.
+    pub async fn retry_due(&self, channels_by_name: &HashMap<String, Arc<dyn Channel>>) {
+        let entries = match self.due(RETRY_BATCH).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Failed to load due outbound replies: {e}");
+                return;
+            }
+        };
+        for entry in entries {
+            let Some(channel) = channels_by_name.get(&entry.channel) else {
+                let error = format!("channel `{}` is not running", entry.channel);
+                if let Err(e) = self.record_failure(entry.id, &error).await {
+                    tracing::warn!(outbox_id = entry.id, "Failed to record send failure: {e}");
+                }
+                continue;
+            };
+            if self
+                .attempt(channel.as_ref(), entry.id, &entry.message())
+                .await
+                .is_ok()
+            {
+                tracing::info!(
+                    outbox_id = entry.id,
+                    channel = %entry.channel,
+                    attempts = entry.attempts + 1,
+                    "Delivered outbound reply on retry"
+                );
+            }
+        }
+    }
+}
+
+/// Retry due replies until `stop` fires.
+pub async fn run_retry_loop(
+    outbox: Arc<ChannelOutbox>,
+    channels_by_name: Arc<HashMap<String, Arc<dyn Channel>>>,
+    stop: CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(RETRY_POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            () = stop.cancelled() => return,
+            _ = ticker.tick() => {}
+        }
+        outbox.retry_due(&channels_by_name).await;
+    }
+}
+
+fn query_entries(
+    conn: &Connection,
+    clause: &str,
+    params: &[&dyn rusqlite::ToSql],
+) -> Result<Vec<OutboundEntry>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, channel, recipient, content, thread_ts, status, attempts, last_error,
+                next_attempt_at, created_at, updated_at
+         FROM channel_outbox {clause}"
+    ))?;
+    let entries = stmt
+        .query_map(params, |row| {
+            let status: String = row.get(5)?;
+            Ok(OutboundEntry {
+                id: row.get(0)?,
+                channel: row.get(1)?,
+                recipient: row.get(2)?,
+                content: row.get(3)?,
+                thread_ts: row.get(4)?,
+                status: OutboundStatus::parse(&status).unwrap_or(OutboundStatus::Pending),
+                attempts: row.get(6)?,
+                last_error: row.get(7)?,
+                next_attempt_at: row.get(8)?,
+                created_at: row.get(9)?,
+                updated_at: row.get(10)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(entries)
+}
+
+/// Delay before the attempt after `attempts` failures: the base backoff,
+/// doubled per earlier failure, capped at an hour.
+fn retry_delay_secs(backoff_secs: u64, attempts: u32) -> u64 {
+    let factor = 1u64
+        .checked_shl(attempts.saturating_sub(1))
+        .unwrap_or(u64::MAX);
+    backoff_secs
+        .saturating_mul(factor)
+        .min(MAX_RETRY_BACKOFF_SECS)
+}
+
+fn now_secs() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+static OUTBOX: LazyLock<RwLock<Option<Arc<ChannelOutbox>>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Open (or disable) the outbound queue for the channel runtime.
+pub fn init_from_config(config: &ReliabilityConfig, workspace_dir: &Path) {
+    let outbox = if config.outbound_queue {
+        match ChannelOutbox::open(workspace_dir, config) {
+            Ok(outbox) => Some(Arc::new(outbox)),
+            Err(e) => {
+                tracing::warn!("Failed to open channel outbox: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    *OUTBOX.write().unwrap_or_else(|e| e.into_inner()) = outbox;
+}
+
+/// The active outbound queue, if enabled.
+pub fn outbox() -> Option<Arc<ChannelOutbox>> {
+    OUTBOX.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Send an agent reply through the outbound queue when it is enabled, so a
+/// failed send is retried instead of lost.
+pub async fn deliver(channel: &dyn Channel, message: &SendMessage) -> Result<()> {
+    match outbox() {
+        Some(outbox) => outbox.deliver(channel, message).await,
+        None => channel.send(message).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::ChannelMessage;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    /// Fails the first `failures` sends.
+    struct FlakyChannel {
+        failures: usize,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Channel for FlakyChannel {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> Result<()> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                anyhow::bail!("platform unavailable");
+            }
+            Ok(())
+        }
+
+        async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn open_outbox(dir: &TempDir, max_attempts: u32) -> ChannelOutbox {
+        ChannelOutbox::open(
+            dir.path(),
+            &ReliabilityConfig {
+                outbound_max_attempts: max_attempts,
+                ..ReliabilityConfig::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn failed_replies_are_retried_until_dead() {
+        let tmp = TempDir::new().unwrap();
+        let outbox = open_outbox(&tmp, 2);
+        let channel = FlakyChannel {
+            failures: 5,
+            calls: AtomicUsize::new(0),
+        };
+
+        let message = SendMessage::new("hello", "chat-1").in_thread(Some("42".into()));
+        assert!(outbox.deliver(&channel, &message).await.is_err());
+        let pending = outbox
+            .list(Some(OutboundStatus::Pending), 10)
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].attempts, 1);
+        assert_eq!(pending[0].thread_ts.as_deref(), Some("42"));
+        assert!(pending[0].next_attempt_at > now_secs());
+        assert!(outbox.due(10).await.unwrap().is_empty());
+
+        let id = pending[0].id;
+        assert_eq!(
+            outbox.record_failure(id, "still down").await.unwrap(),
+            OutboundStatus::Dead
+        );
+        let dead = outbox.list(Some(OutboundStatus::Dead), 10).await.unwrap();
+        assert_eq!(dead[0].last_error.as_deref(), Some("still down"));
+
+        // Requeued dead letters are due at once and delivered by the retry pass.
+        outbox.requeue(id).await.unwrap().unwrap();
+        let healthy: Arc<dyn Channel> = Arc::new(FlakyChannel {
+            failures: 0,
+            calls: AtomicUsize::new(0),
+        });
+        outbox
+            .retry_due(&HashMap::from([("flaky".to_string(), healthy)]))
+            .await;
+        assert_eq!(
+            outbox.status(id).await.unwrap(),
+            Some(OutboundStatus::Delivered)
+        );
+        assert!(outbox.requeue(id).await.unwrap().is_none());
+        assert!(!outbox.discard(id).await.unwrap());
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        assert_eq!(retry_delay_secs(30, 1), 30);
+        assert_eq!(retry_delay_secs(30, 3), 120);
+        assert_eq!(retry_delay_secs(30, 40), MAX_RETRY_BACKOFF_SECS);
+    }
+}
@@ -0,0 +1,177 @@
+//! Conversation personas.
+//!
+//! A persona bundles extra system-prompt instructions, a reply tone, an
+//! allowed tool subset and optionally its own provider/model, so one daemon
+//! can review code strictly on one channel and chat casually on another.
+//! Each chat uses the persona picked with `/persona <name>`, otherwise the
+//! one configured for its channel in `[personas].by_channel`.
+
+use crate::config::{PersonaConfig, PersonasConfig};
+use crate::workspace::Scoped;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, LazyLock};
+
+static PERSONAS: LazyLock<Scoped<Arc<PersonasConfig>>> =
+    LazyLock::new(|| Scoped::new(Arc::new(PersonasConfig::default())));
+
+/// Per-chat choices keyed by conversation history key; `None` switches the
+/// channel's persona off for that chat.
+static SELECTIONS: LazyLock<Mutex<HashMap<String, Option<String>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Apply `[personas]`.
+pub fn init_from_config(config: &PersonasConfig) {
+    PERSONAS.set(Arc::new(config.clone()));
+}
+
+/// The persona a chat is using.
+#[derive(Debug, Clone)]
+pub struct ActivePersona {
+    pub name: String,
+    pub config: PersonaConfig,
+}
+
+impl ActivePersona {
+    /// System prompt section for the persona.
+    pub fn prompt(&self) -> String {
+        let mut prompt = format!("## Persona: {}\n", self.name);
+        let instructions = self.config.system_prompt.trim();
+        if !instructions.is_empty() {
+            let _ = write!(prompt, "\n{instructions}\n");
+        }
+        if let Some(tone) = self.config.tone.as_deref().map(str::trim) {
+            if !tone.is_empty() {
+                let _ = write!(prompt, "\nTone: {tone}\n");
+            }
+        }
+        prompt
+    }
+
+    /// Names in `registered` the persona may not use.
+    pub fn excluded_tools<'a>(&self, registered: impl Iterator<Item = &'a str>) -> Vec<String> {
+        let Some(allowed) = &self.config.tools else {
+            return Vec::new();
+        };
+        registered
+            .filter(|name| !allowed.iter().any(|tool| tool.trim() == *name))
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Persona for the chat `history_key` on `channel`, if any.
+pub fn active(channel: &str, history_key: &str) -> Option<ActivePersona> {
+    let personas = PERSONAS.get();
+    let name = match SELECTIONS.lock().get(history_key) {
+        Some(selection) => selection.clone()?,
+        None => personas.by_channel.get(channel)?.clone(),
+    };
+    let config = personas.profiles.get(&name)?.clone();
+    Some(ActivePersona { name, config })
+}
+
+/// Reply to `/persona [name|off|default]` from the chat `history_key`.
+pub fn command(channel: &str, history_key: &str, arg: &str) -> String {
+    let arg = arg.trim();
+    match arg.to_ascii_lowercase().as_str() {
+        "" => describe(channel, history_key),
+        "off" | "none" => {
+            SELECTIONS.lock().insert(history_key.to_string(), None);
+            "Persona off for this chat.".to_string()
+        }
+        "default" => {
+            SELECTIONS.lock().remove(history_key);
+            match active(channel, history_key) {
+                Some(persona) => format!("Back to this channel's persona `{}`.", persona.name),
+                None => "Back to the default: no persona.".to_string(),
+            }
+        }
+        _ => {
+            if !PERSONAS.get().profiles.contains_key(arg) {
+                return format!("Unknown persona `{arg}`.\n\n{}", available());
+            }
+            SELECTIONS
+                .lock()
+                .insert(history_key.to_string(), Some(arg.to_string()));
+            format!("Persona switched to `{arg}` for this chat.")
+        }
+    }
+}
+
+fn describe(channel: &str, history_key: &str) -> String {
+    let current = match active(channel, history_key) {
+        Some(persona) => format!("Current persona: `{}`.", persona.name),
+        None => "No persona active.".to_string(),
+    };
+    format!(
+        "{current}\n\n{}\n\nUse `/persona <name>` to switch, `/persona off` to drop the persona, or `/persona default` for this channel's default.",
+        available()
+    )
+}
+
+fn available() -> String {
+    let personas = PERSONAS.get();
+    if personas.profiles.is_empty() {
+        return "No personas configured. Add them under `[personas.profiles.<name>]`.".to_string();
+    }
+    let mut names: Vec<&String> = personas.profiles.keys().collect();
+    names.sort();
+    let mut out = String::from("Available personas:");
+    for name in names {
+        let profile = &personas.profiles[name];
+        let _ = write!(out, "\n- `{name}`");
+        if let Some(model) = &profile.model {
+            let _ = write!(out, " ({model})");
+        }
+        if let Some(tone) = profile.tone.as_deref().filter(|t| !t.trim().is_empty()) {
+            let _ = write!(out, ": {}", tone.trim());
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn persona_selection_overrides_channel_default() {
+        let mut config = PersonasConfig::default();
+        config.profiles.insert(
+            "reviewer".into(),
+            PersonaConfig {
+                system_prompt: "Review code strictly.".into(),
+                tone: Some("terse".into()),
+                tools: Some(vec!["file_read".into()]),
+                ..PersonaConfig::default()
+            },
+        );
+        config
+            .profiles
+            .insert("casual".into(), PersonaConfig::default());
+        config
+            .by_channel
+            .insert("persona-test".into(), "reviewer".into());
+        init_from_config(&config);
+
+        let key = "persona-test_alice";
+        let reviewer = active("persona-test", key).expect("channel default");
+        assert_eq!(reviewer.name, "reviewer");
+        assert!(reviewer.prompt().contains("Tone: terse"));
+        assert_eq!(
+            reviewer.excluded_tools(["file_read", "shell"].into_iter()),
+            vec!["shell".to_string()]
+        );
+
+        assert!(command("persona-test", key, "casual").contains("`casual`"));
+        assert_eq!(active("persona-test", key).unwrap().name, "casual");
+        assert!(command("persona-test", key, "pirate").starts_with("Unknown persona"));
+
+        command("persona-test", key, "off");
+        assert!(active("persona-test", key).is_none());
+        command("persona-test", key, "default");
+        assert_eq!(active("persona-test", key).unwrap().name, "reviewer");
+    }
+}
@@ -0,0 +1,230 @@
+//! Plan-first channel turns.
+//!
+//! `/plan <request>` (or every turn with `[agent].plan_mode`) runs the agent
+//! with tool execution switched off: the tool calls it would make come back
+//! as a checklist and are kept as the sender's pending plan. `/plan-approve`
+//! approves the whole plan at once and runs the request again with the
+//! plan's tools pre-approved for that turn; `/plan-discard` drops it.
+
+use crate::agent::loop_::planning::{PlanRecorder, PlanScope, PlannedToolCall};
+use crate::config::AgentConfig;
+use crate::util::truncate_with_ellipsis;
+use crate::workspace::Scoped;
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+
+/// How long an unapproved plan is kept.
+const PLAN_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Longest argument preview shown per checklist item.
+const ARGUMENT_PREVIEW_CHARS: usize = 120;
+
+const PLANNING_INSTRUCTIONS: &str = "[Planning mode] Do not carry out this request yet. \
+Call the tools you would use, in order; none of them will run. Then reply with a short \
+summary of the plan and anything the user should check before approving it.";
+
+static PLAN_MODE: Scoped<bool> = Scoped::new(false);
+
+static PENDING_PLANS: LazyLock<Mutex<HashMap<String, PendingPlan>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Apply `[agent].plan_mode`.
+pub fn init_from_config(config: &AgentConfig) {
+    PLAN_MODE.set(config.plan_mode);
+}
+
+/// Whether every channel turn is planned first.
+pub fn plan_mode() -> bool {
+    PLAN_MODE.get()
+}
+
+/// Plan chat commands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanCommand {
+    /// `/plan <request>`
+    Plan(String),
+    /// `/plan-approve`
+    Approve,
+    /// `/plan-discard`
+    Discard,
+}
+
+impl PlanCommand {
+    pub fn parse(content: &str) -> Option<Self> {
+        let trimmed = content.trim();
+        let (command, tail) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+        match command.split('@').next()?.to_ascii_lowercase().as_str() {
+            "/plan" => Some(Self::Plan(tail.trim().to_string())),
+            "/plan-approve" => Some(Self::Approve),
+            "/plan-discard" => Some(Self::Discard),
+            _ => None,
+        }
+    }
+}
+
+/// A plan waiting for approval.
+#[derive(Debug, Clone)]
+pub struct PendingPlan {
+    /// The request the plan was made for.
+    pub request: String,
+    pub steps: Vec<PlannedToolCall>,
+    created_at: Instant,
+}
+
+impl PendingPlan {
+    pub fn new(request: &str, steps: Vec<PlannedToolCall>) -> Self {
+        Self {
+            request: request.to_string(),
+            steps,
+            created_at: Instant::now(),
+        }
+    }
+
+    /// Distinct tools the plan uses.
+    pub fn tools(&self) -> HashSet<String> {
+        self.steps.iter().map(|step| step.tool.clone()).collect()
+    }
+}
+
+/// Keep `plan` as the pending plan of conversation `key`, replacing any
+/// earlier one.
+pub fn store(key: &str, plan: PendingPlan) {
+    let mut plans = PENDING_PLANS.lock();
+    plans.retain(|_, plan| plan.created_at.elapsed() < PLAN_TTL);
+    plans.insert(key.to_string(), plan);
+}
+
+/// Remove and return the pending plan of conversation `key`.
+pub fn take(key: &str) -> Option<PendingPlan> {
+    PENDING_PLANS
+        .lock()
+        .remove(key)
+        .filter(|plan| plan.created_at.elapsed() < PLAN_TTL)
+}
+
+/// How a channel turn handles tool calls.
+pub enum PlanTurn {
+    /// Run tools as usual.
+    Direct,
+    /// Record tool calls as a plan without running them.
+    Plan(PlanRecorder),
+    /// Run an approved plan.
+    Execute(PendingPlan),
+}
+
+impl PlanTurn {
+    pub fn plan() -> Self {
+        Self::Plan(PlanRecorder::default())
+    }
+
+    /// Task-local scope for the tool loop.
+    pub fn scope(&self) -> Option<PlanScope> {
+        match self {
+            Self::Direct => None,
+            Self::Plan(recorder) => Some(PlanScope::Recording(recorder.clone())),
+            Self::Execute(plan) => Some(PlanScope::Approved(Arc::new(plan.tools()))),
+        }
+    }
+
+    /// The user turn sent to the model for `content`.
+    pub fn decorate(&self, content: &str) -> String {
+        match self {
+            Self::Direct => content.to_string(),
+            Self::Plan(_) => format!("{content}\n\n{PLANNING_INSTRUCTIONS}"),
+            Self::Execute(plan) => {
+                let mut decorated = format!(
+                    "{content}\n\n[Approved plan] The user approved this plan; carry it out now:"
+                );
+                for (idx, step) in plan.steps.iter().enumerate() {
+                    let _ = write!(
+                        decorated,
+                        "\n{}. `{}` {}",
+                        idx + 1,
+                        step.tool,
+                        step.arguments
+                    );
+                }
+                decorated
+            }
+        }
+    }
+
+    /// The reply for a finished turn. A planning turn that called tools
+    /// stores its plan under `key` and answers with the checklist.
+    pub fn finish(&self, key: &str, request: &str, response: String) -> String {
+        let Self::Plan(recorder) = self else {
+            return response;
+        };
+        let steps = recorder.steps();
+        if steps.is_empty() {
+            return response;
+        }
+        let checklist = render_checklist(&steps, &response);
+        store(key, PendingPlan::new(request, steps));
+        checklist
+    }
+}
+
+/// Render plan steps as a checklist under the model's summary.
+pub fn render_checklist(steps: &[PlannedToolCall], summary: &str) -> String {
+    let mut rendered = String::new();
+    let summary = summary.trim();
+    if !summary.is_empty() {
+        rendered.push_str(summary);
+        rendered.push_str("\n\n");
+    }
+    rendered.push_str("Plan (nothing has run yet):");
+    for (idx, step) in steps.iter().enumerate() {
+        let _ = write!(
+            rendered,
+            "\n- [ ] {}. `{}` {}",
+            idx + 1,
+            step.tool,
+            truncate_with_ellipsis(&step.arguments.to_string(), ARGUMENT_PREVIEW_CHARS)
+        );
+    }
+    rendered.push_str(
+        "\n\nReply `/plan-approve` to run these steps, or `/plan-discard` to drop the plan.",
+    );
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn planning_turns_store_their_checklist() {
+        assert_eq!(
+            PlanCommand::parse("/plan clean up the logs"),
+            Some(PlanCommand::Plan("clean up the logs".into()))
+        );
+        assert_eq!(
+            PlanCommand::parse("/plan-approve@zeroclaw_bot"),
+            Some(PlanCommand::Approve)
+        );
+        assert_eq!(PlanCommand::parse("/planet"), None);
+
+        let turn = PlanTurn::plan();
+        let Some(PlanScope::Recording(recorder)) = turn.scope() else {
+            panic!("planning turn should record");
+        };
+        recorder.record("shell", serde_json::json!({ "command": "rm logs/*.old" }));
+        let reply = turn.finish("test_plan_key", "clean up the logs", "I will prune.".into());
+        assert!(reply.starts_with("I will prune.\n\nPlan (nothing has run yet):"));
+        assert!(reply.contains("- [ ] 1. `shell`"));
+
+        let plan = take("test_plan_key").expect("plan should be pending");
+        assert_eq!(plan.request, "clean up the logs");
+        assert!(plan.tools().contains("shell"));
+        assert!(take("test_plan_key").is_none());
+        assert!(PlanTurn::Execute(plan)
+            .decorate("clean up the logs")
+            .contains("1. `shell`"));
+    }
+}
@@ -0,0 +1,689 @@
+//! Message queue channel: Redis Streams or NATS.
+//!
+//! The channel consumes `inbound`, either a Redis stream read through
+//! consumer group `group` or a NATS subject subscribed in queue group
+//! `group`, and publishes replies to the message's `reply_to`, or to
+//! `outbound` when it names none.
+//!
+//! Inbound messages carry `content`, `sender` and optional `reply_to` and
+//! `thread` fields: stream entry fields on Redis, a JSON object on NATS. A
+//! plain-text NATS payload is taken as the content, and the reply subject of
+//! a NATS request counts as its `reply_to`. Replies carry `content` plus the
+//! message's `thread`, as stream fields on Redis and as a JSON object on
+//! NATS. Messages with the same `sender` and `thread` share a conversation.
+//!
+//! Redis entries are acknowledged once they are handed to the agent; entries
+//! read but not acknowledged before a restart are delivered again to the
+//! same `consumer`. NATS core subjects have no redelivery. TLS is not
+//! supported by either client; connect through a local TLS proxy.
+
+use super::session::redis::{Connection as RedisConnection, RedisEndpoint, Reply};
+use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::{QueueBackend, QueueConfig};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, Mutex};
+
+const DEFAULT_NATS_PORT: u16 = 4222;
+
+/// How long one `XREADGROUP` call blocks waiting for new entries.
+const REDIS_BLOCK_MILLIS: &str = "5000";
+
+/// Entries read per `XREADGROUP` call.
+const REDIS_READ_COUNT: &str = "16";
+
+/// Approximate length reply streams are trimmed to on every `XADD`.
+const REDIS_REPLY_MAXLEN: &str = "10000";
+
+/// NATS servers ping idle clients every two minutes by default.
+const NATS_READ_TIMEOUT: Duration = Duration::from_secs(300);
+
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sender used when a message does not name one.
+const ANONYMOUS_SENDER: &str = "anonymous";
+
+/// A message as producers publish it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+struct InboundMessage {
+    content: String,
+    #[serde(default)]
+    sender: Option<String>,
+    #[serde(default)]
+    reply_to: Option<String>,
+    #[serde(default)]
+    thread: Option<String>,
+}
+
+impl InboundMessage {
+    /// Build from Redis stream entry fields. Entries without `content` are
+    /// rejected.
+    fn from_fields(fields: &[(String, String)]) -> Option<Self> {
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+                .filter(|value| !value.is_empty())
+        };
+        Some(Self {
+            content: field("content")?,
+            sender: field("sender"),
+            reply_to: field("reply_to"),
+            thread: field("thread"),
+        })
+    }
+
+    /// Build from a NATS payload: a JSON object, or plain text. Objects
+    /// without `content` are rejected.
+    fn from_payload(payload: &[u8], nats_reply: Option<&str>) -> Option<Self> {
+        let mut message = match serde_json::from_slice::<serde_json::Value>(payload) {
+            Ok(value @ serde_json::Value::Object(_)) => serde_json::from_value(value).ok()?,
+            _ => Self {
+                content: String::from_utf8_lossy(payload).into_owned(),
+                ..Self::default()
+            },
+        };
+        if message.content.trim().is_empty() {
+            return None;
+        }
+        if message.reply_to.is_none() {
+            message.reply_to = nats_reply.map(str::to_string);
+        }
+        Some(message)
+    }
+}
+
+/// Parsed `nats://` connection target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct NatsEndpoint {
+    host: String,
+    port: u16,
+    user: Option<String>,
+    password: Option<String>,
+    token: Option<String>,
+}
+
+impl NatsEndpoint {
+    fn parse(raw: &str) -> Result<Self> {
+        let url = reqwest::Url::parse(raw.trim()).context("Invalid channels_config.queue.url")?;
+        if url.scheme() != "nats" {
+            bail!(
+                "channels_config.queue.url: unsupported scheme '{}'; use nats:// (TLS is not supported)",
+                url.scheme()
+            );
+        }
+        let host = url
+            .host_str()
+            .context("channels_config.queue.url must include a host")?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let decode = |s: &str| {
+            urlencoding::decode(s)
+                .map(|v| v.into_owned())
+                .unwrap_or_else(|_| s.to_string())
+        };
+        let user = Some(url.username()).filter(|u| !u.is_empty()).map(decode);
+        let password = url.password().map(decode);
+        // `nats://<token>@host` carries a token instead of a user.
+        let (user, token) = match (user, &password) {
+            (Some(token), None) => (None, Some(token)),
+            (user, _) => (user, None),
+        };
+        Ok(Self {
+            host,
+            port: url.port().unwrap_or(DEFAULT_NATS_PORT),
+            user,
+            password,
+            token,
+        })
+    }
+
+    fn connect_line(&self) -> String {
+        let mut options = serde_json::json!({
+            "verbose": false,
+            "pedantic": false,
+            "name": "zeroclaw",
+            "lang": "rust",
+            "version": env!("CARGO_PKG_VERSION"),
+            "protocol": 0,
+        });
+        if let Some(user) = &self.user {
+            options["user"] = user.clone().into();
+        }
+        if let Some(password) = &self.password {
+            options["pass"] = password.clone().into();
+        }
+        if let Some(token) = &self.token {
+            options["auth_token"] = token.clone().into();
+        }
+        format!("CONNECT {options}\r\n")
+    }
+}
+
+/// Header of a NATS `MSG` frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct NatsMsgHeader {
+    subject: String,
+    reply: Option<String>,
+    size: usize,
+}
+
+impl NatsMsgHeader {
+    /// Parse `MSG <subject> <sid> [reply-to] <#bytes>`.
+    fn parse(line: &str) -> Option<Self> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let (subject, reply, size) = match parts.as_slice() {
+            ["MSG", subject, _sid, size] => (subject, None, size),
+            ["MSG", subject, _sid, reply, size] => (subject, Some(reply), size),
+            _ => return None,
+        };
+        Some(Self {
+            subject: (*subject).to_string(),
+            reply: reply.map(|r| (*r).to_string()),
+            size: size.parse().ok()?,
+        })
+    }
+}
+
+/// Redis Streams / NATS worker channel.
+pub struct QueueChannel {
+    config: QueueConfig,
+    /// Connection replies are published on (Redis).
+    redis_publisher: Mutex<Option<RedisConnection>>,
+    /// Write half of the subscription connection (NATS).
+    nats_writer: Arc<Mutex<Option<OwnedWriteHalf>>>,
+}
+
+impl QueueChannel {
+    pub fn new(config: QueueConfig) -> Self {
+        Self {
+            config,
+            redis_publisher: Mutex::new(None),
+            nats_writer: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn is_sender_allowed(&self, sender: &str) -> bool {
+        self.config
+            .allowed_senders
+            .iter()
+            .any(|s| s == "*" || s == sender)
+    }
+
+    /// Turn an inbound message into a channel message, or `None` when its
+    /// sender is not allowed.
+    fn accept(&self, id: String, message: InboundMessage) -> Option<ChannelMessage> {
+        let sender = message
+            .sender
+            .unwrap_or_else(|| ANONYMOUS_SENDER.to_string());
+        if !self.is_sender_allowed(&sender) {
+            tracing::warn!(
+                "Queue: ignoring message from unauthorized sender '{sender}'. \
+                 Add it to channels_config.queue.allowed_senders."
+            );
+            return None;
+        }
+        let reply_target = message
+            .reply_to
+            .filter(|target| !target.is_empty() && !target.contains(char::is_whitespace))
+            .unwrap_or_else(|| self.config.outbound.clone());
+        Some(ChannelMessage {
+            id,
+            sender,
+            reply_target,
+            content: message.content,
+            channel: "queue".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            thread_ts: message.thread,
+        })
+    }
+
+    fn redis_endpoint(&self) -> Result<RedisEndpoint> {
+        RedisEndpoint::parse(&self.config.url, "channels_config.queue.url")
+    }
+
+    async fn listen_redis(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
+        let mut conn = RedisConnection::open(&self.redis_endpoint()?).await?;
+        let stream = self.config.inbound.as_bytes();
+        let group = self.config.group.as_bytes();
+        let consumer = self.config.consumer.as_bytes();
+        match conn
+            .command(&[b"XGROUP", b"CREATE", stream, group, b"$", b"MKSTREAM"])
+            .await
+        {
+            Ok(_) => {}
+            Err(e) if e.to_string().contains("BUSYGROUP") => {}
+            Err(e) => return Err(e.context("Failed to create the Redis consumer group")),
+        }
+        tracing::info!(
+            "Queue: consuming Redis stream '{}' as {}/{}",
+            self.config.inbound,
+            self.config.group,
+            self.config.consumer
+        );
+
+        // Entries delivered before a restart but never acknowledged come
+        // first; `>` then waits for new ones.
+        let mut cursor: &[u8] = b"0";
+        loop {
+            let reply = conn
+                .command(&[
+                    b"XREADGROUP",
+                    b"GROUP",
+                    group,
+                    consumer,
+                    b"COUNT",
+                    REDIS_READ_COUNT.as_bytes(),
+                    b"BLOCK",
+                    REDIS_BLOCK_MILLIS.as_bytes(),
+                    b"STREAMS",
+                    stream,
+                    cursor,
+                ])
+                .await?;
+            let entries = parse_stream_entries(reply)?;
+            if entries.is_empty() {
+                cursor = b">";
+                continue;
+            }
+            for (id, fields) in entries {
+                match InboundMessage::from_fields(&fields) {
+                    Some(message) => {
+                        if let Some(msg) = self.accept(id.clone(), message) {
+                            if tx.send(msg).await.is_err() {
+                                return Ok(());
+                            }
+                        }
+                    }
+                    None => tracing::warn!("Queue: dropping stream entry {id} without content"),
+                }
+                conn.command(&[b"XACK", stream, group, id.as_bytes()])
+                    .await?;
+            }
+        }
+    }
+
+    async fn publish_redis(&self, target: &str, fields: &[(&str, &str)]) -> Result<()> {
+        let mut publisher = self.redis_publisher.lock().await;
+        if publisher.is_none() {
+            *publisher = Some(RedisConnection::open(&self.redis_endpoint()?).await?);
+        }
+        let Some(conn) = publisher.as_mut() else {
+            bail!("Redis publisher is not connected");
+        };
+        let mut args: Vec<&[u8]> = vec![
+            b"XADD",
+            target.as_bytes(),
+            b"MAXLEN",
+            b"~",
+            REDIS_REPLY_MAXLEN.as_bytes(),
+            b"*",
+        ];
+        for (key, value) in fields {
+            args.push(key.as_bytes());
+            args.push(value.as_bytes());
+        }
+        let result = conn.command(&args).await;
+        if result.is_err() {
+            // Never reuse a connection that may be mid-reply.
+            *publisher = None;
+        }
+        result.map(|_| ())
+    }
+
+    async fn listen_nats(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
+        let endpoint = NatsEndpoint::parse(&self.config.url)?;
+        let stream = TcpStream::connect((endpoint.host.as_str(), endpoint.port))
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to connect to NATS at {}:{}",
+                    endpoint.host, endpoint.port
+                )
+            })?;
+        stream.set_nodelay(true)?;
+        let (read, mut write) = stream.into_split();
+        let mut reader = BufReader::new(read);
+
+        let info = read_nats_line(&mut reader).await?;
+        if !info.starts_with("INFO") {
+            bail!("Unexpected NATS greeting: {info}");
+        }
+        write
+            .write_all(format!("{}PING\r\n", endpoint.connect_line()).as_bytes())
+            .await?;
+        loop {
+            let line = read_nats_line(&mut reader).await?;
+            match line.as_str() {
+                "PONG" => break,
+                l if l.starts_with("-ERR") => bail!("NATS rejected the connection: {l}"),
+                _ => {}
+            }
+        }
+        write
+            .write_all(
+                format!("SUB {} {} 1\r\n", self.config.inbound, self.config.group).as_bytes(),
+            )
+            .await?;
+        *self.nats_writer.lock().await = Some(write);
+        tracing::info!(
+            "Queue: subscribed to NATS subject '{}' in queue group '{}'",
+            self.config.inbound,
+            self.config.group
+        );
+
+        let result = self.read_nats_messages(&mut reader, &tx).await;
+        *self.nats_writer.lock().await = None;
+        result
+    }
+
+    async fn read_nats_messages(
+        &self,
+        reader: &mut BufReader<tokio::net::tcp::OwnedReadHalf>,
+        tx: &mpsc::Sender<ChannelMessage>,
+    ) -> Result<()> {
+        loop {
+            let line = read_nats_line(reader).await?;
+            if line == "PING" {
+                self.write_nats(b"PONG\r\n").await?;
+                continue;
+            }
+            if line.starts_with("-ERR") {
+                bail!("NATS error: {line}");
+            }
+            let Some(header) = NatsMsgHeader::parse(&line) else {
+                // +OK, PONG and INFO updates need no action.
+                continue;
+            };
+            let mut payload = vec![0u8; header.size + 2];
+            reader.read_exact(&mut payload).await?;
+            payload.truncate(header.size);
+
+            let Some(message) = InboundMessage::from_payload(&payload, header.reply.as_deref())
+            else {
+                tracing::warn!("Queue: dropping empty message on '{}'", header.subject);
+                continue;
+            };
+            let id = format!("nats_{}", uuid::Uuid::new_v4());
+            if let Some(msg) = self.accept(id, message) {
+                if tx.send(msg).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    async fn write_nats(&self, frame: &[u8]) -> Result<()> {
+        let mut writer = self.nats_writer.lock().await;
+        let Some(write) = writer.as_mut() else {
+            bail!("NATS is not connected");
+        };
+        write.write_all(frame).await?;
+        Ok(())
+    }
+}
+
+async fn read_nats_line(reader: &mut BufReader<tokio::net::tcp::OwnedReadHalf>) -> Result<String> {
+    let mut line = String::new();
+    let read = tokio::time::timeout(NATS_READ_TIMEOUT, reader.read_line(&mut line))
+        .await
+        .context("NATS connection idle for too long")??;
+    if read == 0 {
+        bail!("NATS closed the connection");
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Entry ids and fields of an `XREADGROUP` reply.
+fn parse_stream_entries(reply: Reply) -> Result<Vec<(String, Vec<(String, String)>)>> {
+    let streams = match reply {
+        Reply::Array(streams) => streams,
+        // Nothing arrived within the block timeout.
+        Reply::Bulk(None) => return Ok(Vec::new()),
+        other => bail!("Unexpected Redis reply to XREADGROUP: {other:?}"),
+    };
+    let text = |reply: Reply| match reply {
+        Reply::Bulk(Some(raw)) => Some(String::from_utf8_lossy(&raw).into_owned()),
+        _ => None,
+    };
+    let mut entries = Vec::new();
+    for stream in streams {
+        let Reply::Array(mut parts) = stream else {
+            bail!("Malformed Redis stream reply");
+        };
+        let Some(Reply::Array(items)) = parts.pop() else {
+            bail!("Malformed Redis stream reply");
+        };
+        for item in items {
+            let Reply::Array(item) = item else {
+                bail!("Malformed Redis stream entry");
+            };
+            let mut item = item.into_iter();
+            let Some(id) = item.next().and_then(text) else {
+                bail!("Redis stream entry without an id");
+            };
+            // Entries deleted while pending come back with nil fields.
+            let mut fields = Vec::new();
+            if let Some(Reply::Array(raw)) = item.next() {
+                let mut raw = raw.into_iter();
+                while let (Some(key), Some(value)) = (raw.next(), raw.next()) {
+                    if let (Some(key), Some(value)) = (text(key), text(value)) {
+                        fields.push((key, value));
+                    }
+                }
+            }
+            entries.push((id, fields));
+        }
+    }
+    Ok(entries)
+}
+
+#[async_trait]
+impl Channel for QueueChannel {
+    fn name(&self) -> &str {
+        "queue"
+    }
+
+    async fn send(&self, message: &SendMessage) -> Result<()> {
+        let target = message.recipient.as_str();
+        let publish = async {
+            match self.config.backend {
+                QueueBackend::Redis => {
+                    let mut fields = vec![("content", message.content.as_str())];
+                    if let Some(thread) = message.thread_ts.as_deref() {
+                        fields.push(("thread", thread));
+                    }
+                    self.publish_redis(target, &fields).await
+                }
+                QueueBackend::Nats => {
+                    let mut payload = serde_json::json!({ "content": message.content });
+                    if let Some(thread) = &message.thread_ts {
+                        payload["thread"] = thread.clone().into();
+                    }
+                    let payload = payload.to_string();
+                    self.write_nats(
+                        format!("PUB {target} {}\r\n{payload}\r\n", payload.len()).as_bytes(),
+                    )
+                    .await
+                }
+            }
+        };
+        tokio::time::timeout(SEND_TIMEOUT, publish)
+            .await
+            .with_context(|| format!("Timed out publishing a reply to '{target}'"))?
+    }
+
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
+        match self.config.backend {
+            QueueBackend::Redis => self.listen_redis(tx).await,
+            QueueBackend::Nats => self.listen_nats(tx).await,
+        }
+    }
+
+    async fn health_check(&self) -> bool {
+        match self.config.backend {
+            QueueBackend::Redis => {
+                let Ok(endpoint) = self.redis_endpoint() else {
+                    return false;
+                };
+                let ping = async {
+                    RedisConnection::open(&endpoint)
+                        .await?
+                        .command(&[b"PING"])
+                        .await
+                };
+                matches!(
+                    tokio::time::timeout(SEND_TIMEOUT, ping).await,
+                    Ok(Ok(Reply::Status(_)))
+                )
+            }
+            QueueBackend::Nats => self.nats_writer.lock().await.is_some(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    fn queue_config(backend: QueueBackend, url: String) -> QueueConfig {
+        QueueConfig {
+            backend,
+            url,
+            inbound: "agent.requests".into(),
+            outbound: "agent.replies".into(),
+            group: "workers".into(),
+            consumer: "worker-1".into(),
+            allowed_senders: vec!["billing".into()],
+        }
+    }
+
+    #[test]
+    fn inbound_messages_parse_from_stream_fields_and_nats_payloads() {
+        let bulk = |s: &str| Reply::Bulk(Some(s.as_bytes().to_vec()));
+        let reply = Reply::Array(vec![Reply::Array(vec![
+            bulk("agent.requests"),
+            Reply::Array(vec![
+                Reply::Array(vec![
+                    bulk("1-0"),
+                    Reply::Array(vec![
+                        bulk("sender"),
+                        bulk("billing"),
+                        bulk("content"),
+                        bulk("hi"),
+                    ]),
+                ]),
+                Reply::Array(vec![bulk("2-0"), Reply::Bulk(None)]),
+            ]),
+        ])]);
+        let entries = parse_stream_entries(reply).unwrap();
+        assert_eq!(entries.len(), 2);
+        let message = InboundMessage::from_fields(&entries[0].1).unwrap();
+        assert_eq!(message.content, "hi");
+        assert_eq!(message.sender.as_deref(), Some("billing"));
+        assert!(InboundMessage::from_fields(&entries[1].1).is_none());
+        assert!(parse_stream_entries(Reply::Array(Vec::new()))
+            .unwrap()
+            .is_empty());
+
+        let header = NatsMsgHeader::parse("MSG agent.requests 1 _INBOX.abc 11").unwrap();
+        assert_eq!(header.reply.as_deref(), Some("_INBOX.abc"));
+        assert_eq!(header.size, 11);
+        let plain = InboundMessage::from_payload(b"hello world", Some("_INBOX.abc")).unwrap();
+        assert_eq!(plain.reply_to.as_deref(), Some("_INBOX.abc"));
+        assert!(plain.sender.is_none());
+        assert!(InboundMessage::from_payload(br#"{"sender":"billing"}"#, None).is_none());
+
+        let endpoint = NatsEndpoint::parse("nats://s3cret@nats.internal").unwrap();
+        assert_eq!(endpoint.port, DEFAULT_NATS_PORT);
+        assert_eq!(endpoint.token.as_deref(), Some("s3cret"));
+        assert!(endpoint
+            .connect_line()
+            .contains("\"auth_token\":\"s3cret\""));
+        assert!(NatsEndpoint::parse("tls://nats.internal").is_err());
+    }
+
+    #[tokio::test]
+    async fn nats_worker_receives_requests_and_publishes_replies() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut socket = BufReader::new(socket);
+            socket
+                .get_mut()
+                .write_all(b"INFO {\"server_id\":\"test\"}\r\n")
+                .await
+                .unwrap();
+            let mut received = Vec::new();
+            let mut line = String::new();
+            while !line.starts_with("SUB") {
+                line.clear();
+                socket.read_line(&mut line).await.unwrap();
+                if line.starts_with("PING") {
+                    socket.get_mut().write_all(b"PONG\r\n").await.unwrap();
+                }
+                received.push(line.trim_end().to_string());
+            }
+            let payload = r#"{"sender":"billing","content":"refund 42","thread":"t-1"}"#;
+            let denied = r#"{"sender":"mallory","content":"hi"}"#;
+            for body in [denied, payload] {
+                let frame = format!(
+                    "MSG agent.requests 1 _INBOX.r1 {}\r\n{body}\r\n",
+                    body.len()
+                );
+                socket.get_mut().write_all(frame.as_bytes()).await.unwrap();
+            }
+            let mut publish = String::new();
+            socket.read_line(&mut publish).await.unwrap();
+            let mut body = String::new();
+            socket.read_line(&mut body).await.unwrap();
+            received.push(publish.trim_end().to_string());
+            received.push(body.trim_end().to_string());
+            received
+        });
+
+        let channel = Arc::new(QueueChannel::new(queue_config(
+            QueueBackend::Nats,
+            format!("nats://127.0.0.1:{port}"),
+        )));
+        let (tx, mut rx) = mpsc::channel(4);
+        let listening = Arc::clone(&channel);
+        tokio::spawn(async move { listening.listen(tx).await });
+
+        let msg = rx.recv().await.unwrap();
+        assert_eq!(msg.sender, "billing");
+        assert_eq!(msg.content, "refund 42");
+        assert_eq!(msg.reply_target, "_INBOX.r1");
+        assert_eq!(msg.thread_ts.as_deref(), Some("t-1"));
+
+        channel
+            .send(&SendMessage::new("done", &msg.reply_target).in_thread(msg.thread_ts.clone()))
+            .await
+            .unwrap();
+
+        let received = server.await.unwrap();
+        assert!(received[0].starts_with("CONNECT {"));
+        assert!(received.contains(&"SUB agent.requests workers 1".to_string()));
+        let body = received.last().unwrap();
+        assert_eq!(
+            received[received.len() - 2],
+            format!("PUB _INBOX.r1 {}", body.len())
+        );
+        let reply: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(reply["content"], "done");
+        assert_eq!(reply["thread"], "t-1");
+    }
+}
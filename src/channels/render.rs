@@ -0,0 +1,277 @@
+//! Markdown rendering profiles for outbound channel messages.
+//!
+//! Agent replies are GitHub-flavoured markdown, which most chat platforms
+//! render only partially. Right before a reply (or cron/heartbeat
+//! announcement) is sent, it is converted to the dialect of the target
+//! channel: Slack mrkdwn, WhatsApp formatting, or plain text. Tables become
+//! code blocks where the channel cannot draw them, and code blocks and
+//! inline code are never rewritten. Channels that convert markdown
+//! themselves (Telegram's HTML mode) and channels with full GFM support get
+//! the text unchanged.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// How a channel displays markdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkdownProfile {
+    /// Full GFM, or the channel converts it itself.
+    Gfm,
+    /// GFM without tables (Discord).
+    GfmWithoutTables,
+    /// Slack mrkdwn: `*bold*`, `_italic_`, `~strike~`, `<url|text>`.
+    SlackMrkdwn,
+    /// WhatsApp: `*bold*`, `_italic_`, `~strike~`, no headings or links.
+    WhatsApp,
+    /// No formatting at all.
+    Plain,
+}
+
+impl MarkdownProfile {
+    fn renders_tables(self) -> bool {
+        self == Self::Gfm
+    }
+}
+
+/// Profile for a channel, by `Channel::name()`.
+pub fn profile_for_channel(channel: &str) -> MarkdownProfile {
+    match channel {
+        "slack" => MarkdownProfile::SlackMrkdwn,
+        "discord" => MarkdownProfile::GfmWithoutTables,
+        "whatsapp" | "whatsapp_web" | "wati" => MarkdownProfile::WhatsApp,
+        "irc" | "imessage" | "signal" | "email" | "nostr" | "linq" | "notify" => {
+            MarkdownProfile::Plain
+        }
+        _ => MarkdownProfile::Gfm,
+    }
+}
+
+/// Render `text` for delivery on `channel`.
+pub fn render_for_channel(channel: &str, text: &str) -> String {
+    render(text, profile_for_channel(channel))
+}
+
+static BOLD_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\*\*(\S(?:.*?\S)?)\*\*").unwrap());
+static ITALIC_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\*(\S(?:[^*]*?\S)?)\*").unwrap());
+static STRIKE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"~~(\S(?:.*?\S)?)~~").unwrap());
+static LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"!?\[([^\]]*)\]\(([^)\s]+)\)").unwrap());
+static HEADING_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s{0,3}#{1,6}\s+(.*?)\s*#*\s*$").unwrap());
+static TABLE_SEPARATOR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*\|?\s*:?-{3,}:?\s*(\|\s*:?-{3,}:?\s*)*\|?\s*$").unwrap());
+
+/// Stands in for a bold marker while italics are converted.
+const BOLD_PLACEHOLDER: char = '\u{1}';
+
+/// Convert GFM `text` to `profile`.
+pub fn render(text: &str, profile: MarkdownProfile) -> String {
+    if profile == MarkdownProfile::Gfm {
+        return text.to_string();
+    }
+
+    let lines: Vec<&str> = text.lines().collect();
+    let mut out: Vec<String> = Vec::with_capacity(lines.len());
+    let mut in_fence = false;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            if profile != MarkdownProfile::Plain {
+                // Slack and WhatsApp ignore the language tag.
+                out.push(
+                    if in_fence && profile != MarkdownProfile::GfmWithoutTables {
+                        "```".to_string()
+                    } else {
+                        line.to_string()
+                    },
+                );
+            }
+            i += 1;
+            continue;
+        }
+        if in_fence {
+            out.push(line.to_string());
+            i += 1;
+            continue;
+        }
+
+        if !profile.renders_tables() && is_table_start(&lines, i) {
+            let end = (i..lines.len())
+                .find(|&j| !lines[j].trim_start().starts_with('|'))
+                .unwrap_or(lines.len());
+            out.extend(render_table(&lines[i..end], profile));
+            i = end;
+            continue;
+        }
+
+        out.push(render_line(line, profile));
+        i += 1;
+    }
+
+    let mut rendered = out.join("\n");
+    if text.ends_with('\n') {
+        rendered.push('\n');
+    }
+    rendered
+}
+
+fn is_table_start(lines: &[&str], i: usize) -> bool {
+    lines[i].trim_start().starts_with('|')
+        && lines
+            .get(i + 1)
+            .is_some_and(|next| TABLE_SEPARATOR_RE.is_match(next))
+}
+
+fn render_table(rows: &[&str], profile: MarkdownProfile) -> Vec<String> {
+    if profile == MarkdownProfile::Plain {
+        return rows
+            .iter()
+            .filter(|row| !TABLE_SEPARATOR_RE.is_match(row))
+            .map(|row| {
+                row.trim()
+                    .trim_matches('|')
+                    .split('|')
+                    .map(|cell| render_inline(cell.trim(), profile))
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            })
+            .collect();
+    }
+    let mut block = Vec::with_capacity(rows.len() + 2);
+    block.push("```".to_string());
+    block.extend(rows.iter().map(|row| (*row).to_string()));
+    block.push("```".to_string());
+    block
+}
+
+fn render_line(line: &str, profile: MarkdownProfile) -> String {
+    if profile == MarkdownProfile::GfmWithoutTables {
+        return line.to_string();
+    }
+    if let Some(caps) = HEADING_RE.captures(line) {
+        let title = render_inline(&caps[1], profile);
+        return match profile {
+            MarkdownProfile::Plain => title,
+            _ => format!("*{title}*"),
+        };
+    }
+    render_inline(line, profile)
+}
+
+/// Convert inline markup outside of `code` spans.
+fn render_inline(line: &str, profile: MarkdownProfile) -> String {
+    line.split('`')
+        .enumerate()
+        .map(|(index, segment)| {
+            if index % 2 == 1 {
+                match profile {
+                    MarkdownProfile::Plain => segment.to_string(),
+                    _ => format!("`{segment}`"),
+                }
+            } else {
+                render_prose(segment, profile)
+            }
+        })
+        .collect()
+}
+
+fn render_prose(segment: &str, profile: MarkdownProfile) -> String {
+    let bold = BOLD_PLACEHOLDER.to_string();
+    let mut text = BOLD_RE
+        .replace_all(segment, format!("{bold}${{1}}{bold}").as_str())
+        .into_owned();
+    text = match profile {
+        // WhatsApp's single-asterisk markup already means bold.
+        MarkdownProfile::SlackMrkdwn => ITALIC_RE.replace_all(&text, "_${1}_").into_owned(),
+        MarkdownProfile::Plain => ITALIC_RE.replace_all(&text, "${1}").into_owned(),
+        _ => text,
+    };
+    let strike = match profile {
+        MarkdownProfile::Plain => "${1}",
+        _ => "~${1}~",
+    };
+    text = STRIKE_RE.replace_all(&text, strike).into_owned();
+    text = LINK_RE
+        .replace_all(&text, |caps: &regex::Captures| {
+            let (label, url) = (&caps[1], &caps[2]);
+            match profile {
+                MarkdownProfile::SlackMrkdwn if label.is_empty() => format!("<{url}>"),
+                MarkdownProfile::SlackMrkdwn => format!("<{url}|{label}>"),
+                _ if label.is_empty() || label == url => url.to_string(),
+                _ => format!("{label} ({url})"),
+            }
+        })
+        .into_owned();
+    let marker = match profile {
+        MarkdownProfile::Plain => "",
+        _ => "*",
+    };
+    text.replace(BOLD_PLACEHOLDER, marker)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "## Status\n\
+**Build** is *green*, see [CI](https://ci.example.com).\n\
+Use `**raw**` as-is.\n\
+\n\
+| job | result |\n\
+|-----|--------|\n\
+| test | ~~fail~~ pass |\n\
+\n\
+```rust\n\
+let x = **not bold**;\n\
+```";
+
+    #[test]
+    fn slack_gets_mrkdwn_and_table_code_block() {
+        assert_eq!(
+            render(SAMPLE, MarkdownProfile::SlackMrkdwn),
+            "*Status*\n\
+*Build* is _green_, see <https://ci.example.com|CI>.\n\
+Use `**raw**` as-is.\n\
+\n\
+```\n\
+| job | result |\n\
+|-----|--------|\n\
+| test | ~~fail~~ pass |\n\
+```\n\
+\n\
+```\n\
+let x = **not bold**;\n\
+```"
+        );
+    }
+
+    #[test]
+    fn plain_strips_markup() {
+        assert_eq!(
+            render(SAMPLE, MarkdownProfile::Plain),
+            "Status\n\
+Build is green, see CI (https://ci.example.com).\n\
+Use **raw** as-is.\n\
+\n\
+job | result\n\
+test | fail pass\n\
+\n\
+let x = **not bold**;"
+        );
+    }
+
+    #[test]
+    fn whatsapp_keeps_single_asterisks_and_discord_only_wraps_tables() {
+        assert_eq!(
+            render("**a** and *b*", MarkdownProfile::WhatsApp),
+            "*a* and *b*"
+        );
+        let discord = render(SAMPLE, MarkdownProfile::GfmWithoutTables);
+        assert!(discord.starts_with("## Status\n**Build** is *green*"));
+        assert!(discord.contains("```\n| job | result |"));
+        assert!(discord.contains("```rust\n"));
+        assert_eq!(render(SAMPLE, profile_for_channel("matrix")), SAMPLE);
+    }
+}
@@ -0,0 +1,333 @@
+use super::{
+    create_checkpoint, create_session_store, delete_session, fork_checkpoint, is_session_key,
+    list_sessions, load_metadata, update_tags, SessionEntry, SessionStore,
+};
+use crate::config::Config;
+use crate::cost::usage::{UsageGroup, UsageLedger, UsageRow};
+use crate::util::truncate_with_ellipsis;
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A session row as printed by `session list --json`.
+#[derive(Debug, Serialize)]
+struct SessionListing {
+    #[serde(flatten)]
+    entry: SessionEntry,
+    usage: Option<UsageRow>,
+}
+
+/// Handle `zeroclaw session <subcommand>` CLI commands.
+pub async fn handle_command(command: crate::SessionCommands, config: &Config) -> Result<()> {
+    let store = open_store(config)?;
+    match command {
+        crate::SessionCommands::List { channel, tag, json } => {
+            let listings =
+                session_listings(store.as_ref(), channel.as_deref(), tag.as_deref(), config)
+                    .await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&listings)?);
+                return Ok(());
+            }
+            if listings.is_empty() {
+                println!("No sessions stored.");
+                return Ok(());
+            }
+            println!(
+                "{:<40} {:<32} {:>8} {:<20} {:>10} {:>10}  tags",
+                "session", "title", "messages", "last_activity", "tokens", "cost_usd"
+            );
+            for listing in &listings {
+                let (tokens, cost) = listing.usage.as_ref().map_or_else(
+                    || ("-".to_string(), "-".to_string()),
+                    |usage| {
+                        (
+                            (usage.input_tokens + usage.output_tokens).to_string(),
+                            format!("{:.4}", usage.cost_usd),
+                        )
+                    },
+                );
+                let metadata = listing.entry.metadata.as_ref();
+                println!(
+                    "{:<40} {:<32} {:>8} {:<20} {:>10} {:>10}  {}",
+                    listing.entry.summary.key,
+                    metadata.and_then(|meta| meta.title.as_deref()).map_or_else(
+                        || "-".to_string(),
+                        |title| truncate_with_ellipsis(title, 29)
+                    ),
+                    listing.entry.summary.messages,
+                    format_timestamp(listing.entry.summary.last_activity),
+                    tokens,
+                    cost,
+                    metadata.map_or_else(String::new, |meta| meta.tags.join(","))
+                );
+            }
+            Ok(())
+        }
+        crate::SessionCommands::Show { session } => {
+            let turns = load_session(store.as_ref(), &session).await?;
+            let usage = session_usage(config)?.remove(&session);
+            let metadata = load_metadata(store.as_ref(), &session)
+                .await?
+                .unwrap_or_default();
+            let last_activity = store
+                .list(&session)
+                .await?
+                .into_iter()
+                .find(|summary| summary.key == session)
+                .and_then(|summary| summary.last_activity);
+
+            println!("Session:       {session}");
+            println!(
+                "Title:         {}",
+                metadata.title.as_deref().unwrap_or("-")
+            );
+            if !metadata.channel.is_empty() {
+                println!("Channel:       {}", metadata.channel);
+            }
+            if let Some(principal) = metadata.principal.as_deref() {
+                println!("Principal:     {principal}");
+            }
+            println!("Created:       {}", format_timestamp(metadata.created_at));
+            if !metadata.tags.is_empty() {
+                println!("Tags:          {}", metadata.tags.join(", "));
+            }
+            println!("Messages:      {}", turns.len());
+            println!("Last activity: {}", format_timestamp(last_activity));
+            match usage {
+                Some(usage) => println!(
+                    "Tokens:        {} in / {} out over {} requests (${:.4})",
+                    usage.input_tokens, usage.output_tokens, usage.requests, usage.cost_usd
+                ),
+                None => println!("Tokens:        -"),
+            }
+            for turn in &turns {
+                println!("\n[{}]\n{}", turn.role, turn.content);
+            }
+            Ok(())
+        }
+        crate::SessionCommands::Delete { session, yes } => {
+            if !yes {
+                let confirmed = dialoguer::Confirm::new()
+                    .with_prompt(format!("  Delete session '{session}' and its checkpoints?"))
+                    .default(false)
+                    .interact()?;
+                if !confirmed {
+                    println!("Aborted.");
+                    return Ok(());
+                }
+            }
+            let checkpoints = delete_session(store.as_ref(), &session).await?;
+            println!("✓ Deleted session '{session}' ({checkpoints} checkpoints)");
+            Ok(())
+        }
+        crate::SessionCommands::Tag {
+            session,
+            tags,
+            remove,
+        } => {
+            let (add, drop) = if remove {
+                (Vec::new(), tags)
+            } else {
+                (tags, Vec::new())
+            };
+            let tags = update_tags(store.as_ref(), &session, &add, &drop).await?;
+            if tags.is_empty() {
+                println!("✓ '{session}' has no tags");
+            } else {
+                println!("✓ '{session}' tags: {}", tags.join(", "));
+            }
+            Ok(())
+        }
+        crate::SessionCommands::Export { session, output } => {
+            let turns = load_session(store.as_ref(), &session).await?;
+            let export = serde_json::to_string_pretty(&serde_json::json!({
+                "session": session,
+                "messages": turns,
+            }))?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, format!("{export}\n"))
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    println!(
+                        "✓ Exported '{session}' ({} messages) to {}",
+                        turns.len(),
+                        path.display()
+                    );
+                }
+                None => println!("{export}"),
+            }
+            Ok(())
+        }
+        crate::SessionCommands::Checkpoint {
+            session,
+            name,
+            messages,
+        } => {
+            let count = create_checkpoint(store.as_ref(), &session, &name, messages).await?;
+            println!("✓ Checkpoint '{name}' of '{session}' saved ({count} messages)");
+            Ok(())
+        }
+        crate::SessionCommands::Fork {
+            session,
+            checkpoint,
+            new_session,
+        } => {
+            let count =
+                fork_checkpoint(store.as_ref(), &session, &checkpoint, &new_session).await?;
+            println!(
+                "✓ Forked '{new_session}' from checkpoint '{checkpoint}' of '{session}' ({count} messages)"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Handle `zeroclaw db <subcommand>` CLI commands.
+pub async fn handle_db_command(command: crate::DbCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::DbCommands::Maintain { json } => {
+            let store = open_store(config)?;
+            let Some(report) = store.maintain().await? else {
+                println!(
+                    "The {} session store keeps no local database; nothing to maintain.",
+                    store.name()
+                );
+                return Ok(());
+            };
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            println!("✓ Maintained {}", report.db_path.display());
+            println!("  Expired entries removed: {}", report.expired_removed);
+            println!("  Free pages reclaimed:    {}", report.pages_freed);
+            println!(
+                "  Size on disk:            {} → {}",
+                format_bytes(report.bytes_before),
+                format_bytes(report.bytes_after)
+            );
+            Ok(())
+        }
+        crate::DbCommands::Migrate { dry_run, json } => {
+            crate::db::handle_migrate_command(&config.workspace_dir, dry_run, json)
+        }
+    }
+}
+
+fn open_store(config: &Config) -> Result<Arc<dyn SessionStore>> {
+    create_session_store(&config.session, &config.workspace_dir)?.context(
+        "session.backend is \"memory\"; sessions live only inside the running process. \
+         Set session.backend = \"sqlite\" or \"redis\" to manage them from the CLI.",
+    )
+}
+
+async fn load_session(
+    store: &dyn SessionStore,
+    session: &str,
+) -> Result<Vec<crate::providers::ChatMessage>> {
+    match store.load(session).await? {
+        Some(turns) if is_session_key(session) => Ok(turns),
+        _ => bail!(
+            "Session '{session}' not found in the {} store",
+            store.name()
+        ),
+    }
+}
+
+/// Sessions of `channel` (all channels when `None`), optionally only those
+/// tagged `tag`, joined with their recorded token usage.
+async fn session_listings(
+    store: &dyn SessionStore,
+    channel: Option<&str>,
+    tag: Option<&str>,
+    config: &Config,
+) -> Result<Vec<SessionListing>> {
+    let prefix = channel.map_or_else(String::new, |channel| format!("{channel}_"));
+    let mut usage = session_usage(config)?;
+    Ok(list_sessions(store, &prefix, tag)
+        .await?
+        .into_iter()
+        .map(|entry| SessionListing {
+            usage: usage.remove(&entry.summary.key),
+            entry,
+        })
+        .collect())
+}
+
+/// Usage ledger totals keyed by session. Empty when `[cost]` is disabled,
+/// since nothing is recorded then.
+fn session_usage(config: &Config) -> Result<HashMap<String, UsageRow>> {
+    if !config.cost.enabled {
+        return Ok(HashMap::new());
+    }
+    let ledger = UsageLedger::open(&config.workspace_dir, &config.cost)?;
+    Ok(ledger
+        .report(UsageGroup::Session, None)?
+        .into_iter()
+        .map(|row| (row.key.clone(), row))
+        .collect())
+}
+
+fn format_timestamp(timestamp: Option<i64>) -> String {
+    timestamp
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .map_or_else(
+            || "-".to_string(),
+            |time| time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        )
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    const MIB: u64 = 1024 * 1024;
+    if bytes >= MIB {
+        format!("{:.1} MiB", bytes as f64 / MIB as f64)
+    } else {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::session::SqliteSessionStore;
+    use crate::providers::ChatMessage;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn list_skips_checkpoints_and_filters_by_channel() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let store = SqliteSessionStore::new(tmp.path(), &config.session).unwrap();
+        store
+            .save("telegram_alice", &[ChatMessage::user("hi")])
+            .await
+            .unwrap();
+        store
+            .save("discord_bob", &[ChatMessage::user("yo")])
+            .await
+            .unwrap();
+        create_checkpoint(&store, "telegram_alice", "base", None)
+            .await
+            .unwrap();
+
+        let all = session_listings(&store, None, None, &config).await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().all(|listing| listing.usage.is_none()));
+
+        let telegram = session_listings(&store, Some("telegram"), None, &config)
+            .await
+            .unwrap();
+        assert_eq!(telegram.len(), 1);
+        assert_eq!(telegram[0].entry.summary.key, "telegram_alice");
+
+        assert_eq!(delete_session(&store, "telegram_alice").await.unwrap(), 1);
+        assert_eq!(store.list("").await.unwrap().len(), 1);
+        assert!(load_session(&store, "telegram_alice").await.is_err());
+    }
+}
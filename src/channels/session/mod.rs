@@ -0,0 +1,519 @@
+//! Pluggable storage for per-sender channel conversation history.
+//!
+//! The channel runtime always works from its in-process history map. When a
+//! [`SessionStore`] is configured, each turn first reloads the sender's
+//! history from the store and writes it back afterwards. That lets several
+//! instances behind a load balancer continue the same conversation.
+//!
+//! Persistent stores also hold named checkpoints: frozen copies of a
+//! session that can later be forked into a new session, and per-session
+//! metadata (channel, principal, creation time, a title taken from the
+//! first message, and user-assigned tags).
+//!
+//! Stores with local files are maintained in the background (see
+//! [`run_maintenance_loop`]) and by `zeroclaw db maintain`.
+
+pub mod cli;
+pub mod redis;
+pub mod sqlite;
+pub mod tokens;
+
+use crate::config::{SessionBackend, SessionConfig};
+use crate::observability::traits::{Observer, ObserverMetric};
+use crate::providers::ChatMessage;
+use crate::util::truncate_with_ellipsis;
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub use self::redis::RedisSessionStore;
+pub use self::sqlite::SqliteSessionStore;
+
+/// Key prefixes for checkpoints, pinned messages and metadata. Channel
+/// history keys start with the channel name and never contain `:`, so these
+/// cannot collide with a session.
+const CHECKPOINT_KEY_PREFIX: &str = "checkpoint:";
+const PINS_KEY_PREFIX: &str = "pins:";
+const META_KEY_PREFIX: &str = "meta:";
+
+/// Role of the single stored turn that carries a session's metadata.
+const METADATA_ROLE: &str = "metadata";
+
+/// Longest auto-generated session title, in characters.
+const TITLE_MAX_CHARS: usize = 60;
+
+/// Most tags kept per session.
+const MAX_TAGS: usize = 16;
+
+/// One stored session, as reported by [`SessionStore::list`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionSummary {
+    pub key: String,
+    pub messages: usize,
+    /// Unix timestamp of the last write, when the backend can tell.
+    pub last_activity: Option<i64>,
+}
+
+/// Descriptive data kept next to a session.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionMetadata {
+    pub channel: String,
+    /// `<channel>:<sender>` the session belongs to.
+    #[serde(default)]
+    pub principal: Option<String>,
+    /// Unix timestamp of the first stored turn; unknown for sessions stored
+    /// before metadata was kept.
+    #[serde(default)]
+    pub created_at: Option<i64>,
+    /// First line of the first message, shortened.
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl SessionMetadata {
+    /// Metadata for a session that starts with `first_message`.
+    pub fn new(channel: &str, principal: Option<String>, first_message: &str) -> Self {
+        Self {
+            channel: channel.to_string(),
+            principal,
+            created_at: Some(chrono::Utc::now().timestamp()),
+            title: title_from_message(first_message),
+            tags: Vec::new(),
+        }
+    }
+}
+
+/// Outcome of one [`SessionStore::maintain`] pass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MaintenanceReport {
+    pub db_path: PathBuf,
+    /// Expired sessions, checkpoints and pins deleted.
+    pub expired_removed: usize,
+    /// Size of the database and its write-ahead log before the pass.
+    pub bytes_before: u64,
+    /// Size of the database and its write-ahead log after the pass.
+    pub bytes_after: u64,
+    /// Free pages returned to the filesystem.
+    pub pages_freed: u64,
+}
+
+/// External storage for conversation history, keyed by sender history key.
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Backend name for logs and diagnostics.
+    fn name(&self) -> &str;
+
+    /// Load the stored turns for `key`, or `None` if there is no session.
+    async fn load(&self, key: &str) -> Result<Option<Vec<ChatMessage>>>;
+
+    /// Replace the stored turns for `key` and refresh its TTL.
+    async fn save(&self, key: &str, turns: &[ChatMessage]) -> Result<()>;
+
+    /// Delete the session for `key`.
+    async fn remove(&self, key: &str) -> Result<()>;
+
+    /// Stored entries whose key starts with `prefix`, most recently active
+    /// first. Checkpoints and pins are included; see [`is_session_key`].
+    async fn list(&self, prefix: &str) -> Result<Vec<SessionSummary>>;
+
+    /// Purge expired entries and reclaim local storage. Backends without
+    /// local files return `None`.
+    async fn maintain(&self) -> Result<Option<MaintenanceReport>> {
+        Ok(None)
+    }
+}
+
+/// Build the configured external session store. The `memory` backend keeps
+/// history in-process only and returns `None`.
+pub fn create_session_store(
+    config: &SessionConfig,
+    workspace_dir: &Path,
+) -> Result<Option<Arc<dyn SessionStore>>> {
+    match config.backend {
+        SessionBackend::Memory => Ok(None),
+        SessionBackend::Sqlite => Ok(Some(Arc::new(SqliteSessionStore::new(
+            workspace_dir,
+            config,
+        )?))),
+        SessionBackend::Redis => Ok(Some(Arc::new(RedisSessionStore::from_config(config)?))),
+    }
+}
+
+/// Maintain `store` every `interval` until `stop` is cancelled, reporting
+/// the database size to `observer`.
+pub async fn run_maintenance_loop(
+    store: Arc<dyn SessionStore>,
+    observer: Arc<dyn Observer>,
+    interval: Duration,
+    stop: tokio_util::sync::CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            () = stop.cancelled() => return,
+            _ = ticker.tick() => {}
+        }
+        match store.maintain().await {
+            Ok(Some(report)) => {
+                tracing::debug!(
+                    expired = report.expired_removed,
+                    bytes_before = report.bytes_before,
+                    bytes_after = report.bytes_after,
+                    "Session store maintained"
+                );
+                observer.record_metric(&ObserverMetric::DatabaseSize {
+                    database: "sessions".into(),
+                    bytes: report.bytes_after,
+                });
+            }
+            // Nothing to maintain on this backend.
+            Ok(None) => return,
+            Err(e) => tracing::warn!("Session store maintenance failed: {e:#}"),
+        }
+    }
+}
+
+/// Store key holding checkpoint `name` of `session`.
+pub fn checkpoint_key(session: &str, name: &str) -> String {
+    format!("{CHECKPOINT_KEY_PREFIX}{session}:{name}")
+}
+
+/// Store key holding the pinned messages of `session`.
+pub fn pins_key(session: &str) -> String {
+    format!("{PINS_KEY_PREFIX}{session}")
+}
+
+/// Store key holding the metadata of `session`.
+pub fn metadata_key(session: &str) -> String {
+    format!("{META_KEY_PREFIX}{session}")
+}
+
+/// Whether `key` names a conversation rather than a checkpoint, pin set or
+/// metadata entry.
+pub fn is_session_key(key: &str) -> bool {
+    !key.starts_with(CHECKPOINT_KEY_PREFIX)
+        && !key.starts_with(PINS_KEY_PREFIX)
+        && !key.starts_with(META_KEY_PREFIX)
+}
+
+/// Session title from the first non-empty line of `message`.
+fn title_from_message(message: &str) -> Option<String> {
+    let line = message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())?;
+    Some(truncate_with_ellipsis(line, TITLE_MAX_CHARS))
+}
+
+/// Metadata stored for `session`, if any.
+pub async fn load_metadata(
+    store: &dyn SessionStore,
+    session: &str,
+) -> Result<Option<SessionMetadata>> {
+    let Some(turns) = store.load(&metadata_key(session)).await? else {
+        return Ok(None);
+    };
+    let Some(turn) = turns.into_iter().find(|turn| turn.role == METADATA_ROLE) else {
+        return Ok(None);
+    };
+    Ok(Some(serde_json::from_str(&turn.content)?))
+}
+
+/// Replace the metadata of `session`.
+pub async fn save_metadata(
+    store: &dyn SessionStore,
+    session: &str,
+    metadata: &SessionMetadata,
+) -> Result<()> {
+    let turn = ChatMessage {
+        role: METADATA_ROLE.into(),
+        content: serde_json::to_string(metadata)?,
+    };
+    store.save(&metadata_key(session), &[turn]).await
+}
+
+/// Record metadata for `session` on its first turn. Stores with a TTL get
+/// the existing entry written back so it expires with the session.
+pub async fn touch_metadata(
+    store: &dyn SessionStore,
+    session: &str,
+    refresh_ttl: bool,
+    init: impl FnOnce() -> SessionMetadata + Send,
+) -> Result<()> {
+    match load_metadata(store, session).await? {
+        Some(existing) if refresh_ttl => save_metadata(store, session, &existing).await,
+        Some(_) => Ok(()),
+        None => save_metadata(store, session, &init()).await,
+    }
+}
+
+fn validate_tag(tag: &str) -> Result<()> {
+    if tag.is_empty()
+        || tag.len() > 32
+        || !tag
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        bail!("Tag '{tag}' must be 1-32 characters of [A-Za-z0-9_.-]");
+    }
+    Ok(())
+}
+
+/// Add and remove tags on `session`. Returns the resulting tags, sorted.
+pub async fn update_tags(
+    store: &dyn SessionStore,
+    session: &str,
+    add: &[String],
+    remove: &[String],
+) -> Result<Vec<String>> {
+    if store.load(session).await?.is_none() {
+        bail!(
+            "Session '{session}' not found in the {} store",
+            store.name()
+        );
+    }
+    for tag in add {
+        validate_tag(tag)?;
+    }
+    // Sessions stored before metadata existed start from an empty entry.
+    let mut metadata = load_metadata(store, session).await?.unwrap_or_default();
+    metadata.tags.retain(|tag| !remove.contains(tag));
+    metadata.tags.extend(add.iter().cloned());
+    metadata.tags.sort();
+    metadata.tags.dedup();
+    if metadata.tags.len() > MAX_TAGS {
+        bail!("A session can have at most {MAX_TAGS} tags");
+    }
+    save_metadata(store, session, &metadata).await?;
+    Ok(metadata.tags)
+}
+
+/// A stored session with its metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionEntry {
+    #[serde(flatten)]
+    pub summary: SessionSummary,
+    pub metadata: Option<SessionMetadata>,
+}
+
+/// Sessions whose key starts with `prefix`, most recently active first,
+/// optionally only those tagged `tag`.
+pub async fn list_sessions(
+    store: &dyn SessionStore,
+    prefix: &str,
+    tag: Option<&str>,
+) -> Result<Vec<SessionEntry>> {
+    let mut entries = Vec::new();
+    for summary in store.list(prefix).await? {
+        if !is_session_key(&summary.key) {
+            continue;
+        }
+        let metadata = load_metadata(store, &summary.key).await?;
+        if let Some(tag) = tag {
+            if !metadata
+                .as_ref()
+                .is_some_and(|meta| meta.tags.iter().any(|t| t == tag))
+            {
+                continue;
+            }
+        }
+        entries.push(SessionEntry { summary, metadata });
+    }
+    Ok(entries)
+}
+
+/// Delete `session` along with its checkpoints and pinned messages.
+/// Returns the number of checkpoints removed.
+pub async fn delete_session(store: &dyn SessionStore, session: &str) -> Result<usize> {
+    if store.load(session).await?.is_none() {
+        bail!(
+            "Session '{session}' not found in the {} store",
+            store.name()
+        );
+    }
+    store.remove(session).await?;
+    store.remove(&pins_key(session)).await?;
+    store.remove(&metadata_key(session)).await?;
+
+    let checkpoints = store.list(&checkpoint_key(session, "")).await?;
+    for checkpoint in &checkpoints {
+        store.remove(&checkpoint.key).await?;
+    }
+    Ok(checkpoints.len())
+}
+
+fn validate_checkpoint_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || name.len() > 64
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        bail!("Checkpoint name must be 1-64 characters of [A-Za-z0-9_.-]");
+    }
+    Ok(())
+}
+
+/// Snapshot `session` as checkpoint `name`, optionally keeping only the first
+/// `messages` turns. Returns the number of turns captured.
+pub async fn create_checkpoint(
+    store: &dyn SessionStore,
+    session: &str,
+    name: &str,
+    messages: Option<usize>,
+) -> Result<usize> {
+    validate_checkpoint_name(name)?;
+    let Some(mut turns) = store.load(session).await? else {
+        bail!(
+            "Session '{session}' not found in the {} store",
+            store.name()
+        );
+    };
+    if let Some(limit) = messages {
+        if limit == 0 || limit > turns.len() {
+            bail!(
+                "--messages must be between 1 and {} for session '{session}'",
+                turns.len()
+            );
+        }
+        turns.truncate(limit);
+    }
+    store.save(&checkpoint_key(session, name), &turns).await?;
+    Ok(turns.len())
+}
+
+/// Start `new_session` from checkpoint `name` of `session`. Refuses to
+/// overwrite an existing session. Returns the number of turns copied.
+pub async fn fork_checkpoint(
+    store: &dyn SessionStore,
+    session: &str,
+    name: &str,
+    new_session: &str,
+) -> Result<usize> {
+    validate_checkpoint_name(name)?;
+    if new_session.trim().is_empty() || !is_session_key(new_session) {
+        bail!("Invalid target session key '{new_session}'");
+    }
+    let Some(turns) = store.load(&checkpoint_key(session, name)).await? else {
+        bail!("Checkpoint '{name}' not found for session '{session}'");
+    };
+    if store
+        .load(new_session)
+        .await?
+        .is_some_and(|existing| !existing.is_empty())
+    {
+        bail!("Session '{new_session}' already exists; delete it first or pick another key");
+    }
+    store.save(new_session, &turns).await?;
+    if let Some(mut metadata) = load_metadata(store, session).await? {
+        metadata.created_at = Some(chrono::Utc::now().timestamp());
+        save_metadata(store, new_session, &metadata).await?;
+    }
+    Ok(turns.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn checkpoint_then_fork_copies_frozen_turns() {
+        let tmp = TempDir::new().unwrap();
+        let store = SqliteSessionStore::new(tmp.path(), &SessionConfig::default()).unwrap();
+        let turns = vec![
+            ChatMessage::user("use metric units"),
+            ChatMessage::assistant("noted"),
+            ChatMessage::user("convert 5 miles"),
+            ChatMessage::assistant("8.05 km"),
+        ];
+        store.save("telegram_alice", &turns).await.unwrap();
+
+        assert_eq!(
+            create_checkpoint(&store, "telegram_alice", "setup", Some(2))
+                .await
+                .unwrap(),
+            2
+        );
+        // Later changes to the source session do not affect the checkpoint.
+        store.remove("telegram_alice").await.unwrap();
+
+        assert_eq!(
+            fork_checkpoint(&store, "telegram_alice", "setup", "cli_experiment")
+                .await
+                .unwrap(),
+            2
+        );
+        let forked = store.load("cli_experiment").await.unwrap().unwrap();
+        assert_eq!(forked[1].content, "noted");
+
+        assert!(
+            fork_checkpoint(&store, "telegram_alice", "setup", "cli_experiment")
+                .await
+                .is_err()
+        );
+        assert!(
+            create_checkpoint(&store, "telegram_alice", "bad name", None)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn metadata_keeps_first_title_and_filters_by_tag() {
+        let tmp = TempDir::new().unwrap();
+        let store = SqliteSessionStore::new(tmp.path(), &SessionConfig::default()).unwrap();
+        store
+            .save("telegram_alice", &[ChatMessage::user("plan the trip")])
+            .await
+            .unwrap();
+        store
+            .save("discord_bob", &[ChatMessage::user("hi")])
+            .await
+            .unwrap();
+        for message in ["\n  Plan the Lisbon trip\nbudget 2k", "second message"] {
+            touch_metadata(&store, "telegram_alice", false, || {
+                SessionMetadata::new("telegram", Some("telegram:alice".into()), message)
+            })
+            .await
+            .unwrap();
+        }
+        let metadata = load_metadata(&store, "telegram_alice")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Plan the Lisbon trip"));
+        assert_eq!(metadata.principal.as_deref(), Some("telegram:alice"));
+
+        let tags = update_tags(
+            &store,
+            "telegram_alice",
+            &["travel".into(), "q3".into()],
+            &[],
+        )
+        .await
+        .unwrap();
+        assert_eq!(tags, vec!["q3", "travel"]);
+        assert!(
+            update_tags(&store, "telegram_alice", &["bad tag".into()], &[])
+                .await
+                .is_err()
+        );
+
+        assert_eq!(list_sessions(&store, "", None).await.unwrap().len(), 2);
+        let travel = list_sessions(&store, "", Some("travel")).await.unwrap();
+        assert_eq!(travel.len(), 1);
+        assert_eq!(travel[0].summary.key, "telegram_alice");
+
+        delete_session(&store, "telegram_alice").await.unwrap();
+        assert!(load_metadata(&store, "telegram_alice")
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
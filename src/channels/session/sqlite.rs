@@ -0,0 +1,320 @@
+//! SQLite session backend.
+//!
+//! Sessions live in `<workspace>/sessions/sessions.db`, one row per key with
+//! the JSON-encoded turns. This keeps channel history across restarts on a
+//! single host and lets CLI commands inspect sessions owned by a running
+//! daemon. `ttl_secs` is enforced on read: expired rows are deleted instead
+//! of returned.
+//!
+//! The database uses incremental auto-vacuum. [`SessionStore::maintain`]
+//! purges expired rows, truncates the WAL and returns free pages to the
+//! filesystem; databases created without auto-vacuum are switched over by
+//! one full `VACUUM` on their first pass.
+
+use super::{MaintenanceReport, SessionStore, SessionSummary};
+use crate::config::SessionConfig;
+use crate::db::{migrate, Migration, Schema, Step};
+use crate::providers::ChatMessage;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Tables of `sessions/sessions.db`.
+pub static SCHEMA: Schema = Schema {
+    name: "sessions",
+    path: "sessions/sessions.db",
+    migrations: &[Migration {
+        version: 1,
+        description: "create sessions",
+        step: Step::Sql(
+            "CREATE TABLE IF NOT EXISTS sessions (
+                 key        TEXT PRIMARY KEY,
+                 turns      TEXT NOT NULL,
+                 updated_at INTEGER NOT NULL
+             );",
+        ),
+    }],
+};
+
+/// Session store backed by a local SQLite database.
+pub struct SqliteSessionStore {
+    conn: Arc<Mutex<Connection>>,
+    db_path: PathBuf,
+    ttl_secs: u64,
+}
+
+impl SqliteSessionStore {
+    pub fn new(workspace_dir: &Path, config: &SessionConfig) -> Result<Self> {
+        let db_path = SCHEMA.db_path(workspace_dir);
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA auto_vacuum  = INCREMENTAL;
+             PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        migrate(&conn, &SCHEMA)?;
+
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            db_path,
+            ttl_secs: config.ttl_secs,
+        })
+    }
+
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    /// Bytes used by the database file and its write-ahead log.
+    pub fn size_on_disk(&self) -> u64 {
+        let mut wal = self.db_path.clone().into_os_string();
+        wal.push("-wal");
+        [self.db_path.as_path(), Path::new(&wal)]
+            .iter()
+            .filter_map(|path| std::fs::metadata(path).ok())
+            .map(|meta| meta.len())
+            .sum()
+    }
+
+    async fn with_conn<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T> + Send + 'static,
+    {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || f(&conn.lock())).await?
+    }
+}
+
+#[async_trait]
+impl SessionStore for SqliteSessionStore {
+    fn name(&self) -> &str {
+        "sqlite"
+    }
+
+    async fn load(&self, key: &str) -> Result<Option<Vec<ChatMessage>>> {
+        let key = key.to_string();
+        let ttl = i64::try_from(self.ttl_secs).unwrap_or(i64::MAX);
+        self.with_conn(move |conn| {
+            let row: Option<(String, i64)> = conn
+                .query_row(
+                    "SELECT turns, updated_at FROM sessions WHERE key = ?1",
+                    params![key],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            let Some((turns, updated_at)) = row else {
+                return Ok(None);
+            };
+            if ttl > 0 && chrono::Utc::now().timestamp().saturating_sub(updated_at) > ttl {
+                conn.execute("DELETE FROM sessions WHERE key = ?1", params![key])?;
+                return Ok(None);
+            }
+            Ok(Some(serde_json::from_str(&turns).with_context(|| {
+                format!("Corrupt session payload for '{key}'")
+            })?))
+        })
+        .await
+    }
+
+    async fn save(&self, key: &str, turns: &[ChatMessage]) -> Result<()> {
+        let key = key.to_string();
+        let payload = serde_json::to_string(turns)?;
+        self.with_conn(move |conn| {
+            conn.execute(
+                "INSERT INTO sessions (key, turns, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(key) DO UPDATE SET turns = excluded.turns, updated_at = excluded.updated_at",
+                params![key, payload, chrono::Utc::now().timestamp()],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn remove(&self, key: &str) -> Result<()> {
+        let key = key.to_string();
+        self.with_conn(move |conn| {
+            conn.execute("DELETE FROM sessions WHERE key = ?1", params![key])?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<SessionSummary>> {
+        let prefix = prefix.to_string();
+        let ttl = i64::try_from(self.ttl_secs).unwrap_or(i64::MAX);
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT key, turns, updated_at FROM sessions
+                 WHERE substr(key, 1, length(?1)) = ?1 AND (?2 = 0 OR updated_at >= ?3)
+                 ORDER BY updated_at DESC, key",
+            )?;
+            let cutoff = chrono::Utc::now().timestamp().saturating_sub(ttl);
+            let rows = stmt
+                .query_map(params![prefix, ttl, cutoff], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, i64>(2)?,
+                    ))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            rows.into_iter()
+                .map(|(key, turns, updated_at)| {
+                    let turns: Vec<serde_json::Value> = serde_json::from_str(&turns)
+                        .with_context(|| format!("Corrupt session payload for '{key}'"))?;
+                    Ok(SessionSummary {
+                        key,
+                        messages: turns.len(),
+                        last_activity: Some(updated_at),
+                    })
+                })
+                .collect()
+        })
+        .await
+    }
+
+    async fn maintain(&self) -> Result<Option<MaintenanceReport>> {
+        let bytes_before = self.size_on_disk();
+        let ttl = i64::try_from(self.ttl_secs).unwrap_or(i64::MAX);
+        let (expired_removed, pages_freed) = self
+            .with_conn(move |conn| {
+                let expired_removed = if ttl > 0 {
+                    let cutoff = chrono::Utc::now().timestamp().saturating_sub(ttl);
+                    conn.execute(
+                        "DELETE FROM sessions WHERE updated_at < ?1",
+                        params![cutoff],
+                    )?
+                } else {
+                    0
+                };
+
+                let free_pages = |conn: &Connection| -> rusqlite::Result<u64> {
+                    conn.query_row("PRAGMA freelist_count", [], |row| row.get(0))
+                };
+                let free_before = free_pages(conn)?;
+                let auto_vacuum: i64 =
+                    conn.query_row("PRAGMA auto_vacuum", [], |row| row.get(0))?;
+                if auto_vacuum == 2 {
+                    // Each step frees one page, so drain the statement.
+                    let mut stmt = conn.prepare("PRAGMA incremental_vacuum")?;
+                    let mut rows = stmt.query([])?;
+                    while rows.next()?.is_some() {}
+                } else {
+                    conn.execute_batch("PRAGMA auto_vacuum = INCREMENTAL; VACUUM;")?;
+                }
+                let free_after = free_pages(conn)?;
+
+                let busy: i64 =
+                    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |row| row.get(0))?;
+                if busy != 0 {
+                    tracing::debug!("Session WAL checkpoint blocked by an open reader");
+                }
+                Ok((expired_removed, free_before.saturating_sub(free_after)))
+            })
+            .await?;
+
+        Ok(Some(MaintenanceReport {
+            db_path: self.db_path.clone(),
+            expired_removed,
+            bytes_before,
+            bytes_after: self.size_on_disk(),
+            pages_freed,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn save_load_remove_roundtrip() {
+        let tmp = TempDir::new().unwrap();
+        let store = SqliteSessionStore::new(tmp.path(), &SessionConfig::default()).unwrap();
+
+        assert!(store.load("telegram_alice").await.unwrap().is_none());
+        store
+            .save(
+                "telegram_alice",
+                &[ChatMessage::user("hi"), ChatMessage::assistant("hello")],
+            )
+            .await
+            .unwrap();
+        let turns = store.load("telegram_alice").await.unwrap().unwrap();
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[1].content, "hello");
+
+        store.remove("telegram_alice").await.unwrap();
+        assert!(store.load("telegram_alice").await.unwrap().is_none());
+        assert!(store.db_path().exists());
+    }
+
+    #[tokio::test]
+    async fn list_filters_by_prefix() {
+        let tmp = TempDir::new().unwrap();
+        let store = SqliteSessionStore::new(tmp.path(), &SessionConfig::default()).unwrap();
+        store
+            .save("telegram_alice", &[ChatMessage::user("hi")])
+            .await
+            .unwrap();
+        store
+            .save(
+                "discord_bob",
+                &[ChatMessage::user("a"), ChatMessage::assistant("b")],
+            )
+            .await
+            .unwrap();
+
+        let all = store.list("").await.unwrap();
+        assert_eq!(all.len(), 2);
+        let discord = store.list("discord_").await.unwrap();
+        assert_eq!(discord.len(), 1);
+        assert_eq!(discord[0].key, "discord_bob");
+        assert_eq!(discord[0].messages, 2);
+        assert!(discord[0].last_activity.is_some());
+    }
+
+    #[tokio::test]
+    async fn maintain_purges_expired_rows_and_reclaims_space() {
+        let tmp = TempDir::new().unwrap();
+        let config = SessionConfig {
+            ttl_secs: 60,
+            ..SessionConfig::default()
+        };
+        let store = SqliteSessionStore::new(tmp.path(), &config).unwrap();
+        let long_turn = ChatMessage::user("x".repeat(64 * 1024));
+        for n in 0..8 {
+            store
+                .save(&format!("telegram_{n}"), std::slice::from_ref(&long_turn))
+                .await
+                .unwrap();
+        }
+        store
+            .with_conn(|conn| {
+                conn.execute(
+                    "UPDATE sessions SET updated_at = 0 WHERE key != 'telegram_0'",
+                    [],
+                )?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let report = store.maintain().await.unwrap().unwrap();
+        assert_eq!(report.expired_removed, 7);
+        assert!(report.pages_freed > 0);
+        assert!(report.bytes_after < report.bytes_before);
+        assert_eq!(store.list("").await.unwrap().len(), 1);
+    }
+}
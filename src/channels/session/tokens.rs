@@ -0,0 +1,176 @@
+//! Token-budget trimming for channel history.
+//!
+//! Counting by messages ignores that a single pasted log can fill the
+//! context window. Before each model call, the channel runtime estimates
+//! the prompt size for the target model and drops the oldest turns until the
+//! system prompt, history, and `response_reserve_tokens` fit in the model's
+//! context window. Estimates are heuristic (no tokenizer is bundled) and err
+//! on the high side.
+
+use crate::config::SessionConfig;
+use crate::providers::ChatMessage;
+
+/// Fixed per-message overhead for role markers and separators.
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
+/// Context window for `model` on `provider`, honouring
+/// `session.context_tokens` when set.
+pub fn context_window(provider: &str, model: &str, config: &SessionConfig) -> usize {
+    if config.context_tokens > 0 {
+        return config.context_tokens;
+    }
+    crate::providers::capabilities::context_window(provider, model)
+}
+
+/// Approximate chars per token for ASCII text. Claude and Gemini tokenizers
+/// are a little less dense than OpenAI's on English text.
+fn ascii_chars_per_token(model: &str) -> usize {
+    let model = model.to_ascii_lowercase();
+    if model.contains("claude") || model.contains("gemini") {
+        3
+    } else {
+        4
+    }
+}
+
+/// Estimate tokens for `text` on `model`. Non-ASCII characters (CJK, emoji)
+/// are counted as one token each.
+pub fn estimate_text_tokens(model: &str, text: &str) -> usize {
+    let (ascii, other) = text.chars().fold((0usize, 0usize), |(a, o), c| {
+        if c.is_ascii() {
+            (a + 1, o)
+        } else {
+            (a, o + 1)
+        }
+    });
+    ascii.div_ceil(ascii_chars_per_token(model)) + other
+}
+
+/// Longest prefix of `text` estimated at no more than `max_tokens` on
+/// `model`.
+pub fn truncate_to_tokens<'a>(model: &str, text: &'a str, max_tokens: usize) -> &'a str {
+    let per_token = ascii_chars_per_token(model);
+    let (mut ascii, mut other) = (0usize, 0usize);
+    for (idx, c) in text.char_indices() {
+        if c.is_ascii() {
+            ascii += 1;
+        } else {
+            other += 1;
+        }
+        if ascii.div_ceil(per_token) + other > max_tokens {
+            return &text[..idx];
+        }
+    }
+    text
+}
+
+/// Estimate tokens for one chat message including framing overhead.
+pub fn estimate_message_tokens(model: &str, message: &ChatMessage) -> usize {
+    estimate_text_tokens(model, &message.content) + MESSAGE_OVERHEAD_TOKENS
+}
+
+/// Drop the oldest turns until `system_prompt` plus `turns` fit within the
+/// model's context window minus the response reserve. The latest turn is
+/// always kept, and history never starts with an assistant turn. Returns the
+/// number of turns removed.
+pub fn trim_to_token_budget(
+    turns: &mut Vec<ChatMessage>,
+    system_prompt: &str,
+    provider: &str,
+    model: &str,
+    config: &SessionConfig,
+) -> usize {
+    let budget = context_window(provider, model, config)
+        .saturating_sub(config.response_reserve_tokens)
+        .saturating_sub(estimate_text_tokens(model, system_prompt) + MESSAGE_OVERHEAD_TOKENS);
+
+    let costs: Vec<usize> = turns
+        .iter()
+        .map(|turn| estimate_message_tokens(model, turn))
+        .collect();
+    let mut total: usize = costs.iter().sum();
+    let mut drop = 0;
+    while total > budget && drop + 1 < turns.len() {
+        total -= costs[drop];
+        drop += 1;
+    }
+    while drop + 1 < turns.len() && turns[drop].role != "user" {
+        drop += 1;
+    }
+
+    turns.drain(..drop);
+    drop
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn context_window_uses_model_table_and_override() {
+        let config = SessionConfig::default();
+        assert_eq!(
+            context_window("openrouter", "anthropic/claude-sonnet-4", &config),
+            200_000
+        );
+        assert_eq!(context_window("openai", "gpt-4o-mini", &config), 128_000);
+        assert_eq!(
+            context_window("ollama", "some-local-model", &config),
+            32_000
+        );
+
+        let pinned = SessionConfig {
+            context_tokens: 8_000,
+            ..SessionConfig::default()
+        };
+        assert_eq!(
+            context_window("anthropic", "claude-sonnet-4", &pinned),
+            8_000
+        );
+    }
+
+    #[test]
+    fn estimate_counts_non_ascii_per_char() {
+        assert_eq!(estimate_text_tokens("gpt-4o", "abcdefgh"), 2);
+        assert_eq!(estimate_text_tokens("gpt-4o", "你好"), 2);
+    }
+
+    #[test]
+    fn trim_drops_oldest_turns_including_oversized_ones() {
+        let config = SessionConfig {
+            context_tokens: 1_000,
+            response_reserve_tokens: 200,
+            ..SessionConfig::default()
+        };
+        let mut turns = vec![
+            ChatMessage::user("x".repeat(8_000)),
+            ChatMessage::assistant("ok"),
+            ChatMessage::user("short question"),
+            ChatMessage::assistant("short answer"),
+            ChatMessage::user("latest"),
+        ];
+
+        let removed = trim_to_token_budget(&mut turns, "system", "openai", "gpt-4o", &config);
+        assert_eq!(removed, 2);
+        assert_eq!(turns[0].content, "short question");
+        assert_eq!(turns.last().unwrap().content, "latest");
+    }
+
+    #[test]
+    fn trim_keeps_latest_turn_even_when_over_budget() {
+        let config = SessionConfig {
+            context_tokens: 500,
+            response_reserve_tokens: 100,
+            ..SessionConfig::default()
+        };
+        let mut turns = vec![
+            ChatMessage::user("old"),
+            ChatMessage::assistant("reply"),
+            ChatMessage::user("y".repeat(10_000)),
+        ];
+
+        trim_to_token_budget(&mut turns, "system", "openai", "gpt-4o", &config);
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].role, "user");
+    }
+}
@@ -1,9 +1,11 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelMessage, HomeView, MessageRef, SendMessage};
+use crate::config::StreamMode;
 use async_trait::async_trait;
 use chrono::Utc;
 use futures_util::{SinkExt, StreamExt};
 use reqwest::header::HeaderMap;
 use std::collections::HashMap;
+use std::fmt::Write;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio_tungstenite::tungstenite::Message as WsMessage;
 
@@ -15,13 +17,45 @@ pub struct SlackChannel {
     allowed_users: Vec<String>,
     mention_only: bool,
     group_reply_allowed_sender_ids: Vec<String>,
+    stream_mode: StreamMode,
+    draft_update_interval_ms: u64,
+    /// Last draft edit per channel, for rate limiting `chat.update` calls.
+    last_draft_edit: parking_lot::Mutex<HashMap<String, Instant>>,
+    app_home: bool,
+    /// Mention-only choices users made on the App Home tab.
+    mention_only_by_user: parking_lot::Mutex<HashMap<String, bool>>,
 }
 
+/// A Web API call Slack answered with HTTP 429.
+#[derive(Debug)]
+struct SlackRateLimited {
+    method: String,
+    retry_after_secs: u64,
+}
+
+impl std::fmt::Display for SlackRateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Slack {} rate limited; retry after {}s",
+            self.method, self.retry_after_secs
+        )
+    }
+}
+
+impl std::error::Error for SlackRateLimited {}
+
 const SLACK_HISTORY_MAX_RETRIES: u32 = 3;
 const SLACK_HISTORY_DEFAULT_RETRY_AFTER_SECS: u64 = 1;
 const SLACK_HISTORY_MAX_BACKOFF_SECS: u64 = 120;
 const SLACK_HISTORY_MAX_JITTER_MS: u64 = 500;
 
+/// `action_id`s of the App Home buttons.
+const HOME_ACTION_APPROVE: &str = "zeroclaw_home_approve";
+const HOME_ACTION_DENY: &str = "zeroclaw_home_deny";
+const HOME_ACTION_RESET: &str = "zeroclaw_home_reset";
+const HOME_ACTION_MENTION_ONLY: &str = "zeroclaw_home_mention_only";
+
 impl SlackChannel {
     pub fn new(
         bot_token: String,
@@ -36,6 +70,11 @@ impl SlackChannel {
             allowed_users,
             mention_only: false,
             group_reply_allowed_sender_ids: Vec::new(),
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            last_draft_edit: parking_lot::Mutex::new(HashMap::new()),
+            app_home: false,
+            mention_only_by_user: parking_lot::Mutex::new(HashMap::new()),
         }
     }
 
@@ -51,10 +90,73 @@ impl SlackChannel {
         self
     }
 
+    /// Configure streaming mode for progressive draft updates.
+    pub fn with_streaming(
+        mut self,
+        stream_mode: StreamMode,
+        draft_update_interval_ms: u64,
+    ) -> Self {
+        self.stream_mode = stream_mode;
+        self.draft_update_interval_ms = draft_update_interval_ms;
+        self
+    }
+
+    /// Publish an App Home tab. Home events arrive only over Socket Mode.
+    pub fn with_app_home(mut self, app_home: bool) -> Self {
+        self.app_home = app_home;
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.slack")
     }
 
+    /// POST a Web API method and return the parsed response. Slack reports
+    /// most app-level errors with HTTP 200 and `"ok": false`.
+    async fn call_web_api(
+        &self,
+        method: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let resp = self
+            .http_client()
+            .post(format!("https://slack.com/api/{method}"))
+            .bearer_auth(&self.bot_token)
+            .json(body)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(SlackRateLimited {
+                method: method.to_string(),
+                retry_after_secs: Self::parse_retry_after_secs(resp.headers())
+                    .unwrap_or(SLACK_HISTORY_DEFAULT_RETRY_AFTER_SECS),
+            }
+            .into());
+        }
+        let body = resp
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+
+        if !status.is_success() {
+            let sanitized = crate::providers::sanitize_api_error(&body);
+            anyhow::bail!("Slack {method} failed ({status}): {sanitized}");
+        }
+
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
+            let err = parsed
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown");
+            anyhow::bail!("Slack {method} failed: {err}");
+        }
+
+        Ok(parsed)
+    }
+
     /// Check if a Slack user ID is in the allowlist.
     /// Empty list means deny everyone until explicitly configured.
     /// `"*"` means allow everyone.
@@ -73,6 +175,265 @@ impl SlackChannel {
             .any(|entry| entry == "*" || entry == user_id)
     }
 
+    /// Mention-only policy for `user`: their App Home choice, else the
+    /// channel's group-reply policy.
+    fn mention_only_for(&self, user_id: &str) -> bool {
+        self.mention_only_by_user
+            .lock()
+            .get(user_id)
+            .copied()
+            .unwrap_or(self.mention_only)
+    }
+
+    /// Turn an `app_home_opened` event into a `/home` command so the
+    /// dispatcher publishes the user's view.
+    fn parse_app_home_opened(&self, event: &serde_json::Value) -> Option<ChannelMessage> {
+        if !self.app_home || event.get("tab").and_then(|v| v.as_str()) != Some("home") {
+            return None;
+        }
+        let user = event.get("user").and_then(|v| v.as_str())?;
+        if !self.is_user_allowed(user) {
+            return None;
+        }
+        let event_ts = event
+            .get("event_ts")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        Some(Self::home_command_message(
+            user,
+            event_ts,
+            "/home".to_string(),
+        ))
+    }
+
+    /// Map an App Home button press to the runtime command it stands for.
+    /// The mention-only toggle is applied here and answered with `/home`
+    /// to republish the view.
+    fn parse_home_action(&self, payload: &serde_json::Value) -> Option<ChannelMessage> {
+        if !self.app_home || payload.get("type").and_then(|v| v.as_str()) != Some("block_actions") {
+            return None;
+        }
+        let user = payload
+            .get("user")
+            .and_then(|user| user.get("id"))
+            .and_then(|v| v.as_str())?;
+        if !self.is_user_allowed(user) {
+            tracing::warn!("Slack: ignoring App Home action from unauthorized user: {user}");
+            return None;
+        }
+        let action = payload.get("actions")?.as_array()?.first()?;
+        let action_id = action.get("action_id").and_then(|v| v.as_str())?;
+        let value = action
+            .get("value")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .trim();
+        let content = match action_id {
+            HOME_ACTION_APPROVE if !value.is_empty() => format!("/approve-allow {value}"),
+            HOME_ACTION_DENY if !value.is_empty() => format!("/approve-deny {value}"),
+            HOME_ACTION_RESET => "/new".to_string(),
+            HOME_ACTION_MENTION_ONLY => {
+                self.mention_only_by_user
+                    .lock()
+                    .insert(user.to_string(), value == "on");
+                "/home".to_string()
+            }
+            _ => return None,
+        };
+        let action_ts = action
+            .get("action_ts")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        Some(Self::home_command_message(user, action_ts, content))
+    }
+
+    /// Slack `ts` of an inbound message id (`slack_<channel>_<ts>`).
+    fn message_ts<'a>(channel_id: &str, message_id: &'a str) -> Option<&'a str> {
+        message_id
+            .strip_prefix("slack_")?
+            .strip_prefix(channel_id)?
+            .strip_prefix('_')
+            .filter(|ts| !ts.is_empty())
+    }
+
+    /// Slack has no typing indicator for bots, so the dispatcher's 👀 / ✅ /
+    /// ⚠️ acknowledgement reactions show processing state instead.
+    fn reaction_name(emoji: &str) -> Option<&'static str> {
+        match emoji {
+            "\u{1F440}" => Some("eyes"),
+            "\u{2705}" => Some("white_check_mark"),
+            "\u{26A0}\u{FE0F}" | "\u{26A0}" => Some("warning"),
+            _ => None,
+        }
+    }
+
+    async fn call_reactions_api(
+        &self,
+        method: &str,
+        channel_id: &str,
+        message_id: &str,
+        emoji: &str,
+    ) -> anyhow::Result<()> {
+        let (Some(ts), Some(name)) = (
+            Self::message_ts(channel_id, message_id),
+            Self::reaction_name(emoji),
+        ) else {
+            return Ok(());
+        };
+        let body = serde_json::json!({
+            "channel": channel_id,
+            "timestamp": ts,
+            "name": name,
+        });
+        match self.call_web_api(method, &body).await {
+            Ok(_) => Ok(()),
+            // Re-adding or removing an absent reaction is not a failure.
+            Err(e)
+                if e.to_string().ends_with("already_reacted")
+                    || e.to_string().ends_with("no_reaction") =>
+            {
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// App Home commands reply in the user's DM with the app.
+    fn home_command_message(user: &str, ts: &str, content: String) -> ChannelMessage {
+        ChannelMessage {
+            id: format!("slack_home_{user}_{ts}"),
+            sender: user.to_string(),
+            reply_target: user.to_string(),
+            content,
+            channel: "slack".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: None,
+        }
+    }
+
+    /// Block Kit blocks of the App Home view.
+    fn home_blocks(&self, user: &str, view: &HomeView) -> Vec<serde_json::Value> {
+        let mut status = format!(
+            "*Status*: running, up {}h {}m",
+            view.uptime_secs / 3600,
+            view.uptime_secs % 3600 / 60
+        );
+        for (component, state) in view.components.iter().filter(|(_, state)| state != "ok") {
+            let _ = write!(status, "\n• `{component}`: {state}");
+        }
+        let mut blocks = vec![
+            serde_json::json!({
+                "type": "header",
+                "text": { "type": "plain_text", "text": "ZeroClaw" }
+            }),
+            serde_json::json!({
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": status }
+            }),
+            serde_json::json!({ "type": "divider" }),
+            serde_json::json!({
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": "*Pending approvals*" }
+            }),
+        ];
+
+        if view.pending_approvals.is_empty() {
+            blocks.push(Self::home_context("Nothing is waiting for your approval."));
+        }
+        for approval in &view.pending_approvals {
+            blocks.push(serde_json::json!({
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": format!(
+                        "`{}` wants to run `{}` (expires {})",
+                        approval.request_id, approval.tool_name, approval.expires_at
+                    )
+                }
+            }));
+            blocks.push(serde_json::json!({
+                "type": "actions",
+                "elements": [
+                    Self::home_button("Approve", HOME_ACTION_APPROVE, &approval.request_id, Some("primary")),
+                    Self::home_button("Deny", HOME_ACTION_DENY, &approval.request_id, Some("danger")),
+                ]
+            }));
+        }
+
+        blocks.push(serde_json::json!({ "type": "divider" }));
+        blocks.push(serde_json::json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": "*Recent messages*" }
+        }));
+        if view.recent_turns.is_empty() {
+            blocks.push(Self::home_context("No messages yet."));
+        } else {
+            let recent = view
+                .recent_turns
+                .iter()
+                .map(|turn| format!("• {turn}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            blocks.push(Self::home_context(&recent));
+        }
+
+        let mention_only = self.mention_only_for(user);
+        blocks.push(serde_json::json!({ "type": "divider" }));
+        blocks.push(serde_json::json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "*Mention-only in channels*: {}",
+                    if mention_only { "on" } else { "off" }
+                )
+            },
+            "accessory": Self::home_button(
+                if mention_only { "Turn off" } else { "Turn on" },
+                HOME_ACTION_MENTION_ONLY,
+                if mention_only { "off" } else { "on" },
+                None,
+            )
+        }));
+        let mut reset = Self::home_button("Reset session", HOME_ACTION_RESET, "reset", None);
+        reset["confirm"] = serde_json::json!({
+            "title": { "type": "plain_text", "text": "Reset session?" },
+            "text": { "type": "plain_text", "text": "This clears your conversation history. Pinned messages are kept." },
+            "confirm": { "type": "plain_text", "text": "Reset" },
+            "deny": { "type": "plain_text", "text": "Cancel" }
+        });
+        blocks.push(serde_json::json!({ "type": "actions", "elements": [reset] }));
+        blocks
+    }
+
+    fn home_context(text: &str) -> serde_json::Value {
+        serde_json::json!({
+            "type": "context",
+            "elements": [{ "type": "mrkdwn", "text": text }]
+        })
+    }
+
+    fn home_button(
+        label: &str,
+        action_id: &str,
+        value: &str,
+        style: Option<&str>,
+    ) -> serde_json::Value {
+        let mut button = serde_json::json!({
+            "type": "button",
+            "text": { "type": "plain_text", "text": label },
+            "action_id": action_id,
+            "value": value,
+        });
+        if let Some(style) = style {
+            button["style"] = serde_json::json!(style);
+        }
+        button
+    }
+
     /// Get the bot's own user ID so we can ignore our own messages
     async fn get_bot_user_id(&self) -> Option<String> {
         let resp: serde_json::Value = self
@@ -398,6 +759,17 @@ impl SlackChannel {
                     tracing::warn!("Slack Socket Mode: received disconnect event");
                     break;
                 }
+                if envelope_type == "interactive" {
+                    let action = envelope
+                        .get("payload")
+                        .and_then(|payload| self.parse_home_action(payload));
+                    if let Some(channel_msg) = action {
+                        if tx.send(channel_msg).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                    continue;
+                }
                 if envelope_type != "events_api" {
                     continue;
                 }
@@ -408,6 +780,14 @@ impl SlackChannel {
                 else {
                     continue;
                 };
+                if event.get("type").and_then(|v| v.as_str()) == Some("app_home_opened") {
+                    if let Some(channel_msg) = self.parse_app_home_opened(event) {
+                        if tx.send(channel_msg).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                    continue;
+                }
                 if event.get("type").and_then(|v| v.as_str()) != Some("message") {
                     continue;
                 }
@@ -466,8 +846,9 @@ impl SlackChannel {
                 let is_group_message = Self::is_group_channel_id(&channel_id);
                 let allow_sender_without_mention =
                     is_group_message && self.is_group_sender_trigger_enabled(user);
-                let require_mention =
-                    self.mention_only && is_group_message && !allow_sender_without_mention;
+                let require_mention = self.mention_only_for(user)
+                    && is_group_message
+                    && !allow_sender_without_mention;
 
                 let Some(normalized_text) =
                     Self::normalize_incoming_content(text, require_mention, bot_user_id)
@@ -500,6 +881,19 @@ impl SlackChannel {
         }
     }
 
+    /// Whether the throttle allows another `chat.update` of `recipient`'s
+    /// draft. A rate-limit backoff is stored as an instant in the future.
+    fn draft_edit_due(&self, recipient: &str) -> bool {
+        self.last_draft_edit
+            .lock()
+            .get(recipient)
+            .is_none_or(|last_time| {
+                *last_time <= Instant::now()
+                    && u64::try_from(last_time.elapsed().as_millis()).unwrap_or(u64::MAX)
+                        >= self.draft_update_interval_ms
+            })
+    }
+
     fn parse_retry_after_secs(headers: &HeaderMap) -> Option<u64> {
         let value = headers
             .get(reqwest::header::RETRY_AFTER)?
@@ -652,6 +1046,11 @@ impl Channel for SlackChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        self.send_with_ref(message).await?;
+        Ok(())
+    }
+
+    async fn send_with_ref(&self, message: &SendMessage) -> anyhow::Result<Option<MessageRef>> {
         let mut body = serde_json::json!({
             "channel": message.recipient,
             "text": message.content
@@ -661,41 +1060,171 @@ impl Channel for SlackChannel {
             body["thread_ts"] = serde_json::json!(ts);
         }
 
-        let resp = self
-            .http_client()
-            .post("https://slack.com/api/chat.postMessage")
-            .bearer_auth(&self.bot_token)
-            .json(&body)
-            .send()
-            .await?;
+        let resp = self.call_web_api("chat.postMessage", &body).await?;
+        Ok(resp
+            .get("ts")
+            .and_then(|ts| ts.as_str())
+            .map(|ts| MessageRef::new(message.recipient.as_str(), ts)))
+    }
 
-        let status = resp.status();
-        let body = resp
-            .text()
+    fn supports_message_edits(&self) -> bool {
+        true
+    }
+
+    async fn edit_message(&self, message: &MessageRef, content: &str) -> anyhow::Result<()> {
+        let body = serde_json::json!({
+            "channel": message.recipient,
+            "ts": message.message_id,
+            "text": content,
+        });
+        self.call_web_api("chat.update", &body).await?;
+        Ok(())
+    }
+
+    async fn delete_message(&self, message: &MessageRef) -> anyhow::Result<()> {
+        let body = serde_json::json!({
+            "channel": message.recipient,
+            "ts": message.message_id,
+        });
+        self.call_web_api("chat.delete", &body).await?;
+        Ok(())
+    }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
+
+    fn supports_home_view(&self) -> bool {
+        self.app_home
+    }
+
+    async fn add_reaction(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        emoji: &str,
+    ) -> anyhow::Result<()> {
+        self.call_reactions_api("reactions.add", channel_id, message_id, emoji)
             .await
-            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+    }
 
-        if !status.is_success() {
-            let sanitized = crate::providers::sanitize_api_error(&body);
-            anyhow::bail!("Slack chat.postMessage failed ({status}): {sanitized}");
+    async fn remove_reaction(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        emoji: &str,
+    ) -> anyhow::Result<()> {
+        self.call_reactions_api("reactions.remove", channel_id, message_id, emoji)
+            .await
+    }
+
+    async fn publish_home_view(&self, user: &str, view: &HomeView) -> anyhow::Result<()> {
+        let body = serde_json::json!({
+            "user_id": user,
+            "view": {
+                "type": "home",
+                "blocks": self.home_blocks(user, view),
+            }
+        });
+        self.call_web_api("views.publish", &body).await?;
+        Ok(())
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        if self.stream_mode == StreamMode::Off {
+            return Ok(None);
         }
 
-        // Slack returns 200 for most app-level errors; check JSON "ok" field
-        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
-        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
-            let err = parsed
-                .get("error")
-                .and_then(|e| e.as_str())
-                .unwrap_or("unknown");
-            anyhow::bail!("Slack chat.postMessage failed: {err}");
+        let initial_text = if message.content.is_empty() {
+            "..."
+        } else {
+            message.content.as_str()
+        };
+        let mut body = serde_json::json!({
+            "channel": message.recipient,
+            "text": initial_text,
+        });
+        if let Some(ref ts) = message.thread_ts {
+            body["thread_ts"] = serde_json::json!(ts);
         }
 
+        let resp = self.call_web_api("chat.postMessage", &body).await?;
+        self.last_draft_edit
+            .lock()
+            .insert(message.recipient.clone(), Instant::now());
+        Ok(resp.get("ts").and_then(|ts| ts.as_str()).map(String::from))
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<Option<String>> {
+        if !self.draft_edit_due(recipient) {
+            return Ok(None);
+        }
+
+        let body = serde_json::json!({
+            "channel": recipient,
+            "ts": message_id,
+            "text": text,
+        });
+        match self.call_web_api("chat.update", &body).await {
+            Ok(_) => {
+                self.last_draft_edit
+                    .lock()
+                    .insert(recipient.to_string(), Instant::now());
+            }
+            Err(e) => {
+                // Skipped edits lose nothing since each one carries the full
+                // text, so simply hold off until Slack's Retry-After passes.
+                if let Some(limited) = e.downcast_ref::<SlackRateLimited>() {
+                    self.last_draft_edit.lock().insert(
+                        recipient.to_string(),
+                        Instant::now() + Duration::from_secs(limited.retry_after_secs),
+                    );
+                }
+                tracing::debug!("{e}");
+            }
+        }
+        Ok(None)
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+        let body = serde_json::json!({
+            "channel": recipient,
+            "ts": message_id,
+            "text": text,
+        });
+        self.call_web_api("chat.update", &body).await?;
+        Ok(())
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+        let body = serde_json::json!({
+            "channel": recipient,
+            "ts": message_id,
+        });
+        self.call_web_api("chat.delete", &body).await?;
         Ok(())
     }
 
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         let bot_user_id = self.get_bot_user_id().await.unwrap_or_default();
         let scoped_channel = self.configured_channel_id();
+        if self.app_home && self.configured_app_token().is_none() {
+            tracing::warn!(
+                "Slack app_home without Socket Mode (app_token): Home tab opens and button presses are not received"
+            );
+        }
         if self.configured_app_token().is_some() {
             tracing::info!("Slack channel listening in Socket Mode");
             return self
@@ -811,8 +1340,9 @@ impl Channel for SlackChannel {
                         let is_group_message = Self::is_group_channel_id(&channel_id);
                         let allow_sender_without_mention =
                             is_group_message && self.is_group_sender_trigger_enabled(user);
-                        let require_mention =
-                            self.mention_only && is_group_message && !allow_sender_without_mention;
+                        let require_mention = self.mention_only_for(user)
+                            && is_group_message
+                            && !allow_sender_without_mention;
                         let Some(normalized_text) =
                             Self::normalize_incoming_content(text, require_mention, &bot_user_id)
                         else {
@@ -844,19 +1374,58 @@ impl Channel for SlackChannel {
     }
 
     async fn health_check(&self) -> bool {
-        self.http_client()
+        // auth.test answers 200 even for revoked tokens; the verdict is in `ok`.
+        let Ok(resp) = self
+            .http_client()
             .get("https://slack.com/api/auth.test")
             .bearer_auth(&self.bot_token)
             .send()
             .await
-            .map(|r| r.status().is_success())
-            .unwrap_or(false)
+        else {
+            return false;
+        };
+        if !resp.status().is_success() {
+            return false;
+        }
+        resp.json::<serde_json::Value>()
+            .await
+            .is_ok_and(|body| body.get("ok") == Some(&serde_json::Value::Bool(true)))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::channels::traits::HomeApproval;
+
+    #[test]
+    fn supports_draft_updates_respects_stream_mode() {
+        let off = SlackChannel::new("xoxb-fake".into(), None, None, vec![]);
+        assert!(!off.supports_draft_updates());
+
+        let partial = SlackChannel::new("xoxb-fake".into(), None, None, vec![])
+            .with_streaming(StreamMode::Partial, 750);
+        assert!(partial.supports_draft_updates());
+        assert_eq!(partial.draft_update_interval_ms, 750);
+    }
+
+    #[test]
+    fn draft_edits_wait_for_interval_and_rate_limit_backoff() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, None, vec![])
+            .with_streaming(StreamMode::Partial, 0);
+        assert!(ch.draft_edit_due("C123"));
+
+        ch.last_draft_edit
+            .lock()
+            .insert("C123".into(), Instant::now() - Duration::from_millis(5));
+        assert!(ch.draft_edit_due("C123"));
+
+        ch.last_draft_edit
+            .lock()
+            .insert("C123".into(), Instant::now() + Duration::from_secs(30));
+        assert!(!ch.draft_edit_due("C123"));
+        assert!(ch.draft_edit_due("C456"));
+    }
 
     #[test]
     fn slack_channel_name() {
@@ -1142,4 +1711,88 @@ mod tests {
         let delay = SlackChannel::compute_retry_delay(30, 3, 250);
         assert_eq!(delay, Duration::from_secs(120) + Duration::from_millis(250));
     }
+
+    #[test]
+    fn reactions_target_the_inbound_message_ts() {
+        assert_eq!(
+            SlackChannel::message_ts("C123", "slack_C123_1700000000.000100"),
+            Some("1700000000.000100")
+        );
+        assert_eq!(SlackChannel::message_ts("C123", "slack_C999_1.2"), None);
+        assert_eq!(SlackChannel::message_ts("U1", "slack_home_U1_1.2"), None);
+        assert_eq!(SlackChannel::reaction_name("\u{1F440}"), Some("eyes"));
+        assert_eq!(
+            SlackChannel::reaction_name("\u{26A0}\u{FE0F}"),
+            Some("warning")
+        );
+        assert_eq!(SlackChannel::reaction_name("🎉"), None);
+    }
+
+    fn home_action(user: &str, action_id: &str, value: &str) -> serde_json::Value {
+        serde_json::json!({
+            "type": "block_actions",
+            "user": { "id": user },
+            "actions": [{ "action_id": action_id, "value": value, "action_ts": "1.2" }]
+        })
+    }
+
+    #[test]
+    fn home_actions_map_to_runtime_commands() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, None, vec!["U1".into()])
+            .with_group_reply_policy(true, vec![])
+            .with_app_home(true);
+
+        let approve = ch
+            .parse_home_action(&home_action("U1", HOME_ACTION_APPROVE, "apr-1"))
+            .unwrap();
+        assert_eq!(approve.content, "/approve-allow apr-1");
+        assert_eq!(approve.reply_target, "U1");
+        assert_eq!(
+            ch.parse_home_action(&home_action("U1", HOME_ACTION_RESET, "reset"))
+                .unwrap()
+                .content,
+            "/new"
+        );
+
+        assert!(ch.mention_only_for("U1"));
+        let toggle = ch
+            .parse_home_action(&home_action("U1", HOME_ACTION_MENTION_ONLY, "off"))
+            .unwrap();
+        assert_eq!(toggle.content, "/home");
+        assert!(!ch.mention_only_for("U1"));
+        assert!(ch.mention_only_for("U2"));
+
+        assert!(ch
+            .parse_home_action(&home_action("U2", HOME_ACTION_APPROVE, "apr-1"))
+            .is_none());
+        assert!(
+            SlackChannel::new("xoxb-fake".into(), None, None, vec!["*".into()])
+                .parse_home_action(&home_action("U1", HOME_ACTION_RESET, "reset"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn home_blocks_offer_buttons_for_pending_approvals() {
+        let ch =
+            SlackChannel::new("xoxb-fake".into(), None, None, vec!["*".into()]).with_app_home(true);
+        let view = HomeView {
+            uptime_secs: 3_720,
+            components: vec![("channel:slack".into(), "error".into())],
+            recent_turns: vec!["[2026-03-01 09:00:00 UTC] deploy please".into()],
+            pending_approvals: vec![HomeApproval {
+                request_id: "apr-1".into(),
+                tool_name: "shell".into(),
+                expires_at: "2026-03-01T09:30:00Z".into(),
+            }],
+        };
+
+        let blocks = serde_json::Value::Array(ch.home_blocks("U1", &view)).to_string();
+        assert!(blocks.contains("up 1h 2m"));
+        assert!(blocks.contains("`channel:slack`: error"));
+        assert!(blocks.contains(r#""action_id":"zeroclaw_home_approve","#));
+        assert!(blocks.contains(r#""value":"apr-1""#));
+        assert!(blocks.contains("deploy please"));
+        assert!(blocks.contains("*Mention-only in channels*: off"));
+    }
 }
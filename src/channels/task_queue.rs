@@ -0,0 +1,463 @@
+//! Persistent queue of accepted channel messages.
+//!
+//! Every message the dispatch loop accepts is recorded in
+//! `<workspace>/state/channel_tasks.db` and marked done once its turn
+//! finishes. Rows still `queued` or `running` at startup belong to turns a
+//! restart interrupted; they are handed back to the dispatch loop so the
+//! request is answered after all. A row is keyed by channel, sender, reply
+//! target and platform message id, so a resumed message updates its own row
+//! instead of adding another, and two chats reusing a message id do not
+//! share one.
+//!
+//! A message delivered again while its turn is still queued or running is
+//! dropped. The final reply of a turn is stored on its row before it is
+//! sent; a message delivered again after that (a retried webhook, or a
+//! restart between the tool calls and the send) is answered with the stored
+//! reply instead of running the turn, and its tools, a second time.
+
+use super::traits::ChannelMessage;
+use crate::db::{add_column_if_missing, migrate, Migration, Schema, Step};
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Finished rows are kept this long for inspection before being pruned.
+const FINISHED_TASK_RETENTION_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Processing state of a queued task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStatus {
+    Queued,
+    Running,
+    /// Handed back to the dispatch loop after a restart.
+    Resuming,
+    Done,
+    Abandoned,
+}
+
+impl TaskStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Resuming => "resuming",
+            Self::Done => "done",
+            Self::Abandoned => "abandoned",
+        }
+    }
+}
+
+/// Handle to a recorded task, returned by [`ChannelTaskQueue::enqueue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskId(i64);
+
+/// Outcome of [`ChannelTaskQueue::enqueue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Enqueued {
+    Task(TaskId),
+    /// The same message is already queued or running; this delivery is a
+    /// duplicate.
+    InFlight,
+}
+
+/// An unfinished task found at startup.
+#[derive(Debug, Clone)]
+pub struct ResumableTask {
+    pub id: TaskId,
+    pub message: ChannelMessage,
+    /// How many times this task has already been resumed.
+    pub resumes: u32,
+    /// Final reply, when the turn finished but may not have been delivered.
+    pub response: Option<String>,
+}
+
+/// Tables of `state/channel_tasks.db`.
+pub static SCHEMA: Schema = Schema {
+    name: "channel_tasks",
+    path: "state/channel_tasks.db",
+    migrations: &[
+        Migration {
+            version: 1,
+            description: "create channel_tasks",
+            step: Step::Sql(
+                "CREATE TABLE IF NOT EXISTS channel_tasks (
+                     id           INTEGER PRIMARY KEY AUTOINCREMENT,
+                     channel      TEXT NOT NULL,
+                     message_id   TEXT NOT NULL,
+                     sender       TEXT NOT NULL,
+                     reply_target TEXT NOT NULL,
+                     content      TEXT NOT NULL,
+                     thread_ts    TEXT,
+                     timestamp    INTEGER NOT NULL,
+                     status       TEXT NOT NULL,
+                     resumes      INTEGER NOT NULL DEFAULT 0,
+                     updated_at   INTEGER NOT NULL,
+                     UNIQUE (channel, message_id)
+                 );
+                 CREATE INDEX IF NOT EXISTS idx_channel_tasks_status ON channel_tasks(status);",
+            ),
+        },
+        Migration {
+            version: 2,
+            description: "add channel_tasks.response",
+            step: Step::Rust(|conn| {
+                add_column_if_missing(conn, "channel_tasks", "response", "TEXT")
+            }),
+        },
+        Migration {
+            version: 3,
+            description: "key channel_tasks by sender and reply target",
+            step: Step::Sql(
+                "CREATE TABLE channel_tasks_new (
+                     id           INTEGER PRIMARY KEY AUTOINCREMENT,
+                     channel      TEXT NOT NULL,
+                     message_id   TEXT NOT NULL,
+                     sender       TEXT NOT NULL,
+                     reply_target TEXT NOT NULL,
+                     content      TEXT NOT NULL,
+                     thread_ts    TEXT,
+                     timestamp    INTEGER NOT NULL,
+                     status       TEXT NOT NULL,
+                     resumes      INTEGER NOT NULL DEFAULT 0,
+                     updated_at   INTEGER NOT NULL,
+                     response     TEXT,
+                     UNIQUE (channel, sender, reply_target, message_id)
+                 );
+                 INSERT INTO channel_tasks_new
+                     SELECT id, channel, message_id, sender, reply_target, content, thread_ts,
+                            timestamp, status, resumes, updated_at, response
+                     FROM channel_tasks;
+                 DROP TABLE channel_tasks;
+                 ALTER TABLE channel_tasks_new RENAME TO channel_tasks;
+                 CREATE INDEX IF NOT EXISTS idx_channel_tasks_status ON channel_tasks(status);",
+            ),
+        },
+    ],
+};
+
+pub struct ChannelTaskQueue {
+    conn: Arc<Mutex<Connection>>,
+    db_path: PathBuf,
+}
+
+impl ChannelTaskQueue {
+    pub fn open(workspace_dir: &Path) -> Result<Self> {
+        let db_path = SCHEMA.db_path(workspace_dir);
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        migrate(&conn, &SCHEMA)?;
+        conn.execute(
+            "DELETE FROM channel_tasks WHERE status IN ('done', 'abandoned') AND updated_at < ?1",
+            params![now_secs() - FINISHED_TASK_RETENTION_SECS],
+        )?;
+
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            db_path,
+        })
+    }
+
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    async fn with_conn<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T> + Send + 'static,
+    {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || f(&conn.lock())).await?
+    }
+
+    /// Record an accepted message. A resumed task coming back through the
+    /// dispatch loop keeps its row and has its resume count bumped; a
+    /// message whose turn is already queued or running is reported as
+    /// [`Enqueued::InFlight`] and left alone.
+    pub async fn enqueue(&self, msg: &ChannelMessage) -> Result<Enqueued> {
+        let msg = msg.clone();
+        self.with_conn(move |conn| {
+            let id = conn
+                .query_row(
+                    "INSERT INTO channel_tasks
+                         (channel, message_id, sender, reply_target, content, thread_ts,
+                          timestamp, status, resumes, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 'queued', 0, ?8)
+                     ON CONFLICT (channel, sender, reply_target, message_id) DO UPDATE SET
+                         resumes = CASE WHEN status = 'resuming' THEN resumes + 1 ELSE 0 END,
+                         status = 'queued',
+                         content = excluded.content,
+                         thread_ts = excluded.thread_ts,
+                         updated_at = excluded.updated_at
+                     WHERE status NOT IN ('queued', 'running')
+                     RETURNING id",
+                    params![
+                        msg.channel,
+                        msg.id,
+                        msg.sender,
+                        msg.reply_target,
+                        msg.content,
+                        msg.thread_ts,
+                        i64::try_from(msg.timestamp).unwrap_or(i64::MAX),
+                        now_secs(),
+                    ],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            Ok(id.map_or(Enqueued::InFlight, |id| Enqueued::Task(TaskId(id))))
+        })
+        .await
+    }
+
+    pub async fn set_status(&self, id: TaskId, status: TaskStatus) -> Result<()> {
+        self.with_conn(move |conn| {
+            conn.execute(
+                "UPDATE channel_tasks SET status = ?1, updated_at = ?2 WHERE id = ?3",
+                params![status.as_str(), now_secs(), id.0],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Store the final reply of `msg`'s turn, before it is sent.
+    pub async fn record_response(&self, msg: &ChannelMessage, response: &str) -> Result<()> {
+        let msg = msg.clone();
+        let response = response.to_string();
+        self.with_conn(move |conn| {
+            conn.execute(
+                "UPDATE channel_tasks SET response = ?1, updated_at = ?2
+                 WHERE channel = ?3 AND sender = ?4 AND reply_target = ?5 AND message_id = ?6",
+                params![
+                    response,
+                    now_secs(),
+                    msg.channel,
+                    msg.sender,
+                    msg.reply_target,
+                    msg.id
+                ],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Stored reply of an earlier turn for the same message, with the row
+    /// it belongs to. A turn still running has its reply on the way and is
+    /// not replayed.
+    pub async fn completed_response(
+        &self,
+        msg: &ChannelMessage,
+    ) -> Result<Option<(TaskId, String)>> {
+        let msg = msg.clone();
+        self.with_conn(move |conn| {
+            let row = conn
+                .query_row(
+                    "SELECT id, response FROM channel_tasks
+                     WHERE channel = ?1 AND sender = ?2 AND reply_target = ?3
+                       AND message_id = ?4 AND response IS NOT NULL
+                       AND status NOT IN ('queued', 'running')",
+                    params![msg.channel, msg.sender, msg.reply_target, msg.id],
+                    |row| Ok((TaskId(row.get(0)?), row.get(1)?)),
+                )
+                .optional()?;
+            Ok(row)
+        })
+        .await
+    }
+
+    /// Tasks left `queued` or `running` by a previous process, oldest first,
+    /// marked `resuming` until they come back through [`Self::enqueue`].
+    /// Tasks already resumed `max_resumes` times are marked abandoned instead
+    /// of being returned, so a message that crashes the daemon cannot loop.
+    pub async fn take_unfinished(&self, max_resumes: u32) -> Result<Vec<ResumableTask>> {
+        self.with_conn(move |conn| {
+            let abandoned = conn.execute(
+                "UPDATE channel_tasks SET status = 'abandoned', updated_at = ?1
+                 WHERE status IN ('queued', 'running', 'resuming') AND resumes >= ?2",
+                params![now_secs(), max_resumes],
+            )?;
+            if abandoned > 0 {
+                tracing::warn!(
+                    "Abandoned {abandoned} channel task(s) that exceeded {max_resumes} resume(s)"
+                );
+            }
+
+            let mut stmt = conn.prepare(
+                "SELECT id, channel, message_id, sender, reply_target, content, thread_ts,
+                        timestamp, resumes, response
+                 FROM channel_tasks
+                 WHERE status IN ('queued', 'running', 'resuming')
+                 ORDER BY id",
+            )?;
+            let tasks = stmt
+                .query_map([], |row| {
+                    Ok(ResumableTask {
+                        id: TaskId(row.get(0)?),
+                        message: ChannelMessage {
+                            channel: row.get(1)?,
+                            id: row.get(2)?,
+                            sender: row.get(3)?,
+                            reply_target: row.get(4)?,
+                            content: row.get(5)?,
+                            thread_ts: row.get(6)?,
+                            timestamp: u64::try_from(row.get::<_, i64>(7)?).unwrap_or(0),
+                        },
+                        resumes: row.get(8)?,
+                        response: row.get(9)?,
+                    })
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            conn.execute(
+                "UPDATE channel_tasks SET status = 'resuming', updated_at = ?1
+                 WHERE status IN ('queued', 'running')",
+                params![now_secs()],
+            )?;
+            Ok(tasks)
+        })
+        .await
+    }
+}
+
+fn now_secs() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn message(id: &str, content: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: id.into(),
+            sender: "alice".into(),
+            reply_target: "chat-1".into(),
+            content: content.into(),
+            channel: "telegram".into(),
+            timestamp: 1_700_000_000,
+            thread_ts: Some("42".into()),
+        }
+    }
+
+    async fn enqueue(queue: &ChannelTaskQueue, msg: &ChannelMessage) -> TaskId {
+        match queue.enqueue(msg).await.unwrap() {
+            Enqueued::Task(id) => id,
+            Enqueued::InFlight => panic!("{} is already in flight", msg.id),
+        }
+    }
+
+    #[tokio::test]
+    async fn unfinished_tasks_survive_reopen() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let queue = ChannelTaskQueue::open(tmp.path()).unwrap();
+            let done = enqueue(&queue, &message("m1", "finished")).await;
+            queue.set_status(done, TaskStatus::Done).await.unwrap();
+            let running = enqueue(&queue, &message("m2", "long task")).await;
+            queue
+                .set_status(running, TaskStatus::Running)
+                .await
+                .unwrap();
+            enqueue(&queue, &message("m3", "never started")).await;
+        }
+
+        let queue = ChannelTaskQueue::open(tmp.path()).unwrap();
+        let tasks = queue.take_unfinished(2).await.unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].message.content, "long task");
+        assert_eq!(tasks[0].message.thread_ts.as_deref(), Some("42"));
+        assert_eq!(tasks[0].resumes, 0);
+        assert_eq!(tasks[1].message.id, "m3");
+    }
+
+    #[tokio::test]
+    async fn repeatedly_resumed_tasks_are_abandoned() {
+        let tmp = TempDir::new().unwrap();
+        let queue = ChannelTaskQueue::open(tmp.path()).unwrap();
+        let msg = message("m1", "crashes the daemon");
+
+        // First run plus two resumes, none of which finished.
+        for expected_resumes in 0..3 {
+            enqueue(&queue, &msg).await;
+            let tasks = queue.take_unfinished(2).await.unwrap();
+            if expected_resumes < 2 {
+                assert_eq!(tasks[0].resumes, expected_resumes);
+            } else {
+                assert!(tasks.is_empty());
+            }
+        }
+
+        // A later redelivery of a finished message starts a fresh count.
+        let id = enqueue(&queue, &msg).await;
+        queue.set_status(id, TaskStatus::Done).await.unwrap();
+        enqueue(&queue, &msg).await;
+        assert_eq!(queue.take_unfinished(2).await.unwrap()[0].resumes, 0);
+    }
+
+    #[tokio::test]
+    async fn stored_responses_answer_redelivered_messages() {
+        let tmp = TempDir::new().unwrap();
+        let queue = ChannelTaskQueue::open(tmp.path()).unwrap();
+        let msg = message("m1", "deploy it");
+        let id = enqueue(&queue, &msg).await;
+        assert!(queue.completed_response(&msg).await.unwrap().is_none());
+
+        // The process dies after the turn finished but before the send.
+        queue.set_status(id, TaskStatus::Running).await.unwrap();
+        queue.record_response(&msg, "Deployed v2.").await.unwrap();
+        drop(queue);
+
+        let queue = ChannelTaskQueue::open(tmp.path()).unwrap();
+        let tasks = queue.take_unfinished(2).await.unwrap();
+        assert_eq!(tasks[0].response.as_deref(), Some("Deployed v2."));
+        assert_eq!(
+            queue.completed_response(&msg).await.unwrap(),
+            Some((id, "Deployed v2.".to_string()))
+        );
+        assert!(queue
+            .completed_response(&message("m2", "other"))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn duplicates_of_an_in_flight_turn_are_dropped() {
+        let tmp = TempDir::new().unwrap();
+        let queue = ChannelTaskQueue::open(tmp.path()).unwrap();
+        let msg = message("m1", "deploy it");
+        let id = enqueue(&queue, &msg).await;
+        assert_eq!(queue.enqueue(&msg).await.unwrap(), Enqueued::InFlight);
+
+        // The reply is stored but still being sent.
+        queue.set_status(id, TaskStatus::Running).await.unwrap();
+        queue.record_response(&msg, "Deployed v2.").await.unwrap();
+        assert_eq!(queue.enqueue(&msg).await.unwrap(), Enqueued::InFlight);
+        assert!(queue.completed_response(&msg).await.unwrap().is_none());
+
+        // Another chat reusing the platform message id gets its own turn.
+        let mut other = msg.clone();
+        other.sender = "bob".into();
+        other.reply_target = "chat-2".into();
+        assert_ne!(enqueue(&queue, &other).await, id);
+        assert!(queue.completed_response(&other).await.unwrap().is_none());
+
+        queue.set_status(id, TaskStatus::Done).await.unwrap();
+        assert_eq!(
+            queue.completed_response(&msg).await.unwrap(),
+            Some((id, "Deployed v2.".to_string()))
+        );
+    }
+}
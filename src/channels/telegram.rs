@@ -1,4 +1,5 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::attachments::{sanitize_attachment_filename, AttachmentStore};
+use super::traits::{Channel, ChannelMessage, MessageRef, SendMessage};
 use crate::config::{Config, StreamMode};
 use crate::security::pairing::PairingGuard;
 use anyhow::Context;
@@ -204,24 +205,6 @@ fn is_http_url(target: &str) -> bool {
     target.starts_with("http://") || target.starts_with("https://")
 }
 
-fn sanitize_attachment_filename(file_name: &str) -> Option<String> {
-    let basename = Path::new(file_name).file_name()?.to_str()?.trim();
-    if basename.is_empty() || basename == "." || basename == ".." {
-        return None;
-    }
-
-    let sanitized: String = basename
-        .replace(['/', '\\'], "_")
-        .chars()
-        .take(128)
-        .collect();
-    if sanitized.is_empty() || sanitized == "." || sanitized == ".." {
-        None
-    } else {
-        Some(sanitized)
-    }
-}
-
 fn sanitize_generated_extension(raw_ext: &str) -> String {
     let cleaned: String = raw_ext
         .chars()
@@ -275,57 +258,6 @@ fn resolve_workspace_attachment_path(workspace: &Path, target: &str) -> anyhow::
     Ok(resolved)
 }
 
-async fn resolve_workspace_attachment_output_path(
-    workspace: &Path,
-    file_name: &str,
-) -> anyhow::Result<PathBuf> {
-    let safe_name = sanitize_attachment_filename(file_name)
-        .ok_or_else(|| anyhow::anyhow!("invalid attachment filename: {file_name}"))?;
-
-    fs::create_dir_all(workspace).await?;
-    let workspace_root = fs::canonicalize(workspace)
-        .await
-        .unwrap_or_else(|_| workspace.to_path_buf());
-
-    let save_dir = workspace.join("telegram_files");
-    fs::create_dir_all(&save_dir).await?;
-    let resolved_save_dir = fs::canonicalize(&save_dir).await.with_context(|| {
-        format!(
-            "failed to resolve Telegram attachment save directory: {}",
-            save_dir.display()
-        )
-    })?;
-
-    if !resolved_save_dir.starts_with(&workspace_root) {
-        anyhow::bail!(
-            "Telegram attachment save directory escapes workspace: {}",
-            save_dir.display()
-        );
-    }
-
-    let output_path = resolved_save_dir.join(safe_name);
-    match fs::symlink_metadata(&output_path).await {
-        Ok(meta) => {
-            if meta.file_type().is_symlink() {
-                anyhow::bail!(
-                    "refusing to write Telegram attachment through symlink: {}",
-                    output_path.display()
-                );
-            }
-            if !meta.is_file() {
-                anyhow::bail!(
-                    "Telegram attachment output path is not a regular file: {}",
-                    output_path.display()
-                );
-            }
-        }
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
-        Err(e) => return Err(e.into()),
-    }
-
-    Ok(output_path)
-}
-
 fn infer_attachment_kind_from_target(target: &str) -> Option<TelegramAttachmentKind> {
     let normalized = target
         .split('?')
@@ -1400,10 +1332,10 @@ Allowlist Telegram username (without '@') or numeric user ID.",
 
     /// Attempt to parse a Telegram update as a document/photo attachment.
     ///
-    /// Downloads the file to `{workspace_dir}/telegram_files/` and returns a
-    /// `ChannelMessage` with the local file path. Returns `None` if the message
-    /// is not an attachment, workspace_dir is not configured, or the file exceeds
-    /// size limits.
+    /// Saves the file to `{workspace_dir}/attachments/telegram/` through the
+    /// attachment store and returns a `ChannelMessage` with the local file path.
+    /// Returns `None` if the message is not an attachment, workspace_dir is not
+    /// configured, or the file exceeds size limits.
     async fn try_parse_attachment_message(
         &self,
         update: &serde_json::Value,
@@ -1520,21 +1452,16 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             }
         };
 
-        let local_path =
-            match resolve_workspace_attachment_output_path(workspace, &local_filename).await {
-                Ok(path) => path,
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to resolve attachment output path for {}: {e}",
-                        local_filename
-                    );
-                    return None;
-                }
-            };
-        if let Err(e) = tokio::fs::write(&local_path, &file_data).await {
-            tracing::warn!("Failed to save attachment to {}: {e}", local_path.display());
-            return None;
-        }
+        let local_path = match AttachmentStore::new(workspace)
+            .save("telegram", &local_filename, &file_data)
+            .await
+        {
+            Ok(path) => path,
+            Err(e) => {
+                tracing::warn!("Failed to save attachment {local_filename}: {e:#}");
+                return None;
+            }
+        };
 
         // Build message content.
         // Photos with image extensions use [IMAGE:] marker so the multimodal
@@ -3083,7 +3010,7 @@ impl Channel for TelegramChannel {
         tool_name: &str,
         arguments: &serde_json::Value,
         thread_ts: Option<String>,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<Option<MessageRef>> {
         let (chat_id, parsed_thread_id) = Self::parse_reply_target(recipient);
         let thread_id = parsed_thread_id.or(thread_ts);
 
@@ -3094,19 +3021,28 @@ impl Channel for TelegramChannel {
             raw_args
         };
 
+        let diff = crate::approval::approval_preview(tool_name, arguments)
+            .map(|preview| format!("\n\n{preview}"))
+            .unwrap_or_default();
+
         let mut body = serde_json::json!({
             "chat_id": chat_id,
             "text": format!(
-                "Approval required for tool `{tool_name}`.\nRequest ID: `{request_id}`\nArgs: `{args_preview}`",
+                "{}{diff}",
+                crate::i18n::t_with(
+                    "telegram",
+                    "approval.required",
+                    &[("tool", tool_name), ("id", request_id), ("args", &args_preview)],
+                ),
             ),
             "reply_markup": {
                 "inline_keyboard": [[
                     {
-                        "text": "Approve",
+                        "text": crate::i18n::t("telegram", "approval.approve_button"),
                         "callback_data": format!("{TELEGRAM_APPROVAL_CALLBACK_APPROVE_PREFIX}{request_id}")
                     },
                     {
-                        "text": "Deny",
+                        "text": crate::i18n::t("telegram", "approval.deny_button"),
                         "callback_data": format!("{TELEGRAM_APPROVAL_CALLBACK_DENY_PREFIX}{request_id}")
                     }
                 ]]
@@ -3131,6 +3067,67 @@ impl Channel for TelegramChannel {
             anyhow::bail!("Telegram approval prompt failed ({status}): {sanitized}");
         }
 
+        let resp_json: serde_json::Value = response.json().await?;
+        Ok(resp_json
+            .get("result")
+            .and_then(|r| r.get("message_id"))
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| MessageRef::new(recipient, id.to_string())))
+    }
+
+    fn supports_message_edits(&self) -> bool {
+        true
+    }
+
+    async fn edit_message(&self, message: &MessageRef, content: &str) -> anyhow::Result<()> {
+        let (chat_id, _) = Self::parse_reply_target(&message.recipient);
+        let message_id: i64 = message.message_id.parse().map_err(|e| {
+            anyhow::anyhow!("Invalid Telegram message_id '{}': {e}", message.message_id)
+        })?;
+        let response = self
+            .http_client()
+            .post(self.api_url("editMessageText"))
+            .json(&serde_json::json!({
+                "chat_id": chat_id,
+                "message_id": message_id,
+                "text": strip_tool_call_tags(content),
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let err = response.text().await.unwrap_or_default();
+            if err.contains("message is not modified") {
+                return Ok(());
+            }
+            let sanitized = Self::sanitize_telegram_error(&err);
+            anyhow::bail!("Telegram editMessageText failed ({status}): {sanitized}");
+        }
+        Ok(())
+    }
+
+    async fn delete_message(&self, message: &MessageRef) -> anyhow::Result<()> {
+        let (chat_id, _) = Self::parse_reply_target(&message.recipient);
+        let message_id: i64 = message.message_id.parse().map_err(|e| {
+            anyhow::anyhow!("Invalid Telegram message_id '{}': {e}", message.message_id)
+        })?;
+        let response = self
+            .http_client()
+            .post(self.api_url("deleteMessage"))
+            .json(&serde_json::json!({
+                "chat_id": chat_id,
+                "message_id": message_id,
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let err = response.text().await.unwrap_or_default();
+            let sanitized = Self::sanitize_telegram_error(&err);
+            anyhow::bail!("Telegram deleteMessage failed ({status}): {sanitized}");
+        }
         Ok(())
     }
 
@@ -3857,9 +3854,14 @@ mod tests {
         tokio::fs::create_dir_all(&outside)
             .await
             .expect("outside dir should exist");
-        symlink_dir(&outside, &workspace.join("telegram_files"));
+        tokio::fs::create_dir_all(workspace.join("attachments"))
+            .await
+            .expect("attachments dir should exist");
+        symlink_dir(&outside, &workspace.join("attachments").join("telegram"));
 
-        let result = resolve_workspace_attachment_output_path(&workspace, "doc.txt").await;
+        let result = AttachmentStore::new(&workspace)
+            .output_path("telegram", "doc.txt")
+            .await;
         assert!(result.is_err(), "symlinked save dir must be rejected");
     }
 
@@ -3867,7 +3869,7 @@ mod tests {
     async fn resolve_workspace_attachment_output_path_rejects_symlink_target_file() {
         let temp = tempfile::tempdir().expect("tempdir");
         let workspace = temp.path().join("workspace");
-        let save_dir = workspace.join("telegram_files");
+        let save_dir = workspace.join("attachments").join("telegram");
         tokio::fs::create_dir_all(&save_dir)
             .await
             .expect("save dir should exist");
@@ -3878,7 +3880,9 @@ mod tests {
             .expect("outside fixture should be written");
         symlink_file(&outside, &save_dir.join("doc.txt"));
 
-        let result = resolve_workspace_attachment_output_path(&workspace, "doc.txt").await;
+        let result = AttachmentStore::new(&workspace)
+            .output_path("telegram", "doc.txt")
+            .await;
         assert!(result.is_err(), "symlink target file must be rejected");
     }
 
@@ -5521,7 +5525,7 @@ mod tests {
     /// Markdown files must never produce `[IMAGE:]` markers (issue #1274).
     #[test]
     fn markdown_file_never_produces_image_marker() {
-        let local_path = std::path::Path::new("/tmp/workspace/telegram_files/notes.md");
+        let local_path = std::path::Path::new("/tmp/workspace/attachments/telegram/notes.md");
         let local_filename = "notes.md";
 
         // Even if Telegram misclassifies as Photo, extension guard prevents [IMAGE:].
@@ -14,6 +14,45 @@ pub struct ChannelMessage {
     pub thread_ts: Option<String>,
 }
 
+/// Per-user status page for channels with a home surface (Slack App Home).
+#[derive(Debug, Clone, Default)]
+pub struct HomeView {
+    /// Seconds since the daemon started.
+    pub uptime_secs: u64,
+    /// Health status per component, e.g. `("channel:slack", "ok")`.
+    pub components: Vec<(String, String)>,
+    /// The user's latest messages, newest first.
+    pub recent_turns: Vec<String>,
+    /// Approval requests raised in the user's turns.
+    pub pending_approvals: Vec<HomeApproval>,
+}
+
+/// Pending approval request shown on a [`HomeView`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HomeApproval {
+    pub request_id: String,
+    pub tool_name: String,
+    pub expires_at: String,
+}
+
+/// Reference to a message the bot sent, for later edits or deletion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageRef {
+    /// Recipient the message was sent to, as passed in [`SendMessage`].
+    pub recipient: String,
+    /// Platform message identifier (Telegram `message_id`, Slack `ts`).
+    pub message_id: String,
+}
+
+impl MessageRef {
+    pub fn new(recipient: impl Into<String>, message_id: impl Into<String>) -> Self {
+        Self {
+            recipient: recipient.into(),
+            message_id: message_id.into(),
+        }
+    }
+}
+
 /// Message to send through a channel
 #[derive(Debug, Clone)]
 pub struct SendMessage {
@@ -65,6 +104,28 @@ pub trait Channel: Send + Sync {
     /// Send a message through this channel
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()>;
 
+    /// Send a message and return a reference to it when the platform
+    /// reports one. Messages split into several parts reference the last.
+    async fn send_with_ref(&self, message: &SendMessage) -> anyhow::Result<Option<MessageRef>> {
+        self.send(message).await?;
+        Ok(None)
+    }
+
+    /// Whether [`Channel::edit_message`] and [`Channel::delete_message`] work.
+    fn supports_message_edits(&self) -> bool {
+        false
+    }
+
+    /// Replace the content of a message sent earlier.
+    async fn edit_message(&self, _message: &MessageRef, _content: &str) -> anyhow::Result<()> {
+        anyhow::bail!("{} does not support editing messages", self.name())
+    }
+
+    /// Delete a message sent earlier.
+    async fn delete_message(&self, _message: &MessageRef) -> anyhow::Result<()> {
+        anyhow::bail!("{} does not support deleting messages", self.name())
+    }
+
     /// Start listening for incoming messages (long-running)
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()>;
 
@@ -124,6 +185,8 @@ pub trait Channel: Send + Sync {
     }
 
     /// Send an interactive approval prompt, if supported by the channel.
+    /// Returns a reference to the prompt when the channel reports one, so it
+    /// can be closed once the request is settled.
     ///
     /// Default behavior sends a plain-text fallback with slash-command actions.
     async fn send_approval_prompt(
@@ -133,7 +196,7 @@ pub trait Channel: Send + Sync {
         tool_name: &str,
         arguments: &serde_json::Value,
         thread_ts: Option<String>,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<Option<MessageRef>> {
         let raw_args = arguments.to_string();
         let args_preview = if raw_args.len() > 220 {
             let end = crate::util::floor_utf8_char_boundary(&raw_args, 220);
@@ -141,10 +204,23 @@ pub trait Channel: Send + Sync {
         } else {
             raw_args
         };
+        let diff = crate::approval::approval_preview(tool_name, arguments)
+            .map(|preview| format!("\n```diff\n{preview}\n```"))
+            .unwrap_or_default();
         let message = format!(
-            "Approval required for tool `{tool_name}`.\nRequest ID: `{request_id}`\nArgs: `{args_preview}`\nApprove: `/approve-allow {request_id}`\nDeny: `/approve-deny {request_id}`"
+            "{}{diff}\n{}",
+            crate::i18n::t_with(
+                self.name(),
+                "approval.required",
+                &[
+                    ("tool", tool_name),
+                    ("id", request_id),
+                    ("args", &args_preview)
+                ],
+            ),
+            crate::i18n::t_with(self.name(), "approval.commands", &[("id", request_id)]),
         );
-        self.send(&SendMessage::new(message, recipient).in_thread(thread_ts))
+        self.send_with_ref(&SendMessage::new(message, recipient).in_thread(thread_ts))
             .await
     }
 
@@ -171,6 +247,16 @@ pub trait Channel: Send + Sync {
     ) -> anyhow::Result<()> {
         Ok(())
     }
+
+    /// Whether this channel renders a per-user home view.
+    fn supports_home_view(&self) -> bool {
+        false
+    }
+
+    /// Publish `view` as the home view of `user`.
+    async fn publish_home_view(&self, _user: &str, _view: &HomeView) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -273,6 +359,21 @@ mod tests {
         assert!(channel.cancel_draft("bob", "msg_1").await.is_ok());
     }
 
+    #[tokio::test]
+    async fn default_message_edit_methods_are_unsupported() {
+        let channel = DummyChannel;
+        let message = MessageRef::new("bob", "msg_1");
+
+        assert!(!channel.supports_message_edits());
+        assert!(channel
+            .send_with_ref(&SendMessage::new("hello", "bob"))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(channel.edit_message(&message, "edited").await.is_err());
+        assert!(channel.delete_message(&message).await.is_err());
+    }
+
     #[tokio::test]
     async fn listen_sends_message_to_channel() {
         let channel = DummyChannel;
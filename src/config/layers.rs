@@ -0,0 +1,405 @@
+//! Config layering.
+//!
+//! `config.toml` is the base layer. Under it sit the defaults of installed
+//! skill packs, which only fill keys `config.toml` leaves unset. On top of
+//! it, in order:
+//!
+//! 1. `config.local.toml` next to it (machine-specific, keep out of VCS);
+//! 2. the active profile, `profiles/<name>.toml`, selected with
+//!    `--profile <name>` or `ZEROCLAW_PROFILE`;
+//! 3. `ZEROCLAW__SECTION__KEY` environment variables.
+//!
+//! Tables merge key by key; any other value replaces the one below it.
+//! `save()` writes only the base layer: values that still match an overlay
+//! are put back to what `config.toml` had, so local and profile settings
+//! never leak into the shared file.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Machine-local overlay file, next to `config.toml`.
+pub const LOCAL_CONFIG_FILE: &str = "config.local.toml";
+
+/// Directory (inside the config directory) holding profile overlays.
+pub const PROFILES_DIR: &str = "profiles";
+
+/// Environment variable naming the active profile; `--profile` sets it.
+pub const PROFILE_ENV: &str = "ZEROCLAW_PROFILE";
+
+/// Prefix of environment variables that override single config keys.
+/// `__` separates path segments: `ZEROCLAW__GATEWAY__PORT=8080`.
+const ENV_OVERRIDE_PREFIX: &str = "ZEROCLAW__";
+
+/// What the overlays contributed on top of `config.toml`.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLayers {
+    /// `config.toml` as read, before overlays.
+    base: toml::Table,
+    /// All overlays merged, as written in their sources.
+    overlay: toml::Table,
+    /// Active profile, if any.
+    pub profile: Option<String>,
+}
+
+impl ConfigLayers {
+    pub fn is_empty(&self) -> bool {
+        self.overlay.is_empty()
+    }
+}
+
+/// Profile selected through `ZEROCLAW_PROFILE`.
+pub fn active_profile() -> Option<String> {
+    std::env::var(PROFILE_ENV)
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+pub fn profile_path(zeroclaw_dir: &Path, profile: &str) -> PathBuf {
+    zeroclaw_dir
+        .join(PROFILES_DIR)
+        .join(format!("{profile}.toml"))
+}
+
+/// Overlay files that exist (or must exist) for `profile`, in merge order.
+pub fn overlay_files(zeroclaw_dir: &Path, profile: Option<&str>) -> Vec<PathBuf> {
+    let mut files = vec![zeroclaw_dir.join(LOCAL_CONFIG_FILE)];
+    files.extend(profile.map(|name| profile_path(zeroclaw_dir, name)));
+    files
+}
+
+/// Apply `defaults` (for keys it leaves unset), `config.local.toml`, the
+/// profile and env overrides to the raw `config.toml` text. Returns the
+/// merged text and what was layered on.
+pub fn apply_layers(
+    zeroclaw_dir: &Path,
+    contents: String,
+    profile: Option<&str>,
+    defaults: toml::Table,
+) -> Result<(String, ConfigLayers)> {
+    let mut overlay = toml::Table::new();
+
+    let local = zeroclaw_dir.join(LOCAL_CONFIG_FILE);
+    if local.exists() {
+        merge_tables(&mut overlay, read_table(&local)?);
+    }
+    if let Some(name) = profile {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            bail!("Invalid profile name '{name}': use letters, digits, '-' or '_'");
+        }
+        let path = profile_path(zeroclaw_dir, name);
+        if !path.exists() {
+            bail!(
+                "Profile '{name}' not found: {} does not exist",
+                path.display()
+            );
+        }
+        merge_tables(&mut overlay, read_table(&path)?);
+    }
+    merge_tables(&mut overlay, env_overlay(std::env::vars()));
+
+    if overlay.is_empty() && defaults.is_empty() {
+        return Ok((
+            contents,
+            ConfigLayers {
+                profile: profile.map(str::to_string),
+                ..ConfigLayers::default()
+            },
+        ));
+    }
+
+    let base: toml::Table = toml::from_str(&contents).context("Failed to parse config file")?;
+    // Defaults become part of the overlay, so `save()` keeps them out of
+    // `config.toml` like any other layered value.
+    let mut layered = unset_in(&base, defaults);
+    merge_tables(&mut layered, overlay);
+    let layers = ConfigLayers {
+        base: toml::Table::new(),
+        overlay: layered,
+        profile: profile.map(str::to_string),
+    };
+    if layers.is_empty() {
+        return Ok((contents, ConfigLayers { base, ..layers }));
+    }
+
+    let mut merged = base.clone();
+    merge_tables(&mut merged, layers.overlay.clone());
+    let merged = toml::to_string(&merged).context("Failed to re-serialize layered config")?;
+    Ok((merged, ConfigLayers { base, ..layers }))
+}
+
+fn read_table(path: &Path) -> Result<toml::Table> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    toml::from_str(&raw).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// The parts of `defaults` that `base` does not set.
+fn unset_in(base: &toml::Table, defaults: toml::Table) -> toml::Table {
+    let mut unset = toml::Table::new();
+    for (key, value) in defaults {
+        match (base.get(&key), value) {
+            (None, value) => {
+                unset.insert(key, value);
+            }
+            (Some(toml::Value::Table(existing)), toml::Value::Table(incoming)) => {
+                let nested = unset_in(existing, incoming);
+                if !nested.is_empty() {
+                    unset.insert(key, toml::Value::Table(nested));
+                }
+            }
+            (Some(_), _) => {}
+        }
+    }
+    unset
+}
+
+/// Merge `overlay` into `target`: tables recursively, other values replace.
+pub fn merge_tables(target: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (target.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(incoming)) => {
+                merge_tables(existing, incoming);
+            }
+            (_, value) => {
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Build an overlay from `ZEROCLAW__SECTION__KEY=value` variables. Values are
+/// parsed as TOML (`true`, `8080`, `["a", "b"]`) and fall back to a string,
+/// also when the parsed value does not fit the key: `ZEROCLAW__API_KEY=123456`
+/// or a date-like value for a string field stays a string.
+fn env_overlay(vars: impl Iterator<Item = (String, String)>) -> toml::Table {
+    let mut overlay = toml::Table::new();
+    for (name, raw) in vars {
+        let Some(path) = name.strip_prefix(ENV_OVERRIDE_PREFIX) else {
+            continue;
+        };
+        let segments: Vec<String> = path.split("__").map(str::to_ascii_lowercase).collect();
+        if segments.iter().any(String::is_empty) {
+            tracing::warn!("Ignoring malformed config override variable {name}");
+            continue;
+        }
+
+        let value = parse_env_value(&raw);
+        let typed = !value.is_str();
+        let mut table = nest(&segments, value);
+        if typed && !config_accepts(&table) {
+            let as_string = nest(&segments, toml::Value::String(raw.clone()));
+            if config_accepts(&as_string) {
+                table = as_string;
+            }
+        }
+        merge_tables(&mut overlay, table);
+    }
+    overlay
+}
+
+/// `value` under the key path `segments`.
+fn nest(segments: &[String], value: toml::Value) -> toml::Table {
+    let mut table = toml::Table::new();
+    table.insert(segments[segments.len() - 1].clone(), value);
+    for segment in segments[..segments.len() - 1].iter().rev() {
+        let mut parent = toml::Table::new();
+        parent.insert(segment.clone(), toml::Value::Table(table));
+        table = parent;
+    }
+    table
+}
+
+/// Whether the default config still deserializes with `overlay` applied,
+/// i.e. every value in it has the type its key expects.
+fn config_accepts(overlay: &toml::Table) -> bool {
+    static DEFAULTS: OnceLock<Option<toml::Table>> = OnceLock::new();
+    let Some(defaults) = DEFAULTS.get_or_init(|| {
+        toml::to_string(&super::schema::Config::default())
+            .ok()
+            .and_then(|raw| toml::from_str(&raw).ok())
+    }) else {
+        return true;
+    };
+    let mut probe = defaults.clone();
+    merge_tables(&mut probe, overlay.clone());
+    toml::to_string(&probe)
+        .ok()
+        .is_some_and(|raw| toml::from_str::<super::schema::Config>(&raw).is_ok())
+}
+
+fn parse_env_value(raw: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {raw}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
+
+/// Undo overlays in a document about to be written to `config.toml`. A key
+/// whose current value (`plain`, unencrypted) still equals its overlay value
+/// gets the base value back, or is dropped if the base did not set it.
+pub fn restore_base_values(doc: &mut toml::Table, plain: &toml::Table, layers: &ConfigLayers) {
+    restore_table(doc, Some(plain), Some(&layers.base), &layers.overlay);
+}
+
+fn restore_table(
+    doc: &mut toml::Table,
+    plain: Option<&toml::Table>,
+    base: Option<&toml::Table>,
+    overlay: &toml::Table,
+) {
+    for (key, overlay_value) in overlay {
+        let plain_value = plain.and_then(|table| table.get(key));
+        let base_value = base.and_then(|table| table.get(key));
+        if let toml::Value::Table(overlay_table) = overlay_value {
+            if let Some(toml::Value::Table(doc_table)) = doc.get_mut(key) {
+                restore_table(
+                    doc_table,
+                    plain_value.and_then(toml::Value::as_table),
+                    base_value.and_then(toml::Value::as_table),
+                    overlay_table,
+                );
+            }
+            continue;
+        }
+        if plain_value != Some(overlay_value) {
+            continue;
+        }
+        match base_value {
+            Some(value) => {
+                doc.insert(key.clone(), value.clone());
+            }
+            None => {
+                doc.remove(key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const BASE: &str = r#"
+default_model = "base-model"
+default_temperature = 0.7
+
+[gateway]
+port = 42617
+host = "127.0.0.1"
+"#;
+
+    #[test]
+    fn overlays_apply_in_order() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join(LOCAL_CONFIG_FILE),
+            "default_model = \"local-model\"\n[gateway]\nport = 9000\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(tmp.path().join(PROFILES_DIR)).unwrap();
+        std::fs::write(
+            profile_path(tmp.path(), "work"),
+            "default_model = \"work-model\"\n",
+        )
+        .unwrap();
+
+        let (merged, layers) =
+            apply_layers(tmp.path(), BASE.into(), Some("work"), toml::Table::new()).unwrap();
+        let merged: toml::Table = toml::from_str(&merged).unwrap();
+        assert_eq!(merged["default_model"].as_str(), Some("work-model"));
+        assert_eq!(merged["gateway"]["port"].as_integer(), Some(9000));
+        assert_eq!(merged["gateway"]["host"].as_str(), Some("127.0.0.1"));
+        assert_eq!(layers.profile.as_deref(), Some("work"));
+
+        let err =
+            apply_layers(tmp.path(), BASE.into(), Some("missing"), toml::Table::new()).unwrap_err();
+        assert!(err.to_string().contains("Profile 'missing' not found"));
+    }
+
+    #[test]
+    fn defaults_fill_only_unset_keys_and_stay_out_of_base_file() {
+        let tmp = TempDir::new().unwrap();
+        let defaults: toml::Table = toml::from_str(
+            "default_model = \"pack-model\"\n[gateway]\nport = 1\nrequest_timeout_secs = 30\n[kb]\nenabled = true\n",
+        )
+        .unwrap();
+
+        let (merged, layers) = apply_layers(tmp.path(), BASE.into(), None, defaults).unwrap();
+        let plain: toml::Table = toml::from_str(&merged).unwrap();
+        assert_eq!(plain["default_model"].as_str(), Some("base-model"));
+        assert_eq!(plain["gateway"]["port"].as_integer(), Some(42617));
+        assert_eq!(
+            plain["gateway"]["request_timeout_secs"].as_integer(),
+            Some(30)
+        );
+        assert_eq!(plain["kb"]["enabled"].as_bool(), Some(true));
+
+        let mut doc = plain.clone();
+        restore_base_values(&mut doc, &plain, &layers);
+        assert!(!doc["kb"].as_table().unwrap().contains_key("enabled"));
+        assert!(!doc["gateway"]
+            .as_table()
+            .unwrap()
+            .contains_key("request_timeout_secs"));
+    }
+
+    #[test]
+    fn env_overrides_build_nested_typed_values() {
+        let overlay = env_overlay(
+            [
+                ("ZEROCLAW__GATEWAY__PORT", "8080"),
+                ("ZEROCLAW__AUTONOMY__AUTO_APPROVE", "[\"shell\"]"),
+                ("ZEROCLAW__DEFAULT_MODEL", "gpt-4o"),
+                ("ZEROCLAW__COMPOSIO__ENTITY_ID", "123456"),
+                ("ZEROCLAW__GATEWAY__HOST", "2024-06-01"),
+                ("ZEROCLAW__BROKEN____KEY", "x"),
+                ("ZEROCLAW_API_KEY", "ignored"),
+            ]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string())),
+        );
+        assert_eq!(overlay["gateway"]["port"].as_integer(), Some(8080));
+        assert_eq!(
+            overlay["autonomy"]["auto_approve"][0].as_str(),
+            Some("shell")
+        );
+        assert_eq!(overlay["default_model"].as_str(), Some("gpt-4o"));
+        // Values that only parse as the wrong TOML type stay strings.
+        assert_eq!(overlay["composio"]["entity_id"].as_str(), Some("123456"));
+        assert_eq!(overlay["gateway"]["host"].as_str(), Some("2024-06-01"));
+        assert!(!overlay.contains_key("broken"));
+        assert!(!overlay.contains_key("api_key"));
+    }
+
+    #[test]
+    fn restore_keeps_overlay_values_out_of_base_file() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join(LOCAL_CONFIG_FILE),
+            "api_key = \"local-key\"\n[gateway]\nport = 9000\n",
+        )
+        .unwrap();
+        let (merged, layers) =
+            apply_layers(tmp.path(), BASE.into(), None, toml::Table::new()).unwrap();
+
+        // The user changed the port at runtime; the api key is untouched.
+        let mut plain: toml::Table = toml::from_str(&merged).unwrap();
+        plain["gateway"]
+            .as_table_mut()
+            .unwrap()
+            .insert("port".into(), toml::Value::Integer(9100));
+        let mut doc = plain.clone();
+        restore_base_values(&mut doc, &plain, &layers);
+
+        assert!(!doc.contains_key("api_key"));
+        assert_eq!(doc["gateway"]["port"].as_integer(), Some(9100));
+        assert_eq!(doc["default_model"].as_str(), Some("base-model"));
+    }
+}
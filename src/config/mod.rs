@@ -1,3 +1,4 @@
+pub mod layers;
 pub mod schema;
 pub mod traits;
 
@@ -5,22 +6,38 @@ pub mod traits;
 pub use schema::{
     apply_runtime_proxy_to_builder, build_runtime_proxy_client,
     build_runtime_proxy_client_with_timeouts, runtime_proxy_config, set_runtime_proxy_config,
-    AgentConfig, AgentsIpcConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig,
-    BrowserConfig, BuiltinHooksConfig, ChannelsConfig, ClassificationRule, ComposioConfig, Config,
-    CoordinationConfig, CostConfig, CronConfig, DelegateAgentConfig, DiscordConfig,
-    EconomicConfig, EconomicTokenPricing,
-    DockerRuntimeConfig, EmbeddingRouteConfig, EstopConfig, FeishuConfig, GatewayConfig,
-    GroupReplyConfig, GroupReplyMode, HardwareConfig, HardwareTransport, HeartbeatConfig,
-    HooksConfig, HttpRequestConfig, IMessageConfig, IdentityConfig, LarkConfig, MatrixConfig,
-    MemoryConfig, ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig,
-    NonCliNaturalLanguageApprovalMode, ObservabilityConfig, OtpChallengeDelivery, OtpConfig,
-    OtpMethod, PeripheralBoardConfig, PeripheralsConfig, PerplexityFilterConfig, PluginEntryConfig,
-    PluginsConfig, ProviderConfig, ProxyConfig, ProxyScope, QdrantConfig,
-    QueryClassificationConfig, ReliabilityConfig, ResearchPhaseConfig, ResearchTrigger,
+    AgentConfig, AgentsIpcConfig, AnalyticsConfig, AttachmentsConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig,
+    BrowserConfig, BuiltinHooksConfig, ChannelApprovalPolicy, ChannelsConfig, ClassificationRule, CommandCategory,
+    CommandPolicyConfig, CommandRuleAction, CommandRuleConfig, ComposioConfig, Config,
+    ContentInjectionAction, ContentInjectionConfig, ContextBudgetsConfig, ConvertDocumentConfig,
+    CoordinationConfig, CostConfig, CronConfig, CronJobConfig, DailyBudgetConfig,
+    DelegateAgentConfig, DiscordConfig,
+    EconomicConfig, EconomicTokenPricing, EgressConfig,
+    DockerRuntimeConfig, EmbeddingRouteConfig, EstopConfig, FeedSourceConfig, FeedsConfig,
+    FeishuConfig, GatewayAuthConfig,
+    GatewayConfig, GatewayLimitsConfig, GatewayRouteAuthConfig, GatewayRouteLimitConfig,
+    GatewayTenantConfig,
+    GroupReplyConfig, GroupReplyMode, HardwareConfig, HardwareTransport, HeartbeatChannelConfig,
+    HeartbeatConfig, HeartbeatQuietHoursConfig,
+    HooksConfig, HttpRequestConfig, I18nConfig, IMessageConfig, IdentityConfig, JiraConfig,
+    JiraDeployment, KbConfig, LarkConfig, LeakGuardConfig, LinearConfig, MailboxConfig,
+    MatrixConfig, MemoryAccessConfig,
+    MemoryConfig, MemoryDecayConfig, ModelRouteConfig, ModerationAction, ModerationConfig,
+    ModerationRuleConfig, MultimodalConfig, NextcloudTalkConfig,
+    NonCliNaturalLanguageApprovalMode, NotifyConfig, NotifyPriority, NotifyService,
+    NotifyTargetConfig, ObservabilityConfig, OtpChallengeDelivery, OtpConfig,
+    OtpMethod, PdfEngine, PeripheralBoardConfig, PersonaConfig, PersonasConfig, PeripheralsConfig, PerplexityFilterConfig, PluginEntryConfig,
+    PluginsConfig, PrincipalLocaleConfig, PrometheusConfig, ProviderCapabilityConfig, ProviderConfig, ProviderRetryPolicyConfig, ProxyConfig,
+    ProxyScope, QdrantConfig,
+    QueryClassificationConfig, QueueBackend, QueueConfig, ReliabilityConfig, ResearchPhaseConfig,
+    ResearchTrigger,
     ResourceLimitsConfig, RuntimeConfig, SandboxBackend, SandboxConfig, SchedulerConfig,
-    SecretsConfig, SecurityConfig, SecurityRoleConfig, SkillsConfig, SkillsPromptInjectionMode,
+    SecretBackend, SecretsConfig, SecurityConfig, SecurityRoleConfig, SessionBackend,
+    SessionConfig, SessionHistoryStrategy, SessionRedisConfig, ShellSessionConfig, SkillsConfig,
+    SkillsPromptInjectionMode,
     SlackConfig, StorageConfig, StorageProviderConfig, StorageProviderSection, StreamMode,
-    SyscallAnomalyConfig, TelegramConfig, TranscriptionConfig, TunnelConfig, UrlAccessConfig,
+    SyscallAnomalyConfig, TelegramConfig, ToolAnomalyAction, ToolAnomalyConfig, ToolSchemaDialect,
+    TranscriptionConfig, TunnelConfig, UrlAccessConfig,
     WasmCapabilityEscalationMode, WasmConfig, WasmModuleHashPolicy, WasmRuntimeConfig,
     WasmSecurityConfig, WebFetchConfig, WebSearchConfig, WebhookConfig,
 };
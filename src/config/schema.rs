@@ -38,9 +38,15 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "channel.whatsapp",
     "tool.browser",
     "tool.composio",
+    "tool.feed_fetch",
     "tool.http_request",
+    "tool.notify",
+    "tool.prom_query",
+    "tool.jira",
+    "tool.linear",
     "tool.pushover",
     "memory.embeddings",
+    "memory.kb",
     "tunnel.custom",
     "transcription.groq",
 ];
@@ -95,6 +101,12 @@ pub struct Config {
     /// Path to config.toml - computed from home, not serialized
     #[serde(skip)]
     pub config_path: PathBuf,
+    /// `secret://` references resolved at load time, restored by `save()`.
+    #[serde(skip)]
+    pub secret_refs: Vec<crate::security::vault::ResolvedSecretRef>,
+    /// Overlays applied on top of `config.toml` at load time, undone by `save()`.
+    #[serde(skip)]
+    pub layers: crate::config::layers::ConfigLayers,
     /// API key for the selected provider. Overridden by `ZEROCLAW_API_KEY` or `API_KEY` env vars.
     pub api_key: Option<String>,
     /// Base URL override for provider API (e.g. "http://10.0.0.1:11434" for remote Ollama)
@@ -161,6 +173,11 @@ pub struct Config {
     #[serde(default)]
     pub embedding_routes: Vec<EmbeddingRouteConfig>,
 
+    /// Tool-calling and context-window limits per provider/model, overriding
+    /// the built-in table (`[[provider_capabilities]]`).
+    #[serde(default)]
+    pub provider_capabilities: Vec<ProviderCapabilityConfig>,
+
     /// Automatic query classification — maps user messages to model hints.
     #[serde(default)]
     pub query_classification: QueryClassificationConfig,
@@ -173,6 +190,42 @@ pub struct Config {
     #[serde(default)]
     pub cron: CronConfig,
 
+    /// RSS/Atom feed watcher (`[feeds]`).
+    #[serde(default)]
+    pub feeds: FeedsConfig,
+
+    /// Daily activity digest (`[analytics]`).
+    #[serde(default)]
+    pub analytics: AnalyticsConfig,
+
+    /// Mailbox for the email lookup tools (`[mailbox]`).
+    #[serde(default)]
+    pub mailbox: MailboxConfig,
+
+    /// Prometheus endpoint for the `prom_query` tool (`[prometheus]`).
+    #[serde(default)]
+    pub prometheus: PrometheusConfig,
+
+    /// Jira site for the ticketing tools (`[jira]`).
+    #[serde(default)]
+    pub jira: JiraConfig,
+
+    /// Linear workspace for the ticketing tools (`[linear]`).
+    #[serde(default)]
+    pub linear: LinearConfig,
+
+    /// Knowledge base of private documents (`[kb]`).
+    #[serde(default)]
+    pub kb: KbConfig,
+
+    /// External converters for the `convert_document` tool (`[convert_document]`).
+    #[serde(default)]
+    pub convert_document: ConvertDocumentConfig,
+
+    /// Named personas selectable per channel or chat (`[personas]`).
+    #[serde(default)]
+    pub personas: PersonasConfig,
+
     /// Goal loop configuration for autonomous long-term goal execution (`[goal_loop]`).
     #[serde(default)]
     pub goal_loop: GoalLoopConfig,
@@ -205,10 +258,30 @@ pub struct Config {
     #[serde(default)]
     pub secrets: SecretsConfig,
 
+    /// Channel conversation session storage (`[session]`).
+    #[serde(default)]
+    pub session: SessionConfig,
+
+    /// Retention of files received through channels (`[attachments]`).
+    #[serde(default)]
+    pub attachments: AttachmentsConfig,
+
+    /// Language of user-facing runtime messages (`[i18n]`).
+    #[serde(default)]
+    pub i18n: I18nConfig,
+
+    /// Push notification targets (`[notify]`).
+    #[serde(default)]
+    pub notify: NotifyConfig,
+
     /// Browser automation configuration (`[browser]`).
     #[serde(default)]
     pub browser: BrowserConfig,
 
+    /// Persistent PTY shell sessions tool (`[shell_session]`).
+    #[serde(default)]
+    pub shell_session: ShellSessionConfig,
+
     /// HTTP request tool configuration (`[http_request]`).
     #[serde(default)]
     pub http_request: HttpRequestConfig,
@@ -285,6 +358,14 @@ pub struct Config {
     #[serde(default)]
     pub model_support_vision: Option<bool>,
 
+    /// Native tool-calling override for the active provider/model.
+    /// - `None` (default): use provider's built-in default
+    /// - `Some(false)`: send tools as prompt instructions (e.g. a local model
+    ///   that was not trained for function calling)
+    /// - `Some(true)`: force native tool calling on
+    #[serde(default)]
+    pub model_support_tools: Option<bool>,
+
     /// WASM plugin engine configuration (`[wasm]` section).
     #[serde(default)]
     pub wasm: WasmConfig,
@@ -360,6 +441,9 @@ pub struct DelegateAgentConfig {
     /// Maximum tool-call iterations in agentic mode.
     #[serde(default = "default_max_tool_iterations")]
     pub max_iterations: usize,
+    /// Maximum output tokens per provider call made by the sub-agent.
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
 }
 
 fn default_max_depth() -> u32 {
@@ -382,6 +466,7 @@ impl std::fmt::Debug for DelegateAgentConfig {
             .field("agentic", &self.agentic)
             .field("allowed_tools", &self.allowed_tools)
             .field("max_iterations", &self.max_iterations)
+            .field("max_tokens", &self.max_tokens)
             .finish()
     }
 }
@@ -412,6 +497,7 @@ impl std::fmt::Debug for Config {
             self.channels_config.qq.is_some(),
             self.channels_config.nostr.is_some(),
             self.channels_config.clawdtalk.is_some(),
+            self.channels_config.queue.is_some(),
         ]
         .into_iter()
         .filter(|enabled| *enabled)
@@ -748,6 +834,23 @@ pub struct AgentConfig {
     /// Set to `0` to disable. Default: `3`.
     #[serde(default = "default_loop_detection_failure_streak")]
     pub loop_detection_failure_streak: usize,
+    /// Maximum tool calls running at once across all sessions and channels.
+    /// Set to `0` for no limit. Default: `8`.
+    #[serde(default = "default_agent_max_concurrent_tools")]
+    pub max_concurrent_tools: usize,
+    /// Per-tool maximum concurrent calls, keyed by tool name. Calls beyond the
+    /// limit wait in arrival order. Default: `{ browser = 1, screenshot = 1 }`.
+    #[serde(default = "default_agent_tool_concurrency")]
+    pub tool_concurrency: HashMap<String, usize>,
+    /// Plan every channel turn before running tools: the agent replies with
+    /// a checklist of the tool calls it would make, which `/plan-approve`
+    /// runs. `/plan <request>` does this for one turn. Default: `false`.
+    #[serde(default)]
+    pub plan_mode: bool,
+    /// Journal file writes, git commits and memory writes per turn so
+    /// `/undo` and `zeroclaw undo last` can revert them. Default: `true`.
+    #[serde(default = "default_true")]
+    pub undo_journal: bool,
 }
 
 fn default_agent_max_tool_iterations() -> usize {
@@ -774,6 +877,14 @@ fn default_loop_detection_failure_streak() -> usize {
     3
 }
 
+fn default_agent_max_concurrent_tools() -> usize {
+    8
+}
+
+fn default_agent_tool_concurrency() -> HashMap<String, usize> {
+    HashMap::from([("browser".into(), 1), ("screenshot".into(), 1)])
+}
+
 impl Default for AgentConfig {
     fn default() -> Self {
         Self {
@@ -785,6 +896,10 @@ impl Default for AgentConfig {
             loop_detection_no_progress_threshold: default_loop_detection_no_progress_threshold(),
             loop_detection_ping_pong_cycles: default_loop_detection_ping_pong_cycles(),
             loop_detection_failure_streak: default_loop_detection_failure_streak(),
+            max_concurrent_tools: default_agent_max_concurrent_tools(),
+            tool_concurrency: default_agent_tool_concurrency(),
+            plan_mode: false,
+            undo_journal: true,
         }
     }
 }
@@ -988,6 +1103,59 @@ pub struct CostConfig {
     /// Per-model pricing (USD per 1M tokens)
     #[serde(default)]
     pub prices: std::collections::HashMap<String, ModelPricing>,
+
+    /// Monthly spending limit per channel principal in USD; `0` disables
+    /// the limit (default: 0)
+    #[serde(default)]
+    pub principal_monthly_limit_usd: f64,
+
+    /// Per-principal overrides of `principal_monthly_limit_usd`, keyed
+    /// `<channel>:<sender>`
+    #[serde(default)]
+    pub principal_limits: std::collections::HashMap<String, f64>,
+
+    /// Daily caps applied to every channel principal
+    #[serde(default)]
+    pub daily_budget: DailyBudgetConfig,
+
+    /// Daily caps for all principals of a channel together, keyed by
+    /// channel name
+    #[serde(default)]
+    pub channel_daily_budgets: std::collections::HashMap<String, DailyBudgetConfig>,
+
+    /// Per-principal overrides of `daily_budget`, keyed `<channel>:<sender>`
+    #[serde(default)]
+    pub principal_daily_budgets: std::collections::HashMap<String, DailyBudgetConfig>,
+
+    /// Route a principal's turns to the `budget` model route once it has
+    /// used this percentage of its daily token budget or monthly spending
+    /// limit; `0` disables the downgrade (default: 80)
+    #[serde(default = "default_downgrade_percent")]
+    pub downgrade_at_percent: u8,
+}
+
+/// Usage caps per UTC day (`[cost.daily_budget]`). `0` leaves a metric
+/// unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct DailyBudgetConfig {
+    /// Channel turns (agent replies)
+    #[serde(default)]
+    pub max_turns: u64,
+
+    /// Tool calls executed
+    #[serde(default)]
+    pub max_tool_calls: u64,
+
+    /// Provider tokens, input plus output
+    #[serde(default)]
+    pub max_tokens: u64,
+}
+
+impl DailyBudgetConfig {
+    /// Whether any metric is capped.
+    pub fn is_limited(&self) -> bool {
+        self.max_turns > 0 || self.max_tool_calls > 0 || self.max_tokens > 0
+    }
 }
 
 /// Per-model pricing entry (USD per 1M tokens).
@@ -1014,6 +1182,10 @@ fn default_warn_percent() -> u8 {
     80
 }
 
+fn default_downgrade_percent() -> u8 {
+    80
+}
+
 impl Default for CostConfig {
     fn default() -> Self {
         Self {
@@ -1023,6 +1195,12 @@ impl Default for CostConfig {
             warn_at_percent: default_warn_percent(),
             allow_override: false,
             prices: get_default_pricing(),
+            principal_monthly_limit_usd: 0.0,
+            principal_limits: std::collections::HashMap::new(),
+            daily_budget: DailyBudgetConfig::default(),
+            channel_daily_budgets: std::collections::HashMap::new(),
+            principal_daily_budgets: std::collections::HashMap::new(),
+            downgrade_at_percent: default_downgrade_percent(),
         }
     }
 }
@@ -1256,6 +1434,11 @@ pub struct GatewayConfig {
     /// Paired bearer tokens (managed automatically, not user-edited)
     #[serde(default)]
     pub paired_tokens: Vec<String>,
+    /// Static API keys accepted as `Authorization: Bearer <key>` on the
+    /// OpenAI-compatible `/v1/*` endpoints, alongside paired tokens.
+    /// Encrypted at rest when `secrets.encrypt` is on.
+    #[serde(default)]
+    pub api_keys: Vec<String>,
 
     /// Max `/pair` requests per minute per client key.
     #[serde(default = "default_pair_rate_limit")]
@@ -1285,6 +1468,47 @@ pub struct GatewayConfig {
     /// Node-control protocol scaffold (`[gateway.node_control]`).
     #[serde(default)]
     pub node_control: NodeControlConfig,
+
+    /// Gateway-wide request authentication (`[gateway.auth]`).
+    #[serde(default)]
+    pub auth: GatewayAuthConfig,
+
+    /// Request body limits and handler timeouts (`[gateway.limits]`).
+    #[serde(default)]
+    pub limits: GatewayLimitsConfig,
+
+    /// Keep this many recent platform webhook bodies per channel, redacted,
+    /// for `zeroclaw debug payloads`. `0` disables the archive. Default: `0`.
+    #[serde(default)]
+    pub payload_archive_size: usize,
+
+    /// Additional agent workspaces served by this gateway
+    /// (`[gateway.tenants.<name>]`), reached under `/t/<name>/...` or with
+    /// the `X-ZeroClaw-Tenant: <name>` header.
+    #[serde(default)]
+    pub tenants: HashMap<String, GatewayTenantConfig>,
+}
+
+/// One extra workspace hosted by the gateway (`[gateway.tenants.<name>]`).
+///
+/// The tenant runs with its own config, sessions, memory and tools; only
+/// the listener, `[gateway.auth]` and `[gateway.limits]` of the main config
+/// are shared.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct GatewayTenantConfig {
+    /// Directory holding the tenant's `config.toml`. Its workspace is
+    /// `<config_dir>/workspace`.
+    pub config_dir: PathBuf,
+}
+
+impl GatewayTenantConfig {
+    pub fn config_path(&self) -> PathBuf {
+        self.config_dir.join("config.toml")
+    }
+
+    pub fn workspace_dir(&self) -> PathBuf {
+        self.config_dir.join("workspace")
+    }
 }
 
 /// Node-control scaffold settings under `[gateway.node_control]`.
@@ -1305,6 +1529,122 @@ pub struct NodeControlConfig {
     pub allowed_node_ids: Vec<String>,
 }
 
+/// Request authentication enforced in front of every gateway route
+/// (`[gateway.auth]`), on top of pairing and platform webhook signatures.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayAuthConfig {
+    /// Enforce the rules below before requests reach any handler.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Client IPs or CIDRs (e.g. `10.0.0.0/8`) allowed to reach the gateway.
+    /// Empty means any address. Honors `trust_forwarded_headers`.
+    #[serde(default)]
+    pub ip_allowlist: Vec<String>,
+
+    /// Require a client certificate verified by a TLS-terminating proxy on
+    /// every route. See `client_cert_header`.
+    #[serde(default)]
+    pub require_client_cert: bool,
+
+    /// Header the proxy sets after verifying the client certificate
+    /// (nginx `$ssl_client_verify`, default `X-SSL-Client-Verify`). Requests
+    /// needing a client cert must carry it with the value `SUCCESS`.
+    #[serde(default = "default_gateway_client_cert_header")]
+    pub client_cert_header: String,
+
+    /// Per-route rules, matched by longest path prefix.
+    #[serde(default)]
+    pub routes: Vec<GatewayRouteAuthConfig>,
+}
+
+/// Authentication rule for one route prefix under `[[gateway.auth.routes]]`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayRouteAuthConfig {
+    /// Path prefix this rule covers, e.g. `/webhook` or `/api/admin`.
+    pub path: String,
+
+    /// API keys accepted in `X-API-Key` (or `Authorization: Bearer` when the
+    /// route has no bearer auth of its own). Empty means no key is required.
+    /// Encrypted at rest when `secrets.encrypt` is on.
+    #[serde(default)]
+    pub api_keys: Vec<String>,
+
+    /// Replaces the global `ip_allowlist` for this route when non-empty.
+    #[serde(default)]
+    pub ip_allowlist: Vec<String>,
+
+    /// Require a proxy-verified client certificate on this route.
+    #[serde(default)]
+    pub require_client_cert: bool,
+}
+
+/// Request body limits and handler timeouts under `[gateway.limits]`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayLimitsConfig {
+    /// Largest accepted request body in bytes for routes without an override.
+    #[serde(default = "default_gateway_max_body_bytes")]
+    pub max_body_bytes: usize,
+
+    /// Seconds a handler may take (including reading the body) before the
+    /// request fails with `408`.
+    #[serde(default = "default_gateway_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Per-route overrides, matched by longest path prefix.
+    #[serde(default)]
+    pub routes: Vec<GatewayRouteLimitConfig>,
+}
+
+/// Limit override for one route prefix under `[[gateway.limits.routes]]`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayRouteLimitConfig {
+    /// Path prefix this override covers, e.g. `/webhook` or `/v1`.
+    pub path: String,
+
+    /// Body limit for this route; unset inherits `max_body_bytes`.
+    #[serde(default)]
+    pub max_body_bytes: Option<usize>,
+
+    /// Handler timeout for this route; unset inherits `timeout_secs`.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+fn default_gateway_max_body_bytes() -> usize {
+    65_536
+}
+
+fn default_gateway_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for GatewayLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: default_gateway_max_body_bytes(),
+            timeout_secs: default_gateway_timeout_secs(),
+            routes: Vec::new(),
+        }
+    }
+}
+
+fn default_gateway_client_cert_header() -> String {
+    "X-SSL-Client-Verify".into()
+}
+
+impl Default for GatewayAuthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ip_allowlist: Vec::new(),
+            require_client_cert: false,
+            client_cert_header: default_gateway_client_cert_header(),
+            routes: Vec::new(),
+        }
+    }
+}
+
 fn default_gateway_port() -> u16 {
     42617
 }
@@ -1345,6 +1685,7 @@ impl Default for GatewayConfig {
             require_pairing: true,
             allow_public_bind: false,
             paired_tokens: Vec::new(),
+            api_keys: Vec::new(),
             pair_rate_limit_per_minute: default_pair_rate_limit(),
             webhook_rate_limit_per_minute: default_webhook_rate_limit(),
             trust_forwarded_headers: false,
@@ -1352,6 +1693,10 @@ impl Default for GatewayConfig {
             idempotency_ttl_secs: default_idempotency_ttl_secs(),
             idempotency_max_keys: default_gateway_idempotency_max_keys(),
             node_control: NodeControlConfig::default(),
+            auth: GatewayAuthConfig::default(),
+            limits: GatewayLimitsConfig::default(),
+            payload_archive_size: 0,
+            tenants: HashMap::new(),
         }
     }
 }
@@ -1388,83 +1733,478 @@ impl Default for ComposioConfig {
     }
 }
 
-// ── Secrets (encrypted credential store) ────────────────────────
+// ── Session storage ─────────────────────────────────────────────
 
-/// Secrets encryption configuration (`[secrets]` section).
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct SecretsConfig {
-    /// Enable encryption for API keys and tokens in config.toml
-    #[serde(default = "default_true")]
-    pub encrypt: bool,
+/// Where per-sender channel conversation history is kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionBackend {
+    /// In-process only; history is lost on restart and not shared.
+    #[default]
+    Memory,
+    /// `<workspace>/sessions/sessions.db`; survives restarts on one host.
+    Sqlite,
+    /// Redis, shared by every instance pointing at the same server.
+    Redis,
 }
 
-impl Default for SecretsConfig {
-    fn default() -> Self {
-        Self { encrypt: true }
-    }
+/// How channel history is shortened once it exceeds `session.max_messages`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionHistoryStrategy {
+    /// Drop the oldest messages.
+    #[default]
+    Truncate,
+    /// Replace the oldest `summarize_batch` messages with a model-written summary.
+    Summarize,
 }
 
-// ── Browser (friendly-service browsing only) ───────────────────
-
-/// Computer-use sidecar configuration (`[browser.computer_use]` section).
-///
-/// Delegates OS-level mouse, keyboard, and screenshot actions to a local sidecar.
+/// Channel session storage configuration (`[session]` section).
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct BrowserComputerUseConfig {
-    /// Sidecar endpoint for computer-use actions (OS-level mouse/keyboard/screenshot)
-    #[serde(default = "default_browser_computer_use_endpoint")]
-    pub endpoint: String,
-    /// Optional bearer token for computer-use sidecar
+pub struct SessionConfig {
+    /// Storage backend for conversation history.
     #[serde(default)]
-    pub api_key: Option<String>,
-    /// Per-action request timeout in milliseconds
-    #[serde(default = "default_browser_computer_use_timeout_ms")]
-    pub timeout_ms: u64,
-    /// Allow remote/public endpoint for computer-use sidecar (default: false)
+    pub backend: SessionBackend,
+
+    /// Expire idle sessions after this many seconds (`0` = never). Mapped to
+    /// `EXPIRE` on the Redis backend.
     #[serde(default)]
-    pub allow_remote_endpoint: bool,
-    /// Optional window title/process allowlist forwarded to sidecar policy
+    pub ttl_secs: u64,
+
+    /// Maximum messages kept per sender before the history strategy applies.
+    #[serde(default = "default_session_max_messages")]
+    pub max_messages: usize,
+
+    /// What to do with history beyond `max_messages`.
     #[serde(default)]
-    pub window_allowlist: Vec<String>,
-    /// Optional X-axis boundary for coordinate-based actions
+    pub history_strategy: SessionHistoryStrategy,
+
+    /// Number of oldest messages folded into one summary per pass
+    /// (`history_strategy = "summarize"` only).
+    #[serde(default = "default_session_summarize_batch")]
+    pub summarize_batch: usize,
+
+    /// Context window (tokens) assumed for every model. `0` uses the built-in
+    /// per-model table.
     #[serde(default)]
-    pub max_coordinate_x: Option<i64>,
-    /// Optional Y-axis boundary for coordinate-based actions
+    pub context_tokens: usize,
+
+    /// Tokens kept free for the model's response when fitting history into
+    /// the context window.
+    #[serde(default = "default_session_response_reserve_tokens")]
+    pub response_reserve_tokens: usize,
+
+    /// Maximum pinned messages per session (`/pin`). Pins are never trimmed
+    /// or summarized. `0` disables pinning.
+    #[serde(default = "default_session_max_pinned")]
+    pub max_pinned: usize,
+
+    /// Token budgets for per-turn system prompt sections
+    /// (`[session.context_budgets]`).
     #[serde(default)]
-    pub max_coordinate_y: Option<i64>,
+    pub context_budgets: ContextBudgetsConfig,
+
+    /// Redis backend settings (`[session.redis]`).
+    #[serde(default)]
+    pub redis: SessionRedisConfig,
+
+    /// Minutes between maintenance passes on the SQLite store while
+    /// channels run: expired sessions are purged, the WAL is checkpointed
+    /// and free pages are vacuumed (`0` = only `zeroclaw db maintain`).
+    #[serde(default = "default_session_maintenance_interval_minutes")]
+    pub maintenance_interval_minutes: u64,
 }
 
-fn default_browser_computer_use_endpoint() -> String {
-    "http://127.0.0.1:8787/v1/actions".into()
+/// Token budgets for the sections of the per-turn channel system prompt.
+/// A section over its budget is cut with a marker; `0` means unlimited.
+/// The identity section (the startup prompt) is never cut.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ContextBudgetsConfig {
+    /// Runtime tool availability and the tool-call protocol. Unlimited by
+    /// default, since cutting it hides tool schemas from the model.
+    #[serde(default)]
+    pub tools: usize,
+
+    /// Channel delivery hints, execution visibility, and reply target.
+    #[serde(default = "default_context_budget_channel")]
+    pub channel: usize,
+
+    /// Pinned messages.
+    #[serde(default = "default_context_budget_memory")]
+    pub memory: usize,
+
+    /// Current date, time zone, and locale.
+    #[serde(default = "default_context_budget_time")]
+    pub time: usize,
 }
 
-fn default_browser_computer_use_timeout_ms() -> u64 {
-    15_000
+fn default_context_budget_channel() -> usize {
+    1_000
 }
 
-impl Default for BrowserComputerUseConfig {
+fn default_context_budget_memory() -> usize {
+    2_000
+}
+
+fn default_context_budget_time() -> usize {
+    100
+}
+
+impl Default for ContextBudgetsConfig {
     fn default() -> Self {
         Self {
-            endpoint: default_browser_computer_use_endpoint(),
-            api_key: None,
-            timeout_ms: default_browser_computer_use_timeout_ms(),
-            allow_remote_endpoint: false,
-            window_allowlist: Vec::new(),
-            max_coordinate_x: None,
-            max_coordinate_y: None,
+            tools: 0,
+            channel: default_context_budget_channel(),
+            memory: default_context_budget_memory(),
+            time: default_context_budget_time(),
         }
     }
 }
 
-/// Browser automation configuration (`[browser]` section).
+fn default_session_max_messages() -> usize {
+    50
+}
+
+fn default_session_summarize_batch() -> usize {
+    20
+}
+
+fn default_session_response_reserve_tokens() -> usize {
+    4096
+}
+
+fn default_session_max_pinned() -> usize {
+    10
+}
+
+fn default_session_maintenance_interval_minutes() -> u64 {
+    360
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            backend: SessionBackend::default(),
+            ttl_secs: 0,
+            max_messages: default_session_max_messages(),
+            history_strategy: SessionHistoryStrategy::default(),
+            summarize_batch: default_session_summarize_batch(),
+            context_tokens: 0,
+            response_reserve_tokens: default_session_response_reserve_tokens(),
+            max_pinned: default_session_max_pinned(),
+            context_budgets: ContextBudgetsConfig::default(),
+            redis: SessionRedisConfig::default(),
+            maintenance_interval_minutes: default_session_maintenance_interval_minutes(),
+        }
+    }
+}
+
+/// Channel attachment retention (`[attachments]` section).
 ///
-/// Controls the `browser_open` tool and browser automation backends.
+/// Files received through channels are stored under
+/// `<workspace>/attachments/<channel>/` and garbage-collected by age and by
+/// the total size of the store.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct BrowserConfig {
-    /// Enable `browser_open` tool (opens URLs in the system browser without scraping)
+pub struct AttachmentsConfig {
+    /// Delete attachments older than this many hours (`0` = keep forever).
+    #[serde(default = "default_attachments_max_age_hours")]
+    pub max_age_hours: u64,
+
+    /// Cap on the total size of all stored attachments, in MiB (`0` =
+    /// unlimited). The oldest files are deleted first.
+    #[serde(default = "default_attachments_max_total_mb")]
+    pub max_total_mb: u64,
+
+    /// Interval between background collections while channels run, in
+    /// minutes (`0` = only collect when a file is saved).
+    #[serde(default = "default_attachments_gc_interval_minutes")]
+    pub gc_interval_minutes: u64,
+}
+
+fn default_attachments_max_age_hours() -> u64 {
+    24 * 7
+}
+
+fn default_attachments_max_total_mb() -> u64 {
+    1024
+}
+
+fn default_attachments_gc_interval_minutes() -> u64 {
+    60
+}
+
+impl Default for AttachmentsConfig {
+    fn default() -> Self {
+        Self {
+            max_age_hours: default_attachments_max_age_hours(),
+            max_total_mb: default_attachments_max_total_mb(),
+            gc_interval_minutes: default_attachments_gc_interval_minutes(),
+        }
+    }
+}
+
+/// Language of user-facing runtime messages (`[i18n]` section).
+///
+/// Approval prompts, error notices and other messages the runtime itself
+/// sends come from a locale catalog. `en` and `zh-CN` are built in; files
+/// in `<workspace>/locales/<locale>.toml` override or add strings.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct I18nConfig {
+    /// Locale used when a channel has no entry in `channels`.
+    #[serde(default = "default_i18n_locale")]
+    pub locale: String,
+
+    /// Per-channel locale, keyed by channel name (e.g. `telegram = "zh-CN"`).
     #[serde(default)]
-    pub enabled: bool,
-    /// Allowed domains for `browser_open` (exact or subdomain match)
+    pub channels: HashMap<String, String>,
+
+    /// IANA time zone (e.g. `Europe/Berlin`) for principals without their
+    /// own. Unset uses the host's local time zone.
+    #[serde(default)]
+    pub timezone: Option<String>,
+
+    /// Time zone and locale per channel principal.
+    ///
+    /// Keys are `"<channel>:<sender>"`, `"<channel>:*"` or `"*:<sender>"`;
+    /// the most specific match wins, field by field. Principals without a
+    /// configured value use the `user_timezone` / `user_locale` entries of
+    /// their private memory tier when present.
+    #[serde(default)]
+    pub principals: HashMap<String, PrincipalLocaleConfig>,
+}
+
+/// Time zone and locale of one principal (`[i18n.principals."<channel>:<sender>"]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PrincipalLocaleConfig {
+    /// IANA time zone, e.g. `America/New_York`.
+    #[serde(default)]
+    pub timezone: Option<String>,
+
+    /// Locale tag, e.g. `zh-CN`.
+    #[serde(default)]
+    pub locale: Option<String>,
+}
+
+fn default_i18n_locale() -> String {
+    "en".into()
+}
+
+impl Default for I18nConfig {
+    fn default() -> Self {
+        Self {
+            locale: default_i18n_locale(),
+            channels: HashMap::new(),
+            timezone: None,
+            principals: HashMap::new(),
+        }
+    }
+}
+
+/// Push notification targets (`[notify]` section).
+///
+/// Each `[notify.targets.<name>]` entry is an ntfy topic, a Pushover user or
+/// a Gotify application. The `notify` tool, cron delivery
+/// (`channel = "notify"`, `to = "<name>"`) and heartbeat alerts send through
+/// these targets, so they reach a phone without any chat channel.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct NotifyConfig {
+    /// Target used when a sender names none. Unset uses the only target
+    /// when exactly one is configured.
+    #[serde(default)]
+    pub default_target: Option<String>,
+
+    /// Named push targets.
+    #[serde(default)]
+    pub targets: HashMap<String, NotifyTargetConfig>,
+}
+
+/// Push service of a [`NotifyTargetConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyService {
+    /// ntfy (`https://ntfy.sh` or a self-hosted server).
+    Ntfy,
+    /// Pushover (`https://api.pushover.net`).
+    Pushover,
+    /// A self-hosted Gotify server.
+    Gotify,
+}
+
+/// Notification priority, mapped onto each service's own scale.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+/// One push target (`[notify.targets.<name>]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NotifyTargetConfig {
+    pub service: NotifyService,
+
+    /// Server base URL. ntfy defaults to `https://ntfy.sh`; required for
+    /// Gotify; not used by Pushover.
+    #[serde(default)]
+    pub server: Option<String>,
+
+    /// ntfy topic (required for ntfy).
+    #[serde(default)]
+    pub topic: Option<String>,
+
+    /// ntfy access token, Pushover application token or Gotify application
+    /// token. Encrypted at rest.
+    #[serde(default)]
+    pub token: Option<String>,
+
+    /// Pushover user or group key (required for Pushover). Encrypted at rest.
+    #[serde(default)]
+    pub user_key: Option<String>,
+
+    /// Priority used when the sender does not set one.
+    #[serde(default)]
+    pub priority: NotifyPriority,
+}
+
+/// Redis session backend configuration (`[session.redis]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SessionRedisConfig {
+    /// Connection URL: `redis://[user:password@]host[:port][/db]`.
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Prefix prepended to every session key.
+    #[serde(default = "default_session_redis_key_prefix")]
+    pub key_prefix: String,
+
+    /// Maximum pooled connections.
+    #[serde(default = "default_session_redis_pool_size")]
+    pub pool_size: usize,
+
+    /// Connect and command timeout (seconds).
+    #[serde(default = "default_session_redis_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_session_redis_key_prefix() -> String {
+    "zeroclaw:session:".to_string()
+}
+
+fn default_session_redis_pool_size() -> usize {
+    4
+}
+
+fn default_session_redis_timeout_secs() -> u64 {
+    5
+}
+
+impl Default for SessionRedisConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            key_prefix: default_session_redis_key_prefix(),
+            pool_size: default_session_redis_pool_size(),
+            timeout_secs: default_session_redis_timeout_secs(),
+        }
+    }
+}
+
+// ── Secrets (encrypted credential store) ────────────────────────
+
+/// Storage backend for named secrets referenced as `secret://<name>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretBackend {
+    /// `~/.zeroclaw/secrets.json`, values sealed with the local secret key.
+    #[default]
+    File,
+    /// OS credential store (macOS Keychain via `security`, Linux Secret Service via `secret-tool`).
+    Keychain,
+}
+
+/// Secrets encryption configuration (`[secrets]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SecretsConfig {
+    /// Enable encryption for API keys and tokens in config.toml
+    #[serde(default = "default_true")]
+    pub encrypt: bool,
+    /// Where `zeroclaw secrets set` stores named secrets resolved from `secret://<name>`.
+    #[serde(default)]
+    pub backend: SecretBackend,
+}
+
+impl Default for SecretsConfig {
+    fn default() -> Self {
+        Self {
+            encrypt: true,
+            backend: SecretBackend::default(),
+        }
+    }
+}
+
+// ── Browser (friendly-service browsing only) ───────────────────
+
+/// Computer-use sidecar configuration (`[browser.computer_use]` section).
+///
+/// Delegates OS-level mouse, keyboard, and screenshot actions to a local sidecar.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BrowserComputerUseConfig {
+    /// Sidecar endpoint for computer-use actions (OS-level mouse/keyboard/screenshot)
+    #[serde(default = "default_browser_computer_use_endpoint")]
+    pub endpoint: String,
+    /// Optional bearer token for computer-use sidecar
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Per-action request timeout in milliseconds
+    #[serde(default = "default_browser_computer_use_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Allow remote/public endpoint for computer-use sidecar (default: false)
+    #[serde(default)]
+    pub allow_remote_endpoint: bool,
+    /// Optional window title/process allowlist forwarded to sidecar policy
+    #[serde(default)]
+    pub window_allowlist: Vec<String>,
+    /// Optional X-axis boundary for coordinate-based actions
+    #[serde(default)]
+    pub max_coordinate_x: Option<i64>,
+    /// Optional Y-axis boundary for coordinate-based actions
+    #[serde(default)]
+    pub max_coordinate_y: Option<i64>,
+}
+
+fn default_browser_computer_use_endpoint() -> String {
+    "http://127.0.0.1:8787/v1/actions".into()
+}
+
+fn default_browser_computer_use_timeout_ms() -> u64 {
+    15_000
+}
+
+impl Default for BrowserComputerUseConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: default_browser_computer_use_endpoint(),
+            api_key: None,
+            timeout_ms: default_browser_computer_use_timeout_ms(),
+            allow_remote_endpoint: false,
+            window_allowlist: Vec::new(),
+            max_coordinate_x: None,
+            max_coordinate_y: None,
+        }
+    }
+}
+
+/// Browser automation configuration (`[browser]` section).
+///
+/// Controls the `browser_open` tool and browser automation backends.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BrowserConfig {
+    /// Enable `browser_open` tool (opens URLs in the system browser without scraping)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Allowed domains for `browser_open` (exact or subdomain match)
     #[serde(default)]
     pub allowed_domains: Vec<String>,
     /// Browser for browser_open tool: "disable" | "brave" | "chrome" | "firefox" | "edge" | "msedge" | "default"
@@ -1543,6 +2283,53 @@ impl Default for BrowserConfig {
     }
 }
 
+// ── Shell sessions ──────────────────────────────────────────────
+
+/// Persistent shell sessions tool (`[shell_session]` section).
+///
+/// Keeps an interactive shell on a pseudo-terminal alive across tool calls,
+/// so `cd`, exported variables and an activated virtualenv carry over.
+/// Registered only on Unix hosts with shell access; the terminal comes from
+/// the system `script` utility.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ShellSessionConfig {
+    /// Register the `shell_session` tool
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Shell started in each session (program name or path, no arguments)
+    #[serde(default = "default_shell_session_shell")]
+    pub shell: String,
+    /// Close a session after this many seconds without a call
+    #[serde(default = "default_shell_session_idle_timeout_secs")]
+    pub idle_timeout_secs: u64,
+    /// Maximum open sessions
+    #[serde(default = "default_shell_session_max_sessions")]
+    pub max_sessions: usize,
+}
+
+impl Default for ShellSessionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            shell: default_shell_session_shell(),
+            idle_timeout_secs: default_shell_session_idle_timeout_secs(),
+            max_sessions: default_shell_session_max_sessions(),
+        }
+    }
+}
+
+fn default_shell_session_shell() -> String {
+    "sh".into()
+}
+
+fn default_shell_session_idle_timeout_secs() -> u64 {
+    600
+}
+
+fn default_shell_session_max_sessions() -> usize {
+    4
+}
+
 // ── HTTP request tool ───────────────────────────────────────────
 
 /// HTTP request tool configuration (`[http_request]` section).
@@ -2455,6 +3242,75 @@ impl Default for QdrantConfig {
     }
 }
 
+/// Expiry and decay policy for memory entries (`[memory.decay]`).
+///
+/// Entries expire `ttl_days[<category>]` days after they were created;
+/// categories without a TTL (by default `core`) never expire. Recall scores
+/// of non-core entries are halved every `half_life_days`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MemoryDecayConfig {
+    /// Enable expiry, recall decay, and background garbage collection.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Days to keep entries per category name (`core`, `daily`,
+    /// `conversation`, or a custom category). `0` or absent = keep forever.
+    #[serde(default = "default_memory_ttl_days")]
+    pub ttl_days: HashMap<String, u32>,
+    /// Recall score half-life in days for non-core entries. `0` disables decay.
+    #[serde(default = "default_memory_half_life_days")]
+    pub half_life_days: u32,
+    /// Minutes between garbage collection passes in the daemon.
+    #[serde(default = "default_memory_gc_interval_minutes")]
+    pub gc_interval_minutes: u64,
+}
+
+fn default_memory_ttl_days() -> HashMap<String, u32> {
+    HashMap::from([("daily".to_string(), 7), ("conversation".to_string(), 30)])
+}
+
+fn default_memory_half_life_days() -> u32 {
+    30
+}
+
+fn default_memory_gc_interval_minutes() -> u64 {
+    60
+}
+
+impl Default for MemoryDecayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_days: default_memory_ttl_days(),
+            half_life_days: default_memory_half_life_days(),
+            gc_interval_minutes: default_memory_gc_interval_minutes(),
+        }
+    }
+}
+
+/// Write rules for shared memory tiers (`[memory.access]`).
+///
+/// Channel senders always read and write their own principal tier and can
+/// read their channel's tier and the global tier. These flags control
+/// whether they may also write the shared tiers.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MemoryAccessConfig {
+    /// Allow senders to write memories shared with their whole channel.
+    #[serde(default = "default_true")]
+    pub channel_write: bool,
+    /// Allow senders to write memories shared with every channel.
+    #[serde(default)]
+    pub global_write: bool,
+}
+
+impl Default for MemoryAccessConfig {
+    fn default() -> Self {
+        Self {
+            channel_write: true,
+            global_write: false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct MemoryConfig {
@@ -2503,6 +3359,14 @@ pub struct MemoryConfig {
     /// Max tokens per chunk for document splitting
     #[serde(default = "default_chunk_size")]
     pub chunk_max_tokens: usize,
+    /// Keep each channel sender's memories in a private namespace: entries
+    /// they store and auto-save are only recalled into their own turns.
+    #[serde(default = "default_true")]
+    pub principal_isolation: bool,
+    /// After each channel reply, ask the model to extract durable facts from
+    /// the exchange and store them as core memories with their source.
+    #[serde(default)]
+    pub fact_extraction: bool,
 
     // ── Response Cache (saves tokens on repeated prompts) ──────
     /// Enable LLM response caching to avoid paying for duplicate prompts
@@ -2537,6 +3401,14 @@ pub struct MemoryConfig {
     /// Used when `backend = "qdrant"` or `backend = "sqlite_qdrant_hybrid"`.
     #[serde(default)]
     pub qdrant: QdrantConfig,
+
+    // ── Expiry and decay ───────────────────────────────────────
+    /// Per-category TTLs, recall decay, and garbage collection.
+    #[serde(default)]
+    pub decay: MemoryDecayConfig,
+    /// Write rules for the channel and global memory tiers.
+    #[serde(default)]
+    pub access: MemoryAccessConfig,
 }
 
 fn default_embedding_provider() -> String {
@@ -2599,6 +3471,8 @@ impl Default for MemoryConfig {
             min_relevance_score: default_min_relevance_score(),
             embedding_cache_size: default_cache_size(),
             chunk_max_tokens: default_chunk_size(),
+            principal_isolation: true,
+            fact_extraction: false,
             response_cache_enabled: false,
             response_cache_ttl_minutes: default_response_cache_ttl(),
             response_cache_max_entries: default_response_cache_max(),
@@ -2607,6 +3481,8 @@ impl Default for MemoryConfig {
             auto_hydrate: true,
             sqlite_open_timeout_secs: None,
             qdrant: QdrantConfig::default(),
+            decay: MemoryDecayConfig::default(),
+            access: MemoryAccessConfig::default(),
         }
     }
 }
@@ -2639,6 +3515,31 @@ pub struct ObservabilityConfig {
     /// Maximum entries retained when runtime_trace_mode = "rolling".
     #[serde(default = "default_runtime_trace_max_entries")]
     pub runtime_trace_max_entries: usize,
+
+    /// Write a JSON execution report per turn under `state/turn-reports/`.
+    #[serde(default)]
+    pub turn_reports: bool,
+
+    /// Most recent turn reports kept on disk; older ones are pruned.
+    #[serde(default = "default_turn_reports_max")]
+    pub turn_reports_max: usize,
+
+    /// Write a redacted incident report under `state/incidents/` when the
+    /// process panics or the daemon exits with an error.
+    #[serde(default = "default_true")]
+    pub incident_reports: bool,
+
+    /// Most recent incident reports kept on disk; older ones are pruned.
+    #[serde(default = "default_incident_reports_max")]
+    pub incident_reports_max: usize,
+
+    /// Channel notified of incidents (`telegram`, `slack`, `notify`, ...).
+    #[serde(default)]
+    pub incident_channel: Option<String>,
+
+    /// Recipient on `incident_channel`.
+    #[serde(default)]
+    pub incident_to: Option<String>,
 }
 
 impl Default for ObservabilityConfig {
@@ -2650,10 +3551,24 @@ impl Default for ObservabilityConfig {
             runtime_trace_mode: default_runtime_trace_mode(),
             runtime_trace_path: default_runtime_trace_path(),
             runtime_trace_max_entries: default_runtime_trace_max_entries(),
+            turn_reports: false,
+            turn_reports_max: default_turn_reports_max(),
+            incident_reports: true,
+            incident_reports_max: default_incident_reports_max(),
+            incident_channel: None,
+            incident_to: None,
         }
     }
 }
 
+fn default_turn_reports_max() -> usize {
+    500
+}
+
+fn default_incident_reports_max() -> usize {
+    20
+}
+
 fn default_runtime_trace_mode() -> String {
     "none".to_string()
 }
@@ -2830,11 +3745,13 @@ pub struct AutonomyConfig {
     #[serde(default)]
     pub shell_env_passthrough: Vec<String>,
 
-    /// Tools that never require approval (e.g. read-only tools).
+    /// Tools that never require approval (e.g. read-only tools). Entries are
+    /// tool names or globs such as `github_*`.
     #[serde(default = "default_auto_approve")]
     pub auto_approve: Vec<String>,
 
-    /// Tools that always require interactive approval, even after "Always".
+    /// Tools that always require interactive approval, even after "Always"
+    /// or a matching `auto_approve` entry. Entries are tool names or globs.
     #[serde(default = "default_always_ask")]
     pub always_ask: Vec<String>,
 
@@ -2844,6 +3761,22 @@ pub struct AutonomyConfig {
     #[serde(default)]
     pub allowed_roots: Vec<String>,
 
+    /// Per-tool filesystem scopes, keyed by tool name.
+    ///
+    /// A listed tool may only touch resolved paths under one of its roots, in
+    /// addition to the workspace/`allowed_roots` checks. Entries support the
+    /// same forms as `allowed_roots`. For `shell`, the first root also pins the
+    /// command working directory.
+    ///
+    /// Example: `file_write = ["output"]`, `shell = ["scratch"]`.
+    #[serde(default)]
+    pub tool_path_scopes: HashMap<String, Vec<String>>,
+
+    /// Shell command policy engine: allow/deny/critical rules and
+    /// deny-by-default command categories.
+    #[serde(default)]
+    pub command_policy: CommandPolicyConfig,
+
     /// Tools to exclude from non-CLI channels (e.g. Telegram, Discord).
     ///
     /// When a tool is listed here, non-CLI channels will not expose it to the
@@ -2865,6 +3798,25 @@ pub struct AutonomyConfig {
     #[serde(default)]
     pub non_cli_approval_approvers: Vec<String>,
 
+    /// Deepest delegation chain any sub-agent may start, on top of each
+    /// agent's own `max_depth`. Default: `3`.
+    #[serde(default = "default_max_delegation_depth")]
+    pub max_delegation_depth: u32,
+
+    /// Upper bound on tool-call iterations for one sub-agent run; an agent's
+    /// `max_iterations` is clamped to this. Default: `20`.
+    #[serde(default = "default_max_subagent_iterations")]
+    pub max_subagent_iterations: usize,
+
+    /// Upper bound on output tokens per sub-agent provider call; an agent's
+    /// `max_tokens` is clamped to this. `0` leaves sub-agents uncapped. Default: `0`.
+    #[serde(default)]
+    pub max_subagent_tokens: u32,
+
+    /// Maximum sub-agent tasks a single `delegate` call may run in parallel. Default: `4`.
+    #[serde(default = "default_max_parallel_subagents")]
+    pub max_parallel_subagents: usize,
+
     /// Natural-language handling mode for non-CLI approval-management commands.
     ///
     /// Values:
@@ -2885,22 +3837,69 @@ pub struct AutonomyConfig {
     #[serde(default)]
     pub non_cli_natural_language_approval_mode_by_channel:
         HashMap<String, NonCliNaturalLanguageApprovalMode>,
+
+    /// Autonomy level per channel principal, replacing `level` for the turns
+    /// of matching senders.
+    ///
+    /// Keys are `"<channel>:<sender>"`, `"<channel>:*"` or `"*:<sender>"`;
+    /// the most specific match wins.
+    ///
+    /// Example:
+    /// - `"telegram:alice" = "full"`
+    /// - `"slack:*" = "supervised"`
+    /// - `"github:*" = "readonly"`
+    #[serde(default)]
+    pub level_overrides: HashMap<String, AutonomyLevel>,
+
+    /// `auto_approve` / `always_ask` lists per channel, checked before the
+    /// global lists for calls from that channel.
+    ///
+    /// Keys are channel names (`cli` for the terminal agent). Example:
+    /// - `telegram = { always_ask = ["shell", "file_*"] }`
+    /// - `cli = { auto_approve = ["shell"] }`
+    #[serde(default)]
+    pub approval_policy_by_channel: HashMap<String, ChannelApprovalPolicy>,
 }
 
-fn default_auto_approve() -> Vec<String> {
-    vec!["file_read".into(), "memory_recall".into()]
+/// Approval lists for one channel (`[autonomy.approval_policy_by_channel.<channel>]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ChannelApprovalPolicy {
+    /// Tools (names or globs) this channel runs without a prompt.
+    #[serde(default)]
+    pub auto_approve: Vec<String>,
+    /// Tools (names or globs) this channel always prompts for.
+    #[serde(default)]
+    pub always_ask: Vec<String>,
 }
 
-fn default_always_ask() -> Vec<String> {
-    vec![]
+fn default_max_delegation_depth() -> u32 {
+    3
 }
 
-fn default_non_cli_excluded_tools() -> Vec<String> {
+fn default_max_subagent_iterations() -> usize {
+    20
+}
+
+fn default_max_parallel_subagents() -> usize {
+    4
+}
+
+fn default_auto_approve() -> Vec<String> {
+    vec!["file_read".into(), "memory_recall".into()]
+}
+
+fn default_always_ask() -> Vec<String> {
+    vec![]
+}
+
+fn default_non_cli_excluded_tools() -> Vec<String> {
     [
         "shell",
         "file_write",
         "file_edit",
         "git_operations",
+        "git_commit",
+        "git_branch",
         "browser",
         "browser_open",
         "http_request",
@@ -2916,6 +3915,7 @@ fn default_non_cli_excluded_tools() -> Vec<String> {
         "web_access_config",
         "model_routing_config",
         "pushover",
+        "notify",
         "composio",
         "delegate",
         "screenshot",
@@ -2983,10 +3983,102 @@ impl Default for AutonomyConfig {
             auto_approve: default_auto_approve(),
             always_ask: default_always_ask(),
             allowed_roots: Vec::new(),
+            tool_path_scopes: HashMap::new(),
+            command_policy: CommandPolicyConfig::default(),
             non_cli_excluded_tools: default_non_cli_excluded_tools(),
             non_cli_approval_approvers: Vec::new(),
+            max_delegation_depth: default_max_delegation_depth(),
+            max_subagent_iterations: default_max_subagent_iterations(),
+            max_subagent_tokens: 0,
+            max_parallel_subagents: default_max_parallel_subagents(),
             non_cli_natural_language_approval_mode: NonCliNaturalLanguageApprovalMode::default(),
             non_cli_natural_language_approval_mode_by_channel: HashMap::new(),
+            level_overrides: HashMap::new(),
+            approval_policy_by_channel: HashMap::new(),
+        }
+    }
+}
+
+/// Built-in shell command categories that can be denied or flagged critical.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandCategory {
+    /// System and language package installs (`apt install`, `pip install`, `npm install <pkg>`, ...).
+    PackageInstall,
+    /// Raw sockets, remote copies, and HTTP uploads (`nc`, `scp`, `curl -d`, ...).
+    NetworkExfiltration,
+    /// Privilege escalation (`sudo`, `su`, `doas`, `pkexec`, ...).
+    PrivilegeEscalation,
+    /// Deleting or overwriting data (`rm`, `dd`, `mkfs`, `git push --force`, ...).
+    Destructive,
+}
+
+/// What a matching command rule does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandRuleAction {
+    /// Permit the command even if it falls in a denied category.
+    Allow,
+    /// Reject the command outright.
+    Deny,
+    /// Escalate the command to the critical risk tier, which always requires
+    /// explicit approval regardless of autonomy level.
+    Critical,
+}
+
+/// A single shell command rule. All configured matchers must match a command
+/// segment for the rule to apply; at least one matcher is required.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CommandRuleConfig {
+    /// Action applied when the rule matches.
+    pub action: CommandRuleAction,
+    /// Glob over the program basename (for example `docker`, `kubectl*`).
+    #[serde(default)]
+    pub program: Option<String>,
+    /// Glob over the space-joined arguments (for example `push *`).
+    #[serde(default)]
+    pub args: Option<String>,
+    /// Regex over the whole command segment.
+    #[serde(default)]
+    pub regex: Option<String>,
+    /// Human-readable reason surfaced in denials and approval prompts.
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Shell command policy engine configuration (`[autonomy.command_policy]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CommandPolicyConfig {
+    /// Categories denied unless an `allow` rule matches.
+    #[serde(default = "default_denied_command_categories")]
+    pub deny_categories: Vec<CommandCategory>,
+    /// Categories escalated to the critical tier unless an `allow` rule
+    /// matches. They still pass the regular risk gates first.
+    #[serde(default = "default_critical_command_categories")]
+    pub critical_categories: Vec<CommandCategory>,
+    /// Ordered allow/deny/critical rules.
+    #[serde(default)]
+    pub rules: Vec<CommandRuleConfig>,
+}
+
+fn default_denied_command_categories() -> Vec<CommandCategory> {
+    vec![
+        CommandCategory::PackageInstall,
+        CommandCategory::NetworkExfiltration,
+        CommandCategory::PrivilegeEscalation,
+    ]
+}
+
+fn default_critical_command_categories() -> Vec<CommandCategory> {
+    vec![CommandCategory::Destructive]
+}
+
+impl Default for CommandPolicyConfig {
+    fn default() -> Self {
+        Self {
+            deny_categories: default_denied_command_categories(),
+            critical_categories: default_critical_command_categories(),
+            rules: Vec::new(),
         }
     }
 }
@@ -3369,6 +4461,30 @@ pub struct ReliabilityConfig {
     /// as provider-scoped remap chains during provider fallback.
     #[serde(default)]
     pub model_fallbacks: std::collections::HashMap<String, Vec<String>>,
+    /// Seconds a provider that exhausted its retries on transient errors
+    /// (429, 5xx, timeouts) is tried after the healthy ones. `0` disables.
+    #[serde(default = "default_provider_unhealthy_cooldown_secs")]
+    pub provider_unhealthy_cooldown_secs: u64,
+    /// Upper bound (ms) for the exponential provider retry delay.
+    #[serde(default = "default_provider_max_backoff_ms")]
+    pub provider_max_backoff_ms: u64,
+    /// Randomize retry delays (between half and the full backoff) so
+    /// concurrent sessions do not retry in lockstep.
+    #[serde(default = "default_true")]
+    pub provider_backoff_jitter: bool,
+    /// HTTP status codes worth retrying. Empty uses the built-in
+    /// classification (429, 408 and 5xx retry; other 4xx fail over at once).
+    #[serde(default)]
+    pub provider_retry_status_codes: Vec<u16>,
+    /// Consecutive exhausted-retry failures after which a provider's circuit
+    /// opens and it is skipped for `provider_unhealthy_cooldown_secs`.
+    /// `0` disables the breaker (failing providers are only demoted).
+    #[serde(default = "default_provider_circuit_breaker_threshold")]
+    pub provider_circuit_breaker_threshold: u32,
+    /// Per-provider overrides of the retry policy, keyed by provider name
+    /// as used in `default_provider` / `fallback_providers`.
+    #[serde(default)]
+    pub provider_policies: std::collections::HashMap<String, ProviderRetryPolicyConfig>,
     /// Initial backoff for channel/daemon restarts.
     #[serde(default = "default_channel_backoff_secs")]
     pub channel_initial_backoff_secs: u64,
@@ -3381,6 +4497,28 @@ pub struct ReliabilityConfig {
     /// Max retries for cron job execution attempts.
     #[serde(default = "default_scheduler_retries")]
     pub scheduler_retries: u32,
+    /// Seconds the daemon waits on SIGINT/SIGTERM for in-flight channel
+    /// turns and gateway requests to finish before cancelling them.
+    #[serde(default = "default_shutdown_grace_secs")]
+    pub shutdown_grace_secs: u64,
+    /// Record accepted channel messages on disk and resume the ones a
+    /// restart interrupted, with a notice to the sender. Redelivered
+    /// messages whose turn already finished get the stored reply instead.
+    #[serde(default = "default_true")]
+    pub resume_unfinished_tasks: bool,
+    /// Times an interrupted channel task is resumed before it is abandoned.
+    #[serde(default = "default_max_task_resumes")]
+    pub max_task_resumes: u32,
+    /// Record agent replies on disk before sending them and retry failed
+    /// sends in the background.
+    #[serde(default = "default_true")]
+    pub outbound_queue: bool,
+    /// Send attempts before a reply moves to the dead-letter list.
+    #[serde(default = "default_outbound_max_attempts")]
+    pub outbound_max_attempts: u32,
+    /// Delay before the first retry of a failed reply; doubles per attempt.
+    #[serde(default = "default_outbound_retry_backoff_secs")]
+    pub outbound_retry_backoff_secs: u64,
 }
 
 fn default_provider_retries() -> u32 {
@@ -3391,6 +4529,18 @@ fn default_provider_backoff_ms() -> u64 {
     500
 }
 
+fn default_provider_unhealthy_cooldown_secs() -> u64 {
+    60
+}
+
+fn default_provider_max_backoff_ms() -> u64 {
+    10_000
+}
+
+fn default_provider_circuit_breaker_threshold() -> u32 {
+    3
+}
+
 fn default_channel_backoff_secs() -> u64 {
     2
 }
@@ -3407,6 +4557,22 @@ fn default_scheduler_retries() -> u32 {
     2
 }
 
+fn default_shutdown_grace_secs() -> u64 {
+    30
+}
+
+fn default_max_task_resumes() -> u32 {
+    2
+}
+
+fn default_outbound_max_attempts() -> u32 {
+    6
+}
+
+fn default_outbound_retry_backoff_secs() -> u64 {
+    30
+}
+
 impl Default for ReliabilityConfig {
     fn default() -> Self {
         Self {
@@ -3415,14 +4581,45 @@ impl Default for ReliabilityConfig {
             fallback_providers: Vec::new(),
             api_keys: Vec::new(),
             model_fallbacks: std::collections::HashMap::new(),
+            provider_unhealthy_cooldown_secs: default_provider_unhealthy_cooldown_secs(),
+            provider_max_backoff_ms: default_provider_max_backoff_ms(),
+            provider_backoff_jitter: true,
+            provider_retry_status_codes: Vec::new(),
+            provider_circuit_breaker_threshold: default_provider_circuit_breaker_threshold(),
+            provider_policies: std::collections::HashMap::new(),
             channel_initial_backoff_secs: default_channel_backoff_secs(),
             channel_max_backoff_secs: default_channel_backoff_max_secs(),
             scheduler_poll_secs: default_scheduler_poll_secs(),
             scheduler_retries: default_scheduler_retries(),
+            shutdown_grace_secs: default_shutdown_grace_secs(),
+            resume_unfinished_tasks: true,
+            max_task_resumes: default_max_task_resumes(),
+            outbound_queue: true,
+            outbound_max_attempts: default_outbound_max_attempts(),
+            outbound_retry_backoff_secs: default_outbound_retry_backoff_secs(),
         }
     }
 }
 
+/// Retry policy override for one provider (`[reliability.provider_policies.<name>]`).
+///
+/// Unset fields inherit the global `[reliability]` values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ProviderRetryPolicyConfig {
+    /// Retries before failing over to the next provider.
+    #[serde(default)]
+    pub max_retries: Option<u32>,
+    /// Base backoff (ms) for retry delay.
+    #[serde(default)]
+    pub backoff_ms: Option<u64>,
+    /// Upper bound (ms) for retry delay.
+    #[serde(default)]
+    pub max_backoff_ms: Option<u64>,
+    /// HTTP status codes worth retrying for this provider.
+    #[serde(default)]
+    pub retry_status_codes: Option<Vec<u16>>,
+}
+
 // ── Scheduler ────────────────────────────────────────────────────
 
 /// Scheduler configuration for periodic task execution (`[scheduler]` section).
@@ -3533,6 +4730,54 @@ pub struct EmbeddingRouteConfig {
     pub api_key: Option<String>,
 }
 
+/// JSON Schema subset a provider accepts in tool parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolSchemaDialect {
+    /// Gemini: no `$ref`, formats, patterns or numeric/length bounds.
+    Gemini,
+    /// Anthropic: references are inlined.
+    Anthropic,
+    /// OpenAI: accepted as written.
+    Openai,
+    /// References and `additionalProperties` removed.
+    Conservative,
+}
+
+/// Tool-calling and context-window limits for matching provider/model pairs
+/// (`[[provider_capabilities]]`).
+///
+/// Unset fields keep the built-in value. When several entries match, the
+/// last one wins field by field.
+///
+/// ```toml
+/// [[provider_capabilities]]
+/// provider = "ollama"
+/// model = "qwen2.5*"
+/// max_tools = 24
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProviderCapabilityConfig {
+    /// Provider name (`openrouter`, `ollama`, `custom:<url>`, ...) or `*`.
+    pub provider: String,
+    /// Model name; a trailing `*` matches by prefix. Unset matches every model.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Most tools sent in one request; later-registered tools are dropped first.
+    #[serde(default)]
+    pub max_tools: Option<usize>,
+    /// Longest tool name; longer names are shortened with a hash suffix.
+    #[serde(default)]
+    pub max_tool_name_len: Option<usize>,
+    /// JSON Schema subset tool parameters are reduced to.
+    #[serde(default)]
+    pub schema: Option<ToolSchemaDialect>,
+    /// Context window in tokens; history is compacted before a request
+    /// that would not fit.
+    #[serde(default)]
+    pub context_window: Option<usize>,
+}
+
 // ── Query Classification ─────────────────────────────────────────
 
 /// Automatic query classification — classifies user messages by keyword/pattern
@@ -3587,6 +4832,49 @@ pub struct HeartbeatConfig {
     /// Optional delivery recipient/chat identifier (required when `target` is set).
     #[serde(default, alias = "recipient")]
     pub to: Option<String>,
+    /// Run a proactive check-in on each tick: review upcoming reminders,
+    /// memory follow-ups and `watch` resources, and message the user only when
+    /// something is actionable. Default: `false`.
+    #[serde(default)]
+    pub proactive: bool,
+    /// Resources the proactive check-in should look at (URLs, file paths, or
+    /// short descriptions such as "CI status of main").
+    #[serde(default)]
+    pub watch: Vec<String>,
+    /// Window during which the heartbeat stays silent.
+    #[serde(default)]
+    pub quiet_hours: Option<HeartbeatQuietHoursConfig>,
+    /// Additional delivery targets (`[[heartbeat.channels]]`), each of which
+    /// can be switched off individually.
+    #[serde(default)]
+    pub channels: Vec<HeartbeatChannelConfig>,
+}
+
+/// Quiet hours for heartbeat delivery (`[heartbeat.quiet_hours]`).
+///
+/// `start` and `end` are `HH:MM` in `tz`. A window where `start > end` wraps
+/// past midnight (e.g. `22:00`–`07:00`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HeartbeatQuietHoursConfig {
+    /// Start of the quiet window, `HH:MM`.
+    pub start: String,
+    /// End of the quiet window, `HH:MM` (exclusive).
+    pub end: String,
+    /// IANA timezone for `start`/`end`. Default: UTC.
+    #[serde(default)]
+    pub tz: Option<String>,
+}
+
+/// One heartbeat delivery target (`[[heartbeat.channels]]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HeartbeatChannelConfig {
+    /// Channel name (`telegram`, `discord`, `slack`, `mattermost`, `whatsapp`, ...).
+    pub channel: String,
+    /// Recipient/chat identifier on that channel.
+    pub to: String,
+    /// Deliver heartbeat output to this target. Default: `true`.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
 }
 
 impl Default for HeartbeatConfig {
@@ -3597,6 +4885,10 @@ impl Default for HeartbeatConfig {
             message: None,
             target: None,
             to: None,
+            proactive: false,
+            watch: Vec::new(),
+            quiet_hours: None,
+            channels: Vec::new(),
         }
     }
 }
@@ -3646,6 +4938,53 @@ pub struct CronConfig {
     /// Maximum number of historical cron run records to retain. Default: `50`.
     #[serde(default = "default_max_run_history")]
     pub max_run_history: u32,
+    /// Jobs declared in config (`[[cron.jobs]]`), synced into the cron store
+    /// when the scheduler starts.
+    #[serde(default)]
+    pub jobs: Vec<CronJobConfig>,
+}
+
+/// A scheduled job declared under `[[cron.jobs]]`.
+///
+/// Set exactly one schedule (`cron` or `every_secs`) and one action
+/// (`prompt`, `tool` or `command`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CronJobConfig {
+    /// Unique job name. The stored job id is `config:<name>`.
+    pub name: String,
+    /// Cron expression, e.g. `"0 9 * * 1-5"`.
+    #[serde(default)]
+    pub cron: Option<String>,
+    /// IANA timezone for `cron` (default UTC).
+    #[serde(default)]
+    pub tz: Option<String>,
+    /// Run every N seconds instead of on a cron expression.
+    #[serde(default)]
+    pub every_secs: Option<u64>,
+    /// Prompt to run through the agent.
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Tool to invoke directly, with `args` as its parameters.
+    #[serde(default)]
+    pub tool: Option<String>,
+    /// JSON arguments for `tool`.
+    #[serde(default)]
+    pub args: Option<serde_json::Value>,
+    /// Shell command to run.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Model override for `prompt` jobs.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Channel to deliver the output to (`slack`, `telegram`, `discord`, ...).
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Recipient on `channel`: channel id, chat id, or email address.
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Disable without removing the entry.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
 }
 
 fn default_max_run_history() -> u32 {
@@ -3657,10 +4996,524 @@ impl Default for CronConfig {
         Self {
             enabled: true,
             max_run_history: default_max_run_history(),
+            jobs: Vec::new(),
+        }
+    }
+}
+
+// ── Analytics ───────────────────────────────────────────────────
+
+/// Daily activity digest (`[analytics]` section).
+///
+/// Once a day the scheduler summarizes the previous UTC day from the usage
+/// ledger (`[cost] enabled = true`) per channel: turns, most used tools,
+/// approval decisions, failures and cost, and sends it to `channel` / `to`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AnalyticsConfig {
+    /// Send the daily digest. Default: `false`.
+    #[serde(default)]
+    pub digest_enabled: bool,
+    /// UTC hour (0-23) from which the previous day's digest is sent.
+    /// Default: `8`.
+    #[serde(default = "default_analytics_digest_hour")]
+    pub digest_hour: u8,
+    /// Channel that receives the digest (`telegram`, `slack`, `notify`, ...).
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Recipient on `channel`.
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Tools listed per channel. Default: `5`.
+    #[serde(default = "default_analytics_top_tools")]
+    pub top_tools: usize,
+}
+
+fn default_analytics_digest_hour() -> u8 {
+    8
+}
+
+fn default_analytics_top_tools() -> usize {
+    5
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            digest_enabled: false,
+            digest_hour: default_analytics_digest_hour(),
+            channel: None,
+            to: None,
+            top_tools: default_analytics_top_tools(),
+        }
+    }
+}
+
+// ── Feeds ───────────────────────────────────────────────────────
+
+/// RSS/Atom feed watcher (`[feeds]` section).
+///
+/// The scheduler polls each `[[feeds.sources]]` entry on its interval,
+/// remembers the items it has seen in `<workspace>/state/feeds.db`, and
+/// sends new items as a digest to the entry's `channel` / `to`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FeedsConfig {
+    /// Poll feeds from the scheduler. Default: `true`.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Minutes between polls of a feed without its own interval. Default: `60`.
+    #[serde(default = "default_feeds_interval_minutes")]
+    pub interval_minutes: u64,
+    /// Most items listed in one digest. Default: `10`.
+    #[serde(default = "default_feeds_max_items")]
+    pub max_items: usize,
+    /// Watched feeds.
+    #[serde(default)]
+    pub sources: Vec<FeedSourceConfig>,
+}
+
+/// One watched feed (`[[feeds.sources]]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FeedSourceConfig {
+    /// Unique feed name, used by the `feed_fetch` tool and in digests.
+    pub name: String,
+    /// RSS or Atom URL (`http://` or `https://`).
+    pub url: String,
+    /// Minutes between polls; unset uses `feeds.interval_minutes`.
+    #[serde(default)]
+    pub interval_minutes: Option<u64>,
+    /// Channel that receives digests (`telegram`, `slack`, `notify`, ...).
+    /// Without one, new items are only recorded.
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Recipient on `channel`.
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Only items whose title or summary contains one of these words
+    /// (case-insensitive). Empty matches every item.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Disable without removing the entry.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_feeds_interval_minutes() -> u64 {
+    60
+}
+
+fn default_feeds_max_items() -> usize {
+    10
+}
+
+impl Default for FeedsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: default_feeds_interval_minutes(),
+            max_items: default_feeds_max_items(),
+            sources: Vec::new(),
+        }
+    }
+}
+
+// ── Mailbox ─────────────────────────────────────────────────────
+
+/// Mailbox queried by the `email_search` / `email_read` tools (`[mailbox]`
+/// section).
+///
+/// Separate from `[channels_config.email]`: the agent can look things up in
+/// the mailbox without every message becoming a chat turn. Folders are
+/// opened read-only unless `mark_seen` is set.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MailboxConfig {
+    /// Register the mailbox tools. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// IMAP server hostname (TLS only).
+    #[serde(default)]
+    pub imap_host: String,
+    /// IMAP server port. Default: `993`.
+    #[serde(default = "default_mailbox_imap_port")]
+    pub imap_port: u16,
+    /// Login name.
+    #[serde(default)]
+    pub username: String,
+    /// Password or app password. Encrypted at rest.
+    #[serde(default)]
+    pub password: String,
+    /// Folders the tools may open; the first is the default. Default: `["INBOX"]`.
+    #[serde(default = "default_mailbox_folders")]
+    pub folders: Vec<String>,
+    /// Most messages one search returns. Default: `20`.
+    #[serde(default = "default_mailbox_max_results")]
+    pub max_results: usize,
+    /// Mark messages read by `email_read` as seen. Default: `false`.
+    #[serde(default)]
+    pub mark_seen: bool,
+}
+
+fn default_mailbox_imap_port() -> u16 {
+    993
+}
+
+fn default_mailbox_folders() -> Vec<String> {
+    vec!["INBOX".into()]
+}
+
+fn default_mailbox_max_results() -> usize {
+    20
+}
+
+impl Default for MailboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            imap_host: String::new(),
+            imap_port: default_mailbox_imap_port(),
+            username: String::new(),
+            password: String::new(),
+            folders: default_mailbox_folders(),
+            max_results: default_mailbox_max_results(),
+            mark_seen: false,
+        }
+    }
+}
+
+// ── Prometheus ──────────────────────────────────────────────────
+
+/// Prometheus endpoint for the `prom_query` tool (`[prometheus]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PrometheusConfig {
+    /// Register the `prom_query` tool. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Base URL of the Prometheus HTTP API, e.g. `http://localhost:9090`.
+    #[serde(default)]
+    pub url: String,
+    /// Bearer token sent with every query. Encrypted at rest.
+    #[serde(default)]
+    pub bearer_token: Option<String>,
+    /// Regexes a query must match (any of them). Empty allows every query.
+    #[serde(default)]
+    pub allowed_queries: Vec<String>,
+    /// Longest range a range query may cover, in hours. Default: `24`.
+    #[serde(default = "default_prometheus_max_range_hours")]
+    pub max_range_hours: u64,
+    /// Most series included in one answer. Default: `20`.
+    #[serde(default = "default_prometheus_max_series")]
+    pub max_series: usize,
+    /// Request timeout in seconds. Default: `15`.
+    #[serde(default = "default_prometheus_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_prometheus_max_range_hours() -> u64 {
+    24
+}
+
+fn default_prometheus_max_series() -> usize {
+    20
+}
+
+fn default_prometheus_timeout_secs() -> u64 {
+    15
+}
+
+impl Default for PrometheusConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: String::new(),
+            bearer_token: None,
+            allowed_queries: Vec::new(),
+            max_range_hours: default_prometheus_max_range_hours(),
+            max_series: default_prometheus_max_series(),
+            timeout_secs: default_prometheus_timeout_secs(),
+        }
+    }
+}
+
+// ── Knowledge base ──────────────────────────────────────────────
+
+/// Private documents the agent can search (`[kb]` section).
+///
+/// Documents are chunked and embedded with the `[memory]` embedding settings
+/// into `<workspace>/state/kb.db`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct KbConfig {
+    /// Register `kb_search` and inject matching passages into turns.
+    /// Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Files, directories (relative to the workspace or absolute) and
+    /// http(s) URLs that `zeroclaw kb ingest` reads when given no arguments.
+    #[serde(default)]
+    pub sources: Vec<String>,
+    /// Approximate tokens per chunk. Default: `400`.
+    #[serde(default = "default_kb_chunk_tokens")]
+    pub chunk_tokens: usize,
+    /// Add the best-matching passages to each user message. Default: `true`.
+    #[serde(default = "default_true")]
+    pub auto_inject: bool,
+    /// Token budget for injected passages. Default: `800`.
+    #[serde(default = "default_kb_context_tokens")]
+    pub context_tokens: usize,
+    /// Most passages considered per message. Default: `4`.
+    #[serde(default = "default_kb_top_k")]
+    pub top_k: usize,
+    /// Minimum relevance (0.0-1.0) for a passage to be injected.
+    /// Default: `0.35`.
+    #[serde(default = "default_kb_min_score")]
+    pub min_score: f64,
+}
+
+fn default_kb_chunk_tokens() -> usize {
+    400
+}
+
+fn default_kb_context_tokens() -> usize {
+    800
+}
+
+fn default_kb_top_k() -> usize {
+    4
+}
+
+fn default_kb_min_score() -> f64 {
+    0.35
+}
+
+impl Default for KbConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sources: Vec::new(),
+            chunk_tokens: default_kb_chunk_tokens(),
+            auto_inject: true,
+            context_tokens: default_kb_context_tokens(),
+            top_k: default_kb_top_k(),
+            min_score: default_kb_min_score(),
         }
     }
 }
 
+// ── Ticketing ───────────────────────────────────────────────────
+
+/// Where a [`JiraConfig`] instance is hosted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JiraDeployment {
+    /// Jira Cloud (`*.atlassian.net`): `email` + API token, REST API v3.
+    #[default]
+    Cloud,
+    /// Jira Server / Data Center: personal access token, REST API v2.
+    Server,
+}
+
+/// Jira site for the `jira_search` and `jira_create_issue` tools (`[jira]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JiraConfig {
+    /// Register the Jira tools. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Jira Cloud or Server / Data Center. Default: `cloud`.
+    #[serde(default)]
+    pub deployment: JiraDeployment,
+    /// Site URL, e.g. `https://example.atlassian.net`.
+    #[serde(default)]
+    pub base_url: String,
+    /// Account email (Jira Cloud only).
+    #[serde(default)]
+    pub email: Option<String>,
+    /// API token (Cloud) or personal access token (Server). Encrypted at rest.
+    #[serde(default)]
+    pub api_token: Option<String>,
+    /// Project keys the tools may search and file issues in. The first is the
+    /// default project.
+    #[serde(default)]
+    pub projects: Vec<String>,
+    /// Issue type used when the agent does not name one. Default: `"Task"`.
+    #[serde(default = "default_jira_issue_type")]
+    pub default_issue_type: String,
+    /// Most issues one search returns. Default: `20`.
+    #[serde(default = "default_ticketing_max_results")]
+    pub max_results: usize,
+}
+
+fn default_jira_issue_type() -> String {
+    "Task".into()
+}
+
+fn default_ticketing_max_results() -> usize {
+    20
+}
+
+impl Default for JiraConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            deployment: JiraDeployment::default(),
+            base_url: String::new(),
+            email: None,
+            api_token: None,
+            projects: Vec::new(),
+            default_issue_type: default_jira_issue_type(),
+            max_results: default_ticketing_max_results(),
+        }
+    }
+}
+
+/// Linear workspace for the `linear_search` and `linear_create_issue` tools
+/// (`[linear]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LinearConfig {
+    /// Register the Linear tools. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Personal API key. Encrypted at rest.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Team keys (e.g. `"ENG"`) the tools may search and file issues in. The
+    /// first is the default team.
+    #[serde(default)]
+    pub teams: Vec<String>,
+    /// Most issues one search returns. Default: `20`.
+    #[serde(default = "default_ticketing_max_results")]
+    pub max_results: usize,
+}
+
+impl Default for LinearConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            api_key: None,
+            teams: Vec::new(),
+            max_results: default_ticketing_max_results(),
+        }
+    }
+}
+
+/// Jira project and Linear team keys: an uppercase letter followed by
+/// uppercase letters, digits or `_`. The tools splice keys into JQL and
+/// GraphQL filters, so nothing else is accepted.
+fn is_tracker_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+// ── Document conversion ─────────────────────────────────────────
+
+/// Renderer that turns HTML into PDF for `convert_document`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PdfEngine {
+    /// Headless Chrome/Chromium when found, otherwise `wkhtmltopdf`.
+    #[default]
+    Auto,
+    /// Headless Chrome/Chromium (`--print-to-pdf`).
+    Chrome,
+    /// `wkhtmltopdf`.
+    Wkhtmltopdf,
+}
+
+/// External converters used by the `convert_document` tool
+/// (`[convert_document]` section).
+///
+/// Markdown, HTML and DOCX are converted with pandoc; PDF output is rendered
+/// from HTML by `pdf_engine`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ConvertDocumentConfig {
+    /// Register `convert_document`. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// pandoc executable. Default: `"pandoc"`.
+    #[serde(default = "default_pandoc_path")]
+    pub pandoc_path: String,
+    /// HTML to PDF renderer. Default: `auto`.
+    #[serde(default)]
+    pub pdf_engine: PdfEngine,
+    /// Chrome/Chromium executable; looked up on `PATH` when unset.
+    #[serde(default)]
+    pub chrome_path: Option<String>,
+    /// wkhtmltopdf executable. Default: `"wkhtmltopdf"`.
+    #[serde(default = "default_wkhtmltopdf_path")]
+    pub wkhtmltopdf_path: String,
+    /// Time limit for each converter run. Default: `60`.
+    #[serde(default = "default_convert_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_pandoc_path() -> String {
+    "pandoc".into()
+}
+
+fn default_wkhtmltopdf_path() -> String {
+    "wkhtmltopdf".into()
+}
+
+fn default_convert_timeout_secs() -> u64 {
+    60
+}
+
+impl Default for ConvertDocumentConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            pandoc_path: default_pandoc_path(),
+            pdf_engine: PdfEngine::default(),
+            chrome_path: None,
+            wkhtmltopdf_path: default_wkhtmltopdf_path(),
+            timeout_secs: default_convert_timeout_secs(),
+        }
+    }
+}
+
+// ── Personas ────────────────────────────────────────────────────
+
+/// One persona: how the agent presents itself and what it may use.
+///
+/// ```toml
+/// [personas.profiles.reviewer]
+/// system_prompt = "You review pull requests. Point out bugs before style."
+/// tone = "strict, terse"
+/// tools = ["file_read", "git_operations"]
+/// provider = "anthropic"
+/// model = "claude-sonnet-4-6"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PersonaConfig {
+    /// Instructions added to the system prompt while the persona is active.
+    #[serde(default)]
+    pub system_prompt: String,
+    /// Tone of the replies, e.g. `"casual, friendly"`.
+    #[serde(default)]
+    pub tone: Option<String>,
+    /// Tools the persona may use; unset allows every tool.
+    #[serde(default)]
+    pub tools: Option<Vec<String>>,
+    /// Provider for the persona's turns; requires `model`.
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Model for the persona's turns, on `provider` or the default provider.
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// Personas for channel conversations (`[personas]` section).
+///
+/// A chat uses the persona picked with `/persona <name>`, otherwise the one
+/// set for its channel in `by_channel`, otherwise none.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PersonasConfig {
+    /// Persona per channel name, e.g. `github = "reviewer"`.
+    #[serde(default)]
+    pub by_channel: HashMap<String, String>,
+    /// Personas by name.
+    #[serde(default)]
+    pub profiles: HashMap<String, PersonaConfig>,
+}
+
 // ── Tunnel ──────────────────────────────────────────────────────
 
 /// Tunnel configuration for exposing the gateway publicly (`[tunnel]` section).
@@ -3800,6 +5653,8 @@ pub struct ChannelsConfig {
     pub nostr: Option<NostrConfig>,
     /// ClawdTalk voice channel configuration.
     pub clawdtalk: Option<crate::channels::clawdtalk::ClawdTalkConfig>,
+    /// Message queue channel configuration (Redis Streams or NATS).
+    pub queue: Option<QueueConfig>,
     /// Base timeout in seconds for processing a single channel message (LLM + tools).
     /// Runtime uses this as a per-turn budget that scales with tool-loop depth
     /// (up to 4x, capped) so one slow/retried model call does not consume the
@@ -3892,6 +5747,10 @@ impl ChannelsConfig {
                 Box::new(ConfigWrapper::new(self.clawdtalk.as_ref())),
                 self.clawdtalk.is_some(),
             ),
+            (
+                Box::new(ConfigWrapper::new(self.queue.as_ref())),
+                self.queue.is_some(),
+            ),
         ]
     }
 
@@ -3933,6 +5792,7 @@ impl Default for ChannelsConfig {
             qq: None,
             nostr: None,
             clawdtalk: None,
+            queue: None,
             message_timeout_secs: default_channel_message_timeout_secs(),
         }
     }
@@ -4130,6 +5990,16 @@ pub struct SlackConfig {
     /// Group-chat trigger controls.
     #[serde(default)]
     pub group_reply: Option<GroupReplyConfig>,
+    /// Streaming mode for progressive response delivery via message edits.
+    #[serde(default)]
+    pub stream_mode: StreamMode,
+    /// Minimum interval (ms) between draft message edits to avoid rate limits.
+    #[serde(default = "default_draft_update_interval_ms")]
+    pub draft_update_interval_ms: u64,
+    /// Publish an App Home tab with status, recent messages, pending
+    /// approvals and per-user settings. Requires Socket Mode (`app_token`).
+    #[serde(default)]
+    pub app_home: bool,
 }
 
 impl ChannelConfig for SlackConfig {
@@ -4640,46 +6510,389 @@ impl FeishuConfig {
     }
 }
 
-// ── Security Config ─────────────────────────────────────────────────
+// ── Security Config ─────────────────────────────────────────────────
+
+/// Security configuration for sandboxing, resource limits, and audit logging
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+pub struct SecurityConfig {
+    /// Sandbox configuration
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+
+    /// Resource limits
+    #[serde(default)]
+    pub resources: ResourceLimitsConfig,
+
+    /// Audit logging configuration
+    #[serde(default)]
+    pub audit: AuditConfig,
+
+    /// OTP gating configuration for sensitive actions/domains.
+    #[serde(default)]
+    pub otp: OtpConfig,
+
+    /// Custom security role definitions used for user-level tool authorization.
+    #[serde(default)]
+    pub roles: Vec<SecurityRoleConfig>,
+
+    /// Emergency-stop state machine configuration.
+    #[serde(default)]
+    pub estop: EstopConfig,
+
+    /// Syscall anomaly detection profile for daemon shell/process execution.
+    #[serde(default)]
+    pub syscall_anomaly: SyscallAnomalyConfig,
+
+    /// Lightweight statistical filter for adversarial suffixes (opt-in).
+    #[serde(default)]
+    pub perplexity_filter: PerplexityFilterConfig,
+
+    /// Shared URL access policy for network-enabled tools.
+    #[serde(default)]
+    pub url_access: UrlAccessConfig,
+
+    /// Outbound credential-leak guard settings.
+    #[serde(default)]
+    pub leak_guard: LeakGuardConfig,
+
+    /// Prompt-injection heuristics for fetched/untrusted tool output.
+    #[serde(default)]
+    pub content_injection: ContentInjectionConfig,
+
+    /// Shared egress limits for network-capable tools.
+    #[serde(default)]
+    pub egress: EgressConfig,
+
+    /// Behavioral monitor for per-session tool-call rates and sequences.
+    #[serde(default)]
+    pub tool_anomaly: ToolAnomalyConfig,
+
+    /// Post-generation moderation of outbound channel replies.
+    #[serde(default)]
+    pub moderation: ModerationConfig,
+}
+
+/// What the tool-call anomaly monitor does when a threshold is crossed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolAnomalyAction {
+    /// Record the anomaly and keep going.
+    Log,
+    /// Pause the session: every further tool call needs explicit approval
+    /// until one is approved.
+    #[default]
+    Pause,
+}
+
+/// Per-session tool-call anomaly detection (`[security.tool_anomaly]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ToolAnomalyConfig {
+    /// Enable the tool-call behavioral monitor.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Action taken when an anomaly is detected.
+    #[serde(default)]
+    pub action: ToolAnomalyAction,
+
+    /// Rolling window for the call-rate check (seconds).
+    #[serde(default = "default_tool_anomaly_window_secs")]
+    pub window_secs: u64,
+
+    /// Tool calls allowed per session within `window_secs`.
+    #[serde(default = "default_tool_anomaly_max_calls_per_window")]
+    pub max_calls_per_window: u32,
+
+    /// Distinct paths read within `read_window_secs` after which a request to
+    /// a domain the session has not contacted before is flagged.
+    #[serde(default = "default_tool_anomaly_read_burst_paths")]
+    pub read_burst_paths: u32,
+
+    /// Rolling window for the read-then-egress check (seconds).
+    #[serde(default = "default_tool_anomaly_read_window_secs")]
+    pub read_window_secs: u64,
+
+    /// Tools whose `path` argument counts as a local read.
+    #[serde(default = "default_tool_anomaly_read_tools")]
+    pub read_tools: Vec<String>,
+
+    /// Tools whose `url` argument counts as outbound traffic.
+    #[serde(default = "default_tool_anomaly_egress_tools")]
+    pub egress_tools: Vec<String>,
+}
+
+fn default_tool_anomaly_window_secs() -> u64 {
+    60
+}
+
+fn default_tool_anomaly_max_calls_per_window() -> u32 {
+    40
+}
+
+fn default_tool_anomaly_read_burst_paths() -> u32 {
+    8
+}
+
+fn default_tool_anomaly_read_window_secs() -> u64 {
+    600
+}
+
+fn default_tool_anomaly_read_tools() -> Vec<String> {
+    ["file_read", "pdf_read", "docx_read", "image_info"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+fn default_tool_anomaly_egress_tools() -> Vec<String> {
+    ["http_request", "web_fetch", "browser_open", "browser"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+impl Default for ToolAnomalyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            action: ToolAnomalyAction::default(),
+            window_secs: default_tool_anomaly_window_secs(),
+            max_calls_per_window: default_tool_anomaly_max_calls_per_window(),
+            read_burst_paths: default_tool_anomaly_read_burst_paths(),
+            read_window_secs: default_tool_anomaly_read_window_secs(),
+            read_tools: default_tool_anomaly_read_tools(),
+            egress_tools: default_tool_anomaly_egress_tools(),
+        }
+    }
+}
+
+/// Egress rate limits shared by `web_fetch`, `http_request`, `browser`, and
+/// `browser_open` (`[security.egress]`). Allow/block lists live in
+/// `[security.url_access]`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct EgressConfig {
+    /// Default per-host request budget per minute across all tools (`0` = unlimited).
+    #[serde(default)]
+    pub rate_limit_per_minute: u32,
+
+    /// Per-domain overrides (requests per minute). A domain entry also covers
+    /// its subdomains and shares one budget across them; `0` = unlimited.
+    #[serde(default)]
+    pub domain_rate_limits: HashMap<String, u32>,
+}
+
+/// How tool output flagged by the content-injection guard is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentInjectionAction {
+    /// Remove the instruction-like lines and tool-call payloads.
+    Strip,
+    /// Keep the content but wrap it in an `<untrusted_content>` block with a
+    /// notice telling the model to treat it strictly as data.
+    #[default]
+    Quarantine,
+    /// Quarantine the content and require explicit approval for every further
+    /// tool call in the same turn.
+    Escalate,
+}
+
+/// Prompt-injection detection for content returned by fetch/read tools.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ContentInjectionConfig {
+    /// Enable scanning of tool output for instruction-like payloads.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Action applied when a payload is detected.
+    #[serde(default)]
+    pub action: ContentInjectionAction,
+
+    /// Tools whose output is treated as untrusted content.
+    #[serde(default = "default_content_injection_tools")]
+    pub tools: Vec<String>,
+}
+
+fn default_content_injection_tools() -> Vec<String> {
+    [
+        "web_fetch",
+        "web_search_tool",
+        "http_request",
+        "browser",
+        "file_read",
+        "pdf_read",
+        "docx_read",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+impl Default for ContentInjectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            action: ContentInjectionAction::default(),
+            tools: default_content_injection_tools(),
+        }
+    }
+}
+
+/// Outbound credential-leak guard configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LeakGuardConfig {
+    /// Trailing characters withheld from streamed (draft) updates until more
+    /// content arrives, so secrets split across chunks are still redacted.
+    #[serde(default = "default_leak_guard_stream_overlap_chars")]
+    pub stream_overlap_chars: usize,
+
+    /// Hold channel replies with detected credentials for review instead of
+    /// sending them redacted.
+    #[serde(default)]
+    pub quarantine: bool,
+
+    /// Seconds a quarantined reply waits for review before it is discarded.
+    #[serde(default = "default_leak_guard_quarantine_ttl_secs")]
+    pub quarantine_ttl_secs: u64,
+}
+
+fn default_leak_guard_stream_overlap_chars() -> usize {
+    crate::security::leak_detector::DEFAULT_STREAM_OVERLAP_CHARS
+}
+
+fn default_leak_guard_quarantine_ttl_secs() -> u64 {
+    3600
+}
+
+impl Default for LeakGuardConfig {
+    fn default() -> Self {
+        Self {
+            stream_overlap_chars: default_leak_guard_stream_overlap_chars(),
+            quarantine: false,
+            quarantine_ttl_secs: default_leak_guard_quarantine_ttl_secs(),
+        }
+    }
+}
+
+/// What reply moderation does with flagged content.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    /// Deliver the reply unchanged and record the finding.
+    Warn,
+    /// Replace the flagged text and deliver the rest.
+    Rewrite,
+    /// Withhold the reply and send `block_message` instead.
+    #[default]
+    Block,
+}
 
-/// Security configuration for sandboxing, resource limits, and audit logging
-#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
-pub struct SecurityConfig {
-    /// Sandbox configuration
+/// One regex rule of `[security.moderation]`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ModerationRuleConfig {
+    /// Regex matched against the reply.
+    pub pattern: String,
+
+    /// Action when the pattern matches.
     #[serde(default)]
-    pub sandbox: SandboxConfig,
+    pub action: ModerationAction,
 
-    /// Resource limits
+    /// Text substituted for each match with `action = "rewrite"`.
+    /// Defaults to `security.moderation.replacement`.
     #[serde(default)]
-    pub resources: ResourceLimitsConfig,
+    pub replacement: Option<String>,
+}
 
-    /// Audit logging configuration
+/// Outbound reply moderation (`[security.moderation]`).
+///
+/// Runs on the final channel reply before `on_message_sending` hooks: regex
+/// rules first, then an OpenAI-compatible moderation endpoint, then a
+/// classifier model reached through a `[[model_routes]]` hint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ModerationConfig {
+    /// Enable reply moderation.
     #[serde(default)]
-    pub audit: AuditConfig,
+    pub enabled: bool,
 
-    /// OTP gating configuration for sensitive actions/domains.
+    /// Regex rules, applied in order.
     #[serde(default)]
-    pub otp: OtpConfig,
+    pub rules: Vec<ModerationRuleConfig>,
 
-    /// Custom security role definitions used for user-level tool authorization.
+    /// OpenAI-compatible moderation endpoint
+    /// (e.g. `https://api.openai.com/v1/moderations`).
     #[serde(default)]
-    pub roles: Vec<SecurityRoleConfig>,
+    pub endpoint: Option<String>,
 
-    /// Emergency-stop state machine configuration.
+    /// Bearer token for `endpoint`. Encrypted at rest when secrets encryption
+    /// is enabled.
     #[serde(default)]
-    pub estop: EstopConfig,
+    pub endpoint_api_key: Option<String>,
 
-    /// Syscall anomaly detection profile for daemon shell/process execution.
+    /// Model sent to `endpoint`.
+    #[serde(default = "default_moderation_endpoint_model")]
+    pub endpoint_model: String,
+
+    /// `[[model_routes]]` hint of a classifier model asked to answer `SAFE`
+    /// or `UNSAFE: <reason>` for each reply.
     #[serde(default)]
-    pub syscall_anomaly: SyscallAnomalyConfig,
+    pub classifier_hint: Option<String>,
 
-    /// Lightweight statistical filter for adversarial suffixes (opt-in).
+    /// Action when the endpoint or classifier flags a reply.
     #[serde(default)]
-    pub perplexity_filter: PerplexityFilterConfig,
+    pub flagged_action: ModerationAction,
 
-    /// Shared URL access policy for network-enabled tools.
+    /// Replacement text for rewrites. A reply flagged by the endpoint or
+    /// classifier is replaced as a whole.
+    #[serde(default = "default_moderation_replacement")]
+    pub replacement: String,
+
+    /// Sent to the requester instead of a blocked reply.
+    #[serde(default = "default_moderation_block_message")]
+    pub block_message: String,
+
+    /// Timeout for endpoint and classifier checks (seconds).
+    #[serde(default = "default_moderation_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Block replies when the endpoint or classifier cannot be reached.
+    /// When false, the reply is delivered and the failure is logged.
     #[serde(default)]
-    pub url_access: UrlAccessConfig,
+    pub fail_closed: bool,
+}
+
+fn default_moderation_endpoint_model() -> String {
+    "omni-moderation-latest".into()
+}
+
+fn default_moderation_replacement() -> String {
+    "[removed by moderation]".into()
+}
+
+fn default_moderation_block_message() -> String {
+    "This reply was withheld by content moderation.".into()
+}
+
+fn default_moderation_timeout_secs() -> u64 {
+    10
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rules: Vec::new(),
+            endpoint: None,
+            endpoint_api_key: None,
+            endpoint_model: default_moderation_endpoint_model(),
+            classifier_hint: None,
+            flagged_action: ModerationAction::default(),
+            replacement: default_moderation_replacement(),
+            block_message: default_moderation_block_message(),
+            timeout_secs: default_moderation_timeout_secs(),
+            fail_closed: false,
+        }
+    }
 }
 
 /// Lightweight perplexity-style filter configuration.
@@ -5343,6 +7556,63 @@ impl ChannelConfig for NostrConfig {
     }
 }
 
+/// Message queue backend for [`QueueConfig`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueBackend {
+    /// Redis Streams, consumed through a consumer group.
+    #[default]
+    Redis,
+    /// NATS core subjects, consumed through a queue group.
+    Nats,
+}
+
+/// Message queue channel configuration (`[channels_config.queue]`).
+///
+/// Consumes messages from a Redis stream or NATS subject and publishes
+/// replies back, so zeroclaw can work as an agent worker behind existing
+/// event-driven infrastructure.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct QueueConfig {
+    #[serde(default)]
+    pub backend: QueueBackend,
+    /// `redis://[user:password@]host[:port][/db]` or
+    /// `nats://[user:password@|token@]host[:port]`.
+    pub url: String,
+    /// Stream key (Redis) or subject (NATS) messages are read from.
+    pub inbound: String,
+    /// Stream key or subject replies go to when a message names no `reply_to`.
+    pub outbound: String,
+    /// Redis consumer group or NATS queue group; workers in the same group
+    /// share the inbound messages.
+    #[serde(default = "default_queue_group")]
+    pub group: String,
+    /// Consumer name within the Redis group. Unacknowledged messages are
+    /// redelivered to the consumer with the same name after a restart.
+    #[serde(default = "default_queue_consumer")]
+    pub consumer: String,
+    /// Allowed `sender` values. Empty = deny all, "*" = allow all.
+    #[serde(default)]
+    pub allowed_senders: Vec<String>,
+}
+
+fn default_queue_group() -> String {
+    "zeroclaw".into()
+}
+
+fn default_queue_consumer() -> String {
+    "zeroclaw-1".into()
+}
+
+impl ChannelConfig for QueueConfig {
+    fn name() -> &'static str {
+        "Queue"
+    }
+    fn desc() -> &'static str {
+        "Redis Streams / NATS worker"
+    }
+}
+
 pub fn default_nostr_relays() -> Vec<String> {
     vec![
         "wss://relay.damus.io".to_string(),
@@ -5363,6 +7633,8 @@ impl Default for Config {
         Self {
             workspace_dir: zeroclaw_dir.join("workspace"),
             config_path: zeroclaw_dir.join("config.toml"),
+            secret_refs: Vec::new(),
+            layers: Default::default(),
             api_key: None,
             api_url: None,
             default_provider: Some("openrouter".to_string()),
@@ -5382,8 +7654,18 @@ impl Default for Config {
             skills: SkillsConfig::default(),
             model_routes: Vec::new(),
             embedding_routes: Vec::new(),
+            provider_capabilities: Vec::new(),
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
+            feeds: FeedsConfig::default(),
+            analytics: AnalyticsConfig::default(),
+            mailbox: MailboxConfig::default(),
+            prometheus: PrometheusConfig::default(),
+            jira: JiraConfig::default(),
+            linear: LinearConfig::default(),
+            kb: KbConfig::default(),
+            convert_document: ConvertDocumentConfig::default(),
+            personas: PersonasConfig::default(),
             goal_loop: GoalLoopConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
@@ -5392,7 +7674,12 @@ impl Default for Config {
             gateway: GatewayConfig::default(),
             composio: ComposioConfig::default(),
             secrets: SecretsConfig::default(),
+            session: SessionConfig::default(),
+            attachments: AttachmentsConfig::default(),
+            i18n: I18nConfig::default(),
+            notify: NotifyConfig::default(),
             browser: BrowserConfig::default(),
+            shell_session: ShellSessionConfig::default(),
             http_request: HttpRequestConfig::default(),
             multimodal: MultimodalConfig::default(),
             web_fetch: WebFetchConfig::default(),
@@ -5412,6 +7699,7 @@ impl Default for Config {
             agents_ipc: AgentsIpcConfig::default(),
             mcp: McpConfig::default(),
             model_support_vision: None,
+            model_support_tools: None,
             wasm: WasmConfig::default(),
         }
     }
@@ -5605,6 +7893,48 @@ pub(crate) async fn resolve_runtime_dirs_for_onboarding() -> Result<(PathBuf, Pa
     Ok((config_dir, workspace_dir))
 }
 
+/// Resolve the config directory and `[secrets]` settings without loading the
+/// full config, so `zeroclaw secrets` works even while a reference is unset.
+pub(crate) async fn load_secrets_settings() -> Result<(PathBuf, SecretsConfig)> {
+    let (zeroclaw_dir, _) = resolve_runtime_dirs_for_onboarding().await?;
+    let config_path = zeroclaw_dir.join("config.toml");
+    if !config_path.exists() {
+        return Ok((zeroclaw_dir, SecretsConfig::default()));
+    }
+    let contents = fs::read_to_string(&config_path)
+        .await
+        .context("Failed to read config file")?;
+    let doc: toml::Table = toml::from_str(&contents).context("Failed to parse config file")?;
+    let secrets = secrets_section(&doc)?;
+    Ok((zeroclaw_dir, secrets))
+}
+
+fn secrets_section(doc: &toml::Table) -> Result<SecretsConfig> {
+    Ok(doc
+        .get("secrets")
+        .cloned()
+        .map(toml::Value::try_into::<SecretsConfig>)
+        .transpose()
+        .context("Invalid [secrets] section")?
+        .unwrap_or_default())
+}
+
+/// Replace `secret://` references in raw config text with their values.
+fn resolve_config_secret_refs(
+    zeroclaw_dir: &Path,
+    contents: String,
+) -> Result<(String, Vec<crate::security::vault::ResolvedSecretRef>)> {
+    if !contents.contains(crate::security::SECRET_REF_PREFIX) {
+        return Ok((contents, Vec::new()));
+    }
+    let mut doc: toml::Table = toml::from_str(&contents).context("Failed to parse config file")?;
+    let secrets = secrets_section(&doc)?;
+    let vault = crate::security::SecretVault::new(zeroclaw_dir, &secrets);
+    let refs = vault.resolve_document(&mut doc)?;
+    let resolved = toml::to_string(&doc).context("Failed to re-serialize resolved config")?;
+    Ok((resolved, refs))
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum ConfigResolutionSource {
     EnvConfigDir,
@@ -5918,6 +8248,9 @@ fn decrypt_channel_secrets(
             "config.channels_config.clawdtalk.webhook_secret",
         )?;
     }
+    if let Some(ref mut queue) = channels.queue {
+        decrypt_secret(store, &mut queue.url, "config.channels_config.queue.url")?;
+    }
     Ok(())
 }
 
@@ -6080,6 +8413,9 @@ fn encrypt_channel_secrets(
             "config.channels_config.clawdtalk.webhook_secret",
         )?;
     }
+    if let Some(ref mut queue) = channels.queue {
+        encrypt_secret(store, &mut queue.url, "config.channels_config.queue.url")?;
+    }
     Ok(())
 }
 
@@ -6236,110 +8572,7 @@ impl Config {
                 }
             }
 
-            let contents = fs::read_to_string(&config_path)
-                .await
-                .context("Failed to read config file")?;
-
-            // Track ignored/unknown config keys to warn users about silent misconfigurations
-            // (e.g., using [providers.ollama] which doesn't exist instead of top-level api_url)
-            let mut ignored_paths: Vec<String> = Vec::new();
-            let mut config: Config = serde_ignored::deserialize(
-                toml::de::Deserializer::parse(&contents).context("Failed to parse config file")?,
-                |path| {
-                    ignored_paths.push(path.to_string());
-                },
-            )
-            .context("Failed to deserialize config file")?;
-
-            // Warn about each unknown config key
-            for path in ignored_paths {
-                tracing::warn!(
-                    "Unknown config key ignored: \"{}\". Check config.toml for typos or deprecated options.",
-                    path
-                );
-            }
-            // Set computed paths that are skipped during serialization
-            config.config_path = config_path.clone();
-            config.workspace_dir = workspace_dir;
-            let store = crate::security::SecretStore::new(&zeroclaw_dir, config.secrets.encrypt);
-            decrypt_optional_secret(&store, &mut config.api_key, "config.api_key")?;
-            decrypt_optional_secret(
-                &store,
-                &mut config.transcription.api_key,
-                "config.transcription.api_key",
-            )?;
-            decrypt_optional_secret(
-                &store,
-                &mut config.composio.api_key,
-                "config.composio.api_key",
-            )?;
-            decrypt_optional_secret(
-                &store,
-                &mut config.proxy.http_proxy,
-                "config.proxy.http_proxy",
-            )?;
-            decrypt_optional_secret(
-                &store,
-                &mut config.proxy.https_proxy,
-                "config.proxy.https_proxy",
-            )?;
-            decrypt_optional_secret(
-                &store,
-                &mut config.proxy.all_proxy,
-                "config.proxy.all_proxy",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.browser.computer_use.api_key,
-                "config.browser.computer_use.api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.web_search.brave_api_key,
-                "config.web_search.brave_api_key",
-            )?;
-            decrypt_optional_secret(
-                &store,
-                &mut config.web_search.perplexity_api_key,
-                "config.web_search.perplexity_api_key",
-            )?;
-            decrypt_optional_secret(
-                &store,
-                &mut config.web_search.exa_api_key,
-                "config.web_search.exa_api_key",
-            )?;
-            decrypt_optional_secret(
-                &store,
-                &mut config.web_search.jina_api_key,
-                "config.web_search.jina_api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.storage.provider.config.db_url,
-                "config.storage.provider.config.db_url",
-            )?;
-            decrypt_vec_secrets(
-                &store,
-                &mut config.reliability.api_keys,
-                "config.reliability.api_keys",
-            )?;
-            decrypt_vec_secrets(
-                &store,
-                &mut config.gateway.paired_tokens,
-                "config.gateway.paired_tokens",
-            )?;
-
-            for agent in config.agents.values_mut() {
-                decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
-            }
-
-            decrypt_channel_secrets(&store, &mut config.channels_config)?;
-
-            config.apply_env_overrides();
-            config.validate()?;
+            let config = Self::load_from_file(&config_path, workspace_dir).await?;
             tracing::info!(
                 path = %config.config_path.display(),
                 workspace = %config.workspace_dir.display(),
@@ -6374,6 +8607,172 @@ impl Config {
         }
     }
 
+    /// Read, decrypt and validate the config file at `config_path`, with
+    /// environment overrides applied. Used at startup and when the daemon
+    /// reloads a changed config file.
+    pub async fn load_from_file(config_path: &Path, workspace_dir: PathBuf) -> Result<Self> {
+        let zeroclaw_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+        let contents = fs::read_to_string(config_path)
+            .await
+            .context("Failed to read config file")?;
+        let (contents, layers) = crate::config::layers::apply_layers(
+            zeroclaw_dir,
+            contents,
+            crate::config::layers::active_profile().as_deref(),
+            crate::skills::config_defaults(&workspace_dir),
+        )?;
+        let (contents, secret_refs) = resolve_config_secret_refs(zeroclaw_dir, contents)?;
+
+        // Track ignored/unknown config keys to warn users about silent misconfigurations
+        // (e.g., using [providers.ollama] which doesn't exist instead of top-level api_url)
+        let mut ignored_paths: Vec<String> = Vec::new();
+        let mut config: Config = serde_ignored::deserialize(
+            toml::de::Deserializer::parse(&contents).context("Failed to parse config file")?,
+            |path| {
+                ignored_paths.push(path.to_string());
+            },
+        )
+        .context("Failed to deserialize config file")?;
+
+        // Warn about each unknown config key
+        for path in ignored_paths {
+            tracing::warn!(
+                "Unknown config key ignored: \"{}\". Check config.toml for typos or deprecated options.",
+                path
+            );
+        }
+        // Set computed paths that are skipped during serialization
+        config.config_path = config_path.to_path_buf();
+        config.workspace_dir = workspace_dir;
+        config.secret_refs = secret_refs;
+        config.layers = layers;
+        let store = crate::security::SecretStore::new(zeroclaw_dir, config.secrets.encrypt);
+        decrypt_optional_secret(&store, &mut config.api_key, "config.api_key")?;
+        decrypt_optional_secret(
+            &store,
+            &mut config.transcription.api_key,
+            "config.transcription.api_key",
+        )?;
+        decrypt_optional_secret(
+            &store,
+            &mut config.composio.api_key,
+            "config.composio.api_key",
+        )?;
+        decrypt_optional_secret(
+            &store,
+            &mut config.proxy.http_proxy,
+            "config.proxy.http_proxy",
+        )?;
+        decrypt_optional_secret(
+            &store,
+            &mut config.proxy.https_proxy,
+            "config.proxy.https_proxy",
+        )?;
+        decrypt_optional_secret(
+            &store,
+            &mut config.proxy.all_proxy,
+            "config.proxy.all_proxy",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut config.browser.computer_use.api_key,
+            "config.browser.computer_use.api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut config.web_search.brave_api_key,
+            "config.web_search.brave_api_key",
+        )?;
+        decrypt_optional_secret(
+            &store,
+            &mut config.security.moderation.endpoint_api_key,
+            "config.security.moderation.endpoint_api_key",
+        )?;
+        decrypt_optional_secret(
+            &store,
+            &mut config.web_search.perplexity_api_key,
+            "config.web_search.perplexity_api_key",
+        )?;
+        decrypt_optional_secret(
+            &store,
+            &mut config.web_search.exa_api_key,
+            "config.web_search.exa_api_key",
+        )?;
+        decrypt_optional_secret(
+            &store,
+            &mut config.web_search.jina_api_key,
+            "config.web_search.jina_api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut config.storage.provider.config.db_url,
+            "config.storage.provider.config.db_url",
+        )?;
+        decrypt_optional_secret(
+            &store,
+            &mut config.session.redis.url,
+            "config.session.redis.url",
+        )?;
+        decrypt_vec_secrets(
+            &store,
+            &mut config.reliability.api_keys,
+            "config.reliability.api_keys",
+        )?;
+        decrypt_vec_secrets(
+            &store,
+            &mut config.gateway.paired_tokens,
+            "config.gateway.paired_tokens",
+        )?;
+        decrypt_vec_secrets(
+            &store,
+            &mut config.gateway.api_keys,
+            "config.gateway.api_keys",
+        )?;
+        for route in &mut config.gateway.auth.routes {
+            decrypt_vec_secrets(
+                &store,
+                &mut route.api_keys,
+                "config.gateway.auth.routes.*.api_keys",
+            )?;
+        }
+
+        for agent in config.agents.values_mut() {
+            decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
+        }
+
+        decrypt_secret(
+            &store,
+            &mut config.mailbox.password,
+            "config.mailbox.password",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut config.prometheus.bearer_token,
+            "config.prometheus.bearer_token",
+        )?;
+        decrypt_optional_secret(&store, &mut config.jira.api_token, "config.jira.api_token")?;
+        decrypt_optional_secret(&store, &mut config.linear.api_key, "config.linear.api_key")?;
+
+        for target in config.notify.targets.values_mut() {
+            decrypt_optional_secret(&store, &mut target.token, "config.notify.targets.*.token")?;
+            decrypt_optional_secret(
+                &store,
+                &mut target.user_key,
+                "config.notify.targets.*.user_key",
+            )?;
+        }
+
+        decrypt_channel_secrets(&store, &mut config.channels_config)?;
+
+        config.apply_env_overrides();
+        config.validate()?;
+        Ok(config)
+    }
+
     fn normalize_reasoning_level_override(raw: Option<&str>, source: &str) -> Option<String> {
         let value = raw?.trim();
         if value.is_empty() {
@@ -6561,11 +8960,99 @@ impl Config {
         if self.gateway.host.trim().is_empty() {
             anyhow::bail!("gateway.host must not be empty");
         }
+        let validate_ip_allowlist = |field: &str, entries: &[String]| -> Result<()> {
+            for (i, entry) in entries.iter().enumerate() {
+                let parsed = if entry.contains('/') {
+                    parse_cidr_notation(entry).map(|_| ())
+                } else {
+                    entry
+                        .trim()
+                        .parse::<IpAddr>()
+                        .map(|_| ())
+                        .map_err(anyhow::Error::from)
+                };
+                parsed.with_context(|| {
+                    format!("{field}[{i}] is not a valid IP address or CIDR: {entry}")
+                })?;
+            }
+            Ok(())
+        };
+        validate_ip_allowlist("gateway.auth.ip_allowlist", &self.gateway.auth.ip_allowlist)?;
+        if self.gateway.auth.client_cert_header.trim().is_empty() {
+            anyhow::bail!("gateway.auth.client_cert_header must not be empty");
+        }
+        let requires_client_cert = self.gateway.auth.require_client_cert
+            || self
+                .gateway
+                .auth
+                .routes
+                .iter()
+                .any(|route| route.require_client_cert);
+        if self.gateway.auth.enabled
+            && requires_client_cert
+            && !self.gateway.trust_forwarded_headers
+        {
+            anyhow::bail!(
+                "gateway.auth.require_client_cert needs gateway.trust_forwarded_headers = true: \
+                 the client_cert_header is only trustworthy when set by a proxy"
+            );
+        }
+        for (i, route) in self.gateway.auth.routes.iter().enumerate() {
+            if !route.path.starts_with('/') {
+                anyhow::bail!("gateway.auth.routes[{i}].path must start with '/'");
+            }
+            validate_ip_allowlist(
+                &format!("gateway.auth.routes[{i}].ip_allowlist"),
+                &route.ip_allowlist,
+            )?;
+        }
+        if self.gateway.limits.max_body_bytes == 0 {
+            anyhow::bail!("gateway.limits.max_body_bytes must be greater than 0");
+        }
+        if self.gateway.limits.timeout_secs == 0 {
+            anyhow::bail!("gateway.limits.timeout_secs must be greater than 0");
+        }
+        for (i, route) in self.gateway.limits.routes.iter().enumerate() {
+            if !route.path.starts_with('/') {
+                anyhow::bail!("gateway.limits.routes[{i}].path must start with '/'");
+            }
+            if route.max_body_bytes == Some(0) {
+                anyhow::bail!("gateway.limits.routes[{i}].max_body_bytes must be greater than 0");
+            }
+            if route.timeout_secs == Some(0) {
+                anyhow::bail!("gateway.limits.routes[{i}].timeout_secs must be greater than 0");
+            }
+        }
+        for (name, tenant) in &self.gateway.tenants {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                anyhow::bail!(
+                    "gateway.tenants.{name}: tenant names may only contain letters, digits, '-' and '_'"
+                );
+            }
+            if tenant.config_dir.as_os_str().is_empty() {
+                anyhow::bail!("gateway.tenants.{name}.config_dir must not be empty");
+            }
+            if self.config_path.parent() == Some(tenant.config_dir.as_path()) {
+                anyhow::bail!(
+                    "gateway.tenants.{name}.config_dir must differ from the main config directory"
+                );
+            }
+        }
 
         // Autonomy
         if self.autonomy.max_actions_per_hour == 0 {
             anyhow::bail!("autonomy.max_actions_per_hour must be greater than 0");
         }
+        if self.autonomy.max_subagent_iterations == 0 {
+            anyhow::bail!("autonomy.max_subagent_iterations must be greater than 0");
+        }
+        if self.autonomy.max_parallel_subagents == 0 {
+            anyhow::bail!("autonomy.max_parallel_subagents must be greater than 0");
+        }
         for (i, env_name) in self.autonomy.shell_env_passthrough.iter().enumerate() {
             if !is_valid_env_var_name(env_name) {
                 anyhow::bail!(
@@ -6593,6 +9080,79 @@ impl Config {
                 );
             }
         }
+        for (i, rule) in self.autonomy.command_policy.rules.iter().enumerate() {
+            if rule.program.is_none() && rule.args.is_none() && rule.regex.is_none() {
+                anyhow::bail!(
+                    "autonomy.command_policy.rules[{i}] must set at least one of program, args, or regex"
+                );
+            }
+            for (field, pattern) in [("program", &rule.program), ("args", &rule.args)] {
+                if let Some(pattern) = pattern {
+                    if let Err(e) = glob::Pattern::new(pattern) {
+                        anyhow::bail!(
+                            "autonomy.command_policy.rules[{i}].{field} is not a valid glob: {e}"
+                        );
+                    }
+                }
+            }
+            if let Some(pattern) = &rule.regex {
+                if let Err(e) = regex::Regex::new(pattern) {
+                    anyhow::bail!("autonomy.command_policy.rules[{i}].regex is invalid: {e}");
+                }
+            }
+        }
+        let mut approval_lists = vec![
+            ("auto_approve".to_string(), &self.autonomy.auto_approve),
+            ("always_ask".to_string(), &self.autonomy.always_ask),
+        ];
+        for (channel, policy) in &self.autonomy.approval_policy_by_channel {
+            if channel.trim().is_empty() {
+                anyhow::bail!("autonomy.approval_policy_by_channel keys must not be empty");
+            }
+            approval_lists.push((
+                format!("approval_policy_by_channel.{channel}.auto_approve"),
+                &policy.auto_approve,
+            ));
+            approval_lists.push((
+                format!("approval_policy_by_channel.{channel}.always_ask"),
+                &policy.always_ask,
+            ));
+        }
+        for (field, entries) in approval_lists {
+            for (i, entry) in entries.iter().enumerate() {
+                if entry.trim().is_empty() {
+                    anyhow::bail!("autonomy.{field}[{i}] must not be empty");
+                }
+                if entry.trim() != entry {
+                    anyhow::bail!("autonomy.{field}[{i}] has surrounding whitespace: {entry:?}");
+                }
+                if crate::approval::is_tool_pattern(entry) {
+                    if let Err(e) = glob::Pattern::new(entry) {
+                        anyhow::bail!("autonomy.{field}[{i}] is not a valid glob: {e}");
+                    }
+                }
+            }
+        }
+        for key in self.autonomy.level_overrides.keys() {
+            let valid = key.split_once(':').is_some_and(|(channel, principal)| {
+                !channel.trim().is_empty()
+                    && !principal.trim().is_empty()
+                    && !(channel == "*" && principal == "*")
+            });
+            if !valid {
+                anyhow::bail!(
+                    "autonomy.level_overrides key {key:?} is invalid; expected \"<channel>:<sender>\", \"<channel>:*\" or \"*:<sender>\" (use autonomy.level for everyone)"
+                );
+            }
+        }
+        for (tool_name, roots) in &self.autonomy.tool_path_scopes {
+            if roots.is_empty() {
+                anyhow::bail!("autonomy.tool_path_scopes.{tool_name} must list at least one root");
+            }
+            if roots.iter().any(|root| root.trim().is_empty()) {
+                anyhow::bail!("autonomy.tool_path_scopes.{tool_name} contains an empty root");
+            }
+        }
 
         // Security OTP / estop
         if self.security.otp.token_ttl_secs == 0 {
@@ -6832,6 +9392,12 @@ impl Config {
                 );
             }
         }
+        if self.security.tool_anomaly.window_secs == 0 {
+            anyhow::bail!("security.tool_anomaly.window_secs must be greater than 0");
+        }
+        if self.security.tool_anomaly.read_window_secs == 0 {
+            anyhow::bail!("security.tool_anomaly.read_window_secs must be greater than 0");
+        }
         if self.security.perplexity_filter.perplexity_threshold <= 1.0 {
             anyhow::bail!(
                 "security.perplexity_filter.perplexity_threshold must be greater than 1.0"
@@ -6848,6 +9414,31 @@ impl Config {
                 "security.perplexity_filter.symbol_ratio_threshold must be between 0.0 and 1.0"
             );
         }
+        if self.security.leak_guard.stream_overlap_chars < 16 {
+            anyhow::bail!("security.leak_guard.stream_overlap_chars must be at least 16");
+        }
+        for (i, rule) in self.security.moderation.rules.iter().enumerate() {
+            if let Err(e) = regex::Regex::new(&rule.pattern) {
+                anyhow::bail!("security.moderation.rules[{i}].pattern is invalid: {e}");
+            }
+        }
+        if let Some(endpoint) = self.security.moderation.endpoint.as_deref() {
+            if !endpoint.starts_with("https://") && !endpoint.starts_with("http://") {
+                anyhow::bail!("security.moderation.endpoint must be an http(s) URL");
+            }
+        }
+        if self
+            .security
+            .moderation
+            .classifier_hint
+            .as_deref()
+            .is_some_and(|hint| !self.model_routes.iter().any(|route| route.hint == hint))
+        {
+            anyhow::bail!("security.moderation.classifier_hint must match a [[model_routes]] hint");
+        }
+        if self.security.moderation.timeout_secs == 0 {
+            anyhow::bail!("security.moderation.timeout_secs must be greater than 0");
+        }
 
         // Browser
         if normalize_browser_open_choice(&self.browser.browser_open).is_none() {
@@ -6906,6 +9497,105 @@ impl Config {
             anyhow::bail!("web_search.timeout_secs must be greater than 0");
         }
 
+        // Session storage
+        if self.session.max_messages < 2 {
+            anyhow::bail!("session.max_messages must be at least 2");
+        }
+        if self.session.history_strategy == SessionHistoryStrategy::Summarize
+            && (self.session.summarize_batch < 2
+                || self.session.summarize_batch >= self.session.max_messages)
+        {
+            anyhow::bail!(
+                "session.summarize_batch must be at least 2 and less than session.max_messages"
+            );
+        }
+        if self.session.context_tokens > 0
+            && self.session.response_reserve_tokens >= self.session.context_tokens
+        {
+            anyhow::bail!(
+                "session.response_reserve_tokens must be less than session.context_tokens"
+            );
+        }
+        if self.session.backend == SessionBackend::Redis {
+            if self
+                .session
+                .redis
+                .url
+                .as_deref()
+                .is_none_or(|url| url.trim().is_empty())
+            {
+                anyhow::bail!("session.redis.url is required when session.backend = \"redis\"");
+            }
+            if self.session.redis.pool_size == 0 {
+                anyhow::bail!("session.redis.pool_size must be greater than 0");
+            }
+            if self.session.redis.timeout_secs == 0 {
+                anyhow::bail!("session.redis.timeout_secs must be greater than 0");
+            }
+        }
+
+        if let Some(queue) = &self.channels_config.queue {
+            let scheme = match queue.backend {
+                QueueBackend::Redis => "redis://",
+                QueueBackend::Nats => "nats://",
+            };
+            if !queue.url.trim().starts_with(scheme) {
+                anyhow::bail!("channels_config.queue.url must start with {scheme}");
+            }
+            if queue.inbound.trim().is_empty() || queue.outbound.trim().is_empty() {
+                anyhow::bail!("channels_config.queue.inbound and outbound must not be empty");
+            }
+            if queue.group.trim().is_empty() || queue.consumer.trim().is_empty() {
+                anyhow::bail!("channels_config.queue.group and consumer must not be empty");
+            }
+        }
+
+        // Push notifications
+        for (name, target) in &self.notify.targets {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                anyhow::bail!(
+                    "notify.targets.{name}: names may only contain letters, digits, '-' and '_'"
+                );
+            }
+            let set =
+                |value: &Option<String>| value.as_deref().is_some_and(|v| !v.trim().is_empty());
+            let missing = match target.service {
+                NotifyService::Ntfy => (!set(&target.topic)).then_some("topic"),
+                NotifyService::Pushover => {
+                    if set(&target.token) {
+                        (!set(&target.user_key)).then_some("user_key")
+                    } else {
+                        Some("token")
+                    }
+                }
+                NotifyService::Gotify => {
+                    if set(&target.server) {
+                        (!set(&target.token)).then_some("token")
+                    } else {
+                        Some("server")
+                    }
+                }
+            };
+            if let Some(field) = missing {
+                anyhow::bail!("notify.targets.{name}.{field} is required for this service");
+            }
+            if let Some(server) = target.server.as_deref() {
+                let server = server.trim();
+                if !server.starts_with("https://") && !server.starts_with("http://") {
+                    anyhow::bail!("notify.targets.{name}.server must be an http(s) URL");
+                }
+            }
+        }
+        if let Some(default) = &self.notify.default_target {
+            if !self.notify.targets.contains_key(default) {
+                anyhow::bail!("notify.default_target {default:?} is not a configured target");
+            }
+        }
+
         // Scheduler
         if self.scheduler.max_concurrent == 0 {
             anyhow::bail!("scheduler.max_concurrent must be greater than 0");
@@ -6913,6 +9603,313 @@ impl Config {
         if self.scheduler.max_tasks == 0 {
             anyhow::bail!("scheduler.max_tasks must be greater than 0");
         }
+        let mut cron_job_names = std::collections::HashSet::new();
+        for (i, job) in self.cron.jobs.iter().enumerate() {
+            let name = job.name.trim();
+            if name.is_empty() {
+                anyhow::bail!("cron.jobs[{i}].name must not be empty");
+            }
+            if !cron_job_names.insert(name) {
+                anyhow::bail!("cron.jobs[{i}].name '{name}' is used by another job");
+            }
+            match (&job.cron, job.every_secs) {
+                (Some(_), None) | (None, Some(1..)) => {}
+                (None, Some(0)) => {
+                    anyhow::bail!("cron.jobs[{i}].every_secs must be greater than 0")
+                }
+                _ => anyhow::bail!("cron.jobs[{i}] must set exactly one of cron or every_secs"),
+            }
+            let actions = [
+                job.prompt.is_some(),
+                job.tool.is_some(),
+                job.command.is_some(),
+            ];
+            if actions.iter().filter(|set| **set).count() != 1 {
+                anyhow::bail!("cron.jobs[{i}] must set exactly one of prompt, tool or command");
+            }
+            if job.args.is_some() && job.tool.is_none() {
+                anyhow::bail!("cron.jobs[{i}].args requires tool");
+            }
+            if job.channel.is_some() != job.to.is_some() {
+                anyhow::bail!("cron.jobs[{i}] must set channel and to together");
+            }
+        }
+
+        // Feeds
+        if self.feeds.interval_minutes == 0 || self.feeds.max_items == 0 {
+            anyhow::bail!("feeds.interval_minutes and feeds.max_items must be greater than 0");
+        }
+        let mut feed_names = std::collections::HashSet::new();
+        for (i, feed) in self.feeds.sources.iter().enumerate() {
+            let name = feed.name.trim();
+            if name.is_empty() {
+                anyhow::bail!("feeds.sources[{i}].name must not be empty");
+            }
+            if !feed_names.insert(name) {
+                anyhow::bail!("feeds.sources[{i}].name '{name}' is used by another feed");
+            }
+            let url = feed.url.trim();
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                anyhow::bail!("feeds.sources[{i}].url must be an http(s) URL");
+            }
+            if feed.interval_minutes == Some(0) {
+                anyhow::bail!("feeds.sources[{i}].interval_minutes must be greater than 0");
+            }
+            if feed.channel.is_some() != feed.to.is_some() {
+                anyhow::bail!("feeds.sources[{i}] must set channel and to together");
+            }
+        }
+
+        // Observability
+        if self.observability.incident_reports_max == 0 {
+            anyhow::bail!("observability.incident_reports_max must be greater than 0");
+        }
+        if self.observability.incident_channel.is_some() != self.observability.incident_to.is_some()
+        {
+            anyhow::bail!(
+                "observability.incident_channel and observability.incident_to must be set together"
+            );
+        }
+
+        // Shell sessions
+        let shell = self.shell_session.shell.trim();
+        if shell.is_empty()
+            || !shell
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '.' | '_' | '-' | '+'))
+        {
+            anyhow::bail!(
+                "shell_session.shell must be a program name or path without arguments or quotes"
+            );
+        }
+        if self.shell_session.idle_timeout_secs == 0 {
+            anyhow::bail!("shell_session.idle_timeout_secs must be greater than 0");
+        }
+        if self.shell_session.max_sessions == 0 {
+            anyhow::bail!("shell_session.max_sessions must be greater than 0");
+        }
+
+        // Analytics
+        if self.analytics.digest_hour > 23 {
+            anyhow::bail!("analytics.digest_hour must be between 0 and 23");
+        }
+        if self.analytics.top_tools == 0 {
+            anyhow::bail!("analytics.top_tools must be greater than 0");
+        }
+        if self.analytics.channel.is_some() != self.analytics.to.is_some() {
+            anyhow::bail!("analytics.channel and analytics.to must be set together");
+        }
+        if self.analytics.digest_enabled {
+            if self.analytics.channel.is_none() {
+                anyhow::bail!(
+                    "analytics.digest_enabled requires analytics.channel and analytics.to"
+                );
+            }
+            if !self.cost.enabled {
+                anyhow::bail!("analytics.digest_enabled requires cost.enabled = true (the digest reads the usage ledger)");
+            }
+        }
+
+        // Mailbox
+        if self.mailbox.enabled {
+            if self.mailbox.imap_host.trim().is_empty() || self.mailbox.username.trim().is_empty() {
+                anyhow::bail!("mailbox.imap_host and mailbox.username are required when mailbox.enabled = true");
+            }
+            if self
+                .mailbox
+                .folders
+                .iter()
+                .all(|folder| folder.trim().is_empty())
+            {
+                anyhow::bail!("mailbox.folders must list at least one folder");
+            }
+            if self.mailbox.max_results == 0 {
+                anyhow::bail!("mailbox.max_results must be greater than 0");
+            }
+        }
+
+        // Prometheus
+        if self.prometheus.enabled {
+            let url = self.prometheus.url.trim();
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                anyhow::bail!(
+                    "prometheus.url must be an http(s) URL when prometheus.enabled = true"
+                );
+            }
+            if self.prometheus.max_range_hours == 0
+                || self.prometheus.max_series == 0
+                || self.prometheus.timeout_secs == 0
+            {
+                anyhow::bail!(
+                    "prometheus.max_range_hours, max_series and timeout_secs must be greater than 0"
+                );
+            }
+        }
+        for (i, pattern) in self.prometheus.allowed_queries.iter().enumerate() {
+            if let Err(e) = regex::Regex::new(pattern) {
+                anyhow::bail!("prometheus.allowed_queries[{i}] is not a valid regex: {e}");
+            }
+        }
+
+        // Knowledge base
+        if self.kb.chunk_tokens == 0 || self.kb.top_k == 0 {
+            anyhow::bail!("kb.chunk_tokens and kb.top_k must be greater than 0");
+        }
+        if !(0.0..=1.0).contains(&self.kb.min_score) {
+            anyhow::bail!("kb.min_score must be between 0.0 and 1.0");
+        }
+        for (i, source) in self.kb.sources.iter().enumerate() {
+            if source.trim().is_empty() {
+                anyhow::bail!("kb.sources[{i}] must not be empty");
+            }
+        }
+
+        // Document conversion
+        if self.convert_document.enabled {
+            if self.convert_document.pandoc_path.trim().is_empty() {
+                anyhow::bail!("convert_document.pandoc_path must not be empty");
+            }
+            if self.convert_document.timeout_secs == 0 {
+                anyhow::bail!("convert_document.timeout_secs must be greater than 0");
+            }
+        }
+
+        // Personas
+        for (name, persona) in &self.personas.profiles {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                anyhow::bail!(
+                    "personas.profiles.{name:?}: names may only use letters, digits, '_' and '-'"
+                );
+            }
+            if matches!(
+                name.to_ascii_lowercase().as_str(),
+                "off" | "none" | "default"
+            ) {
+                anyhow::bail!("personas.profiles.{name}: the name is reserved by /persona");
+            }
+            if persona.provider.is_some() && persona.model.is_none() {
+                anyhow::bail!("personas.profiles.{name}.provider requires model");
+            }
+            if persona
+                .tools
+                .iter()
+                .flatten()
+                .any(|tool| tool.trim().is_empty())
+            {
+                anyhow::bail!("personas.profiles.{name}.tools must not contain empty names");
+            }
+        }
+        for (channel, name) in &self.personas.by_channel {
+            if !self.personas.profiles.contains_key(name) {
+                anyhow::bail!("personas.by_channel.{channel} names unknown persona {name:?}");
+            }
+        }
+
+        // Ticketing
+        if self.jira.enabled {
+            let url = self.jira.base_url.trim();
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                anyhow::bail!("jira.base_url must be an http(s) URL when jira.enabled = true");
+            }
+            if self
+                .jira
+                .api_token
+                .as_deref()
+                .is_none_or(|t| t.trim().is_empty())
+            {
+                anyhow::bail!("jira.api_token is required when jira.enabled = true");
+            }
+            if self.jira.deployment == JiraDeployment::Cloud
+                && self
+                    .jira
+                    .email
+                    .as_deref()
+                    .is_none_or(|e| e.trim().is_empty())
+            {
+                anyhow::bail!("jira.email is required for deployment = \"cloud\"");
+            }
+            if self.jira.projects.is_empty() {
+                anyhow::bail!("jira.projects must list at least one project key");
+            }
+            if self.jira.max_results == 0 {
+                anyhow::bail!("jira.max_results must be greater than 0");
+            }
+        }
+        for (i, key) in self.jira.projects.iter().enumerate() {
+            if !is_tracker_key(key) {
+                anyhow::bail!("jira.projects[{i}] must be a project key like \"OPS\", got '{key}'");
+            }
+        }
+        if self.linear.enabled {
+            if self
+                .linear
+                .api_key
+                .as_deref()
+                .is_none_or(|k| k.trim().is_empty())
+            {
+                anyhow::bail!("linear.api_key is required when linear.enabled = true");
+            }
+            if self.linear.teams.is_empty() {
+                anyhow::bail!("linear.teams must list at least one team key");
+            }
+            if self.linear.max_results == 0 {
+                anyhow::bail!("linear.max_results must be greater than 0");
+            }
+        }
+        for (i, key) in self.linear.teams.iter().enumerate() {
+            if !is_tracker_key(key) {
+                anyhow::bail!("linear.teams[{i}] must be a team key like \"ENG\", got '{key}'");
+            }
+        }
+
+        // Heartbeat
+        if let Some(quiet) = &self.heartbeat.quiet_hours {
+            for (key, value) in [("start", &quiet.start), ("end", &quiet.end)] {
+                if chrono::NaiveTime::parse_from_str(value.trim(), "%H:%M").is_err() {
+                    anyhow::bail!("heartbeat.quiet_hours.{key} must be HH:MM, got '{value}'");
+                }
+            }
+            if let Some(tz) = &quiet.tz {
+                if tz.trim().parse::<chrono_tz::Tz>().is_err() {
+                    anyhow::bail!("heartbeat.quiet_hours.tz '{tz}' is not a valid IANA timezone");
+                }
+            }
+        }
+        for (i, target) in self.heartbeat.channels.iter().enumerate() {
+            if target.channel.trim().is_empty() || target.to.trim().is_empty() {
+                anyhow::bail!("heartbeat.channels[{i}] must set channel and to");
+            }
+        }
+
+        // I18n
+        if let Some(tz) = &self.i18n.timezone {
+            if tz.trim().parse::<chrono_tz::Tz>().is_err() {
+                anyhow::bail!("i18n.timezone '{tz}' is not a valid IANA timezone");
+            }
+        }
+        for (key, principal) in &self.i18n.principals {
+            let valid = key.split_once(':').is_some_and(|(channel, sender)| {
+                !channel.trim().is_empty()
+                    && !sender.trim().is_empty()
+                    && !(channel == "*" && sender == "*")
+            });
+            if !valid {
+                anyhow::bail!(
+                    "i18n.principals key {key:?} is invalid; expected \"<channel>:<sender>\", \"<channel>:*\" or \"*:<sender>\" (use i18n.timezone for everyone)"
+                );
+            }
+            if let Some(tz) = &principal.timezone {
+                if tz.trim().parse::<chrono_tz::Tz>().is_err() {
+                    anyhow::bail!(
+                        "i18n.principals.{key:?}.timezone '{tz}' is not a valid IANA timezone"
+                    );
+                }
+            }
+        }
 
         // Model routes
         for (i, route) in self.model_routes.iter().enumerate() {
@@ -6980,6 +9977,22 @@ impl Config {
             }
         }
 
+        // Provider capabilities
+        for (i, entry) in self.provider_capabilities.iter().enumerate() {
+            if entry.provider.trim().is_empty() {
+                anyhow::bail!("provider_capabilities[{i}].provider must not be empty");
+            }
+            if entry.max_tools == Some(0) {
+                anyhow::bail!("provider_capabilities[{i}].max_tools must be greater than 0");
+            }
+            if entry.max_tool_name_len.is_some_and(|len| len < 16) {
+                anyhow::bail!("provider_capabilities[{i}].max_tool_name_len must be at least 16");
+            }
+            if entry.context_window.is_some_and(|tokens| tokens < 1_024) {
+                anyhow::bail!("provider_capabilities[{i}].context_window must be at least 1024");
+            }
+        }
+
         for (profile_key, profile) in &self.model_providers {
             let profile_name = profile_key.trim();
             if profile_name.is_empty() {
@@ -7308,6 +10321,18 @@ impl Config {
             }
         }
 
+        // Tool-calling override: ZEROCLAW_MODEL_SUPPORT_TOOLS or MODEL_SUPPORT_TOOLS
+        if let Ok(flag) = std::env::var("ZEROCLAW_MODEL_SUPPORT_TOOLS")
+            .or_else(|_| std::env::var("MODEL_SUPPORT_TOOLS"))
+        {
+            let normalized = flag.trim().to_ascii_lowercase();
+            match normalized.as_str() {
+                "1" | "true" | "yes" | "on" => self.model_support_tools = Some(true),
+                "0" | "false" | "no" | "off" => self.model_support_tools = Some(false),
+                _ => {}
+            }
+        }
+
         // Web search enabled: ZEROCLAW_WEB_SEARCH_ENABLED or WEB_SEARCH_ENABLED
         if let Ok(enabled) = std::env::var("ZEROCLAW_WEB_SEARCH_ENABLED")
             .or_else(|_| std::env::var("WEB_SEARCH_ENABLED"))
@@ -7768,6 +10793,11 @@ impl Config {
             &mut config_to_save.web_search.brave_api_key,
             "config.web_search.brave_api_key",
         )?;
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.security.moderation.endpoint_api_key,
+            "config.security.moderation.endpoint_api_key",
+        )?;
         encrypt_optional_secret(
             &store,
             &mut config_to_save.web_search.perplexity_api_key,
@@ -7789,6 +10819,11 @@ impl Config {
             &mut config_to_save.storage.provider.config.db_url,
             "config.storage.provider.config.db_url",
         )?;
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.session.redis.url,
+            "config.session.redis.url",
+        )?;
         encrypt_vec_secrets(
             &store,
             &mut config_to_save.reliability.api_keys,
@@ -7799,15 +10834,70 @@ impl Config {
             &mut config_to_save.gateway.paired_tokens,
             "config.gateway.paired_tokens",
         )?;
+        encrypt_vec_secrets(
+            &store,
+            &mut config_to_save.gateway.api_keys,
+            "config.gateway.api_keys",
+        )?;
+        for route in &mut config_to_save.gateway.auth.routes {
+            encrypt_vec_secrets(
+                &store,
+                &mut route.api_keys,
+                "config.gateway.auth.routes.*.api_keys",
+            )?;
+        }
+
+        for agent in config_to_save.agents.values_mut() {
+            encrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
+        }
+
+        encrypt_secret(
+            &store,
+            &mut config_to_save.mailbox.password,
+            "config.mailbox.password",
+        )?;
+
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.prometheus.bearer_token,
+            "config.prometheus.bearer_token",
+        )?;
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.jira.api_token,
+            "config.jira.api_token",
+        )?;
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.linear.api_key,
+            "config.linear.api_key",
+        )?;
 
-        for agent in config_to_save.agents.values_mut() {
-            encrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
+        for target in config_to_save.notify.targets.values_mut() {
+            encrypt_optional_secret(&store, &mut target.token, "config.notify.targets.*.token")?;
+            encrypt_optional_secret(
+                &store,
+                &mut target.user_key,
+                "config.notify.targets.*.user_key",
+            )?;
         }
 
         encrypt_channel_secrets(&store, &mut config_to_save.channels_config)?;
 
-        let toml_str =
+        let mut toml_str =
             toml::to_string_pretty(&config_to_save).context("Failed to serialize config")?;
+        if !self.secret_refs.is_empty() || !self.layers.is_empty() {
+            // Compare against unencrypted values to see which references and
+            // overlay values still apply.
+            let plain: toml::Table =
+                toml::from_str(&toml::to_string(self).context("Failed to serialize config")?)
+                    .context("Failed to serialize config")?;
+            let mut doc: toml::Table =
+                toml::from_str(&toml_str).context("Failed to serialize config")?;
+            crate::security::vault::restore_references(&mut doc, &plain, &self.secret_refs);
+            crate::config::layers::restore_base_values(&mut doc, &plain, &self.layers);
+            toml_str = toml::to_string_pretty(&doc).context("Failed to serialize config")?;
+        }
 
         let parent_dir = self
             .config_path
@@ -8050,6 +11140,7 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
 
@@ -8258,6 +11349,86 @@ recipient = "42"
         assert_eq!(parsed.to.as_deref(), Some("42"));
     }
 
+    #[test]
+    async fn heartbeat_proactive_settings_parse_and_validate() {
+        let raw = r#"
+enabled = true
+proactive = true
+watch = ["https://status.example.com", "CI status of main"]
+
+[quiet_hours]
+start = "22:00"
+end = "07:30"
+tz = "Europe/Berlin"
+
+[[channels]]
+channel = "slack"
+to = "C123"
+
+[[channels]]
+channel = "telegram"
+to = "42"
+enabled = false
+"#;
+        let parsed: HeartbeatConfig = toml::from_str(raw).unwrap();
+        assert!(parsed.proactive);
+        assert_eq!(parsed.watch.len(), 2);
+        assert_eq!(parsed.quiet_hours.as_ref().unwrap().end, "07:30");
+        assert!(parsed.channels[0].enabled);
+        assert!(!parsed.channels[1].enabled);
+
+        let mut config = Config {
+            heartbeat: parsed,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+
+        config.heartbeat.quiet_hours.as_mut().unwrap().start = "25:00".into();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("heartbeat.quiet_hours.start"));
+
+        config.heartbeat.quiet_hours.as_mut().unwrap().start = "22:00".into();
+        config.heartbeat.quiet_hours.as_mut().unwrap().tz = Some("Mars/Base".into());
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("heartbeat.quiet_hours.tz"));
+    }
+
+    #[test]
+    async fn moderation_config_parses_and_validates() {
+        let raw = r#"
+default_temperature = 0.7
+
+[security.moderation]
+enabled = true
+flagged_action = "rewrite"
+classifier_hint = "moderation"
+
+[[security.moderation.rules]]
+pattern = "(?i)internal only"
+action = "warn"
+
+[[model_routes]]
+hint = "moderation"
+provider = "openrouter"
+model = "small-model"
+"#;
+        let mut config: Config = toml::from_str(raw).unwrap();
+        let moderation = &config.security.moderation;
+        assert!(moderation.enabled);
+        assert_eq!(moderation.flagged_action, ModerationAction::Rewrite);
+        assert_eq!(moderation.rules[0].action, ModerationAction::Warn);
+        config.validate().unwrap();
+
+        config.security.moderation.rules[0].pattern = "(".into();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("security.moderation.rules[0]"));
+
+        config.security.moderation.rules.clear();
+        config.model_routes.clear();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("classifier_hint"));
+    }
+
     #[test]
     async fn cron_config_default() {
         let c = CronConfig::default();
@@ -8270,6 +11441,7 @@ recipient = "42"
         let c = CronConfig {
             enabled: false,
             max_run_history: 100,
+            jobs: Vec::new(),
         };
         let json = serde_json::to_string(&c).unwrap();
         let parsed: CronConfig = serde_json::from_str(&json).unwrap();
@@ -8277,6 +11449,52 @@ recipient = "42"
         assert_eq!(parsed.max_run_history, 100);
     }
 
+    #[test]
+    async fn cron_jobs_parse_and_validate() {
+        let toml_str = r#"
+workspace_dir = "/tmp/workspace"
+config_path = "/tmp/config.toml"
+default_temperature = 0.7
+
+[[cron.jobs]]
+name = "standup-digest"
+cron = "0 9 * * 1-5"
+tz = "America/New_York"
+prompt = "Summarize my assigned GitHub issues"
+channel = "slack"
+to = "C0STANDUP"
+
+[[cron.jobs]]
+name = "disk-check"
+every_secs = 3600
+tool = "shell"
+args = { command = "df -h" }
+"#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.cron.jobs.len(), 2);
+        assert_eq!(
+            config.cron.jobs[1].args.as_ref().unwrap()["command"],
+            "df -h"
+        );
+        config.validate().expect("declared jobs are valid");
+
+        config.cron.jobs[1].cron = Some("0 * * * *".into());
+        let err = config.validate().expect_err("expected two schedules");
+        assert!(err.to_string().contains("cron.jobs[1]"));
+
+        config.cron.jobs[1].cron = None;
+        config.cron.jobs[1].prompt = Some("also this".into());
+        let err = config.validate().expect_err("expected two actions");
+        assert!(err
+            .to_string()
+            .contains("exactly one of prompt, tool or command"));
+
+        config.cron.jobs[1].prompt = None;
+        config.cron.jobs[1].name = "standup-digest".into();
+        let err = config.validate().expect_err("expected duplicate name");
+        assert!(err.to_string().contains("is used by another job"));
+    }
+
     #[test]
     async fn config_defaults_cron_when_section_missing() {
         let toml_str = r#"
@@ -8327,6 +11545,8 @@ default_temperature = 0.7
         let config = Config {
             workspace_dir: PathBuf::from("/tmp/test/workspace"),
             config_path: PathBuf::from("/tmp/test/config.toml"),
+            secret_refs: Vec::new(),
+            layers: Default::default(),
             api_key: Some("sk-test-key".into()),
             api_url: None,
             default_provider: Some("openrouter".into()),
@@ -8352,11 +11572,19 @@ default_temperature = 0.7
                 auto_approve: vec!["file_read".into()],
                 always_ask: vec![],
                 allowed_roots: vec![],
+                tool_path_scopes: HashMap::new(),
+                command_policy: CommandPolicyConfig::default(),
                 non_cli_excluded_tools: vec![],
                 non_cli_approval_approvers: vec![],
+                max_delegation_depth: 2,
+                max_subagent_iterations: 15,
+                max_subagent_tokens: 2048,
+                max_parallel_subagents: 3,
                 non_cli_natural_language_approval_mode:
                     NonCliNaturalLanguageApprovalMode::RequestConfirm,
                 non_cli_natural_language_approval_mode_by_channel: HashMap::new(),
+                level_overrides: HashMap::new(),
+                approval_policy_by_channel: HashMap::new(),
             },
             security: SecurityConfig::default(),
             runtime: RuntimeConfig {
@@ -8371,6 +11599,7 @@ default_temperature = 0.7
             plugins: PluginsConfig::default(),
             model_routes: Vec::new(),
             embedding_routes: Vec::new(),
+            provider_capabilities: Vec::new(),
             query_classification: QueryClassificationConfig::default(),
             heartbeat: HeartbeatConfig {
                 enabled: true,
@@ -8378,8 +11607,18 @@ default_temperature = 0.7
                 message: Some("Check London time".into()),
                 target: Some("telegram".into()),
                 to: Some("123456".into()),
+                ..HeartbeatConfig::default()
             },
             cron: CronConfig::default(),
+            feeds: FeedsConfig::default(),
+            analytics: AnalyticsConfig::default(),
+            mailbox: MailboxConfig::default(),
+            prometheus: PrometheusConfig::default(),
+            jira: JiraConfig::default(),
+            linear: LinearConfig::default(),
+            kb: KbConfig::default(),
+            convert_document: ConvertDocumentConfig::default(),
+            personas: PersonasConfig::default(),
             goal_loop: GoalLoopConfig::default(),
             channels_config: ChannelsConfig {
                 cli: true,
@@ -8412,6 +11651,7 @@ default_temperature = 0.7
                 qq: None,
                 nostr: None,
                 clawdtalk: None,
+                queue: None,
                 message_timeout_secs: 300,
             },
             memory: MemoryConfig::default(),
@@ -8420,7 +11660,12 @@ default_temperature = 0.7
             gateway: GatewayConfig::default(),
             composio: ComposioConfig::default(),
             secrets: SecretsConfig::default(),
+            session: SessionConfig::default(),
+            attachments: AttachmentsConfig::default(),
+            i18n: I18nConfig::default(),
+            notify: NotifyConfig::default(),
             browser: BrowserConfig::default(),
+            shell_session: ShellSessionConfig::default(),
             http_request: HttpRequestConfig::default(),
             multimodal: MultimodalConfig::default(),
             web_fetch: WebFetchConfig::default(),
@@ -8438,6 +11683,7 @@ default_temperature = 0.7
             agents_ipc: AgentsIpcConfig::default(),
             mcp: McpConfig::default(),
             model_support_vision: None,
+            model_support_tools: None,
             wasm: WasmConfig::default(),
         };
 
@@ -8717,6 +11963,8 @@ reasoning_level = "high"
         assert_eq!(cfg.max_history_messages, 50);
         assert!(!cfg.parallel_tools);
         assert_eq!(cfg.tool_dispatcher, "auto");
+        assert_eq!(cfg.max_concurrent_tools, 8);
+        assert_eq!(cfg.tool_concurrency.get("browser"), Some(&1));
     }
 
     #[test]
@@ -8729,6 +11977,10 @@ max_tool_iterations = 20
 max_history_messages = 80
 parallel_tools = true
 tool_dispatcher = "xml"
+max_concurrent_tools = 4
+
+[agent.tool_concurrency]
+browser = 2
 "#;
         let parsed: Config = toml::from_str(raw).unwrap();
         assert!(parsed.agent.compact_context);
@@ -8736,6 +11988,11 @@ tool_dispatcher = "xml"
         assert_eq!(parsed.agent.max_history_messages, 80);
         assert!(parsed.agent.parallel_tools);
         assert_eq!(parsed.agent.tool_dispatcher, "xml");
+        assert_eq!(parsed.agent.max_concurrent_tools, 4);
+        assert_eq!(
+            parsed.agent.tool_concurrency,
+            HashMap::from([("browser".to_string(), 2)])
+        );
     }
 
     #[tokio::test]
@@ -8761,6 +12018,8 @@ tool_dispatcher = "xml"
         let config = Config {
             workspace_dir: dir.join("workspace"),
             config_path: config_path.clone(),
+            secret_refs: Vec::new(),
+            layers: Default::default(),
             api_key: Some("sk-roundtrip".into()),
             api_url: None,
             default_provider: Some("openrouter".into()),
@@ -8781,9 +12040,19 @@ tool_dispatcher = "xml"
             plugins: PluginsConfig::default(),
             model_routes: Vec::new(),
             embedding_routes: Vec::new(),
+            provider_capabilities: Vec::new(),
             query_classification: QueryClassificationConfig::default(),
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
+            feeds: FeedsConfig::default(),
+            analytics: AnalyticsConfig::default(),
+            mailbox: MailboxConfig::default(),
+            prometheus: PrometheusConfig::default(),
+            jira: JiraConfig::default(),
+            linear: LinearConfig::default(),
+            kb: KbConfig::default(),
+            convert_document: ConvertDocumentConfig::default(),
+            personas: PersonasConfig::default(),
             goal_loop: GoalLoopConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
@@ -8792,7 +12061,12 @@ tool_dispatcher = "xml"
             gateway: GatewayConfig::default(),
             composio: ComposioConfig::default(),
             secrets: SecretsConfig::default(),
+            session: SessionConfig::default(),
+            attachments: AttachmentsConfig::default(),
+            i18n: I18nConfig::default(),
+            notify: NotifyConfig::default(),
             browser: BrowserConfig::default(),
+            shell_session: ShellSessionConfig::default(),
             http_request: HttpRequestConfig::default(),
             multimodal: MultimodalConfig::default(),
             web_fetch: WebFetchConfig::default(),
@@ -8810,6 +12084,7 @@ tool_dispatcher = "xml"
             agents_ipc: AgentsIpcConfig::default(),
             mcp: McpConfig::default(),
             model_support_vision: None,
+            model_support_tools: None,
             wasm: WasmConfig::default(),
         };
 
@@ -8856,6 +12131,7 @@ tool_dispatcher = "xml"
         config.storage.provider.config.db_url = Some("postgres://user:pw@host/db".into());
         config.reliability.api_keys = vec!["backup-credential".into()];
         config.gateway.paired_tokens = vec!["zc_0123456789abcdef".into()];
+        config.gateway.api_keys = vec!["sk-gateway-credential".into()];
         config.channels_config.telegram = Some(TelegramConfig {
             bot_token: "telegram-credential".into(),
             allowed_users: Vec::new(),
@@ -8879,6 +12155,7 @@ tool_dispatcher = "xml"
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
 
@@ -8989,6 +12266,10 @@ tool_dispatcher = "xml"
         assert!(crate::security::SecretStore::is_encrypted(paired_token));
         assert_eq!(store.decrypt(paired_token).unwrap(), "zc_0123456789abcdef");
 
+        let api_key = &stored.gateway.api_keys[0];
+        assert!(crate::security::SecretStore::is_encrypted(api_key));
+        assert_eq!(store.decrypt(api_key).unwrap(), "sk-gateway-credential");
+
         let telegram_token = stored
             .channels_config
             .telegram
@@ -9338,6 +12619,7 @@ allowed_users = ["@ops:matrix.org"]
             qq: None,
             nostr: None,
             clawdtalk: None,
+            queue: None,
             message_timeout_secs: 300,
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
@@ -9616,6 +12898,7 @@ channel_id = "C123"
             qq: None,
             nostr: None,
             clawdtalk: None,
+            queue: None,
             message_timeout_secs: 300,
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
@@ -9698,6 +12981,7 @@ channel_id = "C123"
             require_pairing: true,
             allow_public_bind: false,
             paired_tokens: vec!["zc_test_token".into()],
+            api_keys: Vec::new(),
             pair_rate_limit_per_minute: 12,
             webhook_rate_limit_per_minute: 80,
             trust_forwarded_headers: true,
@@ -9709,6 +12993,10 @@ channel_id = "C123"
                 auth_token: Some("node-token".into()),
                 allowed_node_ids: vec!["node-1".into(), "node-2".into()],
             },
+            auth: GatewayAuthConfig::default(),
+            limits: GatewayLimitsConfig::default(),
+            payload_archive_size: 0,
+            tenants: HashMap::new(),
         };
         let toml_str = toml::to_string(&g).unwrap();
         let parsed: GatewayConfig = toml::from_str(&toml_str).unwrap();
@@ -9844,7 +13132,10 @@ enable = true
 
     #[test]
     async fn secrets_config_serde_roundtrip() {
-        let s = SecretsConfig { encrypt: false };
+        let s = SecretsConfig {
+            encrypt: false,
+            ..SecretsConfig::default()
+        };
         let toml_str = toml::to_string(&s).unwrap();
         let parsed: SecretsConfig = toml::from_str(&toml_str).unwrap();
         assert!(!parsed.encrypt);
@@ -11110,6 +14401,23 @@ default_model = "legacy-model"
         std::env::remove_var("ZEROCLAW_MODEL_SUPPORT_VISION");
     }
 
+    #[test]
+    async fn env_override_model_support_tools() {
+        let _env_guard = env_override_lock().await;
+        let mut config = Config::default();
+        assert_eq!(config.model_support_tools, None);
+
+        std::env::set_var("ZEROCLAW_MODEL_SUPPORT_TOOLS", "off");
+        config.apply_env_overrides();
+        assert_eq!(config.model_support_tools, Some(false));
+
+        std::env::set_var("ZEROCLAW_MODEL_SUPPORT_TOOLS", "1");
+        config.apply_env_overrides();
+        assert_eq!(config.model_support_tools, Some(true));
+
+        std::env::remove_var("ZEROCLAW_MODEL_SUPPORT_TOOLS");
+    }
+
     #[test]
     async fn env_override_invalid_port_ignored() {
         let _env_guard = env_override_lock().await;
@@ -12015,6 +15323,428 @@ symbol_ratio_threshold = 0.25
         assert!(err.to_string().contains("security.url_access.allow_cidrs"));
     }
 
+    #[test]
+    async fn gateway_auth_validation_checks_allowlists_and_paths() {
+        let mut config = Config::default();
+        config.gateway.auth.ip_allowlist = vec!["127.0.0.1".into(), "10.0.0.0/8".into()];
+        config.validate().expect("plain IPs and CIDRs are valid");
+
+        config.gateway.auth.ip_allowlist = vec!["10.0.0.300".into()];
+        let err = config.validate().expect_err("expected invalid IP");
+        assert!(err.to_string().contains("gateway.auth.ip_allowlist[0]"));
+
+        config.gateway.auth.ip_allowlist.clear();
+        config.gateway.auth.routes = vec![GatewayRouteAuthConfig {
+            path: "webhook".into(),
+            api_keys: vec!["k".into()],
+            ip_allowlist: Vec::new(),
+            require_client_cert: false,
+        }];
+        let err = config.validate().expect_err("expected relative path");
+        assert!(err.to_string().contains("gateway.auth.routes[0].path"));
+
+        config.gateway.auth.routes.clear();
+        config.gateway.auth.enabled = true;
+        config.gateway.auth.require_client_cert = true;
+        let err = config
+            .validate()
+            .expect_err("expected untrusted cert header");
+        assert!(err.to_string().contains("trust_forwarded_headers"));
+        config.gateway.trust_forwarded_headers = true;
+        config
+            .validate()
+            .expect("cert header from a trusted proxy is valid");
+    }
+
+    #[test]
+    async fn gateway_limits_validation_rejects_zero_limits_and_relative_paths() {
+        let mut config = Config::default();
+        config.gateway.limits.routes = vec![GatewayRouteLimitConfig {
+            path: "/webhook".into(),
+            max_body_bytes: Some(1_048_576),
+            timeout_secs: None,
+        }];
+        config.validate().expect("route override is valid");
+
+        config.gateway.limits.routes[0].timeout_secs = Some(0);
+        let err = config.validate().expect_err("expected zero timeout");
+        assert!(err
+            .to_string()
+            .contains("gateway.limits.routes[0].timeout_secs"));
+
+        config.gateway.limits.routes[0].timeout_secs = None;
+        config.gateway.limits.routes[0].path = "webhook".into();
+        let err = config.validate().expect_err("expected relative path");
+        assert!(err.to_string().contains("gateway.limits.routes[0].path"));
+
+        config.gateway.limits.routes.clear();
+        config.gateway.limits.max_body_bytes = 0;
+        let err = config.validate().expect_err("expected zero body limit");
+        assert!(err.to_string().contains("gateway.limits.max_body_bytes"));
+    }
+
+    #[test]
+    async fn queue_channel_validation_matches_url_scheme_to_backend() {
+        let mut config = Config::default();
+        let mut queue: QueueConfig = toml::from_str(
+            r#"
+url = "redis://localhost:6379"
+inbound = "agent.requests"
+outbound = "agent.replies"
+"#,
+        )
+        .unwrap();
+        assert_eq!(queue.backend, QueueBackend::Redis);
+        assert_eq!(queue.group, "zeroclaw");
+        config.channels_config.queue = Some(queue.clone());
+        config.validate().expect("redis queue is valid");
+
+        queue.backend = QueueBackend::Nats;
+        config.channels_config.queue = Some(queue);
+        let err = config.validate().expect_err("expected scheme mismatch");
+        assert!(err.to_string().contains("nats://"));
+    }
+
+    #[test]
+    async fn feeds_validation_checks_names_urls_and_delivery() {
+        let feeds: FeedsConfig = toml::from_str(
+            r#"
+[[sources]]
+name = "rust-blog"
+url = "https://blog.rust-lang.org/feed.xml"
+channel = "telegram"
+to = "123"
+"#,
+        )
+        .unwrap();
+        assert_eq!(feeds.interval_minutes, 60);
+        assert!(feeds.sources[0].enabled);
+        let mut config = Config {
+            feeds,
+            ..Config::default()
+        };
+        config.validate().expect("feed is valid");
+
+        config.feeds.sources[0].to = None;
+        let err = config.validate().expect_err("expected channel without to");
+        assert!(err.to_string().contains("channel and to together"));
+
+        config.feeds.sources[0].channel = None;
+        config.feeds.sources[0].url = "file:///etc/passwd".into();
+        let err = config.validate().expect_err("expected non-http url");
+        assert!(err.to_string().contains("feeds.sources[0].url"));
+    }
+
+    #[test]
+    async fn incident_notifications_need_channel_and_recipient() {
+        let mut config = Config::default();
+        assert!(config.observability.incident_reports);
+        config.observability.incident_channel = Some("telegram".into());
+        let err = config
+            .validate()
+            .expect_err("expected channel without recipient");
+        assert!(err.to_string().contains("incident_to"));
+
+        config.observability.incident_to = Some("123".into());
+        config.validate().expect("incident notifications are valid");
+    }
+
+    #[test]
+    async fn shell_session_shell_must_be_a_bare_program() {
+        let shell_session: ShellSessionConfig = toml::from_str("idle_timeout_secs = 60").unwrap();
+        assert!(shell_session.enabled);
+        assert_eq!(shell_session.shell, "sh");
+        assert_eq!(shell_session.max_sessions, 4);
+        let mut config = Config {
+            shell_session,
+            ..Config::default()
+        };
+        config.validate().expect("default shell is valid");
+
+        config.shell_session.shell = "/bin/bash".into();
+        config.validate().expect("absolute path is valid");
+
+        config.shell_session.shell = "bash -c 'id'".into();
+        let err = config
+            .validate()
+            .expect_err("expected shell with arguments");
+        assert!(err.to_string().contains("shell_session.shell"));
+
+        config.shell_session.shell = "bash".into();
+        config.shell_session.idle_timeout_secs = 0;
+        let err = config.validate().expect_err("expected zero idle timeout");
+        assert!(err.to_string().contains("idle_timeout_secs"));
+    }
+
+    #[test]
+    async fn analytics_digest_requires_delivery_and_cost_tracking() {
+        let analytics: AnalyticsConfig = toml::from_str(
+            r#"
+digest_enabled = true
+channel = "telegram"
+to = "123"
+"#,
+        )
+        .unwrap();
+        assert_eq!(analytics.digest_hour, 8);
+        assert_eq!(analytics.top_tools, 5);
+        let mut config = Config {
+            analytics,
+            ..Config::default()
+        };
+        let err = config
+            .validate()
+            .expect_err("expected digest without cost tracking");
+        assert!(err.to_string().contains("cost.enabled"));
+
+        config.cost.enabled = true;
+        config.validate().expect("digest is valid");
+
+        config.analytics.to = None;
+        let err = config.validate().expect_err("expected channel without to");
+        assert!(err.to_string().contains("set together"));
+
+        config.analytics.to = Some("123".into());
+        config.analytics.digest_hour = 24;
+        let err = config.validate().expect_err("expected out-of-range hour");
+        assert!(err.to_string().contains("analytics.digest_hour"));
+    }
+
+    #[test]
+    async fn prometheus_validation_checks_url_and_patterns() {
+        let mut config = Config {
+            prometheus: PrometheusConfig {
+                enabled: true,
+                url: "prometheus:9090".into(),
+                ..PrometheusConfig::default()
+            },
+            ..Config::default()
+        };
+        let err = config.validate().expect_err("expected url without scheme");
+        assert!(err.to_string().contains("prometheus.url"));
+
+        config.prometheus.url = "http://prometheus:9090".into();
+        config.validate().expect("prometheus is valid");
+
+        config.prometheus.allowed_queries = vec!["^rate(".into()];
+        let err = config.validate().expect_err("expected invalid regex");
+        assert!(err.to_string().contains("allowed_queries[0]"));
+    }
+
+    #[test]
+    async fn provider_capabilities_parse_and_validate_limits() {
+        let mut config: Config = toml::from_str(
+            r#"
+default_temperature = 0.7
+
+[[provider_capabilities]]
+provider = "ollama"
+model = "qwen2.5*"
+max_tools = 24
+schema = "gemini"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.provider_capabilities[0].schema,
+            Some(ToolSchemaDialect::Gemini)
+        );
+        config.validate().expect("entry is valid");
+
+        config.provider_capabilities[0].max_tools = Some(0);
+        let err = config.validate().expect_err("expected zero tool limit");
+        assert!(err
+            .to_string()
+            .contains("provider_capabilities[0].max_tools"));
+
+        config.provider_capabilities[0].max_tools = None;
+        config.provider_capabilities[0].max_tool_name_len = Some(8);
+        let err = config.validate().expect_err("expected short name limit");
+        assert!(err
+            .to_string()
+            .contains("provider_capabilities[0].max_tool_name_len"));
+
+        config.provider_capabilities[0].max_tool_name_len = None;
+        config.provider_capabilities[0].context_window = Some(512);
+        let err = config.validate().expect_err("expected tiny context window");
+        assert!(err
+            .to_string()
+            .contains("provider_capabilities[0].context_window"));
+    }
+
+    #[test]
+    async fn personas_validation_checks_names_and_channel_defaults() {
+        let mut config: Config = toml::from_str(
+            r#"
+default_temperature = 0.7
+
+[personas.by_channel]
+github = "reviewer"
+
+[personas.profiles.reviewer]
+system_prompt = "Review pull requests strictly."
+tools = ["file_read"]
+provider = "anthropic"
+model = "claude-sonnet-4-6"
+"#,
+        )
+        .unwrap();
+        config.validate().expect("personas are valid");
+
+        config
+            .personas
+            .by_channel
+            .insert("telegram".into(), "casual".into());
+        let err = config.validate().expect_err("expected unknown persona");
+        assert!(err.to_string().contains("personas.by_channel.telegram"));
+
+        config.personas.by_channel.remove("telegram");
+        config.personas.profiles.get_mut("reviewer").unwrap().model = None;
+        let err = config
+            .validate()
+            .expect_err("expected provider without model");
+        assert!(err.to_string().contains("requires model"));
+
+        config
+            .personas
+            .profiles
+            .get_mut("reviewer")
+            .unwrap()
+            .provider = None;
+        config
+            .personas
+            .profiles
+            .insert("off".into(), PersonaConfig::default());
+        let err = config.validate().expect_err("expected reserved name");
+        assert!(err.to_string().contains("reserved"));
+    }
+
+    #[test]
+    async fn kb_validation_checks_ranges_and_sources() {
+        let mut config = Config {
+            kb: KbConfig {
+                enabled: true,
+                sources: vec!["docs/handbook".into()],
+                ..KbConfig::default()
+            },
+            ..Config::default()
+        };
+        config.validate().expect("defaults are valid");
+
+        config.kb.min_score = 1.5;
+        let err = config.validate().expect_err("expected out-of-range score");
+        assert!(err.to_string().contains("kb.min_score"));
+
+        config.kb.min_score = 0.35;
+        config.kb.sources.push("  ".into());
+        let err = config.validate().expect_err("expected empty source");
+        assert!(err.to_string().contains("kb.sources[1]"));
+    }
+
+    #[test]
+    async fn ticketing_validation_requires_credentials_and_keys() {
+        let mut config = Config {
+            jira: toml::from_str(
+                r#"
+enabled = true
+base_url = "https://example.atlassian.net"
+api_token = "token"
+projects = ["OPS"]
+"#,
+            )
+            .unwrap(),
+            ..Config::default()
+        };
+        let err = config.validate().expect_err("expected missing cloud email");
+        assert!(err.to_string().contains("jira.email"));
+
+        config.jira.deployment = JiraDeployment::Server;
+        config.validate().expect("server needs no email");
+
+        config.jira.projects = vec!["ops) OR project = HR".into()];
+        let err = config.validate().expect_err("expected invalid project key");
+        assert!(err.to_string().contains("jira.projects[0]"));
+
+        config.jira.projects = vec!["OPS".into()];
+        config.linear = LinearConfig {
+            enabled: true,
+            api_key: Some("lin_api_test".into()),
+            ..LinearConfig::default()
+        };
+        let err = config.validate().expect_err("expected missing teams");
+        assert!(err.to_string().contains("linear.teams"));
+    }
+
+    #[test]
+    async fn notify_validation_requires_service_fields() {
+        let notify: NotifyConfig = toml::from_str(
+            r#"
+default_target = "phone"
+
+[targets.phone]
+service = "ntfy"
+topic = "zeroclaw-alerts"
+"#,
+        )
+        .unwrap();
+        let mut config = Config {
+            notify,
+            ..Config::default()
+        };
+        assert_eq!(
+            config.notify.targets["phone"].priority,
+            NotifyPriority::Normal
+        );
+        config.validate().expect("ntfy target is valid");
+
+        let pushover: NotifyTargetConfig =
+            toml::from_str("service = \"pushover\"\ntoken = \"app-token\"").unwrap();
+        config.notify.targets.insert("pager".into(), pushover);
+        let err = config.validate().expect_err("expected missing user key");
+        assert!(err.to_string().contains("notify.targets.pager.user_key"));
+
+        config.notify.targets.remove("pager");
+        config.notify.default_target = Some("desk".into());
+        let err = config
+            .validate()
+            .expect_err("expected unknown default target");
+        assert!(err.to_string().contains("notify.default_target"));
+    }
+
+    #[test]
+    async fn gateway_tenant_validation_checks_names_and_dirs() {
+        let mut config = Config::default();
+        config.config_path = PathBuf::from("/srv/zeroclaw/config.toml");
+        config.gateway.tenants.insert(
+            "sales-team".into(),
+            GatewayTenantConfig {
+                config_dir: PathBuf::from("/srv/zeroclaw/tenants/sales"),
+            },
+        );
+        config.validate().expect("tenant is valid");
+
+        config.gateway.tenants.insert(
+            "support".into(),
+            GatewayTenantConfig {
+                config_dir: PathBuf::from("/srv/zeroclaw"),
+            },
+        );
+        let err = config.validate().expect_err("expected shared config dir");
+        assert!(err
+            .to_string()
+            .contains("gateway.tenants.support.config_dir"));
+
+        config.gateway.tenants.remove("support");
+        config.gateway.tenants.insert(
+            "a/b".into(),
+            GatewayTenantConfig {
+                config_dir: PathBuf::from("/srv/zeroclaw/tenants/ab"),
+            },
+        );
+        let err = config.validate().expect_err("expected invalid name");
+        assert!(err.to_string().contains("gateway.tenants.a/b"));
+    }
+
     #[test]
     async fn security_validation_rejects_blank_url_access_domain() {
         let mut config = Config::default();
@@ -12317,4 +16047,36 @@ symbol_ratio_threshold = 0.25
             .validate()
             .expect("disabled coordination should allow empty lead agent");
     }
+
+    #[test]
+    async fn autonomy_validation_checks_approval_patterns() {
+        let mut config = Config::default();
+        config.autonomy.auto_approve = vec!["github_*".into()];
+        config.autonomy.always_ask = vec!["file_[rw]*".into()];
+        config.validate().expect("valid globs should pass");
+
+        config.autonomy.always_ask = vec!["file_[".into()];
+        let err = config
+            .validate()
+            .expect_err("expected invalid glob failure");
+        assert!(err.to_string().contains("autonomy.always_ask[0]"));
+
+        config.autonomy.always_ask = vec![];
+        config.autonomy.auto_approve = vec![" shell".into()];
+        let err = config.validate().expect_err("expected whitespace failure");
+        assert!(err.to_string().contains("autonomy.auto_approve[0]"));
+
+        config.autonomy.auto_approve = vec![];
+        config.autonomy.approval_policy_by_channel.insert(
+            "telegram".into(),
+            ChannelApprovalPolicy {
+                auto_approve: vec![],
+                always_ask: vec!["shell".into(), "".into()],
+            },
+        );
+        let err = config.validate().expect_err("expected empty entry failure");
+        assert!(err
+            .to_string()
+            .contains("autonomy.approval_policy_by_channel.telegram.always_ask[1]"));
+    }
 }
@@ -0,0 +1,65 @@
+use super::usage::{UsageGroup, UsageLedger};
+use crate::config::Config;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+
+/// Handle `zeroclaw usage <subcommand>` CLI commands.
+pub fn handle_command(command: crate::UsageCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::UsageCommands::Report { by, since, json } => {
+            let group = UsageGroup::parse(&by).with_context(|| {
+                format!("Unknown grouping '{by}'. Use day, session, channel, principal or model.")
+            })?;
+            let since = since
+                .map(|raw| {
+                    NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d").with_context(|| {
+                        format!("Invalid --since date '{raw}', expected YYYY-MM-DD")
+                    })
+                })
+                .transpose()?;
+
+            let ledger = UsageLedger::open(&config.workspace_dir, &config.cost)?;
+            let rows = ledger.report(group, since)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&rows)?);
+                return Ok(());
+            }
+            if rows.is_empty() {
+                println!("No usage recorded.");
+                return Ok(());
+            }
+
+            println!(
+                "{:<32} {:>8} {:>12} {:>12} {:>10}",
+                by, "requests", "input", "output", "cost_usd"
+            );
+            let mut total_cost = 0.0;
+            for row in &rows {
+                let marker = if row.estimated_requests > 0 { "*" } else { "" };
+                println!(
+                    "{:<32} {:>8} {:>12} {:>12} {:>10.4}{marker}",
+                    row.key, row.requests, row.input_tokens, row.output_tokens, row.cost_usd
+                );
+                total_cost += row.cost_usd;
+            }
+            println!("\nTotal: ${total_cost:.4}");
+            if rows.iter().any(|row| row.estimated_requests > 0) {
+                println!("* includes requests with estimated token counts");
+            }
+            Ok(())
+        }
+        crate::UsageCommands::Digest { day } => {
+            let day = match day {
+                Some(raw) => NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+                    .with_context(|| format!("Invalid --day date '{raw}', expected YYYY-MM-DD"))?,
+                None => chrono::Utc::now()
+                    .date_naive()
+                    .pred_opt()
+                    .context("no previous day")?,
+            };
+            println!("{}", crate::analytics::digest_for(config, day)?);
+            Ok(())
+        }
+    }
+}
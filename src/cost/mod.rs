@@ -1,8 +1,18 @@
+pub mod cli;
 pub mod tracker;
 pub mod types;
+pub mod usage;
 
 // Re-exported for potential external use (public API)
 #[allow(unused_imports)]
 pub use tracker::CostTracker;
 #[allow(unused_imports)]
-pub use types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
+pub use types::{
+    lookup_pricing, BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod,
+    DEFAULT_INPUT_PRICE_PER_MILLION, DEFAULT_OUTPUT_PRICE_PER_MILLION,
+};
+#[allow(unused_imports)]
+pub use usage::{
+    ActivityKind, BudgetExhausted, ChannelActivity, DailyUsage, UsageEvent, UsageGroup,
+    UsageLedger, UsageRow, UsageScope, USAGE_SCOPE,
+};
@@ -1,4 +1,6 @@
+use crate::config::schema::ModelPricing;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Token usage information from a single API call.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -152,6 +154,52 @@ impl Default for CostSummary {
     }
 }
 
+/// Conservative pricing (USD per 1M tokens) assumed for models without a
+/// configured price.
+pub const DEFAULT_INPUT_PRICE_PER_MILLION: f64 = 3.0;
+pub const DEFAULT_OUTPUT_PRICE_PER_MILLION: f64 = 15.0;
+
+/// Look up pricing (USD per 1M input/output tokens) for a model, trying
+/// `provider/model`, the bare model name, then model family matches.
+pub fn lookup_pricing(
+    prices: &HashMap<String, ModelPricing>,
+    provider: &str,
+    model: &str,
+) -> Option<(f64, f64)> {
+    // Try exact match first: "provider/model"
+    let full_name = format!("{provider}/{model}");
+    if let Some(pricing) = prices.get(&full_name) {
+        return Some((pricing.input, pricing.output));
+    }
+
+    // Try just the model name
+    if let Some(pricing) = prices.get(model) {
+        return Some((pricing.input, pricing.output));
+    }
+
+    // Try model family matching (e.g., "claude-sonnet-4" matches any claude-sonnet-4-*)
+    for (key, pricing) in prices {
+        // Strip provider prefix if present
+        let key_model = key.split('/').next_back().unwrap_or(key);
+
+        // Check if model starts with the key (family match)
+        if model.starts_with(key_model) || key_model.starts_with(model) {
+            return Some((pricing.input, pricing.output));
+        }
+
+        // Check for common model name patterns
+        // e.g., "claude-3-5-sonnet-20241022" should match "claude-3.5-sonnet"
+        let normalized_model = model.replace('-', ".");
+        let normalized_key = key_model.replace('-', ".");
+        if normalized_model.contains(&normalized_key) || normalized_key.contains(&normalized_model)
+        {
+            return Some((pricing.input, pricing.output));
+        }
+    }
+
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
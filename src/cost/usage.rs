@@ -0,0 +1,890 @@
+//! Per-call token usage ledger.
+//!
+//! Every LLM call made by the agent loop is recorded in
+//! `<workspace>/state/usage.db` with its session, channel and principal
+//! (`<channel>:<sender>`). Token counts come from the provider response when
+//! reported and are estimated from message sizes otherwise. The ledger backs
+//! `zeroclaw usage report`, the gateway `/api/usage` endpoint, and the
+//! per-principal monthly budgets in `[cost]`.
+//!
+//! Channel turns and the tool calls they execute are counted per principal
+//! and UTC day as well, for the daily budgets (`[cost.daily_budget]`,
+//! `[cost.channel_daily_budgets]`, `[cost.principal_daily_budgets]`) that
+//! channels check before processing a message.
+//!
+//! Tool calls by name, approval decisions and failed channel turns are
+//! counted per channel and UTC day for the daily activity digest
+//! ([`crate::analytics`]).
+
+use super::types::{
+    lookup_pricing, TokenUsage, DEFAULT_INPUT_PRICE_PER_MILLION, DEFAULT_OUTPUT_PRICE_PER_MILLION,
+};
+use crate::config::schema::{CostConfig, DailyBudgetConfig};
+use crate::db::{migrate, Migration, Schema, Step};
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+tokio::task_local! {
+    /// Ledger and session that LLM calls of the current turn are billed to.
+    pub static USAGE_SCOPE: Option<UsageScope>;
+}
+
+/// Where the current turn's usage is recorded.
+#[derive(Clone)]
+pub struct UsageScope {
+    pub ledger: Arc<UsageLedger>,
+    pub session: String,
+}
+
+/// One LLM call to record.
+#[derive(Debug, Clone)]
+pub struct UsageEvent<'a> {
+    pub session: &'a str,
+    pub channel: &'a str,
+    pub principal: Option<&'a str>,
+    pub provider: &'a str,
+    pub model: &'a str,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Token counts were estimated rather than reported by the provider.
+    pub estimated: bool,
+}
+
+/// Dimension a usage report is aggregated by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageGroup {
+    Day,
+    Session,
+    Channel,
+    Principal,
+    Model,
+}
+
+impl UsageGroup {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "day" | "daily" => Some(Self::Day),
+            "session" => Some(Self::Session),
+            "channel" => Some(Self::Channel),
+            "principal" | "sender" => Some(Self::Principal),
+            "model" => Some(Self::Model),
+            _ => None,
+        }
+    }
+
+    fn column(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Session => "session",
+            Self::Channel => "channel",
+            Self::Principal => "COALESCE(principal, '-')",
+            Self::Model => "provider || '/' || model",
+        }
+    }
+}
+
+/// Aggregated usage for one group key.
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageRow {
+    pub key: String,
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+    /// Requests whose token counts were estimated.
+    pub estimated_requests: u64,
+}
+
+/// Turns, tool calls and tokens used in one UTC day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct DailyUsage {
+    pub turns: u64,
+    pub tool_calls: u64,
+    pub tokens: u64,
+}
+
+/// Agent activity counted for the daily digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityKind {
+    /// A tool call, by tool name.
+    Tool,
+    /// A tool call that failed, by tool name.
+    ToolFailure,
+    /// An answered approval prompt, by decision (`yes`, `no`, `always`).
+    Approval,
+    /// A channel turn that ended without a reply, by reason.
+    TurnFailure,
+}
+
+impl ActivityKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Tool => "tool",
+            Self::ToolFailure => "tool_failure",
+            Self::Approval => "approval",
+            Self::TurnFailure => "turn_failure",
+        }
+    }
+}
+
+/// What happened on one channel during one UTC day. Name/count lists are
+/// ordered by count, highest first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ChannelActivity {
+    pub channel: String,
+    pub turns: u64,
+    pub tools: Vec<(String, u64)>,
+    pub tool_failures: Vec<(String, u64)>,
+    pub approvals: Vec<(String, u64)>,
+    pub turn_failures: Vec<(String, u64)>,
+    pub requests: u64,
+    pub cost_usd: f64,
+}
+
+/// A daily budget that has been used up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BudgetExhausted {
+    /// Channel name or `<channel>:<sender>` the budget applies to.
+    pub scope: String,
+    /// `turns`, `tool_calls` or `tokens`.
+    pub metric: &'static str,
+    pub used: u64,
+    pub limit: u64,
+}
+
+impl DailyUsage {
+    /// First metric of `budget` this usage has reached.
+    fn exhausted(&self, budget: &DailyBudgetConfig) -> Option<(&'static str, u64, u64)> {
+        [
+            ("turns", self.turns, budget.max_turns),
+            ("tool_calls", self.tool_calls, budget.max_tool_calls),
+            ("tokens", self.tokens, budget.max_tokens),
+        ]
+        .into_iter()
+        .find(|(_, used, limit)| *limit > 0 && used >= limit)
+    }
+}
+
+fn channel_entry(
+    channels: &mut BTreeMap<String, ChannelActivity>,
+    channel: String,
+) -> &mut ChannelActivity {
+    channels
+        .entry(channel.clone())
+        .or_insert_with(|| ChannelActivity {
+            channel,
+            ..ChannelActivity::default()
+        })
+}
+
+fn today() -> String {
+    Utc::now().format("%Y-%m-%d").to_string()
+}
+
+fn to_u64(value: Option<i64>) -> u64 {
+    value.unwrap_or_default().try_into().unwrap_or_default()
+}
+
+/// Rough token estimate for text without provider-reported usage
+/// (~4 chars per token).
+pub fn estimate_tokens(text: &str) -> u64 {
+    u64::try_from(text.chars().count().div_ceil(4)).unwrap_or(u64::MAX)
+}
+
+/// Tables of `state/usage.db`.
+pub static SCHEMA: Schema = Schema {
+    name: "usage",
+    path: "state/usage.db",
+    migrations: &[
+        Migration {
+            version: 1,
+            description: "create token_usage",
+            step: Step::Sql(
+                "CREATE TABLE IF NOT EXISTS token_usage (
+                     id            INTEGER PRIMARY KEY AUTOINCREMENT,
+                     recorded_at   INTEGER NOT NULL,
+                     day           TEXT NOT NULL,
+                     month         TEXT NOT NULL,
+                     session       TEXT NOT NULL,
+                     channel       TEXT NOT NULL,
+                     principal     TEXT,
+                     provider      TEXT NOT NULL,
+                     model         TEXT NOT NULL,
+                     input_tokens  INTEGER NOT NULL,
+                     output_tokens INTEGER NOT NULL,
+                     estimated     INTEGER NOT NULL,
+                     cost_usd      REAL NOT NULL
+                 );
+                 CREATE INDEX IF NOT EXISTS idx_token_usage_day ON token_usage(day);
+                 CREATE INDEX IF NOT EXISTS idx_token_usage_principal_month
+                     ON token_usage(principal, month);",
+            ),
+        },
+        Migration {
+            version: 2,
+            description: "create turn_usage and budget_overrides",
+            step: Step::Sql(
+                "CREATE TABLE IF NOT EXISTS turn_usage (
+                     day        TEXT NOT NULL,
+                     principal  TEXT NOT NULL,
+                     channel    TEXT NOT NULL,
+                     turns      INTEGER NOT NULL DEFAULT 0,
+                     tool_calls INTEGER NOT NULL DEFAULT 0,
+                     PRIMARY KEY (day, principal)
+                 );
+                 CREATE TABLE IF NOT EXISTS budget_overrides (
+                     day        TEXT NOT NULL,
+                     scope      TEXT NOT NULL,
+                     granted_by TEXT NOT NULL,
+                     granted_at INTEGER NOT NULL,
+                     PRIMARY KEY (day, scope)
+                 );",
+            ),
+        },
+        Migration {
+            version: 3,
+            description: "create activity and digests_sent",
+            step: Step::Sql(
+                "CREATE TABLE IF NOT EXISTS activity (
+                     day     TEXT NOT NULL,
+                     channel TEXT NOT NULL,
+                     kind    TEXT NOT NULL,
+                     name    TEXT NOT NULL,
+                     count   INTEGER NOT NULL DEFAULT 0,
+                     PRIMARY KEY (day, channel, kind, name)
+                 );
+                 CREATE TABLE IF NOT EXISTS digests_sent (
+                     day     TEXT PRIMARY KEY,
+                     sent_at INTEGER NOT NULL
+                 );",
+            ),
+        },
+    ],
+};
+
+/// SQLite-backed usage ledger.
+pub struct UsageLedger {
+    conn: Mutex<Connection>,
+    config: CostConfig,
+}
+
+impl UsageLedger {
+    pub fn open(workspace_dir: &Path, config: &CostConfig) -> Result<Self> {
+        let db_path = SCHEMA.db_path(workspace_dir);
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        migrate(&conn, &SCHEMA)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            config: config.clone(),
+        })
+    }
+
+    /// Record one call, pricing it from `[cost.prices]`.
+    pub fn record(&self, event: &UsageEvent<'_>) -> Result<()> {
+        let (input_price, output_price) =
+            lookup_pricing(&self.config.prices, event.provider, event.model).unwrap_or((
+                DEFAULT_INPUT_PRICE_PER_MILLION,
+                DEFAULT_OUTPUT_PRICE_PER_MILLION,
+            ));
+        let usage = TokenUsage::new(
+            event.model,
+            event.input_tokens,
+            event.output_tokens,
+            input_price,
+            output_price,
+        );
+        let now = Utc::now();
+        self.conn.lock().execute(
+            "INSERT INTO token_usage (recorded_at, day, month, session, channel, principal,
+                 provider, model, input_tokens, output_tokens, estimated, cost_usd)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                now.timestamp(),
+                now.format("%Y-%m-%d").to_string(),
+                now.format("%Y-%m").to_string(),
+                event.session,
+                event.channel,
+                event.principal,
+                event.provider,
+                event.model,
+                i64::try_from(usage.input_tokens).unwrap_or(i64::MAX),
+                i64::try_from(usage.output_tokens).unwrap_or(i64::MAX),
+                event.estimated,
+                usage.cost_usd,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Usage aggregated by `group`, optionally from `since` (inclusive),
+    /// most expensive first.
+    pub fn report(&self, group: UsageGroup, since: Option<NaiveDate>) -> Result<Vec<UsageRow>> {
+        let since = since.map_or_else(String::new, |day| day.format("%Y-%m-%d").to_string());
+        let key = group.column();
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {key} AS key, COUNT(*), SUM(input_tokens), SUM(output_tokens),
+                    SUM(cost_usd), SUM(estimated)
+             FROM token_usage
+             WHERE day >= ?1
+             GROUP BY key
+             ORDER BY SUM(cost_usd) DESC, key"
+        ))?;
+        let rows = stmt.query_map(params![since], |row| {
+            Ok(UsageRow {
+                key: row.get(0)?,
+                requests: row.get::<_, i64>(1)?.try_into().unwrap_or_default(),
+                input_tokens: row.get::<_, i64>(2)?.try_into().unwrap_or_default(),
+                output_tokens: row.get::<_, i64>(3)?.try_into().unwrap_or_default(),
+                cost_usd: row.get(4)?,
+                estimated_requests: row.get::<_, i64>(5)?.try_into().unwrap_or_default(),
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    /// Spend of `principal` in the current calendar month (UTC).
+    pub fn principal_month_cost(&self, principal: &str) -> Result<f64> {
+        let month = Utc::now().format("%Y-%m").to_string();
+        let cost: Option<f64> = self.conn.lock().query_row(
+            "SELECT SUM(cost_usd) FROM token_usage WHERE principal = ?1 AND month = ?2",
+            params![principal, month],
+            |row| row.get(0),
+        )?;
+        Ok(cost.unwrap_or(0.0))
+    }
+
+    /// Monthly limit for `principal`, if any.
+    pub fn principal_limit(&self, principal: &str) -> Option<f64> {
+        let limit = self
+            .config
+            .principal_limits
+            .get(principal)
+            .copied()
+            .unwrap_or(self.config.principal_monthly_limit_usd);
+        (limit > 0.0).then_some(limit)
+    }
+
+    /// Fail when `principal` has spent its monthly budget.
+    pub fn check_principal_budget(&self, principal: &str) -> Result<()> {
+        let Some(limit) = self.principal_limit(principal) else {
+            return Ok(());
+        };
+        let spent = self.principal_month_cost(principal)?;
+        if spent >= limit {
+            anyhow::bail!(
+                "Monthly budget exhausted for {principal}: ${spent:.2} of ${limit:.2} spent. \
+                 Further requests are blocked until next month."
+            );
+        }
+        Ok(())
+    }
+
+    /// Count one channel turn of `principal` (`<channel>:<sender>`).
+    pub fn record_turn(&self, channel: &str, principal: &str) -> Result<()> {
+        self.bump_turn_usage(channel, principal, 1, 0)
+    }
+
+    /// Count `count` tool calls executed for `principal`.
+    pub fn record_tool_calls(&self, channel: &str, principal: &str, count: u64) -> Result<()> {
+        if count == 0 {
+            return Ok(());
+        }
+        self.bump_turn_usage(channel, principal, 0, count)
+    }
+
+    fn bump_turn_usage(
+        &self,
+        channel: &str,
+        principal: &str,
+        turns: u64,
+        tool_calls: u64,
+    ) -> Result<()> {
+        self.conn.lock().execute(
+            "INSERT INTO turn_usage (day, principal, channel, turns, tool_calls)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(day, principal) DO UPDATE SET
+                 turns = turns + excluded.turns,
+                 tool_calls = tool_calls + excluded.tool_calls",
+            params![
+                today(),
+                principal,
+                channel,
+                i64::try_from(turns).unwrap_or(i64::MAX),
+                i64::try_from(tool_calls).unwrap_or(i64::MAX),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Today's usage of `principal`, or of the whole channel when
+    /// `principal` is `None`.
+    pub fn daily_usage(&self, channel: &str, principal: Option<&str>) -> Result<DailyUsage> {
+        let day = today();
+        let conn = self.conn.lock();
+        let (turns, tool_calls): (Option<i64>, Option<i64>) = conn.query_row(
+            "SELECT SUM(turns), SUM(tool_calls) FROM turn_usage
+             WHERE day = ?1 AND channel = ?2 AND (?3 IS NULL OR principal = ?3)",
+            params![day, channel, principal],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let tokens: Option<i64> = conn.query_row(
+            "SELECT SUM(input_tokens + output_tokens) FROM token_usage
+             WHERE day = ?1 AND channel = ?2 AND (?3 IS NULL OR principal = ?3)",
+            params![day, channel, principal],
+            |row| row.get(0),
+        )?;
+        Ok(DailyUsage {
+            turns: to_u64(turns),
+            tool_calls: to_u64(tool_calls),
+            tokens: to_u64(tokens),
+        })
+    }
+
+    /// Daily budget of `principal`: its own entry in
+    /// `[cost.principal_daily_budgets]`, else `[cost.daily_budget]`.
+    pub fn principal_daily_budget(&self, principal: &str) -> DailyBudgetConfig {
+        self.config
+            .principal_daily_budgets
+            .get(principal)
+            .copied()
+            .unwrap_or(self.config.daily_budget)
+    }
+
+    /// The first daily budget `principal` or its channel has used up, if
+    /// any. Budgets lifted with [`Self::override_daily_budget`] are skipped.
+    pub fn check_daily_budget(
+        &self,
+        channel: &str,
+        principal: &str,
+    ) -> Result<Option<BudgetExhausted>> {
+        let budget = self.principal_daily_budget(principal);
+        if budget.is_limited() && !self.is_overridden(principal)? {
+            let usage = self.daily_usage(channel, Some(principal))?;
+            if let Some((metric, used, limit)) = usage.exhausted(&budget) {
+                return Ok(Some(BudgetExhausted {
+                    scope: principal.to_string(),
+                    metric,
+                    used,
+                    limit,
+                }));
+            }
+        }
+        if let Some(budget) = self.config.channel_daily_budgets.get(channel) {
+            if budget.is_limited() && !self.is_overridden(channel)? {
+                let usage = self.daily_usage(channel, None)?;
+                if let Some((metric, used, limit)) = usage.exhausted(budget) {
+                    return Ok(Some(BudgetExhausted {
+                        scope: channel.to_string(),
+                        metric,
+                        used,
+                        limit,
+                    }));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Percentage of a budget `principal` has used once it reaches
+    /// `[cost].downgrade_at_percent`: the higher of its own and its
+    /// channel's daily token budgets and its monthly spending limit.
+    /// Overridden daily budgets do not count.
+    pub fn budget_pressure(&self, channel: &str, principal: &str) -> Result<Option<u64>> {
+        let threshold = u64::from(self.config.downgrade_at_percent);
+        if threshold == 0 {
+            return Ok(None);
+        }
+        let mut used_percent = 0;
+        let own = self.principal_daily_budget(principal).max_tokens;
+        if own > 0 && !self.is_overridden(principal)? {
+            let tokens = self.daily_usage(channel, Some(principal))?.tokens;
+            used_percent = used_percent.max(tokens.saturating_mul(100) / own);
+        }
+        let shared = self
+            .config
+            .channel_daily_budgets
+            .get(channel)
+            .map_or(0, |budget| budget.max_tokens);
+        if shared > 0 && !self.is_overridden(channel)? {
+            let tokens = self.daily_usage(channel, None)?.tokens;
+            used_percent = used_percent.max(tokens.saturating_mul(100) / shared);
+        }
+        if let Some(limit) = self.principal_limit(principal) {
+            let spent = self.principal_month_cost(principal)?;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let spent_percent = (spent / limit * 100.0).floor().max(0.0) as u64;
+            used_percent = used_percent.max(spent_percent);
+        }
+        Ok((used_percent >= threshold).then_some(used_percent))
+    }
+
+    /// Lift the daily budgets of `scope` (a channel name or
+    /// `<channel>:<sender>`) for the rest of the current UTC day.
+    pub fn override_daily_budget(&self, scope: &str, granted_by: &str) -> Result<()> {
+        self.conn.lock().execute(
+            "INSERT OR REPLACE INTO budget_overrides (day, scope, granted_by, granted_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![today(), scope, granted_by, Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    fn is_overridden(&self, scope: &str) -> Result<bool> {
+        let count: i64 = self.conn.lock().query_row(
+            "SELECT COUNT(*) FROM budget_overrides WHERE day = ?1 AND scope = ?2",
+            params![today(), scope],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Count one `kind` event named `name` on `channel` today.
+    pub fn record_activity(&self, channel: &str, kind: ActivityKind, name: &str) -> Result<()> {
+        self.conn.lock().execute(
+            "INSERT INTO activity (day, channel, kind, name, count)
+             VALUES (?1, ?2, ?3, ?4, 1)
+             ON CONFLICT(day, channel, kind, name) DO UPDATE SET count = count + 1",
+            params![today(), channel, kind.as_str(), name],
+        )?;
+        Ok(())
+    }
+
+    /// Per-channel activity on `day`, busiest channel first.
+    pub fn channel_activity(&self, day: NaiveDate) -> Result<Vec<ChannelActivity>> {
+        let day = day.format("%Y-%m-%d").to_string();
+        let conn = self.conn.lock();
+        let mut channels: BTreeMap<String, ChannelActivity> = BTreeMap::new();
+
+        let mut stmt = conn.prepare(
+            "SELECT channel, SUM(turns) FROM turn_usage WHERE day = ?1 GROUP BY channel",
+        )?;
+        for row in stmt.query_map(params![day], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<i64>>(1)?))
+        })? {
+            let (channel, turns) = row?;
+            channel_entry(&mut channels, channel).turns = to_u64(turns);
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT channel, COUNT(*), SUM(cost_usd) FROM token_usage
+             WHERE day = ?1 GROUP BY channel",
+        )?;
+        for row in stmt.query_map(params![day], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, Option<f64>>(2)?,
+            ))
+        })? {
+            let (channel, requests, cost) = row?;
+            let activity = channel_entry(&mut channels, channel);
+            activity.requests = to_u64(Some(requests));
+            activity.cost_usd = cost.unwrap_or_default();
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT channel, kind, name, count FROM activity
+             WHERE day = ?1 ORDER BY count DESC, name",
+        )?;
+        for row in stmt.query_map(params![day], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })? {
+            let (channel, kind, name, count) = row?;
+            let activity = channel_entry(&mut channels, channel);
+            let list = match kind.as_str() {
+                "tool" => &mut activity.tools,
+                "tool_failure" => &mut activity.tool_failures,
+                "approval" => &mut activity.approvals,
+                "turn_failure" => &mut activity.turn_failures,
+                _ => continue,
+            };
+            list.push((name, to_u64(Some(count))));
+        }
+
+        let mut channels: Vec<ChannelActivity> = channels.into_values().collect();
+        channels.sort_by(|a, b| b.turns.cmp(&a.turns).then(b.requests.cmp(&a.requests)));
+        Ok(channels)
+    }
+
+    /// Whether the digest for `day` has been sent.
+    pub fn digest_sent(&self, day: NaiveDate) -> Result<bool> {
+        let count: i64 = self.conn.lock().query_row(
+            "SELECT COUNT(*) FROM digests_sent WHERE day = ?1",
+            params![day.format("%Y-%m-%d").to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Remember that the digest for `day` went out.
+    pub fn mark_digest_sent(&self, day: NaiveDate) -> Result<()> {
+        self.conn.lock().execute(
+            "INSERT OR REPLACE INTO digests_sent (day, sent_at) VALUES (?1, ?2)",
+            params![day.format("%Y-%m-%d").to_string(), Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn event<'a>(session: &'a str, principal: Option<&'a str>) -> UsageEvent<'a> {
+        UsageEvent {
+            session,
+            channel: "telegram",
+            principal,
+            provider: "test",
+            model: "unpriced-model",
+            input_tokens: 1_000_000,
+            output_tokens: 0,
+            estimated: false,
+        }
+    }
+
+    #[test]
+    fn report_aggregates_by_group() {
+        let tmp = TempDir::new().unwrap();
+        let ledger = UsageLedger::open(tmp.path(), &CostConfig::default()).unwrap();
+        ledger.record(&event("s1", Some("telegram:alice"))).unwrap();
+        ledger.record(&event("s1", Some("telegram:alice"))).unwrap();
+        ledger
+            .record(&UsageEvent {
+                estimated: true,
+                ..event("s2", None)
+            })
+            .unwrap();
+
+        let by_session = ledger.report(UsageGroup::Session, None).unwrap();
+        assert_eq!(by_session.len(), 2);
+        assert_eq!(by_session[0].key, "s1");
+        assert_eq!(by_session[0].requests, 2);
+        assert_eq!(by_session[0].input_tokens, 2_000_000);
+        assert!((by_session[0].cost_usd - 2.0 * DEFAULT_INPUT_PRICE_PER_MILLION).abs() < 1e-9);
+        assert_eq!(by_session[1].estimated_requests, 1);
+
+        let by_principal = ledger.report(UsageGroup::Principal, None).unwrap();
+        let keys: Vec<&str> = by_principal.iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(keys, vec!["telegram:alice", "-"]);
+
+        let tomorrow = Utc::now().date_naive().succ_opt().unwrap();
+        assert!(ledger
+            .report(UsageGroup::Day, Some(tomorrow))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn channel_activity_collects_turns_tools_and_cost() {
+        let tmp = TempDir::new().unwrap();
+        let ledger = UsageLedger::open(tmp.path(), &CostConfig::default()).unwrap();
+        ledger.record(&event("s1", Some("telegram:alice"))).unwrap();
+        ledger.record_turn("telegram", "telegram:alice").unwrap();
+        ledger.record_turn("telegram", "telegram:bob").unwrap();
+        ledger.record_turn("slack", "slack:carol").unwrap();
+        for name in ["shell", "shell", "file_read"] {
+            ledger
+                .record_activity("telegram", ActivityKind::Tool, name)
+                .unwrap();
+        }
+        ledger
+            .record_activity("telegram", ActivityKind::ToolFailure, "shell")
+            .unwrap();
+        ledger
+            .record_activity("telegram", ActivityKind::Approval, "no")
+            .unwrap();
+        ledger
+            .record_activity("slack", ActivityKind::TurnFailure, "timeout")
+            .unwrap();
+
+        let today = Utc::now().date_naive();
+        let activity = ledger.channel_activity(today).unwrap();
+        assert_eq!(activity.len(), 2);
+        let telegram = &activity[0];
+        assert_eq!(telegram.channel, "telegram");
+        assert_eq!(telegram.turns, 2);
+        assert_eq!(
+            telegram.tools,
+            vec![("shell".to_string(), 2), ("file_read".to_string(), 1)]
+        );
+        assert_eq!(telegram.tool_failures, vec![("shell".to_string(), 1)]);
+        assert_eq!(telegram.approvals, vec![("no".to_string(), 1)]);
+        assert_eq!(telegram.requests, 1);
+        assert!((telegram.cost_usd - DEFAULT_INPUT_PRICE_PER_MILLION).abs() < 1e-9);
+        assert_eq!(activity[1].turn_failures, vec![("timeout".to_string(), 1)]);
+
+        assert!(!ledger.digest_sent(today).unwrap());
+        ledger.mark_digest_sent(today).unwrap();
+        assert!(ledger.digest_sent(today).unwrap());
+        assert!(ledger
+            .channel_activity(today.pred_opt().unwrap())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn principal_budget_blocks_once_spent() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = CostConfig {
+            principal_monthly_limit_usd: 5.0,
+            ..CostConfig::default()
+        };
+        config.principal_limits.insert("telegram:bob".into(), 0.0);
+        let ledger = UsageLedger::open(tmp.path(), &config).unwrap();
+
+        ledger.check_principal_budget("telegram:alice").unwrap();
+        ledger.record(&event("s1", Some("telegram:alice"))).unwrap();
+        ledger.record(&event("s1", Some("telegram:alice"))).unwrap();
+        let err = ledger.check_principal_budget("telegram:alice").unwrap_err();
+        assert!(err.to_string().contains("Monthly budget exhausted"));
+
+        // A zero override disables the limit for that principal.
+        assert!(ledger.principal_limit("telegram:bob").is_none());
+        assert!(ledger.principal_limit("telegram:carol").is_some());
+    }
+
+    #[test]
+    fn daily_budgets_block_until_overridden() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = CostConfig {
+            daily_budget: DailyBudgetConfig {
+                max_turns: 2,
+                ..DailyBudgetConfig::default()
+            },
+            ..CostConfig::default()
+        };
+        config.channel_daily_budgets.insert(
+            "telegram".into(),
+            DailyBudgetConfig {
+                max_tool_calls: 5,
+                ..DailyBudgetConfig::default()
+            },
+        );
+        config.principal_daily_budgets.insert(
+            "telegram:bob".into(),
+            DailyBudgetConfig {
+                max_tokens: 500_000,
+                ..DailyBudgetConfig::default()
+            },
+        );
+        let ledger = UsageLedger::open(tmp.path(), &config).unwrap();
+
+        ledger.record_turn("telegram", "telegram:alice").unwrap();
+        assert!(ledger
+            .check_daily_budget("telegram", "telegram:alice")
+            .unwrap()
+            .is_none());
+        ledger.record_turn("telegram", "telegram:alice").unwrap();
+        let exhausted = ledger
+            .check_daily_budget("telegram", "telegram:alice")
+            .unwrap()
+            .unwrap();
+        assert_eq!(exhausted.scope, "telegram:alice");
+        assert_eq!((exhausted.metric, exhausted.used), ("turns", 2));
+
+        // Bob's own budget replaces the default one and caps tokens only.
+        ledger.record_turn("telegram", "telegram:bob").unwrap();
+        ledger.record_turn("telegram", "telegram:bob").unwrap();
+        ledger.record(&event("s1", Some("telegram:bob"))).unwrap();
+        let exhausted = ledger
+            .check_daily_budget("telegram", "telegram:bob")
+            .unwrap()
+            .unwrap();
+        assert_eq!(exhausted.metric, "tokens");
+
+        ledger
+            .override_daily_budget("telegram:bob", "admin")
+            .unwrap();
+        assert!(ledger
+            .check_daily_budget("telegram", "telegram:bob")
+            .unwrap()
+            .is_none());
+
+        // Tool calls of all principals count against the channel budget.
+        ledger
+            .record_tool_calls("telegram", "telegram:bob", 3)
+            .unwrap();
+        ledger
+            .record_tool_calls("telegram", "telegram:carol", 2)
+            .unwrap();
+        let exhausted = ledger
+            .check_daily_budget("telegram", "telegram:carol")
+            .unwrap()
+            .unwrap();
+        assert_eq!(exhausted.scope, "telegram");
+        assert_eq!((exhausted.used, exhausted.limit), (5, 5));
+        assert_eq!(ledger.daily_usage("telegram", None).unwrap().turns, 4);
+    }
+
+    #[test]
+    fn budget_pressure_reports_once_threshold_is_reached() {
+        let tmp = TempDir::new().unwrap();
+        let config = CostConfig {
+            daily_budget: DailyBudgetConfig {
+                max_tokens: 2_500_000,
+                ..DailyBudgetConfig::default()
+            },
+            ..CostConfig::default()
+        };
+        let ledger = UsageLedger::open(tmp.path(), &config).unwrap();
+
+        ledger.record(&event("s1", Some("telegram:alice"))).unwrap();
+        assert_eq!(
+            ledger
+                .budget_pressure("telegram", "telegram:alice")
+                .unwrap(),
+            None
+        );
+        ledger.record(&event("s1", Some("telegram:alice"))).unwrap();
+        assert_eq!(
+            ledger
+                .budget_pressure("telegram", "telegram:alice")
+                .unwrap(),
+            Some(80)
+        );
+        assert_eq!(
+            ledger.budget_pressure("telegram", "telegram:bob").unwrap(),
+            None
+        );
+
+        let disabled = UsageLedger::open(
+            tmp.path(),
+            &CostConfig {
+                downgrade_at_percent: 0,
+                ..config
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            disabled
+                .budget_pressure("telegram", "telegram:alice")
+                .unwrap(),
+            None
+        );
+    }
+}
@@ -15,8 +15,9 @@ pub use schedule::{
 };
 #[allow(unused_imports)]
 pub use store::{
-    add_agent_job, add_job, add_shell_job, due_jobs, get_job, list_jobs, list_runs,
-    record_last_run, record_run, remove_job, reschedule_after_run, update_job,
+    add_agent_job, add_job, add_shell_job, add_tool_job, due_jobs, get_job, list_jobs, list_runs,
+    record_last_run, record_run, remove_job, reschedule_after_run, sync_config_jobs, update_job,
+    SCHEMA,
 };
 pub use types::{CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget};
 
@@ -46,6 +47,14 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                     last_run,
                     last_status,
                 );
+                if job.job_type == JobType::Tool {
+                    println!(
+                        "    tool: {} {}",
+                        job.command,
+                        job.prompt.as_deref().unwrap_or("{}")
+                    );
+                    continue;
+                }
                 if !job.command.is_empty() {
                     println!("    cmd: {}", job.command);
                 }
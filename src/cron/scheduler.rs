@@ -7,7 +7,8 @@ use crate::channels::{
 use crate::config::Config;
 use crate::cron::{
     due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job, reschedule_after_run,
-    update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget,
+    sync_config_jobs, update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, Schedule,
+    SessionTarget,
 };
 use crate::security::SecurityPolicy;
 use anyhow::Result;
@@ -35,6 +36,12 @@ pub async fn run(config: Config) -> Result<()> {
         &config.workspace_dir,
     ));
 
+    match sync_config_jobs(&config) {
+        Ok(0) => {}
+        Ok(count) => tracing::info!("Synced {count} cron job(s) from [[cron.jobs]]"),
+        Err(e) => tracing::warn!("Failed to sync [[cron.jobs]] into the cron store: {e}"),
+    }
+
     crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
     loop {
@@ -52,6 +59,8 @@ pub async fn run(config: Config) -> Result<()> {
         };
 
         process_due_jobs(&config, &security, jobs, SCHEDULER_COMPONENT).await;
+        crate::feeds::poll_due(&config).await;
+        crate::analytics::send_due(&config).await;
     }
 }
 
@@ -73,6 +82,7 @@ async fn execute_job_with_retry(
         let (success, output) = match job.job_type {
             JobType::Shell => run_job_command(config, security, job).await,
             JobType::Agent => run_agent_job(config, security, job).await,
+            JobType::Tool => run_tool_job(config, security, job).await,
         };
         last_output = output;
 
@@ -200,6 +210,116 @@ async fn run_agent_job(
     }
 }
 
+async fn run_tool_job(config: &Config, security: &SecurityPolicy, job: &CronJob) -> (bool, String) {
+    if !security.can_act() {
+        return (
+            false,
+            "blocked by security policy: autonomy is read-only".to_string(),
+        );
+    }
+
+    if security.is_rate_limited() {
+        return (
+            false,
+            "blocked by security policy: rate limit exceeded".to_string(),
+        );
+    }
+
+    // Nobody is around to approve a scheduled call.
+    let tool_name = job.command.trim();
+    if crate::approval::ApprovalManager::from_config(&config.autonomy)
+        .needs_approval(tool_name, "cron")
+    {
+        return (
+            false,
+            format!(
+                "blocked by security policy: tool '{tool_name}' needs approval; add it to autonomy.auto_approve"
+            ),
+        );
+    }
+
+    let args: serde_json::Value = match job.prompt.as_deref().map(serde_json::from_str) {
+        Some(Ok(args)) => args,
+        Some(Err(e)) => return (false, format!("invalid tool arguments: {e}")),
+        None => serde_json::json!({}),
+    };
+
+    if !security.record_action() {
+        return (
+            false,
+            "blocked by security policy: action budget exhausted".to_string(),
+        );
+    }
+
+    let tools = match build_tool_registry(config) {
+        Ok(tools) => tools,
+        Err(e) => return (false, format!("tool job failed: {e}")),
+    };
+    let Some(tool) = tools.iter().find(|tool| tool.name() == tool_name) else {
+        return (
+            false,
+            format!("tool job failed: unknown tool '{tool_name}'"),
+        );
+    };
+
+    match tool.execute(args).await {
+        Ok(result) if result.success => (
+            true,
+            if result.output.trim().is_empty() {
+                format!("tool '{tool_name}' executed")
+            } else {
+                result.output
+            },
+        ),
+        Ok(result) => (
+            false,
+            result
+                .error
+                .unwrap_or_else(|| format!("tool '{tool_name}' reported failure")),
+        ),
+        Err(e) => (false, format!("tool job failed: {e}")),
+    }
+}
+
+fn build_tool_registry(config: &Config) -> Result<Vec<Box<dyn crate::tools::Tool>>> {
+    let security = Arc::new(SecurityPolicy::from_config(
+        &config.autonomy,
+        &config.workspace_dir,
+    ));
+    let memory: Arc<dyn crate::memory::Memory> =
+        Arc::from(crate::memory::create_memory_with_storage(
+            &config.memory,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?);
+    let runtime: Arc<dyn crate::runtime::RuntimeAdapter> =
+        Arc::from(crate::runtime::create_runtime(&config.runtime)?);
+    let (composio_key, composio_entity_id) = if config.composio.enabled {
+        (
+            config.composio.api_key.as_deref(),
+            Some(config.composio.entity_id.as_str()),
+        )
+    } else {
+        (None, None)
+    };
+    Ok(crate::tools::all_tools_with_runtime(
+        Arc::new(config.clone()),
+        &security,
+        runtime,
+        memory,
+        composio_key,
+        composio_entity_id,
+        &config.browser,
+        &config.http_request,
+        &config.web_fetch,
+        &config.workspace_dir,
+        &config.agents,
+        config.api_key.as_deref(),
+        config,
+    ))
+}
+
 async fn persist_job_result(
     config: &Config,
     job: &CronJob,
@@ -320,6 +440,8 @@ pub(crate) async fn deliver_announcement(
     output: &str,
 ) -> Result<()> {
     let normalized = channel.to_ascii_lowercase();
+    let rendered = crate::channels::render::render_for_channel(&normalized, output);
+    let output = rendered.as_str();
     match normalized.as_str() {
         "telegram" => {
             let tg = config
@@ -461,6 +583,14 @@ pub(crate) async fn deliver_announcement(
             let channel = EmailChannel::new(email.clone());
             channel.send(&SendMessage::new(output, target)).await?;
         }
+        "notify" => {
+            crate::notify::send(
+                &config.notify,
+                Some(target),
+                &crate::notify::Notification::new(output),
+            )
+            .await?;
+        }
         other => anyhow::bail!("unsupported delivery channel: {other}"),
     }
 
@@ -679,6 +809,38 @@ mod tests {
         assert!(output.contains("job timed out after"));
     }
 
+    #[tokio::test]
+    async fn run_tool_job_invokes_auto_approved_tool() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        tokio::fs::write(config.workspace_dir.join("notes.txt"), "standup at 9")
+            .await
+            .unwrap();
+        let mut job = test_job("file_read");
+        job.job_type = JobType::Tool;
+        job.prompt = Some(r#"{"path":"notes.txt"}"#.into());
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let (success, output) = run_tool_job(&config, &security, &job).await;
+        assert!(success, "{output}");
+        assert!(output.contains("standup at 9"));
+    }
+
+    #[tokio::test]
+    async fn run_tool_job_blocks_tools_that_need_approval() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let mut job = test_job("shell");
+        job.job_type = JobType::Tool;
+        job.prompt = Some(r#"{"command":"echo hi"}"#.into());
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let (success, output) = run_tool_job(&config, &security, &job).await;
+        assert!(!success);
+        assert!(output.starts_with("blocked by security policy"));
+        assert!(output.contains("autonomy.auto_approve"));
+    }
+
     #[tokio::test]
     async fn run_job_command_blocks_disallowed_command() {
         let tmp = TempDir::new().unwrap();
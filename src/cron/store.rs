@@ -3,6 +3,7 @@ use crate::cron::{
     next_run_for_schedule, schedule_cron_expression, validate_schedule, CronJob, CronJobPatch,
     CronRun, DeliveryConfig, JobType, Schedule, SessionTarget,
 };
+use crate::db::{add_column_if_missing, migrate, Migration, Schema, Step};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::types::{FromSqlResult, ValueRef};
@@ -12,6 +13,78 @@ use uuid::Uuid;
 const MAX_CRON_OUTPUT_BYTES: usize = 16 * 1024;
 const TRUNCATED_OUTPUT_MARKER: &str = "\n...[truncated]";
 
+/// Tables of `cron/jobs.db`.
+pub static SCHEMA: Schema = Schema {
+    name: "cron",
+    path: "cron/jobs.db",
+    migrations: &[
+        Migration {
+            version: 1,
+            description: "create cron_jobs and cron_runs",
+            step: Step::Sql(
+                "CREATE TABLE IF NOT EXISTS cron_jobs (
+                     id               TEXT PRIMARY KEY,
+                     expression       TEXT NOT NULL,
+                     command          TEXT NOT NULL,
+                     schedule         TEXT,
+                     job_type         TEXT NOT NULL DEFAULT 'shell',
+                     prompt           TEXT,
+                     name             TEXT,
+                     session_target   TEXT NOT NULL DEFAULT 'isolated',
+                     model            TEXT,
+                     enabled          INTEGER NOT NULL DEFAULT 1,
+                     delivery         TEXT,
+                     delete_after_run INTEGER NOT NULL DEFAULT 0,
+                     created_at       TEXT NOT NULL,
+                     next_run         TEXT NOT NULL,
+                     last_run         TEXT,
+                     last_status      TEXT,
+                     last_output      TEXT
+                 );
+                 CREATE INDEX IF NOT EXISTS idx_cron_jobs_next_run ON cron_jobs(next_run);
+
+                 CREATE TABLE IF NOT EXISTS cron_runs (
+                     id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                     job_id      TEXT NOT NULL,
+                     started_at  TEXT NOT NULL,
+                     finished_at TEXT NOT NULL,
+                     status      TEXT NOT NULL,
+                     output      TEXT,
+                     duration_ms INTEGER,
+                     FOREIGN KEY (job_id) REFERENCES cron_jobs(id) ON DELETE CASCADE
+                 );
+                 CREATE INDEX IF NOT EXISTS idx_cron_runs_job_id ON cron_runs(job_id);
+                 CREATE INDEX IF NOT EXISTS idx_cron_runs_started_at ON cron_runs(started_at);
+                 CREATE INDEX IF NOT EXISTS idx_cron_runs_job_started ON cron_runs(job_id, started_at);",
+            ),
+        },
+        Migration {
+            version: 2,
+            description: "add cron_jobs schedule, job type, agent and delivery columns",
+            step: Step::Rust(add_cron_job_columns),
+        },
+    ],
+};
+
+/// Columns added to `cron_jobs` after its first release; databases created
+/// before them are upgraded in place.
+fn add_cron_job_columns(conn: &Connection) -> Result<()> {
+    for (column, declaration) in [
+        ("schedule", "TEXT"),
+        ("job_type", "TEXT NOT NULL DEFAULT 'shell'"),
+        ("prompt", "TEXT"),
+        ("name", "TEXT"),
+        ("session_target", "TEXT NOT NULL DEFAULT 'isolated'"),
+        ("model", "TEXT"),
+        ("enabled", "INTEGER NOT NULL DEFAULT 1"),
+        ("delivery", "TEXT"),
+        ("delete_after_run", "INTEGER NOT NULL DEFAULT 0"),
+    ] {
+        add_column_if_missing(conn, "cron_jobs", column, declaration)?;
+    }
+    Ok(())
+}
+
 impl rusqlite::types::FromSql for JobType {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
         let text = value.as_str()?;
@@ -113,6 +186,198 @@ pub fn add_agent_job(
     get_job(config, &id)
 }
 
+pub fn add_tool_job(
+    config: &Config,
+    name: Option<String>,
+    schedule: Schedule,
+    tool: &str,
+    args: &serde_json::Value,
+    delivery: Option<DeliveryConfig>,
+    delete_after_run: bool,
+) -> Result<CronJob> {
+    insert_job(
+        config,
+        &Uuid::new_v4().to_string(),
+        JobType::Tool,
+        name,
+        schedule,
+        tool,
+        Some(serde_json::to_string(args)?),
+        None,
+        delivery.unwrap_or_default(),
+        delete_after_run,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn insert_job(
+    config: &Config,
+    id: &str,
+    job_type: JobType,
+    name: Option<String>,
+    schedule: Schedule,
+    command: &str,
+    prompt: Option<String>,
+    model: Option<String>,
+    delivery: DeliveryConfig,
+    delete_after_run: bool,
+) -> Result<CronJob> {
+    let now = Utc::now();
+    validate_schedule(&schedule, now)?;
+    let next_run = next_run_for_schedule(&schedule, now)?;
+    let expression = schedule_cron_expression(&schedule).unwrap_or_default();
+    let schedule_json = serde_json::to_string(&schedule)?;
+
+    with_connection(config, |conn| {
+        conn.execute(
+            "INSERT INTO cron_jobs (
+                id, expression, command, schedule, job_type, prompt, name, session_target, model,
+                enabled, delivery, delete_after_run, created_at, next_run
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 'isolated', ?8, 1, ?9, ?10, ?11, ?12)",
+            params![
+                id,
+                expression,
+                command,
+                schedule_json,
+                <JobType as Into<&str>>::into(job_type),
+                prompt,
+                name,
+                model,
+                serde_json::to_string(&delivery)?,
+                if delete_after_run { 1 } else { 0 },
+                now.to_rfc3339(),
+                next_run.to_rfc3339(),
+            ],
+        )
+        .context("Failed to insert cron job")?;
+        Ok(())
+    })?;
+
+    get_job(config, id)
+}
+
+/// Id prefix for jobs declared in `[[cron.jobs]]`.
+pub(crate) const CONFIG_JOB_ID_PREFIX: &str = "config:";
+
+/// Bring jobs declared in `[[cron.jobs]]` into the store: new entries are
+/// inserted, changed ones updated in place (keeping run history and, unless
+/// the schedule changed, the next run time), and removed ones deleted.
+/// Returns how many declared jobs are active.
+pub fn sync_config_jobs(config: &Config) -> Result<usize> {
+    let mut declared = std::collections::HashSet::new();
+    for entry in &config.cron.jobs {
+        let id = format!("{CONFIG_JOB_ID_PREFIX}{}", entry.name.trim());
+        declared.insert(id.clone());
+
+        let schedule = match (&entry.cron, entry.every_secs) {
+            (Some(expr), _) => Schedule::Cron {
+                expr: expr.clone(),
+                tz: entry.tz.clone(),
+            },
+            (None, Some(secs)) => Schedule::Every {
+                every_ms: secs.saturating_mul(1000),
+            },
+            (None, None) => anyhow::bail!("cron.jobs '{}' has no schedule", entry.name),
+        };
+        let (job_type, command, prompt) = if let Some(tool) = &entry.tool {
+            let args = entry.args.clone().unwrap_or_else(|| serde_json::json!({}));
+            (
+                JobType::Tool,
+                tool.clone(),
+                Some(serde_json::to_string(&args)?),
+            )
+        } else if let Some(prompt) = &entry.prompt {
+            (JobType::Agent, String::new(), Some(prompt.clone()))
+        } else {
+            (
+                JobType::Shell,
+                entry.command.clone().unwrap_or_default(),
+                None,
+            )
+        };
+        let delivery = match &entry.channel {
+            Some(channel) => DeliveryConfig {
+                mode: "announce".into(),
+                channel: Some(channel.clone()),
+                to: entry.to.clone(),
+                best_effort: true,
+            },
+            None => DeliveryConfig::default(),
+        };
+
+        let Ok(existing) = get_job(config, &id) else {
+            insert_job(
+                config,
+                &id,
+                job_type,
+                Some(entry.name.clone()),
+                schedule,
+                &command,
+                prompt,
+                entry.model.clone(),
+                delivery,
+                false,
+            )?;
+            if !entry.enabled {
+                update_job(
+                    config,
+                    &id,
+                    CronJobPatch {
+                        enabled: Some(false),
+                        ..CronJobPatch::default()
+                    },
+                )?;
+            }
+            continue;
+        };
+
+        let unchanged = existing.schedule == schedule
+            && existing.job_type == job_type
+            && existing.command == command
+            && existing.prompt == prompt
+            && existing.model == entry.model
+            && existing.delivery == delivery
+            && existing.enabled == entry.enabled;
+        if unchanged {
+            continue;
+        }
+        update_job(
+            config,
+            &id,
+            CronJobPatch {
+                schedule: (existing.schedule != schedule).then_some(schedule),
+                job_type: Some(job_type),
+                command: Some(command),
+                prompt,
+                name: Some(entry.name.clone()),
+                enabled: Some(entry.enabled),
+                delivery: Some(delivery),
+                model: entry.model.clone(),
+                ..CronJobPatch::default()
+            },
+        )?;
+    }
+
+    with_connection(config, |conn| {
+        let mut stmt = conn.prepare("SELECT id FROM cron_jobs WHERE id LIKE ?1")?;
+        let stale: Vec<String> = stmt
+            .query_map(params![format!("{CONFIG_JOB_ID_PREFIX}%")], |row| {
+                row.get(0)
+            })?
+            .collect::<rusqlite::Result<Vec<String>>>()?
+            .into_iter()
+            .filter(|id| !declared.contains(id))
+            .collect();
+        for id in stale {
+            conn.execute("DELETE FROM cron_jobs WHERE id = ?1", params![id])
+                .context("Failed to delete stale config cron job")?;
+        }
+        Ok(())
+    })?;
+
+    Ok(declared.len())
+}
+
 pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
@@ -195,6 +460,9 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
         job.expression = schedule_cron_expression(&job.schedule).unwrap_or_default();
         schedule_changed = true;
     }
+    if let Some(job_type) = patch.job_type {
+        job.job_type = job_type;
+    }
     if let Some(command) = patch.command {
         job.command = command;
     }
@@ -483,38 +751,8 @@ fn decode_delivery(delivery_raw: Option<&str>) -> Result<DeliveryConfig> {
     Ok(DeliveryConfig::default())
 }
 
-fn add_column_if_missing(conn: &Connection, name: &str, sql_type: &str) -> Result<()> {
-    let mut stmt = conn.prepare("PRAGMA table_info(cron_jobs)")?;
-    let mut rows = stmt.query([])?;
-    while let Some(row) = rows.next()? {
-        let col_name: String = row.get(1)?;
-        if col_name == name {
-            return Ok(());
-        }
-    }
-    // Drop the statement/rows before executing ALTER to release any locks
-    drop(rows);
-    drop(stmt);
-
-    // Tolerate "duplicate column name" errors to handle the race where
-    // another process adds the column between our PRAGMA check and ALTER.
-    match conn.execute(
-        &format!("ALTER TABLE cron_jobs ADD COLUMN {name} {sql_type}"),
-        [],
-    ) {
-        Ok(_) => Ok(()),
-        Err(rusqlite::Error::SqliteFailure(err, Some(ref msg)))
-            if msg.contains("duplicate column name") =>
-        {
-            tracing::debug!("Column cron_jobs.{name} already exists (concurrent migration): {err}");
-            Ok(())
-        }
-        Err(e) => Err(e).with_context(|| format!("Failed to add cron_jobs.{name}")),
-    }
-}
-
 fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
-    let db_path = config.workspace_dir.join("cron").join("jobs.db");
+    let db_path = SCHEMA.db_path(&config.workspace_dir);
     if let Some(parent) = db_path.parent() {
         std::fs::create_dir_all(parent)
             .with_context(|| format!("Failed to create cron directory: {}", parent.display()))?;
@@ -523,54 +761,8 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
     let conn = Connection::open(&db_path)
         .with_context(|| format!("Failed to open cron DB: {}", db_path.display()))?;
 
-    conn.execute_batch(
-        "PRAGMA foreign_keys = ON;
-         CREATE TABLE IF NOT EXISTS cron_jobs (
-            id               TEXT PRIMARY KEY,
-            expression       TEXT NOT NULL,
-            command          TEXT NOT NULL,
-            schedule         TEXT,
-            job_type         TEXT NOT NULL DEFAULT 'shell',
-            prompt           TEXT,
-            name             TEXT,
-            session_target   TEXT NOT NULL DEFAULT 'isolated',
-            model            TEXT,
-            enabled          INTEGER NOT NULL DEFAULT 1,
-            delivery         TEXT,
-            delete_after_run INTEGER NOT NULL DEFAULT 0,
-            created_at       TEXT NOT NULL,
-            next_run         TEXT NOT NULL,
-            last_run         TEXT,
-            last_status      TEXT,
-            last_output      TEXT
-        );
-        CREATE INDEX IF NOT EXISTS idx_cron_jobs_next_run ON cron_jobs(next_run);
-
-        CREATE TABLE IF NOT EXISTS cron_runs (
-            id          INTEGER PRIMARY KEY AUTOINCREMENT,
-            job_id      TEXT NOT NULL,
-            started_at  TEXT NOT NULL,
-            finished_at TEXT NOT NULL,
-            status      TEXT NOT NULL,
-            output      TEXT,
-            duration_ms INTEGER,
-            FOREIGN KEY (job_id) REFERENCES cron_jobs(id) ON DELETE CASCADE
-        );
-        CREATE INDEX IF NOT EXISTS idx_cron_runs_job_id ON cron_runs(job_id);
-        CREATE INDEX IF NOT EXISTS idx_cron_runs_started_at ON cron_runs(started_at);
-        CREATE INDEX IF NOT EXISTS idx_cron_runs_job_started ON cron_runs(job_id, started_at);",
-    )
-    .context("Failed to initialize cron schema")?;
-
-    add_column_if_missing(&conn, "schedule", "TEXT")?;
-    add_column_if_missing(&conn, "job_type", "TEXT NOT NULL DEFAULT 'shell'")?;
-    add_column_if_missing(&conn, "prompt", "TEXT")?;
-    add_column_if_missing(&conn, "name", "TEXT")?;
-    add_column_if_missing(&conn, "session_target", "TEXT NOT NULL DEFAULT 'isolated'")?;
-    add_column_if_missing(&conn, "model", "TEXT")?;
-    add_column_if_missing(&conn, "enabled", "INTEGER NOT NULL DEFAULT 1")?;
-    add_column_if_missing(&conn, "delivery", "TEXT")?;
-    add_column_if_missing(&conn, "delete_after_run", "INTEGER NOT NULL DEFAULT 0")?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    migrate(&conn, &SCHEMA).context("Failed to initialize cron schema")?;
 
     f(&conn)
 }
@@ -629,6 +821,65 @@ mod tests {
         assert!(!recurring.delete_after_run);
     }
 
+    #[test]
+    fn sync_config_jobs_inserts_updates_and_removes() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        config.cron.jobs = vec![
+            crate::config::CronJobConfig {
+                name: "digest".into(),
+                cron: Some("0 9 * * *".into()),
+                tz: None,
+                every_secs: None,
+                prompt: Some("Summarize my issues".into()),
+                tool: None,
+                args: None,
+                command: None,
+                model: None,
+                channel: Some("slack".into()),
+                to: Some("C123".into()),
+                enabled: true,
+            },
+            crate::config::CronJobConfig {
+                name: "recall".into(),
+                cron: None,
+                tz: None,
+                every_secs: Some(600),
+                prompt: None,
+                tool: Some("memory_recall".into()),
+                args: Some(serde_json::json!({"query": "todo"})),
+                command: None,
+                model: None,
+                channel: None,
+                to: None,
+                enabled: true,
+            },
+        ];
+        add_job(&config, "*/5 * * * *", "echo manual").unwrap();
+
+        assert_eq!(sync_config_jobs(&config).unwrap(), 2);
+        let digest = get_job(&config, "config:digest").unwrap();
+        assert_eq!(digest.job_type, JobType::Agent);
+        assert_eq!(digest.delivery.mode, "announce");
+        assert_eq!(digest.delivery.to.as_deref(), Some("C123"));
+        let recall = get_job(&config, "config:recall").unwrap();
+        assert_eq!(recall.job_type, JobType::Tool);
+        assert_eq!(recall.schedule, Schedule::Every { every_ms: 600_000 });
+
+        // Unchanged entries keep their next run; edits apply in place.
+        let next_run = digest.next_run;
+        config.cron.jobs[0].prompt = Some("Summarize my PRs".into());
+        config.cron.jobs.truncate(1);
+        assert_eq!(sync_config_jobs(&config).unwrap(), 1);
+        let digest = get_job(&config, "config:digest").unwrap();
+        assert_eq!(digest.prompt.as_deref(), Some("Summarize my PRs"));
+        assert_eq!(digest.next_run, next_run);
+        assert!(get_job(&config, "config:recall").is_err());
+
+        // Jobs added outside the config are left alone.
+        assert_eq!(list_jobs(&config).unwrap().len(), 2);
+    }
+
     #[test]
     fn add_list_remove_roundtrip() {
         let tmp = TempDir::new().unwrap();
@@ -7,6 +7,9 @@ pub enum JobType {
     #[default]
     Shell,
     Agent,
+    /// Invoke one tool directly: `command` holds the tool name and `prompt`
+    /// its JSON arguments.
+    Tool,
 }
 
 impl From<JobType> for &'static str {
@@ -14,6 +17,7 @@ impl From<JobType> for &'static str {
         match value {
             JobType::Shell => "shell",
             JobType::Agent => "agent",
+            JobType::Tool => "tool",
         }
     }
 }
@@ -25,8 +29,9 @@ impl TryFrom<&str> for JobType {
         match value.to_lowercase().as_str() {
             "shell" => Ok(JobType::Shell),
             "agent" => Ok(JobType::Agent),
+            "tool" => Ok(JobType::Tool),
             _ => Err(format!(
-                "Invalid job type '{}'. Expected one of: 'shell', 'agent'",
+                "Invalid job type '{}'. Expected one of: 'shell', 'agent', 'tool'",
                 value
             )),
         }
@@ -136,6 +141,7 @@ pub struct CronRun {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CronJobPatch {
     pub schedule: Option<Schedule>,
+    pub job_type: Option<JobType>,
     pub command: Option<String>,
     pub prompt: Option<String>,
     pub name: Option<String>,
@@ -156,6 +162,7 @@ mod tests {
         assert_eq!(JobType::try_from("SHELL").unwrap(), JobType::Shell);
         assert_eq!(JobType::try_from("agent").unwrap(), JobType::Agent);
         assert_eq!(JobType::try_from("AgEnT").unwrap(), JobType::Agent);
+        assert_eq!(JobType::try_from("tool").unwrap(), JobType::Tool);
     }
 
     #[test]
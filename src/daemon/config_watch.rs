@@ -0,0 +1,315 @@
+//! Config hot-reload for the daemon.
+//!
+//! The watcher polls `config.toml` and its overlay files (see
+//! [`crate::config::layers`]) for mtime/size changes, the same check the
+//! channel runtime already uses for provider and approval settings, and
+//! reloads the config when one changes. The reloaded config is diffed against
+//! the running one:
+//!
+//! - provider, model, reliability and approval settings are picked up by the
+//!   channel runtime on the next message, so nothing else happens;
+//! - channels, allowlists, tokens and tool settings are read when the channel
+//!   runtime starts, so the new config is published and the runtime is
+//!   rebuilt (in-flight turns drain first);
+//! - sections only read at daemon start are logged as needing a restart.
+//!
+//! An invalid file is reported and ignored; the running config stays active.
+
+use crate::config::Config;
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::time::SystemTime;
+use tokio::sync::watch;
+use tokio::time::{Duration, MissedTickBehavior};
+
+const POLL_INTERVAL_SECS: u64 = 2;
+
+/// Top-level keys the channel runtime re-reads on every message.
+const LIVE_KEYS: &[&str] = &[
+    "api_key",
+    "api_url",
+    "default_provider",
+    "default_model",
+    "default_temperature",
+    "reliability",
+];
+
+/// `[autonomy]` keys applied live through the approval manager.
+const LIVE_AUTONOMY_KEYS: &[&str] = &[
+    "auto_approve",
+    "always_ask",
+    "approval_policy_by_channel",
+    "non_cli_excluded_tools",
+    "non_cli_approval_approvers",
+    "non_cli_natural_language_approval_mode",
+    "non_cli_natural_language_approval_mode_by_channel",
+];
+
+/// `[security]` keys applied live.
+const LIVE_SECURITY_KEYS: &[&str] = &["perplexity_filter"];
+
+/// Sections only read when the daemon starts.
+const RESTART_KEYS: &[&str] = &["gateway", "tunnel", "heartbeat", "cron", "secrets"];
+
+/// What changed between the running config and a reloaded one.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ConfigDiff {
+    pub channels_added: Vec<String>,
+    pub channels_removed: Vec<String>,
+    pub channels_changed: Vec<String>,
+    /// Other keys read when the channel runtime starts (tools, memory, ...).
+    pub rebuild_keys: Vec<String>,
+    /// Keys the channel runtime already applies per message.
+    pub live_keys: Vec<String>,
+    /// Keys that take effect only after a daemon restart.
+    pub restart_keys: Vec<String>,
+}
+
+impl ConfigDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn requires_channel_rebuild(&self) -> bool {
+        !self.channels_added.is_empty()
+            || !self.channels_removed.is_empty()
+            || !self.channels_changed.is_empty()
+            || !self.rebuild_keys.is_empty()
+    }
+
+    fn summary(&self) -> String {
+        let parts = [
+            ("channels added", &self.channels_added),
+            ("channels removed", &self.channels_removed),
+            ("channels changed", &self.channels_changed),
+            ("rebuild", &self.rebuild_keys),
+            ("live", &self.live_keys),
+            ("restart required", &self.restart_keys),
+        ];
+        parts
+            .iter()
+            .filter(|(_, keys)| !keys.is_empty())
+            .map(|(label, keys)| format!("{label}: {}", keys.join(", ")))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Compare two configs section by section.
+pub(crate) fn diff_configs(running: &Config, reloaded: &Config) -> ConfigDiff {
+    let (Ok(Value::Object(mut old)), Ok(Value::Object(mut new))) = (
+        serde_json::to_value(running),
+        serde_json::to_value(reloaded),
+    ) else {
+        // Cannot compare; rebuild so nothing is silently left stale.
+        return ConfigDiff {
+            rebuild_keys: vec!["config".into()],
+            ..ConfigDiff::default()
+        };
+    };
+
+    let mut diff = ConfigDiff::default();
+    for (section, live) in [
+        ("autonomy", LIVE_AUTONOMY_KEYS),
+        ("security", LIVE_SECURITY_KEYS),
+    ] {
+        for key in live {
+            let before = take_nested(&mut old, section, key);
+            let after = take_nested(&mut new, section, key);
+            if before != after {
+                diff.live_keys.push(format!("{section}.{key}"));
+            }
+        }
+    }
+
+    let old_channels = take_object(&mut old, "channels_config");
+    let new_channels = take_object(&mut new, "channels_config");
+    for key in union_keys(&old_channels, &new_channels) {
+        let before = old_channels.get(key).filter(|v| !v.is_null());
+        let after = new_channels.get(key).filter(|v| !v.is_null());
+        match (before, after) {
+            (None, Some(Value::Object(_))) => diff.channels_added.push(key.clone()),
+            (Some(Value::Object(_)), None) => diff.channels_removed.push(key.clone()),
+            (Some(Value::Object(a)), Some(Value::Object(b))) if a != b => {
+                diff.channels_changed.push(key.clone());
+            }
+            (a, b) if a != b => diff.rebuild_keys.push(format!("channels_config.{key}")),
+            _ => {}
+        }
+    }
+
+    for key in union_keys(&old, &new) {
+        if old.get(key) == new.get(key) {
+            continue;
+        }
+        let bucket = if LIVE_KEYS.contains(&key.as_str()) {
+            &mut diff.live_keys
+        } else if RESTART_KEYS.contains(&key.as_str()) {
+            &mut diff.restart_keys
+        } else {
+            &mut diff.rebuild_keys
+        };
+        bucket.push(key.clone());
+    }
+    diff
+}
+
+fn take_object(map: &mut Map<String, Value>, key: &str) -> Map<String, Value> {
+    match map.remove(key) {
+        Some(Value::Object(inner)) => inner,
+        _ => Map::new(),
+    }
+}
+
+fn take_nested(map: &mut Map<String, Value>, section: &str, key: &str) -> Option<Value> {
+    map.get_mut(section)?.as_object_mut()?.remove(key)
+}
+
+fn union_keys<'a>(a: &'a Map<String, Value>, b: &'a Map<String, Value>) -> BTreeSet<&'a String> {
+    a.keys().chain(b.keys()).collect()
+}
+
+async fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Stamps of `config.toml` and its overlay files; `None` while `config.toml`
+/// itself is missing (for example mid-replace).
+async fn layered_stamp(path: &Path) -> Option<Vec<Option<(SystemTime, u64)>>> {
+    let mut stamps = vec![Some(file_stamp(path).await?)];
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let profile = crate::config::layers::active_profile();
+    for file in crate::config::layers::overlay_files(dir, profile.as_deref()) {
+        stamps.push(file_stamp(&file).await);
+    }
+    Some(stamps)
+}
+
+/// Poll the config file and publish reloaded configs that need the channel
+/// runtime rebuilt. Runs until the daemon aborts it.
+pub(crate) async fn watch_config(mut running: Config, tx: watch::Sender<Config>) {
+    let path = running.config_path.clone();
+    let mut stamp = layered_stamp(&path).await;
+    let mut interval = tokio::time::interval(Duration::from_secs(POLL_INTERVAL_SECS));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        interval.tick().await;
+        let latest = layered_stamp(&path).await;
+        if latest.is_none() || latest == stamp {
+            continue;
+        }
+        stamp = latest;
+
+        let reloaded = match Config::load_from_file(&path, running.workspace_dir.clone()).await {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::warn!(
+                    "Ignoring changed config {}; keeping the running config: {e:#}",
+                    path.display()
+                );
+                continue;
+            }
+        };
+        let diff = diff_configs(&running, &reloaded);
+        if diff.is_empty() {
+            continue;
+        }
+
+        tracing::info!(
+            "Config reloaded from {}: {}",
+            path.display(),
+            diff.summary()
+        );
+        if !diff.restart_keys.is_empty() {
+            tracing::warn!(
+                "Changes to {} take effect after the daemon restarts",
+                diff.restart_keys.join(", ")
+            );
+        }
+        if diff.requires_channel_rebuild() {
+            tx.send_replace(reloaded.clone());
+        }
+        running = reloaded;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TelegramConfig;
+
+    fn telegram(token: &str, users: &[&str]) -> TelegramConfig {
+        let users = users
+            .iter()
+            .map(|u| format!("\"{u}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+        toml::from_str(&format!(
+            "bot_token = \"{token}\"\nallowed_users = [{users}]"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn identical_configs_have_no_diff() {
+        let config = Config::default();
+        assert!(diff_configs(&config, &config.clone()).is_empty());
+    }
+
+    #[test]
+    fn channel_changes_require_rebuild() {
+        let mut running = Config::default();
+        running.channels_config.telegram = Some(telegram("old-token", &["alice"]));
+
+        let mut reloaded = running.clone();
+        reloaded.channels_config.telegram = Some(telegram("new-token", &["alice", "bob"]));
+        reloaded.channels_config.discord =
+            Some(toml::from_str("bot_token = \"d\"\nallowed_users = [\"*\"]").unwrap());
+
+        let diff = diff_configs(&running, &reloaded);
+        assert_eq!(diff.channels_added, vec!["discord".to_string()]);
+        assert_eq!(diff.channels_changed, vec!["telegram".to_string()]);
+        assert!(diff.requires_channel_rebuild());
+
+        let diff = diff_configs(&reloaded, &running);
+        assert_eq!(diff.channels_removed, vec!["discord".to_string()]);
+    }
+
+    #[test]
+    fn live_and_restart_only_changes_keep_channels_running() {
+        let running = Config::default();
+        let mut reloaded = running.clone();
+        reloaded.default_model = Some("other-model".into());
+        reloaded.autonomy.auto_approve.push("shell".into());
+        reloaded.gateway.port += 1;
+
+        let diff = diff_configs(&running, &reloaded);
+        assert_eq!(
+            diff.live_keys,
+            vec![
+                "autonomy.auto_approve".to_string(),
+                "default_model".to_string()
+            ]
+        );
+        assert_eq!(diff.restart_keys, vec!["gateway".to_string()]);
+        assert!(!diff.requires_channel_rebuild());
+    }
+
+    #[test]
+    fn tool_settings_require_rebuild() {
+        let running = Config::default();
+        let mut reloaded = running.clone();
+        reloaded.web_fetch.enabled = !running.web_fetch.enabled;
+        reloaded.autonomy.max_actions_per_hour += 1;
+
+        let diff = diff_configs(&running, &reloaded);
+        assert_eq!(
+            diff.rebuild_keys,
+            vec!["autonomy".to_string(), "web_fetch".to_string()]
+        );
+        assert!(diff.requires_channel_rebuild());
+    }
+}
@@ -1,13 +1,45 @@
+mod config_watch;
+
 use crate::config::Config;
 use anyhow::{bail, Result};
 use chrono::Utc;
 use std::future::Future;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+use tokio::sync::watch;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
+use tokio_util::sync::CancellationToken;
 
 const STATUS_FLUSH_SECONDS: u64 = 5;
 
+/// Extra time past `shutdown_grace_secs` for draining components to persist
+/// state after cancelling leftover work.
+const SHUTDOWN_FLUSH_SECONDS: u64 = 5;
+
+/// Process-wide shutdown signal, cancelled once the daemon receives
+/// SIGINT/SIGTERM. The gateway and channel runtime watch it to stop taking
+/// new work and drain what is in flight.
+pub fn shutdown_token() -> CancellationToken {
+    static TOKEN: OnceLock<CancellationToken> = OnceLock::new();
+    TOKEN.get_or_init(CancellationToken::new).clone()
+}
+
+async fn wait_for_shutdown_signal() -> Result<()> {
+    #[cfg(unix)]
+    {
+        let mut sigterm =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result?,
+            _ = sigterm.recv() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    tokio::signal::ctrl_c().await?;
+    Ok(())
+}
+
 pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     // Pre-flight: check if port is already in use by another zeroclaw daemon
     if let Err(_e) = check_port_available(&host, port).await {
@@ -33,21 +65,31 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
 
     crate::health::mark_component_ok("daemon");
 
+    // Incidents the previous process could not report before it went down.
+    {
+        let config = config.clone();
+        tokio::spawn(async move { crate::observability::incident::notify_pending(&config).await });
+    }
+
     if config.heartbeat.enabled {
         let _ =
             crate::heartbeat::engine::HeartbeatEngine::ensure_heartbeat_file(&config.workspace_dir)
                 .await;
     }
 
+    let shutdown = shutdown_token();
     let mut handles: Vec<JoinHandle<()>> = vec![spawn_state_writer(config.clone())];
+    // Components that drain in-flight work on shutdown instead of being aborted.
+    let mut draining: Vec<JoinHandle<()>> = Vec::new();
 
     {
         let gateway_cfg = config.clone();
         let gateway_host = host.clone();
-        handles.push(spawn_component_supervisor(
+        draining.push(spawn_component_supervisor(
             "gateway",
             initial_backoff,
             max_backoff,
+            shutdown.clone(),
             move || {
                 let cfg = gateway_cfg.clone();
                 let host = gateway_host.clone();
@@ -57,21 +99,32 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     }
 
     {
-        if has_supervised_channels(&config) {
-            let channels_cfg = config.clone();
-            handles.push(spawn_component_supervisor(
-                "channels",
-                initial_backoff,
-                max_backoff,
-                move || {
-                    let cfg = channels_cfg.clone();
-                    async move { crate::channels::start_channels(cfg).await }
-                },
-            ));
-        } else {
-            crate::health::mark_component_ok("channels");
-            tracing::info!("No real-time channels configured; channel supervisor disabled");
-        }
+        // Channels are always supervised so a config reload can add them later.
+        let (config_tx, config_rx) = tokio::sync::watch::channel(config.clone());
+        handles.push(tokio::spawn(config_watch::watch_config(
+            config.clone(),
+            config_tx,
+        )));
+        let channels_shutdown = shutdown.clone();
+        draining.push(spawn_component_supervisor(
+            "channels",
+            initial_backoff,
+            max_backoff,
+            shutdown.clone(),
+            move || run_channels_with_reload(config_rx.clone(), channels_shutdown.clone()),
+        ));
+    }
+
+    #[cfg(unix)]
+    {
+        let attach_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "attach",
+            initial_backoff,
+            max_backoff,
+            shutdown.clone(),
+            move || crate::approval::attach::serve_local(attach_cfg.clone()),
+        ));
     }
 
     if config.heartbeat.enabled {
@@ -80,6 +133,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
             "heartbeat",
             initial_backoff,
             max_backoff,
+            shutdown.clone(),
             move || {
                 let cfg = heartbeat_cfg.clone();
                 async move { Box::pin(run_heartbeat_worker(cfg)).await }
@@ -87,12 +141,32 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         ));
     }
 
+    if config.memory.decay.enabled
+        && !matches!(
+            crate::memory::classify_memory_backend(&config.memory.backend),
+            crate::memory::MemoryBackendKind::None
+        )
+    {
+        let gc_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "memory_gc",
+            initial_backoff,
+            max_backoff,
+            shutdown.clone(),
+            move || {
+                let cfg = gc_cfg.clone();
+                async move { crate::memory::decay::run_gc_worker(cfg).await }
+            },
+        ));
+    }
+
     if config.cron.enabled {
         let scheduler_cfg = config.clone();
         handles.push(spawn_component_supervisor(
             "scheduler",
             initial_backoff,
             max_backoff,
+            shutdown.clone(),
             move || {
                 let cfg = scheduler_cfg.clone();
                 async move { crate::cron::scheduler::run(cfg).await }
@@ -108,8 +182,14 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     println!("   Components: gateway, channels, heartbeat, scheduler");
     println!("   Ctrl+C to stop");
 
-    tokio::signal::ctrl_c().await?;
+    wait_for_shutdown_signal().await?;
     crate::health::mark_component_error("daemon", "shutdown requested");
+    tracing::info!(
+        grace_secs = config.reliability.shutdown_grace_secs,
+        "Shutdown requested; draining in-flight work"
+    );
+    println!("🛑 Shutting down, waiting for in-flight work to finish...");
+    shutdown.cancel();
 
     for handle in &handles {
         handle.abort();
@@ -118,6 +198,29 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         let _ = handle.await;
     }
 
+    let deadline = tokio::time::Instant::now()
+        + Duration::from_secs(
+            config
+                .reliability
+                .shutdown_grace_secs
+                .saturating_add(SHUTDOWN_FLUSH_SECONDS),
+        );
+    for handle in &mut draining {
+        if tokio::time::timeout_at(deadline, &mut *handle)
+            .await
+            .is_err()
+        {
+            tracing::warn!("Shutdown grace period elapsed; aborting remaining components");
+            break;
+        }
+    }
+    for handle in &draining {
+        handle.abort();
+    }
+    for handle in draining {
+        let _ = handle.await;
+    }
+
     Ok(())
 }
 
@@ -156,6 +259,7 @@ fn spawn_component_supervisor<F, Fut>(
     name: &'static str,
     initial_backoff_secs: u64,
     max_backoff_secs: u64,
+    shutdown: CancellationToken,
     mut run_component: F,
 ) -> JoinHandle<()>
 where
@@ -166,9 +270,14 @@ where
         let mut backoff = initial_backoff_secs.max(1);
         let max_backoff = max_backoff_secs.max(backoff);
 
-        loop {
+        while !shutdown.is_cancelled() {
             crate::health::mark_component_ok(name);
-            match run_component().await {
+            let result = run_component().await;
+            if shutdown.is_cancelled() {
+                tracing::info!("Daemon component '{name}' stopped for shutdown");
+                break;
+            }
+            match result {
                 Ok(()) => {
                     crate::health::mark_component_error(name, "component exited unexpectedly");
                     tracing::warn!("Daemon component '{name}' exited unexpectedly");
@@ -182,7 +291,10 @@ where
             }
 
             crate::health::bump_component_restart(name);
-            tokio::time::sleep(Duration::from_secs(backoff)).await;
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                () = tokio::time::sleep(Duration::from_secs(backoff)) => {}
+            }
             // Double backoff AFTER sleeping so first error uses initial_backoff
             backoff = backoff.saturating_mul(2).min(max_backoff);
         }
@@ -197,7 +309,23 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
         config.workspace_dir.clone(),
         observer,
     );
-    let delivery = heartbeat_delivery_target(&config)?;
+    let delivery = heartbeat_delivery_targets(&config)?;
+    let quiet_hours = config
+        .heartbeat
+        .quiet_hours
+        .as_ref()
+        .map(crate::heartbeat::proactive::QuietHours::from_config)
+        .transpose()?;
+    let memory = if config.heartbeat.proactive {
+        Some(crate::memory::create_memory_with_storage(
+            &config.memory,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?)
+    } else {
+        None
+    };
 
     let interval_mins = config.heartbeat.interval_minutes.max(5);
     let mut interval = tokio::time::interval(Duration::from_secs(u64::from(interval_mins) * 60));
@@ -205,14 +333,33 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
     loop {
         interval.tick().await;
 
+        let quiet = quiet_hours
+            .as_ref()
+            .is_some_and(|window| window.contains(Utc::now()));
+
         let file_tasks = engine.collect_tasks().await?;
-        let tasks = heartbeat_tasks_for_tick(file_tasks, config.heartbeat.message.as_deref());
-        if tasks.is_empty() {
-            continue;
+        let mut prompts: Vec<String> =
+            heartbeat_tasks_for_tick(file_tasks, config.heartbeat.message.as_deref())
+                .into_iter()
+                .map(|task| format!("[Heartbeat Task] {task}"))
+                .collect();
+
+        // A check-in exists only to message the user, so it is skipped
+        // entirely during quiet hours or when there is nothing to review.
+        if let Some(memory) = memory.as_deref().filter(|_| !quiet) {
+            let context = crate::heartbeat::proactive::CheckInContext::gather(
+                &config,
+                memory,
+                Utc::now(),
+                chrono::Duration::minutes(i64::from(interval_mins)),
+            )
+            .await;
+            if !context.is_empty() {
+                prompts.push(context.prompt());
+            }
         }
 
-        for task in tasks {
-            let prompt = format!("[Heartbeat Task] {task}");
+        for prompt in prompts {
             let temp = config.default_temperature;
             match crate::agent::run(
                 config.clone(),
@@ -228,7 +375,11 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
                 Ok(output) => {
                     crate::health::mark_component_ok("heartbeat");
                     if let Some(announcement) = heartbeat_announcement_text(&output) {
-                        if let Some((channel, target)) = &delivery {
+                        if quiet {
+                            tracing::debug!("Heartbeat output held back during quiet hours");
+                            continue;
+                        }
+                        for (channel, target) in &delivery {
                             if let Err(e) = crate::cron::scheduler::deliver_announcement(
                                 &config,
                                 channel,
@@ -239,9 +390,9 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
                             {
                                 crate::health::mark_component_error(
                                     "heartbeat",
-                                    format!("delivery failed: {e}"),
+                                    format!("delivery to {channel} failed: {e}"),
                                 );
-                                tracing::warn!("Heartbeat delivery failed: {e}");
+                                tracing::warn!("Heartbeat delivery to {channel} failed: {e}");
                             }
                         }
                     } else {
@@ -318,6 +469,27 @@ fn heartbeat_delivery_target(config: &Config) -> Result<Option<(String, String)>
     }
 }
 
+/// The legacy `target`/`to` pair plus every enabled `[[heartbeat.channels]]`
+/// entry, deduplicated.
+fn heartbeat_delivery_targets(config: &Config) -> Result<Vec<(String, String)>> {
+    let mut targets: Vec<(String, String)> =
+        heartbeat_delivery_target(config)?.into_iter().collect();
+    for entry in config
+        .heartbeat
+        .channels
+        .iter()
+        .filter(|entry| entry.enabled)
+    {
+        let channel = entry.channel.trim();
+        validate_heartbeat_channel_config(config, channel)?;
+        let target = (channel.to_string(), entry.to.trim().to_string());
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+    Ok(targets)
+}
+
 fn validate_heartbeat_channel_config(config: &Config, channel: &str) -> Result<()> {
     let normalized = channel.to_ascii_lowercase();
     match normalized.as_str() {
@@ -362,12 +534,60 @@ fn validate_heartbeat_channel_config(config: &Config, channel: &str) -> Result<(
                 );
             }
         }
+        "notify" => {
+            if config.notify.targets.is_empty() {
+                anyhow::bail!(
+                    "heartbeat.target is set to notify but no [notify.targets] are configured"
+                );
+            }
+        }
         other => anyhow::bail!("unsupported heartbeat.target channel: {other}"),
     }
 
     Ok(())
 }
 
+/// Run the channel runtime, rebuilding it whenever the config watcher
+/// publishes a reloaded config.
+async fn run_channels_with_reload(
+    mut configs: watch::Receiver<Config>,
+    shutdown: CancellationToken,
+) -> Result<()> {
+    loop {
+        let config = configs.borrow_and_update().clone();
+        if !has_supervised_channels(&config) {
+            crate::health::mark_component_ok("channels");
+            tracing::info!("No real-time channels configured; waiting for a config change");
+            tokio::select! {
+                () = shutdown.cancelled() => return Ok(()),
+                changed = configs.changed() => {
+                    if changed.is_err() {
+                        // The watcher is gone; nothing can add channels now.
+                        shutdown.cancelled().await;
+                        return Ok(());
+                    }
+                    continue;
+                }
+            }
+        }
+
+        let stop = shutdown.child_token();
+        let channels = crate::channels::run_channels(config, stop.clone());
+        tokio::pin!(channels);
+        tokio::select! {
+            result = &mut channels => return result,
+            changed = configs.changed() => {
+                if changed.is_err() {
+                    return channels.await;
+                }
+                tracing::info!("Config changed; restarting channels");
+                stop.cancel();
+                channels.await?;
+            }
+        }
+    }
+}
+
 fn has_supervised_channels(config: &Config) -> bool {
     config
         .channels_config
@@ -445,9 +665,13 @@ mod tests {
 
     #[tokio::test]
     async fn supervisor_marks_error_and_restart_on_failure() {
-        let handle = spawn_component_supervisor("daemon-test-fail", 1, 1, || async {
-            anyhow::bail!("boom")
-        });
+        let handle = spawn_component_supervisor(
+            "daemon-test-fail",
+            1,
+            1,
+            CancellationToken::new(),
+            || async { anyhow::bail!("boom") },
+        );
 
         tokio::time::sleep(Duration::from_millis(50)).await;
         handle.abort();
@@ -465,7 +689,13 @@ mod tests {
 
     #[tokio::test]
     async fn supervisor_marks_unexpected_exit_as_error() {
-        let handle = spawn_component_supervisor("daemon-test-exit", 1, 1, || async { Ok(()) });
+        let handle = spawn_component_supervisor(
+            "daemon-test-exit",
+            1,
+            1,
+            CancellationToken::new(),
+            || async { Ok(()) },
+        );
 
         tokio::time::sleep(Duration::from_millis(50)).await;
         handle.abort();
@@ -481,6 +711,32 @@ mod tests {
             .contains("component exited unexpectedly"));
     }
 
+    #[tokio::test]
+    async fn supervisor_stops_restarting_after_shutdown() {
+        let shutdown = CancellationToken::new();
+        let runs = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = std::sync::Arc::clone(&runs);
+        let handle = spawn_component_supervisor("daemon-test-shutdown", 1, 1, shutdown.clone(), {
+            let shutdown = shutdown.clone();
+            move || {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let shutdown = shutdown.clone();
+                async move {
+                    shutdown.cancelled().await;
+                    Ok(())
+                }
+            }
+        });
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        shutdown.cancel();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("supervisor should exit on shutdown")
+            .unwrap();
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn detects_no_supervised_channels() {
         let config = Config::default();
@@ -642,6 +898,24 @@ mod tests {
             .contains("unsupported heartbeat.target channel"));
     }
 
+    #[test]
+    fn heartbeat_delivery_target_accepts_push_targets() {
+        let mut config = Config::default();
+        config.heartbeat.target = Some("notify".into());
+        config.heartbeat.to = Some("phone".into());
+        let err = heartbeat_delivery_target(&config).unwrap_err();
+        assert!(err.to_string().contains("[notify.targets]"));
+
+        config.notify.targets.insert(
+            "phone".into(),
+            toml::from_str("service = \"ntfy\"\ntopic = \"alerts\"").unwrap(),
+        );
+        assert_eq!(
+            heartbeat_delivery_target(&config).unwrap(),
+            Some(("notify".to_string(), "phone".to_string()))
+        );
+    }
+
     #[test]
     fn heartbeat_delivery_target_requires_channel_configuration() {
         let mut config = Config::default();
@@ -673,6 +947,50 @@ mod tests {
         assert_eq!(target, Some(("telegram".to_string(), "123456".to_string())));
     }
 
+    #[test]
+    fn heartbeat_delivery_targets_skip_disabled_channels() {
+        let mut config = Config::default();
+        config.heartbeat.target = Some("slack".into());
+        config.heartbeat.to = Some("C1".into());
+        config.heartbeat.channels = vec![
+            crate::config::HeartbeatChannelConfig {
+                channel: "slack".into(),
+                to: "C1".into(),
+                enabled: true,
+            },
+            crate::config::HeartbeatChannelConfig {
+                channel: "slack".into(),
+                to: "C2".into(),
+                enabled: true,
+            },
+            crate::config::HeartbeatChannelConfig {
+                channel: "telegram".into(),
+                to: "42".into(),
+                enabled: false,
+            },
+        ];
+        config.channels_config.slack = Some(crate::config::SlackConfig {
+            bot_token: "xoxb-token".into(),
+            app_token: None,
+            channel_id: None,
+            allowed_users: vec![],
+            group_reply: None,
+            stream_mode: crate::config::StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            app_home: false,
+        });
+
+        // The disabled telegram entry is not validated or delivered to.
+        let targets = heartbeat_delivery_targets(&config).unwrap();
+        assert_eq!(
+            targets,
+            vec![
+                ("slack".to_string(), "C1".to_string()),
+                ("slack".to_string(), "C2".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn heartbeat_delivery_target_accepts_whatsapp_web_target_in_web_mode() {
         let mut config = Config::default();
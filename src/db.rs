@@ -0,0 +1,401 @@
+//! Versioned schema migrations for the embedded SQLite databases.
+//!
+//! Every SQLite-backed subsystem declares its tables as a [`Schema`]: an
+//! ordered list of [`Migration`]s numbered from 1. [`migrate`] applies the
+//! migrations a database has not seen yet, each in its own transaction, and
+//! records them in a `schema_version` table keyed by schema name. Databases
+//! created before that table existed count as version 0; their first
+//! migrations only use `IF NOT EXISTS` and [`add_column_if_missing`], so
+//! replaying them over existing tables is harmless. A database recorded at
+//! a newer version than this build knows is refused instead of being read
+//! with the wrong schema.
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection, OpenFlags, Transaction, TransactionBehavior};
+use serde::Serialize;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const VERSION_TABLE: &str = "CREATE TABLE IF NOT EXISTS schema_version (
+    schema      TEXT NOT NULL,
+    version     INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    applied_at  INTEGER NOT NULL,
+    PRIMARY KEY (schema, version)
+);";
+
+/// What a migration does.
+pub enum Step {
+    /// A batch of SQL statements.
+    Sql(&'static str),
+    /// Code, for changes that depend on what the database already holds.
+    Rust(fn(&Connection) -> Result<()>),
+}
+
+/// One schema change.
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    pub step: Step,
+}
+
+/// The tables of one database file.
+pub struct Schema {
+    pub name: &'static str,
+    /// Location of the database, relative to the workspace.
+    pub path: &'static str,
+    pub migrations: &'static [Migration],
+}
+
+impl Schema {
+    pub fn db_path(&self, workspace_dir: &Path) -> PathBuf {
+        workspace_dir.join(self.path)
+    }
+
+    /// The version a fully migrated database has.
+    pub fn latest(&self) -> u32 {
+        self.migrations.last().map_or(0, |m| m.version)
+    }
+
+    fn check_order(&self) -> Result<()> {
+        for (idx, migration) in self.migrations.iter().enumerate() {
+            if usize::try_from(migration.version).ok() != Some(idx + 1) {
+                bail!(
+                    "{} migrations must be numbered 1, 2, 3, ...; found version {} at position {}",
+                    self.name,
+                    migration.version,
+                    idx + 1
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Every schema, in the order `zeroclaw db migrate` reports them.
+pub fn schemas() -> [&'static Schema; 9] {
+    [
+        &crate::channels::session::sqlite::SCHEMA,
+        &crate::memory::sqlite::SCHEMA,
+        &crate::channels::outbox::SCHEMA,
+        &crate::channels::task_queue::SCHEMA,
+        &crate::tools::undo::SCHEMA,
+        &crate::feeds::SCHEMA,
+        &crate::kb::SCHEMA,
+        &crate::cost::usage::SCHEMA,
+        &crate::cron::SCHEMA,
+    ]
+}
+
+/// A migration listed in a report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationSummary {
+    pub version: u32,
+    pub description: String,
+}
+
+/// Outcome of [`migrate`], or of [`plan`] for a dry run.
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub schema: String,
+    pub from_version: u32,
+    pub to_version: u32,
+    /// Migrations applied, or the ones that would be for a dry run.
+    pub migrations: Vec<MigrationSummary>,
+    pub dry_run: bool,
+}
+
+/// The highest version of `schema` recorded in the database.
+pub fn current_version(conn: &Connection, schema: &str) -> Result<u32> {
+    let tracked = conn
+        .prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_version'")?
+        .exists([])?;
+    if !tracked {
+        return Ok(0);
+    }
+    Ok(conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_version WHERE schema = ?1",
+        params![schema],
+        |row| row.get(0),
+    )?)
+}
+
+fn check_not_newer(schema: &Schema, version: u32) -> Result<()> {
+    if version > schema.latest() {
+        bail!(
+            "the {} database is at schema version {version}, but this build of zeroclaw only \
+             knows up to version {}; upgrade zeroclaw instead of opening it with an older one",
+            schema.name,
+            schema.latest()
+        );
+    }
+    Ok(())
+}
+
+/// Migrations of `schema` that `conn` has not applied yet, without changing
+/// anything.
+pub fn plan(conn: &Connection, schema: &Schema) -> Result<MigrationReport> {
+    schema.check_order()?;
+    let from_version = current_version(conn, schema.name)?;
+    check_not_newer(schema, from_version)?;
+    Ok(MigrationReport {
+        schema: schema.name.to_string(),
+        from_version,
+        to_version: schema.latest(),
+        migrations: schema
+            .migrations
+            .iter()
+            .filter(|m| m.version > from_version)
+            .map(|m| MigrationSummary {
+                version: m.version,
+                description: m.description.to_string(),
+            })
+            .collect(),
+        dry_run: true,
+    })
+}
+
+/// Bring `conn` up to the latest version of `schema`.
+///
+/// Run this after connection-level pragmas such as `journal_mode`, which
+/// cannot change inside a transaction.
+pub fn migrate(conn: &Connection, schema: &Schema) -> Result<MigrationReport> {
+    let mut report = plan(conn, schema)?;
+    report.dry_run = false;
+    if report.migrations.is_empty() {
+        return Ok(report);
+    }
+    conn.execute_batch(VERSION_TABLE)?;
+
+    let mut applied = Vec::new();
+    for migration in schema
+        .migrations
+        .iter()
+        .filter(|m| m.version > report.from_version)
+    {
+        let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+        // Another process may have applied it while this one waited for the lock.
+        if current_version(&tx, schema.name)? >= migration.version {
+            continue;
+        }
+        let result = match migration.step {
+            Step::Sql(sql) => tx.execute_batch(sql).map_err(anyhow::Error::from),
+            Step::Rust(apply) => apply(&tx),
+        };
+        result.with_context(|| {
+            format!(
+                "{} migration {} ({}) failed",
+                schema.name, migration.version, migration.description
+            )
+        })?;
+        tx.execute(
+            "INSERT INTO schema_version (schema, version, description, applied_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                schema.name,
+                migration.version,
+                migration.description,
+                Utc::now().timestamp()
+            ],
+        )?;
+        tx.commit()?;
+        tracing::debug!(
+            schema = schema.name,
+            version = migration.version,
+            "Applied migration: {}",
+            migration.description
+        );
+        applied.push(MigrationSummary {
+            version: migration.version,
+            description: migration.description.to_string(),
+        });
+    }
+    report.migrations = applied;
+    Ok(report)
+}
+
+/// Add `column` to `table` unless a database created before migrations were
+/// tracked already has it.
+pub fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    declaration: &str,
+) -> Result<()> {
+    let exists = conn
+        .prepare("SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2")?
+        .exists(params![table, column])?;
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {table} ADD COLUMN {column} {declaration};"
+        ))?;
+    }
+    Ok(())
+}
+
+/// State of one database file for `zeroclaw db migrate`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DatabaseStatus {
+    pub path: PathBuf,
+    /// `None` when the database has not been created yet.
+    pub report: Option<MigrationReport>,
+}
+
+/// Plan (`dry_run`) or apply pending migrations on every database in the
+/// workspace. Databases that do not exist yet are left alone; they are
+/// created at the latest version when first opened.
+pub fn migrate_workspace(workspace_dir: &Path, dry_run: bool) -> Result<Vec<DatabaseStatus>> {
+    schemas()
+        .into_iter()
+        .map(|schema| {
+            let path = schema.db_path(workspace_dir);
+            if !path.exists() {
+                return Ok(DatabaseStatus { path, report: None });
+            }
+            let report = if dry_run {
+                let conn = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+                    .with_context(|| format!("Failed to open {}", path.display()))?;
+                plan(&conn, schema)
+            } else {
+                let conn = Connection::open(&path)
+                    .with_context(|| format!("Failed to open {}", path.display()))?;
+                conn.busy_timeout(std::time::Duration::from_secs(5))?;
+                migrate(&conn, schema)
+            }
+            .with_context(|| format!("Failed to migrate {}", path.display()))?;
+            Ok(DatabaseStatus {
+                path,
+                report: Some(report),
+            })
+        })
+        .collect()
+}
+
+/// Handle `zeroclaw db migrate`.
+pub fn handle_migrate_command(workspace_dir: &Path, dry_run: bool, json: bool) -> Result<()> {
+    let statuses = migrate_workspace(workspace_dir, dry_run)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&statuses)?);
+        return Ok(());
+    }
+    for status in &statuses {
+        let Some(report) = &status.report else {
+            println!("- {} (not created yet)", status.path.display());
+            continue;
+        };
+        let mut line = format!(
+            "{} {} [{}] v{}",
+            if report.migrations.is_empty() {
+                "✓"
+            } else if dry_run {
+                "→"
+            } else {
+                "↑"
+            },
+            status.path.display(),
+            report.schema,
+            report.from_version
+        );
+        if report.migrations.is_empty() {
+            line.push_str(", up to date");
+        } else {
+            let _ = write!(
+                line,
+                " → v{}{}",
+                report.to_version,
+                if dry_run { " (dry run)" } else { "" }
+            );
+        }
+        println!("{line}");
+        for migration in &report.migrations {
+            println!("    {}. {}", migration.version, migration.description);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_SCHEMA: Schema = Schema {
+        name: "test",
+        path: "test.db",
+        migrations: &[
+            Migration {
+                version: 1,
+                description: "create items",
+                step: Step::Sql("CREATE TABLE IF NOT EXISTS items (id INTEGER PRIMARY KEY);"),
+            },
+            Migration {
+                version: 2,
+                description: "add items.label",
+                step: Step::Rust(|conn| add_column_if_missing(conn, "items", "label", "TEXT")),
+            },
+        ],
+    };
+
+    #[test]
+    fn migrate_adopts_legacy_tables_and_refuses_newer_versions() {
+        let conn = Connection::open_in_memory().unwrap();
+        // A database created before versions were tracked, already holding
+        // the column migration 2 adds.
+        conn.execute_batch(
+            "CREATE TABLE items (id INTEGER PRIMARY KEY, label TEXT);
+             INSERT INTO items (label) VALUES ('kept');",
+        )
+        .unwrap();
+
+        let dry = plan(&conn, &TEST_SCHEMA).unwrap();
+        assert_eq!((dry.from_version, dry.to_version), (0, 2));
+        assert_eq!(dry.migrations.len(), 2);
+        assert_eq!(current_version(&conn, "test").unwrap(), 0);
+
+        let report = migrate(&conn, &TEST_SCHEMA).unwrap();
+        assert!(!report.dry_run);
+        assert_eq!(report.migrations.len(), 2);
+        assert_eq!(current_version(&conn, "test").unwrap(), 2);
+        let label: String = conn
+            .query_row("SELECT label FROM items", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(label, "kept");
+        assert!(migrate(&conn, &TEST_SCHEMA).unwrap().migrations.is_empty());
+
+        conn.execute(
+            "INSERT INTO schema_version VALUES ('test', 3, 'from the future', 0)",
+            [],
+        )
+        .unwrap();
+        assert!(migrate(&conn, &TEST_SCHEMA).is_err());
+    }
+
+    #[test]
+    fn registered_schemas_migrate_from_empty() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut names = std::collections::HashSet::new();
+        for schema in schemas() {
+            assert!(
+                names.insert(schema.name),
+                "duplicate schema {}",
+                schema.name
+            );
+            let path = schema.db_path(tmp.path());
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let conn = Connection::open(&path).unwrap();
+            let report = migrate(&conn, schema).unwrap();
+            assert_eq!(report.to_version, schema.latest());
+            assert_eq!(
+                current_version(&conn, schema.name).unwrap(),
+                schema.latest()
+            );
+        }
+        let statuses = migrate_workspace(tmp.path(), true).unwrap();
+        assert!(statuses.iter().all(|status| status
+            .report
+            .as_ref()
+            .unwrap()
+            .migrations
+            .is_empty()));
+    }
+}
@@ -0,0 +1,422 @@
+//! Live checks run by `zeroclaw doctor` (skipped with `--offline`).
+//!
+//! Unlike the static diagnostics in the parent module these touch the
+//! network and the workspace databases: provider reachability, channel
+//! credentials, workspace permissions, SQLite integrity and clock skew. Each
+//! check lands in one row of a pass/fail table, with a remediation hint when
+//! it did not pass.
+
+use super::Severity;
+use crate::channels::ChannelHealthState;
+use crate::config::Config;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const PROVIDER_TIMEOUT_SECS: u64 = 15;
+const CLOCK_TIMEOUT_SECS: u64 = 10;
+const CLOCK_SKEW_WARN_SECS: i64 = 5;
+const CLOCK_SKEW_FAIL_SECS: i64 = 60;
+/// Queried for its `Date` header when the provider has no known endpoint.
+const CLOCK_FALLBACK_URL: &str = "https://www.cloudflare.com";
+const PROVIDER_SETUP_HINT: &str =
+    "Check `default_provider` and its credentials (`zeroclaw onboard` or `api_key`).";
+const PROVIDER_AUTH_HINT: &str =
+    "The provider rejected the credentials or is rate limiting; check the API key and plan.";
+const NETWORK_HINT: &str = "Check network access, proxy settings and `api_url`.";
+/// Workspace subdirectories holding SQLite databases.
+const DATABASE_DIRS: &[&str] = &["memory", "sessions", "state", "cron"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LiveCheck {
+    name: String,
+    severity: Severity,
+    detail: String,
+    hint: Option<&'static str>,
+}
+
+impl LiveCheck {
+    fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            severity: Severity::Ok,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: impl Into<String>, detail: impl Into<String>, hint: &'static str) -> Self {
+        Self {
+            name: name.into(),
+            severity: Severity::Warn,
+            detail: detail.into(),
+            hint: Some(hint),
+        }
+    }
+
+    fn fail(name: impl Into<String>, detail: impl Into<String>, hint: &'static str) -> Self {
+        Self {
+            name: name.into(),
+            severity: Severity::Error,
+            detail: detail.into(),
+            hint: Some(hint),
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        match self.severity {
+            Severity::Ok => "PASS",
+            Severity::Warn => "WARN",
+            Severity::Error => "FAIL",
+        }
+    }
+}
+
+/// Run the live checks and print them as a table.
+pub async fn run(config: &Config) {
+    let mut checks = vec![check_provider(config).await];
+    checks.extend(check_channels(config).await);
+    checks.push(check_workspace_permissions(&config.workspace_dir));
+    checks.extend(check_databases(&config.workspace_dir));
+    checks.push(check_clock_skew(config).await);
+
+    println!();
+    println!("🩺 Live checks");
+    println!();
+    println!("  {:<28} {:<6} detail", "check", "status");
+    println!("  {:<28} {:<6} ------", "-".repeat(28), "------");
+    for check in &checks {
+        println!(
+            "  {:<28} {:<6} {}",
+            check.name,
+            check.status(),
+            super::truncate_for_display(&check.detail, 100)
+        );
+    }
+
+    let hints: Vec<(&str, &str)> = checks
+        .iter()
+        .filter_map(|c| c.hint.map(|hint| (c.name.as_str(), hint)))
+        .collect();
+    if !hints.is_empty() {
+        println!();
+        for (name, hint) in hints {
+            println!("  💡 {name}: {hint}");
+        }
+    }
+
+    let count = |severity| checks.iter().filter(|c| c.severity == severity).count();
+    println!();
+    println!(
+        "  Live summary: {} passed, {} warnings, {} failed",
+        count(Severity::Ok),
+        count(Severity::Warn),
+        count(Severity::Error)
+    );
+}
+
+fn resolved_provider(config: &Config) -> String {
+    config
+        .default_provider
+        .clone()
+        .unwrap_or_else(|| "openrouter".to_string())
+}
+
+async fn check_provider(config: &Config) -> LiveCheck {
+    let name = resolved_provider(config);
+    let label = format!("provider ({name})");
+    let options = crate::providers::ProviderRuntimeOptions {
+        auth_profile_override: None,
+        provider_api_url: config.api_url.clone(),
+        provider_transport: config.effective_provider_transport(),
+        zeroclaw_dir: config.config_path.parent().map(PathBuf::from),
+        secrets_encrypt: config.secrets.encrypt,
+        reasoning_enabled: config.runtime.reasoning_enabled,
+        reasoning_level: config.effective_provider_reasoning_level(),
+        custom_provider_api_mode: config.provider_api.map(|mode| mode.as_compatible_mode()),
+        max_tokens_override: None,
+        model_support_vision: config.model_support_vision,
+        model_support_tools: config.model_support_tools,
+    };
+    let api_key = config.api_key.clone();
+    let provider_name = name.clone();
+    let created = tokio::task::spawn_blocking(move || {
+        crate::providers::create_provider_with_options(&provider_name, api_key.as_deref(), &options)
+    })
+    .await;
+    let provider = match created {
+        Ok(Ok(provider)) => provider,
+        Ok(Err(e)) => {
+            return LiveCheck::fail(label, super::format_error_chain(&e), PROVIDER_SETUP_HINT)
+        }
+        Err(e) => return LiveCheck::fail(label, e.to_string(), "Re-run `zeroclaw doctor`."),
+    };
+
+    match tokio::time::timeout(
+        Duration::from_secs(PROVIDER_TIMEOUT_SECS),
+        provider.warmup(),
+    )
+    .await
+    {
+        Ok(Ok(())) => LiveCheck::pass(label, "reachable"),
+        Ok(Err(e)) => {
+            let detail = super::format_error_chain(&e);
+            match super::classify_model_probe_error(&detail) {
+                super::ModelProbeOutcome::AuthOrAccess => {
+                    LiveCheck::fail(label, detail, PROVIDER_AUTH_HINT)
+                }
+                _ => LiveCheck::fail(label, detail, NETWORK_HINT),
+            }
+        }
+        Err(_) => LiveCheck::fail(
+            label,
+            format!("timed out (>{PROVIDER_TIMEOUT_SECS}s)"),
+            NETWORK_HINT,
+        ),
+    }
+}
+
+async fn check_channels(config: &Config) -> Vec<LiveCheck> {
+    crate::channels::probe_channel_health(config)
+        .await
+        .into_iter()
+        .map(|(name, state)| {
+            let label = format!("channel ({})", name.to_lowercase());
+            match state {
+                ChannelHealthState::Healthy => LiveCheck::pass(label, "credentials accepted"),
+                ChannelHealthState::Unhealthy => LiveCheck::fail(
+                    label,
+                    "credentials rejected, rate limited or unreachable",
+                    "Verify the channel token in config.toml and that the bot is still installed.",
+                ),
+                ChannelHealthState::Timeout => LiveCheck::fail(
+                    label,
+                    "timed out (>10s)",
+                    "Check network access to the channel API and proxy settings.",
+                ),
+            }
+        })
+        .collect()
+}
+
+fn check_workspace_permissions(workspace_dir: &Path) -> LiveCheck {
+    let label = "workspace permissions";
+    let metadata = match std::fs::metadata(workspace_dir) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            return LiveCheck::fail(
+                label,
+                format!("{}: {e}", workspace_dir.display()),
+                "Run `zeroclaw onboard` to create the workspace.",
+            )
+        }
+    };
+    if metadata.permissions().readonly() {
+        return LiveCheck::fail(
+            label,
+            format!("{} is read-only", workspace_dir.display()),
+            "Give the zeroclaw user write access to the workspace.",
+        );
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = metadata.permissions().mode() & 0o777;
+        if mode & 0o002 != 0 {
+            return LiveCheck::fail(
+                label,
+                format!("{} is world-writable ({mode:o})", workspace_dir.display()),
+                "Restrict the workspace with `chmod 700`.",
+            );
+        }
+        if mode & 0o077 != 0 {
+            return LiveCheck::warn(
+                label,
+                format!(
+                    "{} is readable by others ({mode:o})",
+                    workspace_dir.display()
+                ),
+                "Sessions and memory live here; consider `chmod 700`.",
+            );
+        }
+    }
+
+    LiveCheck::pass(label, "owner-only access")
+}
+
+fn check_databases(workspace_dir: &Path) -> Vec<LiveCheck> {
+    sqlite_databases(workspace_dir)
+        .into_iter()
+        .map(|path| {
+            let label = path
+                .strip_prefix(workspace_dir)
+                .unwrap_or(&path)
+                .display()
+                .to_string();
+            match integrity_check(&path) {
+                Ok(problems) if problems.is_empty() => LiveCheck::pass(label, "integrity ok"),
+                Ok(problems) => LiveCheck::fail(
+                    label,
+                    problems.join("; "),
+                    "Stop zeroclaw, back up the file and rebuild it (`sqlite3 <db> .recover`).",
+                ),
+                Err(e) => LiveCheck::fail(
+                    label,
+                    format!("{e:#}"),
+                    "The database cannot be opened; check file permissions or restore a backup.",
+                ),
+            }
+        })
+        .collect()
+}
+
+/// `*.db` files directly inside the workspace's database directories.
+fn sqlite_databases(workspace_dir: &Path) -> Vec<PathBuf> {
+    let mut databases = Vec::new();
+    for dir in DATABASE_DIRS {
+        let Ok(entries) = std::fs::read_dir(workspace_dir.join(dir)) else {
+            continue;
+        };
+        databases.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "db")),
+        );
+    }
+    databases.sort();
+    databases
+}
+
+/// Problems reported by `PRAGMA integrity_check`; empty when the database
+/// is sound.
+fn integrity_check(path: &Path) -> anyhow::Result<Vec<String>> {
+    let conn = rusqlite::Connection::open_with_flags(
+        path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    conn.busy_timeout(Duration::from_secs(5))?;
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(rows.into_iter().filter(|row| row != "ok").collect())
+}
+
+/// Endpoint whose `Date` header the local clock is compared against.
+fn clock_reference_url(config: &Config) -> String {
+    if let Some(url) = config
+        .api_url
+        .as_deref()
+        .filter(|url| url.starts_with("https://"))
+    {
+        return url.to_string();
+    }
+    match resolved_provider(config).as_str() {
+        "openrouter" => "https://openrouter.ai",
+        "anthropic" => "https://api.anthropic.com",
+        "openai" => "https://api.openai.com",
+        "gemini" | "google" => "https://generativelanguage.googleapis.com",
+        _ => CLOCK_FALLBACK_URL,
+    }
+    .to_string()
+}
+
+async fn check_clock_skew(config: &Config) -> LiveCheck {
+    let label = "clock skew";
+    let url = clock_reference_url(config);
+    let client = crate::config::build_runtime_proxy_client("doctor.clock");
+    let response = match tokio::time::timeout(
+        Duration::from_secs(CLOCK_TIMEOUT_SECS),
+        client.head(&url).send(),
+    )
+    .await
+    {
+        Ok(Ok(response)) => response,
+        Ok(Err(e)) => {
+            return LiveCheck::warn(
+                label,
+                format!("{url} unreachable: {e}"),
+                "Skew could not be measured; check network access.",
+            )
+        }
+        Err(_) => {
+            return LiveCheck::warn(
+                label,
+                format!("{url} timed out"),
+                "Skew could not be measured; check network access.",
+            )
+        }
+    };
+    let Some(remote) = response
+        .headers()
+        .get(reqwest::header::DATE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| chrono::DateTime::parse_from_rfc2822(value).ok())
+    else {
+        return LiveCheck::warn(
+            label,
+            format!("{url} sent no Date header"),
+            "Skew could not be measured.",
+        );
+    };
+
+    let skew = chrono::Utc::now()
+        .signed_duration_since(remote)
+        .num_seconds();
+    let detail = format!("{skew:+}s against {url}");
+    match classify_skew(skew) {
+        Severity::Ok => LiveCheck::pass(label, detail),
+        Severity::Warn => LiveCheck::warn(
+            label,
+            detail,
+            "Enable NTP time sync; signed webhooks and OAuth tokens are time sensitive.",
+        ),
+        Severity::Error => LiveCheck::fail(
+            label,
+            detail,
+            "Enable NTP time sync; signature checks (Slack, WhatsApp) reject requests this far off.",
+        ),
+    }
+}
+
+fn classify_skew(skew_secs: i64) -> Severity {
+    match skew_secs.abs() {
+        s if s <= CLOCK_SKEW_WARN_SECS => Severity::Ok,
+        s if s <= CLOCK_SKEW_FAIL_SECS => Severity::Warn,
+        _ => Severity::Error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn classify_skew_uses_absolute_offset() {
+        assert_eq!(classify_skew(0), Severity::Ok);
+        assert_eq!(classify_skew(-5), Severity::Ok);
+        assert_eq!(classify_skew(30), Severity::Warn);
+        assert_eq!(classify_skew(-61), Severity::Error);
+    }
+
+    #[test]
+    fn databases_are_found_and_checked() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join("state")).unwrap();
+        let db = tmp.path().join("state").join("usage.db");
+        rusqlite::Connection::open(&db)
+            .unwrap()
+            .execute_batch("CREATE TABLE t (x INTEGER); INSERT INTO t VALUES (1);")
+            .unwrap();
+        std::fs::write(tmp.path().join("state").join("notes.txt"), "x").unwrap();
+
+        assert_eq!(sqlite_databases(tmp.path()), vec![db.clone()]);
+        assert!(integrity_check(&db).unwrap().is_empty());
+
+        let checks = check_databases(tmp.path());
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].severity, Severity::Ok);
+    }
+}
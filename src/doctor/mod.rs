@@ -1,9 +1,11 @@
 use crate::config::Config;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use std::io::Write;
 use std::path::Path;
 
+pub mod live;
+
 const DAEMON_STALE_SECONDS: i64 = 30;
 const SCHEDULER_STALE_SECONDS: i64 = 120;
 const CHANNEL_STALE_SECONDS: i64 = 300;
@@ -332,6 +334,60 @@ pub async fn run_models(
     Ok(())
 }
 
+pub fn run_report(config: &Config, id: &str) -> Result<()> {
+    match crate::observability::turn_report::find_report(&config.workspace_dir, id.trim())? {
+        Some(report) => println!("{}", serde_json::to_string_pretty(&report)?),
+        None => {
+            let dir = crate::observability::turn_report::reports_dir(&config.workspace_dir);
+            if config.observability.turn_reports {
+                println!("No turn report found for '{id}' (dir: {}).", dir.display());
+            } else {
+                println!(
+                    "No turn report found for '{id}'.\n\
+                     Enable [observability] turn_reports = true, then reproduce the issue."
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn run_incidents(config: &Config, id: Option<&str>) -> Result<()> {
+    let reports = crate::observability::incident::list_reports(&config.workspace_dir)?;
+    if let Some(id) = id.map(str::trim) {
+        let report = reports
+            .into_iter()
+            .find(|report| report.id == id)
+            .with_context(|| format!("No incident report '{id}'"))?;
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    if reports.is_empty() {
+        println!("No incidents recorded.");
+        return Ok(());
+    }
+    for report in reports {
+        let turn = report
+            .turn_id
+            .as_deref()
+            .map(|turn_id| {
+                format!(
+                    " [turn {turn_id} on {}]",
+                    report.channel.as_deref().unwrap_or("?")
+                )
+            })
+            .unwrap_or_default();
+        println!(
+            "{}  {}  {:<5}  {}{turn}",
+            report.occurred_at,
+            report.id,
+            report.kind,
+            crate::util::truncate_with_ellipsis(&report.message, 100)
+        );
+    }
+    Ok(())
+}
+
 pub fn run_traces(
     config: &Config,
     id: Option<&str>,
@@ -1283,6 +1339,7 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
         config.agents.insert(
@@ -1297,6 +1354,7 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
 
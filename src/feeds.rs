@@ -0,0 +1,527 @@
+//! RSS/Atom feed watcher.
+//!
+//! The cron scheduler calls [`poll_due`] on every tick. Each enabled
+//! `[[feeds.sources]]` entry whose interval has passed is fetched, its items
+//! are compared with the GUIDs recorded in `<workspace>/state/feeds.db`, and
+//! new items matching the entry's keywords go out as one digest to its
+//! `channel` / `to`. The first poll of a feed only records what is already
+//! there, so adding a feed does not flood the channel with its back catalog.
+//! The `feed_fetch` tool reads the same feeds on demand.
+
+use crate::config::{Config, FeedSourceConfig};
+use crate::db::{migrate, Migration, Schema, Step};
+use crate::util::truncate_with_ellipsis;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use parking_lot::Mutex;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Largest feed document read.
+const MAX_FEED_BYTES: usize = 5 * 1024 * 1024;
+const FEED_REQUEST_TIMEOUT_SECS: u64 = 30;
+/// Longest item summary kept.
+const SUMMARY_CHARS: usize = 280;
+/// Seen GUIDs that have dropped out of their feed are forgotten after this.
+const SEEN_RETENTION_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// Tables of `state/feeds.db`.
+pub static SCHEMA: Schema = Schema {
+    name: "feeds",
+    path: "state/feeds.db",
+    migrations: &[Migration {
+        version: 1,
+        description: "create feed_items and feed_state",
+        step: Step::Sql(
+            "CREATE TABLE IF NOT EXISTS feed_items (
+                 feed    TEXT NOT NULL,
+                 guid    TEXT NOT NULL,
+                 seen_at INTEGER NOT NULL,
+                 PRIMARY KEY (feed, guid)
+             );
+             CREATE TABLE IF NOT EXISTS feed_state (
+                 feed           TEXT PRIMARY KEY,
+                 last_polled_at INTEGER NOT NULL,
+                 last_error     TEXT
+             );",
+        ),
+    }],
+};
+
+/// One feed entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FeedItem {
+    pub guid: String,
+    pub title: String,
+    pub link: Option<String>,
+    pub published: Option<String>,
+    pub summary: Option<String>,
+}
+
+impl FeedItem {
+    /// Whether the title or summary contains one of `keywords`
+    /// (case-insensitive); an empty list matches everything.
+    pub fn matches(&self, keywords: &[String]) -> bool {
+        if keywords.is_empty() {
+            return true;
+        }
+        let haystack = format!(
+            "{} {}",
+            self.title,
+            self.summary.as_deref().unwrap_or_default()
+        )
+        .to_lowercase();
+        keywords
+            .iter()
+            .map(|keyword| keyword.trim().to_lowercase())
+            .any(|keyword| !keyword.is_empty() && haystack.contains(&keyword))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Link,
+    Guid,
+    Published,
+    Summary,
+}
+
+impl Field {
+    fn from_tag(tag: &[u8]) -> Option<Self> {
+        match tag {
+            b"title" => Some(Self::Title),
+            b"link" => Some(Self::Link),
+            b"guid" | b"id" => Some(Self::Guid),
+            b"pubDate" | b"published" | b"updated" | b"date" => Some(Self::Published),
+            b"description" | b"summary" | b"content" | b"encoded" => Some(Self::Summary),
+            _ => None,
+        }
+    }
+}
+
+/// The `href` of an Atom `<link>` that points at the entry itself.
+fn atom_link_href(element: &BytesStart<'_>) -> Result<Option<String>> {
+    let rel = element.try_get_attribute("rel")?;
+    if rel.is_some_and(|rel| rel.value.as_ref() != b"alternate") {
+        return Ok(None);
+    }
+    Ok(element
+        .try_get_attribute("href")?
+        .map(|href| href.unescape_value().map(|v| v.trim().to_string()))
+        .transpose()?
+        .filter(|href| !href.is_empty()))
+}
+
+/// Parse an RSS 2.0, RSS 1.0 or Atom document into its items, in document
+/// order.
+pub fn parse_feed(xml: &str) -> Result<Vec<FeedItem>> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut items = Vec::new();
+    let mut is_feed = false;
+    let mut current: Option<FeedItem> = None;
+    let mut field: Option<Field> = None;
+    let mut depth = 0usize;
+    let mut item_depth = 0usize;
+
+    loop {
+        match reader.read_event().context("malformed feed XML")? {
+            Event::Start(element) => {
+                depth += 1;
+                let name = element.local_name();
+                if depth == 1 {
+                    is_feed = matches!(name.as_ref(), b"rss" | b"feed" | b"RDF");
+                }
+                if current.is_none() && matches!(name.as_ref(), b"item" | b"entry") {
+                    current = Some(FeedItem::default());
+                    item_depth = depth;
+                } else if let Some(item) = current.as_mut().filter(|_| depth == item_depth + 1) {
+                    field = Field::from_tag(name.as_ref());
+                    if field == Some(Field::Link) && item.link.is_none() {
+                        item.link = atom_link_href(&element)?;
+                    }
+                    // Keep the first of `summary` / `content` and of
+                    // `published` / `updated`.
+                    if (field == Some(Field::Summary) && item.summary.is_some())
+                        || (field == Some(Field::Published) && item.published.is_some())
+                    {
+                        field = None;
+                    }
+                }
+            }
+            Event::Empty(element) => {
+                if let Some(item) = current.as_mut() {
+                    if depth == item_depth
+                        && element.local_name().as_ref() == b"link"
+                        && item.link.is_none()
+                    {
+                        item.link = atom_link_href(&element)?;
+                    }
+                }
+            }
+            Event::Text(text) => {
+                let text = text.unescape().map_or_else(
+                    |_| String::from_utf8_lossy(&text).into_owned(),
+                    |t| t.into_owned(),
+                );
+                append_field(current.as_mut(), field, &text);
+            }
+            Event::CData(text) => {
+                append_field(current.as_mut(), field, &String::from_utf8_lossy(&text));
+            }
+            Event::End(_) => {
+                if current.is_some() && depth == item_depth {
+                    if let Some(item) = current.take().and_then(finish_item) {
+                        items.push(item);
+                    }
+                    field = None;
+                } else if depth == item_depth + 1 {
+                    field = None;
+                }
+                depth = depth.saturating_sub(1);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !is_feed {
+        anyhow::bail!("not an RSS or Atom feed");
+    }
+    Ok(items)
+}
+
+fn append_field(item: Option<&mut FeedItem>, field: Option<Field>, text: &str) {
+    let (Some(item), Some(field)) = (item, field) else {
+        return;
+    };
+    let slot = match field {
+        Field::Title => &mut item.title,
+        Field::Guid => &mut item.guid,
+        Field::Link => item.link.get_or_insert_with(String::new),
+        Field::Published => item.published.get_or_insert_with(String::new),
+        Field::Summary => item.summary.get_or_insert_with(String::new),
+    };
+    // Text split by inline markup (Atom xhtml content) is joined with a space.
+    if !slot.is_empty() {
+        slot.push(' ');
+    }
+    slot.push_str(text);
+}
+
+fn finish_item(mut item: FeedItem) -> Option<FeedItem> {
+    item.title = collapse_whitespace(&item.title);
+    item.link = item
+        .link
+        .map(|link| link.trim().to_string())
+        .filter(|link| !link.is_empty());
+    item.published = item
+        .published
+        .map(|published| published.trim().to_string())
+        .filter(|published| !published.is_empty());
+    item.summary = item
+        .summary
+        .map(|summary| {
+            truncate_with_ellipsis(
+                &collapse_whitespace(&nanohtml2text::html2text(&summary)),
+                SUMMARY_CHARS,
+            )
+        })
+        .filter(|summary| !summary.is_empty());
+    let guid = item.guid.trim();
+    item.guid = if guid.is_empty() {
+        item.link.clone().unwrap_or_else(|| item.title.clone())
+    } else {
+        guid.to_string()
+    };
+    if item.title.is_empty() {
+        item.title
+            .clone_from(item.link.as_ref().unwrap_or(&item.guid));
+    }
+    (!item.guid.is_empty()).then_some(item)
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Download and parse the feed at `url`.
+pub async fn fetch(url: &str) -> Result<Vec<FeedItem>> {
+    let client = crate::config::build_runtime_proxy_client_with_timeouts(
+        "tool.feed_fetch",
+        FEED_REQUEST_TIMEOUT_SECS,
+        10,
+    );
+    let mut response = client
+        .get(url)
+        .header(
+            reqwest::header::ACCEPT,
+            "application/rss+xml, application/atom+xml, application/xml;q=0.9, */*;q=0.8",
+        )
+        .send()
+        .await
+        .with_context(|| format!("failed to fetch {url}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("{url} returned {status}");
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > MAX_FEED_BYTES {
+            anyhow::bail!("{url} is larger than {MAX_FEED_BYTES} bytes");
+        }
+        body.extend_from_slice(&chunk);
+    }
+    parse_feed(&String::from_utf8_lossy(&body))
+}
+
+/// Seen items and poll times per feed.
+pub struct FeedStore {
+    conn: Mutex<Connection>,
+}
+
+impl FeedStore {
+    pub fn open(workspace_dir: &Path) -> Result<Self> {
+        let db_path = SCHEMA.db_path(workspace_dir);
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        migrate(&conn, &SCHEMA)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// When `feed` was last polled (Unix seconds).
+    pub fn last_polled(&self, feed: &str) -> Result<Option<i64>> {
+        Ok(self
+            .conn
+            .lock()
+            .query_row(
+                "SELECT last_polled_at FROM feed_state WHERE feed = ?1",
+                params![feed],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// Record a poll of `feed` and its error, if any.
+    pub fn record_poll(&self, feed: &str, error: Option<&str>) -> Result<()> {
+        self.conn.lock().execute(
+            "INSERT INTO feed_state (feed, last_polled_at, last_error) VALUES (?1, ?2, ?3)
+             ON CONFLICT(feed) DO UPDATE SET last_polled_at = ?2, last_error = ?3",
+            params![feed, Utc::now().timestamp(), error],
+        )?;
+        Ok(())
+    }
+
+    /// GUIDs of `feed` already seen.
+    pub fn seen(&self, feed: &str) -> Result<HashSet<String>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare("SELECT guid FROM feed_items WHERE feed = ?1")?;
+        let rows = stmt.query_map(params![feed], |row| row.get(0))?;
+        Ok(rows.collect::<rusqlite::Result<HashSet<String>>>()?)
+    }
+
+    /// Mark `items` of `feed` as seen and forget GUIDs that have been out of
+    /// the feed for longer than the retention period.
+    pub fn mark_seen(&self, feed: &str, items: &[FeedItem]) -> Result<()> {
+        let now = Utc::now().timestamp();
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        for item in items {
+            tx.execute(
+                "INSERT INTO feed_items (feed, guid, seen_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(feed, guid) DO UPDATE SET seen_at = ?3",
+                params![feed, item.guid, now],
+            )?;
+        }
+        tx.execute(
+            "DELETE FROM feed_items WHERE feed = ?1 AND seen_at < ?2",
+            params![feed, now - SEEN_RETENTION_SECS],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+/// Digest message for the new items of `feed`.
+pub fn digest(feed: &str, items: &[FeedItem], max_items: usize) -> String {
+    let mut out = format!("New in {feed} ({}):", items.len());
+    for item in items.iter().take(max_items) {
+        match &item.link {
+            Some(link) => {
+                let _ = write!(out, "\n- [{}]({link})", item.title);
+            }
+            None => {
+                let _ = write!(out, "\n- {}", item.title);
+            }
+        }
+    }
+    if items.len() > max_items {
+        let _ = write!(out, "\n…and {} more", items.len() - max_items);
+    }
+    out
+}
+
+/// Poll every enabled feed whose interval has passed.
+pub async fn poll_due(config: &Config) {
+    if !config.feeds.enabled || !config.feeds.sources.iter().any(|feed| feed.enabled) {
+        return;
+    }
+    let store = match FeedStore::open(&config.workspace_dir) {
+        Ok(store) => store,
+        Err(e) => {
+            tracing::warn!("Failed to open the feed store: {e}");
+            return;
+        }
+    };
+    let now = Utc::now().timestamp();
+    for feed in config.feeds.sources.iter().filter(|feed| feed.enabled) {
+        let interval = feed
+            .interval_minutes
+            .unwrap_or(config.feeds.interval_minutes);
+        let due_at = match store.last_polled(&feed.name) {
+            Ok(last) => last.map_or(0, |last| {
+                last.saturating_add(i64::try_from(interval.saturating_mul(60)).unwrap_or(i64::MAX))
+            }),
+            Err(e) => {
+                tracing::warn!(feed = %feed.name, "Failed to read feed state: {e}");
+                continue;
+            }
+        };
+        if due_at > now {
+            continue;
+        }
+        let result = poll_feed(config, &store, feed).await;
+        if let Err(e) = &result {
+            tracing::warn!(feed = %feed.name, "Feed poll failed: {e:#}");
+        }
+        let error = result.err().map(|e| format!("{e:#}"));
+        if let Err(e) = store.record_poll(&feed.name, error.as_deref()) {
+            tracing::warn!(feed = %feed.name, "Failed to record feed poll: {e}");
+        }
+    }
+}
+
+/// Fetch `feed`, deliver its new items and mark them seen. Returns the
+/// number of items delivered.
+async fn poll_feed(config: &Config, store: &FeedStore, feed: &FeedSourceConfig) -> Result<usize> {
+    let items = fetch(feed.url.trim()).await?;
+    let seen = store.seen(&feed.name)?;
+    // Nothing recorded yet: take the current items as the baseline.
+    let first_poll = seen.is_empty();
+    let fresh: Vec<FeedItem> = items
+        .iter()
+        .filter(|item| !seen.contains(&item.guid) && item.matches(&feed.keywords))
+        .cloned()
+        .collect();
+
+    let mut delivered = 0;
+    if !first_poll && !fresh.is_empty() {
+        if let (Some(channel), Some(to)) = (feed.channel.as_deref(), feed.to.as_deref()) {
+            let message = digest(&feed.name, &fresh, config.feeds.max_items);
+            crate::cron::scheduler::deliver_announcement(config, channel, to, &message).await?;
+            delivered = fresh.len();
+        }
+    }
+    // Only after delivery succeeded, so a failed digest is retried.
+    store.mark_seen(&feed.name, &items)?;
+    Ok(delivered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel>
+    <title>Example</title>
+    <link>https://example.com/</link>
+    <item>
+      <title>Release 1.2 &amp; notes</title>
+      <link>https://example.com/1.2</link>
+      <guid isPermaLink="false">rel-1.2</guid>
+      <pubDate>Tue, 13 Oct 2026 09:00:00 GMT</pubDate>
+      <description><![CDATA[<p>Faster builds.</p>]]></description>
+    </item>
+    <item>
+      <title>No guid here</title>
+      <link>https://example.com/post</link>
+    </item>
+  </channel>
+</rss>"#;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Atom example</title>
+  <link href="https://example.org/"/>
+  <entry>
+    <title type="html">Security advisory</title>
+    <link rel="replies" href="https://example.org/a/comments"/>
+    <link href="https://example.org/a"/>
+    <id>urn:uuid:1225c695</id>
+    <updated>2026-10-14T18:30:02Z</updated>
+    <content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml">Patch <em>now</em></div></content>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn parses_rss_and_atom_items() {
+        let items = parse_feed(RSS).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].guid, "rel-1.2");
+        assert_eq!(items[0].title, "Release 1.2 & notes");
+        assert_eq!(items[0].summary.as_deref(), Some("Faster builds."));
+        assert_eq!(
+            items[0].published.as_deref(),
+            Some("Tue, 13 Oct 2026 09:00:00 GMT")
+        );
+        assert_eq!(items[1].guid, "https://example.com/post");
+
+        let items = parse_feed(ATOM).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].guid, "urn:uuid:1225c695");
+        assert_eq!(items[0].link.as_deref(), Some("https://example.org/a"));
+        assert_eq!(items[0].summary.as_deref(), Some("Patch now"));
+
+        assert!(parse_feed("<html><body>hi</body></html>").is_err());
+    }
+
+    #[test]
+    fn store_tracks_seen_items_and_digest_caps_length() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = FeedStore::open(tmp.path()).unwrap();
+        let items = parse_feed(RSS).unwrap();
+        assert!(store.last_polled("example").unwrap().is_none());
+
+        store.mark_seen("example", &items[..1]).unwrap();
+        store.record_poll("example", None).unwrap();
+        let seen = store.seen("example").unwrap();
+        assert!(seen.contains("rel-1.2") && !seen.contains("https://example.com/post"));
+        assert!(store.last_polled("example").unwrap().is_some());
+
+        assert!(items[0].matches(&["BUILDS".into()]));
+        assert!(!items[1].matches(&["builds".into()]));
+
+        let message = digest("example", &items, 1);
+        assert!(message.starts_with("New in example (2):"));
+        assert!(message.contains("- [Release 1.2 & notes](https://example.com/1.2)"));
+        assert!(message.ends_with("…and 1 more"));
+    }
+}
@@ -52,6 +52,22 @@ pub struct MemoryQuery {
     pub category: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct UsageQuery {
+    pub by: Option<String>,
+    pub since: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct OutboxQuery {
+    pub status: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct PayloadsQuery {
+    pub limit: Option<usize>,
+}
+
 #[derive(Deserialize)]
 pub struct MemoryStoreBody {
     pub key: String,
@@ -59,6 +75,16 @@ pub struct MemoryStoreBody {
     pub category: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct StructuredBody {
+    pub prompt: String,
+    pub schema: serde_json::Value,
+    pub system: Option<String>,
+    pub name: Option<String>,
+    pub model: Option<String>,
+    pub max_repairs: Option<u32>,
+}
+
 #[derive(Deserialize)]
 pub struct CronAddBody {
     pub name: Option<String>,
@@ -502,6 +528,146 @@ pub async fn handle_api_cost(
     }
 }
 
+/// Upper bound for caller-requested repair round-trips.
+const MAX_STRUCTURED_REPAIRS: u32 = 5;
+
+/// POST /api/structured — schema-constrained model reply
+pub async fn handle_api_structured(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<StructuredBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    if !body.schema.is_object() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "schema must be a JSON Schema object"})),
+        )
+            .into_response();
+    }
+
+    let model = body.model.unwrap_or_else(|| state.model.clone());
+    let schema_name = body.name.unwrap_or_else(|| "response".to_string());
+    let request = crate::providers::structured::StructuredRequest {
+        system_prompt: body.system.as_deref(),
+        message: &body.prompt,
+        schema_name: &schema_name,
+        schema: &body.schema,
+        max_repairs: body
+            .max_repairs
+            .unwrap_or(crate::providers::structured::DEFAULT_MAX_REPAIRS)
+            .min(MAX_STRUCTURED_REPAIRS),
+    };
+    match crate::providers::structured::chat_structured(
+        state.provider.as_ref(),
+        &request,
+        &model,
+        state.temperature,
+    )
+    .await
+    {
+        Ok(reply) => Json(serde_json::json!({
+            "data": reply.value,
+            "attempts": reply.attempts,
+            "model": model,
+        }))
+        .into_response(),
+        Err(e) => (
+            StatusCode::BAD_GATEWAY,
+            Json(serde_json::json!({
+                "error": crate::providers::sanitize_api_error(&e.to_string())
+            })),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/usage — token usage breakdown (?by=day|session|channel|principal|model&since=YYYY-MM-DD)
+pub async fn handle_api_usage(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<UsageQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let by = params.by.unwrap_or_else(|| "day".to_string());
+    let Some(group) = crate::cost::UsageGroup::parse(&by) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "by must be one of day, session, channel, principal, model"
+            })),
+        )
+            .into_response();
+    };
+    let since = match params
+        .since
+        .as_deref()
+        .map(|raw| chrono::NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d"))
+    {
+        None => None,
+        Some(Ok(day)) => Some(day),
+        Some(Err(_)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": "since must be a YYYY-MM-DD date"})),
+            )
+                .into_response();
+        }
+    };
+
+    let Some(ref ledger) = state.usage_ledger else {
+        return Json(serde_json::json!({"by": by, "usage": []})).into_response();
+    };
+    match ledger.report(group, since) {
+        Ok(rows) => Json(serde_json::json!({"by": by, "usage": rows})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Usage report failed: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/turns/{id}/report — execution report of a turn, by turn id or
+/// channel message id
+pub async fn handle_api_turn_report(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let lookup = tokio::task::spawn_blocking(move || {
+        crate::observability::turn_report::find_report(&workspace_dir, &id)
+    })
+    .await;
+    match lookup
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result)
+    {
+        Ok(Some(report)) => Json(report).into_response(),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "No turn report found for this id"})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Turn report lookup failed: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
 /// GET /api/cli-tools — discovered CLI tools
 pub async fn handle_api_cli_tools(
     State(state): State<AppState>,
@@ -529,6 +695,240 @@ pub async fn handle_api_health(
     Json(serde_json::json!({"health": snapshot})).into_response()
 }
 
+// ── Admin: live channel runtime ─────────────────────────────────
+
+/// 503 response for admin endpoints when no channel runtime shares this process.
+fn require_channel_runtime() -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    if crate::channels::admin::is_running() {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({
+                "error": "Channel runtime is not running in this process (start it with `zeroclaw daemon`)"
+            })),
+        ))
+    }
+}
+
+fn admin_failure(action: &str, e: &anyhow::Error) -> axum::response::Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::json!({"error": format!("{action} failed: {e}")})),
+    )
+        .into_response()
+}
+
+/// GET /api/admin/channels — connected channels with health
+pub async fn handle_api_admin_channels(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).and_then(|()| require_channel_runtime()) {
+        return e.into_response();
+    }
+
+    match crate::channels::admin::list_channels().await {
+        Ok(channels) => Json(serde_json::json!({"channels": channels})).into_response(),
+        Err(e) => admin_failure("Channel listing", &e),
+    }
+}
+
+/// POST /api/admin/reload — reload config.toml into the gateway and channel runtime
+pub async fn handle_api_admin_reload(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = match crate::config::Config::load_or_init().await {
+        Ok(config) => config,
+        Err(e) => return admin_failure("Config reload", &e),
+    };
+    if let Err(e) = config.validate() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("Invalid config: {e}")})),
+        )
+            .into_response();
+    }
+    *state.config.lock() = config;
+
+    let channels = if crate::channels::admin::is_running() {
+        match crate::channels::admin::reload_config().await {
+            Ok(summary) => Some(summary),
+            Err(e) => return admin_failure("Channel runtime reload", &e),
+        }
+    } else {
+        None
+    };
+
+    Json(serde_json::json!({"status": "ok", "channels": channels})).into_response()
+}
+
+/// GET /api/admin/approvals — effective non-CLI approval grants and pending requests
+pub async fn handle_api_admin_approvals(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).and_then(|()| require_channel_runtime()) {
+        return e.into_response();
+    }
+
+    match crate::channels::admin::approvals() {
+        Ok(approvals) => Json(serde_json::json!({"approvals": approvals})).into_response(),
+        Err(e) => admin_failure("Approval listing", &e),
+    }
+}
+
+/// GET /api/admin/tools — registered tools with risk tier, approval policy and last error (like `/tools`)
+pub async fn handle_api_admin_tools(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).and_then(|()| require_channel_runtime()) {
+        return e.into_response();
+    }
+
+    match crate::channels::admin::tools() {
+        Ok(tools) => Json(serde_json::json!({"tools": tools})).into_response(),
+        Err(e) => admin_failure("Tool listing", &e),
+    }
+}
+
+/// DELETE /api/admin/approvals/{tool} — revoke a tool's approval (like `/unapprove`)
+pub async fn handle_api_admin_approval_revoke(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(tool): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).and_then(|()| require_channel_runtime()) {
+        return e.into_response();
+    }
+
+    match crate::channels::admin::revoke_approval(&tool).await {
+        Ok(revoked) => Json(serde_json::json!({"revoked": revoked})).into_response(),
+        Err(e) => admin_failure("Approval revoke", &e),
+    }
+}
+
+/// GET /api/admin/sessions — in-memory channel conversations
+pub async fn handle_api_admin_sessions(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).and_then(|()| require_channel_runtime()) {
+        return e.into_response();
+    }
+
+    match crate::channels::admin::sessions().await {
+        Ok(sessions) => Json(serde_json::json!({"sessions": sessions})).into_response(),
+        Err(e) => admin_failure("Session listing", &e),
+    }
+}
+
+/// DELETE /api/admin/sessions/{key} — clear one sender's history and pins
+pub async fn handle_api_admin_session_clear(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(key): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).and_then(|()| require_channel_runtime()) {
+        return e.into_response();
+    }
+
+    match crate::channels::admin::clear_session(&key).await {
+        Ok(cleared) => Json(serde_json::json!({"key": key, "cleared": cleared})).into_response(),
+        Err(e) => admin_failure("Session clear", &e),
+    }
+}
+
+/// GET /api/admin/outbox?status=dead — outbound replies (dead letters by default)
+pub async fn handle_api_admin_outbox(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<OutboxQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).and_then(|()| require_channel_runtime()) {
+        return e.into_response();
+    }
+
+    let status = match params.status.as_deref().unwrap_or("dead") {
+        "all" => None,
+        raw => match crate::channels::outbox::OutboundStatus::parse(raw) {
+            Some(status) => Some(status),
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({
+                        "error": "status must be dead, pending, delivered or all"
+                    })),
+                )
+                    .into_response()
+            }
+        },
+    };
+    match crate::channels::admin::outbound(status).await {
+        Ok(replies) => Json(serde_json::json!({"replies": replies})).into_response(),
+        Err(e) => admin_failure("Outbox listing", &e),
+    }
+}
+
+/// GET /api/admin/payloads/{channel} — recent archived webhook bodies, newest first
+pub async fn handle_api_admin_payloads(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(channel): Path<String>,
+    Query(params): Query<PayloadsQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let limit = params.limit.unwrap_or(20);
+    match super::payload_archive::recent(&workspace_dir, &channel, limit) {
+        Ok(payloads) => Json(serde_json::json!({"payloads": payloads})).into_response(),
+        Err(e) => admin_failure("Payload listing", &e),
+    }
+}
+
+/// POST /api/admin/outbox/{id}/retry — queue an undelivered reply for immediate delivery
+pub async fn handle_api_admin_outbox_retry(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).and_then(|()| require_channel_runtime()) {
+        return e.into_response();
+    }
+
+    match crate::channels::admin::retry_outbound(id).await {
+        Ok(reply) => Json(serde_json::json!({"queued": reply})).into_response(),
+        Err(e) => admin_failure("Outbox retry", &e),
+    }
+}
+
+/// DELETE /api/admin/outbox/{id} — drop an undelivered reply
+pub async fn handle_api_admin_outbox_discard(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).and_then(|()| require_channel_runtime()) {
+        return e.into_response();
+    }
+
+    match crate::channels::admin::discard_outbound(id).await {
+        Ok(discarded) => {
+            Json(serde_json::json!({"id": id, "discarded": discarded})).into_response()
+        }
+        Err(e) => admin_failure("Outbox discard", &e),
+    }
+}
+
 // ── Helpers ─────────────────────────────────────────────────────
 
 fn normalize_dashboard_config_toml(root: &mut toml::Value) {
@@ -626,6 +1026,14 @@ fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Confi
     for agent in masked.agents.values_mut() {
         mask_optional_secret(&mut agent.api_key);
     }
+    mask_required_secret(&mut masked.mailbox.password);
+    mask_optional_secret(&mut masked.prometheus.bearer_token);
+    mask_optional_secret(&mut masked.jira.api_token);
+    mask_optional_secret(&mut masked.linear.api_key);
+    for target in masked.notify.targets.values_mut() {
+        mask_optional_secret(&mut target.token);
+        mask_optional_secret(&mut target.user_key);
+    }
 
     if let Some(telegram) = masked.channels_config.telegram.as_mut() {
         mask_required_secret(&mut telegram.bot_token);
@@ -693,6 +1101,9 @@ fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Confi
         mask_required_secret(&mut clawdtalk.api_key);
         mask_optional_secret(&mut clawdtalk.webhook_secret);
     }
+    if let Some(queue) = masked.channels_config.queue.as_mut() {
+        mask_required_secret(&mut queue.url);
+    }
     masked
 }
 
@@ -760,6 +1171,19 @@ fn restore_masked_sensitive_fields(
             restore_optional_secret(&mut agent.api_key, &current_agent.api_key);
         }
     }
+    restore_required_secret(&mut incoming.mailbox.password, &current.mailbox.password);
+    restore_optional_secret(
+        &mut incoming.prometheus.bearer_token,
+        &current.prometheus.bearer_token,
+    );
+    restore_optional_secret(&mut incoming.jira.api_token, &current.jira.api_token);
+    restore_optional_secret(&mut incoming.linear.api_key, &current.linear.api_key);
+    for (name, target) in &mut incoming.notify.targets {
+        if let Some(current_target) = current.notify.targets.get(name) {
+            restore_optional_secret(&mut target.token, &current_target.token);
+            restore_optional_secret(&mut target.user_key, &current_target.user_key);
+        }
+    }
 
     if let (Some(incoming_ch), Some(current_ch)) = (
         incoming.channels_config.telegram.as_mut(),
@@ -893,6 +1317,12 @@ fn restore_masked_sensitive_fields(
         restore_required_secret(&mut incoming_ch.api_key, &current_ch.api_key);
         restore_optional_secret(&mut incoming_ch.webhook_secret, &current_ch.webhook_secret);
     }
+    if let (Some(incoming_ch), Some(current_ch)) = (
+        incoming.channels_config.queue.as_mut(),
+        current.channels_config.queue.as_ref(),
+    ) {
+        restore_required_secret(&mut incoming_ch.url, &current_ch.url);
+    }
 }
 
 fn hydrate_config_for_save(
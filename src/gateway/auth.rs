@@ -0,0 +1,395 @@
+//! Gateway-wide request authentication (`[gateway.auth]`).
+//!
+//! Runs as middleware in front of every route, before handler-level pairing
+//! and webhook signature checks. Rules are compiled once at startup:
+//! - IP allowlists (global, or replaced per route)
+//! - per-route API keys in `X-API-Key` / `Authorization: Bearer`
+//! - client certificates verified by a TLS-terminating proxy
+//!
+//! Failures return structured JSON: `401` for a missing or wrong API key,
+//! `403` for a disallowed address or missing client certificate.
+
+use super::{client_ip_from_request, route_prefix_matches};
+use crate::config::GatewayAuthConfig;
+use crate::security::pairing::constant_time_eq;
+use crate::tools::url_validation::{cidr_contains_ip, parse_cidr};
+use anyhow::{Context, Result};
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, HeaderName, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+/// Header carrying a per-route API key.
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Value the proxy sets in the client-cert header after a successful verify.
+const CLIENT_CERT_VERIFIED: &str = "SUCCESS";
+
+type IpRange = (IpAddr, u8);
+
+fn parse_ip_allowlist(field: &str, entries: &[String]) -> Result<Vec<IpRange>> {
+    entries
+        .iter()
+        .map(|entry| {
+            let parsed = if entry.contains('/') {
+                parse_cidr(entry)
+            } else {
+                entry
+                    .trim()
+                    .parse::<IpAddr>()
+                    .map(|ip| (ip, if ip.is_ipv4() { 32 } else { 128 }))
+                    .map_err(anyhow::Error::from)
+            };
+            parsed.with_context(|| format!("{field}: invalid IP or CIDR '{entry}'"))
+        })
+        .collect()
+}
+
+struct RouteRule {
+    prefix: String,
+    api_key_hashes: Vec<String>,
+    ip_allowlist: Vec<IpRange>,
+    require_client_cert: bool,
+}
+
+impl RouteRule {
+    fn matches(&self, path: &str) -> bool {
+        route_prefix_matches(&self.prefix, path)
+    }
+}
+
+/// Why a request was turned away.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthRejection {
+    pub status: StatusCode,
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl IntoResponse for AuthRejection {
+    fn into_response(self) -> Response {
+        let body = Json(serde_json::json!({
+            "error": self.message,
+            "code": self.code,
+        }));
+        if self.status == StatusCode::UNAUTHORIZED {
+            (self.status, [(header::WWW_AUTHENTICATE, "ApiKey")], body).into_response()
+        } else {
+            (self.status, body).into_response()
+        }
+    }
+}
+
+/// Compiled `[gateway.auth]` rules.
+pub struct GatewayAuth {
+    ip_allowlist: Vec<IpRange>,
+    require_client_cert: bool,
+    client_cert_header: HeaderName,
+    routes: Vec<RouteRule>,
+    trust_forwarded_headers: bool,
+}
+
+impl GatewayAuth {
+    /// Compile the config, or `None` when `[gateway.auth]` is disabled.
+    pub fn from_config(
+        config: &GatewayAuthConfig,
+        trust_forwarded_headers: bool,
+    ) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        // Without a trusted proxy any client could send the verify header.
+        if !trust_forwarded_headers
+            && (config.require_client_cert
+                || config.routes.iter().any(|route| route.require_client_cert))
+        {
+            anyhow::bail!(
+                "gateway.auth require_client_cert needs gateway.trust_forwarded_headers = true"
+            );
+        }
+
+        let mut routes = config
+            .routes
+            .iter()
+            .enumerate()
+            .map(|(i, route)| {
+                Ok(RouteRule {
+                    // `/api/` and `/api` cover the same paths.
+                    prefix: route.path.trim().trim_end_matches('/').to_string(),
+                    api_key_hashes: route
+                        .api_keys
+                        .iter()
+                        .map(|key| super::hash_webhook_secret(key.trim()))
+                        .collect(),
+                    ip_allowlist: parse_ip_allowlist(
+                        &format!("gateway.auth.routes[{i}].ip_allowlist"),
+                        &route.ip_allowlist,
+                    )?,
+                    require_client_cert: route.require_client_cert,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // Longest prefix first so the most specific rule wins.
+        routes.sort_by(|a, b| b.prefix.len().cmp(&a.prefix.len()));
+
+        Ok(Some(Self {
+            ip_allowlist: parse_ip_allowlist("gateway.auth.ip_allowlist", &config.ip_allowlist)?,
+            require_client_cert: config.require_client_cert,
+            client_cert_header: HeaderName::try_from(config.client_cert_header.trim())
+                .context("gateway.auth.client_cert_header is not a valid header name")?,
+            routes,
+            trust_forwarded_headers,
+        }))
+    }
+
+    /// Check one request against the global and matching route rules.
+    pub fn check(
+        &self,
+        path: &str,
+        client_ip: Option<IpAddr>,
+        headers: &HeaderMap,
+    ) -> Result<(), AuthRejection> {
+        let route = self.routes.iter().find(|rule| rule.matches(path));
+
+        let allowlist = route
+            .map(|rule| rule.ip_allowlist.as_slice())
+            .filter(|list| !list.is_empty())
+            .unwrap_or(&self.ip_allowlist);
+        if !allowlist.is_empty()
+            && !client_ip
+                .is_some_and(|ip| allowlist.iter().any(|range| cidr_contains_ip(*range, ip)))
+        {
+            return Err(AuthRejection {
+                status: StatusCode::FORBIDDEN,
+                code: "ip_not_allowed",
+                message: "Client address is not in the gateway IP allowlist".into(),
+            });
+        }
+
+        if self.require_client_cert || route.is_some_and(|rule| rule.require_client_cert) {
+            let verified = headers
+                .get(&self.client_cert_header)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|v| v.trim() == CLIENT_CERT_VERIFIED);
+            if !verified {
+                return Err(AuthRejection {
+                    status: StatusCode::FORBIDDEN,
+                    code: "client_cert_required",
+                    message: "A verified client certificate is required".into(),
+                });
+            }
+        }
+
+        if let Some(rule) = route.filter(|rule| !rule.api_key_hashes.is_empty()) {
+            let presented = headers
+                .get(API_KEY_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .or_else(|| {
+                    headers
+                        .get(header::AUTHORIZATION)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|auth| auth.strip_prefix("Bearer "))
+                })
+                .map(str::trim)
+                .filter(|key| !key.is_empty());
+            let Some(presented) = presented else {
+                return Err(AuthRejection {
+                    status: StatusCode::UNAUTHORIZED,
+                    code: "api_key_required",
+                    message: "An API key is required for this route".into(),
+                });
+            };
+            let hash = super::hash_webhook_secret(presented);
+            if !rule
+                .api_key_hashes
+                .iter()
+                .any(|expected| constant_time_eq(expected, &hash))
+            {
+                return Err(AuthRejection {
+                    status: StatusCode::UNAUTHORIZED,
+                    code: "invalid_api_key",
+                    message: "Invalid API key".into(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Middleware enforcing [`GatewayAuth`] on every request.
+pub async fn enforce(
+    State(auth): State<Arc<GatewayAuth>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let peer_addr = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    let client_ip =
+        client_ip_from_request(peer_addr, request.headers(), auth.trust_forwarded_headers);
+
+    match auth.check(request.uri().path(), client_ip, request.headers()) {
+        Ok(()) => next.run(request).await,
+        Err(rejection) => {
+            tracing::warn!(
+                path = request.uri().path(),
+                client = client_ip.map(|ip| ip.to_string()).unwrap_or_default(),
+                code = rejection.code,
+                "Gateway auth rejected request"
+            );
+            rejection.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GatewayRouteAuthConfig;
+    use axum::http::HeaderValue;
+
+    fn auth_with(config: GatewayAuthConfig) -> GatewayAuth {
+        GatewayAuth::from_config(
+            &GatewayAuthConfig {
+                enabled: true,
+                ..config
+            },
+            true,
+        )
+        .unwrap()
+        .unwrap()
+    }
+
+    fn ip(raw: &str) -> Option<IpAddr> {
+        Some(raw.parse().unwrap())
+    }
+
+    #[test]
+    fn disabled_config_compiles_to_none() {
+        assert!(
+            GatewayAuth::from_config(&GatewayAuthConfig::default(), false)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn global_and_route_ip_allowlists() {
+        let auth = auth_with(GatewayAuthConfig {
+            ip_allowlist: vec!["10.0.0.0/8".into(), "127.0.0.1".into()],
+            routes: vec![GatewayRouteAuthConfig {
+                path: "/wati".into(),
+                api_keys: Vec::new(),
+                ip_allowlist: vec!["203.0.113.0/24".into()],
+                require_client_cert: false,
+            }],
+            ..GatewayAuthConfig::default()
+        });
+        let headers = HeaderMap::new();
+
+        assert!(auth.check("/health", ip("10.1.2.3"), &headers).is_ok());
+        assert!(auth.check("/health", ip("127.0.0.1"), &headers).is_ok());
+        let rejected = auth
+            .check("/health", ip("192.168.1.1"), &headers)
+            .unwrap_err();
+        assert_eq!(rejected.status, StatusCode::FORBIDDEN);
+        assert_eq!(rejected.code, "ip_not_allowed");
+        assert!(auth.check("/health", None, &headers).is_err());
+
+        // The route allowlist replaces the global one.
+        assert!(auth.check("/wati", ip("203.0.113.9"), &headers).is_ok());
+        assert!(auth.check("/wati", ip("10.1.2.3"), &headers).is_err());
+        // Prefixes match whole path segments only.
+        assert!(auth.check("/watinot", ip("203.0.113.9"), &headers).is_err());
+    }
+
+    #[test]
+    fn route_api_keys_and_client_certs() {
+        let auth = auth_with(GatewayAuthConfig {
+            routes: vec![
+                GatewayRouteAuthConfig {
+                    path: "/api".into(),
+                    api_keys: vec!["outer".into()],
+                    ip_allowlist: Vec::new(),
+                    require_client_cert: false,
+                },
+                GatewayRouteAuthConfig {
+                    path: "/api/admin/".into(),
+                    api_keys: vec!["admin-key".into()],
+                    ip_allowlist: Vec::new(),
+                    require_client_cert: true,
+                },
+            ],
+            ..GatewayAuthConfig::default()
+        });
+        let local = ip("127.0.0.1");
+
+        assert!(auth.check("/health", local, &HeaderMap::new()).is_ok());
+        let missing = auth
+            .check("/api/status", local, &HeaderMap::new())
+            .unwrap_err();
+        assert_eq!(missing.status, StatusCode::UNAUTHORIZED);
+        assert_eq!(missing.code, "api_key_required");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("outer"));
+        assert!(auth.check("/api/status", local, &headers).is_ok());
+
+        // The longer /api/admin rule wins and also needs a client cert.
+        let no_cert = auth
+            .check("/api/admin/sessions", local, &headers)
+            .unwrap_err();
+        assert_eq!(no_cert.code, "client_cert_required");
+        headers.insert("x-ssl-client-verify", HeaderValue::from_static("SUCCESS"));
+        let wrong_key = auth
+            .check("/api/admin/sessions", local, &headers)
+            .unwrap_err();
+        assert_eq!(wrong_key.code, "invalid_api_key");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ssl-client-verify", HeaderValue::from_static("SUCCESS"));
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer admin-key"),
+        );
+        assert!(auth.check("/api/admin/sessions", local, &headers).is_ok());
+    }
+
+    #[test]
+    fn invalid_allowlist_entry_is_rejected() {
+        let err = GatewayAuth::from_config(
+            &GatewayAuthConfig {
+                enabled: true,
+                ip_allowlist: vec!["10.0.0.0/40".into()],
+                ..GatewayAuthConfig::default()
+            },
+            false,
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("gateway.auth.ip_allowlist"));
+    }
+
+    #[test]
+    fn client_cert_requires_trusted_proxy() {
+        let config = GatewayAuthConfig {
+            enabled: true,
+            routes: vec![GatewayRouteAuthConfig {
+                path: "/api/admin".into(),
+                api_keys: Vec::new(),
+                ip_allowlist: Vec::new(),
+                require_client_cert: true,
+            }],
+            ..GatewayAuthConfig::default()
+        };
+        let err = GatewayAuth::from_config(&config, false).err().unwrap();
+        assert!(err.to_string().contains("trust_forwarded_headers"));
+        assert!(GatewayAuth::from_config(&config, true).unwrap().is_some());
+    }
+}
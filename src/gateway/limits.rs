@@ -0,0 +1,331 @@
+//! Per-route request body limits and handler timeouts (`[gateway.limits]`).
+//!
+//! Runs as middleware in front of every route. The body is read up to the
+//! route's limit before the handler sees it, and reading plus handling must
+//! finish within the route's timeout, so an oversized webhook or a slow
+//! upstream cannot exhaust memory or pin a worker task.
+//!
+//! Failures return structured JSON: `413` (`payload_too_large`) and `408`
+//! (`request_timeout`).
+
+use super::route_prefix_matches;
+use crate::config::GatewayLimitsConfig;
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Built-in overrides for routes that legitimately carry larger bodies.
+/// `[[gateway.limits.routes]]` entries for the same prefix replace them.
+const BUILTIN_BODY_LIMITS: &[(&str, usize)] = &[
+    // Full config documents.
+    ("/api/config", 1_048_576),
+    // Chat histories are much bigger than webhook payloads.
+    (
+        "/v1/chat/completions",
+        super::openai_compat::CHAT_COMPLETIONS_MAX_BODY_SIZE,
+    ),
+];
+
+/// Limits in effect for one request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    pub max_body_bytes: usize,
+    pub timeout: Duration,
+}
+
+struct RouteRule {
+    prefix: String,
+    max_body_bytes: Option<usize>,
+    timeout: Option<Duration>,
+}
+
+/// Compiled `[gateway.limits]` rules.
+pub struct RouteLimits {
+    default: Limits,
+    routes: Vec<RouteRule>,
+}
+
+impl RouteLimits {
+    pub fn from_config(config: &GatewayLimitsConfig) -> Self {
+        let mut routes: Vec<RouteRule> = config
+            .routes
+            .iter()
+            .map(|route| RouteRule {
+                // `/v1/` and `/v1` cover the same paths.
+                prefix: route.path.trim().trim_end_matches('/').to_string(),
+                max_body_bytes: route.max_body_bytes,
+                timeout: route.timeout_secs.map(Duration::from_secs),
+            })
+            .collect();
+        for (prefix, max_body_bytes) in BUILTIN_BODY_LIMITS {
+            if !routes.iter().any(|rule| rule.prefix == *prefix) {
+                routes.push(RouteRule {
+                    prefix: (*prefix).to_string(),
+                    max_body_bytes: Some(*max_body_bytes),
+                    timeout: None,
+                });
+            }
+        }
+        // Longest prefix first so the most specific rule wins.
+        routes.sort_by(|a, b| b.prefix.len().cmp(&a.prefix.len()));
+
+        Self {
+            default: Limits {
+                max_body_bytes: config.max_body_bytes,
+                timeout: Duration::from_secs(config.timeout_secs),
+            },
+            routes,
+        }
+    }
+
+    /// Limits for `path`; unset route fields inherit the global values.
+    /// Tenant paths (`/t/<name>/...`) fall back to the rules of the route
+    /// they nest.
+    pub fn for_path(&self, path: &str) -> Limits {
+        let find = |path: &str| {
+            self.routes
+                .iter()
+                .find(|rule| route_prefix_matches(&rule.prefix, path))
+        };
+        match find(path).or_else(|| super::tenants::strip_tenant_prefix(path).and_then(find)) {
+            Some(rule) => Limits {
+                max_body_bytes: rule.max_body_bytes.unwrap_or(self.default.max_body_bytes),
+                timeout: rule.timeout.unwrap_or(self.default.timeout),
+            },
+            None => self.default,
+        }
+    }
+}
+
+fn limit_error(status: StatusCode, code: &'static str, message: String) -> Response {
+    (
+        status,
+        Json(serde_json::json!({
+            "error": message,
+            "code": code,
+        })),
+    )
+        .into_response()
+}
+
+fn payload_too_large(limit: usize) -> Response {
+    limit_error(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        "payload_too_large",
+        format!("Request body exceeds the {limit} byte limit for this route"),
+    )
+}
+
+fn is_length_limit_error(err: axum::Error) -> bool {
+    err.into_inner()
+        .downcast_ref::<http_body_util::LengthLimitError>()
+        .is_some()
+}
+
+/// Middleware enforcing [`RouteLimits`] on every request.
+pub async fn enforce(
+    State(limits): State<Arc<RouteLimits>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+    let limits = limits.for_path(&path);
+
+    // Reject declared oversize bodies without reading them.
+    let declared = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+    if declared.is_some_and(|len| len > limits.max_body_bytes as u64) {
+        tracing::warn!(
+            path = path.as_str(),
+            limit = limits.max_body_bytes,
+            "Request body too large"
+        );
+        return payload_too_large(limits.max_body_bytes);
+    }
+
+    let handle = async {
+        let (parts, body) = request.into_parts();
+        let bytes = match axum::body::to_bytes(body, limits.max_body_bytes).await {
+            Ok(bytes) => bytes,
+            Err(err) if is_length_limit_error(err) => {
+                tracing::warn!(
+                    path = path.as_str(),
+                    limit = limits.max_body_bytes,
+                    "Request body too large"
+                );
+                return payload_too_large(limits.max_body_bytes);
+            }
+            Err(_) => {
+                return limit_error(
+                    StatusCode::BAD_REQUEST,
+                    "body_read_failed",
+                    "Failed to read request body".into(),
+                );
+            }
+        };
+        next.run(Request::from_parts(parts, Body::from(bytes)))
+            .await
+    };
+
+    match tokio::time::timeout(limits.timeout, handle).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!(
+                path = path.as_str(),
+                timeout_secs = limits.timeout.as_secs(),
+                "Request timed out"
+            );
+            limit_error(
+                StatusCode::REQUEST_TIMEOUT,
+                "request_timeout",
+                format!(
+                    "Request did not complete within {}s",
+                    limits.timeout.as_secs()
+                ),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GatewayRouteLimitConfig;
+    use axum::{routing::post, Router};
+    use tower::Service;
+
+    fn route(
+        path: &str,
+        max_body_bytes: Option<usize>,
+        timeout_secs: Option<u64>,
+    ) -> GatewayRouteLimitConfig {
+        GatewayRouteLimitConfig {
+            path: path.into(),
+            max_body_bytes,
+            timeout_secs,
+        }
+    }
+
+    #[test]
+    fn routes_inherit_and_override_global_limits() {
+        let limits = RouteLimits::from_config(&GatewayLimitsConfig {
+            max_body_bytes: 1_000,
+            timeout_secs: 10,
+            routes: vec![
+                route("/webhook", Some(5_000), None),
+                route("/api/chat/", None, Some(120)),
+                route("/api/config", Some(2_000), None),
+            ],
+        });
+
+        let global = Limits {
+            max_body_bytes: 1_000,
+            timeout: Duration::from_secs(10),
+        };
+        assert_eq!(limits.for_path("/health"), global);
+        assert_eq!(limits.for_path("/webhookx"), global);
+        assert_eq!(limits.for_path("/webhook").max_body_bytes, 5_000);
+        assert_eq!(limits.for_path("/webhook").timeout, global.timeout);
+        assert_eq!(
+            limits.for_path("/api/chat").timeout,
+            Duration::from_secs(120)
+        );
+        assert_eq!(limits.for_path("/api/chat").max_body_bytes, 1_000);
+        // Config entries replace the built-in defaults for the same prefix.
+        assert_eq!(limits.for_path("/api/config").max_body_bytes, 2_000);
+        assert_eq!(
+            limits.for_path("/v1/chat/completions").max_body_bytes,
+            super::super::openai_compat::CHAT_COMPLETIONS_MAX_BODY_SIZE
+        );
+        // Tenant routes inherit the rules of the route they nest.
+        assert_eq!(limits.for_path("/t/sales/webhook").max_body_bytes, 5_000);
+        assert_eq!(limits.for_path("/t/sales").max_body_bytes, 1_000);
+    }
+
+    fn app(config: GatewayLimitsConfig) -> Router {
+        Router::new()
+            .route("/echo", post(|body: String| async move { body }))
+            .route(
+                "/slow",
+                post(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    "done"
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(RouteLimits::from_config(&config)),
+                enforce,
+            ))
+    }
+
+    async fn send(app: &Router, request: Request) -> Response {
+        app.clone().call(request).await.unwrap()
+    }
+
+    async fn json_body(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn oversized_bodies_are_rejected_with_413() {
+        let app = app(GatewayLimitsConfig {
+            max_body_bytes: 16,
+            ..GatewayLimitsConfig::default()
+        });
+
+        let ok = send(
+            &app,
+            Request::post("/echo").body(Body::from("small")).unwrap(),
+        )
+        .await;
+        assert_eq!(ok.status(), StatusCode::OK);
+
+        let declared = send(
+            &app,
+            Request::post("/echo")
+                .header(header::CONTENT_LENGTH, "1024")
+                .body(Body::from("x".repeat(1024)))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(declared.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(json_body(declared).await["code"], "payload_too_large");
+
+        // Streamed bodies without Content-Length are cut off while reading.
+        let chunks = futures_util::stream::iter(
+            (0..8).map(|_| Ok::<_, std::io::Error>(axum::body::Bytes::from("xxxx"))),
+        );
+        let streamed = send(
+            &app,
+            Request::post("/echo")
+                .body(Body::from_stream(chunks))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(streamed.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn slow_handlers_time_out_with_408() {
+        let app = app(GatewayLimitsConfig {
+            routes: vec![route("/slow", None, Some(1))],
+            ..GatewayLimitsConfig::default()
+        });
+
+        let response = send(&app, Request::post("/slow").body(Body::empty()).unwrap()).await;
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+        assert_eq!(json_body(response).await["code"], "request_timeout");
+    }
+}
@@ -3,15 +3,20 @@
 //! This module replaces the raw TCP implementation with axum for:
 //! - Proper HTTP/1.1 parsing and compliance
 //! - Content-Length validation (handled by hyper)
-//! - Request body size limits (64KB max)
-//! - Request timeouts (30s) to prevent slow-loris attacks
+//! - Request body size limits (64KB default, per route via `[gateway.limits]`)
+//! - Request timeouts (30s default) to prevent slow-loris attacks
 //! - Header sanitization (handled by axum/hyper)
 
 pub mod api;
+pub mod auth;
+pub mod limits;
 mod openai_compat;
 mod openclaw_compat;
+pub mod payload_archive;
+pub mod sessions;
 pub mod sse;
 pub mod static_files;
+pub mod tenants;
 pub mod ws;
 
 use crate::channels::{
@@ -19,8 +24,9 @@ use crate::channels::{
     WhatsAppChannel,
 };
 use crate::config::Config;
-use crate::cost::CostTracker;
+use crate::cost::{CostTracker, UsageLedger};
 use crate::memory::{self, Memory, MemoryCategory};
+use crate::observability::turn;
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
 use crate::security::pairing::{constant_time_eq, is_public_bind, PairingGuard};
@@ -34,7 +40,7 @@ use axum::{
     extract::{ConnectInfo, Query, State},
     http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Json, Response},
-    routing::{delete, get, post, put},
+    routing::{delete, get, post},
     Router,
 };
 use futures_util::StreamExt;
@@ -43,14 +49,8 @@ use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tower_http::limit::RequestBodyLimitLayer;
-use tower_http::timeout::TimeoutLayer;
 use uuid::Uuid;
 
-/// Maximum request body size (64KB) — prevents memory exhaustion
-pub const MAX_BODY_SIZE: usize = 65_536;
-/// Request timeout (30s) — prevents slow-loris attacks
-pub const REQUEST_TIMEOUT_SECS: u64 = 30;
 /// Sliding window used by gateway rate limiting.
 pub const RATE_LIMIT_WINDOW_SECS: u64 = 60;
 /// Fallback max distinct client keys tracked in gateway rate limiter.
@@ -261,19 +261,34 @@ fn forwarded_client_ip(headers: &HeaderMap) -> Option<IpAddr> {
         .and_then(parse_client_ip)
 }
 
-pub(crate) fn client_key_from_request(
+fn client_ip_from_request(
     peer_addr: Option<SocketAddr>,
     headers: &HeaderMap,
     trust_forwarded_headers: bool,
-) -> String {
+) -> Option<IpAddr> {
     if trust_forwarded_headers {
         if let Some(ip) = forwarded_client_ip(headers) {
-            return ip.to_string();
+            return Some(ip);
         }
     }
 
-    peer_addr
-        .map(|addr| addr.ip().to_string())
+    peer_addr.map(|addr| addr.ip())
+}
+
+/// Whether `path` falls under a `[gateway.*.routes]` prefix. Prefixes match
+/// whole path segments, so `/wati` covers `/wati/x` but not `/watinot`.
+fn route_prefix_matches(prefix: &str, path: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+pub(crate) fn client_key_from_request(
+    peer_addr: Option<SocketAddr>,
+    headers: &HeaderMap,
+    trust_forwarded_headers: bool,
+) -> String {
+    client_ip_from_request(peer_addr, headers, trust_forwarded_headers)
+        .map(|ip| ip.to_string())
         .unwrap_or_else(|| "unknown".to_string())
 }
 
@@ -296,6 +311,8 @@ pub struct AppState {
     pub auto_save: bool,
     /// SHA-256 hash of `X-Webhook-Secret` (hex-encoded), never plaintext.
     pub webhook_secret_hash: Option<Arc<str>>,
+    /// SHA-256 hashes (hex) of `[gateway] api_keys` accepted on `/v1/*`.
+    pub api_key_hashes: Arc<Vec<String>>,
     pub pairing: Arc<PairingGuard>,
     pub trust_forwarded_headers: bool,
     pub rate_limiter: Arc<GatewayRateLimiter>,
@@ -324,36 +341,17 @@ pub struct AppState {
     pub max_tool_iterations: usize,
     /// Cost tracker (optional, for web dashboard cost page)
     pub cost_tracker: Option<Arc<CostTracker>>,
+    /// Per-call usage ledger (optional, for `/api/usage` breakdowns)
+    pub usage_ledger: Option<Arc<UsageLedger>>,
     /// SSE broadcast channel for real-time events
     pub event_tx: tokio::sync::broadcast::Sender<serde_json::Value>,
+    /// `X-Session-Id` conversation history for `/v1/chat/completions`
+    pub chat_sessions: Arc<sessions::GatewaySessions>,
 }
 
-/// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
+/// Build the handler state of one workspace from its config.
 #[allow(clippy::too_many_lines)]
-pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
-    // ── Security: refuse public bind without tunnel or explicit opt-in ──
-    if is_public_bind(host) && config.tunnel.provider == "none" && !config.gateway.allow_public_bind
-    {
-        anyhow::bail!(
-            "🛑 Refusing to bind to {host} — gateway would be exposed to the internet.\n\
-             Fix: use --host 127.0.0.1 (default), configure a tunnel, or set\n\
-             [gateway] allow_public_bind = true in config.toml (NOT recommended)."
-        );
-    }
-    let config_state = Arc::new(Mutex::new(config.clone()));
-
-    // ── Hooks ──────────────────────────────────────────────────────
-    let hooks: Option<std::sync::Arc<crate::hooks::HookRunner>> = if config.hooks.enabled {
-        Some(std::sync::Arc::new(crate::hooks::HookRunner::new()))
-    } else {
-        None
-    };
-
-    let addr: SocketAddr = format!("{host}:{port}").parse()?;
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    let actual_port = listener.local_addr()?.port();
-    let display_addr = format!("{host}:{actual_port}");
-
+fn build_state(config: &Config) -> Result<AppState> {
     let provider: Arc<dyn Provider> = Arc::from(providers::create_resilient_provider_with_options(
         config.default_provider.as_deref().unwrap_or("openrouter"),
         config.api_key.as_deref(),
@@ -370,6 +368,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             custom_provider_api_mode: config.provider_api.map(|mode| mode.as_compatible_mode()),
             max_tokens_override: None,
             model_support_vision: config.model_support_vision,
+            model_support_tools: config.model_support_tools,
         },
     )?);
     let model = config
@@ -412,7 +411,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         &config.workspace_dir,
         &config.agents,
         config.api_key.as_deref(),
-        &config,
+        config,
     ));
     let tools_registry: Arc<Vec<ToolSpec>> =
         Arc::new(tools_registry_exec.iter().map(|t| t.spec()).collect());
@@ -432,6 +431,18 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         None
     };
 
+    let usage_ledger = if config.cost.enabled {
+        match UsageLedger::open(&config.workspace_dir, &config.cost) {
+            Ok(ledger) => Some(Arc::new(ledger)),
+            Err(e) => {
+                tracing::warn!("Failed to open usage ledger: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     // SSE broadcast channel for real-time events
     let (event_tx, _event_rx) = tokio::sync::broadcast::channel::<serde_json::Value>(256);
     // Extract webhook secret for authentication
@@ -444,6 +455,17 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             })
         });
 
+    let api_key_hashes: Arc<Vec<String>> = Arc::new(
+        config
+            .gateway
+            .api_keys
+            .iter()
+            .map(|key| key.trim())
+            .filter(|key| !key.is_empty())
+            .map(hash_webhook_secret)
+            .collect(),
+    );
+
     // WhatsApp channel (if configured)
     let whatsapp_channel: Option<Arc<WhatsAppChannel>> = config
         .channels_config
@@ -589,6 +611,86 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         idempotency_max_keys,
     ));
 
+    // Wrap observer with broadcast capability for SSE
+    // Use cost-tracking observer when cost tracking is enabled
+    let base_observer = crate::observability::create_observer_with_cost_tracking(
+        &config.observability,
+        cost_tracker.clone(),
+        &config.cost,
+    );
+    let broadcast_observer: Arc<dyn crate::observability::Observer> =
+        Arc::new(sse::BroadcastObserver::new(base_observer, event_tx.clone()));
+
+    Ok(AppState {
+        config: Arc::new(Mutex::new(config.clone())),
+        provider,
+        model,
+        temperature,
+        mem,
+        auto_save: config.memory.auto_save,
+        webhook_secret_hash,
+        api_key_hashes,
+        pairing,
+        trust_forwarded_headers: config.gateway.trust_forwarded_headers,
+        rate_limiter,
+        idempotency_store,
+        whatsapp: whatsapp_channel,
+        whatsapp_app_secret,
+        linq: linq_channel,
+        linq_signing_secret,
+        nextcloud_talk: nextcloud_talk_channel,
+        nextcloud_talk_webhook_secret,
+        wati: wati_channel,
+        qq: qq_channel,
+        qq_webhook_enabled,
+        observer: broadcast_observer,
+        tools_registry,
+        tools_registry_exec,
+        multimodal: multimodal_config,
+        max_tool_iterations,
+        cost_tracker,
+        usage_ledger,
+        event_tx,
+        chat_sessions: Arc::new(sessions::GatewaySessions::from_config(config)),
+    })
+}
+
+/// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
+#[allow(clippy::too_many_lines)]
+pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
+    // ── Security: refuse public bind without tunnel or explicit opt-in ──
+    if is_public_bind(host) && config.tunnel.provider == "none" && !config.gateway.allow_public_bind
+    {
+        anyhow::bail!(
+            "🛑 Refusing to bind to {host} — gateway would be exposed to the internet.\n\
+             Fix: use --host 127.0.0.1 (default), configure a tunnel, or set\n\
+             [gateway] allow_public_bind = true in config.toml (NOT recommended)."
+        );
+    }
+
+    // ── Hooks ──────────────────────────────────────────────────────
+    let hooks: Option<std::sync::Arc<crate::hooks::HookRunner>> = if config.hooks.enabled {
+        Some(std::sync::Arc::new(crate::hooks::HookRunner::new()))
+    } else {
+        None
+    };
+
+    let addr: SocketAddr = format!("{host}:{port}").parse()?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let actual_port = listener.local_addr()?.port();
+    let display_addr = format!("{host}:{actual_port}");
+
+    let state = build_state(&config)?;
+    let tenant_configs = tenants::load(&config).await?;
+    let tenant_states = tenant_configs
+        .iter()
+        .map(|(name, tenant_config)| {
+            build_state(tenant_config)
+                .with_context(|| format!("Failed to start gateway tenant {name}"))
+                .map(|state| (name.clone(), state))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
     // ── Tunnel ────────────────────────────────────────────────
     let tunnel = crate::tunnel::create_tunnel(&config.tunnel)?;
     let mut tunnel_url: Option<String> = None;
@@ -615,21 +717,21 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     println!("  POST /pair      — pair a new client (X-Pairing-Code header)");
     println!("  POST /webhook   — {{\"message\": \"your prompt\"}}");
     println!("  POST /api/chat  — {{\"message\": \"...\", \"context\": [...]}} (tools-enabled, OpenClaw compat)");
-    if whatsapp_channel.is_some() {
+    if state.whatsapp.is_some() {
         println!("  GET  /whatsapp  — Meta webhook verification");
         println!("  POST /whatsapp  — WhatsApp message webhook");
     }
-    if linq_channel.is_some() {
+    if state.linq.is_some() {
         println!("  POST /linq      — Linq message webhook (iMessage/RCS/SMS)");
     }
-    if wati_channel.is_some() {
+    if state.wati.is_some() {
         println!("  GET  /wati      — WATI webhook verification");
         println!("  POST /wati      — WATI message webhook");
     }
-    if nextcloud_talk_channel.is_some() {
+    if state.nextcloud_talk.is_some() {
         println!("  POST /nextcloud-talk — Nextcloud Talk bot webhook");
     }
-    if qq_webhook_enabled {
+    if state.qq_webhook_enabled {
         println!("  POST /qq        — QQ Bot webhook (validation + events)");
     }
     if config.gateway.node_control.enabled {
@@ -641,18 +743,28 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     println!("  GET  /ws/chat   — WebSocket agent chat");
     println!("  GET  /health    — health check");
     println!("  GET  /metrics   — Prometheus metrics");
-    if let Some(code) = pairing.pairing_code() {
+    if let Some(code) = state.pairing.pairing_code() {
         println!();
         println!("  🔐 PAIRING REQUIRED — use this one-time code:");
         println!("     ┌──────────────┐");
         println!("     │  {code}  │");
         println!("     └──────────────┘");
         println!("     Send: POST /pair with header X-Pairing-Code: {code}");
-    } else if pairing.require_pairing() {
+    } else if state.pairing.require_pairing() {
         println!("  🔒 Pairing: ACTIVE (bearer token required)");
     } else {
         println!("  ⚠️  Pairing: DISABLED (all requests accepted)");
     }
+    if !tenant_states.is_empty() {
+        println!("  🏢 Tenants (/t/<name>/... or X-ZeroClaw-Tenant: <name>):");
+        for (name, tenant) in &tenant_states {
+            let workspace = tenant.config.lock().workspace_dir.display().to_string();
+            match tenant.pairing.pairing_code() {
+                Some(code) => println!("     {name} — {workspace} (pairing code: {code})"),
+                None => println!("     {name} — {workspace}"),
+            }
+        }
+    }
     println!("  Press Ctrl+C to stop.\n");
 
     crate::health::mark_component_ok("gateway");
@@ -662,71 +774,104 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         hooks.fire_gateway_start(host, actual_port).await;
     }
 
-    // Wrap observer with broadcast capability for SSE
-    // Use cost-tracking observer when cost tracking is enabled
-    let base_observer = crate::observability::create_observer_with_cost_tracking(
-        &config.observability,
-        cost_tracker.clone(),
-        &config.cost,
-    );
-    let broadcast_observer: Arc<dyn crate::observability::Observer> =
-        Arc::new(sse::BroadcastObserver::new(base_observer, event_tx.clone()));
-
-    let state = AppState {
-        config: config_state,
-        provider,
-        model,
-        temperature,
-        mem,
-        auto_save: config.memory.auto_save,
-        webhook_secret_hash,
-        pairing,
-        trust_forwarded_headers: config.gateway.trust_forwarded_headers,
-        rate_limiter,
-        idempotency_store,
-        whatsapp: whatsapp_channel,
-        whatsapp_app_secret,
-        linq: linq_channel,
-        linq_signing_secret,
-        nextcloud_talk: nextcloud_talk_channel,
-        nextcloud_talk_webhook_secret,
-        wati: wati_channel,
-        qq: qq_channel,
-        qq_webhook_enabled,
-        observer: broadcast_observer,
-        tools_registry,
-        tools_registry_exec,
-        multimodal: multimodal_config,
-        max_tool_iterations,
-        cost_tracker,
-        event_tx,
-    };
-
-    // Config PUT needs larger body limit (1MB)
-    let config_put_router = Router::new()
-        .route("/api/config", put(api::handle_api_config_put))
-        .layer(RequestBodyLimitLayer::new(1_048_576));
-
-    // The OpenAI-compatible endpoints use a larger body limit (512KB) because
-    // chat histories can be much bigger than the default 64KB webhook limit.
-    // They get their own nested router with a separate body limit layer.
-    //
-    // NOTE: The /v1/chat/completions handler routes through the full agent loop
-    // (run_gateway_chat_with_tools) via openclaw_compat, giving OpenClaw callers
-    // tools + memory support. The original simple-chat handler is preserved in
-    // openai_compat.rs for reference.
-    let openai_compat_routes = Router::new()
+    // Build router with middleware
+    let mut app = workspace_routes()
+        // ── Process-wide routes, served for the main workspace only ──
+        .route("/api/cli-tools", get(api::handle_api_cli_tools))
+        .route("/api/health", get(api::handle_api_health))
+        .route("/api/admin/channels", get(api::handle_api_admin_channels))
+        .route("/api/admin/reload", post(api::handle_api_admin_reload))
+        .route("/api/admin/approvals", get(api::handle_api_admin_approvals))
         .route(
-            "/v1/chat/completions",
-            post(openclaw_compat::handle_v1_chat_completions_with_tools),
+            "/api/admin/approvals/{tool}",
+            delete(api::handle_api_admin_approval_revoke),
+        )
+        .route("/api/admin/tools", get(api::handle_api_admin_tools))
+        .route("/api/admin/sessions", get(api::handle_api_admin_sessions))
+        .route(
+            "/api/admin/sessions/{key}",
+            delete(api::handle_api_admin_session_clear),
+        )
+        .route("/api/admin/outbox", get(api::handle_api_admin_outbox))
+        .route(
+            "/api/admin/outbox/{id}",
+            delete(api::handle_api_admin_outbox_discard),
+        )
+        .route(
+            "/api/admin/outbox/{id}/retry",
+            post(api::handle_api_admin_outbox_retry),
+        )
+        .route(
+            "/api/admin/payloads/{channel}",
+            get(api::handle_api_admin_payloads),
         )
-        .layer(RequestBodyLimitLayer::new(
-            openai_compat::CHAT_COMPLETIONS_MAX_BODY_SIZE,
+        .route("/api/node-control", post(handle_node_control))
+        .route("/ws/attach", get(ws::handle_ws_attach))
+        // ── Static assets (web dashboard) ──
+        .route("/_app/{*path}", get(static_files::handle_static))
+        .with_state(state);
+
+    // ── [gateway.tenants]: each workspace under /t/<name> ──
+    for (name, tenant_state) in tenant_states {
+        app = app.nest(&tenants::path_prefix(&name), tenant_routes(tenant_state));
+    }
+
+    let app = app
+        // ── SPA fallback: non-API GET requests serve index.html ──
+        .fallback(get(static_files::handle_spa_fallback))
+        // ── [gateway.limits]: per-route body limits and timeouts ──
+        // The middleware buffers bodies up to the route limit itself, so
+        // axum's fixed 2MB extractor limit must not cap larger overrides.
+        .layer(axum::extract::DefaultBodyLimit::disable())
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::new(limits::RouteLimits::from_config(&config.gateway.limits)),
+            limits::enforce,
         ));
 
-    // Build router with middleware
-    let app = Router::new()
-        // ── Existing routes ──
+    // ── [gateway.auth]: outermost, so it also covers the SPA fallback ──
+    let app = match auth::GatewayAuth::from_config(
+        &config.gateway.auth,
+        config.gateway.trust_forwarded_headers,
+    )? {
+        Some(gateway_auth) => app.layer(axum::middleware::from_fn_with_state(
+            Arc::new(gateway_auth),
+            auth::enforce,
+        )),
+        None => app,
+    };
+
+    // ── X-ZeroClaw-Tenant: rewritten to /t/<name> before routing, so the
+    // auth and limit rules above see the same path as prefixed requests ──
+    let app = tower::Layer::layer(
+        &axum::middleware::from_fn_with_state(
+            Arc::new(
+                tenant_configs
+                    .into_iter()
+                    .map(|(name, _)| name)
+                    .collect::<std::collections::HashSet<_>>(),
+            ),
+            tenants::select_by_header,
+        ),
+        app,
+    );
+
+    // Run the server
+    axum::serve(
+        listener,
+        axum::ServiceExt::<axum::extract::Request>::into_make_service_with_connect_info::<
+            SocketAddr,
+        >(app),
+    )
+    .with_graceful_shutdown(crate::daemon::shutdown_token().cancelled_owned())
+    .await?;
+
+    Ok(())
+}
+
+/// Routes served for every workspace: the main one at `/`, tenants under
+/// `/t/<name>`.
+fn workspace_routes() -> Router<AppState> {
+    Router::new()
         .route("/health", get(handle_health))
         .route("/metrics", get(handle_metrics))
         .route("/pair", post(handle_pair))
@@ -741,11 +886,22 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         // ── OpenClaw migration: tools-enabled chat endpoint ──
         .route("/api/chat", post(openclaw_compat::handle_api_chat))
         // ── OpenAI-compatible endpoints ──
+        //
+        // NOTE: The /v1/chat/completions handler routes through the full agent loop
+        // (run_gateway_chat_with_tools) via openclaw_compat, giving OpenClaw callers
+        // tools + memory support. The original simple-chat handler is preserved in
+        // openai_compat.rs for reference.
+        .route(
+            "/v1/chat/completions",
+            post(openclaw_compat::handle_v1_chat_completions_with_tools),
+        )
         .route("/v1/models", get(openai_compat::handle_v1_models))
-        .merge(openai_compat_routes)
         // ── Web Dashboard API routes ──
         .route("/api/status", get(api::handle_api_status))
-        .route("/api/config", get(api::handle_api_config_get))
+        .route(
+            "/api/config",
+            get(api::handle_api_config_get).put(api::handle_api_config_put),
+        )
         .route("/api/tools", get(api::handle_api_tools))
         .route("/api/cron", get(api::handle_api_cron_list))
         .route("/api/cron", post(api::handle_api_cron_add))
@@ -759,34 +915,24 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/memory", post(api::handle_api_memory_store))
         .route("/api/memory/{key}", delete(api::handle_api_memory_delete))
         .route("/api/cost", get(api::handle_api_cost))
-        .route("/api/cli-tools", get(api::handle_api_cli_tools))
-        .route("/api/health", get(api::handle_api_health))
-        .route("/api/node-control", post(handle_node_control))
+        .route("/api/usage", get(api::handle_api_usage))
+        .route("/api/turns/{id}/report", get(api::handle_api_turn_report))
+        .route("/api/structured", post(api::handle_api_structured))
         // ── SSE event stream ──
         .route("/api/events", get(sse::handle_sse_events))
         // ── WebSocket agent chat ──
         .route("/ws/chat", get(ws::handle_ws_chat))
-        // ── Static assets (web dashboard) ──
-        .route("/_app/{*path}", get(static_files::handle_static))
-        // ── Config PUT with larger body limit ──
-        .merge(config_put_router)
+}
+
+/// Workspace routes of a tenant, served inside its workspace scope.
+fn tenant_routes(state: AppState) -> Router {
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    workspace_routes()
         .with_state(state)
-        .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
-        .layer(TimeoutLayer::with_status_code(
-            StatusCode::REQUEST_TIMEOUT,
-            Duration::from_secs(REQUEST_TIMEOUT_SECS),
+        .layer(axum::middleware::from_fn_with_state(
+            workspace_dir,
+            tenants::enter_workspace,
         ))
-        // ── SPA fallback: non-API GET requests serve index.html ──
-        .fallback(get(static_files::handle_spa_fallback));
-
-    // Run the server
-    axum::serve(
-        listener,
-        app.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .await?;
-
-    Ok(())
 }
 
 // ══════════════════════════════════════════════════════════════════════════════
@@ -838,11 +984,9 @@ async fn handle_metrics(
         );
     }
 
-    let body = if let Some(prom) = state
-        .observer
-        .as_ref()
-        .as_any()
-        .downcast_ref::<crate::observability::PrometheusObserver>()
+    let body = if let Some(prom) = crate::observability::find_observer::<
+        crate::observability::PrometheusObserver,
+    >(state.observer.as_ref())
     {
         prom.encode()
     } else {
@@ -969,21 +1113,41 @@ async fn prepare_gateway_messages_for_provider(
 
 /// Simple chat for webhook endpoint (no tools, for backward compatibility and testing).
 async fn run_gateway_chat_simple(state: &AppState, message: &str) -> anyhow::Result<String> {
-    let prepared_messages = prepare_gateway_messages_for_provider(state, message).await?;
-
-    state
-        .provider
-        .chat_with_history(&prepared_messages, &state.model, state.temperature)
-        .await
+    turn::in_turn("gateway", None, async {
+        let prepared_messages = prepare_gateway_messages_for_provider(state, message).await?;
+        state
+            .provider
+            .chat_with_history(&prepared_messages, &state.model, state.temperature)
+            .await
+    })
+    .await
 }
 
 /// Full-featured chat with tools for channel handlers (WhatsApp, Linq, Nextcloud Talk).
 pub(super) async fn run_gateway_chat_with_tools(
     state: &AppState,
     message: &str,
+) -> anyhow::Result<String> {
+    run_gateway_chat_with_options(
+        state,
+        message,
+        crate::agent::ProcessMessageOptions::default(),
+    )
+    .await
+}
+
+pub(super) async fn run_gateway_chat_with_options(
+    state: &AppState,
+    message: &str,
+    options: crate::agent::ProcessMessageOptions,
 ) -> anyhow::Result<String> {
     let config = state.config.lock().clone();
-    crate::agent::process_message(config, message).await
+    turn::in_turn(
+        "gateway",
+        None,
+        crate::agent::process_message_with_options(config, message, options),
+    )
+    .await
 }
 
 fn sanitize_gateway_response(response: &str, tools: &[Box<dyn Tool>]) -> String {
@@ -1779,6 +1943,8 @@ async fn handle_whatsapp_message(
         }
     }
 
+    payload_archive::record("whatsapp", &body);
+
     // Parse JSON body
     let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
         return (
@@ -1888,6 +2054,8 @@ async fn handle_linq_webhook(
         }
     }
 
+    payload_archive::record("linq", &body);
+
     // Parse JSON body
     let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
         return (
@@ -1992,6 +2160,8 @@ async fn handle_wati_webhook(State(state): State<AppState>, body: Bytes) -> impl
         );
     };
 
+    payload_archive::record("wati", &body);
+
     // Parse JSON body
     let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
         return (
@@ -2101,6 +2271,8 @@ async fn handle_nextcloud_talk_webhook(
         }
     }
 
+    payload_archive::record("nextcloud_talk", &body);
+
     // Parse JSON body
     let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
         return (
@@ -2190,6 +2362,8 @@ async fn handle_qq_webhook(
         );
     }
 
+    payload_archive::record("qq", &body);
+
     let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
         return (
             StatusCode::BAD_REQUEST,
@@ -2275,12 +2449,22 @@ mod tests {
 
     #[test]
     fn security_body_limit_is_64kb() {
-        assert_eq!(MAX_BODY_SIZE, 65_536);
+        let limits = crate::config::GatewayLimitsConfig::default();
+        assert_eq!(limits.max_body_bytes, 65_536);
+        assert_eq!(
+            limits::RouteLimits::from_config(&limits)
+                .for_path("/webhook")
+                .max_body_bytes,
+            65_536
+        );
     }
 
     #[test]
     fn security_timeout_is_30_seconds() {
-        assert_eq!(REQUEST_TIMEOUT_SECS, 30);
+        assert_eq!(
+            crate::config::GatewayLimitsConfig::default().timeout_secs,
+            30
+        );
     }
 
     #[test]
@@ -2352,6 +2536,7 @@ mod tests {
             mem: Arc::new(MockMemory),
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -2371,7 +2556,9 @@ mod tests {
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let response = handle_metrics(State(state), test_connect_info(), HeaderMap::new())
@@ -2408,6 +2595,7 @@ mod tests {
             mem: Arc::new(MockMemory),
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -2427,7 +2615,9 @@ mod tests {
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let response = handle_metrics(State(state), test_connect_info(), HeaderMap::new())
@@ -2450,6 +2640,7 @@ mod tests {
             mem: Arc::new(MockMemory),
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -2469,7 +2660,9 @@ mod tests {
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let response = handle_metrics(State(state), test_public_connect_info(), HeaderMap::new())
@@ -2493,6 +2686,7 @@ mod tests {
             mem: Arc::new(MockMemory),
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(true, std::slice::from_ref(&paired_token))),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -2512,7 +2706,9 @@ mod tests {
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let unauthorized =
@@ -2962,6 +3158,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -2981,7 +3178,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let mut headers = HeaderMap::new();
@@ -3031,6 +3230,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -3050,7 +3250,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let response = handle_webhook(
@@ -3082,6 +3284,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -3101,7 +3304,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let response = handle_webhook(
@@ -3134,6 +3339,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -3153,7 +3359,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let response = handle_webhook(
@@ -3195,6 +3403,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -3214,7 +3423,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let response = handle_node_control(
@@ -3249,6 +3460,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -3268,7 +3480,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let response = handle_node_control(
@@ -3308,6 +3522,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: true,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -3327,7 +3542,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let headers = HeaderMap::new();
@@ -3391,6 +3608,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: Some(Arc::from(hash_webhook_secret(&secret))),
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -3410,7 +3628,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let response = handle_webhook(
@@ -3445,6 +3665,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: Some(Arc::from(hash_webhook_secret(&valid_secret))),
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -3464,7 +3685,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let mut headers = HeaderMap::new();
@@ -3504,6 +3727,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: Some(Arc::from(hash_webhook_secret(&secret))),
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -3523,7 +3747,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let mut headers = HeaderMap::new();
@@ -3568,6 +3794,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -3587,7 +3814,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let response = handle_nextcloud_talk_webhook(
@@ -3627,6 +3856,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -3646,7 +3876,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let mut headers = HeaderMap::new();
@@ -3679,6 +3911,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -3698,7 +3931,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let response = handle_qq_webhook(
@@ -3730,6 +3965,7 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
@@ -3749,7 +3985,9 @@ Reminder set successfully."#;
             multimodal: crate::config::MultimodalConfig::default(),
             max_tool_iterations: 10,
             cost_tracker: None,
+            usage_ledger: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
         };
 
         let mut headers = HeaderMap::new();
@@ -4163,4 +4401,87 @@ Reminder set successfully."#;
         // Should be allowed again
         assert!(limiter.allow("burst-ip"));
     }
+
+    #[tokio::test]
+    async fn tenant_turn_report_is_written_to_and_served_from_the_tenant_workspace() {
+        use tower::Service;
+
+        let temp = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.workspace_dir = temp.path().join("workspace");
+        config.observability.turn_reports = true;
+        crate::workspace::init_tenant(&config);
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(config.clone())),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            api_key_hashes: Arc::new(Vec::new()),
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            wati: None,
+            qq: None,
+            qq_webhook_enabled: false,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            tools_registry_exec: Arc::new(Vec::new()),
+            multimodal: crate::config::MultimodalConfig::default(),
+            max_tool_iterations: 10,
+            cost_tracker: None,
+            usage_ledger: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            chat_sessions: Arc::new(sessions::GatewaySessions::new(None, 50, 0)),
+        };
+        let mut app = Router::new().nest(&tenants::path_prefix("sales"), tenant_routes(state));
+
+        let mut webhook = axum::extract::Request::post("/t/sales/webhook")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"message": "hello"}"#))
+            .unwrap();
+        webhook.extensions_mut().insert(test_connect_info());
+        let response = app.call(webhook).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let reports: Vec<_> = std::fs::read_dir(crate::observability::turn_report::reports_dir(
+            &config.workspace_dir,
+        ))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+        assert_eq!(reports.len(), 1);
+        let turn_id = reports[0]
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        // Outside the tenant's scope, turns keep the main config's settings.
+        assert!(!crate::observability::turn_report::enabled());
+
+        let report = app
+            .call(
+                axum::extract::Request::get(format!("/t/sales/api/turns/{turn_id}/report"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(report.status(), StatusCode::OK);
+        let body = report.into_body().collect().await.unwrap().to_bytes();
+        let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(report["turn_id"], turn_id.as_str());
+        assert_eq!(report["channel"], "gateway");
+    }
 }
@@ -6,6 +6,7 @@
 
 use super::AppState;
 use crate::providers::traits::{ChatMessage, StreamOptions};
+use crate::security::pairing::constant_time_eq;
 use axum::{
     body::Body,
     extract::{ConnectInfo, State},
@@ -482,23 +483,9 @@ pub async fn handle_v1_models(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    // ── Bearer token auth (pairing) ──
-    if state.pairing.require_pairing() {
-        let auth = headers
-            .get(header::AUTHORIZATION)
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
-        let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
-            let err = serde_json::json!({
-                "error": {
-                    "message": "Invalid API key",
-                    "type": "invalid_request_error",
-                    "code": "invalid_api_key"
-                }
-            });
-            return (StatusCode::UNAUTHORIZED, Json(err));
-        }
+    // ── Bearer auth (API key or pairing) ──
+    if let Err(rejection) = authorize_bearer(&state, &headers) {
+        return rejection;
     }
 
     let response = ModelsResponse {
@@ -521,6 +508,50 @@ pub async fn handle_v1_models(
 // HELPERS
 // ══════════════════════════════════════════════════════════════════════════════
 
+/// Check `Authorization: Bearer <key>` on a `/v1/*` request.
+///
+/// A `[gateway] api_keys` entry is always accepted; a paired token is
+/// accepted when pairing is required. With neither configured the request
+/// passes and the caller's own network rules apply.
+pub(super) fn authorize_bearer(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("")
+        .trim();
+
+    if !token.is_empty() {
+        let token_hash = super::hash_webhook_secret(token);
+        if state
+            .api_key_hashes
+            .iter()
+            .any(|hash| constant_time_eq(hash, &token_hash))
+        {
+            return Ok(());
+        }
+    }
+    let require_pairing = state.pairing.require_pairing();
+    if require_pairing && state.pairing.is_authenticated(token) {
+        return Ok(());
+    }
+    if !require_pairing && state.api_key_hashes.is_empty() {
+        return Ok(());
+    }
+
+    let err = serde_json::json!({
+        "error": {
+            "message": "Invalid API key. Use Authorization: Bearer <key> with a [gateway] api_keys entry or a paired token",
+            "type": "invalid_request_error",
+            "code": "invalid_api_key"
+        }
+    });
+    Err((StatusCode::UNAUTHORIZED, Json(err)))
+}
+
 fn unix_timestamp() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -25,10 +25,14 @@
 //! is provided for backward compatibility and may be deprecated once all callers
 //! have migrated to the native endpoint.
 
+use super::openai_compat::authorize_bearer;
+use super::sessions::{session_owner, validate_session_id, GatewaySessions, SESSION_ID_HEADER};
 use super::{
-    client_key_from_request, run_gateway_chat_with_tools, sanitize_gateway_response, AppState,
-    RATE_LIMIT_WINDOW_SECS,
+    client_key_from_request, run_gateway_chat_with_options, run_gateway_chat_with_tools,
+    sanitize_gateway_response, AppState, RATE_LIMIT_WINDOW_SECS,
 };
+use crate::agent::loop_::{DRAFT_CLEAR_SENTINEL, DRAFT_PROGRESS_SENTINEL};
+use crate::agent::ProcessMessageOptions;
 use crate::memory::MemoryCategory;
 use crate::providers;
 use axum::{
@@ -37,9 +41,11 @@ use axum::{
     http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Json},
 };
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use std::time::Instant;
+use tokio_stream::wrappers::ReceiverStream;
 use uuid::Uuid;
 
 // ══════════════════════════════════════════════════════════════════════════════
@@ -350,6 +356,11 @@ struct OaiDelta {
 /// This replaces the simple `provider.chat_with_history()` path from `openai_compat.rs`
 /// with the full `run_gateway_chat_with_tools()` agent loop, giving OpenClaw callers
 /// the same tools + memory experience as native ZeroClaw channels.
+///
+/// Callers authenticate with a `[gateway] api_keys` entry or a paired token.
+/// With `X-Session-Id` the gateway keeps the conversation server-side and only
+/// the last user message of `messages[]` is used. `stream: true` relays the
+/// reply as it is generated.
 pub async fn handle_v1_chat_completions_with_tools(
     State(state): State<AppState>,
     ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
@@ -373,6 +384,7 @@ pub async fn handle_v1_chat_completions_with_tools(
 
     // ── Auth: require at least one layer for non-loopback ──
     if !state.pairing.require_pairing()
+        && state.api_key_hashes.is_empty()
         && state.webhook_secret_hash.is_none()
         && !peer_addr.ip().is_loopback()
     {
@@ -381,7 +393,7 @@ pub async fn handle_v1_chat_completions_with_tools(
         );
         let err = serde_json::json!({
             "error": {
-                "message": "Unauthorized — configure pairing or X-Webhook-Secret for non-local access",
+                "message": "Unauthorized — configure pairing, [gateway] api_keys, or X-Webhook-Secret for non-local access",
                 "type": "invalid_request_error",
                 "code": "unauthorized"
             }
@@ -389,43 +401,33 @@ pub async fn handle_v1_chat_completions_with_tools(
         return (StatusCode::UNAUTHORIZED, Json(err)).into_response();
     }
 
-    // ── Bearer token auth (pairing) ──
-    if state.pairing.require_pairing() {
-        let auth = headers
-            .get(header::AUTHORIZATION)
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
-        let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
-            tracing::warn!(
-                "/v1/chat/completions (compat): rejected — not paired / invalid bearer token"
-            );
-            let err = serde_json::json!({
-                "error": {
-                    "message": "Invalid API key. Pair first via POST /pair, then use Authorization: Bearer <token>",
-                    "type": "invalid_request_error",
-                    "code": "invalid_api_key"
-                }
-            });
-            return (StatusCode::UNAUTHORIZED, Json(err)).into_response();
-        }
+    // ── Bearer auth (API key or pairing) ──
+    if let Err(rejection) = authorize_bearer(&state, &headers) {
+        tracing::warn!("/v1/chat/completions (compat): rejected — invalid bearer token");
+        return rejection.into_response();
     }
 
-    // ── Body size ──
-    if body.len() > super::openai_compat::CHAT_COMPLETIONS_MAX_BODY_SIZE {
-        let err = serde_json::json!({
-            "error": {
-                "message": format!(
-                    "Request body too large ({} bytes, max {})",
-                    body.len(),
-                    super::openai_compat::CHAT_COMPLETIONS_MAX_BODY_SIZE
-                ),
-                "type": "invalid_request_error",
-                "code": "request_too_large"
+    // Body size is enforced per route by `[gateway.limits]`.
+
+    // ── Session header ──
+    let session_id = match headers.get(SESSION_ID_HEADER) {
+        None => None,
+        Some(value) => {
+            let id = value.to_str().unwrap_or("").trim();
+            if let Err(reason) = validate_session_id(id) {
+                let err = serde_json::json!({
+                    "error": {
+                        "message": reason,
+                        "type": "invalid_request_error",
+                        "code": "invalid_session_id"
+                    }
+                });
+                return (StatusCode::BAD_REQUEST, Json(err)).into_response();
             }
-        });
-        return (StatusCode::PAYLOAD_TOO_LARGE, Json(err)).into_response();
-    }
+            Some(id.to_string())
+        }
+    };
+    let session_owner = session_owner(&headers);
 
     // ── Parse body ──
     let request: OaiChatRequest = match serde_json::from_slice(&body) {
@@ -476,42 +478,21 @@ pub async fn handle_v1_chat_completions_with_tools(
         }
     };
 
-    // Build context from conversation history (exclude the last user message)
-    let context_messages: Vec<String> = request
-        .messages
-        .iter()
-        .rev()
-        .skip(1)
-        .rev()
-        .filter(|m| m.role == "user" || m.role == "assistant")
-        .map(|m| {
-            let role_label = if m.role == "user" {
-                "User"
-            } else {
-                "Assistant"
-            };
-            format!("{}: {}", role_label, m.content)
-        })
-        .collect();
-
-    let enriched_message = if context_messages.is_empty() {
-        message.clone()
-    } else {
-        let recent: Vec<&String> = context_messages
-            .iter()
-            .rev()
-            .take(MAX_CONTEXT_MESSAGES)
-            .rev()
-            .collect();
-        let context_block = recent
-            .iter()
-            .map(|s| s.as_str())
-            .collect::<Vec<&str>>()
-            .join("\n");
-        format!(
-            "Recent conversation context:\n{}\n\nCurrent message:\n{}",
-            context_block, message
-        )
+    // With a session the gateway replays its own history; otherwise build
+    // context from the client's messages (excluding the last user message).
+    let (turn_message, options) = match session_id.as_deref() {
+        Some(id) => (
+            message.clone(),
+            ProcessMessageOptions {
+                history: state.chat_sessions.history(&session_owner, id).await,
+                session: Some(GatewaySessions::key(&session_owner, id)),
+                ..ProcessMessageOptions::default()
+            },
+        ),
+        None => (
+            enrich_with_context(&request.messages, &message),
+            ProcessMessageOptions::default(),
+        ),
     };
 
     let is_stream = request.stream.unwrap_or(false);
@@ -553,71 +534,31 @@ pub async fn handle_v1_chat_completions_with_tools(
 
     tracing::info!(
         stream = is_stream,
+        session = session_id.is_some(),
         messages_count = request.messages.len(),
         "Processing /v1/chat/completions (compat shim — full agent loop)"
     );
 
-    // ── Run the full agent loop ──
-    let reply = match run_gateway_chat_with_tools(&state, &enriched_message).await {
-        Ok(response) => {
-            let safe = sanitize_gateway_response(&response, state.tools_registry_exec.as_ref());
-            let duration = started_at.elapsed();
-
-            state
-                .observer
-                .record_event(&crate::observability::ObserverEvent::LlmResponse {
-                    provider: provider_label.clone(),
-                    model: model_label.clone(),
-                    duration,
-                    success: true,
-                    error_message: None,
-                    input_tokens: None,
-                    output_tokens: None,
-                });
-            state.observer.record_metric(
-                &crate::observability::traits::ObserverMetric::RequestLatency(duration),
-            );
-            state
-                .observer
-                .record_event(&crate::observability::ObserverEvent::AgentEnd {
-                    provider: provider_label,
-                    model: model_label,
-                    duration,
-                    tokens_used: None,
-                    cost_usd: None,
-                });
-
-            safe
-        }
-        Err(e) => {
-            let duration = started_at.elapsed();
-            let sanitized = providers::sanitize_api_error(&e.to_string());
+    let model_name = request.model.unwrap_or_else(|| state.model.clone());
+    let turn = CompatTurn {
+        state,
+        message,
+        turn_message,
+        session_id,
+        session_owner,
+        provider_label,
+        model_label,
+        started_at,
+    };
 
-            state
-                .observer
-                .record_event(&crate::observability::ObserverEvent::LlmResponse {
-                    provider: provider_label.clone(),
-                    model: model_label.clone(),
-                    duration,
-                    success: false,
-                    error_message: Some(sanitized.clone()),
-                    input_tokens: None,
-                    output_tokens: None,
-                });
-            state.observer.record_metric(
-                &crate::observability::traits::ObserverMetric::RequestLatency(duration),
-            );
-            state
-                .observer
-                .record_event(&crate::observability::ObserverEvent::AgentEnd {
-                    provider: provider_label,
-                    model: model_label,
-                    duration,
-                    tokens_used: None,
-                    cost_usd: None,
-                });
+    if is_stream {
+        return stream_chat_completion(turn, options, request_id, created, model_name);
+    }
 
-            tracing::error!("/v1/chat/completions (compat) provider error: {sanitized}");
+    // ── Run the full agent loop ──
+    let reply = match turn.run(options).await {
+        Ok(reply) => reply,
+        Err(()) => {
             let err = serde_json::json!({
                 "error": {
                     "message": "LLM request failed",
@@ -629,103 +570,271 @@ pub async fn handle_v1_chat_completions_with_tools(
         }
     };
 
-    let model_name = request.model.unwrap_or_else(|| state.model.clone());
-
     #[allow(clippy::cast_possible_truncation)]
-    let prompt_tokens = (enriched_message.len() / 4) as u32;
+    let prompt_tokens = (turn.turn_message.len() / 4) as u32;
     #[allow(clippy::cast_possible_truncation)]
     let completion_tokens = (reply.len() / 4) as u32;
 
-    if is_stream {
-        // ── Simulated streaming SSE ──
-        // The full agent loop returns a complete response; we chunk it into SSE format.
-        let role_chunk = OaiStreamChunk {
-            id: request_id.clone(),
-            object: "chat.completion.chunk",
-            created,
-            model: model_name.clone(),
-            choices: vec![OaiStreamChoice {
-                index: 0,
-                delta: OaiDelta {
-                    role: Some("assistant"),
-                    content: None,
-                },
-                finish_reason: None,
-            }],
+    // ── Non-streaming JSON ──
+    let response = OaiChatResponse {
+        id: request_id,
+        object: "chat.completion",
+        created,
+        model: model_name,
+        choices: vec![OaiChoice {
+            index: 0,
+            message: OaiMessage {
+                role: "assistant".into(),
+                content: reply,
+            },
+            finish_reason: "stop",
+        }],
+        usage: OaiUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        },
+    };
+    Json(serde_json::to_value(response).unwrap()).into_response()
+}
+
+/// Prefix the last user message with recent `messages[]` turns.
+fn enrich_with_context(messages: &[OaiMessage], message: &str) -> String {
+    let context_messages: Vec<String> = messages
+        .iter()
+        .rev()
+        .skip(1)
+        .rev()
+        .filter(|m| m.role == "user" || m.role == "assistant")
+        .map(|m| {
+            let role_label = if m.role == "user" {
+                "User"
+            } else {
+                "Assistant"
+            };
+            format!("{}: {}", role_label, m.content)
+        })
+        .collect();
+
+    if context_messages.is_empty() {
+        return message.to_string();
+    }
+    let recent: Vec<&String> = context_messages
+        .iter()
+        .rev()
+        .take(MAX_CONTEXT_MESSAGES)
+        .rev()
+        .collect();
+    let context_block = recent
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<&str>>()
+        .join("\n");
+    format!(
+        "Recent conversation context:\n{}\n\nCurrent message:\n{}",
+        context_block, message
+    )
+}
+
+/// One agent turn behind `/v1/chat/completions`, shared by the JSON and
+/// streaming paths.
+struct CompatTurn {
+    state: AppState,
+    message: String,
+    turn_message: String,
+    session_id: Option<String>,
+    session_owner: String,
+    provider_label: String,
+    model_label: String,
+    started_at: Instant,
+}
+
+impl CompatTurn {
+    /// Run the agent loop, record observability, and append the exchange to
+    /// the session. Returns the sanitized reply; failures are logged here.
+    async fn run(&self, options: ProcessMessageOptions) -> Result<String, ()> {
+        let result = run_gateway_chat_with_options(&self.state, &self.turn_message, options).await;
+        let duration = self.started_at.elapsed();
+        let (reply, error_message) = match result {
+            Ok(response) => (
+                Some(sanitize_gateway_response(
+                    &response,
+                    self.state.tools_registry_exec.as_ref(),
+                )),
+                None,
+            ),
+            Err(e) => (None, Some(providers::sanitize_api_error(&e.to_string()))),
         };
 
-        let content_chunk = OaiStreamChunk {
-            id: request_id.clone(),
-            object: "chat.completion.chunk",
-            created,
-            model: model_name.clone(),
-            choices: vec![OaiStreamChoice {
-                index: 0,
-                delta: OaiDelta {
-                    role: None,
-                    content: Some(reply),
-                },
-                finish_reason: None,
-            }],
+        self.state
+            .observer
+            .record_event(&crate::observability::ObserverEvent::LlmResponse {
+                provider: self.provider_label.clone(),
+                model: self.model_label.clone(),
+                duration,
+                success: reply.is_some(),
+                error_message: error_message.clone(),
+                input_tokens: None,
+                output_tokens: None,
+            });
+        self.state
+            .observer
+            .record_metric(&crate::observability::traits::ObserverMetric::RequestLatency(duration));
+        self.state
+            .observer
+            .record_event(&crate::observability::ObserverEvent::AgentEnd {
+                provider: self.provider_label.clone(),
+                model: self.model_label.clone(),
+                duration,
+                tokens_used: None,
+                cost_usd: None,
+            });
+
+        let Some(reply) = reply else {
+            tracing::error!(
+                "/v1/chat/completions (compat) provider error: {}",
+                error_message.unwrap_or_default()
+            );
+            return Err(());
         };
+        if let Some(id) = self.session_id.as_deref() {
+            self.state
+                .chat_sessions
+                .record(&self.session_owner, id, &self.message, &reply)
+                .await;
+        }
+        Ok(reply)
+    }
+}
 
-        let stop_chunk = OaiStreamChunk {
-            id: request_id,
-            object: "chat.completion.chunk",
-            created,
-            model: model_name,
-            choices: vec![OaiStreamChoice {
-                index: 0,
-                delta: OaiDelta {
-                    role: None,
-                    content: None,
-                },
-                finish_reason: Some("stop"),
-            }],
+/// Stream the agent's reply as OpenAI `chat.completion.chunk` events while
+/// the turn runs. Tool-progress deltas are not forwarded.
+fn stream_chat_completion(
+    turn: CompatTurn,
+    mut options: ProcessMessageOptions,
+    request_id: String,
+    created: u64,
+    model_name: String,
+) -> axum::response::Response {
+    let (delta_tx, mut delta_rx) = tokio::sync::mpsc::channel::<String>(64);
+    let (frame_tx, frame_rx) = tokio::sync::mpsc::channel::<String>(64);
+    options.on_delta = Some(delta_tx);
+
+    // The turn outlives the request task, so it re-enters a tenant's scope.
+    let workspace_dir = crate::workspace::current();
+    tokio::spawn(crate::workspace::scope(workspace_dir, async move {
+        let frame = |role: Option<&'static str>,
+                     content: Option<String>,
+                     finish_reason: Option<&'static str>| {
+            let chunk = OaiStreamChunk {
+                id: request_id.clone(),
+                object: "chat.completion.chunk",
+                created,
+                model: model_name.clone(),
+                choices: vec![OaiStreamChoice {
+                    index: 0,
+                    delta: OaiDelta { role, content },
+                    finish_reason,
+                }],
+            };
+            let json = serde_json::to_string(&chunk).unwrap_or_else(|_| "{}".into());
+            format!("data: {json}\n\n")
         };
 
-        let mut output = String::new();
-        output.push_str("data: ");
-        output.push_str(&serde_json::to_string(&role_chunk).unwrap_or_else(|_| "{}".into()));
-        output.push_str("\n\n");
-        output.push_str("data: ");
-        output.push_str(&serde_json::to_string(&content_chunk).unwrap_or_else(|_| "{}".into()));
-        output.push_str("\n\n");
-        output.push_str("data: ");
-        output.push_str(&serde_json::to_string(&stop_chunk).unwrap_or_else(|_| "{}".into()));
-        output.push_str("\n\n");
-        output.push_str("data: [DONE]\n\n");
-
-        axum::response::Response::builder()
-            .status(StatusCode::OK)
-            .header(header::CONTENT_TYPE, "text/event-stream")
-            .header(header::CACHE_CONTROL, "no-cache")
-            .header(header::CONNECTION, "keep-alive")
-            .body(Body::from(output))
-            .unwrap()
-            .into_response()
-    } else {
-        // ── Non-streaming JSON ──
-        let response = OaiChatResponse {
-            id: request_id,
-            object: "chat.completion",
-            created,
-            model: model_name,
-            choices: vec![OaiChoice {
-                index: 0,
-                message: OaiMessage {
-                    role: "assistant".into(),
-                    content: reply,
-                },
-                finish_reason: "stop",
-            }],
-            usage: OaiUsage {
-                prompt_tokens,
-                completion_tokens,
-                total_tokens: prompt_tokens + completion_tokens,
-            },
+        // Sends fail only when the client went away; the turn still runs to
+        // completion so the session stays consistent.
+        let _ = frame_tx.send(frame(Some("assistant"), None, None)).await;
+
+        let mut relay = DeltaRelay::default();
+        let run = turn.run(options);
+        tokio::pin!(run);
+        let result = loop {
+            tokio::select! {
+                result = &mut run => break result,
+                Some(delta) = delta_rx.recv() => {
+                    if let Some(text) = relay.push(&delta) {
+                        let _ = frame_tx.send(frame(None, Some(text), None)).await;
+                    }
+                }
+            }
+        };
+        while let Ok(delta) = delta_rx.try_recv() {
+            if let Some(text) = relay.push(&delta) {
+                let _ = frame_tx.send(frame(None, Some(text), None)).await;
+            }
+        }
+
+        match result {
+            Ok(reply) => {
+                if let Some(tail) = relay.finish(&reply) {
+                    let _ = frame_tx.send(frame(None, Some(tail), None)).await;
+                }
+                let _ = frame_tx.send(frame(None, None, Some("stop"))).await;
+            }
+            Err(()) => {
+                let err = serde_json::json!({
+                    "error": {
+                        "message": "LLM request failed",
+                        "type": "server_error",
+                        "code": "provider_error"
+                    }
+                });
+                let _ = frame_tx.send(format!("data: {err}\n\n")).await;
+            }
+        }
+        let _ = frame_tx.send("data: [DONE]\n\n".to_string()).await;
+    }));
+
+    let body = ReceiverStream::new(frame_rx)
+        .map(|frame| Ok::<_, std::io::Error>(axum::body::Bytes::from(frame)));
+    axum::response::Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/event-stream")
+        .header(header::CACHE_CONTROL, "no-cache")
+        .header(header::CONNECTION, "keep-alive")
+        .body(Body::from_stream(body))
+        .unwrap()
+        .into_response()
+}
+
+/// Turns agent-loop deltas into append-only streamed text.
+///
+/// The loop may clear its draft and restream the answer; text the client
+/// already has is not sent twice.
+#[derive(Default)]
+pub(super) struct DeltaRelay {
+    sent: String,
+    current: String,
+}
+
+impl DeltaRelay {
+    /// Feed one delta; returns new text for the client, if any.
+    pub(super) fn push(&mut self, delta: &str) -> Option<String> {
+        if delta == DRAFT_CLEAR_SENTINEL {
+            self.current.clear();
+            return None;
+        }
+        if delta.starts_with(DRAFT_PROGRESS_SENTINEL) {
+            return None;
+        }
+        self.current.push_str(delta);
+        let out = if let Some(rest) = self.current.strip_prefix(self.sent.as_str()) {
+            rest.to_string()
+        } else if self.sent.starts_with(self.current.as_str()) {
+            return None;
+        } else {
+            // A later answer replaced what was streamed; append it whole.
+            format!("\n\n{}", self.current)
         };
-        Json(serde_json::to_value(response).unwrap()).into_response()
+        self.sent.clone_from(&self.current);
+        (!out.is_empty()).then_some(out)
+    }
+
+    /// Text of the final reply the client has not received yet.
+    fn finish(&mut self, reply: &str) -> Option<String> {
+        let rest = reply.strip_prefix(self.sent.as_str())?;
+        self.sent = reply.to_string();
+        (!rest.is_empty()).then(|| rest.to_string())
     }
 }
 
@@ -925,4 +1034,57 @@ mod tests {
         assert!(context_messages[0].starts_with("User: first"));
         assert!(context_messages[1].starts_with("Assistant: reply"));
     }
+
+    #[test]
+    fn enrich_with_context_prefixes_prior_turns() {
+        let messages = vec![
+            OaiMessage {
+                role: "user".into(),
+                content: "first".into(),
+            },
+            OaiMessage {
+                role: "assistant".into(),
+                content: "reply".into(),
+            },
+            OaiMessage {
+                role: "user".into(),
+                content: "second".into(),
+            },
+        ];
+        let enriched = enrich_with_context(&messages, "second");
+        assert!(enriched.contains("User: first\nAssistant: reply"));
+        assert!(enriched.ends_with("Current message:\nsecond"));
+        assert_eq!(enrich_with_context(&messages[2..], "second"), "second");
+    }
+
+    #[test]
+    fn delta_relay_skips_progress_and_resent_text() {
+        let mut relay = DeltaRelay::default();
+        assert_eq!(
+            relay.push(&format!("{DRAFT_PROGRESS_SENTINEL}running shell")),
+            None
+        );
+        assert_eq!(relay.push(DRAFT_CLEAR_SENTINEL), None);
+        assert_eq!(relay.push("Hello").as_deref(), Some("Hello"));
+        assert_eq!(relay.push(" world").as_deref(), Some(" world"));
+
+        // The loop clears its draft and restreams the same answer.
+        assert_eq!(relay.push(DRAFT_CLEAR_SENTINEL), None);
+        assert_eq!(relay.push("Hello"), None);
+        assert_eq!(relay.push(" world!").as_deref(), Some("!"));
+
+        assert_eq!(
+            relay.finish("Hello world! Done.").as_deref(),
+            Some(" Done.")
+        );
+        assert_eq!(relay.finish("Hello world! Done."), None);
+    }
+
+    #[test]
+    fn delta_relay_appends_replaced_answer() {
+        let mut relay = DeltaRelay::default();
+        assert_eq!(relay.push("Draft").as_deref(), Some("Draft"));
+        relay.push(DRAFT_CLEAR_SENTINEL);
+        assert_eq!(relay.push("Final").as_deref(), Some("\n\nFinal"));
+    }
 }
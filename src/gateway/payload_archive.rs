@@ -0,0 +1,260 @@
+//! Raw webhook payload archive for debugging channel integrations.
+//!
+//! With `[gateway] payload_archive_size = N`, the platform webhooks
+//! (`/whatsapp`, `/linq`, `/wati`, `/nextcloud-talk`, `/qq`) keep the last N
+//! request bodies per channel that passed signature checks in
+//! `<workspace>/state/payloads/<channel>.jsonl`. Bodies are stored after
+//! credential redaction; JSON values under keys such as `token` or `secret`
+//! are masked as well. `GET /api/admin/payloads/{channel}` and
+//! `zeroclaw debug payloads <channel>` read them back.
+
+use crate::config::{Config, GatewayConfig};
+use crate::security::{ArgumentScan, LeakDetector, LeakResult};
+use anyhow::Result;
+use chrono::Utc;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// Largest body archived; longer bodies are cut to this many bytes.
+const MAX_ARCHIVED_BODY_BYTES: usize = 64 * 1024;
+
+/// Lowercase key fragments whose JSON values are always masked.
+const SECRET_KEY_FRAGMENTS: &[&str] = &[
+    "token",
+    "secret",
+    "password",
+    "signature",
+    "api_key",
+    "apikey",
+    "authorization",
+    "encodingaeskey",
+];
+
+const REDACTED: &str = "[REDACTED]";
+
+/// One archived request body.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedPayload {
+    pub received_at: String,
+    pub channel: String,
+    /// Size of the original body in bytes.
+    pub size: usize,
+    /// Redacted JSON body, or the redacted text when it was not JSON.
+    pub payload: serde_json::Value,
+    /// Whether the body was cut at `MAX_ARCHIVED_BODY_BYTES`.
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+/// Per-channel ring of recent webhook bodies.
+pub struct PayloadArchive {
+    dir: PathBuf,
+    keep: usize,
+    write_lock: Mutex<()>,
+}
+
+impl PayloadArchive {
+    pub fn new(workspace_dir: &Path, keep: usize) -> Self {
+        Self {
+            dir: Self::dir(workspace_dir),
+            keep,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn dir(workspace_dir: &Path) -> PathBuf {
+        workspace_dir.join("state").join("payloads")
+    }
+
+    fn path(dir: &Path, channel: &str) -> PathBuf {
+        let name: String = channel
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        dir.join(format!("{name}.jsonl"))
+    }
+
+    /// Archive `body` for `channel`, dropping the oldest entries beyond the
+    /// configured size.
+    pub fn record(&self, channel: &str, body: &[u8]) -> Result<()> {
+        let entry = redact_body(channel, body);
+        let _guard = self.write_lock.lock();
+        std::fs::create_dir_all(&self.dir)?;
+        let path = Self::path(&self.dir, channel);
+        let mut entries = read_entries(&path)?;
+        entries.push(entry);
+        let skip = entries.len().saturating_sub(self.keep);
+        let mut contents = String::new();
+        for entry in &entries[skip..] {
+            contents.push_str(&serde_json::to_string(entry)?);
+            contents.push('\n');
+        }
+        let tmp = path.with_extension("jsonl.tmp");
+        std::fs::write(&tmp, contents)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+}
+
+/// The last `limit` archived payloads of `channel`, newest first.
+pub fn recent(workspace_dir: &Path, channel: &str, limit: usize) -> Result<Vec<ArchivedPayload>> {
+    let path = PayloadArchive::path(&PayloadArchive::dir(workspace_dir), channel);
+    let mut entries = read_entries(&path)?;
+    entries.reverse();
+    entries.truncate(limit);
+    Ok(entries)
+}
+
+fn read_entries(path: &Path) -> Result<Vec<ArchivedPayload>> {
+    let raw = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(raw
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn redact_body(channel: &str, body: &[u8]) -> ArchivedPayload {
+    let truncated = body.len() > MAX_ARCHIVED_BODY_BYTES;
+    let kept = &body[..body.len().min(MAX_ARCHIVED_BODY_BYTES)];
+    let detector = LeakDetector::new();
+    let payload = match serde_json::from_slice::<serde_json::Value>(kept) {
+        Ok(mut json) => {
+            mask_secret_keys(&mut json);
+            match detector.scan_tool_arguments(&json) {
+                ArgumentScan::Clean => json,
+                ArgumentScan::Detected { redacted, .. } => redacted,
+            }
+        }
+        Err(_) => {
+            let text = String::from_utf8_lossy(kept);
+            serde_json::Value::String(match detector.scan(&text) {
+                LeakResult::Clean => text.into_owned(),
+                LeakResult::Detected { redacted, .. } => redacted,
+            })
+        }
+    };
+    ArchivedPayload {
+        received_at: Utc::now().to_rfc3339(),
+        channel: channel.to_string(),
+        size: body.len(),
+        payload,
+        truncated,
+    }
+}
+
+fn mask_secret_keys(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.to_ascii_lowercase();
+                if value.is_string()
+                    && SECRET_KEY_FRAGMENTS
+                        .iter()
+                        .any(|fragment| key.contains(fragment))
+                {
+                    *value = serde_json::Value::String(REDACTED.into());
+                } else {
+                    mask_secret_keys(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(mask_secret_keys),
+        _ => {}
+    }
+}
+
+static ARCHIVE: LazyLock<RwLock<Option<Arc<PayloadArchive>>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Enable (or disable) the archive per `[gateway].payload_archive_size`.
+pub fn init_from_config(config: &GatewayConfig, workspace_dir: &Path) {
+    let archive = (config.payload_archive_size > 0).then(|| {
+        Arc::new(PayloadArchive::new(
+            workspace_dir,
+            config.payload_archive_size,
+        ))
+    });
+    *ARCHIVE.write().unwrap_or_else(|e| e.into_inner()) = archive;
+}
+
+/// Archive a webhook body for `channel` when the archive is enabled.
+pub fn record(channel: &str, body: &[u8]) {
+    let Some(archive) = ARCHIVE.read().unwrap_or_else(|e| e.into_inner()).clone() else {
+        return;
+    };
+    if let Err(e) = archive.record(channel, body) {
+        tracing::warn!(channel, "Failed to archive webhook payload: {e}");
+    }
+}
+
+/// Handle `zeroclaw debug <subcommand>` CLI commands.
+pub fn handle_command(command: crate::DebugCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::DebugCommands::Payloads {
+            channel,
+            limit,
+            json,
+        } => {
+            let entries = recent(&config.workspace_dir, &channel, limit)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+                return Ok(());
+            }
+            if entries.is_empty() {
+                if config.gateway.payload_archive_size == 0 {
+                    println!(
+                        "No payloads archived for {channel}. Set [gateway] payload_archive_size to enable the archive."
+                    );
+                } else {
+                    println!("No payloads archived for {channel} yet.");
+                }
+                return Ok(());
+            }
+            for entry in entries {
+                println!(
+                    "── {} · {} bytes{}",
+                    entry.received_at,
+                    entry.size,
+                    if entry.truncated { " (truncated)" } else { "" }
+                );
+                println!("{}\n", serde_json::to_string_pretty(&entry.payload)?);
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn archive_keeps_last_entries_with_secrets_masked() {
+        let tmp = TempDir::new().unwrap();
+        let archive = PayloadArchive::new(tmp.path(), 2);
+        for n in 0..3 {
+            let body = serde_json::json!({ "n": n, "auth": { "access_token": "abc123" } });
+            archive.record("wati", body.to_string().as_bytes()).unwrap();
+        }
+        archive.record("wati", b"not json").unwrap();
+
+        let entries = recent(tmp.path(), "wati", 10).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].payload, serde_json::json!("not json"));
+        assert_eq!(entries[1].payload["n"], 2);
+        assert_eq!(entries[1].payload["auth"]["access_token"], REDACTED);
+        assert!(recent(tmp.path(), "qq", 10).unwrap().is_empty());
+    }
+}
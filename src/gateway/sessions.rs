@@ -0,0 +1,236 @@
+//! Server-side conversation history for the OpenAI-compatible endpoint.
+//!
+//! Clients that send `X-Session-Id` get their earlier turns replayed by the
+//! gateway instead of resending `messages[]`. History goes to the configured
+//! [`SessionStore`] (sqlite/redis) so several gateways can share it; with the
+//! in-process `memory` backend it lives in a bounded local map.
+//!
+//! Sessions belong to the credential that opened them: the store key
+//! carries a hash of the caller's bearer token (API key or paired token),
+//! so a caller holding another key cannot read a conversation by reusing
+//! its session id.
+
+use crate::channels::session::{self, SessionStore};
+use crate::config::Config;
+use crate::providers::ChatMessage;
+use axum::http::{header, HeaderMap};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Header carrying the caller-chosen session id.
+pub const SESSION_ID_HEADER: &str = "x-session-id";
+
+/// Longest accepted session id.
+const MAX_SESSION_ID_LEN: usize = 128;
+
+/// Upper bound on locally held sessions; the least recently used is evicted.
+const MAX_LOCAL_SESSIONS: usize = 1000;
+
+/// Validate a session id from `X-Session-Id`. Ids are 1-128 characters of
+/// `[A-Za-z0-9_.-]`, which keeps them clear of the store's `:`-prefixed
+/// checkpoint and pin keys.
+pub fn validate_session_id(id: &str) -> Result<(), &'static str> {
+    if id.is_empty() || id.len() > MAX_SESSION_ID_LEN {
+        return Err("X-Session-Id must be 1-128 characters");
+    }
+    if !id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        return Err("X-Session-Id may only contain [A-Za-z0-9_.-]");
+    }
+    Ok(())
+}
+
+/// Owner namespace for the sessions of the caller presenting `headers`: a
+/// prefix of the SHA-256 of its bearer token. Requests without a token
+/// (auth disabled) share one namespace.
+pub fn session_owner(headers: &HeaderMap) -> String {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("")
+        .trim();
+    let mut owner = super::hash_webhook_secret(token);
+    owner.truncate(16);
+    owner
+}
+
+struct LocalSession {
+    turns: Vec<ChatMessage>,
+    last_used: Instant,
+}
+
+/// Conversation history keyed by owner and `X-Session-Id`.
+pub struct GatewaySessions {
+    store: Option<Arc<dyn SessionStore>>,
+    local: Mutex<HashMap<String, LocalSession>>,
+    max_messages: usize,
+    ttl: Option<Duration>,
+}
+
+impl GatewaySessions {
+    pub fn new(store: Option<Arc<dyn SessionStore>>, max_messages: usize, ttl_secs: u64) -> Self {
+        Self {
+            store,
+            local: Mutex::new(HashMap::new()),
+            max_messages,
+            ttl: (ttl_secs > 0).then(|| Duration::from_secs(ttl_secs)),
+        }
+    }
+
+    /// Build from `[session]`, falling back to local history when the
+    /// configured store cannot be opened.
+    pub fn from_config(config: &Config) -> Self {
+        let store = session::create_session_store(&config.session, &config.workspace_dir)
+            .unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Gateway session store unavailable, keeping history in-process: {e}"
+                );
+                None
+            });
+        Self::new(store, config.session.max_messages, config.session.ttl_secs)
+    }
+
+    /// Store key of session `id` of `owner` (see [`session_owner`]); also
+    /// used as the usage-ledger session.
+    pub fn key(owner: &str, id: &str) -> String {
+        format!("gateway_{owner}_{id}")
+    }
+
+    /// Prior user/assistant turns of `owner`'s session `id`, oldest first.
+    pub async fn history(&self, owner: &str, id: &str) -> Vec<ChatMessage> {
+        let key = Self::key(owner, id);
+        if let Some(store) = self.store.as_ref() {
+            match store.load(&key).await {
+                Ok(turns) => return turns.unwrap_or_default(),
+                Err(e) => tracing::warn!(
+                    store = store.name(),
+                    "Failed to load gateway session, using local copy: {e}"
+                ),
+            }
+        }
+        let mut local = self.local.lock();
+        let expired = local
+            .get(&key)
+            .is_some_and(|s| self.ttl.is_some_and(|ttl| s.last_used.elapsed() > ttl));
+        if expired {
+            local.remove(&key);
+            return Vec::new();
+        }
+        local.get(&key).map(|s| s.turns.clone()).unwrap_or_default()
+    }
+
+    /// Append one exchange to `owner`'s session `id`, keeping the newest
+    /// `max_messages` turns.
+    pub async fn record(&self, owner: &str, id: &str, user: &str, assistant: &str) {
+        let mut turns = self.history(owner, id).await;
+        turns.push(ChatMessage::user(user));
+        turns.push(ChatMessage::assistant(assistant));
+        if turns.len() > self.max_messages {
+            turns.drain(..turns.len() - self.max_messages);
+        }
+
+        let key = Self::key(owner, id);
+        if let Some(store) = self.store.as_ref() {
+            match store.save(&key, &turns).await {
+                Ok(()) => return,
+                Err(e) => tracing::warn!(
+                    store = store.name(),
+                    "Failed to persist gateway session, keeping local copy: {e}"
+                ),
+            }
+        }
+        let mut local = self.local.lock();
+        if !local.contains_key(&key) && local.len() >= MAX_LOCAL_SESSIONS {
+            if let Some(oldest) = local
+                .iter()
+                .min_by_key(|(_, s)| s.last_used)
+                .map(|(k, _)| k.clone())
+            {
+                local.remove(&oldest);
+            }
+        }
+        local.insert(
+            key,
+            LocalSession {
+                turns,
+                last_used: Instant::now(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_id_validation() {
+        assert!(validate_session_id("user-42_chat.1").is_ok());
+        assert!(validate_session_id("").is_err());
+        assert!(validate_session_id("has space").is_err());
+        assert!(validate_session_id("pins:abc").is_err());
+        assert!(validate_session_id(&"a".repeat(MAX_SESSION_ID_LEN + 1)).is_err());
+    }
+
+    #[tokio::test]
+    async fn local_history_accumulates_and_trims() {
+        let sessions = GatewaySessions::new(None, 4, 0);
+        assert!(sessions.history("k1", "s1").await.is_empty());
+
+        sessions.record("k1", "s1", "hi", "hello").await;
+        sessions.record("k1", "s1", "how are you", "fine").await;
+        sessions.record("k1", "s1", "bye", "see you").await;
+
+        let turns = sessions.history("k1", "s1").await;
+        assert_eq!(turns.len(), 4);
+        assert_eq!(turns[0].content, "how are you");
+        assert_eq!(turns[3].content, "see you");
+        assert!(sessions.history("k1", "s2").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn sessions_are_isolated_per_credential() {
+        let bearer = |token: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                header::AUTHORIZATION,
+                format!("Bearer {token}").parse().unwrap(),
+            );
+            headers
+        };
+        let alice = session_owner(&bearer("zc_key_alice"));
+        let mallory = session_owner(&bearer("zc_key_mallory"));
+        assert_ne!(alice, mallory);
+        assert_eq!(alice, session_owner(&bearer("zc_key_alice")));
+        assert!(!alice.contains("alice"));
+
+        let sessions = GatewaySessions::new(None, 10, 0);
+        sessions
+            .record(&alice, "s1", "my pin is 1234", "noted")
+            .await;
+        assert!(sessions.history(&mallory, "s1").await.is_empty());
+        assert_eq!(sessions.history(&alice, "s1").await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn store_backed_history_survives_new_instance() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = crate::config::SessionConfig::default();
+        let store: Arc<dyn SessionStore> =
+            Arc::new(session::SqliteSessionStore::new(tmp.path(), &config).unwrap());
+
+        GatewaySessions::new(Some(store.clone()), 10, 0)
+            .record("k1", "s1", "remember 7", "noted")
+            .await;
+        let turns = GatewaySessions::new(Some(store), 10, 0)
+            .history("k1", "s1")
+            .await;
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].content, "remember 7");
+    }
+}
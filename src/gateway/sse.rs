@@ -155,4 +155,8 @@ impl crate::observability::Observer for BroadcastObserver {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn children(&self) -> Vec<&dyn crate::observability::Observer> {
+        vec![self.inner.as_ref()]
+    }
 }
@@ -0,0 +1,177 @@
+//! Multi-tenant gateway routing (`[gateway.tenants]`).
+//!
+//! Each `[gateway.tenants.<name>]` entry points at another config directory.
+//! The gateway loads that config and serves the workspace routes (webhooks,
+//! `/v1/*`, `/api/chat`, `/ws/chat`, the dashboard API) for it under
+//! `/t/<name>/...`, with its own provider, memory, tools, sessions and
+//! pairing. Clients that cannot change the path send
+//! `X-ZeroClaw-Tenant: <name>` instead. Process-wide routes (`/api/admin/*`,
+//! `/api/health`, `/ws/attach`, node control and the dashboard assets) stay
+//! with the main workspace.
+//!
+//! A tenant's requests run inside its [`crate::workspace`] scope, so turn
+//! reports, undo backups, personas, moderation, provider capabilities,
+//! `[agent]` limits and i18n come from the tenant config and land in the
+//! tenant workspace.
+
+use crate::config::Config;
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Request, State},
+    http::{StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Header selecting a tenant for requests on unprefixed paths.
+pub const TENANT_HEADER: &str = "x-zeroclaw-tenant";
+
+const TENANT_PATH_PREFIX: &str = "/t/";
+
+/// Path prefix the routes of tenant `name` are nested under.
+pub fn path_prefix(name: &str) -> String {
+    format!("{TENANT_PATH_PREFIX}{name}")
+}
+
+/// The route inside its workspace for a path under `/t/<name>/`.
+pub fn strip_tenant_prefix(path: &str) -> Option<&str> {
+    let rest = path.strip_prefix(TENANT_PATH_PREFIX)?;
+    rest.find('/').map(|slash| &rest[slash..])
+}
+
+/// Load the config of every `[gateway.tenants]` entry, sorted by name, and
+/// register its workspace settings.
+pub async fn load(config: &Config) -> Result<Vec<(String, Config)>> {
+    let mut names: Vec<&String> = config.gateway.tenants.keys().collect();
+    names.sort();
+    let mut tenants = Vec::with_capacity(names.len());
+    for name in names {
+        let tenant = &config.gateway.tenants[name];
+        let workspace_dir = tenant.workspace_dir();
+        tokio::fs::create_dir_all(&workspace_dir)
+            .await
+            .with_context(|| format!("Failed to create workspace of gateway tenant {name}"))?;
+        let tenant_config = Config::load_from_file(&tenant.config_path(), workspace_dir)
+            .await
+            .with_context(|| format!("Failed to load config of gateway tenant {name}"))?;
+        if !tenant_config.gateway.tenants.is_empty() {
+            tracing::warn!(tenant = %name, "Ignoring [gateway.tenants] in a tenant config");
+        }
+        crate::workspace::init_tenant(&tenant_config);
+        tenants.push((name.clone(), tenant_config));
+    }
+    Ok(tenants)
+}
+
+/// Middleware running a tenant's requests inside its workspace scope.
+pub async fn enter_workspace(
+    State(workspace_dir): State<PathBuf>,
+    request: Request,
+    next: Next,
+) -> Response {
+    crate::workspace::scope(Some(workspace_dir), next.run(request)).await
+}
+
+fn tenant_uri(uri: &Uri, name: &str) -> Option<Uri> {
+    let path_and_query = uri.path_and_query().map_or("/", |pq| pq.as_str());
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(
+        format!("{}{path_and_query}", path_prefix(name))
+            .parse()
+            .ok()?,
+    );
+    Uri::from_parts(parts).ok()
+}
+
+/// Middleware routing requests with [`TENANT_HEADER`] to the named tenant.
+/// Paths already under `/t/` are left alone; unknown tenants get `404`.
+pub async fn select_by_header(
+    State(tenants): State<Arc<HashSet<String>>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(value) = request.headers().get(TENANT_HEADER) else {
+        return next.run(request).await;
+    };
+    if request.uri().path().starts_with(TENANT_PATH_PREFIX) {
+        return next.run(request).await;
+    }
+    let uri = value
+        .to_str()
+        .ok()
+        .map(str::trim)
+        .filter(|name| tenants.contains(*name))
+        .and_then(|name| tenant_uri(request.uri(), name));
+    let Some(uri) = uri else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": "Unknown tenant",
+                "code": "unknown_tenant",
+            })),
+        )
+            .into_response();
+    };
+    *request.uri_mut() = uri;
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get, Router};
+    use tower::{Layer, Service};
+
+    #[tokio::test]
+    async fn header_selects_the_tenant_routes() {
+        let app = Router::new()
+            .route("/whoami", get(|| async { "main" }))
+            .nest(
+                &path_prefix("sales"),
+                Router::new().route("/whoami", get(|| async { "sales" })),
+            );
+        let mut app = axum::middleware::from_fn_with_state(
+            Arc::new(HashSet::from(["sales".to_string()])),
+            select_by_header,
+        )
+        .layer(app);
+
+        for (path, tenant, expected) in [
+            ("/whoami", None, "main"),
+            ("/whoami?x=1", Some("sales"), "sales"),
+            ("/t/sales/whoami", None, "sales"),
+        ] {
+            let mut request = Request::get(path);
+            if let Some(tenant) = tenant {
+                request = request.header(TENANT_HEADER, tenant);
+            }
+            let response = app
+                .call(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert_eq!(body, expected, "{path} with tenant {tenant:?}");
+        }
+
+        let unknown = app
+            .call(
+                Request::get("/whoami")
+                    .header(TENANT_HEADER, "support")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(unknown.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            strip_tenant_prefix("/t/sales/v1/models"),
+            Some("/v1/models")
+        );
+        assert_eq!(strip_tenant_prefix("/v1/models"), None);
+    }
+}
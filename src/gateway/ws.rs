@@ -4,17 +4,29 @@
 //! ```text
 //! Client -> Server: {"type":"message","content":"Hello"}
 //! Server -> Client: {"type":"chunk","content":"Hi! "}
-//! Server -> Client: {"type":"tool_call","name":"shell","args":{...}}
-//! Server -> Client: {"type":"tool_result","name":"shell","output":"..."}
+//! Server -> Client: {"type":"tool_call","id":"call_1","name":"shell","args":{...}}
+//! Server -> Client: {"type":"approval_required","request_id":"apr-1a2b3c4d","name":"shell","args":{...}}
+//! Client -> Server: {"type":"approval","request_id":"apr-1a2b3c4d","approved":true}
+//! Server -> Client: {"type":"tool_result","id":"call_1","name":"shell","success":true,"output":"...","duration_ms":12}
 //! Server -> Client: {"type":"done","full_response":"..."}
 //! ```
+//!
+//! One message is processed at a time per socket. Unanswered approval
+//! prompts expire after five minutes and count as a denial.
 
+use super::openclaw_compat::DeltaRelay;
 use super::AppState;
 use crate::agent::loop_::{
-    build_shell_policy_instructions, build_tool_instructions_from_specs, run_tool_call_loop,
+    build_context, build_shell_policy_instructions, build_tool_instructions_from_specs,
+    redact_tool_arguments, run_tool_call_loop_with_non_cli_approval_context, NonCliApprovalContext,
+    NonCliApprovalPrompt, ToolLoopEvent, CONTENT_INJECTION_CONFIG, TOOL_ANOMALY_MONITOR,
+    TOOL_EVENT_SINK,
 };
-use crate::approval::ApprovalManager;
+use crate::approval::{ApprovalManager, ApprovalResponse, PendingApprovalError};
+use crate::cost::usage::{UsageScope, USAGE_SCOPE};
+use crate::observability::turn;
 use crate::providers::ChatMessage;
+use crate::security::ToolAnomalyMonitor;
 use axum::{
     extract::{
         ws::{Message, WebSocket},
@@ -23,6 +35,15 @@ use axum::{
     http::{header, HeaderMap},
     response::IntoResponse,
 };
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Channel name recorded for tool approvals raised from the WebSocket.
+const WS_CHANNEL: &str = "ws";
 
 const EMPTY_WS_RESPONSE_FALLBACK: &str =
     "Tool execution completed, but the model returned no final text response. Please ask me to summarize the result.";
@@ -170,34 +191,129 @@ pub async fn handle_ws_chat(
         }
     }
 
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
-        .into_response()
+    // The socket is served on its own task; keep a tenant's workspace scope.
+    let workspace_dir = crate::workspace::current();
+    ws.on_upgrade(move |socket| {
+        crate::workspace::scope(workspace_dir, handle_socket(socket, state))
+    })
+    .into_response()
 }
 
-async fn handle_socket(mut socket: WebSocket, state: AppState) {
-    // Maintain conversation history for this WebSocket session
-    let mut history: Vec<ChatMessage> = Vec::new();
-
-    // Build system prompt once for the session
-    let system_prompt = {
-        let config_guard = state.config.lock();
-        build_ws_system_prompt(
-            &config_guard,
-            &state.model,
-            state.tools_registry_exec.as_ref(),
-            state.provider.supports_native_tools(),
+/// GET /ws/attach — approval prompts for `zeroclaw attach --gateway`.
+///
+/// Always requires a paired bearer token: an attached operator approves
+/// tool calls raised on every channel.
+pub async fn handle_ws_attach(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    let token = extract_ws_bearer_token(&headers).unwrap_or_default();
+    if !state.pairing.require_pairing() || !state.pairing.is_authenticated(&token) {
+        return (
+            axum::http::StatusCode::UNAUTHORIZED,
+            "Unauthorized — attaching requires pairing and Authorization: Bearer <token>",
         )
-    };
+            .into_response();
+    }
+
+    ws.on_upgrade(handle_attach_socket).into_response()
+}
+
+async fn handle_attach_socket(socket: WebSocket) {
+    let (mut sink, mut stream) = socket.split();
+    let mut operator = crate::approval::attach::attach();
+    loop {
+        let outgoing = tokio::select! {
+            msg = stream.next() => match msg {
+                Some(Ok(Message::Text(text))) => {
+                    match crate::approval::attach::handle_client_message(&text) {
+                        Some(reply) => reply,
+                        None => continue,
+                    }
+                }
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+            message = operator.next() => match message {
+                Some(message) => message,
+                None => break,
+            },
+        };
+        let Ok(encoded) = serde_json::to_string(&outgoing) else {
+            continue;
+        };
+        if sink.send(Message::Text(encoded.into())).await.is_err() {
+            break;
+        }
+    }
+}
 
-    // Add system message to history
-    history.push(ChatMessage::system(&system_prompt));
+/// State shared by the socket reader and the turn it is running.
+struct WsConnection {
+    state: AppState,
+    /// Requester identity for approval prompts raised on this socket.
+    peer_id: String,
+    history: Mutex<Vec<ChatMessage>>,
+    approval: ApprovalManager,
+    tool_anomaly: Arc<ToolAnomalyMonitor>,
+    out: mpsc::UnboundedSender<serde_json::Value>,
+}
+
+impl WsConnection {
+    fn send(&self, event: serde_json::Value) {
+        let _ = self.out.send(event);
+    }
+
+    fn send_error(&self, message: impl Into<String>) {
+        self.send(serde_json::json!({"type": "error", "message": message.into()}));
+    }
+}
 
-    let approval_manager = {
+async fn handle_socket(socket: WebSocket, state: AppState) {
+    let (mut sink, mut stream) = socket.split();
+    let (out_tx, mut out_rx) = mpsc::unbounded_channel::<serde_json::Value>();
+    let writer = tokio::spawn(async move {
+        while let Some(event) = out_rx.recv().await {
+            if sink
+                .send(Message::Text(event.to_string().into()))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    // Build system prompt once for the session
+    let (system_prompt, approval, tool_anomaly) = {
         let config_guard = state.config.lock();
-        ApprovalManager::from_config(&config_guard.autonomy)
+        (
+            build_ws_system_prompt(
+                &config_guard,
+                &state.model,
+                state.tools_registry_exec.as_ref(),
+                state.provider.supports_native_tools(),
+            ),
+            ApprovalManager::from_config(&config_guard.autonomy),
+            Arc::new(ToolAnomalyMonitor::new(
+                config_guard.security.tool_anomaly.clone(),
+            )),
+        )
     };
 
-    while let Some(msg) = socket.recv().await {
+    let conn = Arc::new(WsConnection {
+        state,
+        peer_id: format!("ws-{}", Uuid::new_v4()),
+        history: Mutex::new(vec![ChatMessage::system(&system_prompt)]),
+        approval,
+        tool_anomaly,
+        out: out_tx,
+    });
+    let cancellation_token = CancellationToken::new();
+    let mut turn: Option<tokio::task::JoinHandle<()>> = None;
+
+    while let Some(msg) = stream.next().await {
         let msg = match msg {
             Ok(Message::Text(text)) => text,
             Ok(Message::Close(_)) | Err(_) => break,
@@ -208,96 +324,281 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
         let parsed: serde_json::Value = match serde_json::from_str(&msg) {
             Ok(v) => v,
             Err(_) => {
-                let err = serde_json::json!({"type": "error", "message": "Invalid JSON"});
-                let _ = socket.send(Message::Text(err.to_string().into())).await;
+                conn.send_error("Invalid JSON");
                 continue;
             }
         };
 
-        let msg_type = parsed["type"].as_str().unwrap_or("");
-        if msg_type != "message" {
-            continue;
+        match parsed["type"].as_str().unwrap_or("") {
+            "message" => {}
+            "approval" => {
+                if let Err(message) = resolve_ws_approval(&conn.approval, &conn.peer_id, &parsed) {
+                    conn.send_error(message);
+                }
+                continue;
+            }
+            _ => continue,
         }
 
         let content = parsed["content"].as_str().unwrap_or("").to_string();
         if content.is_empty() {
             continue;
         }
-        let perplexity_cfg = { state.config.lock().security.perplexity_filter.clone() };
+        if turn.as_ref().is_some_and(|handle| !handle.is_finished()) {
+            conn.send_error("Still working on the previous message; wait for `done`.");
+            continue;
+        }
+        let perplexity_cfg = { conn.state.config.lock().security.perplexity_filter.clone() };
         if let Some(assessment) =
             crate::security::detect_adversarial_suffix(&content, &perplexity_cfg)
         {
-            let err = serde_json::json!({
-                "type": "error",
-                "message": format!(
-                    "Input blocked by security.perplexity_filter: perplexity={:.2} (threshold {:.2}), symbol_ratio={:.2} (threshold {:.2}), suspicious_tokens={}.",
-                    assessment.perplexity,
-                    perplexity_cfg.perplexity_threshold,
-                    assessment.symbol_ratio,
-                    perplexity_cfg.symbol_ratio_threshold,
-                    assessment.suspicious_token_count
-                ),
-            });
-            let _ = socket.send(Message::Text(err.to_string().into())).await;
+            conn.send_error(format!(
+                "Input blocked by security.perplexity_filter: perplexity={:.2} (threshold {:.2}), symbol_ratio={:.2} (threshold {:.2}), suspicious_tokens={}.",
+                assessment.perplexity,
+                perplexity_cfg.perplexity_threshold,
+                assessment.symbol_ratio,
+                perplexity_cfg.symbol_ratio_threshold,
+                assessment.suspicious_token_count
+            ));
             continue;
         }
 
-        // Add user message to history
-        history.push(ChatMessage::user(&content));
-
-        // Get provider info
-        let provider_label = state
-            .config
-            .lock()
-            .default_provider
-            .clone()
-            .unwrap_or_else(|| "unknown".to_string());
-
-        // Broadcast agent_start event
-        let _ = state.event_tx.send(serde_json::json!({
-            "type": "agent_start",
-            "provider": provider_label,
-            "model": state.model,
-        }));
-
-        // Full agentic loop with tools (includes WASM skills, shell, memory, etc.)
-        match super::run_gateway_chat_with_tools(&state, &content).await {
-            Ok(response) => {
-                let safe_response =
-                    finalize_ws_response(&response, &history, state.tools_registry_exec.as_ref());
-                // Add assistant response to history
-                history.push(ChatMessage::assistant(&safe_response));
-
-                // Send the full response as a done message
-                let done = serde_json::json!({
-                    "type": "done",
-                    "full_response": safe_response,
-                });
-                let _ = socket.send(Message::Text(done.to_string().into())).await;
-
-                // Broadcast agent_end event
-                let _ = state.event_tx.send(serde_json::json!({
-                    "type": "agent_end",
-                    "provider": provider_label,
-                    "model": state.model,
-                }));
-            }
-            Err(e) => {
-                let sanitized = crate::providers::sanitize_api_error(&e.to_string());
-                let err = serde_json::json!({
-                    "type": "error",
-                    "message": sanitized,
-                });
-                let _ = socket.send(Message::Text(err.to_string().into())).await;
-
-                // Broadcast error event
-                let _ = state.event_tx.send(serde_json::json!({
-                    "type": "error",
-                    "component": "ws_chat",
-                    "message": sanitized,
-                }));
+        turn = Some(tokio::spawn(crate::workspace::scope(
+            crate::workspace::current(),
+            run_ws_turn(Arc::clone(&conn), content, cancellation_token.child_token()),
+        )));
+    }
+
+    // Socket closed: stop the running turn (and any approval wait).
+    cancellation_token.cancel();
+    if let Some(handle) = turn {
+        let _ = handle.await;
+    }
+    writer.abort();
+}
+
+/// Run one agent turn, streaming chunks, tool events, and approval prompts
+/// to the socket as they happen.
+async fn run_ws_turn(
+    conn: Arc<WsConnection>,
+    content: String,
+    cancellation_token: CancellationToken,
+) {
+    let state = &conn.state;
+    let (provider_label, min_relevance_score, content_injection, excluded_tools) = {
+        let config = state.config.lock();
+        (
+            config
+                .default_provider
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            config.memory.min_relevance_score,
+            config.security.content_injection.clone(),
+            config.autonomy.non_cli_excluded_tools.clone(),
+        )
+    };
+
+    // Broadcast agent_start event
+    let _ = state.event_tx.send(serde_json::json!({
+        "type": "agent_start",
+        "provider": provider_label,
+        "model": state.model,
+    }));
+
+    let mem_context = build_context(state.mem.as_ref(), &content, min_relevance_score).await;
+    let mut history = conn.history.lock().clone();
+    history.push(ChatMessage::user(format!("{mem_context}{content}")));
+
+    let (delta_tx, delta_rx) = mpsc::channel::<String>(64);
+    let (tool_tx, tool_rx) = mpsc::unbounded_channel::<ToolLoopEvent>();
+    let (prompt_tx, prompt_rx) = mpsc::unbounded_channel::<NonCliApprovalPrompt>();
+    let forwarder = tokio::spawn(forward_turn_events(
+        conn.out.clone(),
+        delta_rx,
+        tool_rx,
+        prompt_rx,
+    ));
+
+    let approval_context = NonCliApprovalContext {
+        sender: conn.peer_id.clone(),
+        reply_target: conn.peer_id.clone(),
+        prompt_tx,
+    };
+    let usage_scope = state.usage_ledger.as_ref().map(|ledger| UsageScope {
+        ledger: Arc::clone(ledger),
+        session: conn.peer_id.clone(),
+    });
+    let result = turn::in_turn(
+        WS_CHANNEL,
+        None,
+        USAGE_SCOPE.scope(
+            usage_scope,
+            TOOL_ANOMALY_MONITOR.scope(
+                Arc::clone(&conn.tool_anomaly),
+                CONTENT_INJECTION_CONFIG.scope(
+                    content_injection,
+                    TOOL_EVENT_SINK.scope(
+                        tool_tx,
+                        run_tool_call_loop_with_non_cli_approval_context(
+                            state.provider.as_ref(),
+                            &mut history,
+                            state.tools_registry_exec.as_ref(),
+                            state.observer.as_ref(),
+                            &provider_label,
+                            &state.model,
+                            state.temperature,
+                            true,
+                            Some(&conn.approval),
+                            WS_CHANNEL,
+                            Some(approval_context),
+                            &state.multimodal,
+                            state.max_tool_iterations,
+                            Some(cancellation_token),
+                            Some(delta_tx),
+                            None,
+                            &excluded_tools,
+                        ),
+                    ),
+                ),
+            ),
+        ),
+    )
+    .await;
+    // All event senders are gone once the loop returns.
+    let _ = forwarder.await;
+
+    match result {
+        Ok(response) => {
+            let safe_response =
+                finalize_ws_response(&response, &history, state.tools_registry_exec.as_ref());
+            {
+                let mut session_history = conn.history.lock();
+                session_history.push(ChatMessage::user(&content));
+                session_history.push(ChatMessage::assistant(&safe_response));
             }
+
+            // Send the full response as a done message
+            conn.send(serde_json::json!({
+                "type": "done",
+                "full_response": safe_response,
+            }));
+
+            // Broadcast agent_end event
+            let _ = state.event_tx.send(serde_json::json!({
+                "type": "agent_end",
+                "provider": provider_label,
+                "model": state.model,
+            }));
+        }
+        Err(e) => {
+            let sanitized = crate::providers::sanitize_api_error(&e.to_string());
+            conn.send_error(sanitized.clone());
+
+            // Broadcast error event
+            let _ = state.event_tx.send(serde_json::json!({
+                "type": "error",
+                "component": "ws_chat",
+                "message": sanitized,
+            }));
+        }
+    }
+}
+
+/// Relay one turn's deltas, tool events, and approval prompts to the socket
+/// writer until the turn drops its senders.
+async fn forward_turn_events(
+    out: mpsc::UnboundedSender<serde_json::Value>,
+    mut deltas: mpsc::Receiver<String>,
+    mut tool_events: mpsc::UnboundedReceiver<ToolLoopEvent>,
+    mut prompts: mpsc::UnboundedReceiver<NonCliApprovalPrompt>,
+) {
+    let mut relay = DeltaRelay::default();
+    loop {
+        let event = tokio::select! {
+            Some(delta) = deltas.recv() => match relay.push(&delta) {
+                Some(text) => serde_json::json!({"type": "chunk", "content": text}),
+                None => continue,
+            },
+            Some(event) = tool_events.recv() => tool_event_json(event),
+            Some(prompt) = prompts.recv() => serde_json::json!({
+                "type": "approval_required",
+                "request_id": prompt.request_id,
+                "name": prompt.tool_name,
+                "args": redact_tool_arguments(&prompt.arguments),
+            }),
+            else => break,
+        };
+        let _ = out.send(event);
+    }
+}
+
+fn tool_event_json(event: ToolLoopEvent) -> serde_json::Value {
+    match event {
+        ToolLoopEvent::Started {
+            id,
+            name,
+            arguments,
+        } => serde_json::json!({
+            "type": "tool_call",
+            "id": id,
+            "name": name,
+            "args": arguments,
+        }),
+        ToolLoopEvent::Finished {
+            id,
+            name,
+            success,
+            output,
+            duration,
+        } => serde_json::json!({
+            "type": "tool_result",
+            "id": id,
+            "name": name,
+            "success": success,
+            "output": output,
+            "duration_ms": u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+        }),
+    }
+}
+
+/// Answer an `approval_required` prompt raised on this socket.
+fn resolve_ws_approval(
+    approval: &ApprovalManager,
+    peer_id: &str,
+    parsed: &serde_json::Value,
+) -> Result<(), String> {
+    let request_id = parsed["request_id"].as_str().unwrap_or("").trim();
+    let Some(approved) = parsed["approved"].as_bool() else {
+        return Err("approval needs `request_id` and boolean `approved`".to_string());
+    };
+    if request_id.is_empty() {
+        return Err("approval needs `request_id` and boolean `approved`".to_string());
+    }
+
+    let result = if approved {
+        approval.confirm_non_cli_pending_request(request_id, peer_id, WS_CHANNEL, peer_id)
+    } else {
+        approval.reject_non_cli_pending_request(request_id, peer_id, WS_CHANNEL, peer_id)
+    };
+    match result {
+        Ok(_) => {
+            let decision = if approved {
+                ApprovalResponse::Yes
+            } else {
+                ApprovalResponse::No
+            };
+            approval.record_non_cli_pending_resolution(request_id, decision);
+            Ok(())
+        }
+        Err(PendingApprovalError::NotFound) => {
+            Err(format!("Approval request `{request_id}` was not found"))
+        }
+        Err(PendingApprovalError::Expired) => {
+            Err(format!("Approval request `{request_id}` has expired"))
         }
+        Err(PendingApprovalError::RequesterMismatch) => Err(format!(
+            "Approval request `{request_id}` belongs to another session"
+        )),
     }
 }
 
@@ -507,4 +808,59 @@ Reminder set successfully."#;
         let result = finalize_ws_response("", &history, &tools);
         assert_eq!(result, EMPTY_WS_RESPONSE_FALLBACK);
     }
+
+    #[test]
+    fn tool_event_json_maps_start_and_result() {
+        let started = tool_event_json(ToolLoopEvent::Started {
+            id: Some("call_1".into()),
+            name: "shell".into(),
+            arguments: serde_json::json!({"command": "ls"}),
+        });
+        assert_eq!(started["type"], "tool_call");
+        assert_eq!(started["id"], "call_1");
+        assert_eq!(started["args"]["command"], "ls");
+
+        let finished = tool_event_json(ToolLoopEvent::Finished {
+            id: None,
+            name: "shell".into(),
+            success: false,
+            output: "Error: denied".into(),
+            duration: std::time::Duration::from_millis(12),
+        });
+        assert_eq!(finished["type"], "tool_result");
+        assert_eq!(finished["success"], false);
+        assert_eq!(finished["duration_ms"], 12);
+        assert!(finished["id"].is_null());
+    }
+
+    #[test]
+    fn resolve_ws_approval_records_decision_for_own_request() {
+        let approval = ApprovalManager::from_config(&crate::config::AutonomyConfig::default());
+        let pending =
+            approval.create_non_cli_pending_request("shell", "ws-a", WS_CHANNEL, "ws-a", None);
+
+        let reply = serde_json::json!({
+            "type": "approval",
+            "request_id": pending.request_id,
+            "approved": true,
+        });
+        assert!(resolve_ws_approval(&approval, "ws-b", &reply)
+            .unwrap_err()
+            .contains("another session"));
+        resolve_ws_approval(&approval, "ws-a", &reply).unwrap();
+        assert_eq!(
+            approval.take_non_cli_pending_resolution(&pending.request_id),
+            Some(ApprovalResponse::Yes)
+        );
+        assert!(resolve_ws_approval(&approval, "ws-a", &reply)
+            .unwrap_err()
+            .contains("not found"));
+    }
+
+    #[test]
+    fn resolve_ws_approval_requires_decision() {
+        let approval = ApprovalManager::from_config(&crate::config::AutonomyConfig::default());
+        let reply = serde_json::json!({"type": "approval", "request_id": "apr-1"});
+        assert!(resolve_ws_approval(&approval, "ws-a", &reply).is_err());
+    }
 }
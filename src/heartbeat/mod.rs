@@ -1,4 +1,5 @@
 pub mod engine;
+pub mod proactive;
 
 #[cfg(test)]
 mod tests {
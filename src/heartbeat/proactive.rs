@@ -0,0 +1,247 @@
+use crate::config::{Config, HeartbeatQuietHoursConfig};
+use crate::cron::CronJob;
+use crate::memory::Memory;
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use std::fmt::Write;
+use tracing::warn;
+
+/// Memory search used to find items the user asked to be followed up on.
+const FOLLOW_UP_QUERY: &str = "follow up";
+const MAX_FOLLOW_UPS: usize = 5;
+
+/// Parsed `[heartbeat.quiet_hours]` window.
+#[derive(Debug, Clone)]
+pub struct QuietHours {
+    start: NaiveTime,
+    end: NaiveTime,
+    tz: Tz,
+}
+
+impl QuietHours {
+    pub fn from_config(config: &HeartbeatQuietHoursConfig) -> Result<Self> {
+        let parse = |value: &str| {
+            NaiveTime::parse_from_str(value.trim(), "%H:%M")
+                .with_context(|| format!("invalid quiet hours time '{value}', expected HH:MM"))
+        };
+        let tz = match config.tz.as_deref().map(str::trim) {
+            Some(name) if !name.is_empty() => name
+                .parse::<Tz>()
+                .map_err(|e| anyhow::anyhow!("invalid quiet hours timezone '{name}': {e}"))?,
+            _ => Tz::UTC,
+        };
+        Ok(Self {
+            start: parse(&config.start)?,
+            end: parse(&config.end)?,
+            tz,
+        })
+    }
+
+    /// Whether `now` falls inside the window. `start == end` is an empty window.
+    pub fn contains(&self, now: DateTime<Utc>) -> bool {
+        let local = now.with_timezone(&self.tz).time();
+        if self.start <= self.end {
+            self.start <= local && local < self.end
+        } else {
+            local >= self.start || local < self.end
+        }
+    }
+}
+
+/// What a proactive check-in reviews on one tick.
+#[derive(Debug, Default)]
+pub struct CheckInContext {
+    pub reminders: Vec<String>,
+    pub follow_ups: Vec<String>,
+    pub watch: Vec<String>,
+}
+
+impl CheckInContext {
+    /// Nothing to review — the check-in can be skipped without calling the model.
+    pub fn is_empty(&self) -> bool {
+        self.reminders.is_empty() && self.follow_ups.is_empty() && self.watch.is_empty()
+    }
+
+    /// Collect upcoming reminders, memory follow-ups and watched resources.
+    pub async fn gather(
+        config: &Config,
+        memory: &dyn Memory,
+        now: DateTime<Utc>,
+        horizon: chrono::Duration,
+    ) -> Self {
+        let reminders = match crate::cron::list_jobs(config) {
+            Ok(jobs) => upcoming_reminders(&jobs, now, horizon),
+            Err(e) => {
+                warn!("Heartbeat check-in could not list cron jobs: {e}");
+                Vec::new()
+            }
+        };
+        let follow_ups = match memory.recall(FOLLOW_UP_QUERY, MAX_FOLLOW_UPS, None).await {
+            Ok(entries) => entries
+                .into_iter()
+                .map(|entry| format!("{}: {}", entry.key, entry.content.trim()))
+                .collect(),
+            Err(e) => {
+                warn!("Heartbeat check-in could not recall follow-ups: {e}");
+                Vec::new()
+            }
+        };
+        let watch = config
+            .heartbeat
+            .watch
+            .iter()
+            .map(|item| item.trim())
+            .filter(|item| !item.is_empty())
+            .map(ToString::to_string)
+            .collect();
+
+        Self {
+            reminders,
+            follow_ups,
+            watch,
+        }
+    }
+
+    /// Prompt for the check-in turn. The agent answers `HEARTBEAT_OK` when
+    /// nothing needs the user's attention, which suppresses delivery.
+    pub fn prompt(&self) -> String {
+        let mut prompt = String::from(
+            "[Heartbeat Check-in] Review the items below. If something needs the user's \
+             attention now (a reminder that is due, a follow-up that is overdue, a watched \
+             resource that changed or is failing), write a short message to the user about \
+             it. Otherwise reply with exactly HEARTBEAT_OK.\n",
+        );
+        for (title, items) in [
+            ("Upcoming reminders", &self.reminders),
+            ("Memory follow-ups", &self.follow_ups),
+            ("Watched resources", &self.watch),
+        ] {
+            if items.is_empty() {
+                continue;
+            }
+            let _ = write!(prompt, "\n{title}:\n");
+            for item in items {
+                let _ = writeln!(prompt, "- {item}");
+            }
+        }
+        prompt
+    }
+}
+
+/// Enabled jobs due before `now + horizon`, soonest first.
+fn upcoming_reminders(
+    jobs: &[CronJob],
+    now: DateTime<Utc>,
+    horizon: chrono::Duration,
+) -> Vec<String> {
+    let cutoff = now + horizon;
+    let mut due: Vec<&CronJob> = jobs
+        .iter()
+        .filter(|job| job.enabled && job.next_run <= cutoff)
+        .collect();
+    due.sort_by_key(|job| job.next_run);
+    due.into_iter()
+        .map(|job| {
+            let what = job.prompt.as_deref().unwrap_or(&job.command);
+            let name = job.name.as_deref().unwrap_or(&job.id);
+            format!("{name} at {}: {what}", job.next_run.to_rfc3339())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cron::{DeliveryConfig, JobType, Schedule, SessionTarget};
+    use chrono::TimeZone;
+
+    fn quiet(start: &str, end: &str, tz: Option<&str>) -> QuietHours {
+        QuietHours::from_config(&HeartbeatQuietHoursConfig {
+            start: start.into(),
+            end: end.into(),
+            tz: tz.map(Into::into),
+        })
+        .unwrap()
+    }
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 10, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn quiet_hours_wrap_past_midnight() {
+        let window = quiet("22:00", "07:00", None);
+        assert!(window.contains(at(23, 30)));
+        assert!(window.contains(at(3, 0)));
+        assert!(!window.contains(at(7, 0)));
+        assert!(!window.contains(at(12, 0)));
+    }
+
+    #[test]
+    fn quiet_hours_use_configured_timezone() {
+        // 13:00 UTC is 14:00 in Berlin (CET) on this date.
+        let window = quiet("14:00", "15:00", Some("Europe/Berlin"));
+        assert!(window.contains(at(13, 0)));
+        assert!(!window.contains(at(14, 0)));
+        assert!(QuietHours::from_config(&HeartbeatQuietHoursConfig {
+            start: "9am".into(),
+            end: "10:00".into(),
+            tz: None,
+        })
+        .is_err());
+    }
+
+    fn job(name: &str, next_run: DateTime<Utc>, enabled: bool) -> CronJob {
+        CronJob {
+            id: format!("id-{name}"),
+            expression: String::new(),
+            schedule: Schedule::At { at: next_run },
+            command: String::new(),
+            prompt: Some(format!("remind about {name}")),
+            name: Some(name.into()),
+            job_type: JobType::Agent,
+            session_target: SessionTarget::Isolated,
+            model: None,
+            enabled,
+            delivery: DeliveryConfig::default(),
+            delete_after_run: true,
+            created_at: next_run,
+            next_run,
+            last_run: None,
+            last_status: None,
+            last_output: None,
+        }
+    }
+
+    #[test]
+    fn upcoming_reminders_only_include_enabled_jobs_within_horizon() {
+        let now = at(9, 0);
+        let jobs = vec![
+            job("later", at(11, 0), true),
+            job("dentist", at(9, 20), true),
+            job("paused", at(9, 5), false),
+            job("standup", at(9, 10), true),
+        ];
+        let reminders = upcoming_reminders(&jobs, now, chrono::Duration::minutes(30));
+        assert_eq!(reminders.len(), 2);
+        assert!(reminders[0].starts_with("standup at "));
+        assert!(reminders[1].ends_with("remind about dentist"));
+    }
+
+    #[test]
+    fn check_in_prompt_lists_only_non_empty_sections() {
+        let context = CheckInContext {
+            reminders: Vec::new(),
+            follow_ups: vec!["pr-review: follow up with Sam on PR #42".into()],
+            watch: vec!["https://status.example.com".into()],
+        };
+        assert!(!context.is_empty());
+        let prompt = context.prompt();
+        assert!(prompt.contains("HEARTBEAT_OK"));
+        assert!(!prompt.contains("Upcoming reminders"));
+        assert!(prompt.contains("Memory follow-ups:\n- pr-review"));
+        assert!(prompt.contains("Watched resources:\n- https://status.example.com"));
+        assert!(CheckInContext::default().is_empty());
+    }
+}
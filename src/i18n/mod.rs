@@ -0,0 +1,355 @@
+//! Locale catalogs for user-facing runtime messages.
+//!
+//! Strings the runtime itself sends — approval prompts, error and timeout
+//! notices, quarantine notices — are looked up by key in a catalog chosen
+//! per channel (`[i18n].channels`, falling back to `[i18n].locale`).
+//! Catalogs are TOML files whose nested tables flatten to dotted keys
+//! (`[reply] timeout = "..."` is `reply.timeout`), with `{name}`
+//! placeholders filled by the caller. `en` and `zh-CN` are built in;
+//! `<workspace>/locales/<locale>.toml` overrides their strings or adds a
+//! locale. A missing key falls back to the base language (`zh` for
+//! `zh-TW`), then to English. During a channel turn the locale of the
+//! sender's [`profile`] takes precedence over the channel's.
+
+pub mod profile;
+
+use crate::config::{I18nConfig, PrincipalLocaleConfig};
+use crate::workspace::Scoped;
+use chrono_tz::Tz;
+use profile::PrincipalProfile;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
+
+const FALLBACK_LOCALE: &str = "en";
+
+const BUILTIN_CATALOGS: &[(&str, &str)] = &[
+    ("en", include_str!("locales/en.toml")),
+    ("zh-CN", include_str!("locales/zh-CN.toml")),
+];
+
+/// Directory under the workspace holding catalog overrides.
+const WORKSPACE_LOCALES_DIR: &str = "locales";
+
+type Catalog = HashMap<String, String>;
+
+/// Catalogs and locale selection.
+#[derive(Debug, Clone)]
+pub struct Localizer {
+    locale: String,
+    channel_locales: HashMap<String, String>,
+    catalogs: HashMap<String, Catalog>,
+    timezone: Option<Tz>,
+    principals: HashMap<String, PrincipalLocaleConfig>,
+}
+
+impl Default for Localizer {
+    fn default() -> Self {
+        Self::new(&I18nConfig::default(), None)
+    }
+}
+
+impl Localizer {
+    /// Built-in catalogs plus overrides from `<workspace>/locales/`.
+    pub fn new(config: &I18nConfig, workspace_dir: Option<&Path>) -> Self {
+        let mut catalogs: HashMap<String, Catalog> = HashMap::new();
+        for (locale, source) in BUILTIN_CATALOGS {
+            match parse_catalog(source) {
+                Ok(catalog) => {
+                    catalogs.insert(normalize_locale(locale), catalog);
+                }
+                Err(e) => tracing::error!(locale, "Built-in locale catalog is invalid: {e}"),
+            }
+        }
+        if let Some(dir) = workspace_dir.map(|dir| dir.join(WORKSPACE_LOCALES_DIR)) {
+            for (locale, catalog) in load_catalog_dir(&dir) {
+                catalogs.entry(locale).or_default().extend(catalog);
+            }
+        }
+
+        let localizer = Self {
+            locale: normalize_locale(&config.locale),
+            channel_locales: config
+                .channels
+                .iter()
+                .map(|(channel, locale)| (channel.clone(), normalize_locale(locale)))
+                .collect(),
+            catalogs,
+            timezone: config.timezone.as_deref().and_then(parse_timezone),
+            principals: config.principals.clone(),
+        };
+        for locale in std::iter::once(&localizer.locale).chain(localizer.channel_locales.values()) {
+            if localizer.resolve_chain(locale).len() == 1 && locale != FALLBACK_LOCALE {
+                tracing::warn!(
+                    locale = %locale,
+                    "No catalog for configured locale; messages fall back to English"
+                );
+            }
+        }
+        localizer
+    }
+
+    /// Locale for messages sent on `channel`.
+    pub fn locale_for(&self, channel: &str) -> &str {
+        self.channel_locales
+            .get(channel)
+            .unwrap_or(&self.locale)
+            .as_str()
+    }
+
+    /// `key` in the locale of `channel`, with `{name}` placeholders
+    /// replaced from `args`. Unknown keys render as the key itself.
+    pub fn text(&self, channel: &str, key: &str, args: &[(&str, &str)]) -> String {
+        self.text_in(self.locale_for(channel), key, args)
+    }
+
+    /// `key` in `locale`, with `{name}` placeholders replaced from `args`.
+    pub fn text_in(&self, locale: &str, key: &str, args: &[(&str, &str)]) -> String {
+        let template = self
+            .resolve_chain(locale)
+            .into_iter()
+            .find_map(|catalog| catalog.get(key))
+            .map_or(key, String::as_str);
+        args.iter()
+            .fold(template.to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), value)
+            })
+    }
+
+    /// Catalogs to search for `locale`: exact, base language, English.
+    fn resolve_chain(&self, locale: &str) -> Vec<&Catalog> {
+        let mut chain = Vec::with_capacity(3);
+        let base = locale.split('-').next().unwrap_or(locale);
+        for candidate in [locale, base, FALLBACK_LOCALE] {
+            if let Some(catalog) = self.catalogs.get(candidate) {
+                if !chain.iter().any(|known| std::ptr::eq(*known, catalog)) {
+                    chain.push(catalog);
+                }
+            }
+        }
+        chain
+    }
+
+    /// Time zone and locale configured for `sender` on `channel`. Keys are
+    /// tried from most to least specific, each field separately.
+    pub fn configured_profile(&self, channel: &str, sender: &str) -> PrincipalProfile {
+        let mut profile = PrincipalProfile {
+            default_timezone: self.timezone,
+            ..PrincipalProfile::default()
+        };
+        for key in [
+            format!("{channel}:{sender}"),
+            format!("{channel}:*"),
+            format!("*:{sender}"),
+        ] {
+            let Some(config) = self.principals.get(&key) else {
+                continue;
+            };
+            if profile.timezone.is_none() {
+                profile.timezone = config.timezone.as_deref().and_then(parse_timezone);
+            }
+            if profile.locale.is_none() {
+                profile.locale = config.locale.as_deref().map(normalize_locale);
+            }
+        }
+        profile
+    }
+}
+
+fn parse_timezone(raw: &str) -> Option<Tz> {
+    raw.trim().parse().ok()
+}
+
+/// Canonical locale tag: `zh_CN.UTF-8` and `zh-cn` become `zh-CN`.
+fn normalize_locale(raw: &str) -> String {
+    let tag = raw
+        .trim()
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .replace('_', "-");
+    let mut parts = tag.split('-').filter(|part| !part.is_empty());
+    let Some(language) = parts.next() else {
+        return FALLBACK_LOCALE.to_string();
+    };
+    let mut normalized = language.to_ascii_lowercase();
+    for part in parts {
+        normalized.push('-');
+        if part.len() == 2 {
+            normalized.push_str(&part.to_ascii_uppercase());
+        } else {
+            normalized.push_str(part);
+        }
+    }
+    normalized
+}
+
+/// Flatten a TOML catalog into dotted keys.
+fn parse_catalog(source: &str) -> anyhow::Result<Catalog> {
+    fn flatten(prefix: &str, table: &toml::Table, out: &mut Catalog) {
+        for (key, value) in table {
+            let key = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{prefix}.{key}")
+            };
+            match value {
+                toml::Value::String(text) => {
+                    out.insert(key, text.clone());
+                }
+                toml::Value::Table(nested) => flatten(&key, nested, out),
+                _ => tracing::warn!(key = %key, "Ignoring non-string locale catalog entry"),
+            }
+        }
+    }
+
+    let table: toml::Table = toml::from_str(source)?;
+    let mut catalog = Catalog::new();
+    flatten("", &table, &mut catalog);
+    Ok(catalog)
+}
+
+fn load_catalog_dir(dir: &Path) -> Vec<(String, Catalog)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut catalogs = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("toml") {
+            continue;
+        }
+        let Some(locale) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        match std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|source| parse_catalog(&source))
+        {
+            Ok(catalog) => catalogs.push((normalize_locale(locale), catalog)),
+            Err(e) => tracing::warn!(path = %path.display(), "Skipping locale catalog: {e}"),
+        }
+    }
+    catalogs
+}
+
+static LOCALIZER: LazyLock<Scoped<Arc<Localizer>>> =
+    LazyLock::new(|| Scoped::new(Arc::new(Localizer::default())));
+
+/// Apply `[i18n]` and load `<workspace>/locales/`.
+pub fn init_from_config(config: &I18nConfig, workspace_dir: &Path) {
+    LOCALIZER.set(Arc::new(Localizer::new(config, Some(workspace_dir))));
+}
+
+/// `key` in the locale configured for `channel`.
+pub fn t(channel: &str, key: &str) -> String {
+    t_with(channel, key, &[])
+}
+
+/// `key` in the locale configured for `channel`, with placeholders filled.
+pub fn t_with(channel: &str, key: &str, args: &[(&str, &str)]) -> String {
+    let localizer = LOCALIZER.get();
+    match profile::turn_profile().and_then(|profile| profile.locale) {
+        Some(locale) => localizer.text_in(&locale, key, args),
+        None => localizer.text(channel, key, args),
+    }
+}
+
+/// Configured time zone and locale of `sender` on `channel`.
+fn configured_profile(channel: &str, sender: &str) -> PrincipalProfile {
+    LOCALIZER.get().configured_profile(channel, sender)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_catalogs_define_every_english_key() {
+        let english = parse_catalog(BUILTIN_CATALOGS[0].1).unwrap();
+        for (locale, source) in &BUILTIN_CATALOGS[1..] {
+            let catalog = parse_catalog(source).unwrap();
+            for key in english.keys() {
+                assert!(catalog.contains_key(key), "{locale} is missing `{key}`");
+            }
+        }
+    }
+
+    #[test]
+    fn channel_locale_overrides_and_fallbacks() {
+        let workspace = tempfile::tempdir().unwrap();
+        let locales = workspace.path().join(WORKSPACE_LOCALES_DIR);
+        std::fs::create_dir_all(&locales).unwrap();
+        std::fs::write(
+            locales.join("fr.toml"),
+            "[reply]\ntimeout = \"⚠️ Délai dépassé.\"\n",
+        )
+        .unwrap();
+
+        let localizer = Localizer::new(
+            &I18nConfig {
+                locale: "fr".into(),
+                channels: HashMap::from([
+                    ("telegram".to_string(), "zh_CN.UTF-8".to_string()),
+                    ("discord".to_string(), "zh-TW".to_string()),
+                ]),
+                ..I18nConfig::default()
+            },
+            Some(workspace.path()),
+        );
+
+        assert_eq!(localizer.locale_for("telegram"), "zh-CN");
+        assert_eq!(
+            localizer.text("telegram", "reply.error", &[("error", "boom")]),
+            "⚠️ 出错了：boom"
+        );
+        assert_eq!(
+            localizer.text("slack", "reply.timeout", &[]),
+            "⚠️ Délai dépassé."
+        );
+        assert_eq!(
+            localizer.text("slack", "reply.error", &[("error", "boom")]),
+            "⚠️ Error: boom"
+        );
+        // No zh-TW or zh catalog: English.
+        assert_eq!(
+            localizer.text("discord", "approval.deny_button", &[]),
+            "Deny"
+        );
+        assert_eq!(localizer.text("slack", "no.such.key", &[]), "no.such.key");
+    }
+
+    #[test]
+    fn principal_profiles_merge_from_most_specific_key() {
+        let localizer = Localizer::new(
+            &I18nConfig {
+                timezone: Some("UTC".into()),
+                principals: HashMap::from([
+                    (
+                        "telegram:alice".to_string(),
+                        PrincipalLocaleConfig {
+                            timezone: Some("Europe/Berlin".into()),
+                            locale: None,
+                        },
+                    ),
+                    (
+                        "telegram:*".to_string(),
+                        PrincipalLocaleConfig {
+                            timezone: Some("Asia/Tokyo".into()),
+                            locale: Some("zh_CN".into()),
+                        },
+                    ),
+                ]),
+                ..I18nConfig::default()
+            },
+            None,
+        );
+
+        let alice = localizer.configured_profile("telegram", "alice");
+        assert_eq!(alice.timezone, Some(chrono_tz::Europe::Berlin));
+        assert_eq!(alice.locale.as_deref(), Some("zh-CN"));
+
+        let bob = localizer.configured_profile("slack", "bob");
+        assert_eq!(bob.timezone, None);
+        assert_eq!(bob.effective_timezone(), Some(chrono_tz::UTC));
+    }
+}
@@ -0,0 +1,191 @@
+//! Time zone and locale of the principal a channel turn runs for.
+//!
+//! Each field resolves on its own, in this order: `[i18n.principals]` (the
+//! most specific key wins), then the `user_timezone` / `user_locale` entries
+//! of the principal's private memory tier, which the agent writes when a
+//! user mentions them, then `[i18n].timezone` and the channel locale. Channel
+//! turns run inside [`with_turn_profile`], so the system prompt, message
+//! timestamps, runtime notices and the scheduling tools all work in the
+//! principal's local time instead of the host's.
+
+use crate::memory::{principal, Memory};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::fmt::Write;
+
+/// Principal-tier memory key holding a learned IANA time zone.
+pub const TIMEZONE_MEMORY_KEY: &str = "user_timezone";
+
+/// Principal-tier memory key holding a learned locale tag.
+pub const LOCALE_MEMORY_KEY: &str = "user_locale";
+
+/// Formats accepted for times written without a UTC offset.
+const LOCAL_DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+];
+
+tokio::task_local! {
+    static TURN_PROFILE: PrincipalProfile;
+}
+
+/// Resolved time zone and locale of one principal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrincipalProfile {
+    /// The principal's own time zone, configured or learned.
+    pub timezone: Option<Tz>,
+    /// `[i18n].timezone`, used when the principal has none.
+    pub default_timezone: Option<Tz>,
+    /// Locale tag; `None` uses the channel locale.
+    pub locale: Option<String>,
+}
+
+impl PrincipalProfile {
+    /// Time zone to work in; `None` is the host's local time zone.
+    pub fn effective_timezone(&self) -> Option<Tz> {
+        self.timezone.or(self.default_timezone)
+    }
+
+    /// Interpret a wall-clock time in this profile's time zone. `None` when
+    /// the time does not exist there (skipped by a DST change).
+    pub fn localize(&self, naive: &NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self.effective_timezone() {
+            Some(tz) => tz
+                .from_local_datetime(naive)
+                .earliest()
+                .map(|at| at.with_timezone(&Utc)),
+            None => Local
+                .from_local_datetime(naive)
+                .earliest()
+                .map(|at| at.with_timezone(&Utc)),
+        }
+    }
+}
+
+/// Run `fut` with `profile` as the principal profile of the turn.
+pub async fn with_turn_profile<F: std::future::Future>(
+    profile: PrincipalProfile,
+    fut: F,
+) -> F::Output {
+    TURN_PROFILE.scope(profile, fut).await
+}
+
+/// Profile of the running channel turn, if any.
+pub fn turn_profile() -> Option<PrincipalProfile> {
+    TURN_PROFILE.try_with(Clone::clone).ok()
+}
+
+/// IANA name of the running turn's time zone, if one is known.
+pub fn turn_timezone_name() -> Option<String> {
+    turn_profile()
+        .and_then(|profile| profile.effective_timezone())
+        .map(|tz| tz.name().to_string())
+}
+
+/// Parse a time given by a user or the model. RFC 3339 with an offset is
+/// taken as is; a time without one (`2026-03-01T09:00`, `2026-03-01 09:00`)
+/// is wall-clock time of the turn's principal, or of the host outside a
+/// channel turn.
+pub fn parse_user_datetime(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(raw) {
+        return Some(at.with_timezone(&Utc));
+    }
+    let naive = LOCAL_DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(raw, format).ok())?;
+    turn_profile().unwrap_or_default().localize(&naive)
+}
+
+/// Resolve the profile of `sender` on `channel`. Learned values are read
+/// only when `learned` is set, i.e. when principal memory tiers exist.
+pub async fn resolve(
+    memory: &dyn Memory,
+    channel: &str,
+    sender: &str,
+    learned: bool,
+) -> PrincipalProfile {
+    let mut profile = super::configured_profile(channel, sender);
+    if !learned || (profile.timezone.is_some() && profile.locale.is_some()) {
+        return profile;
+    }
+    let namespace = principal::principal_namespace(channel, sender);
+    if profile.timezone.is_none() {
+        profile.timezone = recall_value(memory, &namespace, TIMEZONE_MEMORY_KEY)
+            .await
+            .and_then(|value| match value.parse::<Tz>() {
+                Ok(tz) => Some(tz),
+                Err(_) => {
+                    tracing::warn!(channel, sender, value, "Ignoring unknown learned time zone");
+                    None
+                }
+            });
+    }
+    if profile.locale.is_none() {
+        profile.locale = recall_value(memory, &namespace, LOCALE_MEMORY_KEY)
+            .await
+            .map(|value| super::normalize_locale(&value));
+    }
+    profile
+}
+
+async fn recall_value(memory: &dyn Memory, namespace: &str, key: &str) -> Option<String> {
+    let stored_key = principal::scoped_key(Some(namespace), key);
+    match memory.get(&stored_key).await {
+        Ok(entry) => entry
+            .map(|entry| entry.content.trim().to_string())
+            .filter(|value| !value.is_empty()),
+        Err(e) => {
+            tracing::debug!(key, "Failed to read principal profile from memory: {e}");
+            None
+        }
+    }
+}
+
+/// System prompt lines describing the principal's time zone. With
+/// `learnable`, a principal without a time zone of its own is asked for it.
+pub fn prompt_section(profile: &PrincipalProfile, learnable: bool) -> String {
+    let mut prompt = String::new();
+    if let Some(tz) = profile.timezone {
+        let _ = write!(
+            prompt,
+            "The user's time zone is {}. Read times they mention as local to it; \
+             `cron_add` and `schedule` use it for schedules without an explicit zone.",
+            tz.name()
+        );
+    } else if learnable {
+        prompt.push_str(
+            "The user's time zone is unknown. When they mention it or where they are, \
+             save it with `memory_store` (key `user_timezone`, scope `principal`, an IANA \
+             name such as `Europe/Berlin`); save a preferred language the same way under \
+             `user_locale`.",
+        );
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn naive_times_resolve_in_the_turn_time_zone() {
+        let profile = PrincipalProfile {
+            timezone: Some(chrono_tz::Asia::Shanghai),
+            default_timezone: Some(chrono_tz::UTC),
+            locale: None,
+        };
+        let at = with_turn_profile(profile, async {
+            assert_eq!(turn_timezone_name().as_deref(), Some("Asia/Shanghai"));
+            parse_user_datetime("2026-03-02 09:00").unwrap()
+        })
+        .await;
+        assert_eq!(at.to_rfc3339(), "2026-03-02T01:00:00+00:00");
+
+        let explicit = parse_user_datetime("2026-03-02T09:00:00-05:00").unwrap();
+        assert_eq!(explicit.to_rfc3339(), "2026-03-02T14:00:00+00:00");
+        assert!(parse_user_datetime("tomorrow at nine").is_none());
+    }
+}
@@ -0,0 +1,845 @@
+//! Knowledge base: private documents the agent can search.
+//!
+//! `zeroclaw kb ingest` reads local files, directories and URLs, splits them
+//! into chunks with the memory chunker and stores each chunk in
+//! `<workspace>/state/kb.db` with its embedding (from the `[memory]`
+//! embedding settings) and an FTS5 index. Retrieval ranks chunks by cosine
+//! similarity and BM25 and merges both with the memory weights, the same
+//! way the SQLite memory backend does. With `[kb].enabled`, `kb_search` is
+//! registered and the best passages for each user message are injected
+//! ahead of it, within `kb.context_tokens`.
+
+use crate::config::{Config, KbConfig};
+use crate::db::{migrate, Migration, Schema, Step};
+use crate::memory::chunker::chunk_markdown;
+use crate::memory::embeddings::EmbeddingProvider;
+use crate::memory::vector;
+use crate::util::truncate_with_ellipsis;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Largest file or URL body ingested.
+const MAX_DOCUMENT_BYTES: usize = 5 * 1024 * 1024;
+const FETCH_TIMEOUT_SECS: u64 = 30;
+/// Chunks sent to the embedding provider per request.
+const EMBED_BATCH: usize = 16;
+/// File extensions read when ingesting a directory.
+const TEXT_EXTENSIONS: &[&str] = &[
+    "md", "markdown", "mdx", "txt", "rst", "adoc", "org", "html", "htm",
+];
+
+/// Tables of `state/kb.db`.
+pub static SCHEMA: Schema = Schema {
+    name: "kb",
+    path: "state/kb.db",
+    migrations: &[Migration {
+        version: 1,
+        description: "create kb_documents, kb_chunks and kb_chunks_fts",
+        step: Step::Sql(
+            "CREATE TABLE IF NOT EXISTS kb_documents (
+                 id           INTEGER PRIMARY KEY,
+                 source       TEXT NOT NULL UNIQUE,
+                 title        TEXT NOT NULL,
+                 content_hash TEXT NOT NULL,
+                 chunks       INTEGER NOT NULL,
+                 embedded     INTEGER NOT NULL,
+                 ingested_at  INTEGER NOT NULL
+             );
+             CREATE TABLE IF NOT EXISTS kb_chunks (
+                 id          INTEGER PRIMARY KEY,
+                 document_id INTEGER NOT NULL,
+                 position    INTEGER NOT NULL,
+                 heading     TEXT,
+                 content     TEXT NOT NULL,
+                 embedding   BLOB
+             );
+             CREATE INDEX IF NOT EXISTS idx_kb_chunks_document ON kb_chunks(document_id);
+             CREATE VIRTUAL TABLE IF NOT EXISTS kb_chunks_fts USING fts5(
+                 content, content=kb_chunks, content_rowid=id
+             );
+             CREATE TRIGGER IF NOT EXISTS kb_chunks_ai AFTER INSERT ON kb_chunks BEGIN
+                 INSERT INTO kb_chunks_fts(rowid, content) VALUES (new.id, new.content);
+             END;
+             CREATE TRIGGER IF NOT EXISTS kb_chunks_ad AFTER DELETE ON kb_chunks BEGIN
+                 INSERT INTO kb_chunks_fts(kb_chunks_fts, rowid, content)
+                 VALUES ('delete', old.id, old.content);
+             END;",
+        ),
+    }],
+};
+
+/// One ingested document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KbDocument {
+    pub source: String,
+    pub title: String,
+    pub chunks: usize,
+    /// Whether the chunks carry embeddings (false with `embedding_provider = "none"`).
+    pub embedded: bool,
+    pub ingested_at: i64,
+}
+
+/// A retrieved chunk.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KbHit {
+    pub source: String,
+    pub title: String,
+    pub heading: Option<String>,
+    pub content: String,
+    /// Hybrid relevance, 0.0-1.0.
+    pub score: f32,
+}
+
+/// What ingesting one document did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestStatus {
+    Added,
+    Updated,
+    /// Same content and settings as the stored copy; nothing was re-embedded.
+    Unchanged,
+    /// The file is gone from an ingested directory.
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IngestReport {
+    pub source: String,
+    pub status: IngestStatus,
+    pub chunks: usize,
+}
+
+/// Chunked documents in `state/kb.db` and the embedder that indexes them.
+pub struct KnowledgeBase {
+    conn: Mutex<Connection>,
+    embedder: Arc<dyn EmbeddingProvider>,
+    settings: KbConfig,
+    vector_weight: f32,
+    keyword_weight: f32,
+}
+
+impl KnowledgeBase {
+    pub fn open(
+        workspace_dir: &Path,
+        embedder: Arc<dyn EmbeddingProvider>,
+        settings: KbConfig,
+        vector_weight: f32,
+        keyword_weight: f32,
+    ) -> Result<Self> {
+        let db_path = SCHEMA.db_path(workspace_dir);
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        migrate(&conn, &SCHEMA)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            embedder,
+            settings,
+            vector_weight,
+            keyword_weight,
+        })
+    }
+
+    /// The knowledge base of `config`, embedding with the `[memory]`
+    /// settings. Opened whether or not `[kb].enabled` is set, so documents
+    /// can be ingested before the agent starts using them.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let embedder = crate::memory::create_embedder(
+            &config.memory,
+            &config.embedding_routes,
+            config.api_key.as_deref(),
+        );
+        #[allow(clippy::cast_possible_truncation)]
+        Self::open(
+            &config.workspace_dir,
+            embedder,
+            config.kb.clone(),
+            config.memory.vector_weight as f32,
+            config.memory.keyword_weight as f32,
+        )
+    }
+
+    /// [`Self::from_config`] when `[kb].enabled` is set. Failures are logged
+    /// and leave the agent without a knowledge base.
+    pub fn if_enabled(config: &Config) -> Option<Arc<Self>> {
+        if !config.kb.enabled {
+            return None;
+        }
+        match Self::from_config(config) {
+            Ok(kb) => Some(Arc::new(kb)),
+            Err(e) => {
+                tracing::warn!("knowledge base unavailable: {e:#}");
+                None
+            }
+        }
+    }
+
+    pub fn settings(&self) -> &KbConfig {
+        &self.settings
+    }
+
+    /// Store `text` as the document `source`, replacing an older copy.
+    pub async fn ingest_text(&self, source: &str, title: &str, text: &str) -> Result<IngestReport> {
+        let hash = self.content_hash(text);
+        let existing: Option<(String, usize)> = self
+            .conn
+            .lock()
+            .query_row(
+                "SELECT content_hash, chunks FROM kb_documents WHERE source = ?1",
+                params![source],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        if let Some((stored_hash, chunks)) = &existing {
+            if *stored_hash == hash {
+                return Ok(IngestReport {
+                    source: source.to_string(),
+                    status: IngestStatus::Unchanged,
+                    chunks: *chunks,
+                });
+            }
+        }
+
+        let chunks = chunk_markdown(text, self.settings.chunk_tokens);
+        let embeddings = if self.embedder.dimensions() == 0 {
+            Vec::new()
+        } else {
+            let mut embeddings = Vec::with_capacity(chunks.len());
+            for batch in chunks.chunks(EMBED_BATCH) {
+                let texts: Vec<&str> = batch.iter().map(|chunk| chunk.content.as_str()).collect();
+                let vectors = self
+                    .embedder
+                    .embed(&texts)
+                    .await
+                    .with_context(|| format!("failed to embed {source}"))?;
+                if vectors.len() != texts.len() {
+                    anyhow::bail!(
+                        "embedding provider returned {} vectors for {} chunks",
+                        vectors.len(),
+                        texts.len()
+                    );
+                }
+                embeddings.extend(vectors);
+            }
+            embeddings
+        };
+
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM kb_chunks WHERE document_id =
+                 (SELECT id FROM kb_documents WHERE source = ?1)",
+            params![source],
+        )?;
+        tx.execute(
+            "INSERT INTO kb_documents (source, title, content_hash, chunks, embedded, ingested_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(source) DO UPDATE SET
+                 title = excluded.title,
+                 content_hash = excluded.content_hash,
+                 chunks = excluded.chunks,
+                 embedded = excluded.embedded,
+                 ingested_at = excluded.ingested_at",
+            params![
+                source,
+                title,
+                hash,
+                chunks.len(),
+                !embeddings.is_empty(),
+                Utc::now().timestamp()
+            ],
+        )?;
+        let document_id: i64 = tx.query_row(
+            "SELECT id FROM kb_documents WHERE source = ?1",
+            params![source],
+            |row| row.get(0),
+        )?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO kb_chunks (document_id, position, heading, content, embedding)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            for chunk in &chunks {
+                insert.execute(params![
+                    document_id,
+                    chunk.index,
+                    chunk.heading.as_deref(),
+                    chunk.content,
+                    embeddings
+                        .get(chunk.index)
+                        .map(|embedding| vector::vec_to_bytes(embedding)),
+                ])?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(IngestReport {
+            source: source.to_string(),
+            status: if existing.is_some() {
+                IngestStatus::Updated
+            } else {
+                IngestStatus::Added
+            },
+            chunks: chunks.len(),
+        })
+    }
+
+    /// Ingest a file, a directory (recursively, text formats only) or an
+    /// http(s) URL. Files that disappeared from an ingested directory are
+    /// dropped. Relative paths are resolved against `workspace_dir`.
+    pub async fn ingest(&self, source: &str, workspace_dir: &Path) -> Result<Vec<IngestReport>> {
+        let source = source.trim();
+        if source.starts_with("https://") || source.starts_with("http://") {
+            let (title, text) = fetch_url(source).await?;
+            return Ok(vec![self.ingest_text(source, &title, &text).await?]);
+        }
+
+        let path = workspace_dir.join(source);
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("{} does not exist", path.display()))?;
+        if path.is_file() {
+            let (title, text) = read_file(&path)?;
+            let source = path.display().to_string();
+            return Ok(vec![self.ingest_text(&source, &title, &text).await?]);
+        }
+
+        let mut files = Vec::new();
+        collect_files(&path, &mut files)?;
+        files.sort();
+        let mut reports = Vec::with_capacity(files.len());
+        let mut current = HashSet::new();
+        for file in files {
+            let source = file.display().to_string();
+            match read_file(&file) {
+                Ok((title, text)) => {
+                    reports.push(self.ingest_text(&source, &title, &text).await?);
+                }
+                Err(e) => tracing::warn!("kb: skipping {source}: {e:#}"),
+            }
+            current.insert(source);
+        }
+        let prefix = format!("{}{}", path.display(), std::path::MAIN_SEPARATOR);
+        for document in self.documents()? {
+            if document.source.starts_with(&prefix) && !current.contains(&document.source) {
+                self.remove(&document.source)?;
+                reports.push(IngestReport {
+                    source: document.source,
+                    status: IngestStatus::Removed,
+                    chunks: 0,
+                });
+            }
+        }
+        Ok(reports)
+    }
+
+    /// Remove `source`, or every document under it when it is a directory.
+    /// Returns how many documents were removed.
+    pub fn remove(&self, source: &str) -> Result<usize> {
+        let source = source.trim().trim_end_matches(std::path::MAIN_SEPARATOR);
+        let prefix = format!("{source}{}%", std::path::MAIN_SEPARATOR);
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM kb_chunks WHERE document_id IN
+                 (SELECT id FROM kb_documents WHERE source = ?1 OR source LIKE ?2)",
+            params![source, prefix],
+        )?;
+        let removed = tx.execute(
+            "DELETE FROM kb_documents WHERE source = ?1 OR source LIKE ?2",
+            params![source, prefix],
+        )?;
+        tx.commit()?;
+        Ok(removed)
+    }
+
+    pub fn documents(&self) -> Result<Vec<KbDocument>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT source, title, chunks, embedded, ingested_at
+             FROM kb_documents ORDER BY source",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(KbDocument {
+                source: row.get(0)?,
+                title: row.get(1)?,
+                chunks: row.get(2)?,
+                embedded: row.get(3)?,
+                ingested_at: row.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// The `limit` chunks most relevant to `query`, best first.
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<KbHit>> {
+        let query = query.trim();
+        if query.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+        let query_embedding = if self.embedder.dimensions() == 0 {
+            None
+        } else {
+            match self.embedder.embed_one(query).await {
+                Ok(embedding) => Some(embedding),
+                Err(e) => {
+                    tracing::warn!("kb: query embedding failed, using keywords only: {e:#}");
+                    None
+                }
+            }
+        };
+
+        let conn = self.conn.lock();
+        let keyword = keyword_search(&conn, query, limit * 4)?;
+        let semantic = match &query_embedding {
+            Some(embedding) => vector_search(&conn, embedding, limit * 4)?,
+            None => Vec::new(),
+        };
+        // Without vectors the keyword score is the whole score, so it still
+        // spans 0.0-1.0 and `kb.min_score` means the same thing.
+        let (vector_weight, keyword_weight) = if semantic.is_empty() {
+            (0.0, 1.0)
+        } else {
+            (self.vector_weight, self.keyword_weight)
+        };
+        let merged =
+            vector::hybrid_merge(&semantic, &keyword, vector_weight, keyword_weight, limit);
+
+        let mut stmt = conn.prepare(
+            "SELECT d.source, d.title, c.heading, c.content
+             FROM kb_chunks c JOIN kb_documents d ON d.id = c.document_id
+             WHERE c.id = ?1",
+        )?;
+        let mut hits = Vec::with_capacity(merged.len());
+        for result in merged {
+            let Ok(id) = result.id.parse::<i64>() else {
+                continue;
+            };
+            let hit = stmt
+                .query_row(params![id], |row| {
+                    Ok(KbHit {
+                        source: row.get(0)?,
+                        title: row.get(1)?,
+                        heading: row.get(2)?,
+                        content: row.get(3)?,
+                        score: result.final_score,
+                    })
+                })
+                .optional()?;
+            hits.extend(hit);
+        }
+        Ok(hits)
+    }
+
+    /// Passages to put ahead of `user_msg`, or an empty string when
+    /// `kb.auto_inject` is off or nothing scores above `kb.min_score`.
+    pub async fn context_for(&self, user_msg: &str) -> String {
+        if !self.settings.auto_inject {
+            return String::new();
+        }
+        match self.search(user_msg, self.settings.top_k).await {
+            Ok(hits) => {
+                format_context(&hits, self.settings.min_score, self.settings.context_tokens)
+            }
+            Err(e) => {
+                tracing::warn!("kb: retrieval failed: {e:#}");
+                String::new()
+            }
+        }
+    }
+
+    /// Fingerprint of a document under the current chunking and embedding
+    /// settings, so changing either re-ingests unchanged files.
+    fn content_hash(&self, text: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(
+            format!(
+                "{}:{}:{}\n",
+                self.settings.chunk_tokens,
+                self.embedder.name(),
+                self.embedder.dimensions()
+            )
+            .as_bytes(),
+        );
+        hasher.update(text.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+fn keyword_search(conn: &Connection, query: &str, limit: usize) -> Result<Vec<(String, f32)>> {
+    let fts_query = query
+        .split_whitespace()
+        .map(|word| word.replace('"', ""))
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{word}\""))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    if fts_query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut stmt = conn.prepare(
+        "SELECT rowid, bm25(kb_chunks_fts) AS score FROM kb_chunks_fts
+         WHERE kb_chunks_fts MATCH ?1 ORDER BY score LIMIT ?2",
+    )?;
+    let rows = stmt.query_map(params![fts_query, limit], |row| {
+        let id: i64 = row.get(0)?;
+        let score: f64 = row.get(1)?;
+        // BM25 is negative, lower is better.
+        #[allow(clippy::cast_possible_truncation)]
+        Ok((id.to_string(), (-score) as f32))
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+fn vector_search(conn: &Connection, query: &[f32], limit: usize) -> Result<Vec<(String, f32)>> {
+    let mut stmt =
+        conn.prepare("SELECT id, embedding FROM kb_chunks WHERE embedding IS NOT NULL")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, Vec<u8>>(1)?))
+    })?;
+    let mut scored = Vec::new();
+    for row in rows {
+        let (id, blob) = row?;
+        let similarity = vector::cosine_similarity(query, &vector::bytes_to_vec(&blob));
+        if similarity > 0.0 {
+            scored.push((id.to_string(), similarity));
+        }
+    }
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.truncate(limit);
+    Ok(scored)
+}
+
+/// `[Knowledge base]` block of the hits scoring at least `min_score`, cut
+/// off once roughly `budget_tokens` (4 characters each) are used.
+fn format_context(hits: &[KbHit], min_score: f64, budget_tokens: usize) -> String {
+    let budget = budget_tokens.saturating_mul(4);
+    let mut context = String::new();
+    let mut used = 0;
+    for hit in hits.iter().filter(|hit| f64::from(hit.score) >= min_score) {
+        let header = match &hit.heading {
+            Some(heading) => format!(
+                "--- {} ({}) › {} ---\n",
+                hit.title,
+                hit.source,
+                heading.trim_start_matches('#').trim()
+            ),
+            None => format!("--- {} ({}) ---\n", hit.title, hit.source),
+        };
+        let remaining = budget.saturating_sub(used + header.chars().count());
+        if remaining < 80 {
+            break;
+        }
+        let content = truncate_with_ellipsis(&hit.content, remaining);
+        used += header.chars().count() + content.chars().count();
+        if context.is_empty() {
+            context.push_str("[Knowledge base]\n");
+        }
+        let _ = writeln!(context, "{header}{content}");
+    }
+    if !context.is_empty() {
+        context.push('\n');
+    }
+    context
+}
+
+fn is_text_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| TEXT_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+fn is_html(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"))
+}
+
+/// Text files under `dir`, skipping hidden entries and symlinks.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?
+    {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        if file_type.is_dir() {
+            collect_files(&path, files)?;
+        } else if file_type.is_file() && is_text_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Title and text of a local file.
+fn read_file(path: &Path) -> Result<(String, String)> {
+    let size = std::fs::metadata(path)?.len();
+    if size > MAX_DOCUMENT_BYTES as u64 {
+        anyhow::bail!(
+            "{} is larger than {MAX_DOCUMENT_BYTES} bytes",
+            path.display()
+        );
+    }
+    let raw = String::from_utf8_lossy(&std::fs::read(path)?).into_owned();
+    let fallback = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if is_html(path) {
+        let title = html_title(&raw).unwrap_or(fallback);
+        return Ok((title, nanohtml2text::html2text(&raw)));
+    }
+    Ok((markdown_title(&raw).unwrap_or(fallback), raw))
+}
+
+/// Title and text of a web page or plain-text URL.
+async fn fetch_url(url: &str) -> Result<(String, String)> {
+    let client = crate::config::build_runtime_proxy_client_with_timeouts(
+        "memory.kb",
+        FETCH_TIMEOUT_SECS,
+        10,
+    );
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("failed to fetch {url}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("{url} returned {status}");
+    }
+    let is_html = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("html"));
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > MAX_DOCUMENT_BYTES {
+            anyhow::bail!("{url} is larger than {MAX_DOCUMENT_BYTES} bytes");
+        }
+        body.extend_from_slice(&chunk);
+    }
+    let raw = String::from_utf8_lossy(&body).into_owned();
+    if is_html {
+        let title = html_title(&raw).unwrap_or_else(|| url.to_string());
+        return Ok((title, nanohtml2text::html2text(&raw)));
+    }
+    Ok((markdown_title(&raw).unwrap_or_else(|| url.to_string()), raw))
+}
+
+fn markdown_title(text: &str) -> Option<String> {
+    text.lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+}
+
+fn html_title(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let start = lower.find("<title")?;
+    let start = start + lower[start..].find('>')? + 1;
+    let end = start + lower[start..].find("</title>")?;
+    let title = nanohtml2text::html2text(&html[start..end]);
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!title.is_empty()).then_some(title)
+}
+
+pub async fn handle_command(command: crate::KbCommands, config: &Config) -> Result<()> {
+    let kb = KnowledgeBase::from_config(config)?;
+    match command {
+        crate::KbCommands::Ingest { sources, json } => {
+            let sources = if sources.is_empty() {
+                config.kb.sources.clone()
+            } else {
+                sources
+            };
+            if sources.is_empty() {
+                anyhow::bail!("nothing to ingest: pass paths or URLs, or set kb.sources");
+            }
+            let mut reports = Vec::new();
+            for source in &sources {
+                reports.extend(kb.ingest(source, &config.workspace_dir).await?);
+            }
+            if json {
+                println!("{}", serde_json::to_string_pretty(&reports)?);
+                return Ok(());
+            }
+            for report in &reports {
+                let status = match report.status {
+                    IngestStatus::Added => "added",
+                    IngestStatus::Updated => "updated",
+                    IngestStatus::Unchanged => "unchanged",
+                    IngestStatus::Removed => "removed",
+                };
+                println!(
+                    "{status:<10} {:>5} chunks  {}",
+                    report.chunks, report.source
+                );
+            }
+            if kb.embedder.dimensions() == 0 {
+                println!(
+                    "No embedding provider is configured ([memory].embedding_provider); retrieval uses keywords only."
+                );
+            }
+            if !config.kb.enabled {
+                println!("Set [kb].enabled = true to let the agent use these documents.");
+            }
+            Ok(())
+        }
+        crate::KbCommands::List { json } => {
+            let documents = kb.documents()?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&documents)?);
+                return Ok(());
+            }
+            if documents.is_empty() {
+                println!("The knowledge base is empty. Add documents with `zeroclaw kb ingest <path|url>`.");
+                return Ok(());
+            }
+            println!(
+                "{:>6} {:<9} {:<25} SOURCE",
+                "CHUNKS", "EMBEDDED", "INGESTED"
+            );
+            for document in documents {
+                let ingested = chrono::DateTime::from_timestamp(document.ingested_at, 0)
+                    .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                println!(
+                    "{:>6} {:<9} {:<25} {}",
+                    document.chunks,
+                    if document.embedded { "yes" } else { "no" },
+                    ingested,
+                    document.source
+                );
+            }
+            Ok(())
+        }
+        crate::KbCommands::Remove { source } => {
+            let removed = kb.remove(&source)?;
+            if removed == 0 {
+                anyhow::bail!("no document matches {source}");
+            }
+            println!("Removed {removed} document(s).");
+            Ok(())
+        }
+        crate::KbCommands::Search { query, limit, json } => {
+            let hits = kb.search(&query, limit).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&hits)?);
+                return Ok(());
+            }
+            if hits.is_empty() {
+                println!("No matching passages.");
+            }
+            for hit in hits {
+                println!("[{:.2}] {} ({})", hit.score, hit.title, hit.source);
+                println!("{}\n", truncate_with_ellipsis(&hit.content, 400));
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::embeddings::NoopEmbedding;
+
+    fn open(tmp: &Path) -> KnowledgeBase {
+        KnowledgeBase::open(
+            tmp,
+            Arc::new(NoopEmbedding),
+            KbConfig {
+                chunk_tokens: 40,
+                ..KbConfig::default()
+            },
+            0.7,
+            0.3,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn ingest_skips_unchanged_documents_and_prunes_deleted_files() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let docs = tmp.path().join("docs");
+        std::fs::create_dir_all(docs.join(".git")).unwrap();
+        std::fs::write(
+            docs.join("vpn.md"),
+            "# VPN\n\nConnect with the WireGuard profile.",
+        )
+        .unwrap();
+        std::fs::write(docs.join("notes.txt"), "Payroll runs on the 25th.").unwrap();
+        std::fs::write(docs.join(".git").join("config.md"), "hidden").unwrap();
+        std::fs::write(docs.join("logo.png"), [0u8, 1, 2]).unwrap();
+        let kb = open(tmp.path());
+
+        let reports = kb.ingest("docs", tmp.path()).await.unwrap();
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|r| r.status == IngestStatus::Added));
+        let documents = kb.documents().unwrap();
+        assert_eq!(documents[1].title, "VPN");
+        assert!(!documents[1].embedded);
+
+        std::fs::remove_file(docs.join("notes.txt")).unwrap();
+        let reports = kb.ingest("docs", tmp.path()).await.unwrap();
+        let statuses: Vec<_> = reports.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![IngestStatus::Unchanged, IngestStatus::Removed]
+        );
+        assert_eq!(kb.documents().unwrap().len(), 1);
+
+        assert_eq!(
+            kb.remove(&docs.canonicalize().unwrap().display().to_string())
+                .unwrap(),
+            1
+        );
+        assert!(kb.documents().unwrap().is_empty());
+        assert!(kb.ingest("missing", tmp.path()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn search_ranks_keyword_matches_and_context_respects_budget() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let kb = open(tmp.path());
+        kb.ingest_text(
+            "handbook",
+            "Handbook",
+            "# Handbook\n\n## Expenses\n\nSubmit expense reports within 30 days.\n\n## Travel\n\nBook flights through the travel portal.",
+        )
+        .await
+        .unwrap();
+
+        let hits = kb.search("expense reports", 3).await.unwrap();
+        assert_eq!(hits[0].heading.as_deref(), Some("## Expenses"));
+        assert!((hits[0].score - 1.0).abs() < f32::EPSILON);
+        assert!(kb.search("  ", 3).await.unwrap().is_empty());
+
+        let context = format_context(&hits, 0.35, 800);
+        assert!(context.starts_with("[Knowledge base]\n--- Handbook (handbook) › Expenses ---\n"));
+        assert!(context.contains("within 30 days"));
+        assert!(format_context(&hits, 0.35, 10).is_empty());
+        assert!(format_context(&hits, 1.1, 800).is_empty());
+    }
+}
@@ -40,6 +40,7 @@ use clap::Subcommand;
 use serde::{Deserialize, Serialize};
 
 pub mod agent;
+pub(crate) mod analytics;
 pub(crate) mod approval;
 pub(crate) mod auth;
 pub mod channels;
@@ -49,19 +50,24 @@ pub(crate) mod cost;
 pub(crate) mod cron;
 pub mod economic;
 pub(crate) mod daemon;
+pub(crate) mod db;
 pub(crate) mod doctor;
+pub(crate) mod feeds;
 pub mod gateway;
 pub mod goals;
 pub(crate) mod hardware;
 pub(crate) mod health;
 pub(crate) mod heartbeat;
 pub mod hooks;
+pub(crate) mod i18n;
 pub(crate) mod identity;
+pub(crate) mod kb;
 // Intentionally unused re-export — public API surface for plugin authors.
 pub(crate) mod integrations;
 pub mod memory;
 pub(crate) mod migration;
 pub(crate) mod multimodal;
+pub(crate) mod notify;
 pub mod observability;
 pub(crate) mod onboard;
 pub mod peripherals;
@@ -77,6 +83,8 @@ pub mod tools;
 pub(crate) mod tunnel;
 pub mod update;
 pub(crate) mod util;
+pub(crate) mod workflows;
+pub(crate) mod workspace;
 
 pub use config::Config;
 
@@ -144,6 +152,30 @@ Examples:
         /// Telegram identity to allow (username without '@' or numeric user ID)
         identity: String,
     },
+    /// Inspect the outbound reply queue (dead letters by default)
+    #[command(long_about = "\
+Inspect the outbound reply queue.
+
+Lists replies recorded in <workspace>/state/channel_outbox.db. Replies \
+that used up reliability.outbound_max_attempts are dead letters; \
+--retry queues one for immediate delivery by the running daemon and \
+--discard drops it.
+
+Examples:
+  zeroclaw channel outbox
+  zeroclaw channel outbox --status all
+  zeroclaw channel outbox --retry 42")]
+    Outbox {
+        /// Status to list: dead, pending, delivered or all
+        #[arg(long, default_value = "dead")]
+        status: String,
+        /// Queue an undelivered reply for immediate delivery
+        #[arg(long, conflicts_with = "discard")]
+        retry: Option<i64>,
+        /// Drop an undelivered reply
+        #[arg(long)]
+        discard: Option<i64>,
+    },
 }
 
 /// Skills management subcommands
@@ -356,6 +388,233 @@ pub enum MemoryCommands {
         #[arg(long)]
         yes: bool,
     },
+    /// Delete entries past their `[memory.decay]` TTL
+    Prune {
+        /// List expired entries without deleting them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Channel session subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SessionCommands {
+    /// List stored sessions with titles, tags, message counts, last activity and token usage
+    List {
+        /// Only list sessions of this channel (e.g. telegram)
+        #[arg(long)]
+        channel: Option<String>,
+        /// Only list sessions with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Print rows as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show a session's stats and messages
+    Show {
+        /// Session key (e.g. telegram_alice)
+        session: String,
+    },
+    /// Delete a session with its checkpoints and pinned messages
+    Delete {
+        /// Session key
+        session: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Add tags to a session, or remove them with --remove
+    Tag {
+        /// Session key (e.g. telegram_alice)
+        session: String,
+        /// Tags (letters, digits, '_', '-', '.')
+        #[arg(required = true)]
+        tags: Vec<String>,
+        /// Remove the tags instead of adding them
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Export a session as JSON
+    Export {
+        /// Session key
+        session: String,
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Snapshot a session as a named checkpoint
+    Checkpoint {
+        /// Session key (e.g. telegram_alice)
+        session: String,
+        /// Checkpoint name
+        name: String,
+        /// Keep only the first N messages of the session
+        #[arg(long)]
+        messages: Option<usize>,
+    },
+    /// Start a new session from a checkpoint
+    Fork {
+        /// Session key the checkpoint was taken from
+        session: String,
+        /// Checkpoint name
+        checkpoint: String,
+        /// Key for the new session
+        new_session: String,
+    },
+}
+
+/// Token usage subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum UsageCommands {
+    /// Show token usage and cost aggregated by a dimension
+    Report {
+        /// Group by: day, session, channel, principal, model
+        #[arg(long, default_value = "day")]
+        by: String,
+        /// Only include usage on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Print rows as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the per-channel activity digest of one UTC day
+    Digest {
+        /// Day to summarize (YYYY-MM-DD); defaults to yesterday
+        #[arg(long)]
+        day: Option<String>,
+    },
+}
+
+/// Workflow subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WorkflowCommands {
+    /// List workflows defined in the workspace
+    List,
+    /// Run a workflow now
+    Run {
+        /// Workflow name
+        name: String,
+    },
+    /// Show recent workflow runs from the execution log
+    Runs {
+        /// Only show runs of this workflow
+        name: Option<String>,
+        /// Maximum number of runs to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+}
+
+/// Tool subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ToolCommands {
+    /// List registered tools with risk tier and approval policy
+    List {
+        /// Print rows as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Run a tool directly, without the model
+    Exec {
+        /// Tool name
+        name: String,
+        /// Tool arguments as a JSON object
+        #[arg(long, default_value = "{}")]
+        args: String,
+    },
+}
+
+/// Undo subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum UndoCommands {
+    /// List turns with actions that can still be undone, newest first
+    List {
+        /// Maximum number of turns to show
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        /// Print rows as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Revert the latest turn that changed files, commits or memory
+    Last,
+    /// Revert a specific turn by id
+    Turn {
+        /// Turn id (as shown by `zeroclaw undo list`)
+        id: String,
+    },
+}
+
+/// Debug subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DebugCommands {
+    /// Show recent webhook bodies archived by the gateway, newest first
+    Payloads {
+        /// Channel name (e.g. whatsapp, linq, wati, nextcloud_talk, qq)
+        channel: String,
+        /// Maximum number of payloads to show
+        #[arg(long, default_value_t = 5)]
+        limit: usize,
+        /// Print entries as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Database subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DbCommands {
+    /// Purge expired sessions, checkpoint the WAL and vacuum sessions.db
+    Maintain {
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Apply pending schema migrations to the workspace databases
+    Migrate {
+        /// List pending migrations without applying them
+        #[arg(long)]
+        dry_run: bool,
+        /// Print the result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Knowledge base subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum KbCommands {
+    /// Add or refresh files, directories and URLs (default: kb.sources)
+    Ingest {
+        /// Paths (relative to the workspace or absolute) or http(s) URLs
+        sources: Vec<String>,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List ingested documents
+    List {
+        /// Print rows as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Remove a document, or every document under a directory
+    Remove {
+        /// Source as shown by `zeroclaw kb list`
+        source: String,
+    },
+    /// Show the passages retrieved for a query
+    Search {
+        query: String,
+        /// Maximum number of passages
+        #[arg(long, default_value_t = 5)]
+        limit: usize,
+        /// Print passages as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 /// Integration subcommands
@@ -37,10 +37,27 @@ use anyhow::{bail, Context, Result};
 use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use dialoguer::{Input, Password};
 use serde::{Deserialize, Serialize};
-use std::io::Write;
+use std::io::{IsTerminal, Read, Write};
 use tracing::{info, warn};
 use tracing_subscriber::{fmt, EnvFilter};
 
+/// Prompt for `zeroclaw run`: the argument itself, or stdin when it is `-`.
+fn read_run_prompt(arg: &str) -> Result<String> {
+    let prompt = if arg == "-" {
+        let mut input = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)
+            .context("Failed to read prompt from stdin")?;
+        input
+    } else {
+        arg.to_string()
+    };
+    let prompt = prompt.trim();
+    if prompt.is_empty() {
+        bail!("Prompt is empty");
+    }
+    Ok(prompt.to_string())
+}
+
 fn parse_temperature(s: &str) -> std::result::Result<f64, String> {
     let t: f64 = s.parse().map_err(|e| format!("{e}"))?;
     if !(0.0..=2.0).contains(&t) {
@@ -50,6 +67,7 @@ fn parse_temperature(s: &str) -> std::result::Result<f64, String> {
 }
 
 mod agent;
+mod analytics;
 mod approval;
 mod auth;
 mod channels;
@@ -61,18 +79,23 @@ mod coordination;
 mod cost;
 mod cron;
 mod daemon;
+mod db;
 mod doctor;
+mod feeds;
 mod gateway;
 mod goals;
 mod hardware;
 mod health;
 mod heartbeat;
 mod hooks;
+mod i18n;
 mod identity;
 mod integrations;
+mod kb;
 mod memory;
 mod migration;
 mod multimodal;
+mod notify;
 mod observability;
 mod onboard;
 mod peripherals;
@@ -86,13 +109,16 @@ mod tools;
 mod tunnel;
 mod update;
 mod util;
+mod workflows;
+mod workspace;
 
 use config::Config;
 
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
-    ChannelCommands, CronCommands, HardwareCommands, IntegrationCommands, MigrateCommands,
-    PeripheralCommands, ServiceCommands, SkillCommands,
+    ChannelCommands, CronCommands, DbCommands, DebugCommands, HardwareCommands,
+    IntegrationCommands, KbCommands, MigrateCommands, PeripheralCommands, ServiceCommands,
+    SessionCommands, SkillCommands, ToolCommands, UndoCommands, UsageCommands, WorkflowCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -131,6 +157,10 @@ struct Cli {
     #[arg(long, global = true)]
     config_dir: Option<String>,
 
+    /// Config profile to layer on top of config.toml (`profiles/<name>.toml`)
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -230,6 +260,69 @@ Examples:
         memory_backend: Option<String>,
     },
 
+    /// Open an interactive chat session in the terminal
+    #[command(long_about = "\
+Open an interactive chat session in the terminal.
+
+A readline REPL with persistent input history (stored as chat_history in \
+the config directory) and slash commands: /tools, /model [name], \
+/approve [pending|<tool>], /session clear, /help and /quit.
+
+Examples:
+  zeroclaw chat
+  zeroclaw chat -p anthropic --model claude-sonnet-4-20250514")]
+    Chat {
+        /// Provider to use (openrouter, anthropic, openai, openai-codex)
+        #[arg(short, long)]
+        provider: Option<String>,
+
+        /// Model to use
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Temperature (0.0 - 2.0)
+        #[arg(short, long, default_value = "0.7", value_parser = parse_temperature)]
+        temperature: f64,
+    },
+
+    /// Run a single agent turn and print only the final answer
+    #[command(long_about = "\
+Run a single agent turn and print only the final answer.
+
+Meant for shell scripts and CI: the prompt is read from stdin when given \
+as '-', only the answer is written to stdout (logs go to stderr, \
+warnings and above unless RUST_LOG is set), and the exit code is \
+non-zero when the turn fails.
+
+Examples:
+  echo \"Summarize CHANGELOG.md\" | zeroclaw run -
+  git diff | zeroclaw run - --tools file_read,memory_recall
+  zeroclaw run \"What time is it in Tokyo?\" --no-tools")]
+    Run {
+        /// Prompt text, or '-' to read it from stdin
+        prompt: String,
+
+        /// Provider to use (openrouter, anthropic, openai, openai-codex)
+        #[arg(short, long)]
+        provider: Option<String>,
+
+        /// Model to use
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Temperature (0.0 - 2.0)
+        #[arg(short, long, value_parser = parse_temperature)]
+        temperature: Option<f64>,
+
+        /// Only allow these tools (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',', conflicts_with = "no_tools")]
+        tools: Vec<String>,
+
+        /// Run without any tools
+        #[arg(long)]
+        no_tools: bool,
+    },
+
     /// Start the gateway server (webhooks, websockets)
     #[command(long_about = "\
 Start the gateway server (webhooks, websockets).
@@ -284,6 +377,32 @@ Examples:
         host: Option<String>,
     },
 
+    /// Answer the running daemon's tool approval prompts from this terminal
+    #[command(long_about = "\
+Answer the running daemon's tool approval prompts from this terminal.
+
+Connects to the daemon and shows every approval it raises, on any \
+channel, as a [Y]es / [N]o / [A]lways prompt; the first answer (here or \
+in the channel) wins. Lets the daemon run under systemd while approvals \
+happen in an interactive terminal.
+
+By default it connects to the local socket next to the config file \
+(attach.sock). With --gateway it connects to GET /ws/attach on a \
+remote gateway, which requires pairing and a bearer token.
+
+Examples:
+  zeroclaw attach
+  zeroclaw attach --gateway http://10.0.0.5:42617 --token <TOKEN>")]
+    Attach {
+        /// Gateway base URL to attach through instead of the local socket
+        #[arg(long)]
+        gateway: Option<String>,
+
+        /// Paired bearer token for --gateway
+        #[arg(long, requires = "gateway")]
+        token: Option<String>,
+    },
+
     /// Manage OS service lifecycle (launchd/systemd user service)
     Service {
         /// Init system to use: auto (detect), systemd, or openrc
@@ -294,8 +413,12 @@ Examples:
         service_command: ServiceCommands,
     },
 
-    /// Run diagnostics for daemon/scheduler/channel freshness
+    /// Run diagnostics, then live provider/channel/database/clock checks
     Doctor {
+        /// Skip live checks (provider, channel credentials, databases, clock skew)
+        #[arg(long)]
+        offline: bool,
+
         #[command(subcommand)]
         doctor_command: Option<DoctorCommands>,
     },
@@ -480,12 +603,179 @@ Examples:
   zeroclaw memory list
   zeroclaw memory list --category core --limit 10
   zeroclaw memory get <key>
-  zeroclaw memory clear --category conversation --yes")]
+  zeroclaw memory clear --category conversation --yes
+  zeroclaw memory prune --dry-run")]
     Memory {
         #[command(subcommand)]
         memory_command: MemoryCommands,
     },
 
+    /// Manage channel sessions (list, show, tag, delete, export, checkpoint, fork)
+    #[command(long_about = "\
+Manage channel conversation sessions.
+
+Sessions are keyed by channel and sender (for example \
+`telegram_alice`). list shows titles (from the first message), \
+message counts, last activity, token usage (when [cost] is enabled) \
+and tags; show prints a transcript; tag labels sessions for \
+list --tag; export writes it as JSON. A checkpoint freezes a copy of a session; \
+fork starts a new session from it, so different instructions can be \
+tried from a known-good state. Requires session.backend = \"sqlite\" \
+or \"redis\".
+
+Examples:
+  zeroclaw session list --channel telegram
+  zeroclaw session show telegram_alice
+  zeroclaw session export telegram_alice -o alice.json
+  zeroclaw session delete telegram_alice --yes
+  zeroclaw session tag telegram_alice travel q3
+  zeroclaw session list --tag travel
+  zeroclaw session checkpoint telegram_alice baseline
+  zeroclaw session checkpoint telegram_alice early --messages 4
+  zeroclaw session fork telegram_alice baseline telegram_alice_trial")]
+    Session {
+        #[command(subcommand)]
+        session_command: SessionCommands,
+    },
+
+    /// Report token usage and cost (by day, session, channel, principal, model)
+    #[command(long_about = "\
+Report token usage and cost recorded per LLM call.
+
+Every call made by the agent is recorded in the workspace usage \
+ledger when [cost].enabled = true, tagged with its session, channel \
+and principal (`<channel>:<sender>`). Token counts the provider does \
+not report are estimated and flagged as such.
+
+Examples:
+  zeroclaw usage report
+  zeroclaw usage report --by principal --since 2026-10-01
+  zeroclaw usage report --by model --json")]
+    Usage {
+        #[command(subcommand)]
+        usage_command: UsageCommands,
+    },
+
+    /// Run and inspect multi-step workflows
+    #[command(long_about = "\
+Run and inspect multi-step workflows.
+
+Workflows are TOML files in <workspace>/workflows/. Each step runs \
+as its own agent turn with optional tool restrictions, success \
+criteria and retries; every run is appended to \
+<workspace>/state/workflow_runs.jsonl.
+
+Examples:
+  zeroclaw workflow list
+  zeroclaw workflow run standup-digest
+  zeroclaw workflow runs standup-digest --limit 5")]
+    Workflow {
+        #[command(subcommand)]
+        workflow_command: WorkflowCommands,
+    },
+
+    /// List tools and run one directly, without the model
+    #[command(long_about = "\
+List tools and run one directly, without the model.
+
+`exec` builds the same tool registry the agent uses and goes through the \
+same approval and security checks: supervised tools prompt on the \
+terminal first (no answer means no), and each tool applies its own \
+policy. Useful for checking tool configuration such as web_fetch \
+allowlists without spending provider tokens.
+
+Examples:
+  zeroclaw tool list
+  zeroclaw tool exec web_fetch --args '{\"url\": \"https://example.com\"}'
+  zeroclaw tool exec memory_recall --args '{\"query\": \"deploy\"}'")]
+    Tool {
+        #[command(subcommand)]
+        tool_command: ToolCommands,
+    },
+
+    /// Revert the file writes, git commits and memory writes of a turn
+    #[command(long_about = "\
+Revert the file writes, git commits and memory writes of a turn.
+
+While [agent].undo_journal is on (the default), file_write, file_edit, \
+git commits and memory_store record what they change in \
+<workspace>/state/undo.db under the turn that made the change. `last` \
+reverts the newest such turn, newest action first. A commit is only \
+undone while it is still HEAD, with `git reset --soft` so its changes \
+stay staged. Entries are kept for a week.
+
+Examples:
+  zeroclaw undo list
+  zeroclaw undo last
+  zeroclaw undo turn 0f9c2d4e-7b1a-4c3e-9d8f-2a6b5c4d3e21")]
+    Undo {
+        #[command(subcommand)]
+        undo_command: UndoCommands,
+    },
+
+    /// Manage the knowledge base of private documents
+    #[command(long_about = "\
+Manage the knowledge base of private documents.
+
+`ingest` reads Markdown, text and HTML files (directories are walked \
+recursively, hidden entries skipped) and http(s) URLs, splits them into \
+chunks and stores them with embeddings from the [memory] embedding \
+settings in <workspace>/state/kb.db. Unchanged documents are skipped and \
+files deleted from an ingested directory are dropped. With no arguments \
+it re-reads [kb].sources. With [kb].enabled = true the agent gets a \
+kb_search tool and the best passages are added to each message.
+
+Examples:
+  zeroclaw kb ingest docs/ https://example.com/handbook
+  zeroclaw kb list
+  zeroclaw kb search \"expense policy\"
+  zeroclaw kb remove docs/old.md")]
+    Kb {
+        #[command(subcommand)]
+        kb_command: KbCommands,
+    },
+
+    /// Inspect data kept for debugging integrations
+    #[command(long_about = "\
+Inspect data kept for debugging integrations.
+
+`payloads` prints the webhook bodies the gateway archived for a \
+channel while [gateway].payload_archive_size is above 0. Bodies are \
+stored after signature checks, with credentials redacted.
+
+Examples:
+  zeroclaw debug payloads wati
+  zeroclaw debug payloads qq --limit 1 --json")]
+    Debug {
+        #[command(subcommand)]
+        debug_command: DebugCommands,
+    },
+
+    /// Maintain local databases (session store WAL checkpoint and vacuum)
+    #[command(long_about = "\
+Maintain local databases.
+
+`maintain` runs one maintenance pass on the SQLite session store \
+(<workspace>/sessions/sessions.db): sessions older than \
+session.ttl_secs are deleted, the write-ahead log is checkpointed and \
+truncated, and free pages are returned to the filesystem. The daemon \
+runs the same pass every session.maintenance_interval_minutes. Safe \
+to run while the daemon is up.
+
+`migrate` brings every SQLite database in the workspace (sessions, \
+memory, outbox, task queue, undo journal) to the schema this build \
+expects. Databases are also migrated whenever they are opened; \
+`--dry-run` lists the pending migrations without changing anything.
+
+Examples:
+  zeroclaw db maintain
+  zeroclaw db maintain --json
+  zeroclaw db migrate --dry-run")]
+    Db {
+        #[command(subcommand)]
+        db_command: DbCommands,
+    },
+
     /// Manage configuration
     #[command(long_about = "\
 Manage ZeroClaw configuration.
@@ -502,6 +792,26 @@ Examples:
         config_command: ConfigCommands,
     },
 
+    /// Manage named secrets referenced from config as secret://<name>
+    #[command(long_about = "\
+Manage named secrets referenced from config.toml.
+
+Any string value in config.toml can be written as `secret://<name>` \
+and is resolved from the secrets backend when the config is loaded \
+(`[secrets].backend`: `file` or `keychain`). `secret://env/<VAR>` \
+reads an environment variable instead. Values are read from a hidden \
+prompt, or from stdin when it is not a terminal.
+
+Examples:
+  zeroclaw secrets set telegram_bot
+  zeroclaw secrets get telegram_bot --reveal
+  zeroclaw secrets rotate gateway_token --generate
+  zeroclaw secrets list")]
+    Secrets {
+        #[command(subcommand)]
+        secrets_command: SecretsCommands,
+    },
+
     /// Generate shell completion script to stdout
     #[command(long_about = "\
 Generate shell completion scripts for `zeroclaw`.
@@ -525,6 +835,33 @@ enum ConfigCommands {
     Schema,
 }
 
+#[derive(Subcommand, Debug)]
+enum SecretsCommands {
+    /// Store a secret (overwrites any existing value)
+    Set {
+        /// Secret name, referenced as secret://<name>
+        name: String,
+    },
+    /// Show a secret (masked unless --reveal)
+    Get {
+        /// Secret name
+        name: String,
+        /// Print the full value
+        #[arg(long)]
+        reveal: bool,
+    },
+    /// Replace an existing secret and record the rotation time
+    Rotate {
+        /// Secret name
+        name: String,
+        /// Generate a random 256-bit hex value instead of prompting
+        #[arg(long)]
+        generate: bool,
+    },
+    /// List stored secret names and timestamps
+    List,
+}
+
 #[derive(Subcommand, Debug)]
 enum EstopSubcommands {
     /// Print current estop status.
@@ -687,6 +1024,16 @@ enum DoctorCommands {
         #[arg(long, default_value = "20")]
         limit: usize,
     },
+    /// Show the execution report of a turn (requires observability.turn_reports)
+    Report {
+        /// Turn id or the channel message id that started the turn
+        id: String,
+    },
+    /// List incident reports (panics, daemon errors) or show one by id
+    Incidents {
+        /// Incident id
+        id: Option<String>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -737,6 +1084,12 @@ async fn main() -> Result<()> {
         }
         std::env::set_var("ZEROCLAW_CONFIG_DIR", config_dir);
     }
+    if let Some(profile) = &cli.profile {
+        if profile.trim().is_empty() {
+            bail!("--profile cannot be empty");
+        }
+        std::env::set_var(config::layers::PROFILE_ENV, profile);
+    }
 
     // Completions must remain stdout-only and should not load config or initialize logging.
     // This avoids warnings/log lines corrupting sourced completion scripts.
@@ -746,12 +1099,22 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
-    // Initialize logging - respects RUST_LOG env var, defaults to INFO
+    // Initialize logging - respects RUST_LOG env var, defaults to INFO.
+    // Pipe mode keeps stdout for the answer alone: logs go to stderr, warnings only.
+    let pipe_mode = matches!(cli.command, Commands::Run { .. });
     let subscriber = fmt::Subscriber::builder()
         .with_timer(tracing_subscriber::fmt::time::ChronoLocal::rfc_3339())
         .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+            EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| EnvFilter::new(if pipe_mode { "warn" } else { "info" })),
         )
+        .with_writer(move || -> Box<dyn std::io::Write> {
+            if pipe_mode {
+                Box::new(std::io::stderr())
+            } else {
+                Box::new(std::io::stdout())
+            }
+        })
         .finish();
 
     tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
@@ -819,10 +1182,21 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Secrets are handled before config load so an unset secret:// reference
+    // can be fixed without the rest of the config resolving.
+    if let Commands::Secrets { secrets_command } = cli.command {
+        return handle_secrets_command(secrets_command).await;
+    }
+
     // All other commands need config loaded first
     let mut config = Config::load_or_init().await?;
     config.apply_env_overrides();
     observability::runtime_trace::init_from_config(&config.observability, &config.workspace_dir);
+    observability::incident::init_from_config(&config);
+    channels::attachments::init_from_config(&config.attachments);
+    gateway::payload_archive::init_from_config(&config.gateway, &config.workspace_dir);
+    approval::quarantine::init_from_config(&config.security.leak_guard);
+    workspace::init_from_config(&config);
     if config.security.otp.enabled {
         let config_dir = config
             .config_path
@@ -838,7 +1212,9 @@ async fn main() -> Result<()> {
     }
 
     match cli.command {
-        Commands::Onboard { .. } | Commands::Completions { .. } => unreachable!(),
+        Commands::Onboard { .. } | Commands::Completions { .. } | Commands::Secrets { .. } => {
+            unreachable!()
+        }
 
         Commands::Agent {
             message,
@@ -888,6 +1264,46 @@ async fn main() -> Result<()> {
             .map(|_| ())
         }
 
+        Commands::Chat {
+            provider,
+            model,
+            temperature,
+        } => agent::run(config, None, provider, model, temperature, Vec::new(), true)
+            .await
+            .map(|_| ()),
+
+        Commands::Run {
+            prompt,
+            provider,
+            model,
+            temperature,
+            tools,
+            no_tools,
+        } => {
+            let prompt = read_run_prompt(&prompt)?;
+            if provider.is_some() {
+                config.default_provider = provider;
+            }
+            if model.is_some() {
+                config.default_model = model;
+            }
+            if let Some(temperature) = temperature {
+                config.default_temperature = temperature;
+            }
+            let allowed_tools = if no_tools {
+                Some(Vec::new())
+            } else {
+                (!tools.is_empty()).then_some(tools)
+            };
+            let options = agent::ProcessMessageOptions {
+                allowed_tools,
+                ..agent::ProcessMessageOptions::default()
+            };
+            let answer = agent::process_message_with_options(config, &prompt, options).await?;
+            println!("{answer}");
+            Ok(())
+        }
+
         Commands::Gateway {
             port,
             host,
@@ -919,7 +1335,15 @@ async fn main() -> Result<()> {
             } else {
                 info!("🧠 Starting ZeroClaw Daemon on {host}:{port}");
             }
-            daemon::run(config, host, port).await
+            let result = daemon::run(config, host, port).await;
+            if let Err(e) = &result {
+                observability::incident::record_error(e).await;
+            }
+            result
+        }
+
+        Commands::Attach { gateway, token } => {
+            approval::attach::run_client(&config, gateway.as_deref(), token.as_deref()).await
         }
 
         Commands::Status => {
@@ -1093,7 +1517,10 @@ async fn main() -> Result<()> {
             service::handle_command(&service_command, &config, init_system)
         }
 
-        Commands::Doctor { doctor_command } => match doctor_command {
+        Commands::Doctor {
+            offline,
+            doctor_command,
+        } => match doctor_command {
             Some(DoctorCommands::Models {
                 provider,
                 use_cache,
@@ -1110,7 +1537,15 @@ async fn main() -> Result<()> {
                 contains.as_deref(),
                 limit,
             ),
-            None => doctor::run(&config),
+            Some(DoctorCommands::Report { id }) => doctor::run_report(&config, &id),
+            Some(DoctorCommands::Incidents { id }) => doctor::run_incidents(&config, id.as_deref()),
+            None => {
+                doctor::run(&config)?;
+                if !offline {
+                    doctor::live::run(&config).await;
+                }
+                Ok(())
+            }
         },
 
         Commands::Channel { channel_command } => match channel_command {
@@ -1133,6 +1568,30 @@ async fn main() -> Result<()> {
             memory::cli::handle_command(memory_command, &config).await
         }
 
+        Commands::Session { session_command } => {
+            channels::session::cli::handle_command(session_command, &config).await
+        }
+
+        Commands::Usage { usage_command } => cost::cli::handle_command(usage_command, &config),
+
+        Commands::Workflow { workflow_command } => {
+            workflows::handle_command(workflow_command, &config).await
+        }
+
+        Commands::Tool { tool_command } => tools::cli::handle_command(tool_command, &config).await,
+
+        Commands::Undo { undo_command } => tools::undo::handle_command(undo_command, &config).await,
+
+        Commands::Kb { kb_command } => kb::handle_command(kb_command, &config).await,
+
+        Commands::Db { db_command } => {
+            channels::session::cli::handle_db_command(db_command, &config).await
+        }
+
+        Commands::Debug { debug_command } => {
+            gateway::payload_archive::handle_command(debug_command, &config)
+        }
+
         Commands::Auth { auth_command } => handle_auth_command(auth_command, &config).await,
 
         Commands::Hardware { hardware_command } => {
@@ -1156,6 +1615,81 @@ async fn main() -> Result<()> {
     }
 }
 
+async fn handle_secrets_command(secrets_command: SecretsCommands) -> Result<()> {
+    let (zeroclaw_dir, secrets_config) = config::schema::load_secrets_settings().await?;
+    let vault = security::SecretVault::new(&zeroclaw_dir, &secrets_config);
+
+    match secrets_command {
+        SecretsCommands::Set { name } => {
+            security::vault::validate_secret_name(&name)?;
+            let value = read_secret_value(&format!("Value for {name}"))?;
+            vault.set(&name, &value)?;
+            println!("✓ Stored secret '{name}' ({:?} backend)", vault.backend());
+            println!("  Reference it in config.toml as \"secret://{name}\"");
+            Ok(())
+        }
+        SecretsCommands::Get { name, reveal } => {
+            let value = vault
+                .get(&name)?
+                .with_context(|| format!("Secret '{name}' is not set"))?;
+            if reveal {
+                println!("{value}");
+            } else {
+                println!("{}", security::redact(&value));
+            }
+            Ok(())
+        }
+        SecretsCommands::Rotate { name, generate } => {
+            security::vault::validate_secret_name(&name)?;
+            let value = if generate {
+                let bytes: [u8; 32] = rand::random();
+                hex::encode(bytes)
+            } else {
+                read_secret_value(&format!("New value for {name}"))?
+            };
+            vault.rotate(&name, &value)?;
+            println!("✓ Rotated secret '{name}'");
+            if generate {
+                println!("  New value: {value}");
+            }
+            println!("  Restart running daemons/channels to pick up the new value.");
+            Ok(())
+        }
+        SecretsCommands::List => {
+            let secrets = vault.list()?;
+            if secrets.is_empty() {
+                println!("No secrets stored. Add one with `zeroclaw secrets set <name>`.");
+                return Ok(());
+            }
+            for info in secrets {
+                let rotated = info
+                    .rotated_at
+                    .map(|t| format!(", rotated {}", t.to_rfc3339()))
+                    .unwrap_or_default();
+                println!(
+                    "{}  (updated {}{rotated})",
+                    info.name,
+                    info.updated_at.to_rfc3339()
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+fn read_secret_value(prompt: &str) -> Result<String> {
+    if std::io::stdin().is_terminal() {
+        return read_auth_input(prompt);
+    }
+    let mut value = String::new();
+    std::io::stdin().read_to_string(&mut value)?;
+    let value = value.trim_end_matches(['\r', '\n']).to_string();
+    if value.is_empty() {
+        bail!("No secret value provided on stdin");
+    }
+    Ok(value)
+}
+
 fn handle_estop_command(
     config: &Config,
     estop_command: Option<EstopSubcommands>,
@@ -2065,6 +2599,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn run_cli_parses_stdin_prompt_and_tool_restrictions() {
+        let cli = Cli::try_parse_from(["zeroclaw", "run", "-", "--tools", "shell,file_read"])
+            .expect("run should parse");
+        match cli.command {
+            Commands::Run {
+                prompt,
+                tools,
+                no_tools,
+                ..
+            } => {
+                assert_eq!(prompt, "-");
+                assert_eq!(tools, vec!["shell".to_string(), "file_read".to_string()]);
+                assert!(!no_tools);
+            }
+            other => panic!("expected run command, got {other:?}"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["zeroclaw", "run", "-", "--tools", "shell", "--no-tools"])
+                .is_err()
+        );
+        assert!(read_run_prompt("  ").is_err());
+        assert_eq!(read_run_prompt(" hi ").unwrap(), "hi");
+    }
+
     #[test]
     fn gateway_cli_defaults_new_pairing_to_false() {
         let cli = Cli::try_parse_from(["zeroclaw", "gateway"]).expect("gateway should parse");
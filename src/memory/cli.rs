@@ -23,6 +23,7 @@ pub async fn handle_command(command: crate::MemoryCommands, config: &Config) ->
         crate::MemoryCommands::Clear { key, category, yes } => {
             handle_clear(config, key, category, yes).await
         }
+        crate::MemoryCommands::Prune { dry_run } => handle_prune(config, dry_run).await,
     }
 }
 
@@ -31,7 +32,7 @@ pub async fn handle_command(command: crate::MemoryCommands, config: &Config) ->
 /// CLI commands (list/get/stats/clear) never use vector search, so we skip
 /// embedding provider initialisation for local backends by using the
 /// migration factory.  Postgres still needs its full connection config.
-fn create_cli_memory(config: &Config) -> Result<Box<dyn Memory>> {
+pub(super) fn create_cli_memory(config: &Config) -> Result<Box<dyn Memory>> {
     let backend = effective_memory_backend_name(
         &config.memory.backend,
         Some(&config.storage.provider.config),
@@ -251,6 +252,41 @@ async fn handle_clear(
     Ok(())
 }
 
+async fn handle_prune(config: &Config, dry_run: bool) -> Result<()> {
+    let policy = &config.memory.decay;
+    if !policy.enabled {
+        println!("Memory decay is disabled; set [memory.decay] enabled = true to expire entries.");
+        return Ok(());
+    }
+
+    let mem = create_cli_memory(config)?;
+    let expired = super::decay::prune(&*mem, policy, dry_run).await?;
+    if expired.is_empty() {
+        println!("No expired entries.");
+        return Ok(());
+    }
+
+    for entry in &expired {
+        println!(
+            "- {} [{}] {}",
+            style(&entry.key).white().bold(),
+            entry.category,
+            entry.timestamp,
+        );
+    }
+    if dry_run {
+        println!("\n{} entries would be pruned.", expired.len());
+    } else {
+        println!(
+            "\n{} Pruned {} expired entries.",
+            style("✓").green().bold(),
+            expired.len(),
+        );
+    }
+
+    Ok(())
+}
+
 /// Delete a single entry by exact key or prefix match.
 async fn handle_clear_key(mem: &dyn Memory, key: &str, yes: bool) -> Result<()> {
     // Resolve the target key (exact match or unique prefix).
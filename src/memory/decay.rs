@@ -0,0 +1,269 @@
+//! Memory expiry, recall decay, and garbage collection.
+//!
+//! Expiry is derived from each entry's creation time and the TTL configured
+//! for its category in `[memory.decay]`, so it works for every backend
+//! without schema changes. [`DecayingMemory`] hides expired entries from
+//! reads and down-weights older non-core entries in recall; [`prune`]
+//! deletes expired entries for good. The daemon runs [`run_gc_worker`]
+//! periodically, and `zeroclaw memory prune` runs a pass on demand.
+
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use crate::config::{Config, MemoryDecayConfig};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+/// Creation time of `entry`. Markdown entries carry a `YYYY-MM-DD` file
+/// name instead of an RFC 3339 timestamp.
+fn created_at(entry: &MemoryEntry) -> Option<DateTime<Utc>> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(&entry.timestamp) {
+        return Some(ts.with_timezone(&Utc));
+    }
+    let date = entry.timestamp.get(..10)?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()?
+        .and_hms_opt(0, 0, 0)
+        .map(|dt| dt.and_utc())
+}
+
+/// When `entry` expires under `policy`, or `None` if it is kept forever.
+pub fn expires_at(entry: &MemoryEntry, policy: &MemoryDecayConfig) -> Option<DateTime<Utc>> {
+    let days = *policy.ttl_days.get(&entry.category.to_string())?;
+    if days == 0 {
+        return None;
+    }
+    Some(created_at(entry)? + Duration::days(i64::from(days)))
+}
+
+pub fn is_expired(entry: &MemoryEntry, policy: &MemoryDecayConfig, now: DateTime<Utc>) -> bool {
+    expires_at(entry, policy).is_some_and(|at| at <= now)
+}
+
+/// Recall score multiplier for `entry`: `0.5^(age / half_life)` for
+/// non-core entries, `1.0` for core entries or when decay is off.
+pub fn decay_factor(entry: &MemoryEntry, policy: &MemoryDecayConfig, now: DateTime<Utc>) -> f64 {
+    if policy.half_life_days == 0 || entry.category == MemoryCategory::Core {
+        return 1.0;
+    }
+    let Some(created) = created_at(entry) else {
+        return 1.0;
+    };
+    let age_days = (now - created).num_seconds().max(0) as f64 / 86_400.0;
+    0.5_f64.powf(age_days / f64::from(policy.half_life_days))
+}
+
+/// Memory wrapper applying a decay policy on read.
+pub struct DecayingMemory {
+    inner: Box<dyn Memory>,
+    policy: MemoryDecayConfig,
+}
+
+impl DecayingMemory {
+    pub fn new(inner: Box<dyn Memory>, policy: MemoryDecayConfig) -> Self {
+        Self { inner, policy }
+    }
+}
+
+/// Wrap `memory` in a [`DecayingMemory`] when the policy is enabled.
+pub fn with_policy(memory: Box<dyn Memory>, policy: &MemoryDecayConfig) -> Box<dyn Memory> {
+    if policy.enabled {
+        Box::new(DecayingMemory::new(memory, policy.clone()))
+    } else {
+        memory
+    }
+}
+
+#[async_trait]
+impl Memory for DecayingMemory {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> Result<()> {
+        self.inner.store(key, content, category, session_id).await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        let now = Utc::now();
+        // Over-fetch so expired and decayed entries do not starve the result.
+        let mut entries: Vec<MemoryEntry> = self
+            .inner
+            .recall(query, limit.saturating_mul(2), session_id)
+            .await?
+            .into_iter()
+            .filter(|entry| !is_expired(entry, &self.policy, now))
+            .collect();
+        for entry in &mut entries {
+            let factor = decay_factor(entry, &self.policy, now);
+            entry.score = entry.score.map(|score| score * factor);
+        }
+        entries.sort_by(|a, b| {
+            b.score
+                .unwrap_or(0.0)
+                .partial_cmp(&a.score.unwrap_or(0.0))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<MemoryEntry>> {
+        Ok(self
+            .inner
+            .get(key)
+            .await?
+            .filter(|entry| !is_expired(entry, &self.policy, Utc::now())))
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        let now = Utc::now();
+        let mut entries = self.inner.list(category, session_id).await?;
+        entries.retain(|entry| !is_expired(entry, &self.policy, now));
+        Ok(entries)
+    }
+
+    async fn forget(&self, key: &str) -> Result<bool> {
+        self.inner.forget(key).await
+    }
+
+    async fn count(&self) -> Result<usize> {
+        self.inner.count().await
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+}
+
+/// Delete entries that have expired under `policy`. With `dry_run`, only
+/// report them. Returns the expired entries.
+pub async fn prune(
+    memory: &dyn Memory,
+    policy: &MemoryDecayConfig,
+    dry_run: bool,
+) -> Result<Vec<MemoryEntry>> {
+    prune_at(memory, policy, Utc::now(), dry_run).await
+}
+
+async fn prune_at(
+    memory: &dyn Memory,
+    policy: &MemoryDecayConfig,
+    now: DateTime<Utc>,
+    dry_run: bool,
+) -> Result<Vec<MemoryEntry>> {
+    let expired: Vec<MemoryEntry> = memory
+        .list(None, None)
+        .await?
+        .into_iter()
+        .filter(|entry| is_expired(entry, policy, now))
+        .collect();
+    if !dry_run {
+        for entry in &expired {
+            memory.forget(&entry.key).await?;
+        }
+    }
+    Ok(expired)
+}
+
+/// Periodic garbage collection for the daemon.
+pub async fn run_gc_worker(config: Config) -> Result<()> {
+    let policy = config.memory.decay.clone();
+    // Use the raw backend: the decaying wrapper hides exactly the entries
+    // this pass is looking for.
+    let memory = super::cli::create_cli_memory(&config)?;
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        policy.gc_interval_minutes.max(1) * 60,
+    ));
+    loop {
+        interval.tick().await;
+        match prune(memory.as_ref(), &policy, false).await {
+            Ok(expired) => {
+                if !expired.is_empty() {
+                    tracing::info!(pruned = expired.len(), "Pruned expired memory entries");
+                }
+                crate::health::mark_component_ok("memory_gc");
+            }
+            Err(e) => crate::health::mark_component_error("memory_gc", e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    fn entry(category: MemoryCategory, age_days: i64) -> MemoryEntry {
+        MemoryEntry {
+            id: "id".into(),
+            key: "k".into(),
+            content: "c".into(),
+            category,
+            timestamp: (Utc::now() - Duration::days(age_days)).to_rfc3339(),
+            session_id: None,
+            score: Some(1.0),
+        }
+    }
+
+    #[test]
+    fn ttl_applies_per_category_and_core_persists() {
+        let policy = MemoryDecayConfig {
+            enabled: true,
+            ..MemoryDecayConfig::default()
+        };
+        let now = Utc::now();
+        assert!(is_expired(&entry(MemoryCategory::Daily, 8), &policy, now));
+        assert!(!is_expired(&entry(MemoryCategory::Daily, 6), &policy, now));
+        assert!(!is_expired(&entry(MemoryCategory::Core, 400), &policy, now));
+        assert!(expires_at(&entry(MemoryCategory::Core, 1), &policy).is_none());
+
+        let factor = decay_factor(&entry(MemoryCategory::Conversation, 30), &policy, now);
+        assert!((factor - 0.5).abs() < 0.01);
+        assert!(
+            (decay_factor(&entry(MemoryCategory::Core, 30), &policy, now) - 1.0).abs()
+                < f64::EPSILON
+        );
+    }
+
+    #[tokio::test]
+    async fn prune_removes_only_expired_entries() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("note", "scratch note", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+        mem.store("pref", "likes tea", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let policy = MemoryDecayConfig {
+            enabled: true,
+            ..MemoryDecayConfig::default()
+        };
+        let later = Utc::now() + Duration::days(8);
+
+        let dry = prune_at(&mem, &policy, later, true).await.unwrap();
+        assert_eq!(dry.len(), 1);
+        assert_eq!(mem.count().await.unwrap(), 2);
+
+        let pruned = prune_at(&mem, &policy, later, false).await.unwrap();
+        assert_eq!(pruned[0].key, "note");
+        assert!(mem.get("note").await.unwrap().is_none());
+        assert!(mem.get("pref").await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,225 @@
+//! Post-turn fact extraction.
+//!
+//! After a channel reply is delivered, the provider can be asked to pull
+//! durable facts and preferences out of the last exchange. Each fact is
+//! stored as a `core` memory with its provenance (channel, sender,
+//! timestamp) and is skipped when an equivalent fact is already stored.
+
+use super::principal::{self, MemoryPrincipal};
+use super::traits::{Memory, MemoryCategory};
+use crate::providers::structured::{self, StructuredRequest};
+use crate::providers::Provider;
+use crate::util::truncate_with_ellipsis;
+use anyhow::Result;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+/// Key prefix for extracted facts.
+const FACT_KEY_PREFIX: &str = "fact_";
+
+/// Marker separating a fact from its provenance line.
+const PROVENANCE_MARKER: &str = "\n[source: ";
+
+const MAX_FACTS_PER_TURN: usize = 5;
+const MAX_FACT_CHARS: usize = 300;
+const MAX_EXCHANGE_CHARS: usize = 4_000;
+
+/// Word overlap (Jaccard) at which two facts count as duplicates.
+const DUPLICATE_SIMILARITY: f64 = 0.8;
+
+const EXTRACTOR_SYSTEM_PROMPT: &str = "You extract durable facts about the user from a chat exchange: stable preferences, personal details, decisions, and ongoing commitments. Ignore one-off requests, small talk, and anything the assistant said that the user did not confirm. List them as short, self-contained statements in the third person (e.g. \"Prefers metric units\"). Return an empty list when there is nothing worth remembering.";
+
+/// Where an extracted fact came from.
+#[derive(Debug, Clone)]
+pub struct FactProvenance {
+    pub channel: String,
+    pub sender: String,
+    pub timestamp: String,
+}
+
+/// Ask `provider` for durable facts stated in one user/assistant exchange.
+pub async fn extract_facts(
+    provider: &dyn Provider,
+    model: &str,
+    user_message: &str,
+    assistant_reply: &str,
+) -> Result<Vec<String>> {
+    let prompt = format!(
+        "User:\n{}\n\nAssistant:\n{}",
+        truncate_with_ellipsis(user_message, MAX_EXCHANGE_CHARS),
+        truncate_with_ellipsis(assistant_reply, MAX_EXCHANGE_CHARS)
+    );
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "facts": {"type": "array", "items": {"type": "string"}}
+        },
+        "required": ["facts"],
+        "additionalProperties": false
+    });
+    let reply = structured::chat_structured(
+        provider,
+        &StructuredRequest {
+            system_prompt: Some(EXTRACTOR_SYSTEM_PROMPT),
+            message: &prompt,
+            schema_name: "extracted_facts",
+            schema: &schema,
+            max_repairs: structured::DEFAULT_MAX_REPAIRS,
+        },
+        model,
+        0.0,
+    )
+    .await?;
+    Ok(parse_facts(&reply.value))
+}
+
+/// Normalize the validated extractor reply (`{"facts": [...]}`): trims,
+/// drops empty entries and caps count and length.
+fn parse_facts(reply: &Value) -> Vec<String> {
+    reply
+        .get("facts")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(|fact| fact.trim().to_string())
+        .filter(|fact| !fact.is_empty())
+        .map(|fact| truncate_with_ellipsis(&fact, MAX_FACT_CHARS))
+        .take(MAX_FACTS_PER_TURN)
+        .collect()
+}
+
+fn fact_words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn is_duplicate(fact: &HashSet<String>, existing: &str) -> bool {
+    let existing = fact_words(existing);
+    let union = fact.union(&existing).count();
+    union == 0 || fact.intersection(&existing).count() as f64 / union as f64 >= DUPLICATE_SIMILARITY
+}
+
+/// Fact text of a stored entry, without its provenance line.
+pub fn strip_provenance(content: &str) -> &str {
+    content
+        .split_once(PROVENANCE_MARKER)
+        .map_or(content, |(fact, _)| fact)
+}
+
+fn fact_key(words: &HashSet<String>) -> String {
+    let mut sorted: Vec<&str> = words.iter().map(String::as_str).collect();
+    sorted.sort_unstable();
+    let digest = Sha256::digest(sorted.join(" ").as_bytes());
+    format!("{FACT_KEY_PREFIX}{}", hex::encode(&digest[..8]))
+}
+
+/// Store `facts` in the principal tier of `principal` with provenance,
+/// skipping facts that match an entry the principal can already see.
+/// Returns the number stored.
+pub async fn store_facts(
+    memory: &dyn Memory,
+    facts: &[String],
+    provenance: &FactProvenance,
+    principal: Option<&MemoryPrincipal>,
+) -> Result<usize> {
+    let namespace = principal.map(MemoryPrincipal::namespace);
+    let namespace = namespace.as_deref();
+    let mut stored = 0;
+    for fact in facts {
+        let words = fact_words(fact);
+        let existing =
+            principal::recall_for_principal(memory, fact, MAX_FACTS_PER_TURN, principal).await?;
+        if existing
+            .iter()
+            .any(|entry| is_duplicate(&words, strip_provenance(&entry.content)))
+        {
+            continue;
+        }
+
+        let content = format!(
+            "{fact}{PROVENANCE_MARKER}{}:{} at {}]",
+            provenance.channel, provenance.sender, provenance.timestamp
+        );
+        memory
+            .store(
+                &principal::scoped_key(namespace, &fact_key(&words)),
+                &content,
+                MemoryCategory::Core,
+                namespace,
+            )
+            .await?;
+        stored += 1;
+    }
+    Ok(stored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parse_facts_trims_and_drops_empty_entries() {
+        assert_eq!(
+            parse_facts(&json!({"facts": ["Prefers metric units", "  "]})),
+            vec!["Prefers metric units".to_string()]
+        );
+        assert!(parse_facts(&json!({"facts": []})).is_empty());
+        assert!(parse_facts(&json!({})).is_empty());
+    }
+
+    #[tokio::test]
+    async fn store_facts_records_provenance_and_skips_duplicates() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let alice = MemoryPrincipal::new(
+            "telegram",
+            "alice",
+            crate::config::MemoryAccessConfig::default(),
+        );
+        let ns = alice.namespace();
+        let provenance = FactProvenance {
+            channel: "telegram".into(),
+            sender: "alice".into(),
+            timestamp: "2026-02-16T00:00:00Z".into(),
+        };
+
+        let first = vec!["Prefers metric units".to_string()];
+        assert_eq!(
+            store_facts(&mem, &first, &provenance, Some(&alice))
+                .await
+                .unwrap(),
+            1
+        );
+
+        let again = vec![
+            "prefers metric units.".to_string(),
+            "Works night shifts".to_string(),
+        ];
+        assert_eq!(
+            store_facts(&mem, &again, &provenance, Some(&alice))
+                .await
+                .unwrap(),
+            1
+        );
+
+        let entries = mem
+            .list(Some(&MemoryCategory::Core), Some(&ns))
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        let metric = entries
+            .iter()
+            .find(|e| e.content.starts_with("Prefers metric units"))
+            .unwrap();
+        assert!(metric
+            .content
+            .ends_with("[source: telegram:alice at 2026-02-16T00:00:00Z]"));
+        assert_eq!(strip_provenance(&metric.content), "Prefers metric units");
+    }
+}
@@ -1,7 +1,9 @@
 pub mod backend;
 pub mod chunker;
 pub mod cli;
+pub mod decay;
 pub mod embeddings;
+pub mod extraction;
 pub mod hybrid;
 pub mod hygiene;
 pub mod lucid;
@@ -9,6 +11,7 @@ pub mod markdown;
 pub mod none;
 #[cfg(feature = "memory-postgres")]
 pub mod postgres;
+pub mod principal;
 pub mod qdrant;
 pub mod response_cache;
 pub mod snapshot;
@@ -173,6 +176,22 @@ fn resolve_embedding_config(
     }
 }
 
+/// Embedding provider selected by `[memory]` (following `hint:` routes),
+/// for subsystems that embed text outside the memory backend.
+pub fn create_embedder(
+    config: &MemoryConfig,
+    embedding_routes: &[EmbeddingRouteConfig],
+    api_key: Option<&str>,
+) -> Arc<dyn embeddings::EmbeddingProvider> {
+    let resolved = resolve_embedding_config(config, embedding_routes, api_key);
+    Arc::from(embeddings::create_embedding_provider(
+        &resolved.provider,
+        resolved.api_key.as_deref(),
+        &resolved.model,
+        resolved.dimensions,
+    ))
+}
+
 /// Factory: create the right memory backend from config
 pub fn create_memory(
     config: &MemoryConfig,
@@ -199,6 +218,23 @@ pub fn create_memory_with_storage_and_routes(
     storage_provider: Option<&StorageProviderConfig>,
     workspace_dir: &Path,
     api_key: Option<&str>,
+) -> anyhow::Result<Box<dyn Memory>> {
+    let memory = create_memory_backend(
+        config,
+        embedding_routes,
+        storage_provider,
+        workspace_dir,
+        api_key,
+    )?;
+    Ok(decay::with_policy(memory, &config.decay))
+}
+
+fn create_memory_backend(
+    config: &MemoryConfig,
+    embedding_routes: &[EmbeddingRouteConfig],
+    storage_provider: Option<&StorageProviderConfig>,
+    workspace_dir: &Path,
+    api_key: Option<&str>,
 ) -> anyhow::Result<Box<dyn Memory>> {
     let backend_name = effective_memory_backend_name(&config.backend, storage_provider);
     let backend_kind = classify_memory_backend(&backend_name);
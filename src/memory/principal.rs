@@ -0,0 +1,294 @@
+//! Scoped memory namespaces and access rules.
+//!
+//! Memory has three tiers:
+//!
+//! - **principal** (`principal:<channel>:<sender>`): private to one sender.
+//! - **channel** (`channel:<channel>`): shared by everyone on one channel.
+//! - **global** (no namespace): shared by everyone.
+//!
+//! Channel turns run with [`MEMORY_PRINCIPAL`] set. Entries are keyed
+//! `<namespace>/<key>` and tagged with their namespace as `session_id`. A
+//! principal reads its own tier, its channel's tier, and the global tier,
+//! never another sender's or another channel's entries. Writes to the
+//! channel and global tiers follow `[memory.access]`. With no principal
+//! (CLI, gateway, or `memory.principal_isolation = false`) every entry is
+//! readable and writable.
+
+use super::traits::{Memory, MemoryEntry};
+use crate::config::MemoryAccessConfig;
+use std::collections::HashSet;
+
+const PRINCIPAL_PREFIX: &str = "principal:";
+const CHANNEL_PREFIX: &str = "channel:";
+
+/// Extra candidates fetched from the shared tiers, since entries belonging
+/// to other principals or channels are filtered out after the backend
+/// ranks them.
+const SHARED_RECALL_OVERFETCH: usize = 4;
+
+tokio::task_local! {
+    /// Principal the current turn runs for.
+    pub static MEMORY_PRINCIPAL: Option<MemoryPrincipal>;
+}
+
+/// Memory tier an entry is written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryScope {
+    Principal,
+    Channel,
+    Global,
+}
+
+impl MemoryScope {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "principal" | "private" | "user" => Some(Self::Principal),
+            "channel" => Some(Self::Channel),
+            "global" | "shared" => Some(Self::Global),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Principal => "principal",
+            Self::Channel => "channel",
+            Self::Global => "global",
+        }
+    }
+}
+
+/// Identity and write rights of a channel sender.
+#[derive(Debug, Clone)]
+pub struct MemoryPrincipal {
+    pub channel: String,
+    pub sender: String,
+    pub access: MemoryAccessConfig,
+}
+
+impl MemoryPrincipal {
+    pub fn new(channel: &str, sender: &str, access: MemoryAccessConfig) -> Self {
+        Self {
+            channel: channel.to_string(),
+            sender: sender.to_string(),
+            access,
+        }
+    }
+
+    /// Namespace of the principal tier.
+    pub fn namespace(&self) -> String {
+        principal_namespace(&self.channel, &self.sender)
+    }
+
+    /// Namespace of the principal's channel tier.
+    pub fn channel_namespace(&self) -> String {
+        channel_namespace(&self.channel)
+    }
+
+    /// Namespace for `scope`; `None` is the global tier.
+    pub fn scope_namespace(&self, scope: MemoryScope) -> Option<String> {
+        match scope {
+            MemoryScope::Principal => Some(self.namespace()),
+            MemoryScope::Channel => Some(self.channel_namespace()),
+            MemoryScope::Global => None,
+        }
+    }
+
+    pub fn can_write(&self, scope: MemoryScope) -> bool {
+        match scope {
+            MemoryScope::Principal => true,
+            MemoryScope::Channel => self.access.channel_write,
+            MemoryScope::Global => self.access.global_write,
+        }
+    }
+
+    /// Tier of a namespace this principal can see.
+    fn scope_of(&self, namespace: Option<&str>) -> Option<MemoryScope> {
+        match namespace {
+            None => Some(MemoryScope::Global),
+            Some(ns) if ns == self.namespace() => Some(MemoryScope::Principal),
+            Some(ns) if ns == self.channel_namespace() => Some(MemoryScope::Channel),
+            Some(ns) if is_scoped_namespace(ns) => None,
+            // Untiered session ids (e.g. gateway sessions) are shared.
+            Some(_) => Some(MemoryScope::Global),
+        }
+    }
+}
+
+/// Namespace for a channel sender.
+pub fn principal_namespace(channel: &str, sender: &str) -> String {
+    format!("{PRINCIPAL_PREFIX}{channel}:{sender}")
+}
+
+/// Namespace shared by everyone on `channel`.
+pub fn channel_namespace(channel: &str) -> String {
+    format!("{CHANNEL_PREFIX}{channel}")
+}
+
+fn is_scoped_namespace(ns: &str) -> bool {
+    ns.starts_with(PRINCIPAL_PREFIX) || ns.starts_with(CHANNEL_PREFIX)
+}
+
+/// Principal of the current task, if any.
+pub fn current_principal() -> Option<MemoryPrincipal> {
+    MEMORY_PRINCIPAL.try_with(Clone::clone).ok().flatten()
+}
+
+/// Storage key for `key` inside `namespace`. Keys are unique per backend, so
+/// two namespaces storing the same key must not overwrite each other.
+pub fn scoped_key(namespace: Option<&str>, key: &str) -> String {
+    match namespace {
+        Some(ns) => format!("{ns}/{key}"),
+        None => key.to_string(),
+    }
+}
+
+/// Key of `entry` without its namespace prefix.
+pub fn display_key(entry: &MemoryEntry) -> &str {
+    entry
+        .session_id
+        .as_deref()
+        .filter(|ns| is_scoped_namespace(ns))
+        .and_then(|ns| entry.key.strip_prefix(ns))
+        .and_then(|rest| rest.strip_prefix('/'))
+        .unwrap_or(&entry.key)
+}
+
+/// Whether `principal` may read `entry`.
+pub fn is_visible_to(entry: &MemoryEntry, principal: Option<&MemoryPrincipal>) -> bool {
+    principal.is_none_or(|p| p.scope_of(entry.session_id.as_deref()).is_some())
+}
+
+/// Whether `principal` may overwrite or delete `entry`.
+pub fn is_writable_by(entry: &MemoryEntry, principal: Option<&MemoryPrincipal>) -> bool {
+    principal.is_none_or(|p| {
+        p.scope_of(entry.session_id.as_deref())
+            .is_some_and(|scope| p.can_write(scope))
+    })
+}
+
+/// Recall up to `limit` entries visible to `principal`: its own entries and
+/// those of its channel and the global tier, best score first.
+pub async fn recall_for_principal(
+    memory: &dyn Memory,
+    query: &str,
+    limit: usize,
+    principal: Option<&MemoryPrincipal>,
+) -> anyhow::Result<Vec<MemoryEntry>> {
+    let Some(principal) = principal else {
+        return memory.recall(query, limit, None).await;
+    };
+
+    let mut entries = memory
+        .recall(query, limit, Some(&principal.namespace()))
+        .await?;
+    entries.extend(
+        memory
+            .recall(query, limit, Some(&principal.channel_namespace()))
+            .await?,
+    );
+    let mut seen: HashSet<String> = entries.iter().map(|e| e.id.clone()).collect();
+    let shared = memory
+        .recall(query, limit.saturating_mul(SHARED_RECALL_OVERFETCH), None)
+        .await?;
+    entries.extend(
+        shared
+            .into_iter()
+            .filter(|e| is_visible_to(e, Some(principal)) && seen.insert(e.id.clone())),
+    );
+    entries.sort_by(|a, b| {
+        b.score
+            .unwrap_or(0.0)
+            .partial_cmp(&a.score.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    entries.truncate(limit);
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryCategory, SqliteMemory};
+    use tempfile::TempDir;
+
+    fn principal(channel: &str, sender: &str) -> MemoryPrincipal {
+        MemoryPrincipal::new(channel, sender, MemoryAccessConfig::default())
+    }
+
+    async fn store_in(mem: &SqliteMemory, ns: Option<&str>, key: &str, content: &str) {
+        mem.store(&scoped_key(ns, key), content, MemoryCategory::Core, ns)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn principals_only_see_their_own_channel_and_global_tiers() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let alice = principal("telegram", "alice");
+        let bob = principal("telegram", "bob");
+        let carol = principal("discord", "carol");
+
+        store_in(&mem, Some(&alice.namespace()), "city", "Lives in Berlin").await;
+        store_in(&mem, Some(&bob.namespace()), "city", "Lives in Lisbon").await;
+        store_in(
+            &mem,
+            Some(&alice.channel_namespace()),
+            "group",
+            "Group lives on Telegram",
+        )
+        .await;
+        store_in(&mem, None, "office", "Office lives in Berlin HQ").await;
+
+        let seen = recall_for_principal(&mem, "lives", 10, Some(&alice))
+            .await
+            .unwrap();
+        let contents: Vec<&str> = seen.iter().map(|e| e.content.as_str()).collect();
+        assert!(contents.contains(&"Lives in Berlin"));
+        assert!(contents.contains(&"Group lives on Telegram"));
+        assert!(contents.contains(&"Office lives in Berlin HQ"));
+        assert!(!contents.contains(&"Lives in Lisbon"));
+
+        let own = seen
+            .iter()
+            .find(|e| e.content == "Lives in Berlin")
+            .unwrap();
+        assert_eq!(display_key(own), "city");
+
+        let other_channel = recall_for_principal(&mem, "lives", 10, Some(&carol))
+            .await
+            .unwrap();
+        assert_eq!(other_channel.len(), 1);
+        assert_eq!(other_channel[0].content, "Office lives in Berlin HQ");
+
+        // Without a principal (operator/CLI), everything is visible.
+        assert_eq!(
+            recall_for_principal(&mem, "lives", 10, None)
+                .await
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn write_access_follows_config() {
+        let alice = principal("telegram", "alice");
+        assert!(alice.can_write(MemoryScope::Principal));
+        assert!(alice.can_write(MemoryScope::Channel));
+        assert!(!alice.can_write(MemoryScope::Global));
+
+        let locked = MemoryPrincipal::new(
+            "telegram",
+            "alice",
+            MemoryAccessConfig {
+                channel_write: false,
+                global_write: false,
+            },
+        );
+        assert!(!locked.can_write(MemoryScope::Channel));
+        assert_eq!(MemoryScope::parse("Shared"), Some(MemoryScope::Global));
+        assert_eq!(MemoryScope::parse("team"), None);
+    }
+}
@@ -1,6 +1,7 @@
 use super::embeddings::EmbeddingProvider;
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use super::vector;
+use crate::db::{add_column_if_missing, Migration, Schema, Step};
 use anyhow::Context;
 use async_trait::async_trait;
 use chrono::Local;
@@ -14,6 +15,73 @@ use std::thread;
 use std::time::Duration;
 use uuid::Uuid;
 
+/// Tables of `memory/brain.db`: memories, FTS5, `embedding_cache`.
+pub static SCHEMA: Schema = Schema {
+    name: "memory",
+    path: "memory/brain.db",
+    migrations: &[
+        Migration {
+            version: 1,
+            description: "create memories, memories_fts and embedding_cache",
+            step: Step::Sql(
+                "-- Core memories table
+                CREATE TABLE IF NOT EXISTS memories (
+                    id          TEXT PRIMARY KEY,
+                    key         TEXT NOT NULL UNIQUE,
+                    content     TEXT NOT NULL,
+                    category    TEXT NOT NULL DEFAULT 'core',
+                    embedding   BLOB,
+                    created_at  TEXT NOT NULL,
+                    updated_at  TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_memories_category ON memories(category);
+                CREATE INDEX IF NOT EXISTS idx_memories_key ON memories(key);
+
+                -- FTS5 full-text search (BM25 scoring)
+                CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
+                    key, content, content=memories, content_rowid=rowid
+                );
+
+                -- FTS5 triggers: keep in sync with memories table
+                CREATE TRIGGER IF NOT EXISTS memories_ai AFTER INSERT ON memories BEGIN
+                    INSERT INTO memories_fts(rowid, key, content)
+                    VALUES (new.rowid, new.key, new.content);
+                END;
+                CREATE TRIGGER IF NOT EXISTS memories_ad AFTER DELETE ON memories BEGIN
+                    INSERT INTO memories_fts(memories_fts, rowid, key, content)
+                    VALUES ('delete', old.rowid, old.key, old.content);
+                END;
+                CREATE TRIGGER IF NOT EXISTS memories_au AFTER UPDATE ON memories BEGIN
+                    INSERT INTO memories_fts(memories_fts, rowid, key, content)
+                    VALUES ('delete', old.rowid, old.key, old.content);
+                    INSERT INTO memories_fts(rowid, key, content)
+                    VALUES (new.rowid, new.key, new.content);
+                END;
+
+                -- Embedding cache with LRU eviction
+                CREATE TABLE IF NOT EXISTS embedding_cache (
+                    content_hash TEXT PRIMARY KEY,
+                    embedding    BLOB NOT NULL,
+                    created_at   TEXT NOT NULL,
+                    accessed_at  TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_cache_accessed ON embedding_cache(accessed_at);",
+            ),
+        },
+        Migration {
+            version: 2,
+            description: "add memories.session_id",
+            step: Step::Rust(add_session_id),
+        },
+    ],
+};
+
+fn add_session_id(conn: &Connection) -> anyhow::Result<()> {
+    add_column_if_missing(conn, "memories", "session_id", "TEXT")?;
+    conn.execute_batch("CREATE INDEX IF NOT EXISTS idx_memories_session ON memories(session_id);")?;
+    Ok(())
+}
+
 /// Maximum allowed open timeout (seconds) to avoid unreasonable waits.
 const SQLITE_OPEN_TIMEOUT_CAP_SECS: u64 = 300;
 
@@ -59,7 +127,7 @@ impl SqliteMemory {
         cache_max: usize,
         open_timeout_secs: Option<u64>,
     ) -> anyhow::Result<Self> {
-        let db_path = workspace_dir.join("memory").join("brain.db");
+        let db_path = SCHEMA.db_path(workspace_dir);
 
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent)?;
@@ -81,7 +149,7 @@ impl SqliteMemory {
              PRAGMA temp_store   = MEMORY;",
         )?;
 
-        Self::init_schema(&conn)?;
+        crate::db::migrate(&conn, &SCHEMA)?;
 
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
@@ -124,68 +192,6 @@ impl SqliteMemory {
         Ok(conn)
     }
 
-    /// Initialize all tables: memories, FTS5, `embedding_cache`
-    fn init_schema(conn: &Connection) -> anyhow::Result<()> {
-        conn.execute_batch(
-            "-- Core memories table
-            CREATE TABLE IF NOT EXISTS memories (
-                id          TEXT PRIMARY KEY,
-                key         TEXT NOT NULL UNIQUE,
-                content     TEXT NOT NULL,
-                category    TEXT NOT NULL DEFAULT 'core',
-                embedding   BLOB,
-                created_at  TEXT NOT NULL,
-                updated_at  TEXT NOT NULL
-            );
-            CREATE INDEX IF NOT EXISTS idx_memories_category ON memories(category);
-            CREATE INDEX IF NOT EXISTS idx_memories_key ON memories(key);
-
-            -- FTS5 full-text search (BM25 scoring)
-            CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
-                key, content, content=memories, content_rowid=rowid
-            );
-
-            -- FTS5 triggers: keep in sync with memories table
-            CREATE TRIGGER IF NOT EXISTS memories_ai AFTER INSERT ON memories BEGIN
-                INSERT INTO memories_fts(rowid, key, content)
-                VALUES (new.rowid, new.key, new.content);
-            END;
-            CREATE TRIGGER IF NOT EXISTS memories_ad AFTER DELETE ON memories BEGIN
-                INSERT INTO memories_fts(memories_fts, rowid, key, content)
-                VALUES ('delete', old.rowid, old.key, old.content);
-            END;
-            CREATE TRIGGER IF NOT EXISTS memories_au AFTER UPDATE ON memories BEGIN
-                INSERT INTO memories_fts(memories_fts, rowid, key, content)
-                VALUES ('delete', old.rowid, old.key, old.content);
-                INSERT INTO memories_fts(rowid, key, content)
-                VALUES (new.rowid, new.key, new.content);
-            END;
-
-            -- Embedding cache with LRU eviction
-            CREATE TABLE IF NOT EXISTS embedding_cache (
-                content_hash TEXT PRIMARY KEY,
-                embedding    BLOB NOT NULL,
-                created_at   TEXT NOT NULL,
-                accessed_at  TEXT NOT NULL
-            );
-            CREATE INDEX IF NOT EXISTS idx_cache_accessed ON embedding_cache(accessed_at);",
-        )?;
-
-        // Migration: add session_id column if not present (safe to run repeatedly)
-        let has_session_id: bool = conn
-            .prepare("SELECT sql FROM sqlite_master WHERE type='table' AND name='memories'")?
-            .query_row([], |row| row.get::<_, String>(0))?
-            .contains("session_id");
-        if !has_session_id {
-            conn.execute_batch(
-                "ALTER TABLE memories ADD COLUMN session_id TEXT;
-                 CREATE INDEX IF NOT EXISTS idx_memories_session ON memories(session_id);",
-            )?;
-        }
-
-        Ok(())
-    }
-
     fn category_to_str(cat: &MemoryCategory) -> String {
         match cat {
             MemoryCategory::Core => "core".into(),
@@ -1506,7 +1512,7 @@ mod tests {
                 .await
                 .unwrap();
         }
-        // Open again — init_schema runs again on existing DB
+        // Open again — migrations find the existing DB up to date
         let mem2 = SqliteMemory::new(tmp.path()).unwrap();
         let entry = mem2.get("k1").await.unwrap();
         assert!(entry.is_some());
@@ -0,0 +1,329 @@
+//! Push notifications through ntfy, Pushover and Gotify.
+//!
+//! Targets come from `[notify.targets.<name>]`. The `notify` tool, cron
+//! delivery (`channel = "notify"`, `to = "<name>"`) and heartbeat alerts all
+//! send through [`send`], so they reach a phone even when no chat channel is
+//! configured.
+
+use crate::config::{NotifyConfig, NotifyPriority, NotifyService, NotifyTargetConfig};
+use crate::util::truncate_with_ellipsis;
+use anyhow::{Context, Result};
+use serde_json::json;
+
+const NTFY_DEFAULT_SERVER: &str = "https://ntfy.sh";
+const PUSHOVER_API_URL: &str = "https://api.pushover.net/1/messages.json";
+const NOTIFY_REQUEST_TIMEOUT_SECS: u64 = 15;
+
+/// Pushover rejects longer messages.
+const PUSHOVER_MAX_MESSAGE_CHARS: usize = 1024;
+
+/// ntfy turns larger bodies into attachments; Gotify shares the cap so
+/// messages look the same everywhere.
+const MAX_MESSAGE_CHARS: usize = 4000;
+
+/// One push notification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub title: Option<String>,
+    pub message: String,
+    /// Overrides the target's configured priority.
+    pub priority: Option<NotifyPriority>,
+}
+
+impl Notification {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            title: None,
+            message: message.into(),
+            priority: None,
+        }
+    }
+}
+
+/// Parse a priority name (`low`, `normal`, `high`).
+pub fn parse_priority(raw: &str) -> Option<NotifyPriority> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "low" => Some(NotifyPriority::Low),
+        "normal" | "default" => Some(NotifyPriority::Normal),
+        "high" | "urgent" => Some(NotifyPriority::High),
+        _ => None,
+    }
+}
+
+/// The target called `name`, or the default target when `name` is unset.
+pub fn resolve_target<'a>(
+    config: &'a NotifyConfig,
+    name: Option<&str>,
+) -> Result<(&'a str, &'a NotifyTargetConfig)> {
+    let name = name.map(str::trim).filter(|name| !name.is_empty());
+    let name = match (name, config.default_target.as_deref()) {
+        (Some(name), _) | (None, Some(name)) => name,
+        (None, None) if config.targets.len() == 1 => config
+            .targets
+            .keys()
+            .next()
+            .map(String::as_str)
+            .unwrap_or_default(),
+        (None, None) if config.targets.is_empty() => {
+            anyhow::bail!("no push targets configured; add a [notify.targets.<name>] section")
+        }
+        (None, None) => anyhow::bail!(
+            "several push targets are configured; name one or set notify.default_target"
+        ),
+    };
+    config
+        .targets
+        .get_key_value(name)
+        .map(|(name, target)| (name.as_str(), target))
+        .ok_or_else(|| {
+            let mut known: Vec<&str> = config.targets.keys().map(String::as_str).collect();
+            known.sort_unstable();
+            anyhow::anyhow!(
+                "unknown notify target {name:?} (configured: {})",
+                known.join(", ")
+            )
+        })
+}
+
+/// Send `notification` to the target called `name` (or the default target)
+/// and return the name of the target used.
+pub async fn send(
+    config: &NotifyConfig,
+    name: Option<&str>,
+    notification: &Notification,
+) -> Result<String> {
+    let (name, target) = resolve_target(config, name)?;
+    let request = build_request(target, notification);
+    let client = crate::config::build_runtime_proxy_client_with_timeouts(
+        "tool.notify",
+        NOTIFY_REQUEST_TIMEOUT_SECS,
+        10,
+    );
+    let mut builder = client.post(&request.url);
+    for (header, value) in &request.headers {
+        builder = builder.header(*header, value);
+    }
+    builder = match &request.body {
+        PushBody::Json(body) => builder.json(body),
+        PushBody::Form(fields) => builder.form(fields),
+    };
+    let response = builder
+        .send()
+        .await
+        .with_context(|| format!("failed to reach push target {name}"))?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        anyhow::bail!(
+            "push target {name} returned {status}: {}",
+            truncate_with_ellipsis(body.trim(), 200)
+        );
+    }
+    if target.service == NotifyService::Pushover {
+        let accepted = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|json| json.get("status").and_then(serde_json::Value::as_i64))
+            == Some(1);
+        if !accepted {
+            anyhow::bail!("Pushover rejected the notification: {}", body.trim());
+        }
+    }
+    Ok(name.to_string())
+}
+
+/// Request body of a [`PushRequest`].
+#[derive(Debug, Clone, PartialEq)]
+enum PushBody {
+    Json(serde_json::Value),
+    Form(Vec<(&'static str, String)>),
+}
+
+/// A service-specific HTTP request, built separately from sending so it can
+/// be checked without a network.
+#[derive(Debug, Clone, PartialEq)]
+struct PushRequest {
+    url: String,
+    headers: Vec<(&'static str, String)>,
+    body: PushBody,
+}
+
+fn build_request(target: &NotifyTargetConfig, notification: &Notification) -> PushRequest {
+    let priority = notification.priority.unwrap_or(target.priority);
+    let token = target
+        .token
+        .as_deref()
+        .map(str::trim)
+        .filter(|token| !token.is_empty());
+    let server = target
+        .server
+        .as_deref()
+        .map(|server| server.trim().trim_end_matches('/'))
+        .filter(|server| !server.is_empty());
+    let title = notification
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|title| !title.is_empty());
+
+    match target.service {
+        NotifyService::Ntfy => {
+            let mut body = json!({
+                "topic": target.topic.as_deref().unwrap_or_default().trim(),
+                "message": truncate_with_ellipsis(&notification.message, MAX_MESSAGE_CHARS),
+                "priority": match priority {
+                    NotifyPriority::Low => 2,
+                    NotifyPriority::Normal => 3,
+                    NotifyPriority::High => 5,
+                },
+            });
+            if let Some(title) = title {
+                body["title"] = json!(title);
+            }
+            PushRequest {
+                url: server.unwrap_or(NTFY_DEFAULT_SERVER).to_string(),
+                headers: token
+                    .map(|token| ("Authorization", format!("Bearer {token}")))
+                    .into_iter()
+                    .collect(),
+                body: PushBody::Json(body),
+            }
+        }
+        NotifyService::Pushover => {
+            let mut fields = vec![
+                ("token", token.unwrap_or_default().to_string()),
+                (
+                    "user",
+                    target
+                        .user_key
+                        .as_deref()
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string(),
+                ),
+                (
+                    "message",
+                    truncate_with_ellipsis(&notification.message, PUSHOVER_MAX_MESSAGE_CHARS),
+                ),
+                (
+                    "priority",
+                    match priority {
+                        NotifyPriority::Low => "-1",
+                        NotifyPriority::Normal => "0",
+                        NotifyPriority::High => "1",
+                    }
+                    .to_string(),
+                ),
+            ];
+            if let Some(title) = title {
+                fields.push(("title", title.to_string()));
+            }
+            PushRequest {
+                url: PUSHOVER_API_URL.to_string(),
+                headers: Vec::new(),
+                body: PushBody::Form(fields),
+            }
+        }
+        NotifyService::Gotify => {
+            let mut body = json!({
+                "message": truncate_with_ellipsis(&notification.message, MAX_MESSAGE_CHARS),
+                "priority": match priority {
+                    NotifyPriority::Low => 2,
+                    NotifyPriority::Normal => 5,
+                    NotifyPriority::High => 8,
+                },
+            });
+            if let Some(title) = title {
+                body["title"] = json!(title);
+            }
+            PushRequest {
+                url: format!("{}/message", server.unwrap_or_default()),
+                headers: vec![("X-Gotify-Key", token.unwrap_or_default().to_string())],
+                body: PushBody::Json(body),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(toml: &str) -> NotifyTargetConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn requests_follow_each_service_api() {
+        let notification = Notification {
+            title: Some("Backup".into()),
+            message: "Nightly backup failed".into(),
+            priority: Some(NotifyPriority::High),
+        };
+
+        let ntfy = build_request(
+            &target("service = \"ntfy\"\ntopic = \"alerts\"\ntoken = \"tk_1\""),
+            &notification,
+        );
+        assert_eq!(ntfy.url, NTFY_DEFAULT_SERVER);
+        assert_eq!(
+            ntfy.headers,
+            vec![("Authorization", "Bearer tk_1".to_string())]
+        );
+        assert_eq!(
+            ntfy.body,
+            PushBody::Json(json!({
+                "topic": "alerts",
+                "title": "Backup",
+                "message": "Nightly backup failed",
+                "priority": 5,
+            }))
+        );
+
+        let pushover = build_request(
+            &target("service = \"pushover\"\ntoken = \"app\"\nuser_key = \"user\""),
+            &Notification::new("hello"),
+        );
+        assert_eq!(pushover.url, PUSHOVER_API_URL);
+        let PushBody::Form(fields) = pushover.body else {
+            panic!("pushover posts a form");
+        };
+        assert!(fields.contains(&("user", "user".to_string())));
+        assert!(fields.contains(&("priority", "0".to_string())));
+
+        let gotify = build_request(
+            &target(
+                "service = \"gotify\"\nserver = \"https://push.example.com/\"\ntoken = \"A1\"\npriority = \"low\"",
+            ),
+            &Notification::new("hello"),
+        );
+        assert_eq!(gotify.url, "https://push.example.com/message");
+        assert_eq!(gotify.headers, vec![("X-Gotify-Key", "A1".to_string())]);
+        assert_eq!(
+            gotify.body,
+            PushBody::Json(json!({ "message": "hello", "priority": 2 }))
+        );
+    }
+
+    #[test]
+    fn targets_resolve_by_name_or_default() {
+        let mut config = NotifyConfig::default();
+        assert!(resolve_target(&config, None).is_err());
+
+        config
+            .targets
+            .insert("phone".into(), target("service = \"ntfy\"\ntopic = \"a\""));
+        assert_eq!(resolve_target(&config, None).unwrap().0, "phone");
+
+        config
+            .targets
+            .insert("desk".into(), target("service = \"ntfy\"\ntopic = \"b\""));
+        assert!(resolve_target(&config, Some(" ")).is_err());
+        assert_eq!(resolve_target(&config, Some("desk")).unwrap().0, "desk");
+        config.default_target = Some("desk".into());
+        assert_eq!(resolve_target(&config, None).unwrap().0, "desk");
+
+        let err = resolve_target(&config, Some("pager")).unwrap_err();
+        assert!(err.to_string().contains("configured: desk, phone"));
+        assert_eq!(parse_priority("HIGH"), Some(NotifyPriority::High));
+    }
+}
@@ -0,0 +1,334 @@
+//! Incident reports.
+//!
+//! [`init_from_config`] installs a panic hook on top of the default one:
+//! the panic is still printed to stderr, and with
+//! `observability.incident_reports = true` it is also written to
+//! `<workspace>/state/incidents/<id>.json` with its location, a backtrace,
+//! the thread and, when it happened inside a turn, the turn id, channel and
+//! last tool the turn started (see [`super::turn`]). A daemon that exits
+//! with an error gets a report too. Messages and backtraces are scrubbed of
+//! credentials; only the newest `incident_reports_max` reports are kept.
+//!
+//! With `incident_channel` / `incident_to` set, a short notice goes to that
+//! admin channel: right away when the process survives the panic (a
+//! panicking task), otherwise from the next daemon start through
+//! [`notify_pending`]. `zeroclaw doctor incidents` lists the reports.
+
+use crate::config::Config;
+use crate::util::truncate_with_ellipsis;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Once, RwLock};
+
+const INCIDENTS_REL_DIR: &str = "state/incidents";
+/// Longest panic or error message kept.
+const MAX_MESSAGE_CHARS: usize = 2_000;
+
+/// What went wrong, without prompt or tool argument text.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IncidentReport {
+    pub id: String,
+    pub occurred_at: String,
+    /// `"panic"` or `"error"`.
+    pub kind: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_tool: Option<String>,
+    pub backtrace: String,
+    pub version: String,
+    /// The admin channel has been told.
+    #[serde(default)]
+    pub notified: bool,
+}
+
+impl IncidentReport {
+    fn new(kind: &str, message: &str, backtrace: &Backtrace) -> Self {
+        let turn = super::turn::snapshot();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            occurred_at: chrono::Utc::now().to_rfc3339(),
+            kind: kind.to_string(),
+            message: redact(&truncate_with_ellipsis(message, MAX_MESSAGE_CHARS)),
+            location: None,
+            thread: std::thread::current().name().map(str::to_string),
+            turn_id: turn.as_ref().map(|turn| turn.turn_id.clone()),
+            channel: turn.as_ref().map(|turn| turn.channel.clone()),
+            last_tool: turn.and_then(|turn| turn.last_tool),
+            backtrace: redact(&backtrace.to_string()),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            notified: false,
+        }
+    }
+
+    /// Short notice for the admin channel.
+    pub fn notice(&self) -> String {
+        let mut out = format!(
+            "ZeroClaw {} {} at {}: {}",
+            self.version, self.kind, self.occurred_at, self.message
+        );
+        if let Some(location) = &self.location {
+            let _ = write!(out, "\nLocation: {location}");
+        }
+        if let Some(turn_id) = &self.turn_id {
+            let _ = write!(
+                out,
+                "\nTurn: {turn_id} on {}",
+                self.channel.as_deref().unwrap_or("?")
+            );
+            if let Some(tool) = &self.last_tool {
+                let _ = write!(out, ", last tool `{tool}`");
+            }
+        }
+        let _ = write!(out, "\nReport: zeroclaw doctor incidents {}", self.id);
+        out
+    }
+}
+
+fn redact(text: &str) -> String {
+    crate::agent::loop_::scrub_credentials(&crate::providers::scrub_secret_patterns(text))
+}
+
+struct IncidentSink {
+    dir: PathBuf,
+    max_reports: usize,
+    /// Set when an admin channel should be notified.
+    notify: Option<Arc<Config>>,
+}
+
+static SINK: LazyLock<RwLock<Option<Arc<IncidentSink>>>> = LazyLock::new(|| RwLock::new(None));
+static HOOK: Once = Once::new();
+
+/// Directory holding the incident reports of `workspace_dir`.
+pub fn incidents_dir(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join(INCIDENTS_REL_DIR)
+}
+
+/// Apply `[observability]` incident settings and install the panic hook.
+pub fn init_from_config(config: &Config) {
+    let observability = &config.observability;
+    let sink = observability.incident_reports.then(|| {
+        Arc::new(IncidentSink {
+            dir: incidents_dir(&config.workspace_dir),
+            max_reports: observability.incident_reports_max.max(1),
+            notify: observability
+                .incident_channel
+                .is_some()
+                .then(|| Arc::new(config.clone())),
+        })
+    });
+    *SINK.write().unwrap_or_else(|e| e.into_inner()) = sink;
+
+    HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            previous(info);
+            on_panic(info);
+        }));
+    });
+}
+
+fn current_sink() -> Option<Arc<IncidentSink>> {
+    SINK.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn on_panic(info: &std::panic::PanicHookInfo<'_>) {
+    let Some(sink) = current_sink() else {
+        return;
+    };
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panic with a non-string payload".to_string());
+    let mut report = IncidentReport::new("panic", &message, &Backtrace::force_capture());
+    report.location = info.location().map(|location| {
+        format!(
+            "{}:{}:{}",
+            location.file(),
+            location.line(),
+            location.column()
+        )
+    });
+    if !write_logged(&sink, &report) {
+        return;
+    }
+
+    // A panicking task leaves the runtime running; tell the admin now.
+    // Otherwise the next start sends it.
+    if let (Some(config), Ok(handle)) = (sink.notify.clone(), tokio::runtime::Handle::try_current())
+    {
+        handle.spawn(async move { notify(&config, report).await });
+    }
+}
+
+/// Record the error a daemon is exiting with and notify the admin channel.
+pub async fn record_error(err: &anyhow::Error) {
+    let Some(sink) = current_sink() else {
+        return;
+    };
+    let report = IncidentReport::new("error", &format!("{err:#}"), err.backtrace());
+    if write_logged(&sink, &report) {
+        if let Some(config) = sink.notify.as_deref() {
+            notify(config, report).await;
+        }
+    }
+}
+
+/// Write `report`, saying where on stderr. Never panics: it runs inside the
+/// panic hook.
+fn write_logged(sink: &IncidentSink, report: &IncidentReport) -> bool {
+    match write_report(&sink.dir, report, sink.max_reports) {
+        Ok(path) => {
+            eprintln!("Incident report written to {}", path.display());
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to write incident report: {e:#}");
+            false
+        }
+    }
+}
+
+fn write_report(dir: &Path, report: &IncidentReport, max_reports: usize) -> Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.json", report.id));
+    fs::write(&path, serde_json::to_vec_pretty(report)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let mut reports = report_files(dir)?;
+    if reports.len() > max_reports {
+        reports.sort_by_key(|(_, modified)| *modified);
+        let excess = reports.len() - max_reports;
+        for (old, _) in reports.into_iter().take(excess) {
+            let _ = fs::remove_file(old);
+        }
+    }
+    Ok(path)
+}
+
+fn report_files(dir: &Path) -> Result<Vec<(PathBuf, std::time::SystemTime)>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            let modified = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::UNIX_EPOCH);
+            files.push((path, modified));
+        }
+    }
+    Ok(files)
+}
+
+/// Incident reports of `workspace_dir`, newest first.
+pub fn list_reports(workspace_dir: &Path) -> Result<Vec<IncidentReport>> {
+    let mut files = report_files(&incidents_dir(workspace_dir))?;
+    files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+    let mut reports = Vec::with_capacity(files.len());
+    for (path, _) in files {
+        let raw = fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        match serde_json::from_slice(&raw) {
+            Ok(report) => reports.push(report),
+            Err(e) => tracing::warn!("Skipping malformed incident report {}: {e}", path.display()),
+        }
+    }
+    Ok(reports)
+}
+
+async fn notify(config: &Config, mut report: IncidentReport) {
+    let observability = &config.observability;
+    let (Some(channel), Some(to)) = (
+        observability.incident_channel.as_deref(),
+        observability.incident_to.as_deref(),
+    ) else {
+        return;
+    };
+    if let Err(e) =
+        crate::cron::scheduler::deliver_announcement(config, channel, to, &report.notice()).await
+    {
+        tracing::warn!("Failed to send incident {} to {channel}: {e:#}", report.id);
+        return;
+    }
+    report.notified = true;
+    let dir = incidents_dir(&config.workspace_dir);
+    if let Err(e) = write_report(&dir, &report, observability.incident_reports_max.max(1)) {
+        tracing::warn!("Failed to update incident report {}: {e:#}", report.id);
+    }
+}
+
+/// Send the incidents the admin channel has not been told about, such as a
+/// panic that took the previous process down.
+pub async fn notify_pending(config: &Config) {
+    if !config.observability.incident_reports || config.observability.incident_channel.is_none() {
+        return;
+    }
+    let reports = match list_reports(&config.workspace_dir) {
+        Ok(reports) => reports,
+        Err(e) => {
+            tracing::warn!("Failed to read incident reports: {e:#}");
+            return;
+        }
+    };
+    // Oldest first, so the admin reads them in order.
+    for report in reports.into_iter().rev().filter(|report| !report.notified) {
+        notify(config, report).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::turn;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn reports_capture_turn_context_redact_and_prune() {
+        let tmp = TempDir::new().unwrap();
+        let dir = incidents_dir(tmp.path());
+
+        let report = turn::in_turn("telegram", None, async {
+            turn::note_tool("shell");
+            IncidentReport::new(
+                "panic",
+                "request failed: api_key=sk-live-abcdefghijklmnop",
+                &Backtrace::disabled(),
+            )
+        })
+        .await;
+        assert_eq!(report.channel.as_deref(), Some("telegram"));
+        assert_eq!(report.last_tool.as_deref(), Some("shell"));
+        assert!(report.turn_id.is_some());
+        assert!(!report.message.contains("abcdefghijklmnop"));
+        assert!(report.notice().contains("last tool `shell`"));
+
+        write_report(&dir, &report, 2).unwrap();
+        for _ in 0..2 {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            let other = IncidentReport::new("error", "daemon stopped", &Backtrace::disabled());
+            assert!(other.turn_id.is_none());
+            write_report(&dir, &other, 2).unwrap();
+        }
+
+        let reports = list_reports(tmp.path()).unwrap();
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|r| r.kind == "error"));
+    }
+}
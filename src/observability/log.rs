@@ -44,6 +44,16 @@ impl Observer for LogObserver {
             ObserverEvent::ChannelMessage { channel, direction } => {
                 info!(channel = %channel, direction = %direction, "channel.message");
             }
+            ObserverEvent::ApprovalDecision {
+                tool,
+                channel,
+                decision,
+            } => {
+                info!(tool = %tool, channel = %channel, decision = %decision, "approval.decision");
+            }
+            ObserverEvent::LeakDetected { channel, source } => {
+                info!(channel = %channel, source = %source, "guardrail.leak_detected");
+            }
             ObserverEvent::HeartbeatTick => {
                 info!("heartbeat.tick");
             }
@@ -101,6 +111,9 @@ impl Observer for LogObserver {
             ObserverMetric::QueueDepth(d) => {
                 info!(depth = d, "metric.queue_depth");
             }
+            ObserverMetric::DatabaseSize { database, bytes } => {
+                info!(database = %database, bytes = bytes, "metric.database_size");
+            }
         }
     }
 
@@ -1,4 +1,5 @@
 pub mod cost;
+pub mod incident;
 pub mod log;
 pub mod multi;
 pub mod noop;
@@ -7,6 +8,8 @@ pub mod otel;
 pub mod prometheus;
 pub mod runtime_trace;
 pub mod traits;
+pub mod turn;
+pub mod turn_report;
 pub mod verbose;
 
 pub use cost::CostObserver;
@@ -18,7 +21,7 @@ pub use noop::NoopObserver;
 #[cfg(feature = "observability-otel")]
 pub use otel::OtelObserver;
 pub use prometheus::PrometheusObserver;
-pub use traits::{Observer, ObserverEvent};
+pub use traits::{find_observer, Observer, ObserverEvent};
 #[allow(unused_imports)]
 pub use verbose::VerboseObserver;
 
@@ -58,7 +61,7 @@ pub fn create_observer_with_cost_tracking(
 fn create_observer_internal(config: &ObservabilityConfig) -> Box<dyn Observer> {
     match config.backend.as_str() {
         "log" => Box::new(LogObserver::new()),
-        "prometheus" => Box::new(PrometheusObserver::new()),
+        "prometheus" => Box::new(PrometheusObserver::shared()),
         "otel" | "opentelemetry" | "otlp" => {
             #[cfg(feature = "observability-otel")]
             match OtelObserver::new(
@@ -38,6 +38,10 @@ impl Observer for MultiObserver {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn children(&self) -> Vec<&dyn Observer> {
+        self.observers.iter().map(|obs| obs.as_ref()).collect()
+    }
 }
 
 #[cfg(test)]
@@ -160,4 +164,17 @@ mod tests {
         assert_eq!(fc1.load(Ordering::SeqCst), 1);
         assert_eq!(fc2.load(Ordering::SeqCst), 1);
     }
+
+    #[test]
+    fn find_observer_reaches_wrapped_backends() {
+        let m = MultiObserver::new(vec![
+            Box::new(crate::observability::NoopObserver),
+            Box::new(crate::observability::PrometheusObserver::new()),
+        ]);
+        assert!(
+            crate::observability::find_observer::<crate::observability::PrometheusObserver>(&m)
+                .is_some()
+        );
+        assert!(crate::observability::find_observer::<CountingObserver>(&m).is_none());
+    }
 }
@@ -1,6 +1,7 @@
 use super::traits::{Observer, ObserverEvent, ObserverMetric};
+use opentelemetry::global::{BoxedSpan, BoxedTracer};
 use opentelemetry::metrics::{Counter, Gauge, Histogram};
-use opentelemetry::trace::{Span, SpanKind, Status, Tracer};
+use opentelemetry::trace::{Span, SpanBuilder, SpanKind, Status, TraceId, Tracer};
 use opentelemetry::{global, KeyValue};
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::metrics::SdkMeterProvider;
@@ -21,12 +22,15 @@ pub struct OtelObserver {
     tool_calls: Counter<u64>,
     tool_duration: Histogram<f64>,
     channel_messages: Counter<u64>,
+    approval_decisions: Counter<u64>,
+    leak_detections: Counter<u64>,
     heartbeat_ticks: Counter<u64>,
     errors: Counter<u64>,
     request_latency: Histogram<f64>,
     tokens_used: Counter<u64>,
     active_sessions: Gauge<u64>,
     queue_depth: Gauge<u64>,
+    database_size: Gauge<u64>,
 }
 
 impl OtelObserver {
@@ -121,6 +125,16 @@ impl OtelObserver {
             .with_description("Total channel messages")
             .build();
 
+        let approval_decisions = meter
+            .u64_counter("zeroclaw.approval.decisions")
+            .with_description("Total tool approval decisions")
+            .build();
+
+        let leak_detections = meter
+            .u64_counter("zeroclaw.leak.detections")
+            .with_description("Total credential leaks redacted from outbound replies")
+            .build();
+
         let heartbeat_ticks = meter
             .u64_counter("zeroclaw.heartbeat.ticks")
             .with_description("Total heartbeat ticks")
@@ -152,6 +166,12 @@ impl OtelObserver {
             .with_description("Current message queue depth")
             .build();
 
+        let database_size = meter
+            .u64_gauge("zeroclaw.database.size")
+            .with_description("On-disk size of local databases, including the WAL")
+            .with_unit("By")
+            .build();
+
         Ok(Self {
             tracer_provider,
             meter_provider: meter_provider_clone,
@@ -162,16 +182,34 @@ impl OtelObserver {
             tool_calls,
             tool_duration,
             channel_messages,
+            approval_decisions,
+            leak_detections,
             heartbeat_ticks,
             errors,
             request_latency,
             tokens_used,
             active_sessions,
             queue_depth,
+            database_size,
         })
     }
 }
 
+/// Build a span inside the current turn's trace. The turn id (a UUID) is
+/// used as the trace id, so every span of one turn groups into one trace.
+fn build_in_turn(tracer: &BoxedTracer, builder: SpanBuilder) -> BoxedSpan {
+    let Some(turn_id) = super::turn::current_turn_id() else {
+        return tracer.build(builder);
+    };
+    let builder = match uuid::Uuid::parse_str(&turn_id) {
+        Ok(id) => builder.with_trace_id(TraceId::from_bytes(*id.as_bytes())),
+        Err(_) => builder,
+    };
+    let mut span = tracer.build(builder);
+    span.set_attribute(KeyValue::new("zeroclaw.turn_id", turn_id));
+    span
+}
+
 impl Observer for OtelObserver {
     fn record_event(&self, event: &ObserverEvent) {
         let tracer = global::tracer("zeroclaw");
@@ -211,8 +249,9 @@ impl Observer for OtelObserver {
                 let start_time = SystemTime::now()
                     .checked_sub(*duration)
                     .unwrap_or(SystemTime::now());
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("llm.call")
+                let mut span = build_in_turn(
+                    &tracer,
+                    SpanBuilder::from_name("llm.call")
                         .with_kind(SpanKind::Internal)
                         .with_start_time(start_time)
                         .with_attributes(vec![
@@ -242,8 +281,9 @@ impl Observer for OtelObserver {
                     .unwrap_or(SystemTime::now());
 
                 // Create a completed span with correct timing
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("agent.invocation")
+                let mut span = build_in_turn(
+                    &tracer,
+                    SpanBuilder::from_name("agent.invocation")
                         .with_kind(SpanKind::Internal)
                         .with_start_time(start_time)
                         .with_attributes(vec![
@@ -286,8 +326,9 @@ impl Observer for OtelObserver {
                     Status::error("")
                 };
 
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("tool.call")
+                let mut span = build_in_turn(
+                    &tracer,
+                    SpanBuilder::from_name("tool.call")
                         .with_kind(SpanKind::Internal)
                         .with_start_time(start_time)
                         .with_attributes(vec![
@@ -308,6 +349,17 @@ impl Observer for OtelObserver {
                     .record(secs, &[KeyValue::new("tool", tool.clone())]);
             }
             ObserverEvent::ChannelMessage { channel, direction } => {
+                let mut span = build_in_turn(
+                    &tracer,
+                    SpanBuilder::from_name("channel.message")
+                        .with_kind(SpanKind::Internal)
+                        .with_attributes(vec![
+                            KeyValue::new("channel", channel.clone()),
+                            KeyValue::new("direction", direction.clone()),
+                        ]),
+                );
+                span.end();
+
                 self.channel_messages.add(
                     1,
                     &[
@@ -316,13 +368,49 @@ impl Observer for OtelObserver {
                     ],
                 );
             }
+            ObserverEvent::ApprovalDecision {
+                tool,
+                channel,
+                decision,
+            } => {
+                let mut span = build_in_turn(
+                    &tracer,
+                    SpanBuilder::from_name("approval.decision")
+                        .with_kind(SpanKind::Internal)
+                        .with_attributes(vec![
+                            KeyValue::new("tool.name", tool.clone()),
+                            KeyValue::new("channel", channel.clone()),
+                            KeyValue::new("decision", decision.clone()),
+                        ]),
+                );
+                span.end();
+
+                self.approval_decisions.add(
+                    1,
+                    &[
+                        KeyValue::new("tool", tool.clone()),
+                        KeyValue::new("channel", channel.clone()),
+                        KeyValue::new("decision", decision.clone()),
+                    ],
+                );
+            }
+            ObserverEvent::LeakDetected { channel, source } => {
+                self.leak_detections.add(
+                    1,
+                    &[
+                        KeyValue::new("channel", channel.clone()),
+                        KeyValue::new("source", source.clone()),
+                    ],
+                );
+            }
             ObserverEvent::HeartbeatTick => {
                 self.heartbeat_ticks.add(1, &[]);
             }
             ObserverEvent::Error { component, message } => {
                 // Create an error span for visibility in trace backends
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("error")
+                let mut span = build_in_turn(
+                    &tracer,
+                    SpanBuilder::from_name("error")
                         .with_kind(SpanKind::Internal)
                         .with_attributes(vec![
                             KeyValue::new("component", component.clone()),
@@ -352,6 +440,10 @@ impl Observer for OtelObserver {
             ObserverMetric::QueueDepth(d) => {
                 self.queue_depth.record(*d as u64, &[]);
             }
+            ObserverMetric::DatabaseSize { database, bytes } => {
+                self.database_size
+                    .record(*bytes, &[KeyValue::new("database", database.clone())]);
+            }
         }
     }
 
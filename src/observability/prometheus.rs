@@ -2,8 +2,12 @@ use super::traits::{Observer, ObserverEvent, ObserverMetric};
 use prometheus::{
     Encoder, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounterVec, Registry, TextEncoder,
 };
+use std::sync::OnceLock;
 
 /// Prometheus-backed observer — exposes metrics for scraping via `/metrics`.
+///
+/// Clones share the same registry and metric handles.
+#[derive(Clone)]
 pub struct PrometheusObserver {
     registry: Registry,
 
@@ -14,11 +18,15 @@ pub struct PrometheusObserver {
     tokens_output_total: IntCounterVec,
     tool_calls: IntCounterVec,
     channel_messages: IntCounterVec,
+    approval_decisions: IntCounterVec,
+    leak_detections: IntCounterVec,
+    egress_requests: IntCounterVec,
     heartbeat_ticks: prometheus::IntCounter,
     errors: IntCounterVec,
 
     // Histograms
     agent_duration: HistogramVec,
+    llm_duration: HistogramVec,
     tool_duration: HistogramVec,
     request_latency: Histogram,
 
@@ -26,9 +34,18 @@ pub struct PrometheusObserver {
     tokens_used: prometheus::IntGauge,
     active_sessions: GaugeVec,
     queue_depth: GaugeVec,
+    database_size: GaugeVec,
 }
 
 impl PrometheusObserver {
+    /// Process-wide observer used by the `prometheus` backend, so the
+    /// gateway, channels, and agents started by one daemon report into the
+    /// registry served at `/metrics`.
+    pub fn shared() -> Self {
+        static SHARED: OnceLock<PrometheusObserver> = OnceLock::new();
+        SHARED.get_or_init(Self::new).clone()
+    }
+
     pub fn new() -> Self {
         let registry = Registry::new();
 
@@ -71,6 +88,33 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let approval_decisions = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_approval_decisions_total",
+                "Total tool approval decisions",
+            ),
+            &["tool", "channel", "decision"],
+        )
+        .expect("valid metric");
+
+        let leak_detections = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_leak_detections_total",
+                "Total credential leaks redacted from outbound replies",
+            ),
+            &["channel", "source"],
+        )
+        .expect("valid metric");
+
+        let egress_requests = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_egress_requests_total",
+                "Network tool requests by host and egress policy outcome",
+            ),
+            &["host", "outcome"],
+        )
+        .expect("valid metric");
+
         let heartbeat_ticks =
             prometheus::IntCounter::new("zeroclaw_heartbeat_ticks_total", "Total heartbeat ticks")
                 .expect("valid metric");
@@ -91,6 +135,16 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let llm_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "zeroclaw_llm_duration_seconds",
+                "LLM provider call duration in seconds",
+            )
+            .buckets(vec![0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0]),
+            &["provider", "model"],
+        )
+        .expect("valid metric");
+
         let tool_duration = HistogramVec::new(
             HistogramOpts::new(
                 "zeroclaw_tool_duration_seconds",
@@ -128,6 +182,15 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let database_size = GaugeVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_database_size_bytes",
+                "On-disk size of local databases, including the WAL",
+            ),
+            &["database"],
+        )
+        .expect("valid metric");
+
         // Register all metrics
         registry.register(Box::new(agent_starts.clone())).ok();
         registry.register(Box::new(llm_requests.clone())).ok();
@@ -137,14 +200,19 @@ impl PrometheusObserver {
             .ok();
         registry.register(Box::new(tool_calls.clone())).ok();
         registry.register(Box::new(channel_messages.clone())).ok();
+        registry.register(Box::new(approval_decisions.clone())).ok();
+        registry.register(Box::new(leak_detections.clone())).ok();
+        registry.register(Box::new(egress_requests.clone())).ok();
         registry.register(Box::new(heartbeat_ticks.clone())).ok();
         registry.register(Box::new(errors.clone())).ok();
         registry.register(Box::new(agent_duration.clone())).ok();
+        registry.register(Box::new(llm_duration.clone())).ok();
         registry.register(Box::new(tool_duration.clone())).ok();
         registry.register(Box::new(request_latency.clone())).ok();
         registry.register(Box::new(tokens_used.clone())).ok();
         registry.register(Box::new(active_sessions.clone())).ok();
         registry.register(Box::new(queue_depth.clone())).ok();
+        registry.register(Box::new(database_size.clone())).ok();
 
         Self {
             registry,
@@ -154,25 +222,51 @@ impl PrometheusObserver {
             tokens_output_total,
             tool_calls,
             channel_messages,
+            approval_decisions,
+            leak_detections,
+            egress_requests,
             heartbeat_ticks,
             errors,
             agent_duration,
+            llm_duration,
             tool_duration,
             request_latency,
             tokens_used,
             active_sessions,
             queue_depth,
+            database_size,
         }
     }
 
     /// Encode all registered metrics into Prometheus text exposition format.
     pub fn encode(&self) -> String {
+        self.sync_egress_counters();
         let encoder = TextEncoder::new();
         let families = self.registry.gather();
         let mut buf = Vec::new();
         encoder.encode(&families, &mut buf).unwrap_or_default();
         String::from_utf8(buf).unwrap_or_default()
     }
+
+    /// Catch the egress counters up with the process-wide egress policies,
+    /// which count requests themselves.
+    fn sync_egress_counters(&self) {
+        for (host, stats) in crate::security::egress::process_stats() {
+            for (outcome, total) in [
+                ("allowed", stats.allowed),
+                ("blocked", stats.blocked),
+                ("rate_limited", stats.rate_limited),
+            ] {
+                if total == 0 {
+                    continue;
+                }
+                let counter = self
+                    .egress_requests
+                    .with_label_values(&[host.as_str(), outcome]);
+                counter.inc_by(total.saturating_sub(counter.get()));
+            }
+        }
+    }
 }
 
 impl Observer for PrometheusObserver {
@@ -201,6 +295,7 @@ impl Observer for PrometheusObserver {
             ObserverEvent::LlmResponse {
                 provider,
                 model,
+                duration,
                 success,
                 input_tokens,
                 output_tokens,
@@ -210,6 +305,9 @@ impl Observer for PrometheusObserver {
                 self.llm_requests
                     .with_label_values(&[provider.as_str(), model.as_str(), success_str])
                     .inc();
+                self.llm_duration
+                    .with_label_values(&[provider.as_str(), model.as_str()])
+                    .observe(duration.as_secs_f64());
                 if let Some(input) = input_tokens {
                     self.tokens_input_total
                         .with_label_values(&[provider.as_str(), model.as_str()])
@@ -242,6 +340,20 @@ impl Observer for PrometheusObserver {
                     .with_label_values(&[channel, direction])
                     .inc();
             }
+            ObserverEvent::ApprovalDecision {
+                tool,
+                channel,
+                decision,
+            } => {
+                self.approval_decisions
+                    .with_label_values(&[tool, channel, decision])
+                    .inc();
+            }
+            ObserverEvent::LeakDetected { channel, source } => {
+                self.leak_detections
+                    .with_label_values(&[channel, source])
+                    .inc();
+            }
             ObserverEvent::HeartbeatTick => {
                 self.heartbeat_ticks.inc();
             }
@@ -272,6 +384,11 @@ impl Observer for PrometheusObserver {
                     .with_label_values(&[] as &[&str])
                     .set(*d as f64);
             }
+            ObserverMetric::DatabaseSize { database, bytes } => {
+                self.database_size
+                    .with_label_values(&[database])
+                    .set(*bytes as f64);
+            }
         }
     }
 
@@ -493,4 +610,91 @@ mod tests {
         assert!(!output.contains("zeroclaw_tokens_input_total{"));
         assert!(!output.contains("zeroclaw_tokens_output_total{"));
     }
+
+    #[test]
+    fn approvals_leaks_and_llm_latency_are_exported() {
+        let obs = PrometheusObserver::new();
+        obs.record_event(&ObserverEvent::ApprovalDecision {
+            tool: "shell".into(),
+            channel: "telegram".into(),
+            decision: "no".into(),
+        });
+        obs.record_event(&ObserverEvent::LeakDetected {
+            channel: "slack".into(),
+            source: "response".into(),
+        });
+        obs.record_event(&ObserverEvent::LlmResponse {
+            provider: "openrouter".into(),
+            model: "claude-sonnet".into(),
+            duration: Duration::from_millis(750),
+            success: true,
+            error_message: None,
+            input_tokens: None,
+            output_tokens: None,
+        });
+
+        let output = obs.encode();
+        assert!(output.contains(
+            r#"zeroclaw_approval_decisions_total{channel="telegram",decision="no",tool="shell"} 1"#
+        ));
+        assert!(output
+            .contains(r#"zeroclaw_leak_detections_total{channel="slack",source="response"} 1"#));
+        assert!(output.contains(
+            r#"zeroclaw_llm_duration_seconds_count{model="claude-sonnet",provider="openrouter"} 1"#
+        ));
+    }
+
+    #[test]
+    fn shared_observer_reports_into_one_registry() {
+        PrometheusObserver::shared().record_event(&ObserverEvent::LeakDetected {
+            channel: "shared-test".into(),
+            source: "draft".into(),
+        });
+        let output = PrometheusObserver::shared().encode();
+        assert!(output.contains(r#"channel="shared-test",source="draft""#));
+    }
+
+    #[test]
+    fn egress_counters_are_exported() {
+        use crate::config::{EgressConfig, UrlAccessConfig};
+        use crate::security::EgressPolicy;
+        use crate::tools::url_validation::{DomainPolicy, UrlSchemePolicy};
+
+        let egress = EgressPolicy::shared(
+            UrlAccessConfig::default(),
+            &EgressConfig {
+                rate_limit_per_minute: 0,
+                domain_rate_limits: [("egress-metrics.example.net".to_string(), 1)]
+                    .into_iter()
+                    .collect(),
+            },
+        );
+        let allowed = vec!["egress-metrics.example.net".to_string()];
+        let rules = DomainPolicy {
+            allowed_domains: &allowed,
+            blocked_domains: &[],
+            allowed_field_name: "test.allowed_domains",
+            blocked_field_name: None,
+            empty_allowed_message: "no domains",
+            scheme_policy: UrlSchemePolicy::HttpOrHttps,
+            ipv6_error_context: "test",
+            url_access: None,
+        };
+        for _ in 0..3 {
+            let _ = egress.authorize("web_fetch", "https://egress-metrics.example.net", &rules);
+        }
+
+        let obs = PrometheusObserver::new();
+        let output = obs.encode();
+        assert!(output.contains(
+            r#"zeroclaw_egress_requests_total{host="egress-metrics.example.net",outcome="allowed"} 1"#
+        ));
+        assert!(output.contains(
+            r#"zeroclaw_egress_requests_total{host="egress-metrics.example.net",outcome="rate_limited"} 2"#
+        ));
+        // Encoding again does not count the same requests twice.
+        assert!(obs.encode().contains(
+            r#"zeroclaw_egress_requests_total{host="egress-metrics.example.net",outcome="rate_limited"} 2"#
+        ));
+    }
 }
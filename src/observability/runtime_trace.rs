@@ -192,7 +192,8 @@ pub fn init_from_config(config: &ObservabilityConfig, workspace_dir: &Path) {
     *guard = logger;
 }
 
-/// Record a runtime trace event.
+/// Record a runtime trace event. Without an explicit `turn_id`, the event
+/// is tagged with the current turn (see [`super::turn`]).
 pub fn record_event(
     event_type: &str,
     channel: Option<&str>,
@@ -218,7 +219,9 @@ pub fn record_event(
         channel: channel.map(str::to_string),
         provider: provider.map(str::to_string),
         model: model.map(str::to_string),
-        turn_id: turn_id.map(str::to_string),
+        turn_id: turn_id
+            .map(str::to_string)
+            .or_else(super::turn::current_turn_id),
         success,
         message: message.map(str::to_string),
         payload,
@@ -325,6 +328,7 @@ mod tests {
             runtime_trace_mode: "rolling".to_string(),
             runtime_trace_path: "state/runtime-trace.jsonl".to_string(),
             runtime_trace_max_entries: 3,
+            ..ObservabilityConfig::default()
         }
     }
 
@@ -56,6 +56,20 @@ pub enum ObserverEvent {
         /// `"inbound"` or `"outbound"`.
         direction: String,
     },
+    /// A tool approval prompt was answered (or timed out).
+    ApprovalDecision {
+        tool: String,
+        /// Channel that raised the prompt (e.g., `"cli"`, `"telegram"`).
+        channel: String,
+        /// `"yes"`, `"no"`, or `"always"`.
+        decision: String,
+    },
+    /// The output guardrail redacted a credential from an outbound reply.
+    LeakDetected {
+        channel: String,
+        /// `"response"` for final replies, `"draft"` for streamed updates.
+        source: String,
+    },
     /// Periodic heartbeat tick from the runtime keep-alive loop.
     HeartbeatTick,
     /// An error occurred in a named component.
@@ -81,6 +95,8 @@ pub enum ObserverMetric {
     ActiveSessions(u64),
     /// Current depth of the inbound message queue.
     QueueDepth(u64),
+    /// On-disk size of a local database, including its write-ahead log.
+    DatabaseSize { database: String, bytes: u64 },
 }
 
 /// Core observability trait for recording agent runtime telemetry.
@@ -124,6 +140,23 @@ pub trait Observer: Send + Sync + 'static {
     /// Enables callers to access concrete observer types when needed
     /// (e.g., retrieving a Prometheus registry handle for custom metrics).
     fn as_any(&self) -> &dyn std::any::Any;
+
+    /// Observers this one forwards to, so [`find_observer`] can reach a
+    /// backend behind fan-out or forwarding wrappers.
+    fn children(&self) -> Vec<&dyn Observer> {
+        Vec::new()
+    }
+}
+
+/// Find the first observer of type `T` in `observer` or its wrapped children.
+pub fn find_observer<T: 'static>(observer: &dyn Observer) -> Option<&T> {
+    if let Some(found) = observer.as_any().downcast_ref::<T>() {
+        return Some(found);
+    }
+    observer
+        .children()
+        .into_iter()
+        .find_map(|child| find_observer::<T>(child))
 }
 
 #[cfg(test)]
@@ -0,0 +1,163 @@
+//! Per-turn correlation ids.
+//!
+//! A turn id is assigned when a message is accepted (channel dispatch,
+//! gateway request, CLI prompt) and held in a task-local for the rest of the
+//! turn. The turn also runs inside a `turn` tracing span, and approval
+//! checks, provider requests, tool calls and outbound sends open child spans
+//! under it, so every log line of a turn carries its `turn_id`. Runtime trace
+//! events and OTLP spans (`observability.backend = "otel"`) use the same id,
+//! which also serves as the OTLP trace id. When turn reports are enabled the
+//! turn also collects a [`TurnReport`], written when it ends.
+//!
+//! Work spawned onto other tasks (fact extraction, memory autosave) leaves
+//! the turn and is not correlated.
+//!
+//! The turn also remembers the last tool it started, so an incident report
+//! (see [`super::incident`]) can say what a panicking turn was doing.
+
+use super::turn_report::{self, TurnReport};
+use parking_lot::Mutex;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::Instrument;
+use uuid::Uuid;
+
+struct ActiveTurn {
+    id: String,
+    channel: String,
+    last_tool: Mutex<Option<String>>,
+    report: Option<Mutex<TurnReport>>,
+}
+
+/// What the current task's turn is doing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnSnapshot {
+    pub turn_id: String,
+    pub channel: String,
+    pub last_tool: Option<String>,
+}
+
+tokio::task_local! {
+    static CURRENT_TURN: Arc<ActiveTurn>;
+}
+
+/// Fresh turn id (a UUID v4 string).
+pub fn new_turn_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+/// Id of the turn the current task is running, if any.
+pub fn current_turn_id() -> Option<String> {
+    CURRENT_TURN.try_with(|turn| turn.id.clone()).ok()
+}
+
+/// The current task's turn, if any. Never blocks, so it is safe to call
+/// from a panic hook.
+pub fn snapshot() -> Option<TurnSnapshot> {
+    CURRENT_TURN
+        .try_with(|turn| TurnSnapshot {
+            turn_id: turn.id.clone(),
+            channel: turn.channel.clone(),
+            last_tool: turn.last_tool.try_lock().and_then(|tool| (*tool).clone()),
+        })
+        .ok()
+}
+
+/// Remember that the current turn is starting `tool`.
+pub fn note_tool(tool: &str) {
+    let _ = CURRENT_TURN.try_with(|turn| {
+        *turn.last_tool.lock() = Some(tool.to_string());
+    });
+}
+
+/// Add to the current turn's report. No-op outside a turn or when turn
+/// reports are disabled.
+pub fn record(update: impl FnOnce(&mut TurnReport)) {
+    let _ = CURRENT_TURN.try_with(|turn| {
+        if let Some(report) = turn.report.as_ref() {
+            update(&mut report.lock());
+        }
+    });
+}
+
+/// Run `fut` as a new turn on `channel`. When a turn is already active the
+/// future joins it instead, so nested entry points (a gateway request
+/// calling the shared agent path) keep one id.
+pub async fn in_turn<F: Future>(channel: &str, message_id: Option<&str>, fut: F) -> F::Output {
+    if CURRENT_TURN.try_with(|_| ()).is_ok() {
+        return fut.await;
+    }
+    let turn_id = new_turn_id();
+    let span = tracing::info_span!(
+        "turn",
+        turn_id = %turn_id,
+        channel = %channel,
+        message_id = tracing::field::Empty,
+    );
+    if let Some(message_id) = message_id {
+        span.record("message_id", message_id);
+    }
+    let report = turn_report::enabled().then(|| {
+        Mutex::new(TurnReport {
+            turn_id: turn_id.clone(),
+            channel: channel.to_string(),
+            message_id: message_id.map(str::to_string),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            ..TurnReport::default()
+        })
+    });
+    let turn = Arc::new(ActiveTurn {
+        id: turn_id,
+        channel: channel.to_string(),
+        last_tool: Mutex::new(None),
+        report,
+    });
+    let started = Instant::now();
+    let output = CURRENT_TURN
+        .scope(Arc::clone(&turn), fut.instrument(span))
+        .await;
+
+    if let Some(report) = turn.report.as_ref() {
+        let mut report = report.lock().clone();
+        report.duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        turn_report::persist(&report);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn nested_turns_keep_the_outer_id() {
+        assert!(current_turn_id().is_none());
+        let (outer, inner) = in_turn("telegram", Some("m1"), async {
+            let outer = current_turn_id();
+            let inner = in_turn("gateway", None, async { current_turn_id() }).await;
+            (outer, inner)
+        })
+        .await;
+        assert!(outer.is_some());
+        assert_eq!(outer, inner);
+        assert!(current_turn_id().is_none());
+
+        let other = in_turn("cli", None, async { current_turn_id() }).await;
+        assert_ne!(other, outer);
+    }
+
+    #[tokio::test]
+    async fn snapshot_reports_channel_and_last_tool() {
+        assert!(snapshot().is_none());
+        let snap = in_turn("slack", None, async {
+            note_tool("shell");
+            note_tool("file_read");
+            snapshot()
+        })
+        .await
+        .expect("inside a turn");
+        assert_eq!(snap.channel, "slack");
+        assert_eq!(snap.last_tool.as_deref(), Some("file_read"));
+    }
+}
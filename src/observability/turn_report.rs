@@ -0,0 +1,216 @@
+//! Per-turn execution reports.
+//!
+//! With `observability.turn_reports = true`, each turn (see [`super::turn`])
+//! writes `<workspace>/state/turn-reports/<turn_id>.json` when it ends: the
+//! tools it ran with their durations, token counts, approval decisions,
+//! redactions and a SHA-256 of the final response. Reports never contain
+//! prompt, argument or response text. Only the newest `turn_reports_max`
+//! files are kept.
+//!
+//! Reports are looked up by turn id or by the channel message id that
+//! started the turn; `zeroclaw doctor report <id>` and the gateway
+//! `GET /api/turns/{id}/report` both go through [`find_report`].
+
+use crate::config::ObservabilityConfig;
+use crate::workspace::Scoped;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const REPORTS_REL_DIR: &str = "state/turn-reports";
+
+/// Execution report of one turn.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TurnReport {
+    pub turn_id: String,
+    pub channel: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub tools: Vec<ToolInvocation>,
+    pub llm_calls: u32,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub approvals: Vec<ApprovalRecord>,
+    /// Where the output guardrail redacted credentials (`"response"`, ...).
+    pub redactions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolInvocation {
+    pub tool: String,
+    pub duration_ms: u64,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalRecord {
+    pub tool: String,
+    /// `"yes"`, `"no"` or `"always"`.
+    pub decision: String,
+}
+
+impl TurnReport {
+    /// Remember the final response by hash only.
+    pub fn set_response(&mut self, response: &str) {
+        self.response_sha256 = Some(hex::encode(Sha256::digest(response.as_bytes())));
+    }
+}
+
+struct ReportWriter {
+    dir: PathBuf,
+    max_reports: usize,
+}
+
+impl ReportWriter {
+    fn write(&self, report: &TurnReport) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(format!("{}.json", report.turn_id));
+        fs::write(&path, serde_json::to_vec_pretty(report)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        self.prune()
+    }
+
+    fn prune(&self) -> Result<()> {
+        let mut reports = report_files(&self.dir)?;
+        if reports.len() <= self.max_reports {
+            return Ok(());
+        }
+        reports.sort_by_key(|(_, modified)| *modified);
+        let excess = reports.len() - self.max_reports;
+        for (path, _) in reports.into_iter().take(excess) {
+            let _ = fs::remove_file(path);
+        }
+        Ok(())
+    }
+}
+
+static REPORT_WRITER: Scoped<Option<Arc<ReportWriter>>> = Scoped::new(None);
+
+/// Directory holding the reports of `workspace_dir`.
+pub fn reports_dir(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join(REPORTS_REL_DIR)
+}
+
+/// Enable (or disable) turn reports.
+pub fn init_from_config(config: &ObservabilityConfig, workspace_dir: &Path) {
+    let writer = config.turn_reports.then(|| {
+        Arc::new(ReportWriter {
+            dir: reports_dir(workspace_dir),
+            max_reports: config.turn_reports_max.max(1),
+        })
+    });
+    REPORT_WRITER.set(writer);
+}
+
+/// Whether turns should collect a report.
+pub(crate) fn enabled() -> bool {
+    REPORT_WRITER.get().is_some()
+}
+
+/// Persist a finished report. Failures are logged, never raised: a report
+/// must not fail the turn it describes.
+pub(crate) fn persist(report: &TurnReport) {
+    if let Some(writer) = REPORT_WRITER.get() {
+        if let Err(e) = writer.write(report) {
+            tracing::warn!("Failed to write turn report: {e:#}");
+        }
+    }
+}
+
+fn report_files(dir: &Path) -> Result<Vec<(PathBuf, std::time::SystemTime)>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            let modified = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::UNIX_EPOCH);
+            files.push((path, modified));
+        }
+    }
+    Ok(files)
+}
+
+/// Report for a turn id or a channel message id. When a message id was
+/// reused (a resumed task), the newest report wins.
+pub fn find_report(workspace_dir: &Path, id: &str) -> Result<Option<TurnReport>> {
+    let dir = reports_dir(workspace_dir);
+    if !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        let path = dir.join(format!("{id}.json"));
+        if path.exists() {
+            return Ok(Some(read_report(&path)?));
+        }
+    }
+
+    let mut files = report_files(&dir)?;
+    files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+    for (path, _) in files {
+        match read_report(&path) {
+            Ok(report) if report.message_id.as_deref() == Some(id) => return Ok(Some(report)),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Skipping unreadable turn report: {e:#}"),
+        }
+    }
+    Ok(None)
+}
+
+fn read_report(path: &Path) -> Result<TurnReport> {
+    let raw = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_slice(&raw).with_context(|| format!("Malformed report {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn report(turn_id: &str, message_id: Option<&str>) -> TurnReport {
+        TurnReport {
+            turn_id: turn_id.into(),
+            channel: "telegram".into(),
+            message_id: message_id.map(str::to_string),
+            ..TurnReport::default()
+        }
+    }
+
+    #[test]
+    fn reports_are_found_by_turn_or_message_id_and_pruned() {
+        let tmp = TempDir::new().unwrap();
+        let writer = ReportWriter {
+            dir: reports_dir(tmp.path()),
+            max_reports: 2,
+        };
+        writer.write(&report("t1", Some("m1"))).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        writer.write(&report("t2", Some("m2"))).unwrap();
+
+        let mut with_response = report("t3", Some("m3"));
+        with_response.set_response("hello");
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        writer.write(&with_response).unwrap();
+
+        assert!(find_report(tmp.path(), "t1").unwrap().is_none());
+        assert_eq!(
+            find_report(tmp.path(), "m2").unwrap().unwrap().turn_id,
+            "t2"
+        );
+        let found = find_report(tmp.path(), "t3").unwrap().unwrap();
+        assert_eq!(found, with_response);
+        assert_eq!(
+            found.response_sha256.as_deref(),
+            Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+    }
+}
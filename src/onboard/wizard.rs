@@ -144,6 +144,8 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
     let config = Config {
         workspace_dir: workspace_dir.clone(),
         config_path: config_path.clone(),
+        secret_refs: Vec::new(),
+        layers: Default::default(),
         api_key: if api_key.is_empty() {
             None
         } else {
@@ -168,8 +170,18 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         skills: crate::config::SkillsConfig::default(),
         model_routes: Vec::new(),
         embedding_routes: Vec::new(),
+        provider_capabilities: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
+        feeds: crate::config::FeedsConfig::default(),
+        analytics: crate::config::AnalyticsConfig::default(),
+        mailbox: crate::config::MailboxConfig::default(),
+        prometheus: crate::config::PrometheusConfig::default(),
+        jira: crate::config::JiraConfig::default(),
+        linear: crate::config::LinearConfig::default(),
+        kb: crate::config::KbConfig::default(),
+        convert_document: crate::config::ConvertDocumentConfig::default(),
+        personas: crate::config::PersonasConfig::default(),
         goal_loop: crate::config::schema::GoalLoopConfig::default(),
         channels_config,
         memory: memory_config, // User-selected memory backend
@@ -178,7 +190,12 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         gateway: crate::config::GatewayConfig::default(),
         composio: composio_config,
         secrets: secrets_config,
+        session: crate::config::SessionConfig::default(),
+        attachments: crate::config::AttachmentsConfig::default(),
+        i18n: crate::config::I18nConfig::default(),
+        notify: crate::config::NotifyConfig::default(),
         browser: BrowserConfig::default(),
+        shell_session: crate::config::ShellSessionConfig::default(),
         http_request: http_request_config,
         multimodal: crate::config::MultimodalConfig::default(),
         web_fetch: web_fetch_config,
@@ -197,6 +214,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         agents_ipc: crate::config::AgentsIpcConfig::default(),
         mcp: crate::config::schema::McpConfig::default(),
         model_support_vision: None,
+        model_support_tools: None,
         wasm: crate::config::WasmConfig::default(),
     };
 
@@ -410,6 +428,8 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
             0
         },
         chunk_max_tokens: 512,
+        principal_isolation: true,
+        fact_extraction: false,
         response_cache_enabled: false,
         response_cache_ttl_minutes: 60,
         response_cache_max_entries: 5_000,
@@ -418,6 +438,8 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         auto_hydrate: true,
         sqlite_open_timeout_secs: None,
         qdrant: crate::config::QdrantConfig::default(),
+        decay: crate::config::MemoryDecayConfig::default(),
+        access: crate::config::MemoryAccessConfig::default(),
     }
 }
 
@@ -509,6 +531,8 @@ async fn run_quick_setup_with_home(
     let mut config = Config {
         workspace_dir: workspace_dir.clone(),
         config_path: config_path.clone(),
+        secret_refs: Vec::new(),
+        layers: Default::default(),
         api_key: credential_override.map(|c| {
             let mut s = String::with_capacity(c.len());
             s.push_str(c);
@@ -533,8 +557,18 @@ async fn run_quick_setup_with_home(
         skills: crate::config::SkillsConfig::default(),
         model_routes: Vec::new(),
         embedding_routes: Vec::new(),
+        provider_capabilities: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
+        feeds: crate::config::FeedsConfig::default(),
+        analytics: crate::config::AnalyticsConfig::default(),
+        mailbox: crate::config::MailboxConfig::default(),
+        prometheus: crate::config::PrometheusConfig::default(),
+        jira: crate::config::JiraConfig::default(),
+        linear: crate::config::LinearConfig::default(),
+        kb: crate::config::KbConfig::default(),
+        convert_document: crate::config::ConvertDocumentConfig::default(),
+        personas: crate::config::PersonasConfig::default(),
         goal_loop: crate::config::schema::GoalLoopConfig::default(),
         channels_config: ChannelsConfig::default(),
         memory: memory_config,
@@ -543,7 +577,12 @@ async fn run_quick_setup_with_home(
         gateway: crate::config::GatewayConfig::default(),
         composio: ComposioConfig::default(),
         secrets: SecretsConfig::default(),
+        session: crate::config::SessionConfig::default(),
+        attachments: crate::config::AttachmentsConfig::default(),
+        i18n: crate::config::I18nConfig::default(),
+        notify: crate::config::NotifyConfig::default(),
         browser: BrowserConfig::default(),
+        shell_session: crate::config::ShellSessionConfig::default(),
         http_request: crate::config::HttpRequestConfig::default(),
         multimodal: crate::config::MultimodalConfig::default(),
         web_fetch: crate::config::WebFetchConfig::default(),
@@ -562,6 +601,7 @@ async fn run_quick_setup_with_home(
         agents_ipc: crate::config::AgentsIpcConfig::default(),
         mcp: crate::config::schema::McpConfig::default(),
         model_support_vision: None,
+        model_support_tools: None,
         wasm: crate::config::WasmConfig::default(),
     };
     if no_totp {
@@ -733,6 +773,10 @@ fn canonical_provider_name(provider_name: &str) -> &str {
 }
 
 fn allows_unauthenticated_model_fetch(provider_name: &str) -> bool {
+    if let Some(base_url) = provider_name.trim().strip_prefix("custom:") {
+        return crate::providers::is_local_endpoint(base_url);
+    }
+
     matches!(
         canonical_provider_name(provider_name),
         "openrouter"
@@ -1519,13 +1563,18 @@ fn fetch_gemini_models(api_key: Option<&str>) -> Result<Vec<String>> {
     Ok(parse_gemini_model_ids(&payload))
 }
 
-fn fetch_ollama_models() -> Result<Vec<String>> {
+fn fetch_ollama_models(provider_api_url: Option<&str>) -> Result<Vec<String>> {
+    let base_url = provider_api_url
+        .map(normalize_ollama_endpoint_url)
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| "http://localhost:11434".to_string());
+    let endpoint = format!("{base_url}/api/tags");
     let client = build_model_fetch_client()?;
     let payload: Value = client
-        .get("http://localhost:11434/api/tags")
+        .get(&endpoint)
         .send()
         .and_then(reqwest::blocking::Response::error_for_status)
-        .context("model fetch failed: GET http://localhost:11434/api/tags")?
+        .with_context(|| format!("model fetch failed: GET {endpoint}"))?
         .json()
         .context("failed to parse Ollama model list response")?;
 
@@ -1545,10 +1594,7 @@ fn normalize_ollama_endpoint_url(raw_url: &str) -> String {
 }
 
 fn ollama_endpoint_is_local(endpoint_url: &str) -> bool {
-    reqwest::Url::parse(endpoint_url)
-        .ok()
-        .and_then(|url| url.host_str().map(|host| host.to_ascii_lowercase()))
-        .is_some_and(|host| matches!(host.as_str(), "localhost" | "127.0.0.1" | "::1" | "0.0.0.0"))
+    crate::providers::is_local_endpoint(endpoint_url)
 }
 
 fn ollama_uses_remote_endpoint(provider_api_url: Option<&str>) -> bool {
@@ -1664,7 +1710,7 @@ fn fetch_live_models_for_provider(
                 ]
             } else {
                 // Local endpoints should not surface cloud-only suffixes.
-                fetch_ollama_models()?
+                fetch_ollama_models(provider_api_url)?
                     .into_iter()
                     .filter(|model_id| !model_id.ends_with(":cloud"))
                     .collect()
@@ -3349,7 +3395,10 @@ fn setup_tool_mode() -> Result<(ComposioConfig, SecretsConfig)> {
         .default(true)
         .interact()?;
 
-    let secrets_config = SecretsConfig { encrypt };
+    let secrets_config = SecretsConfig {
+        encrypt,
+        ..SecretsConfig::default()
+    };
 
     if encrypt {
         println!(
@@ -4266,6 +4315,9 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     },
                     allowed_users,
                     group_reply: None,
+                    stream_mode: StreamMode::default(),
+                    draft_update_interval_ms: 1000,
+                    app_home: false,
                 });
             }
             ChannelMenuChoice::IMessage => {
@@ -7510,6 +7562,12 @@ mod tests {
         assert!(allows_unauthenticated_model_fetch("vllm"));
         assert!(!allows_unauthenticated_model_fetch("openai"));
         assert!(!allows_unauthenticated_model_fetch("deepseek"));
+        assert!(allows_unauthenticated_model_fetch(
+            "custom:http://localhost:8080/v1"
+        ));
+        assert!(!allows_unauthenticated_model_fetch(
+            "custom:https://api.example.com/v1"
+        ));
     }
 
     #[test]
@@ -7825,6 +7883,9 @@ mod tests {
         assert!(!ollama_uses_remote_endpoint(Some(
             "http://127.0.0.1:11434/api"
         )));
+        assert!(!ollama_uses_remote_endpoint(Some(
+            "http://192.168.1.20:11434"
+        )));
         assert!(ollama_uses_remote_endpoint(Some("https://ollama.com")));
         assert!(ollama_uses_remote_endpoint(Some("https://ollama.com/api")));
     }
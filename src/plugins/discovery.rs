@@ -79,8 +79,9 @@ fn scan_dir(dir: &Path, origin: PluginOrigin) -> (Vec<DiscoveredPlugin>, Vec<Plu
 /// Search order (later wins on ID conflict, matching OpenClaw's precedence):
 /// 1. Bundled: `<binary_dir>/extensions/`
 /// 2. Global: `~/.zeroclaw/extensions/`
-/// 3. Workspace: `<workspace>/.zeroclaw/extensions/`
-/// 4. Extra paths from config `[plugins] load_paths`
+/// 3. Skill packs: `<workspace>/skills/<pack>/plugins/`
+/// 4. Workspace: `<workspace>/.zeroclaw/extensions/`
+/// 5. Extra paths from config `[plugins] load_paths`
 pub fn discover_plugins(workspace_dir: Option<&Path>, extra_paths: &[PathBuf]) -> DiscoveryResult {
     let mut all_plugins = Vec::new();
     let mut all_diagnostics = Vec::new();
@@ -103,7 +104,16 @@ pub fn discover_plugins(workspace_dir: Option<&Path>, extra_paths: &[PathBuf]) -
         all_diagnostics.extend(d);
     }
 
-    // 3. Workspace — <workspace>/.zeroclaw/extensions/
+    // 3. Skill packs — <workspace>/skills/<pack>/plugins/
+    if let Some(ws) = workspace_dir {
+        for pack_plugins in crate::skills::plugin_dirs(ws) {
+            let (p, d) = scan_dir(&pack_plugins, PluginOrigin::Workspace);
+            all_plugins.extend(p);
+            all_diagnostics.extend(d);
+        }
+    }
+
+    // 4. Workspace — <workspace>/.zeroclaw/extensions/
     if let Some(ws) = workspace_dir {
         let ws_ext = ws.join(".zeroclaw").join("extensions");
         let (p, d) = scan_dir(&ws_ext, PluginOrigin::Workspace);
@@ -111,7 +121,7 @@ pub fn discover_plugins(workspace_dir: Option<&Path>, extra_paths: &[PathBuf]) -
         all_diagnostics.extend(d);
     }
 
-    // 4. Extra paths from config
+    // 5. Extra paths from config
     for extra in extra_paths {
         let (p, d) = scan_dir(extra, PluginOrigin::Global);
         all_plugins.extend(p);
@@ -174,6 +184,23 @@ version = "0.1.0"
         assert!(result.plugins.iter().any(|p| p.manifest.id == "my-plugin"));
     }
 
+    #[test]
+    fn discover_from_skill_packs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ws = tmp.path().join("project");
+        let pack_plugins = ws.join("skills").join("release-pack").join("plugins");
+        fs::create_dir_all(&pack_plugins).unwrap();
+        make_plugin_dir(&pack_plugins, "changelog");
+
+        let result = discover_plugins(Some(&ws), &[]);
+        let plugin = result
+            .plugins
+            .iter()
+            .find(|p| p.manifest.id == "changelog")
+            .expect("skill pack plugin");
+        assert_eq!(plugin.origin, PluginOrigin::Workspace);
+    }
+
     #[test]
     fn discover_from_extra_paths() {
         let tmp = tempfile::tempdir().unwrap();
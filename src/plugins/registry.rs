@@ -25,7 +25,7 @@ pub enum PluginOrigin {
     Bundled,
     /// Found in `~/.zeroclaw/extensions/`.
     Global,
-    /// Found in `<workspace>/.zeroclaw/extensions/`.
+    /// Found in `<workspace>/.zeroclaw/extensions/` or a skill pack.
     Workspace,
 }
 
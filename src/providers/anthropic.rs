@@ -1,6 +1,7 @@
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
     Provider, ProviderCapabilities, TokenUsage, ToolCall as ProviderToolCall,
+    PROMPT_CACHE_BOUNDARY,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -148,6 +149,12 @@ struct AnthropicUsage {
     input_tokens: Option<u64>,
     #[serde(default)]
     output_tokens: Option<u64>,
+    /// Prompt tokens written to the cache (not counted in `input_tokens`).
+    #[serde(default)]
+    cache_creation_input_tokens: Option<u64>,
+    /// Prompt tokens read from the cache (not counted in `input_tokens`).
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -388,9 +395,18 @@ impl AnthropicProvider {
             }
         }
 
-        // Convert system text to SystemPrompt with cache control if large
-        let system_prompt = system_text.map(|text| {
-            if Self::should_cache_system(&text) {
+        let system_prompt = system_text.map(Self::build_system_prompt);
+
+        (system_prompt, native_messages)
+    }
+
+    /// Convert system text to a SystemPrompt. Text before a
+    /// `PROMPT_CACHE_BOUNDARY` is cached on its own so per-message additions
+    /// after it do not invalidate the prefix; without a boundary the whole
+    /// prompt is cached if large.
+    fn build_system_prompt(text: String) -> SystemPrompt {
+        let Some((prefix, rest)) = text.split_once(PROMPT_CACHE_BOUNDARY) else {
+            return if Self::should_cache_system(&text) {
                 SystemPrompt::Blocks(vec![SystemBlock {
                     block_type: "text".to_string(),
                     text,
@@ -398,10 +414,29 @@ impl AnthropicProvider {
                 }])
             } else {
                 SystemPrompt::String(text)
-            }
-        });
+            };
+        };
 
-        (system_prompt, native_messages)
+        let prefix = prefix.trim_end();
+        let rest = rest.replace(PROMPT_CACHE_BOUNDARY, "");
+        let rest = rest.trim_start();
+        if !Self::should_cache_system(prefix) {
+            return SystemPrompt::String(format!("{prefix}\n\n{rest}").trim().to_string());
+        }
+
+        let mut blocks = vec![SystemBlock {
+            block_type: "text".to_string(),
+            text: prefix.to_string(),
+            cache_control: Some(CacheControl::ephemeral()),
+        }];
+        if !rest.is_empty() {
+            blocks.push(SystemBlock {
+                block_type: "text".to_string(),
+                text: rest.to_string(),
+                cache_control: None,
+            });
+        }
+        SystemPrompt::Blocks(blocks)
     }
 
     fn parse_text_response(response: ChatResponse) -> anyhow::Result<String> {
@@ -417,9 +452,16 @@ impl AnthropicProvider {
         let mut text_parts = Vec::new();
         let mut tool_calls = Vec::new();
 
-        let usage = response.usage.map(|u| TokenUsage {
-            input_tokens: u.input_tokens,
-            output_tokens: u.output_tokens,
+        let usage = response.usage.map(|u| {
+            if let Some(cached) = u.cache_read_input_tokens.filter(|n| *n > 0) {
+                tracing::debug!(cached_input_tokens = cached, "Anthropic prompt cache hit");
+            }
+            let cache_tokens =
+                u.cache_creation_input_tokens.unwrap_or(0) + u.cache_read_input_tokens.unwrap_or(0);
+            TokenUsage {
+                input_tokens: u.input_tokens.map(|n| n + cache_tokens),
+                output_tokens: u.output_tokens,
+            }
         });
 
         for block in response.content {
@@ -484,7 +526,7 @@ impl Provider for AnthropicProvider {
         let request = ChatRequest {
             model: model.to_string(),
             max_tokens: 4096,
-            system: system_prompt.map(ToString::to_string),
+            system: system_prompt.map(|text| text.replace(PROMPT_CACHE_BOUNDARY, "")),
             messages: vec![Message {
                 role: "user".to_string(),
                 content: message.to_string(),
@@ -559,6 +601,10 @@ impl Provider for AnthropicProvider {
         true
     }
 
+    fn supports_prompt_caching(&self) -> bool {
+        true
+    }
+
     fn capabilities(&self) -> ProviderCapabilities {
         ProviderCapabilities {
             native_tool_calling: true,
@@ -1192,6 +1238,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn convert_messages_caches_prefix_before_boundary() {
+        let prefix = "p".repeat(4000);
+        let messages = vec![ChatMessage::system(format!(
+            "{prefix}\n{PROMPT_CACHE_BOUNDARY}\nPinned notes for alice"
+        ))];
+
+        let (system_prompt, _) = AnthropicProvider::convert_messages(&messages);
+
+        match system_prompt.unwrap() {
+            SystemPrompt::Blocks(blocks) => {
+                assert_eq!(blocks.len(), 2);
+                assert_eq!(blocks[0].text, prefix);
+                assert!(blocks[0].cache_control.is_some());
+                assert_eq!(blocks[1].text, "Pinned notes for alice");
+                assert!(blocks[1].cache_control.is_none());
+            }
+            SystemPrompt::String(_) => panic!("Expected Blocks variant for cached prefix"),
+        }
+    }
+
+    #[test]
+    fn convert_messages_strips_boundary_from_small_prefix() {
+        let messages = vec![ChatMessage::system(format!(
+            "Short prefix\n{PROMPT_CACHE_BOUNDARY}\nSuffix"
+        ))];
+
+        let (system_prompt, _) = AnthropicProvider::convert_messages(&messages);
+
+        match system_prompt.unwrap() {
+            SystemPrompt::String(s) => assert_eq!(s, "Short prefix\n\nSuffix"),
+            SystemPrompt::Blocks(_) => panic!("Expected String variant for small prefix"),
+        }
+    }
+
+    #[test]
+    fn native_response_counts_cached_input_tokens() {
+        let json = r#"{
+            "content": [{"type": "text", "text": "Hi"}],
+            "usage": {
+                "input_tokens": 20,
+                "output_tokens": 5,
+                "cache_creation_input_tokens": 100,
+                "cache_read_input_tokens": 3000
+            }
+        }"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let usage = AnthropicProvider::parse_native_response(resp)
+            .usage
+            .unwrap();
+        assert_eq!(usage.input_tokens, Some(3120));
+        assert_eq!(usage.output_tokens, Some(5));
+    }
+
     #[test]
     fn backward_compatibility_native_chat_request() {
         // Test that requests without cache_control serialize identically to old format
@@ -7,7 +7,7 @@
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
     Provider, ProviderCapabilities, StreamChunk, StreamError, StreamOptions, StreamResult,
-    TokenUsage, ToolCall as ProviderToolCall, ToolsPayload,
+    TokenUsage, ToolCall as ProviderToolCall, ToolsPayload, PROMPT_CACHE_BOUNDARY,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -534,6 +534,43 @@ impl BedrockProvider {
         messages.iter().filter(|m| m.role != "system").count() > 4
     }
 
+    /// System prompt blocks with a cachePoint after the stable prefix
+    /// (text before `PROMPT_CACHE_BOUNDARY`), or after the whole prompt when
+    /// there is no boundary and it is large.
+    fn system_blocks(text: &str) -> Vec<SystemBlock> {
+        let text_block = |text: &str| {
+            SystemBlock::Text(TextBlock {
+                text: text.to_string(),
+            })
+        };
+        let cache_point = || {
+            SystemBlock::CachePoint(CachePointWrapper {
+                cache_point: CachePoint::default_cache(),
+            })
+        };
+
+        let Some((prefix, rest)) = text.split_once(PROMPT_CACHE_BOUNDARY) else {
+            let mut blocks = vec![text_block(text)];
+            if Self::should_cache_system(text) {
+                blocks.push(cache_point());
+            }
+            return blocks;
+        };
+
+        let prefix = prefix.trim_end();
+        let rest = rest.replace(PROMPT_CACHE_BOUNDARY, "");
+        let rest = rest.trim_start();
+        if !Self::should_cache_system(prefix) {
+            return vec![text_block(format!("{prefix}\n\n{rest}").trim())];
+        }
+
+        let mut blocks = vec![text_block(prefix), cache_point()];
+        if !rest.is_empty() {
+            blocks.push(text_block(rest));
+        }
+        blocks
+    }
+
     // ── Message conversion ──────────────────────────────────────
 
     fn convert_messages(
@@ -546,9 +583,7 @@ impl BedrockProvider {
             match msg.role.as_str() {
                 "system" => {
                     if system_blocks.is_empty() {
-                        system_blocks.push(SystemBlock::Text(TextBlock {
-                            text: msg.content.clone(),
-                        }));
+                        system_blocks = Self::system_blocks(&msg.content);
                     }
                 }
                 "assistant" => {
@@ -1244,17 +1279,7 @@ impl Provider for BedrockProvider {
     ) -> anyhow::Result<String> {
         let credentials = self.resolve_credentials().await?;
 
-        let system = system_prompt.map(|text| {
-            let mut blocks = vec![SystemBlock::Text(TextBlock {
-                text: text.to_string(),
-            })];
-            if Self::should_cache_system(text) {
-                blocks.push(SystemBlock::CachePoint(CachePointWrapper {
-                    cache_point: CachePoint::default_cache(),
-                }));
-            }
-            blocks
-        });
+        let system = system_prompt.map(Self::system_blocks);
 
         let request = ConverseRequest {
             system,
@@ -1286,20 +1311,7 @@ impl Provider for BedrockProvider {
     ) -> anyhow::Result<ProviderChatResponse> {
         let credentials = self.resolve_credentials().await?;
 
-        let (system_blocks, mut converse_messages) = Self::convert_messages(request.messages);
-
-        // Apply cachePoint to system if large.
-        let system = system_blocks.map(|mut blocks| {
-            let has_large_system = blocks
-                .iter()
-                .any(|b| matches!(b, SystemBlock::Text(tb) if Self::should_cache_system(&tb.text)));
-            if has_large_system {
-                blocks.push(SystemBlock::CachePoint(CachePointWrapper {
-                    cache_point: CachePoint::default_cache(),
-                }));
-            }
-            blocks
-        });
+        let (system, mut converse_messages) = Self::convert_messages(request.messages);
 
         // Apply cachePoint to last message if conversation is long.
         if Self::should_cache_conversation(request.messages) {
@@ -1331,6 +1343,10 @@ impl Provider for BedrockProvider {
         Ok(Self::parse_converse_response(response))
     }
 
+    fn supports_prompt_caching(&self) -> bool {
+        true
+    }
+
     fn supports_streaming(&self) -> bool {
         true
     }
@@ -1355,17 +1371,7 @@ impl Provider for BedrockProvider {
             }
         };
 
-        let system = system_prompt.map(|text| {
-            let mut blocks = vec![SystemBlock::Text(TextBlock {
-                text: text.to_string(),
-            })];
-            if Self::should_cache_system(text) {
-                blocks.push(SystemBlock::CachePoint(CachePointWrapper {
-                    cache_point: CachePoint::default_cache(),
-                }));
-            }
-            blocks
-        });
+        let system = system_prompt.map(Self::system_blocks);
 
         let request = ConverseRequest {
             system,
@@ -1762,6 +1768,21 @@ mod tests {
         assert_eq!(msgs[0].role, "user");
     }
 
+    #[test]
+    fn convert_messages_places_cache_point_at_boundary() {
+        let prefix = "p".repeat(4000);
+        let messages = vec![
+            ChatMessage::system(format!("{prefix}\n{PROMPT_CACHE_BOUNDARY}\nPer-turn notes")),
+            ChatMessage::user("Hello"),
+        ];
+        let (system, _) = BedrockProvider::convert_messages(&messages);
+        let blocks = system.unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert!(matches!(&blocks[0], SystemBlock::Text(tb) if tb.text == prefix));
+        assert!(matches!(blocks[1], SystemBlock::CachePoint(_)));
+        assert!(matches!(&blocks[2], SystemBlock::Text(tb) if tb.text == "Per-turn notes"));
+    }
+
     #[test]
     fn convert_messages_user_and_assistant() {
         let messages = vec![
@@ -0,0 +1,392 @@
+//! Request limits per provider and model.
+//!
+//! Providers differ in how many tools one request may carry, which JSON
+//! Schema keywords they accept in tool parameters, how long a function
+//! name may be and how many tokens fit in the context window. [`lookup`] and
+//! [`context_window`] resolve those limits for a provider/model pair from a
+//! built-in table and the `[[provider_capabilities]]` overrides, and
+//! [`shape_tools`] fits the tool specs of a native tool-calling request to
+//! them. Tools renamed on the way out are mapped back with
+//! [`ShapedTools::original_name`] when the model calls them.
+
+use crate::config::{ProviderCapabilityConfig, ToolSchemaDialect};
+use crate::tools::{CleaningStrategy, SchemaCleanr, ToolSpec};
+use crate::workspace::Scoped;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, LazyLock};
+
+/// Function name limit of the OpenAI, Anthropic and Gemini APIs.
+const DEFAULT_MAX_TOOL_NAME_LEN: usize = 64;
+/// Most tools the OpenAI and Gemini APIs accept in one request.
+const DEFAULT_MAX_TOOLS: usize = 128;
+/// Hex digits of the hash that keeps shortened names unique.
+const NAME_HASH_LEN: usize = 8;
+
+/// Context window used when the model is not in [`MODEL_CONTEXT_WINDOWS`].
+const DEFAULT_CONTEXT_TOKENS: usize = 32_000;
+
+/// Known context windows, matched by substring against the lowercased model
+/// id. The first match wins.
+const MODEL_CONTEXT_WINDOWS: &[(&str, usize)] = &[
+    ("gpt-4.1", 1_000_000),
+    ("gpt-4o", 128_000),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-5", 400_000),
+    ("gpt-3.5", 16_000),
+    ("claude", 200_000),
+    ("gemini", 1_000_000),
+    ("deepseek", 64_000),
+    ("qwen", 128_000),
+    ("glm", 128_000),
+    ("kimi", 128_000),
+    ("moonshot", 128_000),
+    ("mistral", 128_000),
+    ("llama-3", 128_000),
+    ("llama3", 128_000),
+    ("grok", 128_000),
+    // Short OpenAI reasoning ids last so they do not shadow longer names.
+    ("o1", 200_000),
+    ("o3", 200_000),
+    ("o4", 200_000),
+];
+
+static OVERRIDES: LazyLock<Scoped<Arc<Vec<ProviderCapabilityConfig>>>> =
+    LazyLock::new(|| Scoped::new(Arc::new(Vec::new())));
+
+/// Apply `[[provider_capabilities]]` to every lookup made afterwards.
+pub fn init_from_config(entries: &[ProviderCapabilityConfig]) {
+    OVERRIDES.set(Arc::new(entries.to_vec()));
+}
+
+/// Tool-calling limits of one provider/model pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolCapabilities {
+    /// Most tools per request; `None` is unlimited.
+    pub max_tools: Option<usize>,
+    /// Longest function name.
+    pub max_tool_name_len: usize,
+    /// JSON Schema subset tool parameters are reduced to.
+    pub schema: CleaningStrategy,
+}
+
+/// Limits for `model` served by `provider`.
+pub fn lookup(provider: &str, model: &str) -> ToolCapabilities {
+    let overrides = OVERRIDES.get();
+    resolve(provider, model, &overrides)
+}
+
+/// Context window in tokens of `model` served by `provider`.
+pub fn context_window(provider: &str, model: &str) -> usize {
+    let overrides = OVERRIDES.get();
+    resolve_context_window(provider, model, &overrides)
+}
+
+fn resolve_context_window(
+    provider: &str,
+    model: &str,
+    overrides: &[ProviderCapabilityConfig],
+) -> usize {
+    matching(provider, model, overrides)
+        .filter_map(|entry| entry.context_window)
+        .last()
+        .unwrap_or_else(|| {
+            let model = model.to_ascii_lowercase();
+            MODEL_CONTEXT_WINDOWS
+                .iter()
+                .find(|(pattern, _)| model.contains(pattern))
+                .map_or(DEFAULT_CONTEXT_TOKENS, |(_, tokens)| *tokens)
+        })
+}
+
+fn matching<'a>(
+    provider: &'a str,
+    model: &'a str,
+    overrides: &'a [ProviderCapabilityConfig],
+) -> impl Iterator<Item = &'a ProviderCapabilityConfig> {
+    overrides.iter().filter(move |entry| {
+        let entry_provider = entry.provider.trim();
+        (entry_provider == "*" || entry_provider.eq_ignore_ascii_case(provider))
+            && model_matches(entry.model.as_deref(), model)
+    })
+}
+
+fn resolve(
+    provider: &str,
+    model: &str,
+    overrides: &[ProviderCapabilityConfig],
+) -> ToolCapabilities {
+    let mut capabilities = builtin(provider, model);
+    for entry in matching(provider, model, overrides) {
+        if let Some(max_tools) = entry.max_tools {
+            capabilities.max_tools = Some(max_tools);
+        }
+        if let Some(len) = entry.max_tool_name_len {
+            capabilities.max_tool_name_len = len;
+        }
+        if let Some(dialect) = entry.schema {
+            capabilities.schema = match dialect {
+                ToolSchemaDialect::Gemini => CleaningStrategy::Gemini,
+                ToolSchemaDialect::Anthropic => CleaningStrategy::Anthropic,
+                ToolSchemaDialect::Openai => CleaningStrategy::OpenAI,
+                ToolSchemaDialect::Conservative => CleaningStrategy::Conservative,
+            };
+        }
+    }
+    capabilities
+}
+
+fn builtin(provider: &str, model: &str) -> ToolCapabilities {
+    let provider = provider.to_ascii_lowercase();
+    let model = model.to_ascii_lowercase();
+    let mut capabilities = ToolCapabilities {
+        max_tools: Some(DEFAULT_MAX_TOOLS),
+        max_tool_name_len: DEFAULT_MAX_TOOL_NAME_LEN,
+        schema: CleaningStrategy::Conservative,
+    };
+    // Model families are checked before providers: a Gemini model behind
+    // OpenRouter still rejects the keywords Gemini rejects.
+    if model.contains("gemini")
+        || matches!(provider.as_str(), "gemini" | "google" | "google-gemini")
+    {
+        capabilities.schema = CleaningStrategy::Gemini;
+    } else if model.contains("claude") || provider == "anthropic" {
+        capabilities.schema = CleaningStrategy::Anthropic;
+        capabilities.max_tools = None;
+    } else if model.starts_with("gpt-")
+        || model.starts_with("openai/")
+        || provider.starts_with("openai")
+    {
+        capabilities.schema = CleaningStrategy::OpenAI;
+    } else if provider == "ollama" {
+        capabilities.max_tools = None;
+    }
+    capabilities
+}
+
+/// `pattern` is a model name, a prefix ending in `*`, or unset for any model.
+fn model_matches(pattern: Option<&str>, model: &str) -> bool {
+    match pattern.map(str::trim) {
+        None | Some("" | "*") => true,
+        Some(pattern) => match pattern.strip_suffix('*') {
+            Some(prefix) => model.starts_with(prefix),
+            None => model == pattern,
+        },
+    }
+}
+
+/// Tool specs fitted to a provider, with the way back to registered names.
+#[derive(Debug, Default)]
+pub struct ShapedTools {
+    pub specs: Vec<ToolSpec>,
+    /// Registered tools left out to stay within `max_tools`.
+    pub dropped: Vec<String>,
+    /// Shaped name → registered name, for renamed tools only.
+    renamed: HashMap<String, String>,
+}
+
+impl ShapedTools {
+    /// Registered name of the tool the model called `name`.
+    pub fn original_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.renamed.get(name).map_or(name, String::as_str)
+    }
+}
+
+/// Fit `specs` to `capabilities`: keep the first `max_tools` (built-in tools
+/// are registered before MCP and plugin tools), reduce parameter schemas to
+/// the provider's dialect, and replace names that are too long or use
+/// characters outside `[A-Za-z0-9_-]`.
+pub fn shape_tools(mut specs: Vec<ToolSpec>, capabilities: &ToolCapabilities) -> ShapedTools {
+    let dropped = match capabilities.max_tools {
+        Some(max) if specs.len() > max => specs
+            .split_off(max)
+            .into_iter()
+            .map(|spec| spec.name)
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let max_len = capabilities.max_tool_name_len;
+    // Names that are already valid keep priority over generated ones.
+    let mut taken: HashSet<String> = specs
+        .iter()
+        .filter(|spec| is_valid_name(&spec.name, max_len))
+        .map(|spec| spec.name.clone())
+        .collect();
+    let mut renamed = HashMap::new();
+    for spec in &mut specs {
+        spec.parameters =
+            SchemaCleanr::clean(std::mem::take(&mut spec.parameters), capabilities.schema);
+        if is_valid_name(&spec.name, max_len) {
+            continue;
+        }
+        let shaped = shape_name(&spec.name, max_len, &taken);
+        taken.insert(shaped.clone());
+        let original = std::mem::replace(&mut spec.name, shaped.clone());
+        renamed.insert(shaped, original);
+    }
+
+    ShapedTools {
+        specs,
+        dropped,
+        renamed,
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+fn is_valid_name(name: &str, max_len: usize) -> bool {
+    !name.is_empty() && name.len() <= max_len && name.chars().all(is_name_char)
+}
+
+fn shape_name(name: &str, max_len: usize, taken: &HashSet<String>) -> String {
+    let clean: String = name
+        .chars()
+        .map(|c| if is_name_char(c) { c } else { '_' })
+        .collect();
+    if !clean.is_empty() && clean.len() <= max_len && !taken.contains(&clean) {
+        return clean;
+    }
+    let hash = format!("{:x}", Sha256::digest(name.as_bytes()));
+    let keep = max_len.saturating_sub(NAME_HASH_LEN + 1).min(clean.len());
+    format!("{}_{}", &clean[..keep], &hash[..NAME_HASH_LEN])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(name: &str) -> ToolSpec {
+        ToolSpec {
+            name: name.into(),
+            description: String::new(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "minLength": 1, "pattern": "^[a-z]+$" }
+                }
+            }),
+        }
+    }
+
+    #[test]
+    fn lookup_uses_model_family_and_overrides() {
+        let gemini = resolve("openrouter", "google/gemini-2.5-pro", &[]);
+        assert_eq!(gemini.schema, CleaningStrategy::Gemini);
+        let claude = resolve("anthropic", "claude-sonnet-4-6", &[]);
+        assert_eq!(claude.max_tools, None);
+        assert_eq!(
+            resolve("openai", "gpt-4o", &[]).schema,
+            CleaningStrategy::OpenAI
+        );
+
+        let overrides = [
+            ProviderCapabilityConfig {
+                provider: "*".into(),
+                model: None,
+                max_tools: Some(40),
+                max_tool_name_len: None,
+                schema: None,
+                context_window: None,
+            },
+            ProviderCapabilityConfig {
+                provider: "Ollama".into(),
+                model: Some("qwen2.5*".into()),
+                max_tools: Some(12),
+                max_tool_name_len: Some(32),
+                schema: Some(ToolSchemaDialect::Gemini),
+                context_window: None,
+            },
+        ];
+        let small = resolve("ollama", "qwen2.5:7b", &overrides);
+        assert_eq!(small.max_tools, Some(12));
+        assert_eq!(small.max_tool_name_len, 32);
+        assert_eq!(small.schema, CleaningStrategy::Gemini);
+        let other = resolve("ollama", "llama3.1", &overrides);
+        assert_eq!(other.max_tools, Some(40));
+        assert_eq!(other.schema, CleaningStrategy::Conservative);
+    }
+
+    #[test]
+    fn context_window_uses_model_table_and_overrides() {
+        assert_eq!(
+            resolve_context_window("openrouter", "anthropic/claude-sonnet-4", &[]),
+            200_000
+        );
+        assert_eq!(
+            resolve_context_window("openai", "gpt-4o-mini", &[]),
+            128_000
+        );
+        assert_eq!(
+            resolve_context_window("ollama", "some-local-model", &[]),
+            DEFAULT_CONTEXT_TOKENS
+        );
+
+        let overrides = [ProviderCapabilityConfig {
+            provider: "ollama".into(),
+            model: Some("qwen2.5*".into()),
+            max_tools: None,
+            max_tool_name_len: None,
+            schema: None,
+            context_window: Some(32_768),
+        }];
+        assert_eq!(
+            resolve_context_window("ollama", "qwen2.5:7b", &overrides),
+            32_768
+        );
+        assert_eq!(
+            resolve_context_window("openrouter", "qwen2.5-72b", &overrides),
+            128_000
+        );
+    }
+
+    #[test]
+    fn shape_tools_limits_count_cleans_schemas_and_maps_names() {
+        let long = format!("mcp__{}__search", "x".repeat(80));
+        let capabilities = ToolCapabilities {
+            max_tools: Some(3),
+            max_tool_name_len: 64,
+            schema: CleaningStrategy::Gemini,
+        };
+        let shaped = shape_tools(
+            vec![
+                spec("file_read"),
+                spec("docs.search"),
+                spec(&long),
+                spec("extra"),
+            ],
+            &capabilities,
+        );
+
+        assert_eq!(shaped.dropped, vec!["extra".to_string()]);
+        let names: Vec<&str> = shaped.specs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names[0], "file_read");
+        assert_eq!(names[1], "docs_search");
+        assert_eq!(names[2].len(), 64);
+        assert!(names.iter().all(|name| is_valid_name(name, 64)));
+        assert_eq!(shaped.original_name("docs_search"), "docs.search");
+        assert_eq!(shaped.original_name(names[2]), long);
+        assert_eq!(shaped.original_name("file_read"), "file_read");
+        assert_eq!(
+            shaped.specs[0].parameters["properties"]["path"],
+            json!({ "type": "string" })
+        );
+    }
+
+    #[test]
+    fn shape_name_avoids_collisions_with_valid_names() {
+        let capabilities = ToolCapabilities {
+            max_tools: None,
+            max_tool_name_len: 64,
+            schema: CleaningStrategy::OpenAI,
+        };
+        let shaped = shape_tools(vec![spec("a.b"), spec("a_b")], &capabilities);
+        assert_eq!(shaped.specs[1].name, "a_b");
+        assert_ne!(shaped.specs[0].name, "a_b");
+        assert!(shaped.specs[0].name.starts_with("a_b_"));
+        assert_eq!(shaped.original_name(&shaped.specs[0].name), "a.b");
+    }
+}
@@ -62,6 +62,8 @@ pub enum AuthStyle {
     XApiKey,
     /// Custom header name
     Custom(String),
+    /// No authentication header (local inference servers without an API key)
+    None,
 }
 
 /// API mode for OpenAI-compatible endpoints.
@@ -245,7 +247,9 @@ impl OpenAiCompatibleProvider {
         Self {
             name: name.to_string(),
             base_url: base_url.trim_end_matches('/').to_string(),
-            credential: credential.map(ToString::to_string),
+            credential: credential
+                .map(ToString::to_string)
+                .or_else(|| matches!(auth_style, AuthStyle::None).then(String::new)),
             auth_header: auth_style,
             supports_vision,
             supports_responses_fallback,
@@ -403,6 +407,8 @@ struct ApiChatRequest {
     tools: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -597,6 +603,8 @@ struct NativeChatRequest {
     tools: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1181,6 +1189,7 @@ impl OpenAiCompatibleProvider {
                 let name = HeaderName::from_bytes(header.as_bytes())?;
                 headers.insert(name, WsHeaderValue::from_str(credential)?);
             }
+            AuthStyle::None => {}
         }
 
         if let Some(ua) = self.user_agent.as_deref() {
@@ -1343,6 +1352,7 @@ impl OpenAiCompatibleProvider {
             AuthStyle::Bearer => req.header("Authorization", format!("Bearer {credential}")),
             AuthStyle::XApiKey => req.header("x-api-key", credential),
             AuthStyle::Custom(header) => req.header(header, credential),
+            AuthStyle::None => req,
         }
     }
 
@@ -1601,6 +1611,101 @@ impl OpenAiCompatibleProvider {
         .iter()
         .any(|hint| lower.contains(hint))
     }
+
+    /// Stream a chat completion for already-converted messages over SSE.
+    fn stream_api_messages(
+        &self,
+        messages: Vec<Message>,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let credential = match self.credential.as_ref() {
+            Some(value) => value.clone(),
+            None => {
+                let provider_name = self.name.clone();
+                return stream::once(async move {
+                    Err(StreamError::Provider(format!(
+                        "{} API key not set",
+                        provider_name
+                    )))
+                })
+                .boxed();
+            }
+        };
+
+        let request = ApiChatRequest {
+            model: model.to_string(),
+            messages,
+            temperature,
+            max_tokens: self.effective_max_tokens(),
+            stream: Some(options.enabled),
+            response_format: None,
+            tools: None,
+            tool_choice: None,
+        };
+
+        let url = self.chat_completions_url();
+        let client = self.http_client();
+        let auth_header = self.auth_header.clone();
+
+        // Use a channel to bridge the async HTTP response to the stream
+        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
+
+        tokio::spawn(async move {
+            // Build request with auth
+            let mut req_builder = client.post(&url).json(&request);
+
+            // Apply auth header
+            req_builder = match &auth_header {
+                AuthStyle::Bearer => {
+                    req_builder.header("Authorization", format!("Bearer {}", credential))
+                }
+                AuthStyle::XApiKey => req_builder.header("x-api-key", &credential),
+                AuthStyle::Custom(header) => req_builder.header(header, &credential),
+                AuthStyle::None => req_builder,
+            };
+
+            // Set accept header for streaming
+            req_builder = req_builder.header("Accept", "text/event-stream");
+
+            // Send request
+            let response = match req_builder.send().await {
+                Ok(r) => r,
+                Err(e) => {
+                    let _ = tx.send(Err(StreamError::Http(e))).await;
+                    return;
+                }
+            };
+
+            // Check status
+            if !response.status().is_success() {
+                let status = response.status();
+                let error = match response.text().await {
+                    Ok(e) => e,
+                    Err(_) => format!("HTTP error: {}", status),
+                };
+                let _ = tx
+                    .send(Err(StreamError::Provider(format!("{}: {}", status, error))))
+                    .await;
+                return;
+            }
+
+            // Convert to chunk stream and forward to channel
+            let mut chunk_stream = sse_bytes_to_chunks(response, options.count_tokens);
+            while let Some(chunk) = chunk_stream.next().await {
+                if tx.send(chunk).await.is_err() {
+                    break; // Receiver dropped
+                }
+            }
+        });
+
+        // Convert channel receiver to stream
+        stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|chunk| (chunk, rx))
+        })
+        .boxed()
+    }
 }
 
 #[async_trait]
@@ -1659,6 +1764,8 @@ impl Provider for OpenAiCompatibleProvider {
             temperature,
             max_tokens: self.effective_max_tokens(),
             stream: Some(false),
+            response_format: super::structured::current_response_format()
+                .map(|format| format.openai_response_format()),
             tools: None,
             tool_choice: None,
         };
@@ -1788,6 +1895,8 @@ impl Provider for OpenAiCompatibleProvider {
             temperature,
             max_tokens: self.effective_max_tokens(),
             stream: Some(false),
+            response_format: super::structured::current_response_format()
+                .map(|format| format.openai_response_format()),
             tools: None,
             tool_choice: None,
         };
@@ -1905,6 +2014,7 @@ impl Provider for OpenAiCompatibleProvider {
             temperature,
             max_tokens: self.effective_max_tokens(),
             stream: Some(false),
+            response_format: None,
             tools: if tools.is_empty() {
                 None
             } else {
@@ -2033,6 +2143,8 @@ impl Provider for OpenAiCompatibleProvider {
             temperature,
             max_tokens: self.effective_max_tokens(),
             stream: Some(false),
+            response_format: super::structured::current_response_format()
+                .map(|format| format.openai_response_format()),
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
         };
@@ -2153,20 +2265,6 @@ impl Provider for OpenAiCompatibleProvider {
         temperature: f64,
         options: StreamOptions,
     ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
-        let credential = match self.credential.as_ref() {
-            Some(value) => value.clone(),
-            None => {
-                let provider_name = self.name.clone();
-                return stream::once(async move {
-                    Err(StreamError::Provider(format!(
-                        "{} API key not set",
-                        provider_name
-                    )))
-                })
-                .boxed();
-            }
-        };
-
         let mut messages = Vec::new();
         if let Some(sys) = system_prompt {
             messages.push(Message {
@@ -2179,75 +2277,33 @@ impl Provider for OpenAiCompatibleProvider {
             content: Self::to_message_content("user", message, !self.merge_system_into_user),
         });
 
-        let request = ApiChatRequest {
-            model: model.to_string(),
-            messages,
-            temperature,
-            max_tokens: self.effective_max_tokens(),
-            stream: Some(options.enabled),
-            tools: None,
-            tool_choice: None,
-        };
-
-        let url = self.chat_completions_url();
-        let client = self.http_client();
-        let auth_header = self.auth_header.clone();
-
-        // Use a channel to bridge the async HTTP response to the stream
-        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
-
-        tokio::spawn(async move {
-            // Build request with auth
-            let mut req_builder = client.post(&url).json(&request);
-
-            // Apply auth header
-            req_builder = match &auth_header {
-                AuthStyle::Bearer => {
-                    req_builder.header("Authorization", format!("Bearer {}", credential))
-                }
-                AuthStyle::XApiKey => req_builder.header("x-api-key", &credential),
-                AuthStyle::Custom(header) => req_builder.header(header, &credential),
-            };
-
-            // Set accept header for streaming
-            req_builder = req_builder.header("Accept", "text/event-stream");
-
-            // Send request
-            let response = match req_builder.send().await {
-                Ok(r) => r,
-                Err(e) => {
-                    let _ = tx.send(Err(StreamError::Http(e))).await;
-                    return;
-                }
-            };
-
-            // Check status
-            if !response.status().is_success() {
-                let status = response.status();
-                let error = match response.text().await {
-                    Ok(e) => e,
-                    Err(_) => format!("HTTP error: {}", status),
-                };
-                let _ = tx
-                    .send(Err(StreamError::Provider(format!("{}: {}", status, error))))
-                    .await;
-                return;
-            }
-
-            // Convert to chunk stream and forward to channel
-            let mut chunk_stream = sse_bytes_to_chunks(response, options.count_tokens);
-            while let Some(chunk) = chunk_stream.next().await {
-                if tx.send(chunk).await.is_err() {
-                    break; // Receiver dropped
-                }
-            }
-        });
+        self.stream_api_messages(messages, model, temperature, options)
+    }
 
-        // Convert channel receiver to stream
-        stream::unfold(rx, |mut rx| async move {
-            rx.recv().await.map(|chunk| (chunk, rx))
-        })
-        .boxed()
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let effective_messages = if self.merge_system_into_user {
+            Self::flatten_system_messages(messages)
+        } else {
+            messages.to_vec()
+        };
+        let api_messages = effective_messages
+            .iter()
+            .map(|m| Message {
+                role: m.role.clone(),
+                content: Self::to_message_content(
+                    &m.role,
+                    &m.content,
+                    !self.merge_system_into_user,
+                ),
+            })
+            .collect();
+        self.stream_api_messages(api_messages, model, temperature, options)
     }
 
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -2327,6 +2383,7 @@ mod tests {
             temperature: 0.4,
             max_tokens: None,
             stream: Some(false),
+            response_format: None,
             tools: None,
             tool_choice: None,
         };
@@ -2402,6 +2459,22 @@ mod tests {
         assert!(matches!(p.auth_header, AuthStyle::Custom(_)));
     }
 
+    #[test]
+    fn no_auth_style_does_not_require_a_key() {
+        let p = OpenAiCompatibleProvider::new(
+            "vLLM",
+            "http://localhost:8000/v1",
+            None,
+            AuthStyle::None,
+        );
+        assert_eq!(p.credential.as_deref(), Some(""));
+        let req = p
+            .apply_auth_header(p.http_client().get("http://localhost:8000/v1/models"), "")
+            .build()
+            .unwrap();
+        assert!(req.headers().get("authorization").is_none());
+    }
+
     #[test]
     fn custom_constructor_applies_responses_mode_and_max_tokens_override() {
         let provider = OpenAiCompatibleProvider::new_custom_with_mode(
@@ -3175,6 +3248,7 @@ mod tests {
             temperature: 0.7,
             max_tokens: None,
             stream: Some(false),
+            response_format: None,
             tools: Some(tools),
             tool_choice: Some("auto".to_string()),
         };
@@ -18,6 +18,7 @@
 
 pub mod anthropic;
 pub mod bedrock;
+pub mod capabilities;
 pub mod compatible;
 pub mod copilot;
 pub mod gemini;
@@ -27,13 +28,15 @@ pub mod openai_codex;
 pub mod openrouter;
 pub mod reliable;
 pub mod router;
+pub mod structured;
 pub mod telnyx;
 pub mod traits;
+pub mod vcr;
 
 #[allow(unused_imports)]
 pub use traits::{
     ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderCapabilityError,
-    ToolCall, ToolResultMessage,
+    ResponseStream, StreamOptions, ToolCall, ToolResultMessage, PROMPT_CACHE_BOUNDARY,
 };
 
 use crate::auth::AuthService;
@@ -698,6 +701,7 @@ pub struct ProviderRuntimeOptions {
     pub custom_provider_api_mode: Option<CompatibleApiMode>,
     pub max_tokens_override: Option<u32>,
     pub model_support_vision: Option<bool>,
+    pub model_support_tools: Option<bool>,
 }
 
 impl Default for ProviderRuntimeOptions {
@@ -713,6 +717,7 @@ impl Default for ProviderRuntimeOptions {
             custom_provider_api_mode: None,
             max_tokens_override: None,
             model_support_vision: None,
+            model_support_tools: None,
         }
     }
 }
@@ -1000,6 +1005,37 @@ fn parse_custom_provider_url(
     }
 }
 
+/// Whether `url` points at this machine or a private network, where local
+/// inference servers commonly run without an API key.
+pub(crate) fn is_local_endpoint(url: &str) -> bool {
+    let Some(host) = reqwest::Url::parse(url.trim())
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_ascii_lowercase))
+    else {
+        return false;
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    match host.parse::<std::net::IpAddr>() {
+        Ok(std::net::IpAddr::V4(ip)) => {
+            ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified()
+        }
+        Ok(std::net::IpAddr::V6(ip)) => {
+            ip.is_loopback() || ip.is_unspecified() || (ip.segments()[0] & 0xfe00) == 0xfc00
+        }
+        Err(_) => host == "localhost" || host.ends_with(".localhost") || host.ends_with(".local"),
+    }
+}
+
+/// Auth for self-hosted OpenAI-compatible servers: bearer when a key is
+/// configured, no auth header otherwise.
+fn local_server_auth_style(key: Option<&str>) -> AuthStyle {
+    if key.is_some() {
+        AuthStyle::Bearer
+    } else {
+        AuthStyle::None
+    }
+}
+
 /// Factory: create the right provider from config (without custom URL)
 pub fn create_provider(name: &str, api_key: Option<&str>) -> anyhow::Result<Box<dyn Provider>> {
     create_provider_with_options(name, api_key, &ProviderRuntimeOptions::default())
@@ -1269,7 +1305,7 @@ fn create_provider_with_url_and_options(
                 "SGLang",
                 base_url,
                 key,
-                AuthStyle::Bearer,
+                local_server_auth_style(key),
             )))
         }
         "vllm" => {
@@ -1281,7 +1317,7 @@ fn create_provider_with_url_and_options(
                 "vLLM",
                 base_url,
                 key,
-                AuthStyle::Bearer,
+                local_server_auth_style(key),
             )))
         }
         "osaurus" => {
@@ -1331,11 +1367,18 @@ fn create_provider_with_url_and_options(
             let api_mode = options
                 .custom_provider_api_mode
                 .unwrap_or(CompatibleApiMode::OpenAiChatCompletions);
+            // Local servers (llama-server, vLLM, LocalAI, ...) usually run
+            // without a key; remote endpoints keep the missing-key error.
+            let auth_style = if is_local_endpoint(&base_url) {
+                local_server_auth_style(key)
+            } else {
+                AuthStyle::Bearer
+            };
             Ok(Box::new(OpenAiCompatibleProvider::new_custom_with_mode(
                 "Custom",
                 &base_url,
                 key,
-                AuthStyle::Bearer,
+                auth_style,
                 true,
                 api_mode,
                 options.max_tokens_override,
@@ -1455,13 +1498,57 @@ pub fn create_resilient_provider_with_options(
         reliability.provider_retries,
         reliability.provider_backoff_ms,
     )
+    .with_retry_policy(retry_policy_from_config(reliability, None))
+    .with_provider_policies(
+        reliability
+            .provider_policies
+            .iter()
+            .map(|(name, overrides)| {
+                (
+                    name.clone(),
+                    retry_policy_from_config(reliability, Some(overrides)),
+                )
+            })
+            .collect(),
+    )
+    .with_circuit_breaker(reliability.provider_circuit_breaker_threshold)
     .with_api_keys(reliability.api_keys.clone())
     .with_model_fallbacks(reliability.model_fallbacks.clone())
-    .with_vision_override(options.model_support_vision);
+    .with_unhealthy_cooldown(std::time::Duration::from_secs(
+        reliability.provider_unhealthy_cooldown_secs,
+    ))
+    .with_vision_override(options.model_support_vision)
+    .with_tools_override(options.model_support_tools);
 
     Ok(Box::new(reliable))
 }
 
+/// Resolve the retry policy for one provider: `[reliability]` defaults with
+/// an optional `[reliability.provider_policies.<name>]` override applied.
+fn retry_policy_from_config(
+    reliability: &crate::config::ReliabilityConfig,
+    overrides: Option<&crate::config::ProviderRetryPolicyConfig>,
+) -> reliable::RetryPolicy {
+    let overrides = overrides.cloned().unwrap_or_default();
+    let mut policy = reliable::RetryPolicy::new(
+        overrides
+            .max_retries
+            .unwrap_or(reliability.provider_retries),
+        overrides
+            .backoff_ms
+            .unwrap_or(reliability.provider_backoff_ms),
+    );
+    policy.max_backoff_ms = overrides
+        .max_backoff_ms
+        .unwrap_or(reliability.provider_max_backoff_ms)
+        .max(policy.base_backoff_ms);
+    policy.jitter = reliability.provider_backoff_jitter;
+    policy.retry_status_codes = overrides
+        .retry_status_codes
+        .unwrap_or_else(|| reliability.provider_retry_status_codes.clone());
+    policy
+}
+
 /// Create a RouterProvider if model routes are configured, otherwise return a
 /// standard resilient provider. The router wraps individual providers per route,
 /// each with its own retry/fallback chain.
@@ -1493,6 +1580,35 @@ pub fn create_routed_provider_with_options(
     model_routes: &[crate::config::ModelRouteConfig],
     default_model: &str,
     options: &ProviderRuntimeOptions,
+) -> anyhow::Result<Box<dyn Provider>> {
+    let provider = build_routed_provider(
+        primary_name,
+        api_key,
+        api_url,
+        reliability,
+        model_routes,
+        default_model,
+        options,
+    )?;
+
+    // Opt-in cassette recording for building deterministic replay fixtures.
+    match std::env::var(vcr::RECORD_ENV) {
+        Ok(path) if !path.trim().is_empty() => {
+            tracing::info!(path = path.trim(), "Recording provider exchanges");
+            Ok(Box::new(vcr::RecordingProvider::new(provider, path.trim())))
+        }
+        _ => Ok(provider),
+    }
+}
+
+fn build_routed_provider(
+    primary_name: &str,
+    api_key: Option<&str>,
+    api_url: Option<&str>,
+    reliability: &crate::config::ReliabilityConfig,
+    model_routes: &[crate::config::ModelRouteConfig],
+    default_model: &str,
+    options: &ProviderRuntimeOptions,
 ) -> anyhow::Result<Box<dyn Provider>> {
     if model_routes.is_empty() {
         return create_resilient_provider_with_options(
@@ -1572,7 +1688,8 @@ pub fn create_routed_provider_with_options(
 
     Ok(Box::new(
         router::RouterProvider::new(providers, routes, default_model.to_string())
-            .with_vision_override(options.model_support_vision),
+            .with_vision_override(options.model_support_vision)
+            .with_tools_override(options.model_support_tools),
     ))
 }
 
@@ -2538,6 +2655,18 @@ mod tests {
         assert!(p.is_ok());
     }
 
+    #[test]
+    fn local_endpoints_are_detected() {
+        assert!(is_local_endpoint("http://localhost:8080/v1"));
+        assert!(is_local_endpoint("http://127.0.0.1:11434"));
+        assert!(is_local_endpoint("http://192.168.1.20:8000/v1"));
+        assert!(is_local_endpoint("http://[::1]:8000"));
+        assert!(is_local_endpoint("http://gpu-box.local:8000/v1"));
+        assert!(!is_local_endpoint("https://my-llm.example.com"));
+        assert!(!is_local_endpoint("http://8.8.8.8"));
+        assert!(!is_local_endpoint("not a url"));
+    }
+
     #[test]
     fn factory_custom_empty_url_errors() {
         match create_provider("custom:", None) {
@@ -2663,6 +2792,18 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            shutdown_grace_secs: 30,
+            resume_unfinished_tasks: true,
+            max_task_resumes: 2,
+            outbound_queue: true,
+            outbound_max_attempts: 6,
+            outbound_retry_backoff_secs: 30,
+            provider_unhealthy_cooldown_secs: 60,
+            provider_max_backoff_ms: 10_000,
+            provider_backoff_jitter: true,
+            provider_retry_status_codes: Vec::new(),
+            provider_circuit_breaker_threshold: 3,
+            provider_policies: std::collections::HashMap::new(),
         };
 
         let provider = create_resilient_provider(
@@ -2702,6 +2843,18 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            shutdown_grace_secs: 30,
+            resume_unfinished_tasks: true,
+            max_task_resumes: 2,
+            outbound_queue: true,
+            outbound_max_attempts: 6,
+            outbound_retry_backoff_secs: 30,
+            provider_unhealthy_cooldown_secs: 60,
+            provider_max_backoff_ms: 10_000,
+            provider_backoff_jitter: true,
+            provider_retry_status_codes: Vec::new(),
+            provider_circuit_breaker_threshold: 3,
+            provider_policies: std::collections::HashMap::new(),
         };
 
         // Primary uses a ZAI key; fallbacks (lmstudio, ollama) should NOT
@@ -2724,6 +2877,18 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            shutdown_grace_secs: 30,
+            resume_unfinished_tasks: true,
+            max_task_resumes: 2,
+            outbound_queue: true,
+            outbound_max_attempts: 6,
+            outbound_retry_backoff_secs: 30,
+            provider_unhealthy_cooldown_secs: 60,
+            provider_max_backoff_ms: 10_000,
+            provider_backoff_jitter: true,
+            provider_retry_status_codes: Vec::new(),
+            provider_circuit_breaker_threshold: 3,
+            provider_policies: std::collections::HashMap::new(),
         };
 
         let provider =
@@ -2750,6 +2915,18 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            shutdown_grace_secs: 30,
+            resume_unfinished_tasks: true,
+            max_task_resumes: 2,
+            outbound_queue: true,
+            outbound_max_attempts: 6,
+            outbound_retry_backoff_secs: 30,
+            provider_unhealthy_cooldown_secs: 60,
+            provider_max_backoff_ms: 10_000,
+            provider_backoff_jitter: true,
+            provider_retry_status_codes: Vec::new(),
+            provider_circuit_breaker_threshold: 3,
+            provider_policies: std::collections::HashMap::new(),
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -2782,6 +2959,18 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            shutdown_grace_secs: 30,
+            resume_unfinished_tasks: true,
+            max_task_resumes: 2,
+            outbound_queue: true,
+            outbound_max_attempts: 6,
+            outbound_retry_backoff_secs: 30,
+            provider_unhealthy_cooldown_secs: 60,
+            provider_max_backoff_ms: 10_000,
+            provider_backoff_jitter: true,
+            provider_retry_status_codes: Vec::new(),
+            provider_circuit_breaker_threshold: 3,
+            provider_policies: std::collections::HashMap::new(),
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -3167,6 +3356,18 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            shutdown_grace_secs: 30,
+            resume_unfinished_tasks: true,
+            max_task_resumes: 2,
+            outbound_queue: true,
+            outbound_max_attempts: 6,
+            outbound_retry_backoff_secs: 30,
+            provider_unhealthy_cooldown_secs: 60,
+            provider_max_backoff_ms: 10_000,
+            provider_backoff_jitter: true,
+            provider_retry_status_codes: Vec::new(),
+            provider_circuit_breaker_threshold: 3,
+            provider_policies: std::collections::HashMap::new(),
         };
 
         // openai-codex resolves its own OAuth credential; it should not
@@ -3196,6 +3397,18 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            shutdown_grace_secs: 30,
+            resume_unfinished_tasks: true,
+            max_task_resumes: 2,
+            outbound_queue: true,
+            outbound_max_attempts: 6,
+            outbound_retry_backoff_secs: 30,
+            provider_unhealthy_cooldown_secs: 60,
+            provider_max_backoff_ms: 10_000,
+            provider_backoff_jitter: true,
+            provider_retry_status_codes: Vec::new(),
+            provider_circuit_breaker_threshold: 3,
+            provider_policies: std::collections::HashMap::new(),
         };
 
         let provider = create_resilient_provider("ollama", None, None, &reliability);
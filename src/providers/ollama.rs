@@ -25,6 +25,9 @@ struct ChatRequest {
     think: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<serde_json::Value>>,
+    /// JSON Schema the reply must follow (structured output).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -213,6 +216,7 @@ impl OllamaProvider {
             options: Options { temperature },
             think: self.reasoning_enabled,
             tools: tools.map(|t| t.to_vec()),
+            format: super::structured::current_response_format().map(|format| format.schema),
         }
     }
 
@@ -20,6 +20,8 @@ struct ChatRequest {
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -67,6 +69,8 @@ struct NativeChatRequest {
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -341,6 +345,8 @@ impl Provider for OpenAiProvider {
             messages,
             temperature,
             max_tokens: self.max_tokens_override,
+            response_format: super::structured::current_response_format()
+                .map(|format| format.openai_response_format()),
         };
 
         let response = self
@@ -383,6 +389,8 @@ impl Provider for OpenAiProvider {
             max_tokens: self.max_tokens_override,
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            response_format: super::structured::current_response_format()
+                .map(|format| format.openai_response_format()),
         };
 
         let response = self
@@ -447,6 +455,7 @@ impl Provider for OpenAiProvider {
             max_tokens: self.max_tokens_override,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            response_format: None,
         };
 
         let response = self
@@ -545,6 +554,7 @@ mod tests {
             ],
             temperature: 0.7,
             max_tokens: None,
+            response_format: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("\"role\":\"system\""));
@@ -562,6 +572,7 @@ mod tests {
             }],
             temperature: 0.0,
             max_tokens: None,
+            response_format: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(!json.contains("system"));
@@ -6,7 +6,7 @@ use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 // ── Error Classification ─────────────────────────────────────────────────
 // Errors are split into retryable (transient server/network failures) and
@@ -211,6 +211,72 @@ fn push_failure(
     ));
 }
 
+/// HTTP status of a failed provider call, from the typed reqwest error or
+/// the first 4xx/5xx code embedded in the error message.
+fn error_status_code(err: &anyhow::Error) -> Option<u16> {
+    if let Some(status) = err
+        .downcast_ref::<reqwest::Error>()
+        .and_then(reqwest::Error::status)
+    {
+        return Some(status.as_u16());
+    }
+    err.to_string()
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|word| word.parse::<u16>().ok())
+        .find(|code| (400..600).contains(code))
+}
+
+/// Default time a provider stays demoted after exhausting its retries.
+const DEFAULT_UNHEALTHY_COOLDOWN_SECS: u64 = 60;
+
+/// Default cap for the exponential retry delay.
+const DEFAULT_MAX_BACKOFF_MS: u64 = 10_000;
+
+/// How one provider's calls are retried before failing over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /// Randomize each delay between half and the full backoff.
+    pub jitter: bool,
+    /// Status codes worth retrying; empty uses the built-in classification.
+    pub retry_status_codes: Vec<u16>,
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, base_backoff_ms: u64) -> Self {
+        Self {
+            max_retries,
+            base_backoff_ms: base_backoff_ms.max(50),
+            max_backoff_ms: DEFAULT_MAX_BACKOFF_MS,
+            jitter: false,
+            retry_status_codes: Vec::new(),
+        }
+    }
+
+    fn is_non_retryable(&self, err: &anyhow::Error) -> bool {
+        if is_context_window_exceeded(err) {
+            return true;
+        }
+        if !self.retry_status_codes.is_empty() {
+            if let Some(code) = error_status_code(err) {
+                return !self.retry_status_codes.contains(&code);
+            }
+        }
+        is_non_retryable(err)
+    }
+
+    /// Spread `backoff_ms` over `[backoff/2, backoff]` when jitter is on.
+    fn jittered(&self, backoff_ms: u64) -> u64 {
+        if !self.jitter || backoff_ms < 2 {
+            return backoff_ms;
+        }
+        let half = backoff_ms / 2;
+        half + rand::random::<u64>() % (backoff_ms - half + 1)
+    }
+}
+
 // ── Resilient Provider Wrapper ────────────────────────────────────────────
 // Three-level failover strategy: model chain → provider chain → retry loop.
 //   Outer loop:  iterate model fallback chain (original model first, then
@@ -221,11 +287,25 @@ fn push_failure(
 // Loop invariant: `failures` accumulates every failed attempt so the final
 // error message gives operators a complete diagnostic trail.
 
+/// Health of one provider in the failover chain. A provider whose retries
+/// were exhausted on transient errors (429, 5xx, timeouts) is demoted to the
+/// end of the chain until `unhealthy_until` passes or it succeeds again.
+#[derive(Debug, Default)]
+struct ProviderHealth {
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+}
+
 /// Provider wrapper with retry, fallback, auth rotation, and model failover.
 pub struct ReliableProvider {
     providers: Vec<(String, Box<dyn Provider>)>,
-    max_retries: u32,
-    base_backoff_ms: u64,
+    health: Vec<parking_lot::Mutex<ProviderHealth>>,
+    unhealthy_cooldown: Duration,
+    /// Consecutive failures that open a provider's circuit (0 = never).
+    circuit_breaker_threshold: u32,
+    retry_policy: RetryPolicy,
+    /// Per-provider policy overrides: provider_name → policy.
+    provider_policies: HashMap<String, RetryPolicy>,
     /// Extra API keys for rotation (index tracks round-robin position).
     api_keys: Vec<String>,
     key_index: AtomicUsize,
@@ -235,6 +315,8 @@ pub struct ReliableProvider {
     provider_model_fallbacks: HashMap<String, Vec<String>>,
     /// Vision support override from config (`None` = defer to provider).
     vision_override: Option<bool>,
+    /// Native tool-calling override from config (`None` = defer to provider).
+    tools_override: Option<bool>,
 }
 
 impl ReliableProvider {
@@ -243,15 +325,23 @@ impl ReliableProvider {
         max_retries: u32,
         base_backoff_ms: u64,
     ) -> Self {
+        let health = providers
+            .iter()
+            .map(|_| parking_lot::Mutex::new(ProviderHealth::default()))
+            .collect();
         Self {
             providers,
-            max_retries,
-            base_backoff_ms: base_backoff_ms.max(50),
+            health,
+            unhealthy_cooldown: Duration::from_secs(DEFAULT_UNHEALTHY_COOLDOWN_SECS),
+            circuit_breaker_threshold: 0,
+            retry_policy: RetryPolicy::new(max_retries, base_backoff_ms),
+            provider_policies: HashMap::new(),
             api_keys: Vec::new(),
             key_index: AtomicUsize::new(0),
             model_fallbacks: HashMap::new(),
             provider_model_fallbacks: HashMap::new(),
             vision_override: None,
+            tools_override: None,
         }
     }
 
@@ -288,6 +378,195 @@ impl ReliableProvider {
         self
     }
 
+    /// Set native tool-calling override from runtime config.
+    pub fn with_tools_override(mut self, tools_override: Option<bool>) -> Self {
+        self.tools_override = tools_override;
+        self
+    }
+
+    /// Set how long a failing provider stays demoted. Zero disables demotion.
+    pub fn with_unhealthy_cooldown(mut self, cooldown: Duration) -> Self {
+        self.unhealthy_cooldown = cooldown;
+        self
+    }
+
+    /// Open a provider's circuit after `threshold` consecutive failures,
+    /// skipping it entirely until the unhealthy cooldown passes. Zero
+    /// disables the breaker.
+    pub fn with_circuit_breaker(mut self, threshold: u32) -> Self {
+        self.circuit_breaker_threshold = threshold;
+        self
+    }
+
+    /// Replace the default retry policy.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Set per-provider retry policies, keyed by provider name.
+    pub fn with_provider_policies(mut self, policies: HashMap<String, RetryPolicy>) -> Self {
+        self.provider_policies = policies;
+        self
+    }
+
+    fn policy_for(&self, provider_name: &str) -> &RetryPolicy {
+        self.provider_policies
+            .get(provider_name)
+            .unwrap_or(&self.retry_policy)
+    }
+
+    /// Provider indices in the order to try them: healthy providers in
+    /// configured priority, then demoted ones. Providers with an open
+    /// circuit are left out; once the cooldown passes they get one
+    /// half-open attempt, and another failure re-opens the circuit.
+    fn provider_order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let mut healthy = Vec::new();
+        let mut demoted = Vec::new();
+        for index in 0..self.providers.len() {
+            let health = self.health[index].lock();
+            match health.unhealthy_until {
+                Some(until) if until > now => {
+                    let circuit_open = self.circuit_breaker_threshold > 0
+                        && health.consecutive_failures >= self.circuit_breaker_threshold;
+                    if !circuit_open {
+                        demoted.push(index);
+                    }
+                }
+                _ => healthy.push(index),
+            }
+        }
+        healthy.into_iter().chain(demoted).collect()
+    }
+
+    /// [`Self::provider_order`], failing fast when every circuit is open.
+    fn call_order(&self) -> anyhow::Result<Vec<usize>> {
+        let order = self.provider_order();
+        if order.is_empty() && !self.providers.is_empty() {
+            let now = Instant::now();
+            let retry_in = self
+                .health
+                .iter()
+                .filter_map(|health| health.lock().unhealthy_until)
+                .map(|until| until.saturating_duration_since(now))
+                .min()
+                .unwrap_or_default();
+            anyhow::bail!(
+                "All providers are temporarily unavailable (circuit open after repeated failures); \
+                 retrying in {}s",
+                retry_in.as_secs().max(1)
+            );
+        }
+        Ok(order)
+    }
+
+    fn record_success(&self, index: usize) {
+        let mut health = self.health[index].lock();
+        if health.consecutive_failures > 0 {
+            tracing::info!(
+                provider = self.providers[index].0.as_str(),
+                "Provider healthy again"
+            );
+        }
+        *health = ProviderHealth::default();
+    }
+
+    fn record_failure(&self, index: usize) {
+        let mut health = self.health[index].lock();
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        if !self.unhealthy_cooldown.is_zero() {
+            health.unhealthy_until = Some(Instant::now() + self.unhealthy_cooldown);
+            let circuit_open = self.circuit_breaker_threshold > 0
+                && health.consecutive_failures >= self.circuit_breaker_threshold;
+            if circuit_open {
+                tracing::warn!(
+                    provider = self.providers[index].0.as_str(),
+                    consecutive_failures = health.consecutive_failures,
+                    cooldown_secs = self.unhealthy_cooldown.as_secs(),
+                    "Provider circuit open; skipping it until cooldown passes"
+                );
+            } else {
+                tracing::warn!(
+                    provider = self.providers[index].0.as_str(),
+                    consecutive_failures = health.consecutive_failures,
+                    cooldown_secs = self.unhealthy_cooldown.as_secs(),
+                    "Provider marked unhealthy; demoting in failover order"
+                );
+            }
+        }
+    }
+
+    /// Start a stream on the first streaming-capable provider in failover
+    /// order. `start` opens the stream for a provider and resolved model.
+    fn stream_first_capable(
+        &self,
+        model: &str,
+        options: StreamOptions,
+        start: impl Fn(&dyn Provider, &str) -> stream::BoxStream<'static, StreamResult<StreamChunk>>,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        // Try each provider/model combination for streaming
+        // For streaming, we use the first provider that supports it and has streaming enabled
+        for provider_index in self.provider_order() {
+            let (provider_name, provider) = &self.providers[provider_index];
+            if !provider.supports_streaming() || !options.enabled {
+                continue;
+            }
+
+            // Clone provider data for the stream
+            let provider_clone = provider_name.clone();
+
+            // Try the first model in the chain for streaming, with provider remap applied.
+            let base_model = match self.model_chain(model).first() {
+                Some(m) => *m,
+                None => model,
+            };
+            let current_model = self
+                .provider_model_chain(base_model, provider_name, provider_index == 0)
+                .first()
+                .copied()
+                .unwrap_or(base_model)
+                .to_string();
+
+            // For streaming, we attempt once and propagate errors
+            // The caller can retry the entire request if needed
+            let stream = start(provider.as_ref(), &current_model);
+
+            // Use a channel to bridge the stream with logging
+            let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
+
+            tokio::spawn(async move {
+                let mut stream = stream;
+                while let Some(chunk) = stream.next().await {
+                    if let Err(ref e) = chunk {
+                        tracing::warn!(
+                            provider = provider_clone,
+                            model = current_model,
+                            "Streaming error: {e}"
+                        );
+                    }
+                    if tx.send(chunk).await.is_err() {
+                        break; // Receiver dropped
+                    }
+                }
+            });
+
+            // Convert channel receiver to stream
+            return stream::unfold(rx, |mut rx| async move {
+                rx.recv().await.map(|chunk| (chunk, rx))
+            })
+            .boxed();
+        }
+
+        // No streaming support available
+        stream::once(async move {
+            Err(super::traits::StreamError::Provider(
+                "No provider supports streaming".to_string(),
+            ))
+        })
+        .boxed()
+    }
+
     /// Build the list of models to try: [original, fallback1, fallback2, ...]
     fn model_chain<'a>(&'a self, model: &'a str) -> Vec<&'a str> {
         let mut chain = vec![model];
@@ -347,6 +626,16 @@ impl ReliableProvider {
             base
         }
     }
+
+    /// Delay before the next attempt: Retry-After when present, otherwise
+    /// the (optionally jittered) exponential backoff.
+    fn retry_wait(&self, policy: &RetryPolicy, base: u64, err: &anyhow::Error) -> u64 {
+        if parse_retry_after_ms(err).is_some() {
+            self.compute_backoff(base, err)
+        } else {
+            policy.jittered(base)
+        }
+    }
 }
 
 #[async_trait]
@@ -375,14 +664,20 @@ impl Provider for ReliableProvider {
         // Each iteration: attempt one (provider, model) call. On success, return
         // immediately. On non-retryable error, break to next provider. On
         // retryable error, sleep with exponential backoff and retry.
+        // Ordered once per call so demotions during this call do not reshuffle
+        // the chain between model fallbacks.
+        let provider_order = self.call_order()?;
         for current_model in &models {
-            for (provider_index, (provider_name, provider)) in self.providers.iter().enumerate() {
+            for provider_index in provider_order.iter().copied() {
+                let (provider_name, provider) = &self.providers[provider_index];
                 let sent_models =
                     self.provider_model_chain(current_model, provider_name, provider_index == 0);
                 for sent_model in sent_models {
-                    let mut backoff_ms = self.base_backoff_ms;
+                    let policy = self.policy_for(provider_name);
+                    let mut backoff_ms = policy.base_backoff_ms;
+                    let mut last_retryable = false;
 
-                    for attempt in 0..=self.max_retries {
+                    for attempt in 0..=policy.max_retries {
                         match provider
                             .chat_with_system(system_prompt, message, sent_model, temperature)
                             .await
@@ -397,22 +692,24 @@ impl Provider for ReliableProvider {
                                         "Provider recovered (failover/retry)"
                                     );
                                 }
+                                self.record_success(provider_index);
                                 return Ok(resp);
                             }
                             Err(e) => {
                                 let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                                 let non_retryable =
-                                    is_non_retryable(&e) || non_retryable_rate_limit;
+                                    policy.is_non_retryable(&e) || non_retryable_rate_limit;
                                 let rate_limited = is_rate_limited(&e);
                                 let failure_reason = failure_reason(rate_limited, non_retryable);
                                 let error_detail = compact_error_detail(&e);
+                                last_retryable = !non_retryable;
 
                                 push_failure(
                                     &mut failures,
                                     provider_name,
                                     sent_model,
                                     attempt + 1,
-                                    self.max_retries + 1,
+                                    policy.max_retries + 1,
                                     failure_reason,
                                     &error_detail,
                                 );
@@ -450,8 +747,8 @@ impl Provider for ReliableProvider {
                                     break;
                                 }
 
-                                if attempt < self.max_retries {
-                                    let wait = self.compute_backoff(backoff_ms, &e);
+                                if attempt < policy.max_retries {
+                                    let wait = self.retry_wait(policy, backoff_ms, &e);
                                     tracing::warn!(
                                         provider = provider_name,
                                         model = sent_model,
@@ -462,12 +759,16 @@ impl Provider for ReliableProvider {
                                         "Provider call failed, retrying"
                                     );
                                     tokio::time::sleep(Duration::from_millis(wait)).await;
-                                    backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
+                                    backoff_ms =
+                                        (backoff_ms.saturating_mul(2)).min(policy.max_backoff_ms);
                                 }
                             }
                         }
                     }
 
+                    if last_retryable {
+                        self.record_failure(provider_index);
+                    }
                     tracing::warn!(
                         provider = provider_name,
                         model = sent_model,
@@ -499,15 +800,18 @@ impl Provider for ReliableProvider {
     ) -> anyhow::Result<String> {
         let models = self.model_chain(model);
         let mut failures = Vec::new();
-
+        let provider_order = self.call_order()?;
         for current_model in &models {
-            for (provider_index, (provider_name, provider)) in self.providers.iter().enumerate() {
+            for provider_index in provider_order.iter().copied() {
+                let (provider_name, provider) = &self.providers[provider_index];
                 let sent_models =
                     self.provider_model_chain(current_model, provider_name, provider_index == 0);
                 for sent_model in sent_models {
-                    let mut backoff_ms = self.base_backoff_ms;
+                    let policy = self.policy_for(provider_name);
+                    let mut backoff_ms = policy.base_backoff_ms;
+                    let mut last_retryable = false;
 
-                    for attempt in 0..=self.max_retries {
+                    for attempt in 0..=policy.max_retries {
                         match provider
                             .chat_with_history(messages, sent_model, temperature)
                             .await
@@ -522,22 +826,24 @@ impl Provider for ReliableProvider {
                                         "Provider recovered (failover/retry)"
                                     );
                                 }
+                                self.record_success(provider_index);
                                 return Ok(resp);
                             }
                             Err(e) => {
                                 let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                                 let non_retryable =
-                                    is_non_retryable(&e) || non_retryable_rate_limit;
+                                    policy.is_non_retryable(&e) || non_retryable_rate_limit;
                                 let rate_limited = is_rate_limited(&e);
                                 let failure_reason = failure_reason(rate_limited, non_retryable);
                                 let error_detail = compact_error_detail(&e);
+                                last_retryable = !non_retryable;
 
                                 push_failure(
                                     &mut failures,
                                     provider_name,
                                     sent_model,
                                     attempt + 1,
-                                    self.max_retries + 1,
+                                    policy.max_retries + 1,
                                     failure_reason,
                                     &error_detail,
                                 );
@@ -573,8 +879,8 @@ impl Provider for ReliableProvider {
                                     break;
                                 }
 
-                                if attempt < self.max_retries {
-                                    let wait = self.compute_backoff(backoff_ms, &e);
+                                if attempt < policy.max_retries {
+                                    let wait = self.retry_wait(policy, backoff_ms, &e);
                                     tracing::warn!(
                                         provider = provider_name,
                                         model = sent_model,
@@ -585,12 +891,16 @@ impl Provider for ReliableProvider {
                                         "Provider call failed, retrying"
                                     );
                                     tokio::time::sleep(Duration::from_millis(wait)).await;
-                                    backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
+                                    backoff_ms =
+                                        (backoff_ms.saturating_mul(2)).min(policy.max_backoff_ms);
                                 }
                             }
                         }
                     }
 
+                    if last_retryable {
+                        self.record_failure(provider_index);
+                    }
                     tracing::warn!(
                         provider = provider_name,
                         model = sent_model,
@@ -607,10 +917,12 @@ impl Provider for ReliableProvider {
     }
 
     fn supports_native_tools(&self) -> bool {
-        self.providers
-            .first()
-            .map(|(_, p)| p.supports_native_tools())
-            .unwrap_or(false)
+        self.tools_override.unwrap_or_else(|| {
+            self.providers
+                .first()
+                .map(|(_, p)| p.supports_native_tools())
+                .unwrap_or(false)
+        })
     }
 
     fn supports_vision(&self) -> bool {
@@ -630,15 +942,18 @@ impl Provider for ReliableProvider {
     ) -> anyhow::Result<ChatResponse> {
         let models = self.model_chain(model);
         let mut failures = Vec::new();
-
+        let provider_order = self.call_order()?;
         for current_model in &models {
-            for (provider_index, (provider_name, provider)) in self.providers.iter().enumerate() {
+            for provider_index in provider_order.iter().copied() {
+                let (provider_name, provider) = &self.providers[provider_index];
                 let sent_models =
                     self.provider_model_chain(current_model, provider_name, provider_index == 0);
                 for sent_model in sent_models {
-                    let mut backoff_ms = self.base_backoff_ms;
+                    let policy = self.policy_for(provider_name);
+                    let mut backoff_ms = policy.base_backoff_ms;
+                    let mut last_retryable = false;
 
-                    for attempt in 0..=self.max_retries {
+                    for attempt in 0..=policy.max_retries {
                         match provider
                             .chat_with_tools(messages, tools, sent_model, temperature)
                             .await
@@ -653,22 +968,24 @@ impl Provider for ReliableProvider {
                                         "Provider recovered (failover/retry)"
                                     );
                                 }
+                                self.record_success(provider_index);
                                 return Ok(resp);
                             }
                             Err(e) => {
                                 let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                                 let non_retryable =
-                                    is_non_retryable(&e) || non_retryable_rate_limit;
+                                    policy.is_non_retryable(&e) || non_retryable_rate_limit;
                                 let rate_limited = is_rate_limited(&e);
                                 let failure_reason = failure_reason(rate_limited, non_retryable);
                                 let error_detail = compact_error_detail(&e);
+                                last_retryable = !non_retryable;
 
                                 push_failure(
                                     &mut failures,
                                     provider_name,
                                     sent_model,
                                     attempt + 1,
-                                    self.max_retries + 1,
+                                    policy.max_retries + 1,
                                     failure_reason,
                                     &error_detail,
                                 );
@@ -704,8 +1021,8 @@ impl Provider for ReliableProvider {
                                     break;
                                 }
 
-                                if attempt < self.max_retries {
-                                    let wait = self.compute_backoff(backoff_ms, &e);
+                                if attempt < policy.max_retries {
+                                    let wait = self.retry_wait(policy, backoff_ms, &e);
                                     tracing::warn!(
                                         provider = provider_name,
                                         model = sent_model,
@@ -716,12 +1033,16 @@ impl Provider for ReliableProvider {
                                         "Provider call failed, retrying"
                                     );
                                     tokio::time::sleep(Duration::from_millis(wait)).await;
-                                    backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
+                                    backoff_ms =
+                                        (backoff_ms.saturating_mul(2)).min(policy.max_backoff_ms);
                                 }
                             }
                         }
                     }
 
+                    if last_retryable {
+                        self.record_failure(provider_index);
+                    }
                     tracing::warn!(
                         provider = provider_name,
                         model = sent_model,
@@ -745,15 +1066,18 @@ impl Provider for ReliableProvider {
     ) -> anyhow::Result<ChatResponse> {
         let models = self.model_chain(model);
         let mut failures = Vec::new();
-
+        let provider_order = self.call_order()?;
         for current_model in &models {
-            for (provider_index, (provider_name, provider)) in self.providers.iter().enumerate() {
+            for provider_index in provider_order.iter().copied() {
+                let (provider_name, provider) = &self.providers[provider_index];
                 let sent_models =
                     self.provider_model_chain(current_model, provider_name, provider_index == 0);
                 for sent_model in sent_models {
-                    let mut backoff_ms = self.base_backoff_ms;
+                    let policy = self.policy_for(provider_name);
+                    let mut backoff_ms = policy.base_backoff_ms;
+                    let mut last_retryable = false;
 
-                    for attempt in 0..=self.max_retries {
+                    for attempt in 0..=policy.max_retries {
                         let req = ChatRequest {
                             messages: request.messages,
                             tools: request.tools,
@@ -769,22 +1093,24 @@ impl Provider for ReliableProvider {
                                         "Provider recovered (failover/retry)"
                                     );
                                 }
+                                self.record_success(provider_index);
                                 return Ok(resp);
                             }
                             Err(e) => {
                                 let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                                 let non_retryable =
-                                    is_non_retryable(&e) || non_retryable_rate_limit;
+                                    policy.is_non_retryable(&e) || non_retryable_rate_limit;
                                 let rate_limited = is_rate_limited(&e);
                                 let failure_reason = failure_reason(rate_limited, non_retryable);
                                 let error_detail = compact_error_detail(&e);
+                                last_retryable = !non_retryable;
 
                                 push_failure(
                                     &mut failures,
                                     provider_name,
                                     sent_model,
                                     attempt + 1,
-                                    self.max_retries + 1,
+                                    policy.max_retries + 1,
                                     failure_reason,
                                     &error_detail,
                                 );
@@ -820,8 +1146,8 @@ impl Provider for ReliableProvider {
                                     break;
                                 }
 
-                                if attempt < self.max_retries {
-                                    let wait = self.compute_backoff(backoff_ms, &e);
+                                if attempt < policy.max_retries {
+                                    let wait = self.retry_wait(policy, backoff_ms, &e);
                                     tracing::warn!(
                                         provider = provider_name,
                                         model = sent_model,
@@ -832,12 +1158,16 @@ impl Provider for ReliableProvider {
                                         "Provider call failed, retrying"
                                     );
                                     tokio::time::sleep(Duration::from_millis(wait)).await;
-                                    backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
+                                    backoff_ms =
+                                        (backoff_ms.saturating_mul(2)).min(policy.max_backoff_ms);
                                 }
                             }
                         }
                     }
 
+                    if last_retryable {
+                        self.record_failure(provider_index);
+                    }
                     tracing::warn!(
                         provider = provider_name,
                         model = sent_model,
@@ -861,6 +1191,16 @@ impl Provider for ReliableProvider {
         )
     }
 
+    fn supports_prompt_caching(&self) -> bool {
+        // Fallbacks receive the same messages, so every provider in the chain
+        // must understand the cache boundary marker.
+        !self.providers.is_empty()
+            && self
+                .providers
+                .iter()
+                .all(|(_, p)| p.supports_prompt_caching())
+    }
+
     fn supports_streaming(&self) -> bool {
         self.providers.iter().any(|(_, p)| p.supports_streaming())
     }
@@ -873,71 +1213,21 @@ impl Provider for ReliableProvider {
         temperature: f64,
         options: StreamOptions,
     ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
-        // Try each provider/model combination for streaming
-        // For streaming, we use the first provider that supports it and has streaming enabled
-        for (provider_index, (provider_name, provider)) in self.providers.iter().enumerate() {
-            if !provider.supports_streaming() || !options.enabled {
-                continue;
-            }
-
-            // Clone provider data for the stream
-            let provider_clone = provider_name.clone();
-
-            // Try the first model in the chain for streaming, with provider remap applied.
-            let base_model = match self.model_chain(model).first() {
-                Some(m) => *m,
-                None => model,
-            };
-            let current_model = self
-                .provider_model_chain(base_model, provider_name, provider_index == 0)
-                .first()
-                .copied()
-                .unwrap_or(base_model)
-                .to_string();
-
-            // For streaming, we attempt once and propagate errors
-            // The caller can retry the entire request if needed
-            let stream = provider.stream_chat_with_system(
-                system_prompt,
-                message,
-                &current_model,
-                temperature,
-                options,
-            );
-
-            // Use a channel to bridge the stream with logging
-            let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
-
-            tokio::spawn(async move {
-                let mut stream = stream;
-                while let Some(chunk) = stream.next().await {
-                    if let Err(ref e) = chunk {
-                        tracing::warn!(
-                            provider = provider_clone,
-                            model = current_model,
-                            "Streaming error: {e}"
-                        );
-                    }
-                    if tx.send(chunk).await.is_err() {
-                        break; // Receiver dropped
-                    }
-                }
-            });
-
-            // Convert channel receiver to stream
-            return stream::unfold(rx, |mut rx| async move {
-                rx.recv().await.map(|chunk| (chunk, rx))
-            })
-            .boxed();
-        }
+        self.stream_first_capable(model, options, |provider, model| {
+            provider.stream_chat_with_system(system_prompt, message, model, temperature, options)
+        })
+    }
 
-        // No streaming support available
-        stream::once(async move {
-            Err(super::traits::StreamError::Provider(
-                "No provider supports streaming".to_string(),
-            ))
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.stream_first_capable(model, options, |provider, model| {
+            provider.stream_chat_with_history(messages, model, temperature, options)
         })
-        .boxed()
     }
 }
 
@@ -1091,6 +1381,173 @@ mod tests {
         assert_eq!(fallback_calls.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn unhealthy_provider_is_demoted_until_cooldown_passes() {
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let fallback_calls = Arc::new(AtomicUsize::new(0));
+
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "primary".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&primary_calls),
+                        fail_until_attempt: usize::MAX,
+                        response: "never",
+                        error: "503 Service Unavailable",
+                    }),
+                ),
+                (
+                    "fallback".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&fallback_calls),
+                        fail_until_attempt: 0,
+                        response: "from fallback",
+                        error: "fallback down",
+                    }),
+                ),
+            ],
+            1,
+            1,
+        );
+
+        provider.simple_chat("hello", "test", 0.0).await.unwrap();
+        assert_eq!(provider.provider_order(), vec![1, 0]);
+
+        // The demoted primary is skipped while the fallback keeps answering.
+        let result = provider.simple_chat("again", "test", 0.0).await.unwrap();
+        assert_eq!(result, "from fallback");
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 2);
+
+        let provider = provider.with_unhealthy_cooldown(Duration::ZERO);
+        provider.record_failure(0);
+        provider.record_success(1);
+        assert_eq!(provider.provider_order(), vec![1, 0]);
+        provider.record_success(0);
+        assert_eq!(provider.provider_order(), vec![0, 1]);
+    }
+
+    #[tokio::test]
+    async fn open_circuit_skips_provider_and_fails_fast_when_all_open() {
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let fallback_calls = Arc::new(AtomicUsize::new(0));
+
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "primary".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&primary_calls),
+                        fail_until_attempt: usize::MAX,
+                        response: "never",
+                        error: "503 Service Unavailable",
+                    }),
+                ),
+                (
+                    "fallback".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&fallback_calls),
+                        fail_until_attempt: 1,
+                        response: "from fallback",
+                        error: "502 Bad Gateway",
+                    }),
+                ),
+            ],
+            0,
+            1,
+        )
+        .with_circuit_breaker(1);
+
+        // Both fail once: both circuits open and calls fail without trying them.
+        assert!(provider.simple_chat("hello", "test", 0.0).await.is_err());
+        assert!(provider.provider_order().is_empty());
+        let err = provider
+            .simple_chat("again", "test", 0.0)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("circuit open"));
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 1);
+
+        // After the cooldown both get a half-open attempt; the fallback recovers
+        // and the still-failing primary is skipped again.
+        for health in &provider.health {
+            health.lock().unhealthy_until = None;
+        }
+        let result = provider.simple_chat("retry", "test", 0.0).await.unwrap();
+        assert_eq!(result, "from fallback");
+        assert_eq!(provider.provider_order(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn provider_policy_overrides_default_retries() {
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let fallback_calls = Arc::new(AtomicUsize::new(0));
+
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "primary".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&primary_calls),
+                        fail_until_attempt: usize::MAX,
+                        response: "never",
+                        error: "500 Internal Server Error",
+                    }),
+                ),
+                (
+                    "fallback".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&fallback_calls),
+                        fail_until_attempt: 2,
+                        response: "from fallback",
+                        error: "503 Service Unavailable",
+                    }),
+                ),
+            ],
+            3,
+            1,
+        )
+        .with_provider_policies(HashMap::from([(
+            "primary".to_string(),
+            RetryPolicy {
+                retry_status_codes: vec![429, 503],
+                ..RetryPolicy::new(3, 1)
+            },
+        )]));
+
+        let result = provider.simple_chat("hello", "test", 0.0).await.unwrap();
+        assert_eq!(result, "from fallback");
+        // 500 is not in the primary's retryable set: no retries there.
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+        // The fallback keeps the built-in classification and retries 503.
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn jittered_backoff_stays_within_half_to_full_delay() {
+        let policy = RetryPolicy {
+            jitter: true,
+            ..RetryPolicy::new(2, 1_000)
+        };
+        for _ in 0..100 {
+            let wait = policy.jittered(1_000);
+            assert!((500..=1_000).contains(&wait), "wait {wait} out of range");
+        }
+        assert_eq!(RetryPolicy::new(2, 1_000).jittered(1_000), 1_000);
+    }
+
+    #[test]
+    fn error_status_code_reads_embedded_codes() {
+        let err = anyhow::anyhow!("OpenAI API error (503 Service Unavailable): overloaded");
+        assert_eq!(error_status_code(&err), Some(503));
+        assert_eq!(
+            error_status_code(&anyhow::anyhow!("connection reset")),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn returns_aggregated_error_when_all_providers_fail() {
         let provider = ReliableProvider::new(
@@ -2180,4 +2637,30 @@ mod tests {
         // No override set → should defer to provider default (false)
         assert!(!provider.supports_vision());
     }
+
+    #[test]
+    fn tools_override_replaces_provider_tool_support() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let build = |tools_override| {
+            ReliableProvider::new(
+                vec![(
+                    "primary".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&calls),
+                        fail_until_attempt: 0,
+                        response: "ok",
+                        error: "",
+                    }) as Box<dyn Provider>,
+                )],
+                1,
+                100,
+            )
+            .with_tools_override(tools_override)
+        };
+
+        // MockProvider default capabilities → no native tools
+        assert!(!build(None).supports_native_tools());
+        assert!(build(Some(true)).supports_native_tools());
+        assert!(!build(Some(false)).supports_native_tools());
+    }
 }
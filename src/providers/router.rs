@@ -1,8 +1,19 @@
-use super::traits::{ChatMessage, ChatRequest, ChatResponse};
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamOptions, StreamResult,
+};
 use super::Provider;
 use async_trait::async_trait;
+use futures_util::stream;
 use std::collections::HashMap;
 
+/// Route hint used for history summarization and other background tasks, so
+/// they can run on a cheaper model than the main conversation.
+pub const SUMMARIZE_ROUTE_HINT: &str = "summarize";
+
+/// Route hint channel turns switch to once a principal nears its token
+/// budget (`[cost].downgrade_at_percent`).
+pub const BUDGET_ROUTE_HINT: &str = "budget";
+
 /// A single route: maps a task hint to a provider + model combo.
 #[derive(Debug, Clone)]
 pub struct Route {
@@ -25,6 +36,8 @@ pub struct RouterProvider {
     default_model: String,
     /// Vision support override from config (`None` = defer to providers).
     vision_override: Option<bool>,
+    /// Native tool-calling override from config (`None` = defer to provider).
+    tools_override: Option<bool>,
 }
 
 impl RouterProvider {
@@ -69,6 +82,7 @@ impl RouterProvider {
             default_index: 0,
             default_model,
             vision_override: None,
+            tools_override: None,
         }
     }
 
@@ -78,6 +92,12 @@ impl RouterProvider {
         self
     }
 
+    /// Set native tool-calling override from runtime config.
+    pub fn with_tools_override(mut self, tools_override: Option<bool>) -> Self {
+        self.tools_override = tools_override;
+        self
+    }
+
     /// Resolve a model parameter to a (provider, actual_model) pair.
     ///
     /// If the model starts with "hint:", look up the hint in the route table.
@@ -161,10 +181,12 @@ impl Provider for RouterProvider {
     }
 
     fn supports_native_tools(&self) -> bool {
-        self.providers
-            .get(self.default_index)
-            .map(|(_, p)| p.supports_native_tools())
-            .unwrap_or(false)
+        self.tools_override.unwrap_or_else(|| {
+            self.providers
+                .get(self.default_index)
+                .map(|(_, p)| p.supports_native_tools())
+                .unwrap_or(false)
+        })
     }
 
     fn supports_vision(&self) -> bool {
@@ -175,6 +197,52 @@ impl Provider for RouterProvider {
         })
     }
 
+    fn supports_prompt_caching(&self) -> bool {
+        // Any route may receive the prompt, so all of them must support it.
+        !self.providers.is_empty()
+            && self
+                .providers
+                .iter()
+                .all(|(_, p)| p.supports_prompt_caching())
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.providers
+            .get(self.default_index)
+            .is_some_and(|(_, p)| p.supports_streaming())
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        provider.stream_chat_with_system(
+            system_prompt,
+            message,
+            &resolved_model,
+            temperature,
+            options,
+        )
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        provider.stream_chat_with_history(messages, &resolved_model, temperature, options)
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         for (name, provider) in &self.providers {
             tracing::info!(provider = name, "Warming up routed provider");
@@ -0,0 +1,461 @@
+//! Schema-constrained ("structured") responses.
+//!
+//! [`chat_structured`] asks a provider for JSON matching a JSON Schema.
+//! Providers with a native JSON mode (OpenAI, OpenAI-compatible endpoints,
+//! Ollama) receive the schema through [`RESPONSE_FORMAT`]; every reply is also
+//! validated locally, and a reply that does not match is sent back to the
+//! model with the validation errors for repair.
+
+use super::traits::{ChatMessage, ChatRequest, Provider};
+use anyhow::Result;
+use serde_json::{json, Value};
+
+tokio::task_local! {
+    /// Schema the provider calls of the current task are constrained to.
+    pub static RESPONSE_FORMAT: Option<JsonSchemaFormat>;
+}
+
+/// Repair round-trips allowed after the first invalid reply.
+pub const DEFAULT_MAX_REPAIRS: u32 = 2;
+
+/// Maximum validation errors reported back to the model per repair.
+const MAX_REPORTED_ERRORS: usize = 10;
+
+/// A named JSON Schema for provider-native JSON mode.
+#[derive(Debug, Clone)]
+pub struct JsonSchemaFormat {
+    pub name: String,
+    pub schema: Value,
+}
+
+impl JsonSchemaFormat {
+    /// OpenAI-style `response_format` payload.
+    pub fn openai_response_format(&self) -> Value {
+        json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": self.name,
+                "schema": self.schema,
+            }
+        })
+    }
+}
+
+/// Schema requested for the current provider call, if any.
+pub(crate) fn current_response_format() -> Option<JsonSchemaFormat> {
+    RESPONSE_FORMAT.try_with(Clone::clone).ok().flatten()
+}
+
+/// A request for a schema-constrained reply.
+#[derive(Debug, Clone)]
+pub struct StructuredRequest<'a> {
+    pub system_prompt: Option<&'a str>,
+    pub message: &'a str,
+    /// Short identifier for the schema (`[a-zA-Z0-9_-]`), sent to providers
+    /// with native JSON mode.
+    pub schema_name: &'a str,
+    pub schema: &'a Value,
+    pub max_repairs: u32,
+}
+
+/// A validated structured reply.
+#[derive(Debug, Clone)]
+pub struct StructuredResponse {
+    pub value: Value,
+    /// Provider calls used, including repairs.
+    pub attempts: u32,
+}
+
+/// Ask `provider` for JSON matching `request.schema`, repairing invalid
+/// replies up to `request.max_repairs` times.
+pub async fn chat_structured(
+    provider: &dyn Provider,
+    request: &StructuredRequest<'_>,
+    model: &str,
+    temperature: f64,
+) -> Result<StructuredResponse> {
+    let schema_text = serde_json::to_string_pretty(request.schema)?;
+    let instructions = format!(
+        "Respond only with a JSON value that matches this JSON Schema. \
+         Do not add prose or code fences.\n{schema_text}"
+    );
+    let system = match request.system_prompt {
+        Some(prompt) => format!("{prompt}\n\n{instructions}"),
+        None => instructions,
+    };
+    let mut history = vec![
+        ChatMessage::system(system),
+        ChatMessage::user(request.message),
+    ];
+
+    // OpenAI-style native modes only accept object schemas at the root.
+    let mut format =
+        (request.schema.get("type") == Some(&json!("object"))).then(|| JsonSchemaFormat {
+            name: request.schema_name.to_string(),
+            schema: request.schema.clone(),
+        });
+
+    let max_attempts = request.max_repairs.saturating_add(1);
+    let mut errors = Vec::new();
+    for attempt in 1..=max_attempts {
+        let chat_request = ChatRequest {
+            messages: &history,
+            tools: None,
+        };
+        let response = match RESPONSE_FORMAT
+            .scope(
+                format.clone(),
+                provider.chat(chat_request, model, temperature),
+            )
+            .await
+        {
+            Ok(response) => response,
+            Err(e) if format.is_some() => {
+                // Some OpenAI-compatible servers reject `response_format`;
+                // fall back to prompt-only JSON for the rest of the request.
+                tracing::debug!("Native JSON mode failed, retrying without it: {e}");
+                format = None;
+                provider.chat(chat_request, model, temperature).await?
+            }
+            Err(e) => return Err(e),
+        };
+        let raw = response.text.unwrap_or_default();
+
+        errors = match parse_json_reply(&raw) {
+            Some(value) => {
+                let errors = validate(request.schema, &value);
+                if errors.is_empty() {
+                    return Ok(StructuredResponse {
+                        value,
+                        attempts: attempt,
+                    });
+                }
+                errors
+            }
+            None => vec!["reply is not valid JSON".to_string()],
+        };
+
+        tracing::debug!(
+            attempt,
+            errors = errors.len(),
+            "Structured reply failed validation"
+        );
+        let listed: Vec<String> = errors
+            .iter()
+            .take(MAX_REPORTED_ERRORS)
+            .map(|e| format!("- {e}"))
+            .collect();
+        history.push(ChatMessage::assistant(raw));
+        history.push(ChatMessage::user(format!(
+            "Your reply does not match the schema:\n{}\nReply again with only the corrected JSON.",
+            listed.join("\n")
+        )));
+    }
+
+    anyhow::bail!(
+        "Structured reply failed schema validation after {max_attempts} attempt(s): {}",
+        errors.join("; ")
+    )
+}
+
+/// Parse the JSON value in a model reply, tolerating code fences and prose
+/// around a single object or array.
+pub fn parse_json_reply(raw: &str) -> Option<Value> {
+    let trimmed = raw.trim();
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Some(value);
+    }
+    let start = trimmed.find(['{', '['])?;
+    let closer = if trimmed[start..].starts_with('{') {
+        '}'
+    } else {
+        ']'
+    };
+    let end = trimmed.rfind(closer)?;
+    if end < start {
+        return None;
+    }
+    serde_json::from_str(&trimmed[start..=end]).ok()
+}
+
+/// Validate `value` against `schema`, returning one message per violation.
+///
+/// Supports the subset of JSON Schema used for model output: `type`, `enum`,
+/// `const`, `properties`, `required`, `additionalProperties`, `items`,
+/// `minItems`/`maxItems`, `minLength`/`maxLength`, `minimum`/`maximum` and
+/// `anyOf`/`oneOf`. Other keywords are ignored.
+pub fn validate(schema: &Value, value: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(schema, value, "$", &mut errors);
+    errors
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        _ => true,
+    }
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|t| type_matches(t, value)) {
+            errors.push(format!("{path}: expected {}", allowed.join(" or ")));
+            return;
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            errors.push(format!(
+                "{path}: must be one of {}",
+                Value::from(options.clone())
+            ));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(format!("{path}: must equal {expected}"));
+        }
+    }
+
+    for (keyword, exactly_one) in [("anyOf", false), ("oneOf", true)] {
+        if let Some(branches) = schema.get(keyword).and_then(Value::as_array) {
+            let matching = branches
+                .iter()
+                .filter(|branch| validate(branch, value).is_empty())
+                .count();
+            if matching == 0 || (exactly_one && matching > 1) {
+                errors.push(format!("{path}: does not match {keyword}"));
+            }
+        }
+    }
+
+    match value {
+        Value::Object(map) => {
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !map.contains_key(key) {
+                        errors.push(format!("{path}: missing required property '{key}'"));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (key, item) in map {
+                let item_path = format!("{path}.{key}");
+                match properties.and_then(|props| props.get(key)) {
+                    Some(item_schema) => validate_at(item_schema, item, &item_path, errors),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            errors.push(format!("{item_path}: unexpected property"));
+                        }
+                        Some(extra @ Value::Object(_)) => {
+                            validate_at(extra, item, &item_path, errors);
+                        }
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            let len = items.len() as u64;
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                if len < min {
+                    errors.push(format!("{path}: expected at least {min} items"));
+                }
+            }
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                if len > max {
+                    errors.push(format!("{path}: expected at most {max} items"));
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate_at(item_schema, item, &format!("{path}[{index}]"), errors);
+                }
+            }
+        }
+        Value::String(text) => {
+            let len = text.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if len < min {
+                    errors.push(format!("{path}: shorter than {min} characters"));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if len > max {
+                    errors.push(format!("{path}: longer than {max} characters"));
+                }
+            }
+        }
+        Value::Number(number) => {
+            let Some(number) = number.as_f64() else {
+                return;
+            };
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                if number < min {
+                    errors.push(format!("{path}: below minimum {min}"));
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                if number > max {
+                    errors.push(format!("{path}: above maximum {max}"));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+
+    struct ScriptedProvider {
+        replies: Mutex<Vec<&'static str>>,
+        formats_seen: Mutex<Vec<bool>>,
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> Result<String> {
+            unreachable!("structured requests send the full history")
+        }
+
+        async fn chat_with_history(
+            &self,
+            _messages: &[ChatMessage],
+            _model: &str,
+            _temperature: f64,
+        ) -> Result<String> {
+            self.formats_seen
+                .lock()
+                .push(current_response_format().is_some());
+            Ok(self.replies.lock().remove(0).to_string())
+        }
+    }
+
+    fn person_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "minLength": 1},
+                "age": {"type": "integer", "minimum": 0},
+                "tags": {"type": "array", "items": {"type": "string"}, "maxItems": 2}
+            },
+            "required": ["name", "age"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn validate_reports_each_violation_with_its_path() {
+        let schema = person_schema();
+        assert!(validate(&schema, &json!({"name": "Ada", "age": 36})).is_empty());
+
+        let errors = validate(
+            &schema,
+            &json!({"name": "", "age": -1, "tags": ["a", 1, "c"], "extra": true}),
+        );
+        assert!(errors.contains(&"$.name: shorter than 1 characters".to_string()));
+        assert!(errors.contains(&"$.age: below minimum 0".to_string()));
+        assert!(errors.contains(&"$.tags: expected at most 2 items".to_string()));
+        assert!(errors.contains(&"$.tags[1]: expected string".to_string()));
+        assert!(errors.contains(&"$.extra: unexpected property".to_string()));
+
+        let errors = validate(&schema, &json!({"name": "Ada"}));
+        assert_eq!(
+            errors,
+            vec!["$: missing required property 'age'".to_string()]
+        );
+        assert_eq!(
+            validate(&json!({"enum": ["a", "b"]}), &json!("c")),
+            vec![r#"$: must be one of ["a","b"]"#.to_string()]
+        );
+    }
+
+    #[test]
+    fn parse_json_reply_tolerates_fences_and_prose() {
+        assert_eq!(
+            parse_json_reply("```json\n{\"a\": 1}\n```"),
+            Some(json!({"a": 1}))
+        );
+        assert_eq!(
+            parse_json_reply("Here you go: [1, 2] as requested"),
+            Some(json!([1, 2]))
+        );
+        assert!(parse_json_reply("Nothing to report.").is_none());
+    }
+
+    #[tokio::test]
+    async fn invalid_reply_is_repaired_with_validation_errors() {
+        let provider = ScriptedProvider {
+            replies: Mutex::new(vec![
+                r#"{"name": "Ada", "age": "36"}"#,
+                r#"{"name": "Ada", "age": 36}"#,
+            ]),
+            formats_seen: Mutex::new(Vec::new()),
+        };
+        let schema = person_schema();
+        let request = StructuredRequest {
+            system_prompt: None,
+            message: "Describe Ada Lovelace",
+            schema_name: "person",
+            schema: &schema,
+            max_repairs: DEFAULT_MAX_REPAIRS,
+        };
+
+        let response = chat_structured(&provider, &request, "model", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(response.value, json!({"name": "Ada", "age": 36}));
+        assert_eq!(response.attempts, 2);
+        assert_eq!(*provider.formats_seen.lock(), vec![true, true]);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_repairs() {
+        let provider = ScriptedProvider {
+            replies: Mutex::new(vec!["not json", "[]"]),
+            formats_seen: Mutex::new(Vec::new()),
+        };
+        let schema = json!({"type": "array", "minItems": 1});
+        let request = StructuredRequest {
+            system_prompt: Some("You list things."),
+            message: "List nothing",
+            schema_name: "things",
+            schema: &schema,
+            max_repairs: 1,
+        };
+
+        let err = chat_structured(&provider, &request, "model", 0.0)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("after 2 attempt(s)"));
+        // Non-object root schemas are not sent to native JSON modes.
+        assert_eq!(*provider.formats_seen.lock(), vec![false, false]);
+    }
+}
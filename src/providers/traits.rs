@@ -41,6 +41,13 @@ impl ChatMessage {
     }
 }
 
+/// Marks the end of a stable system-prompt prefix.
+///
+/// Providers that report [`Provider::supports_prompt_caching`] split the system
+/// prompt here and ask the API to cache everything before the marker. Callers
+/// only insert it when the active provider supports prompt caching.
+pub const PROMPT_CACHE_BOUNDARY: &str = "<!-- zeroclaw:cache-boundary -->";
+
 /// A tool call requested by the LLM.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolCall {
@@ -209,6 +216,53 @@ pub enum StreamError {
     Io(#[from] std::io::Error),
 }
 
+/// Incremental text response from a streaming provider call.
+///
+/// Wraps the raw chunk stream so consumers deal in text deltas: empty chunks
+/// are skipped and the stream ends at the first final chunk.
+pub struct ResponseStream {
+    inner: stream::BoxStream<'static, StreamResult<StreamChunk>>,
+    done: bool,
+}
+
+impl ResponseStream {
+    pub fn new(inner: stream::BoxStream<'static, StreamResult<StreamChunk>>) -> Self {
+        Self { inner, done: false }
+    }
+
+    /// Next non-empty text delta, or `None` once the response is complete.
+    pub async fn next_delta(&mut self) -> Option<StreamResult<String>> {
+        while !self.done {
+            match self.inner.next().await {
+                None => self.done = true,
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                Some(Ok(chunk)) => {
+                    self.done = chunk.is_final;
+                    if !chunk.delta.is_empty() {
+                        return Some(Ok(chunk.delta));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Drain the stream, calling `on_delta` for every delta, and return the
+    /// full response text.
+    pub async fn collect_text(mut self, mut on_delta: impl FnMut(&str)) -> StreamResult<String> {
+        let mut text = String::new();
+        while let Some(delta) = self.next_delta().await {
+            let delta = delta?;
+            on_delta(&delta);
+            text.push_str(&delta);
+        }
+        Ok(text)
+    }
+}
+
 /// Structured error returned when a requested capability is not supported.
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("provider_capability_error provider={provider} capability={capability} message={message}")]
@@ -413,6 +467,12 @@ pub trait Provider: Send + Sync {
         })
     }
 
+    /// Whether provider honors [`PROMPT_CACHE_BOUNDARY`] markers in the system prompt.
+    /// Default implementation returns false.
+    fn supports_prompt_caching(&self) -> bool {
+        false
+    }
+
     /// Whether provider supports streaming responses.
     /// Default implementation returns false.
     fn supports_streaming(&self) -> bool {
@@ -952,4 +1012,32 @@ mod tests {
 
         assert!(message.contains("non-prompt-guided"));
     }
+
+    #[tokio::test]
+    async fn response_stream_skips_empty_deltas_and_stops_at_final_chunk() {
+        let chunks: Vec<StreamResult<StreamChunk>> = vec![
+            Ok(StreamChunk::delta("Hel")),
+            Ok(StreamChunk::delta("")),
+            Ok(StreamChunk::delta("lo")),
+            Ok(StreamChunk::final_chunk()),
+            Ok(StreamChunk::delta("ignored")),
+        ];
+        let mut seen = Vec::new();
+        let text = ResponseStream::new(stream::iter(chunks).boxed())
+            .collect_text(|delta| seen.push(delta.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(text, "Hello");
+        assert_eq!(seen, vec!["Hel", "lo"]);
+
+        let failing: Vec<StreamResult<StreamChunk>> = vec![
+            Ok(StreamChunk::delta("partial")),
+            Err(StreamError::Provider("503".into())),
+        ];
+        let err = ResponseStream::new(stream::iter(failing).boxed())
+            .collect_text(|_| {})
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("503"));
+    }
 }
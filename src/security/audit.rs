@@ -21,6 +21,7 @@ pub enum AuditEventType {
     AuthFailure,
     PolicyViolation,
     SecurityEvent,
+    ShellSession,
 }
 
 /// Actor information (who performed the action)
@@ -57,6 +58,16 @@ pub struct SecurityContext {
     pub sandbox_backend: Option<String>,
 }
 
+/// One step of an interactive shell session (input sent, output returned)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptEntry {
+    pub session_id: String,
+    /// `open`, `exec`, `send`, `read`, `close` or `idle_timeout`
+    pub step: String,
+    pub input: Option<String>,
+    pub output: String,
+}
+
 /// Complete audit event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditEvent {
@@ -67,6 +78,8 @@ pub struct AuditEvent {
     pub action: Option<Action>,
     pub result: Option<ExecutionResult>,
     pub security: SecurityContext,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<TranscriptEntry>,
 }
 
 impl AuditEvent {
@@ -84,6 +97,7 @@ impl AuditEvent {
                 rate_limit_remaining: None,
                 sandbox_backend: None,
             },
+            transcript: None,
         }
     }
 
@@ -141,6 +155,12 @@ impl AuditEvent {
         self.security.sandbox_backend = sandbox_backend;
         self
     }
+
+    /// Attach a shell session transcript entry
+    pub fn with_transcript(mut self, entry: TranscriptEntry) -> Self {
+        self.transcript = Some(entry);
+        self
+    }
 }
 
 /// Audit logger
@@ -396,6 +416,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn audit_event_transcript_round_trips() -> Result<()> {
+        let event =
+            AuditEvent::new(AuditEventType::ShellSession).with_transcript(TranscriptEntry {
+                session_id: "3".into(),
+                step: "exec".into(),
+                input: Some("echo hi".into()),
+                output: "hi\n".into(),
+            });
+        let json = serde_json::to_string(&event)?;
+        assert!(json.contains("\"event_type\":\"shell_session\""));
+        let parsed: AuditEvent = serde_json::from_str(&json)?;
+        let entry = parsed.transcript.unwrap();
+        assert_eq!(entry.input.as_deref(), Some("echo hi"));
+        assert_eq!(entry.output, "hi\n");
+
+        let plain = serde_json::to_string(&AuditEvent::new(AuditEventType::FileAccess))?;
+        assert!(!plain.contains("transcript"));
+        Ok(())
+    }
+
     #[test]
     fn audit_rotation_creates_numbered_backup() -> Result<()> {
         let tmp = TempDir::new()?;
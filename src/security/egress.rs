@@ -0,0 +1,306 @@
+//! Central egress policy for network-capable tools.
+//!
+//! `web_fetch`, `http_request`, `browser`, and `browser_open` all route URL
+//! checks through one shared [`EgressPolicy`]. It applies the tool's own
+//! domain allow/block lists, the global `[security.url_access]` rules, and
+//! per-domain rate limits from `[security.egress]`. It also keeps per-host
+//! counters of allowed, blocked, and rate-limited requests, exported as
+//! `zeroclaw_egress_requests_total` by the Prometheus observer. Model provider
+//! traffic does not go through this policy.
+
+use crate::config::{EgressConfig, UrlAccessConfig};
+use crate::tools::url_validation::{
+    extract_host, host_matches_allowlist, validate_url, DomainPolicy,
+};
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Policies handed out by [`EgressPolicy::shared`], one per distinct set of
+/// rules.
+static SHARED: OnceLock<Mutex<Vec<Arc<EgressPolicy>>>> = OnceLock::new();
+
+/// Per-host egress counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EgressDomainStats {
+    pub allowed: u64,
+    pub blocked: u64,
+    pub rate_limited: u64,
+}
+
+/// Shared URL/host policy consulted by every network-capable tool.
+#[derive(Debug)]
+pub struct EgressPolicy {
+    url_access: UrlAccessConfig,
+    rate_limit_per_minute: u32,
+    domain_rate_limits: Vec<(String, u32)>,
+    windows: Mutex<HashMap<String, VecDeque<Instant>>>,
+    stats: Mutex<BTreeMap<String, EgressDomainStats>>,
+}
+
+impl EgressPolicy {
+    pub fn new(url_access: UrlAccessConfig, egress: &EgressConfig) -> Self {
+        let mut domain_rate_limits: Vec<(String, u32)> = egress
+            .domain_rate_limits
+            .iter()
+            .map(|(domain, limit)| (domain.trim().to_ascii_lowercase(), *limit))
+            .collect();
+        // Most specific pattern first so `api.example.com` beats `example.com`.
+        domain_rate_limits.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then(a.0.cmp(&b.0)));
+
+        Self {
+            url_access,
+            rate_limit_per_minute: egress.rate_limit_per_minute,
+            domain_rate_limits,
+            windows: Mutex::new(HashMap::new()),
+            stats: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Process-wide policy for these rules. Tool registries built from the
+    /// same config (gateway, channels, every cron run) get the same instance,
+    /// so they draw from one rate window per host and report one set of
+    /// counters.
+    pub fn shared(url_access: UrlAccessConfig, egress: &EgressConfig) -> Arc<Self> {
+        let policy = Self::new(url_access, egress);
+        let mut shared = SHARED.get_or_init(Mutex::default).lock();
+        if let Some(existing) = shared.iter().find(|p| p.same_rules(&policy)) {
+            return existing.clone();
+        }
+        let policy = Arc::new(policy);
+        shared.push(policy.clone());
+        policy
+    }
+
+    /// Policy with global URL access rules and no rate limits.
+    pub fn from_url_access(url_access: UrlAccessConfig) -> Self {
+        Self::new(url_access, &EgressConfig::default())
+    }
+
+    /// Validate `raw_url` for `tool_name` and count it against the host's
+    /// rate budget. `rules` carries the tool-specific allow/block lists; its
+    /// `url_access` field is ignored in favour of the shared configuration.
+    pub fn authorize(
+        &self,
+        tool_name: &str,
+        raw_url: &str,
+        rules: &DomainPolicy<'_>,
+    ) -> anyhow::Result<String> {
+        let policy = DomainPolicy {
+            url_access: Some(&self.url_access),
+            ..rules.clone()
+        };
+
+        let url = match validate_url(raw_url, &policy) {
+            Ok(url) => url,
+            Err(e) => {
+                let host = extract_host(
+                    raw_url.trim(),
+                    rules.scheme_policy,
+                    rules.ipv6_error_context,
+                )
+                .unwrap_or_else(|_| "<invalid>".to_string());
+                tracing::debug!(tool = tool_name, host = %host, "egress blocked: {e}");
+                self.stats.lock().entry(host).or_default().blocked += 1;
+                return Err(e);
+            }
+        };
+
+        let host = extract_host(&url, rules.scheme_policy, rules.ipv6_error_context)?;
+        if let Some((bucket, limit)) = self.rate_limit_for(&host) {
+            if !self.try_acquire(bucket, limit) {
+                tracing::warn!(tool = tool_name, host = %host, limit, "egress rate limit exceeded");
+                self.stats
+                    .lock()
+                    .entry(host.clone())
+                    .or_default()
+                    .rate_limited += 1;
+                anyhow::bail!(
+                    "Egress rate limit exceeded for host '{host}' ({limit} requests/minute, security.egress)"
+                );
+            }
+        }
+
+        self.stats.lock().entry(host).or_default().allowed += 1;
+        Ok(url)
+    }
+
+    /// Snapshot of per-host counters, sorted by host.
+    pub fn stats(&self) -> BTreeMap<String, EgressDomainStats> {
+        self.stats.lock().clone()
+    }
+
+    fn same_rules(&self, other: &Self) -> bool {
+        self.rate_limit_per_minute == other.rate_limit_per_minute
+            && self.domain_rate_limits == other.domain_rate_limits
+            && format!("{:?}", self.url_access) == format!("{:?}", other.url_access)
+    }
+
+    /// Rate bucket and limit for a host. A `domain_rate_limits` entry shares
+    /// one bucket across the domain and its subdomains; otherwise each host
+    /// gets its own bucket under the default limit.
+    fn rate_limit_for<'a>(&'a self, host: &'a str) -> Option<(&'a str, u32)> {
+        let (bucket, limit) = self
+            .domain_rate_limits
+            .iter()
+            .find(|(pattern, _)| host_matches_allowlist(host, std::slice::from_ref(pattern)))
+            .map_or((host, self.rate_limit_per_minute), |(pattern, limit)| {
+                (pattern.as_str(), *limit)
+            });
+        (limit > 0).then_some((bucket, limit))
+    }
+
+    fn try_acquire(&self, bucket: &str, limit: u32) -> bool {
+        let now = Instant::now();
+        let mut windows = self.windows.lock();
+        let window = windows.entry(bucket.to_string()).or_default();
+        while window
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= RATE_WINDOW)
+        {
+            window.pop_front();
+        }
+        if window.len() >= limit as usize {
+            return false;
+        }
+        window.push_back(now);
+        true
+    }
+}
+
+/// Per-host counters summed over every [`EgressPolicy::shared`] policy in
+/// the process.
+pub fn process_stats() -> BTreeMap<String, EgressDomainStats> {
+    let mut totals: BTreeMap<String, EgressDomainStats> = BTreeMap::new();
+    let Some(shared) = SHARED.get() else {
+        return totals;
+    };
+    for policy in shared.lock().iter() {
+        for (host, stats) in policy.stats() {
+            let total = totals.entry(host).or_default();
+            total.allowed += stats.allowed;
+            total.blocked += stats.blocked;
+            total.rate_limited += stats.rate_limited;
+        }
+    }
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::url_validation::UrlSchemePolicy;
+
+    fn rules(allowed: &[String]) -> DomainPolicy<'_> {
+        DomainPolicy {
+            allowed_domains: allowed,
+            blocked_domains: &[],
+            allowed_field_name: "test.allowed_domains",
+            blocked_field_name: None,
+            empty_allowed_message: "no domains",
+            scheme_policy: UrlSchemePolicy::HttpOrHttps,
+            ipv6_error_context: "test",
+            url_access: None,
+        }
+    }
+
+    #[test]
+    fn global_blocklist_applies_to_every_tool() {
+        let policy = EgressPolicy::from_url_access(UrlAccessConfig {
+            domain_blocklist: vec!["evil.example".into()],
+            ..UrlAccessConfig::default()
+        });
+        let allowed = vec!["*".to_string()];
+
+        for tool in ["web_fetch", "http_request", "browser"] {
+            let err = policy
+                .authorize(tool, "https://api.evil.example/x", &rules(&allowed))
+                .unwrap_err();
+            assert!(err.to_string().contains("domain_blocklist"));
+        }
+        assert_eq!(policy.stats()["api.evil.example"].blocked, 3);
+    }
+
+    #[test]
+    fn per_domain_rate_limit_is_shared_across_tools() {
+        let policy = EgressPolicy::new(
+            UrlAccessConfig::default(),
+            &EgressConfig {
+                rate_limit_per_minute: 0,
+                domain_rate_limits: [("example.com".to_string(), 2)].into_iter().collect(),
+            },
+        );
+        let allowed = vec!["example.com".to_string(), "example.org".to_string()];
+
+        assert!(policy
+            .authorize("web_fetch", "https://example.com/a", &rules(&allowed))
+            .is_ok());
+        assert!(policy
+            .authorize(
+                "http_request",
+                "https://docs.example.com/b",
+                &rules(&allowed)
+            )
+            .is_ok());
+        // Subdomains share the domain's budget, regardless of which tool asks.
+        let err = policy
+            .authorize("web_fetch", "https://example.com/c", &rules(&allowed))
+            .unwrap_err();
+        assert!(err.to_string().contains("rate limit"));
+        // Domains without an override stay unlimited by default.
+        for _ in 0..5 {
+            assert!(policy
+                .authorize("web_fetch", "https://example.org", &rules(&allowed))
+                .is_ok());
+        }
+
+        let stats = policy.stats();
+        assert_eq!(stats["example.com"].allowed, 1);
+        assert_eq!(stats["example.com"].rate_limited, 1);
+        assert_eq!(stats["docs.example.com"].allowed, 1);
+    }
+
+    #[test]
+    fn shared_policy_is_reused_for_the_same_rules() {
+        let egress = EgressConfig {
+            rate_limit_per_minute: 1,
+            domain_rate_limits: [("egress-shared.example.net".to_string(), 1)]
+                .into_iter()
+                .collect(),
+        };
+        let first = EgressPolicy::shared(UrlAccessConfig::default(), &egress);
+        let second = EgressPolicy::shared(UrlAccessConfig::default(), &egress);
+        assert!(Arc::ptr_eq(&first, &second));
+        let other = EgressPolicy::shared(
+            UrlAccessConfig::default(),
+            &EgressConfig {
+                rate_limit_per_minute: 2,
+                ..egress.clone()
+            },
+        );
+        assert!(!Arc::ptr_eq(&first, &other));
+
+        let allowed = vec!["egress-shared.example.net".to_string()];
+        assert!(first
+            .authorize(
+                "web_fetch",
+                "https://egress-shared.example.net",
+                &rules(&allowed)
+            )
+            .is_ok());
+        // A second registry built from the same config shares the budget.
+        assert!(second
+            .authorize(
+                "web_fetch",
+                "https://egress-shared.example.net",
+                &rules(&allowed)
+            )
+            .is_err());
+        let totals = process_stats();
+        assert_eq!(totals["egress-shared.example.net"].allowed, 1);
+        assert_eq!(totals["egress-shared.example.net"].rate_limited, 1);
+    }
+}
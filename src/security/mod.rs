@@ -4,7 +4,8 @@
 //! [`SecurityPolicy`] defines autonomy levels, workspace boundaries, and
 //! access-control rules that are enforced across the tool and runtime subsystems.
 //! [`PairingGuard`] implements device pairing for channel authentication, and
-//! [`SecretStore`] handles encrypted credential storage.
+//! [`SecretStore`] handles encrypted credential storage, with [`SecretVault`]
+//! resolving `secret://` references in config.
 //!
 //! OS-level isolation is provided through the [`Sandbox`] trait defined in
 //! [`traits`], with pluggable backends including Docker, Firejail, Bubblewrap,
@@ -21,17 +22,21 @@
 pub mod audit;
 #[cfg(feature = "sandbox-bubblewrap")]
 pub mod bubblewrap;
+pub mod command_policy;
 pub mod detect;
 pub mod docker;
 
 // Prompt injection defense (contributed from RustyClaw, MIT licensed)
 pub mod domain_matcher;
+pub mod egress;
 pub mod estop;
 #[cfg(target_os = "linux")]
 pub mod firejail;
+pub mod injection_guard;
 #[cfg(feature = "sandbox-landlock")]
 pub mod landlock;
 pub mod leak_detector;
+pub mod moderation;
 pub mod otp;
 pub mod pairing;
 pub mod perplexity;
@@ -40,14 +45,20 @@ pub mod prompt_guard;
 pub mod roles;
 pub mod secrets;
 pub mod syscall_anomaly;
+pub mod tool_anomaly;
 pub mod traits;
+pub mod vault;
 
 #[allow(unused_imports)]
-pub use audit::{AuditEvent, AuditEventType, AuditLogger};
+pub use audit::{AuditEvent, AuditEventType, AuditLogger, TranscriptEntry};
+#[allow(unused_imports)]
+pub use command_policy::{CommandPolicy, CommandVerdict};
 #[allow(unused_imports)]
 pub use detect::create_sandbox;
 pub use domain_matcher::DomainMatcher;
 #[allow(unused_imports)]
+pub use egress::{EgressDomainStats, EgressPolicy};
+#[allow(unused_imports)]
 pub use estop::{EstopLevel, EstopManager, EstopState, ResumeSelector};
 #[allow(unused_imports)]
 pub use otp::OtpValidator;
@@ -55,7 +66,9 @@ pub use otp::OtpValidator;
 pub use pairing::PairingGuard;
 #[allow(unused_imports)]
 pub use perplexity::{detect_adversarial_suffix, PerplexityAssessment};
-pub use policy::{AutonomyLevel, SecurityPolicy};
+pub use policy::{
+    resolve_autonomy_override, turn_autonomy, with_turn_autonomy, AutonomyLevel, SecurityPolicy,
+};
 #[allow(unused_imports)]
 pub use roles::{RoleRegistry, ToolAccess};
 #[allow(unused_imports)]
@@ -63,10 +76,16 @@ pub use secrets::SecretStore;
 #[allow(unused_imports)]
 pub use syscall_anomaly::{SyscallAnomalyAlert, SyscallAnomalyDetector, SyscallAnomalyKind};
 #[allow(unused_imports)]
+pub use tool_anomaly::{ToolAnomalyAlert, ToolAnomalyKind, ToolAnomalyMonitor};
+#[allow(unused_imports)]
 pub use traits::{NoopSandbox, Sandbox};
+#[allow(unused_imports)]
+pub use vault::{SecretVault, SECRET_REF_PREFIX};
 // Prompt injection defense exports
 #[allow(unused_imports)]
-pub use leak_detector::{LeakDetector, LeakResult};
+pub use injection_guard::{detect_injection, guard_tool_output, GuardedContent};
+#[allow(unused_imports)]
+pub use leak_detector::{ArgumentScan, LeakDetector, LeakResult, StreamScanner};
 #[allow(unused_imports)]
 pub use prompt_guard::{GuardAction, GuardResult, PromptGuard};
 
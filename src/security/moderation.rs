@@ -0,0 +1,402 @@
+//! Post-generation moderation of outbound replies.
+//!
+//! With `[security.moderation]` enabled, every final channel reply passes
+//! through [`Moderator::moderate`] before `on_message_sending` hooks run.
+//! Three stages can flag content, in order: regex rules, an OpenAI-compatible
+//! moderation endpoint, and a classifier model. Each finding carries an
+//! action: `warn` delivers the reply unchanged, `rewrite` replaces the
+//! flagged text, and `block` withholds the reply. A block stops the
+//! remaining stages. The caller records findings in the runtime trace.
+
+use crate::config::{ModerationAction, ModerationConfig};
+use crate::providers::Provider;
+use crate::workspace::Scoped;
+use regex::Regex;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+const CLASSIFIER_PROMPT: &str = "You are a content moderation classifier. Decide whether the assistant reply below is safe to send to the user. Flag harassment, hate, sexual content involving minors, instructions for serious harm, or self-harm encouragement. Answer with exactly `SAFE`, or `UNSAFE: <short reason>`.";
+
+/// Longest reply excerpt sent to the classifier model.
+const CLASSIFIER_MAX_CHARS: usize = 8_000;
+
+/// One stage that flagged the reply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModerationFinding {
+    /// `rule[<index>]`, `endpoint` or `classifier`.
+    pub source: String,
+    pub action: ModerationAction,
+    /// Categories or reason reported by the stage.
+    pub detail: String,
+}
+
+/// Result of moderating one reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModerationOutcome {
+    /// Text to deliver: the original, a rewrite, or the block message.
+    pub text: String,
+    pub findings: Vec<ModerationFinding>,
+}
+
+impl ModerationOutcome {
+    /// Most severe action taken, if anything was flagged.
+    pub fn action(&self) -> Option<ModerationAction> {
+        self.findings.iter().map(|finding| finding.action).max()
+    }
+
+    /// Whether the delivered text differs from the reply.
+    pub fn modified(&self) -> bool {
+        matches!(
+            self.action(),
+            Some(ModerationAction::Rewrite | ModerationAction::Block)
+        )
+    }
+}
+
+struct ModerationRule {
+    regex: Regex,
+    action: ModerationAction,
+    replacement: String,
+}
+
+/// Compiled `[security.moderation]` settings.
+pub struct Moderator {
+    rules: Vec<ModerationRule>,
+    endpoint: Option<String>,
+    endpoint_api_key: Option<String>,
+    endpoint_model: String,
+    classifier_hint: Option<String>,
+    flagged_action: ModerationAction,
+    replacement: String,
+    block_message: String,
+    timeout: Duration,
+    fail_closed: bool,
+}
+
+impl Moderator {
+    pub fn from_config(config: &ModerationConfig) -> anyhow::Result<Self> {
+        let rules = config
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let regex = Regex::new(&rule.pattern).map_err(|e| {
+                    anyhow::anyhow!("security.moderation.rules[{i}].pattern is invalid: {e}")
+                })?;
+                Ok(ModerationRule {
+                    regex,
+                    action: rule.action,
+                    replacement: rule
+                        .replacement
+                        .clone()
+                        .unwrap_or_else(|| config.replacement.clone()),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self {
+            rules,
+            endpoint: config.endpoint.clone(),
+            endpoint_api_key: config.endpoint_api_key.clone(),
+            endpoint_model: config.endpoint_model.clone(),
+            classifier_hint: config.classifier_hint.clone(),
+            flagged_action: config.flagged_action,
+            replacement: config.replacement.clone(),
+            block_message: config.block_message.clone(),
+            timeout: Duration::from_secs(config.timeout_secs.max(1)),
+            fail_closed: config.fail_closed,
+        })
+    }
+
+    /// `[[model_routes]]` hint of the classifier model, if configured.
+    pub fn classifier_hint(&self) -> Option<&str> {
+        self.classifier_hint.as_deref()
+    }
+
+    /// Moderate `reply`. `classifier` is the provider and model resolved
+    /// from [`Self::classifier_hint`]; the classifier stage is skipped
+    /// without it.
+    pub async fn moderate(
+        &self,
+        reply: &str,
+        classifier: Option<(&dyn Provider, &str)>,
+    ) -> ModerationOutcome {
+        let mut text = reply.to_string();
+        let mut findings = Vec::new();
+
+        for (i, rule) in self.rules.iter().enumerate() {
+            let matches = rule.regex.find_iter(&text).count();
+            if matches == 0 {
+                continue;
+            }
+            findings.push(ModerationFinding {
+                source: format!("rule[{i}]"),
+                action: rule.action,
+                detail: format!("{matches} match(es) of `{}`", rule.regex.as_str()),
+            });
+            match rule.action {
+                ModerationAction::Block => return self.blocked(findings),
+                ModerationAction::Rewrite => {
+                    text = rule
+                        .regex
+                        .replace_all(&text, rule.replacement.as_str())
+                        .into_owned();
+                }
+                ModerationAction::Warn => {}
+            }
+        }
+
+        if let Some(endpoint) = self.endpoint.as_deref() {
+            let verdict = tokio::time::timeout(self.timeout, self.check_endpoint(endpoint, &text))
+                .await
+                .unwrap_or_else(|_| Err(anyhow::anyhow!("timed out")));
+            if let Some(finding) = self.judge("endpoint", verdict) {
+                if let Some(outcome) = self.apply_flag(&mut text, &mut findings, finding) {
+                    return outcome;
+                }
+            }
+        }
+
+        if let Some((provider, model)) = classifier {
+            let verdict =
+                tokio::time::timeout(self.timeout, Self::check_classifier(provider, model, &text))
+                    .await
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("timed out")));
+            if let Some(finding) = self.judge("classifier", verdict) {
+                if let Some(outcome) = self.apply_flag(&mut text, &mut findings, finding) {
+                    return outcome;
+                }
+            }
+        }
+
+        ModerationOutcome { text, findings }
+    }
+
+    fn blocked(&self, findings: Vec<ModerationFinding>) -> ModerationOutcome {
+        ModerationOutcome {
+            text: self.block_message.clone(),
+            findings,
+        }
+    }
+
+    /// Turn an endpoint or classifier verdict (`Some(detail)` when flagged)
+    /// into a finding, applying `fail_closed` to errors.
+    fn judge(
+        &self,
+        source: &str,
+        verdict: anyhow::Result<Option<String>>,
+    ) -> Option<ModerationFinding> {
+        match verdict {
+            Ok(Some(detail)) => Some(ModerationFinding {
+                source: source.to_string(),
+                action: self.flagged_action,
+                detail,
+            }),
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!(source, "Reply moderation check failed: {e}");
+                self.fail_closed.then(|| ModerationFinding {
+                    source: source.to_string(),
+                    action: ModerationAction::Block,
+                    detail: format!("check failed: {e}"),
+                })
+            }
+        }
+    }
+
+    /// Record `finding`; returns the final outcome when it blocks.
+    fn apply_flag(
+        &self,
+        text: &mut String,
+        findings: &mut Vec<ModerationFinding>,
+        finding: ModerationFinding,
+    ) -> Option<ModerationOutcome> {
+        let action = finding.action;
+        findings.push(finding);
+        match action {
+            ModerationAction::Block => Some(self.blocked(std::mem::take(findings))),
+            ModerationAction::Rewrite => {
+                text.clone_from(&self.replacement);
+                None
+            }
+            ModerationAction::Warn => None,
+        }
+    }
+
+    async fn check_endpoint(&self, endpoint: &str, text: &str) -> anyhow::Result<Option<String>> {
+        let client = crate::config::build_runtime_proxy_client("provider.openai");
+        let mut request = client.post(endpoint).json(&serde_json::json!({
+            "model": self.endpoint_model,
+            "input": text,
+        }));
+        if let Some(key) = self.endpoint_api_key.as_deref() {
+            request = request.bearer_auth(key);
+        }
+        let response = request.send().await?.error_for_status()?;
+        let body: serde_json::Value = response.json().await?;
+        let Some(result) = body.pointer("/results/0") else {
+            anyhow::bail!("moderation response has no results");
+        };
+        if !result
+            .get("flagged")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+        {
+            return Ok(None);
+        }
+        let categories: Vec<&str> = result
+            .get("categories")
+            .and_then(serde_json::Value::as_object)
+            .map(|categories| {
+                categories
+                    .iter()
+                    .filter(|(_, flagged)| flagged.as_bool() == Some(true))
+                    .map(|(name, _)| name.as_str())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(Some(if categories.is_empty() {
+            "flagged".to_string()
+        } else {
+            categories.join(", ")
+        }))
+    }
+
+    async fn check_classifier(
+        provider: &dyn Provider,
+        model: &str,
+        text: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let excerpt: String = text.chars().take(CLASSIFIER_MAX_CHARS).collect();
+        let answer = provider
+            .chat_with_system(Some(CLASSIFIER_PROMPT), &excerpt, model, 0.0)
+            .await?;
+        Ok(parse_classifier_answer(&answer))
+    }
+}
+
+/// `Some(reason)` when the classifier answered `UNSAFE`.
+fn parse_classifier_answer(answer: &str) -> Option<String> {
+    let answer = answer.trim();
+    let rest = answer
+        .get(..6)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("unsafe"))
+        .map(|_| &answer[6..])?;
+    let reason = rest.trim_start_matches(':').trim();
+    Some(if reason.is_empty() {
+        "unsafe".to_string()
+    } else {
+        reason.to_string()
+    })
+}
+
+static MODERATOR: Scoped<Option<Arc<Moderator>>> = Scoped::new(None);
+
+/// Apply `[security.moderation]`. Moderation stays off when it is disabled
+/// or the rules fail to compile.
+pub fn init_from_config(config: &ModerationConfig) {
+    let moderator = if config.enabled {
+        match Moderator::from_config(config) {
+            Ok(moderator) => Some(Arc::new(moderator)),
+            Err(e) => {
+                tracing::error!("Reply moderation disabled: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    MODERATOR.set(moderator);
+}
+
+/// The configured moderator, or `None` when moderation is off.
+pub fn moderator() -> Option<Arc<Moderator>> {
+    MODERATOR.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ModerationRuleConfig;
+
+    fn rule(pattern: &str, action: ModerationAction) -> ModerationRuleConfig {
+        ModerationRuleConfig {
+            pattern: pattern.into(),
+            action,
+            replacement: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn rules_rewrite_warn_and_block_in_order() {
+        let moderator = Moderator::from_config(&ModerationConfig {
+            enabled: true,
+            rules: vec![
+                rule(r"(?i)darn", ModerationAction::Rewrite),
+                rule(r"(?i)internal-only", ModerationAction::Warn),
+            ],
+            ..ModerationConfig::default()
+        })
+        .unwrap();
+
+        let outcome = moderator
+            .moderate("Darn, that is internal-only.", None)
+            .await;
+        assert_eq!(
+            outcome.text,
+            "[removed by moderation], that is internal-only."
+        );
+        assert_eq!(outcome.action(), Some(ModerationAction::Rewrite));
+        assert_eq!(outcome.findings.len(), 2);
+        assert!(outcome.modified());
+
+        let clean = moderator.moderate("All good.", None).await;
+        assert_eq!(clean.text, "All good.");
+        assert!(clean.findings.is_empty());
+
+        let blocking = Moderator::from_config(&ModerationConfig {
+            enabled: true,
+            rules: vec![
+                rule("secret plan", ModerationAction::Block),
+                rule("plan", ModerationAction::Rewrite),
+            ],
+            ..ModerationConfig::default()
+        })
+        .unwrap();
+        let outcome = blocking.moderate("The secret plan is ready.", None).await;
+        assert_eq!(outcome.text, ModerationConfig::default().block_message);
+        assert_eq!(outcome.findings.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn unreachable_endpoint_follows_fail_closed() {
+        let config = ModerationConfig {
+            enabled: true,
+            endpoint: Some("http://127.0.0.1:9/v1/moderations".into()),
+            timeout_secs: 2,
+            ..ModerationConfig::default()
+        };
+        let open = Moderator::from_config(&config).unwrap();
+        assert!(open.moderate("hello", None).await.findings.is_empty());
+
+        let closed = Moderator::from_config(&ModerationConfig {
+            fail_closed: true,
+            ..config
+        })
+        .unwrap();
+        assert_eq!(
+            closed.moderate("hello", None).await.action(),
+            Some(ModerationAction::Block)
+        );
+    }
+
+    #[test]
+    fn classifier_answers_are_parsed() {
+        assert_eq!(parse_classifier_answer("SAFE"), None);
+        assert_eq!(
+            parse_classifier_answer(" unsafe: threatens a person "),
+            Some("threatens a person".into())
+        );
+        assert_eq!(parse_classifier_answer("UNSAFE"), Some("unsafe".into()));
+    }
+}
@@ -1,6 +1,8 @@
+use super::command_policy::{CommandPolicy, CommandVerdict};
 use parking_lot::Mutex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
@@ -32,12 +34,53 @@ impl std::str::FromStr for AutonomyLevel {
     }
 }
 
+tokio::task_local! {
+    static TURN_AUTONOMY: AutonomyLevel;
+}
+
+/// Run `fut` with `level`, when set, in place of the configured autonomy
+/// level of every [`SecurityPolicy`] and `ApprovalManager` it consults.
+/// Channels use this to apply `autonomy.level_overrides` to one turn.
+pub async fn with_turn_autonomy<F: std::future::Future>(
+    level: Option<AutonomyLevel>,
+    fut: F,
+) -> F::Output {
+    match level {
+        Some(level) => TURN_AUTONOMY.scope(level, fut).await,
+        None => fut.await,
+    }
+}
+
+/// Autonomy level override of the running turn, if any.
+pub fn turn_autonomy() -> Option<AutonomyLevel> {
+    TURN_AUTONOMY.try_with(|level| *level).ok()
+}
+
+/// Level for `principal` on `channel` from `autonomy.level_overrides`.
+/// `channel:principal` wins over `channel:*`, which wins over `*:principal`.
+pub fn resolve_autonomy_override(
+    overrides: &HashMap<String, AutonomyLevel>,
+    channel: &str,
+    principal: &str,
+) -> Option<AutonomyLevel> {
+    [
+        format!("{channel}:{principal}"),
+        format!("{channel}:*"),
+        format!("*:{principal}"),
+    ]
+    .iter()
+    .find_map(|key| overrides.get(key).copied())
+}
+
 /// Risk score for shell command execution.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CommandRiskLevel {
     Low,
     Medium,
     High,
+    /// Escalated by a `critical` command-policy rule: always requires explicit
+    /// approval, at every autonomy level.
+    Critical,
 }
 
 /// Classifies whether a tool operation is read-only or side-effecting.
@@ -101,14 +144,33 @@ pub struct SecurityPolicy {
     pub allowed_commands: Vec<String>,
     pub forbidden_paths: Vec<String>,
     pub allowed_roots: Vec<PathBuf>,
+    /// Per-tool path scopes. A tool listed here may only touch resolved paths
+    /// under one of its roots (on top of the workspace/allowlist checks).
+    pub tool_path_scopes: HashMap<String, Vec<PathBuf>>,
     pub max_actions_per_hour: u32,
     pub max_cost_per_day_cents: u32,
     pub require_approval_for_medium_risk: bool,
     pub block_high_risk_commands: bool,
     pub shell_env_passthrough: Vec<String>,
+    pub command_policy: CommandPolicy,
+    /// Deepest delegation chain a sub-agent may start, across all agents.
+    pub max_delegation_depth: u32,
+    /// Cap on tool-call iterations for one sub-agent run.
+    pub max_subagent_iterations: usize,
+    /// Cap on output tokens per sub-agent provider call (`0` = uncapped).
+    pub max_subagent_tokens: u32,
+    /// Cap on sub-agent tasks one `delegate` call may run in parallel.
+    pub max_parallel_subagents: usize,
     pub tracker: ActionTracker,
 }
 
+/// Limits a single sub-agent run is held to after policy caps are applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DelegationBudget {
+    pub max_iterations: usize,
+    pub max_tokens: Option<u32>,
+}
+
 impl Default for SecurityPolicy {
     fn default() -> Self {
         Self {
@@ -153,11 +215,17 @@ impl Default for SecurityPolicy {
                 "~/.config".into(),
             ],
             allowed_roots: Vec::new(),
+            tool_path_scopes: HashMap::new(),
             max_actions_per_hour: 20,
             max_cost_per_day_cents: 500,
             require_approval_for_medium_risk: true,
             block_high_risk_commands: true,
             shell_env_passthrough: vec![],
+            command_policy: CommandPolicy::default(),
+            max_delegation_depth: 3,
+            max_subagent_iterations: 20,
+            max_subagent_tokens: 0,
+            max_parallel_subagents: 4,
             tracker: ActionTracker::new(),
         }
     }
@@ -183,6 +251,16 @@ fn expand_user_path(path: &str) -> PathBuf {
     PathBuf::from(path)
 }
 
+/// Expand `~` and anchor relative entries at the workspace.
+fn resolve_policy_root(root: &str, workspace_dir: &Path) -> PathBuf {
+    let expanded = expand_user_path(root);
+    if expanded.is_absolute() {
+        expanded
+    } else {
+        workspace_dir.join(expanded)
+    }
+}
+
 // ── Shell Command Parsing Utilities ───────────────────────────────────────
 // These helpers implement a minimal quote-aware shell lexer. They exist
 // because security validation must reason about the *structure* of a
@@ -191,7 +269,7 @@ fn expand_user_path(path: &str) -> PathBuf {
 // inside quoted arguments or chained operators.
 /// Skip leading environment variable assignments (e.g. `FOO=bar cmd args`).
 /// Returns the remainder starting at the first non-assignment word.
-fn skip_env_assignments(s: &str) -> &str {
+pub(super) fn skip_env_assignments(s: &str) -> &str {
     let mut rest = s;
     loop {
         let Some(word) = rest.split_whitespace().next() else {
@@ -228,7 +306,7 @@ enum QuoteState {
 ///
 /// Characters inside single or double quotes are treated as literals, so
 /// `sqlite3 db "SELECT 1; SELECT 2;"` remains a single segment.
-fn split_unquoted_segments(command: &str) -> Vec<String> {
+pub(super) fn split_unquoted_segments(command: &str) -> Vec<String> {
     let mut segments = Vec::new();
     let mut current = String::new();
     let mut quote = QuoteState::None;
@@ -566,8 +644,20 @@ impl SecurityPolicy {
     // highest risk across all segments wins. This prevents bypasses like
     // `ls && rm -rf /` from being classified as Low just because `ls` is safe.
 
-    /// Classify command risk. Any high-risk segment marks the whole command high.
+    /// Classify command risk. Any high-risk segment marks the whole command high;
+    /// a matching `critical` command-policy rule or critical category raises it
+    /// to critical.
     pub fn command_risk_level(&self, command: &str) -> CommandRiskLevel {
+        if matches!(
+            self.command_policy.evaluate(command),
+            CommandVerdict::Critical(_) | CommandVerdict::Flagged(_)
+        ) {
+            return CommandRiskLevel::Critical;
+        }
+        Self::heuristic_risk_level(command)
+    }
+
+    fn heuristic_risk_level(command: &str) -> CommandRiskLevel {
         let mut saw_medium = false;
 
         for segment in split_unquoted_segments(command) {
@@ -676,9 +766,11 @@ impl SecurityPolicy {
     // ── Command Execution Policy Gate ──────────────────────────────────────
     // Validation follows a strict precedence order:
     //   1. Allowlist check (is the base command permitted at all?)
+    //   1b. Command policy rules/categories (deny, or escalate to critical)
     //   2. Risk classification (high / medium / low)
     //   3. Policy flags (block_high_risk_commands, require_approval_for_medium_risk)
     //   4. Autonomy level × approval status (supervised requires explicit approval)
+    //   5. Critical categories (e.g. destructive) need explicit approval at any level
     // This ordering ensures deny-by-default: unknown commands are rejected
     // before any risk or autonomy logic runs.
 
@@ -696,7 +788,25 @@ impl SecurityPolicy {
             return Err(format!("Path blocked by security policy: {path}"));
         }
 
-        let risk = self.command_risk_level(command);
+        let flagged = match self.command_policy.evaluate(command) {
+            CommandVerdict::Deny(reason) => {
+                return Err(format!("Command blocked by command policy: {reason}"));
+            }
+            // Critical rules are an explicit operator decision, so they take
+            // precedence over `block_high_risk_commands` but never run unapproved.
+            CommandVerdict::Critical(reason) => {
+                if !approved {
+                    return Err(format!(
+                        "Command requires explicit approval (approved=true): critical-risk operation ({reason})"
+                    ));
+                }
+                return Ok(CommandRiskLevel::Critical);
+            }
+            CommandVerdict::Flagged(reason) => Some(reason),
+            CommandVerdict::Default => None,
+        };
+
+        let risk = Self::heuristic_risk_level(command);
 
         if risk == CommandRiskLevel::High {
             if self.block_high_risk_commands {
@@ -709,7 +819,7 @@ impl SecurityPolicy {
                 }
                 return Err("Command blocked: high-risk command is disallowed by policy".into());
             }
-            if self.autonomy == AutonomyLevel::Supervised && !approved {
+            if self.effective_autonomy() == AutonomyLevel::Supervised && !approved {
                 return Err(
                     "Command requires explicit approval (approved=true): high-risk operation"
                         .into(),
@@ -718,7 +828,7 @@ impl SecurityPolicy {
         }
 
         if risk == CommandRiskLevel::Medium
-            && self.autonomy == AutonomyLevel::Supervised
+            && self.effective_autonomy() == AutonomyLevel::Supervised
             && self.require_approval_for_medium_risk
             && !approved
         {
@@ -727,6 +837,15 @@ impl SecurityPolicy {
             );
         }
 
+        if let Some(reason) = flagged {
+            if !approved {
+                return Err(format!(
+                    "Command requires explicit approval (approved=true): critical-risk operation ({reason})"
+                ));
+            }
+            return Ok(CommandRiskLevel::Critical);
+        }
+
         Ok(risk)
     }
 
@@ -745,7 +864,7 @@ impl SecurityPolicy {
     /// - Blocks shell redirections (`<`, `>`, `>>`) that can bypass path policy
     /// - Blocks dangerous arguments (e.g. `find -exec`, `git config`)
     pub fn is_command_allowed(&self, command: &str) -> bool {
-        if self.autonomy == AutonomyLevel::ReadOnly {
+        if self.effective_autonomy() == AutonomyLevel::ReadOnly {
             return false;
         }
 
@@ -1019,9 +1138,59 @@ impl SecurityPolicy {
         )
     }
 
+    /// Validate a canonicalized path for a specific tool.
+    ///
+    /// Applies [`Self::is_resolved_path_allowed`] first, then confines the path
+    /// to the tool's configured scope roots, if any. Scope roots are
+    /// canonicalized too, so a symlink inside a scope that points elsewhere in
+    /// the workspace is still rejected.
+    pub fn check_tool_resolved_path(&self, tool_name: &str, resolved: &Path) -> Result<(), String> {
+        if !self.is_resolved_path_allowed(resolved) {
+            return Err(self.resolved_path_violation_message(resolved));
+        }
+
+        let Some(scopes) = self.tool_path_scopes.get(tool_name) else {
+            return Ok(());
+        };
+
+        let in_scope = scopes.iter().any(|scope| {
+            let canonical = scope.canonicalize().unwrap_or_else(|_| scope.clone());
+            resolved.starts_with(&canonical)
+        });
+        if in_scope {
+            return Ok(());
+        }
+
+        let roots = scopes
+            .iter()
+            .map(|scope| scope.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        Err(format!(
+            "Resolved path is outside the scope configured for '{tool_name}': {}. Allowed: {roots}. Adjust [autonomy.tool_path_scopes] to widen it.",
+            resolved.display()
+        ))
+    }
+
+    /// Working directory for a tool: the first scope root when one is
+    /// configured, otherwise the workspace. Used to pin the shell's cwd.
+    pub fn tool_working_dir(&self, tool_name: &str) -> PathBuf {
+        self.tool_path_scopes
+            .get(tool_name)
+            .and_then(|scopes| scopes.first())
+            .cloned()
+            .unwrap_or_else(|| self.workspace_dir.clone())
+    }
+
+    /// Autonomy level in force: the running turn's override, if any,
+    /// otherwise the configured level.
+    pub fn effective_autonomy(&self) -> AutonomyLevel {
+        turn_autonomy().unwrap_or(self.autonomy)
+    }
+
     /// Check if autonomy level permits any action at all
     pub fn can_act(&self) -> bool {
-        self.autonomy != AutonomyLevel::ReadOnly
+        self.effective_autonomy() != AutonomyLevel::ReadOnly
     }
 
     // ── Tool Operation Gating ──────────────────────────────────────────────
@@ -1056,6 +1225,51 @@ impl SecurityPolicy {
         }
     }
 
+    // ── Delegation Budgets ─────────────────────────────────────────────────
+    // Sub-agents inherit the parent's policy. Depth is the lower of the
+    // agent's own `max_depth` and the policy cap; per-run iterations and
+    // tokens are clamped to the policy caps.
+
+    /// Resolve the budget for a sub-agent started at `depth`, or explain why
+    /// the delegation is not allowed.
+    pub fn delegation_budget(
+        &self,
+        depth: u32,
+        agent_max_depth: u32,
+        max_iterations: usize,
+        max_tokens: Option<u32>,
+    ) -> Result<DelegationBudget, String> {
+        let max_depth = agent_max_depth.min(self.max_delegation_depth);
+        if depth >= max_depth {
+            return Err(format!(
+                "Delegation depth limit reached ({depth}/{max_depth}). \
+                 Cannot delegate further to prevent infinite loops."
+            ));
+        }
+
+        let policy_tokens = (self.max_subagent_tokens > 0).then_some(self.max_subagent_tokens);
+        let max_tokens = match (max_tokens.filter(|tokens| *tokens > 0), policy_tokens) {
+            (Some(requested), Some(cap)) => Some(requested.min(cap)),
+            (requested, cap) => requested.or(cap),
+        };
+
+        Ok(DelegationBudget {
+            max_iterations: max_iterations.clamp(1, self.max_subagent_iterations.max(1)),
+            max_tokens,
+        })
+    }
+
+    /// Check that a fan-out of `tasks` sub-agents fits the parallel cap.
+    pub fn check_parallel_delegation(&self, tasks: usize) -> Result<(), String> {
+        if tasks > self.max_parallel_subagents {
+            return Err(format!(
+                "Security policy: {tasks} parallel sub-agent tasks requested, limit is {}",
+                self.max_parallel_subagents
+            ));
+        }
+        Ok(())
+    }
+
     /// Record an action and check if the rate limit has been exceeded.
     /// Returns `true` if the action is allowed, `false` if rate-limited.
     pub fn record_action(&self) -> bool {
@@ -1082,13 +1296,17 @@ impl SecurityPolicy {
             allowed_roots: autonomy_config
                 .allowed_roots
                 .iter()
-                .map(|root| {
-                    let expanded = expand_user_path(root);
-                    if expanded.is_absolute() {
-                        expanded
-                    } else {
-                        workspace_dir.join(expanded)
-                    }
+                .map(|root| resolve_policy_root(root, workspace_dir))
+                .collect(),
+            tool_path_scopes: autonomy_config
+                .tool_path_scopes
+                .iter()
+                .map(|(tool, roots)| {
+                    let roots = roots
+                        .iter()
+                        .map(|root| resolve_policy_root(root, workspace_dir))
+                        .collect();
+                    (tool.clone(), roots)
                 })
                 .collect(),
             max_actions_per_hour: autonomy_config.max_actions_per_hour,
@@ -1096,6 +1314,11 @@ impl SecurityPolicy {
             require_approval_for_medium_risk: autonomy_config.require_approval_for_medium_risk,
             block_high_risk_commands: autonomy_config.block_high_risk_commands,
             shell_env_passthrough: autonomy_config.shell_env_passthrough.clone(),
+            command_policy: CommandPolicy::from_config(&autonomy_config.command_policy),
+            max_delegation_depth: autonomy_config.max_delegation_depth,
+            max_subagent_iterations: autonomy_config.max_subagent_iterations,
+            max_subagent_tokens: autonomy_config.max_subagent_tokens,
+            max_parallel_subagents: autonomy_config.max_parallel_subagents,
             tracker: ActionTracker::new(),
         }
     }
@@ -1184,6 +1407,73 @@ mod tests {
         assert!(err.contains("Rate limit exceeded"));
     }
 
+    // ── Delegation budgets ───────────────────────────────────
+
+    #[test]
+    fn delegation_depth_uses_lower_of_agent_and_policy_limits() {
+        let p = SecurityPolicy {
+            max_delegation_depth: 2,
+            ..default_policy()
+        };
+        assert!(p.delegation_budget(1, 5, 10, None).is_ok());
+        let err = p.delegation_budget(2, 5, 10, None).unwrap_err();
+        assert!(err.contains("depth limit reached (2/2)"));
+        assert!(p.delegation_budget(0, 1, 10, None).is_ok());
+        assert!(p.delegation_budget(1, 1, 10, None).is_err());
+    }
+
+    #[test]
+    fn delegation_budget_clamps_iterations_and_tokens() {
+        let p = SecurityPolicy {
+            max_subagent_iterations: 5,
+            max_subagent_tokens: 1000,
+            ..default_policy()
+        };
+        assert_eq!(
+            p.delegation_budget(0, 3, 50, Some(4000)).unwrap(),
+            DelegationBudget {
+                max_iterations: 5,
+                max_tokens: Some(1000),
+            }
+        );
+        assert_eq!(
+            p.delegation_budget(0, 3, 3, None).unwrap(),
+            DelegationBudget {
+                max_iterations: 3,
+                max_tokens: Some(1000),
+            }
+        );
+
+        let uncapped = default_policy();
+        assert_eq!(
+            uncapped.delegation_budget(0, 3, 10, Some(512)).unwrap(),
+            DelegationBudget {
+                max_iterations: 10,
+                max_tokens: Some(512),
+            }
+        );
+        assert_eq!(
+            uncapped
+                .delegation_budget(0, 3, 10, None)
+                .unwrap()
+                .max_tokens,
+            None
+        );
+    }
+
+    #[test]
+    fn parallel_delegation_respects_policy_cap() {
+        let p = SecurityPolicy {
+            max_parallel_subagents: 2,
+            ..default_policy()
+        };
+        assert!(p.check_parallel_delegation(2).is_ok());
+        assert!(p
+            .check_parallel_delegation(3)
+            .unwrap_err()
+            .contains("limit is 2"));
+    }
+
     // ── is_command_allowed ───────────────────────────────────
 
     #[test]
@@ -1311,7 +1601,7 @@ mod tests {
             ..SecurityPolicy::default()
         };
         assert_eq!(
-            p.command_risk_level("git reset --hard HEAD~1"),
+            p.command_risk_level("git reset HEAD~1"),
             CommandRiskLevel::Medium
         );
         assert_eq!(
@@ -1323,11 +1613,11 @@ mod tests {
     #[test]
     fn command_risk_high_for_dangerous_commands() {
         let p = SecurityPolicy {
-            allowed_commands: vec!["rm".into()],
+            allowed_commands: vec!["chmod".into()],
             ..SecurityPolicy::default()
         };
         assert_eq!(
-            p.command_risk_level("rm -rf /tmp/test"),
+            p.command_risk_level("chmod -R 777 /tmp/test"),
             CommandRiskLevel::High
         );
     }
@@ -1362,6 +1652,95 @@ mod tests {
         assert!(result.unwrap_err().contains("high-risk"));
     }
 
+    #[test]
+    fn validate_command_applies_command_policy_categories() {
+        let p = SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            allowed_commands: vec!["pip".into(), "sudo".into()],
+            block_high_risk_commands: false,
+            ..SecurityPolicy::default()
+        };
+
+        let err = p
+            .validate_command_execution("pip install requests", true)
+            .unwrap_err();
+        assert!(err.contains("command policy"));
+        assert!(err.contains("package_install"));
+        assert!(p.validate_command_execution("sudo ls", true).is_err());
+    }
+
+    #[test]
+    fn validate_command_critical_rule_requires_approval_in_full_mode() {
+        let autonomy = crate::config::AutonomyConfig {
+            level: AutonomyLevel::Full,
+            allowed_commands: vec!["git".into()],
+            command_policy: crate::config::CommandPolicyConfig {
+                rules: vec![crate::config::CommandRuleConfig {
+                    action: crate::config::CommandRuleAction::Critical,
+                    program: Some("git".into()),
+                    args: Some("push*".into()),
+                    regex: None,
+                    reason: Some("pushes leave the machine".into()),
+                }],
+                ..crate::config::CommandPolicyConfig::default()
+            },
+            ..crate::config::AutonomyConfig::default()
+        };
+        let p = SecurityPolicy::from_config(&autonomy, Path::new("/tmp"));
+
+        assert_eq!(
+            p.command_risk_level("git push origin main"),
+            CommandRiskLevel::Critical
+        );
+        let err = p
+            .validate_command_execution("git push origin main", false)
+            .unwrap_err();
+        assert!(err.contains("critical-risk"));
+        assert!(err.contains("pushes leave the machine"));
+        assert_eq!(
+            p.validate_command_execution("git push origin main", true)
+                .unwrap(),
+            CommandRiskLevel::Critical
+        );
+        assert_eq!(
+            p.validate_command_execution("git status", false).unwrap(),
+            CommandRiskLevel::Low
+        );
+    }
+
+    #[test]
+    fn validate_command_destructive_category_needs_approval_after_risk_gates() {
+        let p = SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            allowed_commands: vec!["git".into(), "rm".into()],
+            ..SecurityPolicy::default()
+        };
+
+        let err = p
+            .validate_command_execution("git push --force origin main", false)
+            .unwrap_err();
+        assert!(err.contains("critical-risk"));
+        assert!(err.contains("destructive"));
+        assert_eq!(
+            p.validate_command_execution("git push --force origin main", true)
+                .unwrap(),
+            CommandRiskLevel::Critical
+        );
+        assert_eq!(
+            p.command_risk_level("git reset --hard HEAD~1"),
+            CommandRiskLevel::Critical
+        );
+        // Flagging never lifts `block_high_risk_commands`.
+        assert_eq!(
+            p.command_risk_level("rm -rf build"),
+            CommandRiskLevel::Critical
+        );
+        let err = p
+            .validate_command_execution("rm -rf build", true)
+            .unwrap_err();
+        assert!(err.contains("high-risk"));
+    }
+
     #[test]
     fn validate_command_full_mode_skips_medium_risk_approval_gate() {
         let p = SecurityPolicy {
@@ -2370,4 +2749,117 @@ mod tests {
             "URL-encoded parent dir traversal must be blocked"
         );
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn tool_path_scopes_confine_tool_and_block_symlink_escape() {
+        use std::os::unix::fs::symlink;
+
+        let root = std::env::temp_dir().join("zeroclaw_test_tool_path_scopes");
+        let workspace = root.join("workspace");
+        let output = workspace.join("output");
+        let notes = workspace.join("notes");
+
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&output).unwrap();
+        std::fs::create_dir_all(&notes).unwrap();
+
+        // Symlink inside the scope pointing elsewhere in the workspace
+        let link_path = output.join("link_to_notes");
+        symlink(&notes, &link_path).unwrap();
+
+        let workspace = workspace.canonicalize().unwrap();
+        let autonomy = crate::config::AutonomyConfig {
+            tool_path_scopes: [("file_write".to_string(), vec!["output".to_string()])]
+                .into_iter()
+                .collect(),
+            ..crate::config::AutonomyConfig::default()
+        };
+        let policy = SecurityPolicy::from_config(&autonomy, &workspace);
+
+        let in_scope = workspace.join("output").join("report.md");
+        assert!(policy
+            .check_tool_resolved_path("file_write", &in_scope)
+            .is_ok());
+
+        let out_of_scope = workspace.join("notes").join("todo.md");
+        let err = policy
+            .check_tool_resolved_path("file_write", &out_of_scope)
+            .unwrap_err();
+        assert!(err.contains("file_write"));
+        assert!(err.contains("tool_path_scopes"));
+
+        let via_symlink = link_path.canonicalize().unwrap().join("todo.md");
+        assert!(policy
+            .check_tool_resolved_path("file_write", &via_symlink)
+            .is_err());
+
+        // Unscoped tools keep plain workspace confinement
+        assert!(policy
+            .check_tool_resolved_path("file_read", &out_of_scope)
+            .is_ok());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn tool_working_dir_uses_first_scope_root() {
+        let workspace = PathBuf::from("/workspace");
+        let autonomy = crate::config::AutonomyConfig {
+            tool_path_scopes: [(
+                "shell".to_string(),
+                vec!["scratch".to_string(), "/data/shared".to_string()],
+            )]
+            .into_iter()
+            .collect(),
+            ..crate::config::AutonomyConfig::default()
+        };
+        let policy = SecurityPolicy::from_config(&autonomy, &workspace);
+
+        assert_eq!(
+            policy.tool_working_dir("shell"),
+            PathBuf::from("/workspace/scratch")
+        );
+        assert_eq!(policy.tool_working_dir("file_write"), workspace);
+    }
+
+    #[tokio::test]
+    async fn turn_autonomy_override_applies_to_most_specific_principal() {
+        let overrides: HashMap<String, AutonomyLevel> = [
+            ("telegram:alice".to_string(), AutonomyLevel::Full),
+            ("telegram:*".to_string(), AutonomyLevel::ReadOnly),
+            ("*:bob".to_string(), AutonomyLevel::Supervised),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            resolve_autonomy_override(&overrides, "telegram", "alice"),
+            Some(AutonomyLevel::Full)
+        );
+        assert_eq!(
+            resolve_autonomy_override(&overrides, "telegram", "bob"),
+            Some(AutonomyLevel::ReadOnly)
+        );
+        assert_eq!(
+            resolve_autonomy_override(&overrides, "slack", "bob"),
+            Some(AutonomyLevel::Supervised)
+        );
+        assert_eq!(
+            resolve_autonomy_override(&overrides, "slack", "carol"),
+            None
+        );
+
+        let policy = SecurityPolicy::default();
+        assert!(policy.can_act());
+        let (can_act, allowed) = with_turn_autonomy(Some(AutonomyLevel::ReadOnly), async {
+            (policy.can_act(), policy.is_command_allowed("ls"))
+        })
+        .await;
+        assert!(!can_act);
+        assert!(!allowed);
+        assert_eq!(
+            with_turn_autonomy(None, async { policy.effective_autonomy() }).await,
+            AutonomyLevel::Supervised
+        );
+    }
 }
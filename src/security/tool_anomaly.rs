@@ -0,0 +1,295 @@
+//! Behavioral anomaly detection over agent tool-call patterns.
+//!
+//! The monitor keeps a small rolling profile per session (CLI, or one channel
+//! conversation) and flags two patterns: bursts of tool calls above the
+//! configured rate, and a read-then-egress sequence where many distinct local
+//! paths are read shortly before a request to a domain the session has never
+//! contacted. With [`ToolAnomalyAction::Pause`], a flagged session stays
+//! paused, so every tool call needs explicit approval until one is approved.
+
+use crate::config::{ToolAnomalyAction, ToolAnomalyConfig};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Sessions idle for longer than this are dropped from the monitor.
+const SESSION_IDLE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+const MAX_TRACKED_SESSIONS: usize = 1024;
+
+/// Anomaly category emitted by [`ToolAnomalyMonitor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolAnomalyKind {
+    CallRateExceeded,
+    ReadThenEgress,
+}
+
+/// A detected anomaly for one session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolAnomalyAlert {
+    pub kind: ToolAnomalyKind,
+    pub message: String,
+    /// The session is paused and this call needs explicit approval.
+    pub paused: bool,
+}
+
+#[derive(Debug)]
+struct SessionProfile {
+    calls: VecDeque<Instant>,
+    reads: VecDeque<(Instant, String)>,
+    contacted_hosts: HashSet<String>,
+    paused: Option<(ToolAnomalyKind, String)>,
+    last_seen: Instant,
+}
+
+impl SessionProfile {
+    fn new(now: Instant) -> Self {
+        Self {
+            calls: VecDeque::new(),
+            reads: VecDeque::new(),
+            contacted_hosts: HashSet::new(),
+            paused: None,
+            last_seen: now,
+        }
+    }
+}
+
+/// Shared per-session tool-call monitor.
+#[derive(Debug)]
+pub struct ToolAnomalyMonitor {
+    config: ToolAnomalyConfig,
+    sessions: Mutex<HashMap<String, SessionProfile>>,
+}
+
+impl ToolAnomalyMonitor {
+    pub fn new(config: ToolAnomalyConfig) -> Self {
+        Self {
+            config,
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record a tool call that is about to run and report any anomaly.
+    pub fn observe(
+        &self,
+        session: &str,
+        tool_name: &str,
+        args: &serde_json::Value,
+    ) -> Option<ToolAnomalyAlert> {
+        self.observe_at(session, tool_name, args, Instant::now())
+    }
+
+    fn observe_at(
+        &self,
+        session: &str,
+        tool_name: &str,
+        args: &serde_json::Value,
+        now: Instant,
+    ) -> Option<ToolAnomalyAlert> {
+        if !self.config.enabled {
+            return None;
+        }
+
+        let mut sessions = self.sessions.lock();
+        if sessions.len() >= MAX_TRACKED_SESSIONS && !sessions.contains_key(session) {
+            sessions.retain(|_, p| now.duration_since(p.last_seen) < SESSION_IDLE_TTL);
+            if sessions.len() >= MAX_TRACKED_SESSIONS {
+                if let Some(oldest) = sessions
+                    .iter()
+                    .min_by_key(|(_, p)| p.last_seen)
+                    .map(|(k, _)| k.clone())
+                {
+                    sessions.remove(&oldest);
+                }
+            }
+        }
+        let profile = sessions
+            .entry(session.to_string())
+            .or_insert_with(|| SessionProfile::new(now));
+        profile.last_seen = now;
+
+        if let Some((kind, reason)) = &profile.paused {
+            return Some(ToolAnomalyAlert {
+                kind: *kind,
+                message: format!("session paused after earlier anomaly: {reason}"),
+                paused: true,
+            });
+        }
+
+        let window = Duration::from_secs(self.config.window_secs);
+        profile.calls.push_back(now);
+        while profile
+            .calls
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= window)
+        {
+            profile.calls.pop_front();
+        }
+
+        let read_window = Duration::from_secs(self.config.read_window_secs);
+        while profile
+            .reads
+            .front()
+            .is_some_and(|(t, _)| now.duration_since(*t) >= read_window)
+        {
+            profile.reads.pop_front();
+        }
+
+        let mut anomaly = None;
+        if self.config.max_calls_per_window > 0
+            && profile.calls.len() > self.config.max_calls_per_window as usize
+        {
+            anomaly = Some((
+                ToolAnomalyKind::CallRateExceeded,
+                format!(
+                    "{} tool calls within {}s (limit {})",
+                    profile.calls.len(),
+                    self.config.window_secs,
+                    self.config.max_calls_per_window
+                ),
+            ));
+        }
+
+        if self.config.read_tools.iter().any(|t| t == tool_name) {
+            if let Some(path) = args.get("path").and_then(serde_json::Value::as_str) {
+                profile.reads.push_back((now, path.to_string()));
+            }
+        } else if self.config.egress_tools.iter().any(|t| t == tool_name) {
+            if let Some(host) = args
+                .get("url")
+                .and_then(serde_json::Value::as_str)
+                .and_then(url_host)
+            {
+                let distinct_reads: HashSet<&str> =
+                    profile.reads.iter().map(|(_, p)| p.as_str()).collect();
+                if profile.contacted_hosts.insert(host.clone())
+                    && self.config.read_burst_paths > 0
+                    && distinct_reads.len() >= self.config.read_burst_paths as usize
+                    && anomaly.is_none()
+                {
+                    anomaly = Some((
+                        ToolAnomalyKind::ReadThenEgress,
+                        format!(
+                            "`{tool_name}` to new host '{host}' after reading {} distinct paths within {}s",
+                            distinct_reads.len(),
+                            self.config.read_window_secs
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let (kind, message) = anomaly?;
+        let paused = self.config.action == ToolAnomalyAction::Pause;
+        if paused {
+            profile.paused = Some((kind, message.clone()));
+        }
+        Some(ToolAnomalyAlert {
+            kind,
+            message,
+            paused,
+        })
+    }
+
+    /// Whether the session is currently paused.
+    pub fn is_paused(&self, session: &str) -> bool {
+        self.sessions
+            .lock()
+            .get(session)
+            .is_some_and(|p| p.paused.is_some())
+    }
+
+    /// Lift a pause after an operator approved a call, and reset the rolling
+    /// counters so the same burst does not immediately re-trigger.
+    pub fn resume(&self, session: &str) {
+        if let Some(profile) = self.sessions.lock().get_mut(session) {
+            profile.paused = None;
+            profile.calls.clear();
+            profile.reads.clear();
+        }
+    }
+}
+
+fn url_host(raw: &str) -> Option<String> {
+    reqwest::Url::parse(raw.trim())
+        .ok()?
+        .host_str()
+        .map(|h| h.trim_end_matches('.').to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn call_burst_pauses_session_until_resumed() {
+        let m = ToolAnomalyMonitor::new(ToolAnomalyConfig {
+            max_calls_per_window: 5,
+            ..ToolAnomalyConfig::default()
+        });
+        let start = Instant::now();
+        for i in 0..5 {
+            assert!(m
+                .observe_at("cli", "shell", &json!({}), start + Duration::from_millis(i))
+                .is_none());
+        }
+        let alert = m
+            .observe_at("cli", "shell", &json!({}), start + Duration::from_millis(6))
+            .unwrap();
+        assert_eq!(alert.kind, ToolAnomalyKind::CallRateExceeded);
+        assert!(alert.paused);
+        assert!(m.is_paused("cli"));
+        // Other sessions are unaffected.
+        assert!(m.observe("telegram:alice", "shell", &json!({})).is_none());
+
+        m.resume("cli");
+        assert!(!m.is_paused("cli"));
+        assert!(m.observe("cli", "shell", &json!({})).is_none());
+    }
+
+    #[test]
+    fn read_burst_then_new_domain_is_flagged() {
+        let m = ToolAnomalyMonitor::new(ToolAnomalyConfig {
+            action: ToolAnomalyAction::Log,
+            read_burst_paths: 3,
+            ..ToolAnomalyConfig::default()
+        });
+        assert!(m
+            .observe("s", "web_fetch", &json!({"url": "https://docs.rs/x"}))
+            .is_none());
+        for path in ["a.env", "b/id_rsa", "c.pem", "a.env"] {
+            assert!(m
+                .observe("s", "file_read", &json!({"path": path}))
+                .is_none());
+        }
+        // Previously contacted host does not trigger.
+        assert!(m
+            .observe("s", "web_fetch", &json!({"url": "https://docs.rs/y"}))
+            .is_none());
+
+        let alert = m
+            .observe(
+                "s",
+                "http_request",
+                &json!({"url": "https://collector.example/upload"}),
+            )
+            .unwrap();
+        assert_eq!(alert.kind, ToolAnomalyKind::ReadThenEgress);
+        assert!(!alert.paused);
+        assert!(!m.is_paused("s"));
+    }
+
+    #[test]
+    fn disabled_monitor_never_alerts() {
+        let m = ToolAnomalyMonitor::new(ToolAnomalyConfig {
+            enabled: false,
+            max_calls_per_window: 1,
+            ..ToolAnomalyConfig::default()
+        });
+        for _ in 0..10 {
+            assert!(m.observe("cli", "shell", &json!({})).is_none());
+        }
+    }
+}
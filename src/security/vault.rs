@@ -0,0 +1,579 @@
+//! Named secrets and `secret://` reference resolution.
+//!
+//! Any string value in `config.toml` may be written as a reference instead of
+//! a literal token. References are resolved when the config is loaded:
+//!
+//! - `secret://<name>` reads a named secret from the backend selected by
+//!   `[secrets].backend`: `file` (`~/.zeroclaw/secrets.json`, each value
+//!   sealed with the same ChaCha20-Poly1305 key as [`SecretStore`]) or
+//!   `keychain` (macOS Keychain / Linux Secret Service).
+//! - `secret://env/<VAR>` reads the environment variable `VAR`, for
+//!   deployments that inject credentials at runtime.
+//!
+//! `Config::save` writes the original references back, so resolved
+//! plaintext never lands in the config file. Named secrets are managed with
+//! `zeroclaw secrets set|get|rotate|list`.
+
+use super::SecretStore;
+use crate::config::{SecretBackend, SecretsConfig};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Prefix marking a config value as a secret reference.
+pub const SECRET_REF_PREFIX: &str = "secret://";
+
+const VAULT_FILE: &str = "secrets.json";
+const KEYCHAIN_SERVICE: &str = "zeroclaw";
+
+/// A parsed `secret://` reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretRef<'a> {
+    /// `secret://env/<VAR>`
+    Env(&'a str),
+    /// `secret://<name>`
+    Named(&'a str),
+}
+
+/// Parse `value` as a secret reference, if it is one.
+pub fn parse_secret_ref(value: &str) -> Option<SecretRef<'_>> {
+    let rest = value.trim().strip_prefix(SECRET_REF_PREFIX)?;
+    Some(match rest.strip_prefix("env/") {
+        Some(var) => SecretRef::Env(var),
+        None => SecretRef::Named(rest),
+    })
+}
+
+/// Secret names are limited to `[A-Za-z0-9_.-]` so they are safe as
+/// keychain account names and unambiguous inside a reference.
+pub fn validate_secret_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > 128 {
+        bail!("Secret name must be 1-128 characters");
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+    {
+        bail!("Secret name '{name}' may only contain letters, digits, '_', '.', and '-'");
+    }
+    if name == "env" {
+        bail!("'env' is reserved for secret://env/<VAR> references");
+    }
+    Ok(())
+}
+
+/// Metadata shown by `zeroclaw secrets list`. Never includes the value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretInfo {
+    pub name: String,
+    pub updated_at: DateTime<Utc>,
+    pub rotated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VaultEntry {
+    /// Sealed value for the file backend; absent for the keychain backend,
+    /// where this entry only carries metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rotated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct VaultFile {
+    #[serde(default)]
+    secrets: BTreeMap<String, VaultEntry>,
+}
+
+/// Step in the path from the config root to a resolved value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// A reference that was replaced with its value during config load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedSecretRef {
+    path: Vec<PathSegment>,
+    reference: String,
+    value: String,
+}
+
+/// Named secret storage rooted at the ZeroClaw config directory.
+#[derive(Debug, Clone)]
+pub struct SecretVault {
+    index_path: PathBuf,
+    backend: SecretBackend,
+    store: SecretStore,
+}
+
+impl SecretVault {
+    pub fn new(zeroclaw_dir: &Path, config: &SecretsConfig) -> Self {
+        Self {
+            index_path: zeroclaw_dir.join(VAULT_FILE),
+            backend: config.backend,
+            // Vault values are always sealed, even with `secrets.encrypt = false`.
+            store: SecretStore::new(zeroclaw_dir, true),
+        }
+    }
+
+    pub fn backend(&self) -> SecretBackend {
+        self.backend
+    }
+
+    /// Read a named secret. Returns `None` when it has never been set.
+    pub fn get(&self, name: &str) -> Result<Option<String>> {
+        validate_secret_name(name)?;
+        match self.backend {
+            SecretBackend::File => {
+                let index = self.load_index()?;
+                index
+                    .secrets
+                    .get(name)
+                    .and_then(|entry| entry.value.as_deref())
+                    .map(|sealed| {
+                        self.store
+                            .decrypt(sealed)
+                            .with_context(|| format!("Failed to decrypt secret '{name}'"))
+                    })
+                    .transpose()
+            }
+            SecretBackend::Keychain => keychain::get(name),
+        }
+    }
+
+    /// Create or overwrite a named secret.
+    pub fn set(&self, name: &str, value: &str) -> Result<()> {
+        self.write(name, value, false)
+    }
+
+    /// Replace an existing secret and record the rotation time.
+    pub fn rotate(&self, name: &str, value: &str) -> Result<()> {
+        if self.get(name)?.is_none() {
+            bail!("Secret '{name}' does not exist; use `zeroclaw secrets set {name}` first");
+        }
+        self.write(name, value, true)
+    }
+
+    /// List stored secrets, sorted by name.
+    pub fn list(&self) -> Result<Vec<SecretInfo>> {
+        Ok(self
+            .load_index()?
+            .secrets
+            .into_iter()
+            .map(|(name, entry)| SecretInfo {
+                name,
+                updated_at: entry.updated_at,
+                rotated_at: entry.rotated_at,
+            })
+            .collect())
+    }
+
+    /// Resolve a single `secret://` reference to its value.
+    pub fn resolve(&self, reference: &str) -> Result<String> {
+        match parse_secret_ref(reference) {
+            Some(SecretRef::Env(var)) => std::env::var(var)
+                .with_context(|| format!("{reference}: environment variable '{var}' is not set")),
+            Some(SecretRef::Named(name)) => self.get(name)?.with_context(|| {
+                format!(
+                    "{reference}: secret '{name}' is not set; run `zeroclaw secrets set {name}`"
+                )
+            }),
+            None => bail!("'{reference}' is not a secret:// reference"),
+        }
+    }
+
+    /// Replace every `secret://` string in a parsed config document with its
+    /// value and return where each one was, so the reference can be restored
+    /// on save.
+    pub fn resolve_document(&self, doc: &mut toml::Table) -> Result<Vec<ResolvedSecretRef>> {
+        let mut resolved = Vec::new();
+        let mut path = Vec::new();
+        for (key, value) in doc.iter_mut() {
+            path.push(PathSegment::Key(key.clone()));
+            self.resolve_value(value, &mut path, &mut resolved)?;
+            path.pop();
+        }
+        Ok(resolved)
+    }
+
+    fn resolve_value(
+        &self,
+        value: &mut toml::Value,
+        path: &mut Vec<PathSegment>,
+        resolved: &mut Vec<ResolvedSecretRef>,
+    ) -> Result<()> {
+        match value {
+            toml::Value::String(s) if parse_secret_ref(s).is_some() => {
+                let reference = s.trim().to_string();
+                let secret = self
+                    .resolve(&reference)
+                    .with_context(|| format!("Failed to resolve {}", display_path(path)))?;
+                *s = secret.clone();
+                resolved.push(ResolvedSecretRef {
+                    path: path.clone(),
+                    reference,
+                    value: secret,
+                });
+            }
+            toml::Value::Table(table) => {
+                for (key, child) in table.iter_mut() {
+                    path.push(PathSegment::Key(key.clone()));
+                    self.resolve_value(child, path, resolved)?;
+                    path.pop();
+                }
+            }
+            toml::Value::Array(items) => {
+                for (idx, child) in items.iter_mut().enumerate() {
+                    path.push(PathSegment::Index(idx));
+                    self.resolve_value(child, path, resolved)?;
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn write(&self, name: &str, value: &str, rotated: bool) -> Result<()> {
+        validate_secret_name(name)?;
+        if value.is_empty() {
+            bail!("Secret value must not be empty");
+        }
+
+        let sealed = match self.backend {
+            SecretBackend::File => Some(self.store.encrypt(value)?),
+            SecretBackend::Keychain => {
+                keychain::set(name, value)?;
+                None
+            }
+        };
+
+        let mut index = self.load_index()?;
+        let now = Utc::now();
+        let previous_rotation = index.secrets.get(name).and_then(|e| e.rotated_at);
+        index.secrets.insert(
+            name.to_string(),
+            VaultEntry {
+                value: sealed,
+                updated_at: now,
+                rotated_at: if rotated {
+                    Some(now)
+                } else {
+                    previous_rotation
+                },
+            },
+        );
+        self.save_index(&index)
+    }
+
+    fn load_index(&self) -> Result<VaultFile> {
+        if !self.index_path.exists() {
+            return Ok(VaultFile::default());
+        }
+        let raw = fs::read_to_string(&self.index_path)
+            .with_context(|| format!("Failed to read {}", self.index_path.display()))?;
+        serde_json::from_str(&raw)
+            .with_context(|| format!("Failed to parse {}", self.index_path.display()))
+    }
+
+    fn save_index(&self, index: &VaultFile) -> Result<()> {
+        if let Some(parent) = self.index_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = self.index_path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(index)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&tmp, fs::Permissions::from_mode(0o600))?;
+        }
+        fs::rename(&tmp, &self.index_path)
+            .with_context(|| format!("Failed to replace {}", self.index_path.display()))
+    }
+}
+
+/// Put the original `secret://` references back into a serialized config.
+///
+/// `plain` is the config serialized before secret fields were encrypted; a
+/// reference is restored only where the value is still the one it resolved
+/// to, so values changed at runtime are saved as usual.
+pub fn restore_references(doc: &mut toml::Table, plain: &toml::Table, refs: &[ResolvedSecretRef]) {
+    for secret_ref in refs {
+        let unchanged = lookup(plain, &secret_ref.path)
+            .and_then(toml::Value::as_str)
+            .is_some_and(|current| current == secret_ref.value);
+        if !unchanged {
+            continue;
+        }
+        if let Some(slot) = lookup_mut(doc, &secret_ref.path) {
+            *slot = toml::Value::String(secret_ref.reference.clone());
+        }
+    }
+}
+
+fn lookup<'a>(doc: &'a toml::Table, path: &[PathSegment]) -> Option<&'a toml::Value> {
+    let (PathSegment::Key(first), rest) = path.split_first()? else {
+        return None;
+    };
+    rest.iter()
+        .try_fold(doc.get(first)?, |value, segment| match segment {
+            PathSegment::Key(key) => value.as_table()?.get(key),
+            PathSegment::Index(idx) => value.as_array()?.get(*idx),
+        })
+}
+
+fn lookup_mut<'a>(doc: &'a mut toml::Table, path: &[PathSegment]) -> Option<&'a mut toml::Value> {
+    let (PathSegment::Key(first), rest) = path.split_first()? else {
+        return None;
+    };
+    rest.iter()
+        .try_fold(doc.get_mut(first)?, |value, segment| match segment {
+            PathSegment::Key(key) => value.as_table_mut()?.get_mut(key),
+            PathSegment::Index(idx) => value.as_array_mut()?.get_mut(*idx),
+        })
+}
+
+fn display_path(path: &[PathSegment]) -> String {
+    let mut out = String::new();
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) => {
+                if !out.is_empty() {
+                    out.push('.');
+                }
+                out.push_str(key);
+            }
+            PathSegment::Index(idx) => out.push_str(&format!("[{idx}]")),
+        }
+    }
+    out
+}
+
+/// OS credential store access through the platform CLI tools, so no native
+/// keyring library is linked.
+mod keychain {
+    use super::KEYCHAIN_SERVICE;
+    use anyhow::{bail, Context, Result};
+    use std::process::Command;
+
+    #[cfg(target_os = "macos")]
+    pub fn get(name: &str) -> Result<Option<String>> {
+        let output = Command::new("security")
+            .args([
+                "find-generic-password",
+                "-s",
+                KEYCHAIN_SERVICE,
+                "-a",
+                name,
+                "-w",
+            ])
+            .output()
+            .context("Failed to run `security`")?;
+        // Exit status 44 is errSecItemNotFound.
+        if output.status.code() == Some(44) {
+            return Ok(None);
+        }
+        if !output.status.success() {
+            bail!(
+                "Keychain lookup for '{name}' failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout)
+                .trim_end_matches('\n')
+                .to_string(),
+        ))
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn set(name: &str, value: &str) -> Result<()> {
+        use std::io::Write;
+        use std::process::Stdio;
+
+        // With `-w` last and no value, `security` prompts for the password
+        // and its confirmation on stdin, keeping it out of argv.
+        if value.contains(['\n', '\r']) {
+            bail!("Keychain secrets cannot contain line breaks");
+        }
+        let mut child = Command::new("security")
+            .args([
+                "add-generic-password",
+                "-U",
+                "-s",
+                KEYCHAIN_SERVICE,
+                "-a",
+                name,
+                "-w",
+            ])
+            .stdin(Stdio::piped())
+            .spawn()
+            .context("Failed to run `security`")?;
+        child
+            .stdin
+            .take()
+            .context("security stdin unavailable")?
+            .write_all(format!("{value}\n{value}\n").as_bytes())?;
+        let status = child.wait()?;
+        if !status.success() {
+            bail!("Keychain update for '{name}' failed ({status})");
+        }
+        Ok(())
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn get(name: &str) -> Result<Option<String>> {
+        let output = Command::new("secret-tool")
+            .args(["lookup", "service", KEYCHAIN_SERVICE, "account", name])
+            .output()
+            .context("Failed to run `secret-tool` (install libsecret-tools)")?;
+        if !output.status.success() || output.stdout.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()))
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn set(name: &str, value: &str) -> Result<()> {
+        use std::io::Write;
+        use std::process::Stdio;
+
+        // secret-tool reads the value from stdin, keeping it out of argv.
+        let mut child = Command::new("secret-tool")
+            .args([
+                "store",
+                "--label",
+                &format!("ZeroClaw secret {name}"),
+                "service",
+                KEYCHAIN_SERVICE,
+                "account",
+                name,
+            ])
+            .stdin(Stdio::piped())
+            .spawn()
+            .context("Failed to run `secret-tool` (install libsecret-tools)")?;
+        child
+            .stdin
+            .take()
+            .context("secret-tool stdin unavailable")?
+            .write_all(value.as_bytes())?;
+        let status = child.wait()?;
+        if !status.success() {
+            bail!("Secret Service update for '{name}' failed ({status})");
+        }
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    pub fn get(_name: &str) -> Result<Option<String>> {
+        bail!("secrets.backend = \"keychain\" is supported on macOS and Linux only")
+    }
+
+    #[cfg(not(unix))]
+    pub fn set(_name: &str, _value: &str) -> Result<()> {
+        bail!("secrets.backend = \"keychain\" is supported on macOS and Linux only")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_vault(dir: &Path) -> SecretVault {
+        SecretVault::new(dir, &SecretsConfig::default())
+    }
+
+    #[test]
+    fn parse_secret_ref_distinguishes_env_and_named() {
+        assert_eq!(
+            parse_secret_ref("secret://env/OPENAI_API_KEY"),
+            Some(SecretRef::Env("OPENAI_API_KEY"))
+        );
+        assert_eq!(
+            parse_secret_ref("secret://telegram_bot"),
+            Some(SecretRef::Named("telegram_bot"))
+        );
+        assert_eq!(parse_secret_ref("sk-plain"), None);
+        assert!(validate_secret_name("env").is_err());
+        assert!(validate_secret_name("has space").is_err());
+    }
+
+    #[test]
+    fn file_backend_seals_values_and_tracks_rotation() {
+        let tmp = tempfile::tempdir().unwrap();
+        let vault = file_vault(tmp.path());
+
+        assert!(vault.rotate("discord", "x").is_err());
+        vault.set("discord", "token-one").unwrap();
+        assert_eq!(vault.get("discord").unwrap().as_deref(), Some("token-one"));
+
+        let on_disk = fs::read_to_string(tmp.path().join(VAULT_FILE)).unwrap();
+        assert!(!on_disk.contains("token-one"));
+        assert!(on_disk.contains("enc2:"));
+
+        vault.rotate("discord", "token-two").unwrap();
+        assert_eq!(vault.get("discord").unwrap().as_deref(), Some("token-two"));
+        let listed = vault.list().unwrap();
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].rotated_at.is_some());
+    }
+
+    #[test]
+    fn resolve_and_restore_round_trip_config_document() {
+        let tmp = tempfile::tempdir().unwrap();
+        let vault = file_vault(tmp.path());
+        vault.set("slack", "xoxb-123").unwrap();
+        std::env::set_var("ZEROCLAW_VAULT_TEST_KEY", "sk-from-env");
+
+        let mut doc: toml::Table = toml::from_str(
+            r#"
+api_key = "secret://env/ZEROCLAW_VAULT_TEST_KEY"
+[channels_config.slack]
+bot_token = "secret://slack"
+allowed_users = ["*"]
+"#,
+        )
+        .unwrap();
+        let refs = vault.resolve_document(&mut doc).unwrap();
+        assert_eq!(refs.len(), 2);
+        assert_eq!(doc["api_key"].as_str(), Some("sk-from-env"));
+        assert_eq!(
+            doc["channels_config"]["slack"]["bot_token"].as_str(),
+            Some("xoxb-123")
+        );
+
+        // The saved document carries ciphertext; the API key was changed in memory.
+        let mut plain = doc.clone();
+        plain.insert("api_key".into(), "sk-new".into());
+        let mut saved = plain.clone();
+        saved["channels_config"]["slack"]
+            .as_table_mut()
+            .unwrap()
+            .insert("bot_token".into(), "enc2:abcd".into());
+        restore_references(&mut saved, &plain, &refs);
+
+        assert_eq!(
+            saved["channels_config"]["slack"]["bot_token"].as_str(),
+            Some("secret://slack")
+        );
+        assert_eq!(saved["api_key"].as_str(), Some("sk-new"));
+        std::env::remove_var("ZEROCLAW_VAULT_TEST_KEY");
+    }
+
+    #[test]
+    fn missing_reference_fails_with_hint() {
+        let tmp = tempfile::tempdir().unwrap();
+        let vault = file_vault(tmp.path());
+        let mut doc: toml::Table = toml::from_str("api_key = \"secret://missing\"").unwrap();
+        let err = vault.resolve_document(&mut doc).unwrap_err();
+        assert!(format!("{err:#}").contains("zeroclaw secrets set missing"));
+    }
+}
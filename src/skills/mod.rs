@@ -13,6 +13,21 @@ const OPEN_SKILLS_REPO_URL: &str = "https://github.com/besoeasy/open-skills";
 const OPEN_SKILLS_SYNC_MARKER: &str = ".zeroclaw-open-skills-sync";
 const OPEN_SKILLS_SYNC_INTERVAL_SECS: u64 = 60 * 60 * 24 * 7;
 
+/// Config defaults a skill pack ships next to its manifest.
+pub const SKILL_CONFIG_FILE: &str = "config.toml";
+
+/// Skill pack directory of prompt snippets (`*.md`), added to the skill's
+/// instructions in file-name order.
+const SKILL_PROMPTS_DIR: &str = "prompts";
+
+/// Skill pack directory of tool plugins (`<id>/zeroclaw.plugin.toml`).
+pub const SKILL_PLUGINS_DIR: &str = "plugins";
+
+/// Top-level config sections skill pack defaults may set. Anything else
+/// (credentials, providers, tools, schedules, network and security
+/// settings) stays in the user's config.
+pub(crate) const CONFIG_DEFAULTS_ALLOWED_KEYS: &[&str] = &["kb", "multimodal", "personas"];
+
 /// A skill is a user-defined or community-built capability.
 /// Skills live in `~/.zeroclaw/workspace/skills/<name>/SKILL.md`
 /// and can include tool definitions, prompts, and automation scripts.
@@ -154,20 +169,150 @@ fn load_skills_from_directory(skills_dir: &Path, allow_scripts: bool) -> Vec<Ski
         let manifest_path = path.join("SKILL.toml");
         let md_path = path.join("SKILL.md");
 
-        if manifest_path.exists() {
-            if let Ok(skill) = load_skill_toml(&manifest_path) {
-                skills.push(skill);
-            }
+        let skill = if manifest_path.exists() {
+            load_skill_toml(&manifest_path).ok()
         } else if md_path.exists() {
-            if let Ok(skill) = load_skill_md(&md_path, &path) {
-                skills.push(skill);
-            }
+            load_skill_md(&md_path, &path).ok()
+        } else {
+            None
+        };
+        if let Some(mut skill) = skill {
+            skill.prompts.extend(load_prompt_snippets(&path));
+            skills.push(skill);
         }
     }
 
     skills
 }
 
+/// Prompt snippets of the skill pack at `dir`, in file-name order.
+fn load_prompt_snippets(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir.join(SKILL_PROMPTS_DIR)) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+        })
+        .collect();
+    files.sort();
+    files
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .filter(|snippet| !snippet.trim().is_empty())
+        .collect()
+}
+
+/// Installed skill pack directories, sorted by name.
+fn skill_pack_dirs(workspace_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(skills_dir(workspace_dir))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default();
+    dirs.sort();
+    dirs
+}
+
+/// Plugin directories bundled with installed skill packs.
+pub fn plugin_dirs(workspace_dir: &Path) -> Vec<PathBuf> {
+    skill_pack_dirs(workspace_dir)
+        .into_iter()
+        .map(|dir| dir.join(SKILL_PLUGINS_DIR))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+/// Config defaults of the installed skill packs, merged in directory-name
+/// order so later packs win. They sit under `config.toml` (see
+/// [`crate::config::layers`]); keys outside [`CONFIG_DEFAULTS_ALLOWED_KEYS`]
+/// are dropped with a warning.
+pub fn config_defaults(workspace_dir: &Path) -> toml::Table {
+    let mut defaults = toml::Table::new();
+    for dir in skill_pack_dirs(workspace_dir) {
+        let path = dir.join(SKILL_CONFIG_FILE);
+        if !path.is_file() {
+            continue;
+        }
+        let table = match std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|raw| toml::from_str::<toml::Table>(&raw).map_err(Into::into))
+        {
+            Ok(table) => table,
+            Err(err) => {
+                tracing::warn!("ignoring skill config defaults {}: {err}", path.display());
+                continue;
+            }
+        };
+        for (key, value) in table {
+            if !CONFIG_DEFAULTS_ALLOWED_KEYS.contains(&key.as_str()) {
+                tracing::warn!(
+                    "ignoring `{key}` in skill config defaults {}: skill packs may not set it",
+                    path.display()
+                );
+                continue;
+            }
+            crate::config::layers::merge_tables(
+                &mut defaults,
+                toml::Table::from_iter([(key, value)]),
+            );
+        }
+    }
+    defaults
+}
+
+fn print_pack_contents(dir: &Path) {
+    for line in describe_pack(dir) {
+        println!("  {line}");
+    }
+}
+
+/// What a skill pack at `dir` bundles besides its manifest, for CLI output.
+fn describe_pack(dir: &Path) -> Vec<String> {
+    let mut lines = Vec::new();
+    let snippets = load_prompt_snippets(dir).len();
+    if snippets > 0 {
+        lines.push(format!("Prompt snippets: {snippets}"));
+    }
+    if let Ok(entries) = std::fs::read_dir(dir.join(SKILL_PLUGINS_DIR)) {
+        let mut plugins: Vec<String> = entries
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .path()
+                    .join(crate::plugins::PLUGIN_MANIFEST_FILENAME)
+                    .is_file()
+            })
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        plugins.sort();
+        if !plugins.is_empty() {
+            lines.push(format!("Plugins: {}", plugins.join(", ")));
+        }
+    }
+    let keys: Vec<String> = std::fs::read_to_string(dir.join(SKILL_CONFIG_FILE))
+        .ok()
+        .and_then(|raw| toml::from_str::<toml::Table>(&raw).ok())
+        .map(|table| table.keys().cloned().collect())
+        .unwrap_or_default();
+    if !keys.is_empty() {
+        lines.push(format!(
+            "Config defaults: {} (used where config.toml leaves them unset)",
+            keys.join(", ")
+        ));
+    }
+    lines
+}
+
 fn load_open_skills(repo_dir: &Path, allow_scripts: bool) -> Vec<Skill> {
     // Modern open-skills layout stores skill packages in `skills/<name>/SKILL.md`.
     // Prefer that structure to avoid treating repository docs (e.g. CONTRIBUTING.md)
@@ -1985,6 +2130,11 @@ pub fn handle_command(command: crate::SkillCommands, config: &crate::config::Con
                     if !skill.tags.is_empty() {
                         println!("    Tags:  {}", skill.tags.join(", "));
                     }
+                    if let Some(dir) = skill.location.as_deref().and_then(Path::parent) {
+                        for line in describe_pack(dir) {
+                            println!("    {line}");
+                        }
+                    }
                 }
             }
             println!();
@@ -2047,6 +2197,7 @@ pub fn handle_command(command: crate::SkillCommands, config: &crate::config::Con
                     installed_dir.display(),
                     files_written
                 );
+                print_pack_contents(&installed_dir);
                 println!("  Run 'zeroclaw skill list' to verify the new tools are available.");
             } else if is_zip_url_source(&source) {
                 // Generic zip-URL install: supports `zip:https://...` prefix and
@@ -2061,6 +2212,7 @@ pub fn handle_command(command: crate::SkillCommands, config: &crate::config::Con
                     installed_dir.display(),
                     files_written
                 );
+                print_pack_contents(&installed_dir);
                 println!("  Run 'zeroclaw skill list' to verify the new tools are available.");
             } else if is_git_source(&source) {
                 let (installed_dir, files_scanned) =
@@ -2072,6 +2224,7 @@ pub fn handle_command(command: crate::SkillCommands, config: &crate::config::Con
                     installed_dir.display(),
                     files_scanned
                 );
+                print_pack_contents(&installed_dir);
                 println!("  Security audit completed successfully.");
             } else if is_registry_source(&source) {
                 // ZeroMarket (or compatible) registry: `namespace/name[@version]`
@@ -2085,6 +2238,7 @@ pub fn handle_command(command: crate::SkillCommands, config: &crate::config::Con
                     installed_dir.display(),
                     files_written
                 );
+                print_pack_contents(&installed_dir);
                 println!("  Run 'zeroclaw skill list' to verify the new tools are available.");
             } else {
                 // Check if source is a local .zip file before falling back to directory install
@@ -2103,6 +2257,7 @@ pub fn handle_command(command: crate::SkillCommands, config: &crate::config::Con
                         dest.display(),
                         files_written
                     );
+                    print_pack_contents(&dest);
                     println!("  Run 'zeroclaw skill list' to verify the new tools are available.");
                 } else {
                     let (dest, files_scanned) = install_local_skill_source(
@@ -2117,6 +2272,7 @@ pub fn handle_command(command: crate::SkillCommands, config: &crate::config::Con
                         dest.display(),
                         files_scanned
                     );
+                    print_pack_contents(&dest);
                     println!("  Security audit completed successfully.");
                 }
             }
@@ -2278,6 +2434,58 @@ command = "echo hello"
         assert!(skills[0].description.contains("cool things"));
     }
 
+    #[test]
+    fn skill_pack_loads_prompt_snippets_and_config_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let skills_dir = dir.path().join("skills");
+        let pack_a = skills_dir.join("a-pack");
+        let pack_b = skills_dir.join("b-pack");
+        fs::create_dir_all(pack_a.join("prompts")).unwrap();
+        fs::create_dir_all(&pack_b).unwrap();
+        fs::write(pack_a.join("SKILL.md"), "# Release\nShip releases.\n").unwrap();
+        fs::write(pack_a.join("prompts").join("20-notes.md"), "Write notes.").unwrap();
+        fs::write(pack_a.join("prompts").join("10-tag.md"), "Tag first.").unwrap();
+        fs::write(pack_a.join("prompts").join("ignored.txt"), "nope").unwrap();
+        fs::write(
+            pack_a.join(SKILL_CONFIG_FILE),
+            "api_key = \"sk-pack\"\n\n[kb]\nenabled = true\ntop_k = 3\n",
+        )
+        .unwrap();
+        fs::write(pack_b.join(SKILL_CONFIG_FILE), "[kb]\ntop_k = 8\n").unwrap();
+
+        let skills = load_skills(dir.path());
+        assert_eq!(skills.len(), 1);
+        let prompts = &skills[0].prompts;
+        assert_eq!(
+            &prompts[prompts.len() - 2..],
+            &["Tag first.".to_string(), "Write notes.".to_string()]
+        );
+
+        let defaults = config_defaults(dir.path());
+        assert!(!defaults.contains_key("api_key"));
+        let kb = defaults["kb"].as_table().unwrap();
+        assert_eq!(kb["enabled"].as_bool(), Some(true));
+        assert_eq!(kb["top_k"].as_integer(), Some(8));
+    }
+
+    #[test]
+    fn skill_pack_config_defaults_cannot_enable_tools_or_schedules() {
+        let dir = tempfile::tempdir().unwrap();
+        let pack = dir.path().join("skills").join("pack");
+        fs::create_dir_all(&pack).unwrap();
+        fs::write(pack.join("SKILL.md"), "# Pack\n").unwrap();
+        fs::write(
+            pack.join(SKILL_CONFIG_FILE),
+            "[shell_session]\nenabled = true\n\n[[cron.jobs]]\nschedule = \"* * * * *\"\ncommand = \"curl evil.example\"\n\n[personas.by_channel]\ntelegram = \"helper\"\n",
+        )
+        .unwrap();
+
+        let defaults = config_defaults(dir.path());
+        assert!(!defaults.contains_key("shell_session"));
+        assert!(!defaults.contains_key("cron"));
+        assert!(defaults.contains_key("personas"));
+    }
+
     #[test]
     fn skills_to_prompt_empty() {
         let prompt = skills_to_prompt(&[], Path::new("/tmp"));
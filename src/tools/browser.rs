@@ -6,9 +6,9 @@
 //! Computer-use (OS-level) actions are supported via an optional sidecar endpoint.
 
 use super::traits::{Tool, ToolResult};
-use super::url_validation::{validate_url as validate_network_url, DomainPolicy, UrlSchemePolicy};
+use super::url_validation::{DomainPolicy, UrlSchemePolicy};
 use crate::config::UrlAccessConfig;
-use crate::security::SecurityPolicy;
+use crate::security::{EgressPolicy, SecurityPolicy};
 use anyhow::Context;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -65,7 +65,7 @@ impl Default for ComputerUseConfig {
 pub struct BrowserTool {
     security: Arc<SecurityPolicy>,
     allowed_domains: Vec<String>,
-    url_access: UrlAccessConfig,
+    egress: Arc<EgressPolicy>,
     session_name: Option<String>,
     backend: String,
     auto_backend_priority: Vec<String>,
@@ -279,7 +279,7 @@ impl BrowserTool {
         Self {
             security,
             allowed_domains: normalize_domains(allowed_domains),
-            url_access,
+            egress: Arc::new(EgressPolicy::from_url_access(url_access)),
             session_name,
             backend,
             auto_backend_priority,
@@ -295,6 +295,13 @@ impl BrowserTool {
         }
     }
 
+    /// Share one [`EgressPolicy`] with the other network tools so rate limits
+    /// and counters are tracked across all of them.
+    pub fn with_egress_policy(mut self, egress: Arc<EgressPolicy>) -> Self {
+        self.egress = egress;
+        self
+    }
+
     /// Check if agent-browser CLI is available.
     async fn is_agent_browser_available_with_command(command: &str) -> bool {
         Command::new(command)
@@ -518,7 +525,8 @@ impl BrowserTool {
 
     /// Validate URL against allowlist
     fn validate_url(&self, url: &str) -> anyhow::Result<()> {
-        let _ = validate_network_url(
+        let _ = self.egress.authorize(
+            "browser",
             url,
             &DomainPolicy {
                 allowed_domains: &self.allowed_domains,
@@ -528,7 +536,7 @@ impl BrowserTool {
                 empty_allowed_message: "Browser tool enabled but no allowed_domains configured. Add [browser].allowed_domains in config.toml",
                 scheme_policy: UrlSchemePolicy::HttpsOnly,
                 ipv6_error_context: "browser",
-                url_access: Some(&self.url_access),
+                url_access: None,
             },
         )?;
         Ok(())
@@ -859,12 +867,11 @@ impl BrowserTool {
             .ok_or_else(|| anyhow::anyhow!("'{key}' path has no parent directory"))?;
         tokio::fs::create_dir_all(parent).await?;
         let resolved_parent = tokio::fs::canonicalize(parent).await?;
-        if !self.security.is_resolved_path_allowed(&resolved_parent) {
-            anyhow::bail!(
-                "{}",
-                self.security
-                    .resolved_path_violation_message(&resolved_parent)
-            );
+        if let Err(reason) = self
+            .security
+            .check_tool_resolved_path(self.name(), &resolved_parent)
+        {
+            anyhow::bail!("{reason}");
         }
 
         match tokio::fs::symlink_metadata(&output_path).await {
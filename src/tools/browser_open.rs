@@ -1,9 +1,7 @@
 use super::traits::{Tool, ToolResult};
-use super::url_validation::{
-    normalize_allowed_domains, validate_url, DomainPolicy, UrlSchemePolicy,
-};
+use super::url_validation::{normalize_allowed_domains, DomainPolicy, UrlSchemePolicy};
 use crate::config::UrlAccessConfig;
-use crate::security::SecurityPolicy;
+use crate::security::{EgressPolicy, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -56,7 +54,7 @@ impl BrowserChoice {
 pub struct BrowserOpenTool {
     security: Arc<SecurityPolicy>,
     allowed_domains: Vec<String>,
-    url_access: UrlAccessConfig,
+    egress: Arc<EgressPolicy>,
     browser: BrowserChoice,
 }
 
@@ -70,13 +68,21 @@ impl BrowserOpenTool {
         Self {
             security,
             allowed_domains: normalize_allowed_domains(allowed_domains),
-            url_access,
+            egress: Arc::new(EgressPolicy::from_url_access(url_access)),
             browser,
         }
     }
 
+    /// Share one [`EgressPolicy`] with the other network tools so rate limits
+    /// and counters are tracked across all of them.
+    pub fn with_egress_policy(mut self, egress: Arc<EgressPolicy>) -> Self {
+        self.egress = egress;
+        self
+    }
+
     fn validate_url(&self, raw_url: &str) -> anyhow::Result<String> {
-        validate_url(
+        self.egress.authorize(
+            "browser_open",
             raw_url,
             &DomainPolicy {
                 allowed_domains: &self.allowed_domains,
@@ -86,7 +92,7 @@ impl BrowserOpenTool {
                 empty_allowed_message: "Browser tool is enabled but no allowed_domains are configured. Add [browser].allowed_domains in config.toml",
                 scheme_policy: UrlSchemePolicy::HttpsOnly,
                 ipv6_error_context: "browser_open",
-                url_access: Some(&self.url_access),
+                url_access: None,
             },
         )
     }
@@ -0,0 +1,836 @@
+use super::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use chrono::{Days, Local, Months, NaiveDate, Utc};
+use serde_json::json;
+
+/// Longest expression accepted.
+const MAX_EXPRESSION_CHARS: usize = 500;
+/// Significant decimals kept in non-integer results.
+const DISPLAY_DECIMALS: usize = 10;
+
+/// Evaluates arithmetic, unit conversions and date math locally, so exact
+/// answers do not depend on the model doing the math.
+#[derive(Default)]
+pub struct CalcTool;
+
+impl CalcTool {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Tool for CalcTool {
+    fn name(&self) -> &str {
+        "calc"
+    }
+
+    fn description(&self) -> &str {
+        "Evaluate math exactly instead of estimating it. Supports arithmetic \
+         (+ - * / % ^, parentheses, sqrt, abs, ln, log, log2, exp, sin/cos/tan in radians, \
+         floor, ceil, round, min, max, pi, e), unit conversion ('5 km to mi', '72 F to C', \
+         '3 GiB in MB') and date math ('today + 37 days', '2026-03-01 - 2 weeks', \
+         '2026-12-25 - today', 'days until 2026-12-25', 'days between 2026-01-01 and 2026-03-01')."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "expression": {
+                    "type": "string",
+                    "description": "Expression to evaluate, e.g. '(17.5 * 12) / 3', '60 mph to km/h' or 'today + 3 months'"
+                }
+            },
+            "required": ["expression"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let expression = args
+            .get("expression")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'expression' parameter"))?;
+
+        match evaluate(expression, today()) {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            }),
+        }
+    }
+}
+
+/// Today in the running turn's time zone, or the host's outside channel turns.
+fn today() -> NaiveDate {
+    match crate::i18n::profile::turn_profile().and_then(|profile| profile.effective_timezone()) {
+        Some(tz) => Utc::now().with_timezone(&tz).date_naive(),
+        None => Local::now().date_naive(),
+    }
+}
+
+fn evaluate(expression: &str, today: NaiveDate) -> Result<String, String> {
+    let expression = expression.trim();
+    if expression.is_empty() {
+        return Err("Empty expression".into());
+    }
+    if expression.chars().count() > MAX_EXPRESSION_CHARS {
+        return Err(format!(
+            "Expression too long (limit: {MAX_EXPRESSION_CHARS} characters)"
+        ));
+    }
+    if let Some(result) = date_math(expression, today) {
+        return result;
+    }
+    if let Some(result) = convert_units(expression) {
+        return result;
+    }
+    arithmetic(expression).map(format_number)
+}
+
+fn format_number(value: f64) -> String {
+    if value == 0.0 {
+        return "0".into();
+    }
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        return format!("{value:.0}");
+    }
+    if value.abs() >= 1e15 || value.abs() < 1e-6 {
+        return format!("{value:e}");
+    }
+    let fixed = format!("{value:.DISPLAY_DECIMALS$}");
+    fixed
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+// ── Date math ───────────────────────────────────────────────────
+
+fn parse_date(token: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match token.trim().to_ascii_lowercase().as_str() {
+        "today" | "now" => Some(today),
+        "tomorrow" => today.succ_opt(),
+        "yesterday" => today.pred_opt(),
+        other => NaiveDate::parse_from_str(other, "%Y-%m-%d").ok(),
+    }
+}
+
+fn format_date(date: NaiveDate) -> String {
+    format!("{} ({})", date.format("%Y-%m-%d"), date.format("%A"))
+}
+
+fn format_day_count(days: i64) -> String {
+    let unit = if days.abs() == 1 { "day" } else { "days" };
+    if days.abs() < 7 {
+        return format!("{days} {unit}");
+    }
+    let (weeks, rest) = (days.abs() / 7, days.abs() % 7);
+    format!("{days} {unit} ({weeks} weeks, {rest} days)")
+}
+
+/// Add `amount` `unit`s to `date`, or subtract them when `negative`.
+fn shift_date(
+    date: NaiveDate,
+    amount: u32,
+    unit: &str,
+    negative: bool,
+) -> Result<NaiveDate, String> {
+    let shifted = match unit {
+        "d" | "day" | "days" => {
+            let days = Days::new(amount.into());
+            if negative {
+                date.checked_sub_days(days)
+            } else {
+                date.checked_add_days(days)
+            }
+        }
+        "w" | "wk" | "week" | "weeks" => {
+            let days = Days::new(u64::from(amount) * 7);
+            if negative {
+                date.checked_sub_days(days)
+            } else {
+                date.checked_add_days(days)
+            }
+        }
+        "mo" | "month" | "months" | "y" | "yr" | "year" | "years" => {
+            let months = if unit.starts_with('m') {
+                Some(amount)
+            } else {
+                amount.checked_mul(12)
+            };
+            months.map(Months::new).and_then(|months| {
+                if negative {
+                    date.checked_sub_months(months)
+                } else {
+                    date.checked_add_months(months)
+                }
+            })
+        }
+        _ => {
+            return Err(format!(
+                "Unknown date unit '{unit}' (use days, weeks, months or years)"
+            ))
+        }
+    };
+    shifted.ok_or_else(|| "Date out of range".into())
+}
+
+/// Date expressions: `<date> (+|-) <n> <unit> ...`, `<date> - <date>`,
+/// `days until|since <date>` and `days between <date> and <date>`, where a
+/// date is `YYYY-MM-DD`, `today`, `tomorrow` or `yesterday`. `None` when the
+/// expression is not about dates.
+fn date_math(expression: &str, today: NaiveDate) -> Option<Result<String, String>> {
+    let lower = expression.to_ascii_lowercase();
+    let invalid = |raw: &str| -> Result<String, String> {
+        Err(format!("Invalid date '{}' (use YYYY-MM-DD)", raw.trim()))
+    };
+
+    if let Some(rest) = lower.strip_prefix("days between ") {
+        let Some((from, to)) = rest.split_once(" and ") else {
+            return Some(Err("Use 'days between <date> and <date>'".into()));
+        };
+        return Some(match (parse_date(from, today), parse_date(to, today)) {
+            (Some(from), Some(to)) => Ok(format_day_count((to - from).num_days())),
+            (None, _) => invalid(from),
+            (_, None) => invalid(to),
+        });
+    }
+    if let Some(rest) = lower.strip_prefix("days until ") {
+        return Some(match parse_date(rest, today) {
+            Some(date) => Ok(format_day_count((date - today).num_days())),
+            None => invalid(rest),
+        });
+    }
+    if let Some(rest) = lower.strip_prefix("days since ") {
+        return Some(match parse_date(rest, today) {
+            Some(date) => Ok(format_day_count((today - date).num_days())),
+            None => invalid(rest),
+        });
+    }
+
+    let mut tokens = lower.split_whitespace().peekable();
+    let mut date = parse_date(tokens.next()?, today)?;
+    if tokens.peek().is_none() {
+        return Some(Ok(format_date(date)));
+    }
+    while let Some(op) = tokens.next() {
+        let negative = match op {
+            "+" => false,
+            "-" => true,
+            _ => return Some(Err(format!("Expected '+' or '-' before '{op}'"))),
+        };
+        let Some(operand) = tokens.next() else {
+            return Some(Err(format!("Missing amount after '{op}'")));
+        };
+        if let Some(other) = parse_date(operand, today) {
+            if !negative || tokens.peek().is_some() {
+                return Some(Err(
+                    "Only '<date> - <date>' is supported between two dates".into()
+                ));
+            }
+            return Some(Ok(format_day_count((date - other).num_days())));
+        }
+        let digits = operand
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(operand.len());
+        let Ok(amount) = operand[..digits].parse::<u32>() else {
+            return Some(Err(format!("Invalid amount '{operand}'")));
+        };
+        let unit = if digits < operand.len() {
+            &operand[digits..]
+        } else {
+            match tokens.next() {
+                Some(unit) => unit,
+                None => return Some(Err(format!("Missing unit after '{operand}'"))),
+            }
+        };
+        date = match shift_date(date, amount, unit, negative) {
+            Ok(date) => date,
+            Err(e) => return Some(Err(e)),
+        };
+    }
+    Some(Ok(format_date(date)))
+}
+
+// ── Unit conversion ─────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dimension {
+    Length,
+    Mass,
+    Time,
+    Volume,
+    Area,
+    Speed,
+    Data,
+    Energy,
+    Angle,
+    Temperature,
+}
+
+impl Dimension {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Length => "length",
+            Self::Mass => "mass",
+            Self::Time => "time",
+            Self::Volume => "volume",
+            Self::Area => "area",
+            Self::Speed => "speed",
+            Self::Data => "data",
+            Self::Energy => "energy",
+            Self::Angle => "angle",
+            Self::Temperature => "temperature",
+        }
+    }
+}
+
+/// A unit converts to its dimension's base unit as `value * factor + offset`.
+struct Unit {
+    names: &'static [&'static str],
+    dimension: Dimension,
+    factor: f64,
+    offset: f64,
+}
+
+const fn unit(names: &'static [&'static str], dimension: Dimension, factor: f64) -> Unit {
+    Unit {
+        names,
+        dimension,
+        factor,
+        offset: 0.0,
+    }
+}
+
+const UNITS: &[Unit] = &[
+    // Length, base metre.
+    unit(
+        &["m", "meter", "meters", "metre", "metres"],
+        Dimension::Length,
+        1.0,
+    ),
+    unit(
+        &["km", "kilometer", "kilometers", "kilometre", "kilometres"],
+        Dimension::Length,
+        1e3,
+    ),
+    unit(
+        &["cm", "centimeter", "centimeters"],
+        Dimension::Length,
+        1e-2,
+    ),
+    unit(
+        &["mm", "millimeter", "millimeters"],
+        Dimension::Length,
+        1e-3,
+    ),
+    unit(
+        &["um", "µm", "micrometer", "micrometers"],
+        Dimension::Length,
+        1e-6,
+    ),
+    unit(&["nm", "nanometer", "nanometers"], Dimension::Length, 1e-9),
+    unit(&["in", "inch", "inches"], Dimension::Length, 0.0254),
+    unit(&["ft", "foot", "feet"], Dimension::Length, 0.3048),
+    unit(&["yd", "yard", "yards"], Dimension::Length, 0.9144),
+    unit(&["mi", "mile", "miles"], Dimension::Length, 1609.344),
+    unit(&["nmi"], Dimension::Length, 1852.0),
+    // Mass, base kilogram.
+    unit(&["kg", "kilogram", "kilograms"], Dimension::Mass, 1.0),
+    unit(&["g", "gram", "grams"], Dimension::Mass, 1e-3),
+    unit(&["mg", "milligram", "milligrams"], Dimension::Mass, 1e-6),
+    unit(&["t", "tonne", "tonnes"], Dimension::Mass, 1e3),
+    unit(
+        &["lb", "lbs", "pound", "pounds"],
+        Dimension::Mass,
+        0.453_592_37,
+    ),
+    unit(
+        &["oz", "ounce", "ounces"],
+        Dimension::Mass,
+        0.028_349_523_125,
+    ),
+    unit(&["st", "stone"], Dimension::Mass, 6.350_293_18),
+    // Time, base second.
+    unit(
+        &["s", "sec", "secs", "second", "seconds"],
+        Dimension::Time,
+        1.0,
+    ),
+    unit(
+        &["ms", "millisecond", "milliseconds"],
+        Dimension::Time,
+        1e-3,
+    ),
+    unit(&["min", "mins", "minute", "minutes"], Dimension::Time, 60.0),
+    unit(
+        &["h", "hr", "hrs", "hour", "hours"],
+        Dimension::Time,
+        3600.0,
+    ),
+    unit(&["d", "day", "days"], Dimension::Time, 86_400.0),
+    unit(&["wk", "week", "weeks"], Dimension::Time, 604_800.0),
+    unit(&["yr", "year", "years"], Dimension::Time, 31_557_600.0),
+    // Volume, base litre.
+    unit(
+        &["l", "L", "liter", "liters", "litre", "litres"],
+        Dimension::Volume,
+        1.0,
+    ),
+    unit(
+        &["ml", "mL", "milliliter", "milliliters"],
+        Dimension::Volume,
+        1e-3,
+    ),
+    unit(&["m3"], Dimension::Volume, 1e3),
+    unit(
+        &["gal", "gallon", "gallons"],
+        Dimension::Volume,
+        3.785_411_784,
+    ),
+    unit(&["qt", "quart", "quarts"], Dimension::Volume, 0.946_352_946),
+    unit(&["pt", "pint", "pints"], Dimension::Volume, 0.473_176_473),
+    unit(&["cup", "cups"], Dimension::Volume, 0.236_588_236_5),
+    unit(&["floz", "fl_oz"], Dimension::Volume, 0.029_573_529_562_5),
+    unit(&["tbsp"], Dimension::Volume, 0.014_786_764_781_25),
+    unit(&["tsp"], Dimension::Volume, 0.004_928_921_593_75),
+    // Area, base square metre.
+    unit(&["m2", "sqm"], Dimension::Area, 1.0),
+    unit(&["km2"], Dimension::Area, 1e6),
+    unit(&["cm2"], Dimension::Area, 1e-4),
+    unit(&["ha", "hectare", "hectares"], Dimension::Area, 1e4),
+    unit(&["acre", "acres"], Dimension::Area, 4_046.856_422_4),
+    unit(&["ft2", "sqft"], Dimension::Area, 0.092_903_04),
+    unit(&["mi2"], Dimension::Area, 2_589_988.110_336),
+    // Speed, base metre per second.
+    unit(&["m/s", "mps"], Dimension::Speed, 1.0),
+    unit(&["km/h", "kmh", "kph"], Dimension::Speed, 1.0 / 3.6),
+    unit(&["mph"], Dimension::Speed, 0.447_04),
+    unit(
+        &["kn", "kt", "knot", "knots"],
+        Dimension::Speed,
+        1852.0 / 3600.0,
+    ),
+    unit(&["ft/s", "fps"], Dimension::Speed, 0.3048),
+    // Data, base byte. KB/MB/GB/TB are decimal, KiB/MiB/GiB/TiB binary.
+    unit(&["B", "byte", "bytes"], Dimension::Data, 1.0),
+    unit(&["bit", "bits"], Dimension::Data, 0.125),
+    unit(&["KB", "kB"], Dimension::Data, 1e3),
+    unit(&["MB"], Dimension::Data, 1e6),
+    unit(&["GB"], Dimension::Data, 1e9),
+    unit(&["TB"], Dimension::Data, 1e12),
+    unit(&["KiB"], Dimension::Data, 1_024.0),
+    unit(&["MiB"], Dimension::Data, 1_048_576.0),
+    unit(&["GiB"], Dimension::Data, 1_073_741_824.0),
+    unit(&["TiB"], Dimension::Data, 1_099_511_627_776.0),
+    unit(&["Mbit", "Mb"], Dimension::Data, 125_000.0),
+    unit(&["Gbit", "Gb"], Dimension::Data, 125_000_000.0),
+    // Energy, base joule.
+    unit(&["J", "joule", "joules"], Dimension::Energy, 1.0),
+    unit(&["kJ"], Dimension::Energy, 1e3),
+    unit(&["cal"], Dimension::Energy, 4.184),
+    unit(&["kcal"], Dimension::Energy, 4_184.0),
+    unit(&["Wh"], Dimension::Energy, 3_600.0),
+    unit(&["kWh"], Dimension::Energy, 3.6e6),
+    // Angle, base radian.
+    unit(&["rad", "radian", "radians"], Dimension::Angle, 1.0),
+    unit(
+        &["deg", "degree", "degrees", "°"],
+        Dimension::Angle,
+        std::f64::consts::PI / 180.0,
+    ),
+    // Temperature, base kelvin.
+    Unit {
+        names: &["K", "kelvin"],
+        dimension: Dimension::Temperature,
+        factor: 1.0,
+        offset: 0.0,
+    },
+    Unit {
+        names: &["C", "°C", "celsius"],
+        dimension: Dimension::Temperature,
+        factor: 1.0,
+        offset: 273.15,
+    },
+    Unit {
+        names: &["F", "°F", "fahrenheit"],
+        dimension: Dimension::Temperature,
+        factor: 5.0 / 9.0,
+        offset: 273.15 - 32.0 * 5.0 / 9.0,
+    },
+];
+
+/// Find a unit by name; exact spelling wins over a case-insensitive match,
+/// so `Mb` (megabit) and `MB` (megabyte) stay distinct.
+fn find_unit(name: &str) -> Option<&'static Unit> {
+    let name = name.trim();
+    UNITS
+        .iter()
+        .find(|unit| unit.names.contains(&name))
+        .or_else(|| {
+            UNITS
+                .iter()
+                .find(|unit| unit.names.iter().any(|n| n.eq_ignore_ascii_case(name)))
+        })
+}
+
+/// Split `"<amount> <unit>"` or `"<amount><unit>"` into the amount
+/// expression and the unit.
+fn split_quantity(quantity: &str) -> Option<(&str, &'static Unit)> {
+    let quantity = quantity.trim();
+    if let Some((amount, name)) = quantity.rsplit_once(char::is_whitespace) {
+        if let Some(unit) = find_unit(name) {
+            return Some((amount, unit));
+        }
+    }
+    let start = quantity
+        .rfind(|c: char| c.is_whitespace() || "()+-*/^%".contains(c))
+        .map_or(0, |i| i + 1);
+    quantity[start..]
+        .char_indices()
+        .skip(1)
+        .filter(|(i, _)| {
+            quantity[start..start + i]
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '.')
+        })
+        .find_map(|(i, _)| {
+            find_unit(&quantity[start + i..]).map(|unit| (&quantity[..start + i], unit))
+        })
+}
+
+/// Conversions written `<amount> <unit> to|in <unit>`. `None` when the
+/// expression does not end in a known target unit.
+fn convert_units(expression: &str) -> Option<Result<String, String>> {
+    let lower = expression.to_ascii_lowercase();
+    let split = [" to ", " in ", " as "]
+        .iter()
+        .filter_map(|sep| lower.rfind(sep).map(|i| (i, sep.len())))
+        .max_by_key(|(i, _)| *i)?;
+    let (quantity, target) = (&expression[..split.0], &expression[split.0 + split.1..]);
+    let to = find_unit(target)?;
+    let Some((amount, from)) = split_quantity(quantity) else {
+        return Some(Err(format!("Unknown unit in '{}'", quantity.trim())));
+    };
+    if from.dimension != to.dimension {
+        return Some(Err(format!(
+            "Cannot convert {} ({}) to {} ({})",
+            from.names[0],
+            from.dimension.name(),
+            to.names[0],
+            to.dimension.name()
+        )));
+    }
+    Some(arithmetic(amount).and_then(|value| {
+        let converted = ((value * from.factor + from.offset) - to.offset) / to.factor;
+        if converted.is_finite() {
+            Ok(format!(
+                "{} {} = {} {}",
+                format_number(value),
+                from.names[0],
+                format_number(converted),
+                to.names[0]
+            ))
+        } else {
+            Err("Result is not a finite number".into())
+        }
+    }))
+}
+
+// ── Arithmetic ──────────────────────────────────────────────────
+
+fn arithmetic(expression: &str) -> Result<f64, String> {
+    let mut parser = Parser {
+        chars: expression.chars().collect(),
+        pos: 0,
+    };
+    let value = parser.expr()?;
+    parser.skip_whitespace();
+    if let Some(c) = parser.peek() {
+        return Err(format!("Unexpected '{c}' at position {}", parser.pos + 1));
+    }
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err("Result is not a finite number".into())
+    }
+}
+
+/// Recursive-descent parser; `^` binds tighter than unary minus and is
+/// right-associative, so `-2^2` is `-4` and `2^3^2` is `512`.
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consume `token` after optional whitespace.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let len = token.chars().count();
+        let matches = self
+            .chars
+            .get(self.pos..self.pos + len)
+            .is_some_and(|slice| slice.iter().copied().eq(token.chars()));
+        if matches {
+            self.pos += len;
+        }
+        matches
+    }
+
+    fn expr(&mut self) -> Result<f64, String> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat("+") {
+                value += self.term()?;
+            } else if self.eat("-") {
+                value -= self.term()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<f64, String> {
+        let mut value = self.unary()?;
+        loop {
+            if self.eat("*") {
+                value *= self.unary()?;
+            } else if self.eat("/") {
+                let divisor = self.unary()?;
+                if divisor == 0.0 {
+                    return Err("Division by zero".into());
+                }
+                value /= divisor;
+            } else if self.eat("%") {
+                let divisor = self.unary()?;
+                if divisor == 0.0 {
+                    return Err("Division by zero".into());
+                }
+                value %= divisor;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<f64, String> {
+        if self.eat("-") {
+            return Ok(-self.unary()?);
+        }
+        if self.eat("+") {
+            return self.unary();
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<f64, String> {
+        let base = self.atom()?;
+        if self.eat("^") || self.eat("**") {
+            let exponent = self.unary()?;
+            return Ok(base.powf(exponent));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<f64, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let value = self.expr()?;
+                if !self.eat(")") {
+                    return Err("Missing ')'".into());
+                }
+                Ok(value)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number(),
+            Some(c) if c.is_alphabetic() => self.identifier(),
+            Some(c) => Err(format!("Unexpected '{c}' at position {}", self.pos + 1)),
+            None => Err("Unexpected end of expression".into()),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || c == '.' || c == '_')
+        {
+            self.pos += 1;
+        }
+        // Exponent only when digits follow, so `2e` stays `2` then `e`.
+        if matches!(self.peek(), Some('e' | 'E')) {
+            let mut end = self.pos + 1;
+            if matches!(self.chars.get(end), Some('+' | '-')) {
+                end += 1;
+            }
+            if self.chars.get(end).is_some_and(char::is_ascii_digit) {
+                self.pos = end;
+                while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    self.pos += 1;
+                }
+            }
+        }
+        let text: String = self.chars[start..self.pos]
+            .iter()
+            .filter(|c| **c != '_')
+            .collect();
+        text.parse::<f64>()
+            .map_err(|_| format!("Invalid number '{text}'"))
+    }
+
+    fn identifier(&mut self) -> Result<f64, String> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.pos += 1;
+        }
+        let name: String = self.chars[start..self.pos].iter().collect();
+        let name = name.to_ascii_lowercase();
+        if !self.eat("(") {
+            return match name.as_str() {
+                "pi" => Ok(std::f64::consts::PI),
+                "tau" => Ok(std::f64::consts::TAU),
+                "e" => Ok(std::f64::consts::E),
+                _ => Err(format!("Unknown name '{name}'")),
+            };
+        }
+        let mut args = vec![self.expr()?];
+        while self.eat(",") {
+            args.push(self.expr()?);
+        }
+        if !self.eat(")") {
+            return Err(format!("Missing ')' after arguments of {name}"));
+        }
+        call(&name, &args)
+    }
+}
+
+fn call(name: &str, args: &[f64]) -> Result<f64, String> {
+    let arity = |expected: usize| {
+        if args.len() == expected {
+            Ok(())
+        } else {
+            Err(format!("{name}() takes {expected} argument(s)"))
+        }
+    };
+    match name {
+        "min" | "max" if args.is_empty() => Err(format!("{name}() needs arguments")),
+        "min" => Ok(args.iter().copied().fold(f64::INFINITY, f64::min)),
+        "max" => Ok(args.iter().copied().fold(f64::NEG_INFINITY, f64::max)),
+        "log" if args.len() == 2 => Ok(args[0].log(args[1])),
+        "round" if args.len() == 2 => {
+            let scale = 10f64.powf(args[1].trunc());
+            Ok((args[0] * scale).round() / scale)
+        }
+        _ => {
+            arity(1)?;
+            let x = args[0];
+            let value = match name {
+                "sqrt" => x.sqrt(),
+                "cbrt" => x.cbrt(),
+                "abs" => x.abs(),
+                "ln" => x.ln(),
+                "log" | "log10" => x.log10(),
+                "log2" => x.log2(),
+                "exp" => x.exp(),
+                "sin" => x.sin(),
+                "cos" => x.cos(),
+                "tan" => x.tan(),
+                "asin" => x.asin(),
+                "acos" => x.acos(),
+                "atan" => x.atan(),
+                "floor" => x.floor(),
+                "ceil" => x.ceil(),
+                "round" => x.round(),
+                _ => return Err(format!("Unknown function '{name}'")),
+            };
+            Ok(value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(raw: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn calc_evaluates_arithmetic() {
+        let today = day("2026-10-16");
+        let eval = |expr: &str| evaluate(expr, today);
+        assert_eq!(eval("1 + 2 * 3").unwrap(), "7");
+        assert_eq!(eval("(1 + 2) * 3").unwrap(), "9");
+        assert_eq!(eval("-2^2").unwrap(), "-4");
+        assert_eq!(eval("2^3^2").unwrap(), "512");
+        assert_eq!(eval("2 ** 10").unwrap(), "1024");
+        assert_eq!(eval("10 / 4").unwrap(), "2.5");
+        assert_eq!(eval("17 % 5").unwrap(), "2");
+        assert_eq!(eval("sqrt(16) + max(1, 7, 3)").unwrap(), "11");
+        assert_eq!(eval("round(pi, 2)").unwrap(), "3.14");
+        assert_eq!(eval("1_000_000 * 1.5e3").unwrap(), "1500000000");
+        assert_eq!(eval("0.1 + 0.2").unwrap(), "0.3");
+        assert!(eval("1 / 0").unwrap_err().contains("Division by zero"));
+        assert!(eval("2 +").is_err());
+        assert!(eval("foo(1)").unwrap_err().contains("Unknown function"));
+    }
+
+    #[test]
+    fn calc_converts_units() {
+        let today = day("2026-10-16");
+        let eval = |expr: &str| evaluate(expr, today);
+        assert_eq!(eval("5 km to mi").unwrap(), "5 km = 3.1068559612 mi");
+        assert_eq!(eval("100 C to F").unwrap(), "100 C = 212 F");
+        assert_eq!(eval("-40 fahrenheit in celsius").unwrap(), "-40 F = -40 C");
+        assert_eq!(eval("2 GiB to MB").unwrap(), "2 GiB = 2147.483648 MB");
+        assert_eq!(eval("3ft in in").unwrap(), "3 ft = 36 in");
+        assert_eq!(eval("(2 + 3) h to min").unwrap(), "5 h = 300 min");
+        assert!(eval("5 kg to m").unwrap_err().contains("mass"));
+    }
+
+    #[test]
+    fn calc_does_date_math() {
+        let today = day("2026-10-16");
+        let eval = |expr: &str| evaluate(expr, today);
+        assert_eq!(eval("today + 37 days").unwrap(), "2026-11-22 (Sunday)");
+        assert_eq!(
+            eval("2026-01-31 + 1 month").unwrap(),
+            "2026-02-28 (Saturday)"
+        );
+        assert_eq!(
+            eval("2026-03-01 - 2w + 1 year").unwrap(),
+            "2027-02-15 (Monday)"
+        );
+        assert_eq!(
+            eval("2026-12-25 - today").unwrap(),
+            "70 days (10 weeks, 0 days)"
+        );
+        assert_eq!(eval("days until tomorrow").unwrap(), "1 day");
+        assert_eq!(
+            eval("days between 2026-01-01 and 2026-03-01").unwrap(),
+            "59 days (8 weeks, 3 days)"
+        );
+        assert!(eval("today + 3 fortnights").is_err());
+        assert!(eval("days since 2026-13-01").is_err());
+    }
+}
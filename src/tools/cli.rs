@@ -0,0 +1,151 @@
+use super::traits::{Tool, ToolResult};
+use crate::approval::{ApprovalManager, ApprovalRequest, ApprovalResponse};
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+
+/// Handle `zeroclaw tool <subcommand>` CLI commands.
+pub async fn handle_command(command: crate::ToolCommands, config: &Config) -> Result<()> {
+    let (registry, _memory) = crate::agent::loop_::build_tool_registry(config).await?;
+    match command {
+        crate::ToolCommands::List { json } => {
+            let approvals = ApprovalManager::from_config(&config.autonomy);
+            let rows = super::registry::describe(&registry, &approvals, &[], "cli");
+            if json {
+                println!("{}", serde_json::to_string_pretty(&rows)?);
+                return Ok(());
+            }
+            println!(
+                "{:<28} {:<7} {:<11} DESCRIPTION",
+                "NAME", "RISK", "APPROVAL"
+            );
+            for row in rows {
+                println!(
+                    "{:<28} {:<7} {:<11} {}",
+                    row.name,
+                    row.risk.as_str(),
+                    row.approval.as_str(),
+                    row.description
+                );
+            }
+            Ok(())
+        }
+        crate::ToolCommands::Exec { name, args } => {
+            let args: serde_json::Value =
+                serde_json::from_str(&args).context("--args must be valid JSON")?;
+            if !args.is_object() {
+                bail!("--args must be a JSON object");
+            }
+            let Some(tool) = registry.iter().find(|tool| tool.name() == name) else {
+                bail!("Unknown tool '{name}'. Run `zeroclaw tool list` to see registered tools.");
+            };
+
+            let approvals = ApprovalManager::from_config(&config.autonomy);
+            let result = execute_with_approval(tool.as_ref(), args, &approvals, |request| {
+                approvals.prompt_cli(request)
+            })
+            .await?;
+            if !result.success {
+                bail!(
+                    "{name} failed: {}",
+                    crate::agent::loop_::scrub_credentials(
+                        result.error.as_deref().unwrap_or(&result.output)
+                    )
+                );
+            }
+            println!("{}", crate::agent::loop_::scrub_credentials(&result.output));
+            Ok(())
+        }
+    }
+}
+
+/// Run one tool call the way the CLI agent loop does: supervised tools ask
+/// for approval first (stdin EOF counts as "no") and every decision lands
+/// in the approval audit log. The tool's own security policy checks then
+/// apply as usual.
+async fn execute_with_approval(
+    tool: &dyn Tool,
+    args: serde_json::Value,
+    approvals: &ApprovalManager,
+    prompt: impl FnOnce(&ApprovalRequest) -> ApprovalResponse,
+) -> Result<ToolResult> {
+    if approvals.needs_approval(tool.name(), "cli") {
+        let request = ApprovalRequest {
+            tool_name: tool.name().to_string(),
+            arguments: args.clone(),
+        };
+        let decision = prompt(&request);
+        approvals.record_decision(tool.name(), &args, decision, "cli");
+        if decision == ApprovalResponse::No {
+            bail!("Denied by user.");
+        }
+    }
+    tool.execute(args).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AutonomyConfig;
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    struct EchoTool;
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "Echo the arguments"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            json!({ "type": "object" })
+        }
+
+        async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: args.to_string(),
+                error: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn supervised_tool_runs_only_after_approval() {
+        let approvals = ApprovalManager::from_config(&AutonomyConfig::default());
+
+        let denied = execute_with_approval(&EchoTool, json!({"x": 1}), &approvals, |request| {
+            assert_eq!(request.tool_name, "echo");
+            ApprovalResponse::No
+        })
+        .await;
+        assert!(denied.unwrap_err().to_string().contains("Denied"));
+
+        let result = execute_with_approval(&EchoTool, json!({"x": 1}), &approvals, |_| {
+            ApprovalResponse::Yes
+        })
+        .await
+        .unwrap();
+        assert_eq!(result.output, r#"{"x":1}"#);
+        assert_eq!(approvals.audit_log().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn auto_approved_tool_skips_the_prompt() {
+        let approvals = ApprovalManager::from_config(&AutonomyConfig {
+            auto_approve: vec!["echo".into()],
+            ..AutonomyConfig::default()
+        });
+
+        let result = execute_with_approval(&EchoTool, json!({}), &approvals, |_| {
+            panic!("auto-approved tools must not prompt")
+        })
+        .await
+        .unwrap();
+        assert!(result.success);
+    }
+}
@@ -0,0 +1,117 @@
+//! Process-wide limits on how many tool calls run at once.
+//!
+//! Every tool call takes a slot from its tool's semaphore
+//! (`agent.tool_concurrency`) and then one from the global semaphore
+//! (`agent.max_concurrent_tools`) before it executes. Tokio semaphores hand
+//! out permits in request order, so calls that have to wait are served first
+//! come, first served. A call waiting for a busy tool does not hold a global
+//! slot, so one saturated tool cannot starve the others.
+
+use crate::config::AgentConfig;
+use crate::workspace::Scoped;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Semaphores for the global cap and the per-tool caps.
+#[derive(Debug, Default)]
+pub struct ToolLimiter {
+    global: Option<Arc<Semaphore>>,
+    per_tool: HashMap<String, Arc<Semaphore>>,
+}
+
+/// Slots held by a running tool call; released on drop.
+#[derive(Debug)]
+pub struct ToolPermit {
+    _tool: Option<OwnedSemaphorePermit>,
+    _global: Option<OwnedSemaphorePermit>,
+}
+
+impl From<&AgentConfig> for ToolLimiter {
+    fn from(config: &AgentConfig) -> Self {
+        Self {
+            global: (config.max_concurrent_tools > 0)
+                .then(|| Arc::new(Semaphore::new(config.max_concurrent_tools))),
+            per_tool: config
+                .tool_concurrency
+                .iter()
+                .filter(|(_, limit)| **limit > 0)
+                .map(|(name, limit)| (name.clone(), Arc::new(Semaphore::new(*limit))))
+                .collect(),
+        }
+    }
+}
+
+impl ToolLimiter {
+    /// Wait for a slot to run `tool`.
+    pub async fn acquire(&self, tool: &str) -> ToolPermit {
+        let tool_permit = match self.per_tool.get(tool) {
+            Some(semaphore) => Some(wait_for(semaphore, tool, "tool").await),
+            None => None,
+        };
+        let global_permit = match self.global.as_ref() {
+            Some(semaphore) => Some(wait_for(semaphore, tool, "global").await),
+            None => None,
+        };
+        ToolPermit {
+            _tool: tool_permit,
+            _global: global_permit,
+        }
+    }
+}
+
+async fn wait_for(semaphore: &Arc<Semaphore>, tool: &str, scope: &str) -> OwnedSemaphorePermit {
+    if let Ok(permit) = semaphore.clone().try_acquire_owned() {
+        return permit;
+    }
+    tracing::debug!(tool, scope, "Tool call queued for a concurrency slot");
+    semaphore
+        .clone()
+        .acquire_owned()
+        .await
+        .expect("tool concurrency semaphores are never closed")
+}
+
+static LIMITER: LazyLock<Scoped<Arc<ToolLimiter>>> =
+    LazyLock::new(|| Scoped::new(Arc::new(ToolLimiter::from(&AgentConfig::default()))));
+
+/// Apply `agent.max_concurrent_tools` and `agent.tool_concurrency`. Calls
+/// already running keep the slots they hold under the previous limits.
+pub fn init_from_config(config: &AgentConfig) {
+    LIMITER.set(Arc::new(ToolLimiter::from(config)));
+}
+
+/// Wait for a slot to run `tool` under the configured limits.
+pub async fn acquire(tool: &str) -> ToolPermit {
+    LIMITER.get().acquire(tool).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn per_tool_and_global_limits_queue_excess_calls() {
+        let limiter = ToolLimiter::from(&AgentConfig {
+            max_concurrent_tools: 2,
+            tool_concurrency: HashMap::from([("browser".to_string(), 1)]),
+            ..AgentConfig::default()
+        });
+
+        let browser = limiter.acquire("browser").await;
+        let second_browser =
+            tokio::time::timeout(Duration::from_millis(50), limiter.acquire("browser")).await;
+        assert!(second_browser.is_err(), "browser is limited to one call");
+
+        let _shell = limiter.acquire("shell").await;
+        let third =
+            tokio::time::timeout(Duration::from_millis(50), limiter.acquire("file_read")).await;
+        assert!(third.is_err(), "global cap of two is reached");
+
+        drop(browser);
+        tokio::time::timeout(Duration::from_millis(50), limiter.acquire("file_read"))
+            .await
+            .expect("released slot is handed to the waiting call");
+    }
+}
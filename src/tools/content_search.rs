@@ -221,12 +221,15 @@ impl Tool for ContentSearchTool {
             }
         };
 
-        if !self.security.is_resolved_path_allowed(&resolved_canon) {
+        if let Err(reason) = self
+            .security
+            .check_tool_resolved_path(self.name(), &resolved_canon)
+        {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!(
-                    "Resolved path for '{search_path}' is outside the allowed workspace."
+                    "Resolved path for '{search_path}' is outside the allowed workspace. {reason}"
                 )),
             });
         }
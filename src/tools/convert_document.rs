@@ -0,0 +1,535 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{ConvertDocumentConfig, PdfEngine};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::ffi::OsString;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Maximum input document size (50 MB).
+const MAX_INPUT_BYTES: u64 = 50 * 1024 * 1024;
+/// Converter stderr kept in error messages.
+const MAX_STDERR_CHARS: usize = 1_000;
+/// Chrome/Chromium executables looked up on `PATH` when `chrome_path` is unset.
+const CHROME_CANDIDATES: &[&str] = &[
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "chrome",
+];
+const MACOS_CHROME: &str = "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Markdown,
+    Html,
+    Pdf,
+    Docx,
+}
+
+impl Format {
+    fn parse(name: &str) -> Option<Self> {
+        match name
+            .trim()
+            .trim_start_matches('.')
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "md" | "markdown" => Some(Self::Markdown),
+            "html" | "htm" => Some(Self::Html),
+            "pdf" => Some(Self::Pdf),
+            "docx" => Some(Self::Docx),
+            _ => None,
+        }
+    }
+
+    fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::parse)
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Pdf => "pdf",
+            Self::Docx => "docx",
+        }
+    }
+
+    /// pandoc reader/writer name.
+    fn pandoc_name(self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::Html => "html",
+            Self::Pdf => "pdf",
+            Self::Docx => "docx",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Pandoc { from: Format, to: Format },
+    HtmlToPdf,
+}
+
+/// Converter runs needed to turn `from` into `to`. PDF is only produced
+/// from HTML, so other formats go through pandoc's HTML writer first.
+fn plan(from: Format, to: Format) -> Result<Vec<Step>, String> {
+    if from == to {
+        return Err(format!("Input is already {}", from.pandoc_name()));
+    }
+    match (from, to) {
+        (Format::Pdf, _) => {
+            Err("PDF input is not supported; use pdf_read to extract its text".into())
+        }
+        (Format::Html, Format::Pdf) => Ok(vec![Step::HtmlToPdf]),
+        (_, Format::Pdf) => Ok(vec![
+            Step::Pandoc {
+                from,
+                to: Format::Html,
+            },
+            Step::HtmlToPdf,
+        ]),
+        _ => Ok(vec![Step::Pandoc { from, to }]),
+    }
+}
+
+fn pandoc_args(from: Format, to: Format, input: &Path, output: &Path) -> Vec<OsString> {
+    let title = input
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut args: Vec<OsString> = vec![
+        "--from".into(),
+        from.pandoc_name().into(),
+        "--to".into(),
+        to.pandoc_name().into(),
+        "--standalone".into(),
+        "--metadata".into(),
+        format!("pagetitle={title}").into(),
+        "--output".into(),
+    ];
+    args.push(output.into());
+    args.push(input.into());
+    args
+}
+
+/// Convert documents between markdown, HTML, PDF and DOCX in the workspace.
+///
+/// pandoc handles markdown, HTML and DOCX; PDF is rendered from HTML with
+/// headless Chrome/Chromium or wkhtmltopdf.
+pub struct ConvertDocumentTool {
+    security: Arc<SecurityPolicy>,
+    config: ConvertDocumentConfig,
+}
+
+impl ConvertDocumentTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: ConvertDocumentConfig) -> Self {
+        Self { security, config }
+    }
+
+    /// The HTML to PDF renderer to use and its executable.
+    fn pdf_renderer(&self) -> Result<(PdfEngine, PathBuf), String> {
+        let chrome = || -> Option<PathBuf> {
+            if let Some(path) = &self.config.chrome_path {
+                return which::which(path).ok();
+            }
+            CHROME_CANDIDATES
+                .iter()
+                .find_map(|name| which::which(name).ok())
+                .or_else(|| {
+                    let path = PathBuf::from(MACOS_CHROME);
+                    (cfg!(target_os = "macos") && path.is_file()).then_some(path)
+                })
+        };
+        let wkhtmltopdf = || which::which(&self.config.wkhtmltopdf_path).ok();
+        match self.config.pdf_engine {
+            PdfEngine::Auto => chrome()
+                .map(|path| (PdfEngine::Chrome, path))
+                .or_else(|| wkhtmltopdf().map(|path| (PdfEngine::Wkhtmltopdf, path)))
+                .ok_or_else(|| {
+                    "No PDF renderer found: install Chrome/Chromium or wkhtmltopdf, or set convert_document.chrome_path".into()
+                }),
+            PdfEngine::Chrome => chrome()
+                .map(|path| (PdfEngine::Chrome, path))
+                .ok_or_else(|| {
+                    "Chrome/Chromium not found; set convert_document.chrome_path".into()
+                }),
+            PdfEngine::Wkhtmltopdf => wkhtmltopdf()
+                .map(|path| (PdfEngine::Wkhtmltopdf, path))
+                .ok_or_else(|| {
+                    format!("{} not found", self.config.wkhtmltopdf_path)
+                }),
+        }
+    }
+
+    async fn run(&self, program: &Path, args: Vec<OsString>) -> Result<(), String> {
+        let name = program
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let result = tokio::time::timeout(
+            Duration::from_secs(self.config.timeout_secs),
+            tokio::process::Command::new(program)
+                .args(&args)
+                .kill_on_drop(true)
+                .output(),
+        )
+        .await;
+        match result {
+            Ok(Ok(output)) if output.status.success() => Ok(()),
+            Ok(Ok(output)) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let stderr = stderr.trim();
+                if stderr.is_empty() {
+                    Err(format!("{name} exited with status {}", output.status))
+                } else {
+                    Err(format!(
+                        "{name} failed: {}",
+                        crate::util::truncate_with_ellipsis(stderr, MAX_STDERR_CHARS)
+                    ))
+                }
+            }
+            Ok(Err(e)) if e.kind() == ErrorKind::NotFound => {
+                Err(format!("{} not found", program.display()))
+            }
+            Ok(Err(e)) => Err(format!("Failed to run {name}: {e}")),
+            Err(_) => Err(format!(
+                "{name} timed out after {}s",
+                self.config.timeout_secs
+            )),
+        }
+    }
+
+    async fn html_to_pdf(&self, input: &Path, output: &Path) -> Result<(), String> {
+        let (engine, program) = self.pdf_renderer()?;
+        if engine == PdfEngine::Wkhtmltopdf {
+            let mut args: Vec<OsString> = vec![
+                "--quiet".into(),
+                "--disable-local-file-access".into(),
+                "--allow".into(),
+            ];
+            args.push(input.parent().unwrap_or(input).into());
+            args.push(input.into());
+            args.push(output.into());
+            return self.run(&program, args).await;
+        }
+
+        // A throwaway profile keeps the run independent of any open browser.
+        let profile = tempfile::TempDir::new()
+            .map_err(|e| format!("Failed to create a browser profile directory: {e}"))?;
+        let mut user_data_dir = OsString::from("--user-data-dir=");
+        user_data_dir.push(profile.path());
+        let mut print_to_pdf = OsString::from("--print-to-pdf=");
+        print_to_pdf.push(output);
+        let args: Vec<OsString> = vec![
+            "--headless".into(),
+            "--disable-gpu".into(),
+            "--disable-extensions".into(),
+            "--no-first-run".into(),
+            "--no-pdf-header-footer".into(),
+            user_data_dir,
+            print_to_pdf,
+            input.into(),
+        ];
+        self.run(&program, args).await
+    }
+
+    async fn convert(&self, input: &Path, output: &Path, steps: &[Step]) -> Result<(), String> {
+        let pandoc = PathBuf::from(&self.config.pandoc_path);
+        match steps {
+            [Step::Pandoc { from, to }] => {
+                self.run(&pandoc, pandoc_args(*from, *to, input, output))
+                    .await
+            }
+            [Step::HtmlToPdf] => self.html_to_pdf(input, output).await,
+            [Step::Pandoc { from, to }, Step::HtmlToPdf] => {
+                // The intermediate page sits next to the input so relative
+                // image links still resolve when it is rendered.
+                let stem = input
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let html = input.with_file_name(format!(".{stem}.convert.html"));
+                let result = match self
+                    .run(&pandoc, pandoc_args(*from, *to, input, &html))
+                    .await
+                {
+                    Ok(()) => self.html_to_pdf(&html, output).await,
+                    Err(e) => Err(e),
+                };
+                let _ = tokio::fs::remove_file(&html).await;
+                result
+            }
+            _ => Err("Unsupported conversion".into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ConvertDocumentTool {
+    fn name(&self) -> &str {
+        "convert_document"
+    }
+
+    fn description(&self) -> &str {
+        "Convert a workspace document between markdown, HTML, PDF and DOCX (PDF is output only). \
+         Writes the converted file and returns a [DOCUMENT:<path>] marker; include the marker \
+         in your reply to send the file on chat channels."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "input": {
+                    "type": "string",
+                    "description": "Document to convert (.md, .html, .docx). Relative paths resolve from workspace."
+                },
+                "to": {
+                    "type": "string",
+                    "enum": ["markdown", "html", "pdf", "docx"],
+                    "description": "Target format. Optional when 'output' has a known extension."
+                },
+                "output": {
+                    "type": "string",
+                    "description": "Where to write the result. Defaults to the input path with the target format's extension."
+                }
+            },
+            "required": ["input"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let input = args
+            .get("input")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'input' parameter"))?;
+        let output_arg = args.get("output").and_then(|v| v.as_str());
+        let to_arg = args.get("to").and_then(|v| v.as_str());
+
+        let fail = |error: String| -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            })
+        };
+
+        let Some(from) = Format::from_path(Path::new(input)) else {
+            return fail(format!(
+                "Unsupported input format: {input} (expected .md, .html or .docx)"
+            ));
+        };
+        let to = match (to_arg, output_arg) {
+            (Some(name), _) => match Format::parse(name) {
+                Some(format) => format,
+                None => return fail(format!("Unsupported target format: {name}")),
+            },
+            (None, Some(output)) => match Format::from_path(Path::new(output)) {
+                Some(format) => format,
+                None => {
+                    return fail(format!(
+                        "Cannot tell the target format from {output}; pass 'to'"
+                    ))
+                }
+            },
+            (None, None) => return fail("Pass 'to' or an 'output' path".into()),
+        };
+        let steps = match plan(from, to) {
+            Ok(steps) => steps,
+            Err(e) => return fail(e),
+        };
+        let output = output_arg.map_or_else(
+            || {
+                Path::new(input)
+                    .with_extension(to.extension())
+                    .to_string_lossy()
+                    .into_owned()
+            },
+            String::from,
+        );
+
+        if !self.security.can_act() {
+            return fail("Action blocked: autonomy is read-only".into());
+        }
+        if self.security.is_rate_limited() {
+            return fail("Rate limit exceeded: too many actions in the last hour".into());
+        }
+        for path in [input, output.as_str()] {
+            if !self.security.is_path_allowed(path) {
+                return fail(format!("Path not allowed by security policy: {path}"));
+            }
+        }
+
+        let resolved_input =
+            match tokio::fs::canonicalize(self.security.workspace_dir.join(input)).await {
+                Ok(path) => path,
+                Err(e) => return fail(format!("Failed to resolve input path: {e}")),
+            };
+        if let Err(reason) = self
+            .security
+            .check_tool_resolved_path(self.name(), &resolved_input)
+        {
+            return fail(reason);
+        }
+        match tokio::fs::metadata(&resolved_input).await {
+            Ok(meta) if !meta.is_file() => return fail(format!("Not a file: {input}")),
+            Ok(meta) if meta.len() > MAX_INPUT_BYTES => {
+                return fail(format!(
+                    "Input too large: {} bytes (limit: {MAX_INPUT_BYTES} bytes)",
+                    meta.len()
+                ))
+            }
+            Ok(_) => {}
+            Err(e) => return fail(format!("Failed to read file metadata: {e}")),
+        }
+
+        let full_output = self.security.workspace_dir.join(&output);
+        let (Some(parent), Some(file_name)) = (full_output.parent(), full_output.file_name())
+        else {
+            return fail(format!("Invalid output path: {output}"));
+        };
+        tokio::fs::create_dir_all(parent).await?;
+        let resolved_parent = match tokio::fs::canonicalize(parent).await {
+            Ok(path) => path,
+            Err(e) => return fail(format!("Failed to resolve output path: {e}")),
+        };
+        if let Err(reason) = self
+            .security
+            .check_tool_resolved_path(self.name(), &resolved_parent)
+        {
+            return fail(reason);
+        }
+        let resolved_output = resolved_parent.join(file_name);
+        if resolved_output == resolved_input {
+            return fail("Output would overwrite the input".into());
+        }
+        if let Ok(meta) = tokio::fs::symlink_metadata(&resolved_output).await {
+            if meta.file_type().is_symlink() {
+                return fail(format!(
+                    "Refusing to write through symlink: {}",
+                    resolved_output.display()
+                ));
+            }
+        }
+
+        if !self.security.record_action() {
+            return fail("Rate limit exceeded: action budget exhausted".into());
+        }
+
+        super::undo::record_file_change(&resolved_output).await;
+        if let Err(e) = self
+            .convert(&resolved_input, &resolved_output, &steps)
+            .await
+        {
+            return fail(e);
+        }
+        let size = match tokio::fs::metadata(&resolved_output).await {
+            Ok(meta) => meta.len(),
+            Err(_) => return fail("Converter finished without writing the output file".into()),
+        };
+
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "Converted {input} to {output} ({size} bytes)\n[DOCUMENT:{}]",
+                resolved_output.display()
+            ),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_tool(workspace: PathBuf, autonomy: AutonomyLevel) -> ConvertDocumentTool {
+        ConvertDocumentTool::new(
+            Arc::new(SecurityPolicy {
+                autonomy,
+                workspace_dir: workspace,
+                ..SecurityPolicy::default()
+            }),
+            ConvertDocumentConfig {
+                enabled: true,
+                pandoc_path: "zeroclaw-missing-pandoc".into(),
+                ..ConvertDocumentConfig::default()
+            },
+        )
+    }
+
+    #[test]
+    fn plan_routes_pdf_through_html() {
+        assert_eq!(
+            plan(Format::Markdown, Format::Docx).unwrap(),
+            vec![Step::Pandoc {
+                from: Format::Markdown,
+                to: Format::Docx
+            }]
+        );
+        assert_eq!(
+            plan(Format::Html, Format::Pdf).unwrap(),
+            vec![Step::HtmlToPdf]
+        );
+        assert_eq!(
+            plan(Format::Docx, Format::Pdf).unwrap(),
+            vec![
+                Step::Pandoc {
+                    from: Format::Docx,
+                    to: Format::Html
+                },
+                Step::HtmlToPdf
+            ]
+        );
+        assert!(plan(Format::Pdf, Format::Markdown).is_err());
+        assert!(plan(Format::Html, Format::Html).is_err());
+        assert_eq!(Format::parse(".HTM"), Some(Format::Html));
+        assert_eq!(Format::parse("txt"), None);
+    }
+
+    #[tokio::test]
+    async fn convert_document_checks_formats_paths_and_autonomy() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("report.md"), "# Report\n").unwrap();
+        let tool = test_tool(tmp.path().to_path_buf(), AutonomyLevel::Supervised);
+
+        let result = tool.execute(json!({ "input": "report.md" })).await.unwrap();
+        assert!(result.error.unwrap().contains("'to'"));
+
+        let result = tool
+            .execute(json!({ "input": "scan.pdf", "to": "docx" }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("Unsupported input format"));
+
+        let result = tool
+            .execute(json!({ "input": "report.md", "output": "../report.docx" }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("not allowed"));
+
+        let result = tool
+            .execute(json!({ "input": "report.md", "to": "docx" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not found"));
+
+        let readonly = test_tool(tmp.path().to_path_buf(), AutonomyLevel::ReadOnly);
+        let result = readonly
+            .execute(json!({ "input": "report.md", "to": "html" }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
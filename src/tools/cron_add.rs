@@ -54,8 +54,9 @@ impl Tool for CronAddTool {
     }
 
     fn description(&self) -> &str {
-        "Create a scheduled cron job (shell or agent) with cron/at/every schedules. \
-         Use job_type='agent' with a prompt to run the AI agent on schedule. \
+        "Create a scheduled cron job (shell, agent or tool) with cron/at/every schedules. \
+         Use job_type='agent' with a prompt to run the AI agent on schedule, or job_type='tool' \
+         with tool and tool_args to invoke one tool directly. \
          To deliver output to a channel (Discord, Telegram, Slack, Mattermost, QQ, Lark, Feishu, Email), set \
          delivery={\"mode\":\"announce\",\"channel\":\"discord\",\"to\":\"<channel_id_or_chat_id>\"}. \
          This is the preferred tool for sending scheduled/delayed messages to users via channels."
@@ -68,10 +69,13 @@ impl Tool for CronAddTool {
                 "name": { "type": "string" },
                 "schedule": {
                     "type": "object",
-                    "description": "Schedule object: {kind:'cron',expr,tz?} | {kind:'at',at} | {kind:'every',every_ms}"
+                    "description": "Schedule object: {kind:'cron',expr,tz?} | {kind:'at',at} | {kind:'every',every_ms}. \
+                        Without tz, cron expressions and 'at' times without a UTC offset use the user's time zone."
                 },
-                "job_type": { "type": "string", "enum": ["shell", "agent"] },
+                "job_type": { "type": "string", "enum": ["shell", "agent", "tool"] },
                 "command": { "type": "string" },
+                "tool": { "type": "string", "description": "Tool name for job_type='tool'" },
+                "tool_args": { "type": "object", "description": "Arguments passed to the tool for job_type='tool'" },
                 "prompt": { "type": "string" },
                 "session_target": { "type": "string", "enum": ["isolated", "main"] },
                 "model": { "type": "string" },
@@ -80,8 +84,8 @@ impl Tool for CronAddTool {
                     "description": "Delivery config to send job output to a channel. Example: {\"mode\":\"announce\",\"channel\":\"discord\",\"to\":\"<channel_id>\"}",
                     "properties": {
                         "mode": { "type": "string", "enum": ["none", "announce"], "description": "Set to 'announce' to deliver output to a channel" },
-                        "channel": { "type": "string", "enum": ["telegram", "discord", "slack", "mattermost", "qq", "lark", "feishu", "email"], "description": "Channel type to deliver to" },
-                        "to": { "type": "string", "description": "Target: Discord channel ID, Telegram chat ID, Slack channel, etc." },
+                        "channel": { "type": "string", "enum": ["telegram", "discord", "slack", "mattermost", "qq", "lark", "feishu", "email", "notify"], "description": "Channel type to deliver to (notify = push target from [notify.targets])" },
+                        "to": { "type": "string", "description": "Target: Discord channel ID, Telegram chat ID, Slack channel, push target name, etc." },
                         "best_effort": { "type": "boolean", "description": "If true, delivery failure does not fail the job" }
                     }
                 },
@@ -106,7 +110,7 @@ impl Tool for CronAddTool {
         }
 
         let schedule = match args.get("schedule") {
-            Some(v) => match serde_json::from_value::<Schedule>(v.clone()) {
+            Some(v) => match serde_json::from_value::<Schedule>(localize_schedule(v.clone())) {
                 Ok(schedule) => schedule,
                 Err(e) => {
                     return Ok(ToolResult {
@@ -133,6 +137,7 @@ impl Tool for CronAddTool {
         let job_type = match args.get("job_type").and_then(serde_json::Value::as_str) {
             Some("agent") => JobType::Agent,
             Some("shell") => JobType::Shell,
+            Some("tool") => JobType::Tool,
             Some(other) => {
                 return Ok(ToolResult {
                     success: false,
@@ -143,6 +148,8 @@ impl Tool for CronAddTool {
             None => {
                 if args.get("prompt").is_some() {
                     JobType::Agent
+                } else if args.get("tool").is_some() {
+                    JobType::Tool
                 } else {
                     JobType::Shell
                 }
@@ -159,6 +166,20 @@ impl Tool for CronAddTool {
             .and_then(serde_json::Value::as_bool)
             .unwrap_or(false);
 
+        let delivery = match args.get("delivery") {
+            Some(v) => match serde_json::from_value::<DeliveryConfig>(v.clone()) {
+                Ok(cfg) => Some(cfg),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid delivery config: {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
+
         let result = match job_type {
             JobType::Shell => {
                 let command = match args.get("command").and_then(serde_json::Value::as_str) {
@@ -217,20 +238,6 @@ impl Tool for CronAddTool {
                     .and_then(serde_json::Value::as_str)
                     .map(str::to_string);
 
-                let delivery = match args.get("delivery") {
-                    Some(v) => match serde_json::from_value::<DeliveryConfig>(v.clone()) {
-                        Ok(cfg) => Some(cfg),
-                        Err(e) => {
-                            return Ok(ToolResult {
-                                success: false,
-                                output: String::new(),
-                                error: Some(format!("Invalid delivery config: {e}")),
-                            });
-                        }
-                    },
-                    None => None,
-                };
-
                 if let Some(blocked) = self.enforce_mutation_allowed("cron_add") {
                     return Ok(blocked);
                 }
@@ -246,6 +253,40 @@ impl Tool for CronAddTool {
                     delete_after_run,
                 )
             }
+            JobType::Tool => {
+                let tool = match args.get("tool").and_then(serde_json::Value::as_str) {
+                    Some(tool) if !tool.trim().is_empty() => tool.trim(),
+                    _ => {
+                        return Ok(ToolResult {
+                            success: false,
+                            output: String::new(),
+                            error: Some("Missing 'tool' for tool job".to_string()),
+                        });
+                    }
+                };
+                let tool_args = args.get("tool_args").cloned().unwrap_or_else(|| json!({}));
+                if !tool_args.is_object() {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some("'tool_args' must be an object".to_string()),
+                    });
+                }
+
+                if let Some(blocked) = self.enforce_mutation_allowed("cron_add") {
+                    return Ok(blocked);
+                }
+
+                cron::add_tool_job(
+                    &self.config,
+                    name,
+                    schedule,
+                    tool,
+                    &tool_args,
+                    delivery,
+                    delete_after_run,
+                )
+            }
         };
 
         match result {
@@ -270,6 +311,32 @@ impl Tool for CronAddTool {
     }
 }
 
+/// Fill in the user's time zone: a cron schedule without `tz` gets the turn's
+/// time zone, and an `at` time without a UTC offset is read as local time.
+fn localize_schedule(mut schedule: serde_json::Value) -> serde_json::Value {
+    let Some(fields) = schedule.as_object_mut() else {
+        return schedule;
+    };
+    match fields.get("kind").and_then(serde_json::Value::as_str) {
+        Some("cron") if fields.get("tz").is_none_or(serde_json::Value::is_null) => {
+            if let Some(tz) = crate::i18n::profile::turn_timezone_name() {
+                fields.insert("tz".into(), json!(tz));
+            }
+        }
+        Some("at") => {
+            let at = fields
+                .get("at")
+                .and_then(serde_json::Value::as_str)
+                .and_then(crate::i18n::profile::parse_user_datetime);
+            if let Some(at) = at {
+                fields.insert("at".into(), json!(at.to_rfc3339()));
+            }
+        }
+        _ => {}
+    }
+    schedule
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -314,6 +381,63 @@ mod tests {
         assert!(result.output.contains("next_run"));
     }
 
+    #[tokio::test]
+    async fn adds_tool_job_with_arguments() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "every", "every_ms": 3_600_000 },
+                "tool": "memory_recall",
+                "tool_args": { "query": "follow-ups" }
+            }))
+            .await
+            .unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        let jobs = cron::list_jobs(&cfg).unwrap();
+        assert_eq!(jobs[0].job_type, JobType::Tool);
+        assert_eq!(jobs[0].command, "memory_recall");
+        assert_eq!(jobs[0].prompt.as_deref(), Some(r#"{"query":"follow-ups"}"#));
+    }
+
+    #[tokio::test]
+    async fn schedules_use_the_turn_time_zone() {
+        use crate::i18n::profile::{with_turn_profile, PrincipalProfile};
+
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+        let profile = PrincipalProfile {
+            timezone: Some(chrono_tz::America::New_York),
+            ..PrincipalProfile::default()
+        };
+        with_turn_profile(profile, async {
+            for schedule in [
+                json!({ "kind": "cron", "expr": "0 9 * * *" }),
+                json!({ "kind": "at", "at": "2099-01-02T09:00:00" }),
+            ] {
+                let result = tool
+                    .execute(json!({ "schedule": schedule, "command": "echo ok" }))
+                    .await
+                    .unwrap();
+                assert!(result.success, "{:?}", result.error);
+            }
+        })
+        .await;
+
+        let jobs = cron::list_jobs(&cfg).unwrap();
+        assert!(jobs.iter().any(|job| job.schedule
+            == Schedule::Cron {
+                expr: "0 9 * * *".into(),
+                tz: Some("America/New_York".into()),
+            }));
+        assert!(jobs
+            .iter()
+            .any(|job| job.next_run.to_rfc3339() == "2099-01-02T14:00:00+00:00"));
+    }
+
     #[tokio::test]
     async fn blocks_disallowed_shell_command() {
         let tmp = TempDir::new().unwrap();
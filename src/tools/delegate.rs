@@ -177,7 +177,9 @@ impl Tool for DelegateTool {
     fn description(&self) -> &str {
         "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model \
          (e.g. fast summarization, deep reasoning, code generation). The sub-agent runs a single \
-         prompt by default; with agentic=true it can iterate with a filtered tool-call loop."
+         prompt by default; with agentic=true it can iterate with a filtered tool-call loop. \
+         Pass 'tasks' to run several independent subtasks in parallel and get their results back \
+         together."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -190,7 +192,7 @@ impl Tool for DelegateTool {
                     "type": "string",
                     "minLength": 1,
                     "description": format!(
-                        "Name of the agent to delegate to. Available: {}",
+                        "Name of the agent to delegate to (required unless 'tasks' is given). Available: {}",
                         if agent_names.is_empty() {
                             "(none configured)".to_string()
                         } else {
@@ -201,18 +203,37 @@ impl Tool for DelegateTool {
                 "prompt": {
                     "type": "string",
                     "minLength": 1,
-                    "description": "The task/prompt to send to the sub-agent"
+                    "description": "The task/prompt to send to the sub-agent (required unless 'tasks' is given)"
                 },
                 "context": {
                     "type": "string",
                     "description": "Optional context to prepend (e.g. relevant code, prior findings)"
+                },
+                "tasks": {
+                    "type": "array",
+                    "minItems": 1,
+                    "description": "Run independent subtasks in parallel, each in its own sub-agent session, \
+                                    and return the aggregated results. Use instead of agent/prompt.",
+                    "items": {
+                        "type": "object",
+                        "additionalProperties": false,
+                        "properties": {
+                            "agent": { "type": "string", "minLength": 1 },
+                            "prompt": { "type": "string", "minLength": 1 },
+                            "context": { "type": "string" }
+                        },
+                        "required": ["agent", "prompt"]
+                    }
                 }
-            },
-            "required": ["agent", "prompt"]
+            }
         })
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if let Some(tasks) = args.get("tasks") {
+            return self.execute_parallel(tasks).await;
+        }
+
         let agent_name = args
             .get("agent")
             .and_then(|v| v.as_str())
@@ -247,6 +268,19 @@ impl Tool for DelegateTool {
             .map(str::trim)
             .unwrap_or("");
 
+        self.run_task(agent_name, prompt, context).await
+    }
+}
+
+impl DelegateTool {
+    /// Run one subtask in a fresh sub-agent session, within the budget the
+    /// security policy allows at this depth.
+    async fn run_task(
+        &self,
+        agent_name: &str,
+        prompt: &str,
+        context: &str,
+    ) -> anyhow::Result<ToolResult> {
         // Look up agent config
         let agent_config = match self.agents.get(agent_name) {
             Some(cfg) => cfg,
@@ -268,19 +302,28 @@ impl Tool for DelegateTool {
             }
         };
 
-        // Check recursion depth (immutable — set at construction, incremented for sub-agents)
-        if self.depth >= agent_config.max_depth {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!(
-                    "Delegation depth limit reached ({depth}/{max}). \
-                     Cannot delegate further to prevent infinite loops.",
-                    depth = self.depth,
-                    max = agent_config.max_depth
-                )),
-            });
-        }
+        // Depth is immutable — set at construction, incremented for sub-agents.
+        // The policy caps depth, iterations and tokens for every agent.
+        let budget = match self.security.delegation_budget(
+            self.depth,
+            agent_config.max_depth,
+            agent_config.max_iterations,
+            agent_config.max_tokens,
+        ) {
+            Ok(budget) => budget,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                });
+            }
+        };
+        let agent_config = &DelegateAgentConfig {
+            max_iterations: budget.max_iterations,
+            max_tokens: budget.max_tokens,
+            ..agent_config.clone()
+        };
 
         if let Err(error) = self
             .security
@@ -304,10 +347,15 @@ impl Tool for DelegateTool {
         #[allow(clippy::option_as_ref_deref)]
         let provider_credential = provider_credential_owned.as_ref().map(String::as_str);
 
+        let mut provider_runtime_options = self.provider_runtime_options.clone();
+        if budget.max_tokens.is_some() {
+            provider_runtime_options.max_tokens_override = budget.max_tokens;
+        }
+
         let provider: Box<dyn Provider> = match providers::create_provider_with_options(
             &agent_config.provider,
             provider_credential,
-            &self.provider_runtime_options,
+            &provider_runtime_options,
         ) {
             Ok(p) => p,
             Err(e) => {
@@ -434,9 +482,77 @@ impl Tool for DelegateTool {
             }
         }
     }
-}
 
-impl DelegateTool {
+    /// Fan out `tasks` to sub-agents concurrently and aggregate their results.
+    /// Each task passes the same policy checks as a single delegation.
+    async fn execute_parallel(&self, tasks: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let failure = |error: String| ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error),
+        };
+
+        let Some(items) = tasks.as_array().filter(|items| !items.is_empty()) else {
+            return Ok(failure("'tasks' must be a non-empty array".into()));
+        };
+        if let Err(error) = self.security.check_parallel_delegation(items.len()) {
+            return Ok(failure(error));
+        }
+
+        let mut parsed = Vec::with_capacity(items.len());
+        for (index, item) in items.iter().enumerate() {
+            let field = |key: &str| item.get(key).and_then(|v| v.as_str()).map(str::trim);
+            match (field("agent"), field("prompt")) {
+                (Some(agent), Some(prompt)) if !agent.is_empty() && !prompt.is_empty() => {
+                    parsed.push((agent, prompt, field("context").unwrap_or("")));
+                }
+                _ => {
+                    return Ok(failure(format!(
+                        "tasks[{index}] needs non-empty 'agent' and 'prompt'"
+                    )));
+                }
+            }
+        }
+
+        let results = futures_util::future::join_all(
+            parsed
+                .iter()
+                .map(|(agent, prompt, context)| self.run_task(agent, prompt, context)),
+        )
+        .await;
+
+        let total = parsed.len();
+        let mut succeeded = 0;
+        let mut sections = Vec::with_capacity(total);
+        for (index, ((agent, _, _), result)) in parsed.iter().zip(results).enumerate() {
+            let result = result.unwrap_or_else(|e| failure(e.to_string()));
+            let body = if result.success {
+                succeeded += 1;
+                format!("### Task {} ({agent}): ok\n{}", index + 1, result.output)
+            } else {
+                format!(
+                    "### Task {} ({agent}): failed\n{}",
+                    index + 1,
+                    result.error.as_deref().unwrap_or("unknown error")
+                )
+            };
+            sections.push(body);
+        }
+
+        let aggregated = format!(
+            "[Parallel delegation: {succeeded}/{total} tasks succeeded]\n\n{}",
+            sections.join("\n\n")
+        );
+        if succeeded == 0 {
+            return Ok(failure(aggregated));
+        }
+        Ok(ToolResult {
+            success: true,
+            output: aggregated,
+            error: None,
+        })
+    }
+
     async fn execute_agentic(
         &self,
         agent_name: &str,
@@ -797,6 +913,7 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
         agents.insert(
@@ -811,6 +928,7 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
         agents
@@ -964,6 +1082,7 @@ mod tests {
             agentic: true,
             allowed_tools,
             max_iterations,
+            max_tokens: None,
         }
     }
 
@@ -975,9 +1094,13 @@ mod tests {
         assert!(schema["properties"]["agent"].is_object());
         assert!(schema["properties"]["prompt"].is_object());
         assert!(schema["properties"]["context"].is_object());
-        let required = schema["required"].as_array().unwrap();
-        assert!(required.contains(&json!("agent")));
-        assert!(required.contains(&json!("prompt")));
+        // agent/prompt are optional at the top level so `tasks` can replace them.
+        assert!(schema.get("required").is_none());
+        let task_required = schema["properties"]["tasks"]["items"]["required"]
+            .as_array()
+            .unwrap();
+        assert!(task_required.contains(&json!("agent")));
+        assert!(task_required.contains(&json!("prompt")));
         assert_eq!(schema["additionalProperties"], json!(false));
         assert_eq!(schema["properties"]["agent"]["minLength"], json!(1));
         assert_eq!(schema["properties"]["prompt"]["minLength"], json!(1));
@@ -1047,6 +1170,73 @@ mod tests {
         assert!(result.error.unwrap().contains("depth limit"));
     }
 
+    #[tokio::test]
+    async fn policy_depth_cap_overrides_agent_max_depth() {
+        let security = Arc::new(SecurityPolicy {
+            max_delegation_depth: 1,
+            ..SecurityPolicy::default()
+        });
+        // researcher allows depth 3, but the policy stops delegation at depth 1.
+        let tool = DelegateTool::with_depth(sample_agents(), None, security, 1);
+        let result = tool
+            .execute(json!({"agent": "researcher", "prompt": "test"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("depth limit reached (1/1)"));
+    }
+
+    #[tokio::test]
+    async fn parallel_tasks_respect_policy_cap() {
+        let security = Arc::new(SecurityPolicy {
+            max_parallel_subagents: 1,
+            ..SecurityPolicy::default()
+        });
+        let tool = DelegateTool::new(sample_agents(), None, security);
+        let result = tool
+            .execute(json!({"tasks": [
+                {"agent": "researcher", "prompt": "a"},
+                {"agent": "coder", "prompt": "b"}
+            ]}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("limit is 1"));
+    }
+
+    #[tokio::test]
+    async fn parallel_tasks_reject_malformed_items() {
+        let tool = DelegateTool::new(sample_agents(), None, test_security());
+        let empty = tool.execute(json!({"tasks": []})).await.unwrap();
+        assert!(empty.error.unwrap().contains("non-empty array"));
+
+        let result = tool
+            .execute(
+                json!({"tasks": [{"agent": "researcher", "prompt": "ok"}, {"agent": "coder"}]}),
+            )
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("tasks[1]"));
+    }
+
+    #[tokio::test]
+    async fn parallel_tasks_aggregate_per_task_results() {
+        let tool = DelegateTool::new(sample_agents(), None, test_security());
+        let result = tool
+            .execute(json!({"tasks": [
+                {"agent": "nonexistent", "prompt": "a"},
+                {"agent": "ghost", "prompt": "b"}
+            ]}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        let report = result.error.unwrap();
+        assert!(report.starts_with("[Parallel delegation: 0/2 tasks succeeded]"));
+        assert!(report.contains("### Task 1 (nonexistent): failed\nUnknown agent"));
+        assert!(report.contains("### Task 2 (ghost): failed"));
+    }
+
     #[test]
     fn empty_agents_schema() {
         let tool = DelegateTool::new(HashMap::new(), None, test_security());
@@ -1072,6 +1262,7 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -1178,6 +1369,7 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -1213,6 +1405,7 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -1393,6 +1586,7 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
 
@@ -1462,6 +1656,7 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
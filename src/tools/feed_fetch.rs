@@ -0,0 +1,197 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::FeedsConfig;
+use crate::feeds::{self, FeedStore};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 50;
+
+/// Reads the feeds in `[[feeds.sources]]` on demand.
+pub struct FeedFetchTool {
+    security: Arc<SecurityPolicy>,
+    config: FeedsConfig,
+    workspace_dir: PathBuf,
+}
+
+impl FeedFetchTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: FeedsConfig, workspace_dir: PathBuf) -> Self {
+        Self {
+            security,
+            config,
+            workspace_dir,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for FeedFetchTool {
+    fn name(&self) -> &str {
+        "feed_fetch"
+    }
+
+    fn description(&self) -> &str {
+        "Fetch the latest items of a configured RSS/Atom feed. Items the feed watcher has not seen yet are marked [new]."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        let names: Vec<&str> = self
+            .config
+            .sources
+            .iter()
+            .map(|feed| feed.name.as_str())
+            .collect();
+        json!({
+            "type": "object",
+            "properties": {
+                "feed": {
+                    "type": "string",
+                    "enum": names,
+                    "description": "Name of the feed to read"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum items to return (default: 10, max: 50)",
+                    "minimum": 1,
+                    "maximum": MAX_LIMIT
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Only items whose title or summary contains this text"
+                }
+            },
+            "required": ["feed"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let name = args
+            .get("feed")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'feed' parameter"))?;
+        let Some(feed) = self.config.sources.iter().find(|feed| feed.name == name) else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Unknown feed '{name}'")),
+            });
+        };
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_LIMIT, |n| {
+                usize::try_from(n).unwrap_or(MAX_LIMIT).clamp(1, MAX_LIMIT)
+            });
+        let query = args
+            .get("query")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|query| !query.is_empty())
+            .map(|query| vec![query.to_string()])
+            .unwrap_or_default();
+
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: rate limit exceeded".into()),
+            });
+        }
+
+        let items = match feeds::fetch(feed.url.trim()).await {
+            Ok(items) => items,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("{e:#}")),
+                })
+            }
+        };
+        let seen = FeedStore::open(&self.workspace_dir)
+            .and_then(|store| store.seen(&feed.name))
+            .unwrap_or_default();
+
+        let matching: Vec<_> = items
+            .iter()
+            .filter(|item| item.matches(&query))
+            .take(limit)
+            .collect();
+        if matching.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: format!("No matching items in {name}."),
+                error: None,
+            });
+        }
+        let mut output = format!("{name}: {} item(s)", matching.len());
+        for item in matching {
+            let marker = if seen.is_empty() || seen.contains(&item.guid) {
+                ""
+            } else {
+                "[new] "
+            };
+            let _ = write!(output, "\n\n{marker}{}", item.title);
+            if let Some(published) = &item.published {
+                let _ = write!(output, "\n{published}");
+            }
+            if let Some(link) = &item.link {
+                let _ = write!(output, "\n{link}");
+            }
+            if let Some(summary) = &item.summary {
+                let _ = write!(output, "\n{summary}");
+            }
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn feed_fetch_only_reads_configured_feeds() {
+        let config: FeedsConfig = toml::from_str(
+            r#"
+[[sources]]
+name = "rust-blog"
+url = "https://blog.rust-lang.org/feed.xml"
+"#,
+        )
+        .unwrap();
+        let tool = FeedFetchTool::new(
+            Arc::new(SecurityPolicy::default()),
+            config,
+            std::env::temp_dir(),
+        );
+        assert_eq!(
+            tool.parameters_schema()["properties"]["feed"]["enum"],
+            json!(["rust-blog"])
+        );
+
+        let result = tool
+            .execute(json!({ "feed": "https://evil.example/feed" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Unknown feed"));
+        assert!(tool.execute(json!({})).await.is_err());
+    }
+}
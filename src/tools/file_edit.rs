@@ -2,14 +2,32 @@ use super::traits::{Tool, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-/// Edit a file by replacing an exact string match with new content.
+/// Workspace-relative directory holding pre-edit copies of edited files.
+const BACKUP_REL_DIR: &str = "state/file_edit_backups";
+/// Backups kept before the oldest are pruned.
+const MAX_BACKUPS: usize = 50;
+/// Lines of diff shown in approval prompts.
+const PREVIEW_MAX_LINES: usize = 60;
+/// Characters of diff shown in approval prompts, leaving room for the rest
+/// of a Telegram message (4096 characters) around it.
+const PREVIEW_MAX_CHARS: usize = 3000;
+
+/// Edit a file by exact replacement or by applying a unified diff.
 ///
-/// Uses `old_string` → `new_string` precise replacement within the workspace.
-/// The `old_string` must appear exactly once in the file (zero matches = not
-/// found, multiple matches = ambiguous). `new_string` may be empty to delete
-/// the matched text. Security checks mirror [`super::file_write::FileWriteTool`].
+/// Search/replace: `old_string` must appear exactly once in the file (zero
+/// matches = not found, multiple matches = ambiguous); `new_string` may be
+/// empty to delete the matched text. Diff: every hunk of `diff` must match
+/// the file's current lines, at its stated position or at exactly one other
+/// place. Either way the result is reported as a unified diff, `dry_run`
+/// stops before writing, and writes go through a temp file that replaces
+/// the original atomically after a copy of it is saved under
+/// `state/file_edit_backups/`. Security checks mirror
+/// [`super::file_write::FileWriteTool`].
 pub struct FileEditTool {
     security: Arc<SecurityPolicy>,
 }
@@ -20,6 +38,303 @@ impl FileEditTool {
     }
 }
 
+/// Requested change.
+enum EditSpec<'a> {
+    Replace { old: &'a str, new: &'a str },
+    Diff(&'a str),
+}
+
+impl EditSpec<'_> {
+    /// New file content and a one-line summary.
+    fn apply(&self, content: &str) -> Result<(String, String), String> {
+        match self {
+            Self::Replace { old, new } => {
+                let match_count = content.matches(old).count();
+                if match_count == 0 {
+                    return Err("old_string not found in file".into());
+                }
+                if match_count > 1 {
+                    return Err(format!(
+                        "old_string matches {match_count} times; must match exactly once"
+                    ));
+                }
+                Ok((
+                    content.replacen(old, new, 1),
+                    "replaced 1 occurrence".into(),
+                ))
+            }
+            Self::Diff(diff) => {
+                let hunks = parse_hunks(diff)?;
+                let updated = apply_hunks(content, &hunks)?;
+                Ok((updated, format!("applied {} hunk(s)", hunks.len())))
+            }
+        }
+    }
+}
+
+/// One `@@` section of a unified diff.
+struct Hunk {
+    old_start: usize,
+    lines: Vec<(char, String)>,
+}
+
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    let (start, len) = range.split_once(',').unwrap_or((range, "1"));
+    Some((start.parse().ok()?, len.parse().ok()?))
+}
+
+/// Parse the hunks of a single-file unified diff. File headers are ignored.
+fn parse_hunks(diff: &str) -> Result<Vec<Hunk>, String> {
+    let mut hunks = Vec::new();
+    let mut remaining = (0usize, 0usize);
+    for line in diff.lines() {
+        if remaining == (0, 0) {
+            let Some(header) = line.strip_prefix("@@ ") else {
+                continue;
+            };
+            let mut ranges = header.split_whitespace();
+            let old = ranges.next().and_then(|r| r.strip_prefix('-'));
+            let new = ranges.next().and_then(|r| r.strip_prefix('+'));
+            let (Some((old_start, old_len)), Some((_, new_len))) =
+                (old.and_then(parse_range), new.and_then(parse_range))
+            else {
+                return Err(format!("malformed hunk header: {line}"));
+            };
+            hunks.push(Hunk {
+                old_start,
+                lines: Vec::new(),
+            });
+            remaining = (old_len, new_len);
+            continue;
+        }
+
+        let (kind, text) = match line.chars().next() {
+            // Editors often strip the single space of blank context lines.
+            None => (' ', ""),
+            Some(kind @ (' ' | '-' | '+')) => (kind, &line[1..]),
+            Some('\\') => continue,
+            Some(_) => return Err(format!("unexpected line in hunk: {line}")),
+        };
+        if kind != '+' {
+            remaining.0 = remaining
+                .0
+                .checked_sub(1)
+                .ok_or("hunk longer than its header")?;
+        }
+        if kind != '-' {
+            remaining.1 = remaining
+                .1
+                .checked_sub(1)
+                .ok_or("hunk longer than its header")?;
+        }
+        if let Some(hunk) = hunks.last_mut() {
+            hunk.lines.push((kind, text.to_string()));
+        }
+    }
+
+    if remaining != (0, 0) {
+        return Err("diff ends in the middle of a hunk".into());
+    }
+    if hunks.is_empty() {
+        return Err("diff contains no hunks".into());
+    }
+    Ok(hunks)
+}
+
+/// Apply `hunks` in order. Each must match at its stated line or at exactly
+/// one other place after the previous hunk.
+fn apply_hunks(content: &str, hunks: &[Hunk]) -> Result<String, String> {
+    let ending = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let original: Vec<&str> = content.lines().collect();
+    let mut out: Vec<&str> = Vec::with_capacity(original.len());
+    let mut cursor = 0;
+
+    for (index, hunk) in hunks.iter().enumerate() {
+        let old: Vec<&str> = hunk
+            .lines
+            .iter()
+            .filter(|(kind, _)| *kind != '+')
+            .map(|(_, text)| text.as_str())
+            .collect();
+        let matches_at = |pos: usize| {
+            original
+                .get(pos..pos + old.len())
+                .is_some_and(|window| window == old.as_slice())
+        };
+
+        let hint = hunk.old_start.saturating_sub(1);
+        let pos = if hint >= cursor && matches_at(hint) {
+            hint
+        } else {
+            let candidates: Vec<usize> = (cursor..=original.len().saturating_sub(old.len()))
+                .filter(|&pos| matches_at(pos))
+                .collect();
+            match candidates.as_slice() {
+                [pos] => *pos,
+                [] => {
+                    return Err(format!(
+                        "hunk {} does not apply: lines not found",
+                        index + 1
+                    ))
+                }
+                _ => {
+                    return Err(format!(
+                        "hunk {} is ambiguous: its lines match {} places",
+                        index + 1,
+                        candidates.len()
+                    ))
+                }
+            }
+        };
+
+        out.extend(&original[cursor..pos]);
+        out.extend(
+            hunk.lines
+                .iter()
+                .filter(|(kind, _)| *kind != '-')
+                .map(|(_, text)| text.as_str()),
+        );
+        cursor = pos + old.len();
+    }
+    out.extend(&original[cursor..]);
+
+    let mut updated = out.join(ending);
+    if content.ends_with('\n') && !updated.is_empty() {
+        updated.push_str(ending);
+    }
+    Ok(updated)
+}
+
+/// Unified diff of `old` → `new` as a single hunk with three lines of
+/// context. Empty when nothing changed.
+fn render_diff(path: &str, old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    if old_lines == new_lines {
+        return String::new();
+    }
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let start = prefix.saturating_sub(3);
+    let old_end = (old_lines.len() - suffix + 3).min(old_lines.len());
+    let new_end = (new_lines.len() - suffix + 3).min(new_lines.len());
+    let hunk_start = |len: usize| if len == 0 { start } else { start + 1 };
+
+    let mut diff = format!(
+        "--- a/{path}\n+++ b/{path}\n@@ -{},{} +{},{} @@\n",
+        hunk_start(old_end - start),
+        old_end - start,
+        hunk_start(new_end - start),
+        new_end - start
+    );
+    for line in &old_lines[start..prefix] {
+        let _ = writeln!(diff, " {line}");
+    }
+    for line in &old_lines[prefix..old_lines.len() - suffix] {
+        let _ = writeln!(diff, "-{line}");
+    }
+    for line in &new_lines[prefix..new_lines.len() - suffix] {
+        let _ = writeln!(diff, "+{line}");
+    }
+    for line in &old_lines[old_lines.len() - suffix..old_end] {
+        let _ = writeln!(diff, " {line}");
+    }
+    diff
+}
+
+/// Diff shown in approval prompts for a `file_edit` call, built from its
+/// arguments without reading the file.
+pub fn approval_preview(args: &serde_json::Value) -> Option<String> {
+    let path = args.get("path").and_then(|v| v.as_str()).unwrap_or("?");
+    let preview = if let Some(diff) = args.get("diff").and_then(|v| v.as_str()) {
+        diff.to_string()
+    } else {
+        let old = args.get("old_string").and_then(|v| v.as_str())?;
+        let new = args.get("new_string").and_then(|v| v.as_str())?;
+        let mut preview = format!("--- a/{path}\n+++ b/{path}\n");
+        for line in old.lines() {
+            let _ = writeln!(preview, "-{line}");
+        }
+        for line in new.lines() {
+            let _ = writeln!(preview, "+{line}");
+        }
+        preview
+    };
+
+    let total = preview.lines().count();
+    let mut shown = preview
+        .lines()
+        .take(PREVIEW_MAX_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let more = total.saturating_sub(PREVIEW_MAX_LINES);
+    if let Some((cut, _)) = shown.char_indices().nth(PREVIEW_MAX_CHARS) {
+        shown.truncate(cut);
+        shown.push_str("\n... preview truncated");
+    } else if more > 0 {
+        let _ = write!(shown, "\n... {more} more line(s)");
+    }
+    Some(shown)
+}
+
+/// Copy `target` into `backup_dir`, then replace it with `content` through a
+/// temp file in the same directory. The original is left untouched if any
+/// step fails. Returns the backup path.
+fn write_with_backup(target: &Path, content: &str, backup_dir: &Path) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(backup_dir)?;
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let backup = backup_dir.join(format!(
+        "{}-{file_name}",
+        chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ")
+    ));
+    std::fs::copy(target, &backup)?;
+
+    let dir = target.parent().unwrap_or_else(|| Path::new("."));
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+    tmp.write_all(content.as_bytes())?;
+    tmp.as_file().sync_all()?;
+    if let Ok(meta) = std::fs::metadata(target) {
+        tmp.as_file().set_permissions(meta.permissions())?;
+    }
+    tmp.persist(target).map_err(|e| e.error)?;
+
+    prune_backups(backup_dir);
+    Ok(backup)
+}
+
+/// Keep only the newest [`MAX_BACKUPS`] backups. Names start with a UTC
+/// timestamp, so name order is age order.
+fn prune_backups(backup_dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(backup_dir) else {
+        return;
+    };
+    let mut names: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    if names.len() <= MAX_BACKUPS {
+        return;
+    }
+    names.sort();
+    for path in &names[..names.len() - MAX_BACKUPS] {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
 #[async_trait]
 impl Tool for FileEditTool {
     fn name(&self) -> &str {
@@ -27,7 +342,7 @@ impl Tool for FileEditTool {
     }
 
     fn description(&self) -> &str {
-        "Edit a file by replacing an exact string match with new content"
+        "Edit a file by replacing an exact string match or applying a unified diff; returns the resulting diff"
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -40,14 +355,23 @@ impl Tool for FileEditTool {
                 },
                 "old_string": {
                     "type": "string",
-                    "description": "The exact text to find and replace (must appear exactly once in the file)"
+                    "description": "The exact text to find and replace (must appear exactly once in the file). Required unless 'diff' is given."
                 },
                 "new_string": {
                     "type": "string",
-                    "description": "The replacement text (empty string to delete the matched text)"
+                    "description": "The replacement text (empty string to delete the matched text). Required unless 'diff' is given."
+                },
+                "diff": {
+                    "type": "string",
+                    "description": "Unified diff hunks (@@ -a,b +c,d @@) to apply to this file instead of old_string/new_string"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Check that the edit applies and return the diff without writing",
+                    "default": false
                 }
             },
-            "required": ["path", "old_string", "new_string"]
+            "required": ["path"]
         })
     }
 
@@ -58,23 +382,35 @@ impl Tool for FileEditTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
 
-        let old_string = args
-            .get("old_string")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing 'old_string' parameter"))?;
+        let spec = if let Some(diff) = args.get("diff").and_then(|v| v.as_str()) {
+            EditSpec::Diff(diff)
+        } else {
+            let old_string = args
+                .get("old_string")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow::anyhow!("Missing 'old_string' parameter"))?;
 
-        let new_string = args
-            .get("new_string")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing 'new_string' parameter"))?;
+            let new_string = args
+                .get("new_string")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow::anyhow!("Missing 'new_string' parameter"))?;
 
-        if old_string.is_empty() {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("old_string must not be empty".into()),
-            });
-        }
+            if old_string.is_empty() {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("old_string must not be empty".into()),
+                });
+            }
+            EditSpec::Replace {
+                old: old_string,
+                new: new_string,
+            }
+        };
+        let dry_run = args
+            .get("dry_run")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
 
         // ── 2. Autonomy check ──────────────────────────────────────
         if !self.security.can_act() {
@@ -126,14 +462,14 @@ impl Tool for FileEditTool {
         };
 
         // ── 6. Resolved path post-validation ───────────────────────
-        if !self.security.is_resolved_path_allowed(&resolved_parent) {
+        if let Err(reason) = self
+            .security
+            .check_tool_resolved_path(self.name(), &resolved_parent)
+        {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(
-                    self.security
-                        .resolved_path_violation_message(&resolved_parent),
-                ),
+                error: Some(reason),
             });
         }
 
@@ -161,16 +497,7 @@ impl Tool for FileEditTool {
             }
         }
 
-        // ── 8. Record action ───────────────────────────────────────
-        if !self.security.record_action() {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("Rate limit exceeded: action budget exhausted".into()),
-            });
-        }
-
-        // ── 9. Read → match → replace → write ─────────────────────
+        // ── 8. Read → apply ───────────────────────────────────────
         let content = match tokio::fs::read_to_string(&resolved_target).await {
             Ok(c) => c,
             Err(e) => {
@@ -182,37 +509,59 @@ impl Tool for FileEditTool {
             }
         };
 
-        let match_count = content.matches(old_string).count();
+        let (new_content, summary) = match spec.apply(&content) {
+            Ok(applied) => applied,
+            Err(reason) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(reason),
+                });
+            }
+        };
+        let diff = render_diff(path, &content, &new_content);
 
-        if match_count == 0 {
+        if dry_run {
             return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("old_string not found in file".into()),
+                success: true,
+                output: format!("Dry run: the edit applies cleanly to {path} ({summary}); nothing was written.\n\n{diff}"),
+                error: None,
             });
         }
 
-        if match_count > 1 {
+        // ── 9. Record action ───────────────────────────────────────
+        if !self.security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(format!(
-                    "old_string matches {match_count} times; must match exactly once"
-                )),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
             });
         }
 
-        let new_content = content.replacen(old_string, new_string, 1);
-
-        match tokio::fs::write(&resolved_target, &new_content).await {
-            Ok(()) => Ok(ToolResult {
+        // ── 10. Back up → write atomically ─────────────────────────
+        let backup_dir = self.security.workspace_dir.join(BACKUP_REL_DIR);
+        super::undo::record_file_change(&resolved_target).await;
+        let target = resolved_target.clone();
+        let written = new_content.clone();
+        let write =
+            tokio::task::spawn_blocking(move || write_with_backup(&target, &written, &backup_dir))
+                .await;
+
+        match write {
+            Ok(Ok(backup)) => Ok(ToolResult {
                 success: true,
                 output: format!(
-                    "Edited {path}: replaced 1 occurrence ({} bytes)",
-                    new_content.len()
+                    "Edited {path}: {summary} ({} bytes). Previous version saved to {}.\n\n{diff}",
+                    new_content.len(),
+                    backup.display()
                 ),
                 error: None,
             }),
+            Ok(Err(e)) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to write file: {e}")),
+            }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -261,10 +610,91 @@ mod tests {
         assert!(schema["properties"]["path"].is_object());
         assert!(schema["properties"]["old_string"].is_object());
         assert!(schema["properties"]["new_string"].is_object());
+        assert!(schema["properties"]["diff"].is_object());
         let required = schema["required"].as_array().unwrap();
-        assert!(required.contains(&json!("path")));
-        assert!(required.contains(&json!("old_string")));
-        assert!(required.contains(&json!("new_string")));
+        assert_eq!(required, &vec![json!("path")]);
+    }
+
+    #[tokio::test]
+    async fn file_edit_applies_diff_with_backup() {
+        let dir = tempfile::TempDir::new().unwrap();
+        tokio::fs::write(dir.path().join("notes.md"), "one\ntwo\nthree\nfour\n")
+            .await
+            .unwrap();
+        let tool = FileEditTool::new(test_security(dir.path().to_path_buf()));
+        let diff = "--- a/notes.md\n+++ b/notes.md\n@@ -2,2 +2,2 @@\n two\n-three\n+THREE\n";
+
+        let preview = tool
+            .execute(json!({"path": "notes.md", "diff": diff, "dry_run": true}))
+            .await
+            .unwrap();
+        assert!(preview.success, "{:?}", preview.error);
+        assert!(preview.output.contains("-three\n+THREE"));
+        assert_eq!(
+            tokio::fs::read_to_string(dir.path().join("notes.md"))
+                .await
+                .unwrap(),
+            "one\ntwo\nthree\nfour\n"
+        );
+
+        let result = tool
+            .execute(json!({"path": "notes.md", "diff": diff}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("applied 1 hunk(s)"));
+        assert_eq!(
+            tokio::fs::read_to_string(dir.path().join("notes.md"))
+                .await
+                .unwrap(),
+            "one\ntwo\nTHREE\nfour\n"
+        );
+        let backups: Vec<_> = std::fs::read_dir(dir.path().join(BACKUP_REL_DIR))
+            .unwrap()
+            .flatten()
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(backups[0].path()).unwrap(),
+            "one\ntwo\nthree\nfour\n"
+        );
+
+        // The same diff no longer applies.
+        let stale = tool
+            .execute(json!({"path": "notes.md", "diff": diff}))
+            .await
+            .unwrap();
+        assert!(!stale.success);
+        assert!(stale.error.unwrap().contains("does not apply"));
+    }
+
+    #[test]
+    fn hunks_relocate_to_a_unique_match_and_keep_crlf() {
+        let hunks = parse_hunks("@@ -10,1 +10,2 @@\n b\n+c\n").unwrap();
+        assert_eq!(
+            apply_hunks("a\r\nb\r\nd\r\n", &hunks).unwrap(),
+            "a\r\nb\r\nc\r\nd\r\n"
+        );
+        assert!(apply_hunks("b\nb\n", &hunks)
+            .unwrap_err()
+            .contains("ambiguous"));
+        assert!(parse_hunks("@@ -1,2 +1,2 @@\n a\n").is_err());
+        assert_eq!(
+            approval_preview(&json!({"path": "f.txt", "old_string": "a", "new_string": "b"}))
+                .unwrap(),
+            "--- a/f.txt\n+++ b/f.txt\n-a\n+b"
+        );
+    }
+
+    #[test]
+    fn approval_preview_caps_long_lines_by_characters() {
+        let minified = "é".repeat(PREVIEW_MAX_CHARS * 2);
+        let preview = approval_preview(
+            &json!({"path": "app.min.js", "old_string": "x", "new_string": minified}),
+        )
+        .unwrap();
+        assert!(preview.ends_with("... preview truncated"));
+        assert!(preview.chars().count() <= PREVIEW_MAX_CHARS + "\n... preview truncated".len());
     }
 
     #[tokio::test]
@@ -96,14 +96,14 @@ impl Tool for FileReadTool {
             }
         };
 
-        if !self.security.is_resolved_path_allowed(&resolved_path) {
+        if let Err(reason) = self
+            .security
+            .check_tool_resolved_path(self.name(), &resolved_path)
+        {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(
-                    self.security
-                        .resolved_path_violation_message(&resolved_path),
-                ),
+                error: Some(reason),
             });
         }
 
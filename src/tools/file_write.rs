@@ -103,14 +103,14 @@ impl Tool for FileWriteTool {
             }
         };
 
-        if !self.security.is_resolved_path_allowed(&resolved_parent) {
+        if let Err(reason) = self
+            .security
+            .check_tool_resolved_path(self.name(), &resolved_parent)
+        {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(
-                    self.security
-                        .resolved_path_violation_message(&resolved_parent),
-                ),
+                error: Some(reason),
             });
         }
 
@@ -146,6 +146,7 @@ impl Tool for FileWriteTool {
             });
         }
 
+        super::undo::record_file_change(&resolved_target).await;
         match tokio::fs::write(&resolved_target, content).await {
             Ok(()) => Ok(ToolResult {
                 success: true,
@@ -9,6 +9,7 @@ use std::sync::Arc;
 pub struct GitOperationsTool {
     security: Arc<SecurityPolicy>,
     workspace_dir: std::path::PathBuf,
+    confined: bool,
 }
 
 impl GitOperationsTool {
@@ -16,11 +17,53 @@ impl GitOperationsTool {
         Self {
             security,
             workspace_dir,
+            confined: false,
         }
     }
 
+    /// Operations for the single-purpose workspace git tools: the repository
+    /// root must lie inside the workspace, and hooks, fsmonitor commands and
+    /// credential prompts are disabled for every git invocation.
+    pub(super) fn confined(
+        security: Arc<SecurityPolicy>,
+        workspace_dir: std::path::PathBuf,
+    ) -> Self {
+        Self {
+            security,
+            workspace_dir,
+            confined: true,
+        }
+    }
+
+    pub(super) fn security(&self) -> &SecurityPolicy {
+        &self.security
+    }
+
+    /// Ensure the repository containing the workspace is rooted inside it,
+    /// so a workspace nested in a larger checkout cannot touch that checkout.
+    pub(super) async fn check_workspace_repo(&self) -> Result<(), String> {
+        let toplevel = self
+            .run_git_command(&["rev-parse", "--show-toplevel"])
+            .await
+            .map_err(|_| "Workspace is not inside a git repository".to_string())?;
+        let toplevel = std::path::Path::new(toplevel.trim());
+        let (Ok(toplevel), Ok(workspace)) = (
+            std::fs::canonicalize(toplevel),
+            std::fs::canonicalize(&self.workspace_dir),
+        ) else {
+            return Err("Failed to resolve the workspace repository".into());
+        };
+        if !toplevel.starts_with(&workspace) {
+            return Err(format!(
+                "Repository root {} is outside the workspace; run `git init` in the workspace to use git tools",
+                toplevel.display()
+            ));
+        }
+        Ok(())
+    }
+
     /// Sanitize git arguments to prevent injection attacks
-    fn sanitize_git_args(&self, args: &str) -> anyhow::Result<Vec<String>> {
+    pub(super) fn sanitize_git_args(&self, args: &str) -> anyhow::Result<Vec<String>> {
         let mut result = Vec::new();
         for arg in args.split_whitespace() {
             // Block dangerous git options that could lead to command injection
@@ -65,8 +108,19 @@ impl GitOperationsTool {
         )
     }
 
-    async fn run_git_command(&self, args: &[&str]) -> anyhow::Result<String> {
-        let output = tokio::process::Command::new("git")
+    pub(super) async fn run_git_command(&self, args: &[&str]) -> anyhow::Result<String> {
+        let mut command = tokio::process::Command::new("git");
+        if self.confined {
+            command
+                .args([
+                    "-c",
+                    "core.hooksPath=/dev/null",
+                    "-c",
+                    "core.fsmonitor=false",
+                ])
+                .env("GIT_TERMINAL_PROMPT", "0");
+        }
+        let output = command
             .args(args)
             .current_dir(&self.workspace_dir)
             .output()
@@ -80,7 +134,7 @@ impl GitOperationsTool {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 
-    async fn git_status(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+    pub(super) async fn git_status(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let output = self
             .run_git_command(&["status", "--porcelain=2", "--branch"])
             .await?;
@@ -131,7 +185,7 @@ impl GitOperationsTool {
         })
     }
 
-    async fn git_diff(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+    pub(super) async fn git_diff(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let files = args.get("files").and_then(|v| v.as_str()).unwrap_or(".");
         let cached = args
             .get("cached")
@@ -247,7 +301,7 @@ impl GitOperationsTool {
         })
     }
 
-    async fn git_branch(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+    pub(super) async fn git_branch(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let output = self
             .run_git_command(&["branch", "--format=%(refname:short)|%(HEAD)"])
             .await?;
@@ -287,7 +341,7 @@ impl GitOperationsTool {
         }
     }
 
-    async fn git_commit(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+    pub(super) async fn git_commit(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let message = args
             .get("message")
             .and_then(|v| v.as_str())
@@ -311,11 +365,16 @@ impl GitOperationsTool {
         let output = self.run_git_command(&["commit", "-m", &message]).await;
 
         match output {
-            Ok(_) => Ok(ToolResult {
-                success: true,
-                output: format!("Committed: {message}"),
-                error: None,
-            }),
+            Ok(_) => {
+                if let Ok(head) = self.run_git_command(&["rev-parse", "HEAD"]).await {
+                    super::undo::record_git_commit(&self.workspace_dir, &head);
+                }
+                Ok(ToolResult {
+                    success: true,
+                    output: format!("Committed: {message}"),
+                    error: None,
+                })
+            }
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -526,7 +585,7 @@ impl Tool for GitOperationsTool {
                 });
             }
 
-            match self.security.autonomy {
+            match self.security.effective_autonomy() {
                 AutonomyLevel::ReadOnly => {
                     return Ok(ToolResult {
                         success: false,
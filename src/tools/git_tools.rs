@@ -0,0 +1,358 @@
+use super::git_operations::GitOperationsTool;
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GitToolKind {
+    Status,
+    Diff,
+    Commit,
+    Branch,
+}
+
+/// Single-purpose git tools (`git_status`, `git_diff`, `git_commit`,
+/// `git_branch`) for reviewing and committing what the file tools changed,
+/// without shell access.
+///
+/// They run the git CLI on the workspace repository only: the repository
+/// root must be inside the workspace, paths go through the security policy,
+/// arguments are sanitized like [`GitOperationsTool`], and repository hooks
+/// and fsmonitor commands are disabled.
+pub struct WorkspaceGitTool {
+    kind: GitToolKind,
+    git: GitOperationsTool,
+}
+
+impl WorkspaceGitTool {
+    fn new(kind: GitToolKind, security: Arc<SecurityPolicy>, workspace_dir: &Path) -> Self {
+        Self {
+            kind,
+            git: GitOperationsTool::confined(security, workspace_dir.to_path_buf()),
+        }
+    }
+
+    /// All four workspace git tools.
+    pub fn all(security: &Arc<SecurityPolicy>, workspace_dir: &Path) -> Vec<Self> {
+        [
+            GitToolKind::Status,
+            GitToolKind::Diff,
+            GitToolKind::Commit,
+            GitToolKind::Branch,
+        ]
+        .into_iter()
+        .map(|kind| Self::new(kind, security.clone(), workspace_dir))
+        .collect()
+    }
+
+    fn failure(error: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error.into()),
+        }
+    }
+
+    /// Reject option-like or policy-blocked paths.
+    fn check_paths<'a>(&self, paths: impl IntoIterator<Item = &'a str>) -> Result<(), String> {
+        for path in paths {
+            if path.starts_with('-') || !self.git.security().is_path_allowed(path) {
+                return Err(format!("Path not allowed by security policy: {path}"));
+            }
+        }
+        Ok(())
+    }
+
+    async fn diff(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let files = args.get("files").and_then(|v| v.as_str()).unwrap_or(".");
+        if let Err(reason) = self.check_paths([files]) {
+            return Ok(Self::failure(reason));
+        }
+        self.git.git_diff(args).await
+    }
+
+    async fn commit(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let paths: Vec<&str> = args
+            .get("paths")
+            .and_then(|v| v.as_array())
+            .map(|items| items.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        let stage_all = args.get("all").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        if let Err(reason) = self.check_paths(paths.iter().copied()) {
+            return Ok(Self::failure(reason));
+        }
+        for path in &paths {
+            self.git.sanitize_git_args(path)?;
+        }
+
+        let mut add_args = vec!["add"];
+        if stage_all {
+            add_args.extend(["-A", "--", "."]);
+        } else if !paths.is_empty() {
+            add_args.push("--");
+            add_args.extend(paths.iter().copied());
+        }
+        if add_args.len() > 1 {
+            if let Err(e) = self.git.run_git_command(&add_args).await {
+                return Ok(Self::failure(format!("Add failed: {e}")));
+            }
+        }
+
+        let mut result = self.git.git_commit(args).await?;
+        if result.success {
+            if let Ok(hash) = self
+                .git
+                .run_git_command(&["rev-parse", "--short", "HEAD"])
+                .await
+            {
+                result.output = format!("{} ({})", result.output, hash.trim());
+            }
+        }
+        Ok(result)
+    }
+
+    async fn branch(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list");
+        if action == "list" {
+            return self.git.git_branch(args).await;
+        }
+
+        let Some(name) = args.get("name").and_then(|v| v.as_str()) else {
+            return Ok(Self::failure(format!(
+                "'{action}' requires a branch 'name'"
+            )));
+        };
+        if name.starts_with('-')
+            || self
+                .git
+                .run_git_command(&["check-ref-format", "--branch", name])
+                .await
+                .is_err()
+        {
+            return Ok(Self::failure(format!("Invalid branch name: {name}")));
+        }
+
+        let (git_args, done) = match action {
+            "create" => (vec!["branch", name], format!("Created branch: {name}")),
+            "switch" => (vec!["switch", name], format!("Switched to branch: {name}")),
+            _ => {
+                return Ok(Self::failure(format!(
+                    "Unknown branch action: {action}. Use: list, create, switch"
+                )))
+            }
+        };
+        match self.git.run_git_command(&git_args).await {
+            Ok(_) => Ok(ToolResult {
+                success: true,
+                output: done,
+                error: None,
+            }),
+            Err(e) => Ok(Self::failure(format!("Branch {action} failed: {e}"))),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for WorkspaceGitTool {
+    fn name(&self) -> &str {
+        match self.kind {
+            GitToolKind::Status => "git_status",
+            GitToolKind::Diff => "git_diff",
+            GitToolKind::Commit => "git_commit",
+            GitToolKind::Branch => "git_branch",
+        }
+    }
+
+    fn description(&self) -> &str {
+        match self.kind {
+            GitToolKind::Status => {
+                "Show the workspace git repository status (branch, staged, unstaged, untracked) as JSON"
+            }
+            GitToolKind::Diff => "Show workspace changes as parsed diff hunks (staged with cached=true)",
+            GitToolKind::Commit => {
+                "Commit workspace changes, optionally staging the given paths (or all changes) first"
+            }
+            GitToolKind::Branch => "List, create, or switch branches in the workspace git repository",
+        }
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        match self.kind {
+            GitToolKind::Status => json!({ "type": "object", "properties": {} }),
+            GitToolKind::Diff => json!({
+                "type": "object",
+                "properties": {
+                    "files": {
+                        "type": "string",
+                        "description": "File or directory to diff (default: '.')"
+                    },
+                    "cached": {
+                        "type": "boolean",
+                        "description": "Show staged changes instead of unstaged ones",
+                        "default": false
+                    }
+                }
+            }),
+            GitToolKind::Commit => json!({
+                "type": "object",
+                "properties": {
+                    "message": {
+                        "type": "string",
+                        "description": "Commit message"
+                    },
+                    "paths": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Workspace paths to stage before committing"
+                    },
+                    "all": {
+                        "type": "boolean",
+                        "description": "Stage every change in the workspace (including new files) before committing",
+                        "default": false
+                    }
+                },
+                "required": ["message"]
+            }),
+            GitToolKind::Branch => json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["list", "create", "switch"],
+                        "default": "list"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Branch name (for 'create' and 'switch')"
+                    }
+                }
+            }),
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let writes = match self.kind {
+            GitToolKind::Status | GitToolKind::Diff => false,
+            GitToolKind::Commit => true,
+            GitToolKind::Branch => {
+                args.get("action")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("list")
+                    != "list"
+            }
+        };
+        if writes && !self.git.security().can_act() {
+            return Ok(Self::failure(
+                "Action blocked: git write operations require higher autonomy level",
+            ));
+        }
+
+        if let Err(reason) = self.git.check_workspace_repo().await {
+            return Ok(Self::failure(reason));
+        }
+
+        if !self.git.security().record_action() {
+            return Ok(Self::failure("Action blocked: rate limit exceeded"));
+        }
+
+        match self.kind {
+            GitToolKind::Status => self.git.git_status(args).await,
+            GitToolKind::Diff => self.diff(args).await,
+            GitToolKind::Commit => self.commit(args).await,
+            GitToolKind::Branch => self.branch(args).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn tool(dir: &Path, kind: GitToolKind, autonomy: AutonomyLevel) -> WorkspaceGitTool {
+        let security = Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: dir.to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        WorkspaceGitTool::new(kind, security, dir)
+    }
+
+    #[tokio::test]
+    async fn commit_stages_paths_and_ignores_hooks() {
+        let tmp = TempDir::new().unwrap();
+        let ws = tmp.path();
+        git(ws, &["init", "-q"]);
+        git(ws, &["config", "user.email", "dev@example.com"]);
+        git(ws, &["config", "user.name", "Dev"]);
+        std::fs::write(ws.join(".git/hooks/pre-commit"), "#!/bin/sh\nexit 1\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(
+                ws.join(".git/hooks/pre-commit"),
+                std::fs::Permissions::from_mode(0o755),
+            )
+            .unwrap();
+        }
+        std::fs::write(ws.join("notes.md"), "hello\n").unwrap();
+
+        let commit = tool(ws, GitToolKind::Commit, AutonomyLevel::Supervised);
+        let result = commit
+            .execute(json!({"message": "Add notes", "paths": ["notes.md"]}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.starts_with("Committed: Add notes ("));
+
+        let blocked = commit
+            .execute(json!({"message": "x", "paths": ["/etc/passwd"]}))
+            .await
+            .unwrap();
+        assert!(blocked.error.unwrap().contains("not allowed"));
+
+        let status = tool(ws, GitToolKind::Status, AutonomyLevel::ReadOnly)
+            .execute(json!({}))
+            .await
+            .unwrap();
+        assert!(status.output.contains("\"clean\": true"));
+
+        let read_only_branch = tool(ws, GitToolKind::Branch, AutonomyLevel::ReadOnly)
+            .execute(json!({"action": "create", "name": "feature"}))
+            .await
+            .unwrap();
+        assert!(read_only_branch.error.unwrap().contains("higher autonomy"));
+    }
+
+    #[tokio::test]
+    async fn refuses_repository_rooted_outside_workspace() {
+        let tmp = TempDir::new().unwrap();
+        git(tmp.path(), &["init", "-q"]);
+        let ws = tmp.path().join("workspace");
+        std::fs::create_dir_all(&ws).unwrap();
+
+        let result = tool(&ws, GitToolKind::Status, AutonomyLevel::Supervised)
+            .execute(json!({}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("outside the workspace"));
+    }
+}
@@ -125,8 +125,12 @@ impl Tool for GlobSearchTool {
                 Err(_) => continue, // skip broken symlinks / unresolvable paths
             };
 
-            if !self.security.is_resolved_path_allowed(&resolved) {
-                continue; // silently filter symlink escapes
+            if self
+                .security
+                .check_tool_resolved_path(self.name(), &resolved)
+                .is_err()
+            {
+                continue; // silently filter symlink escapes and out-of-scope paths
             }
 
             // Only include files, not directories
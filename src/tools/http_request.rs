@@ -1,9 +1,7 @@
 use super::traits::{Tool, ToolResult};
-use super::url_validation::{
-    normalize_allowed_domains, validate_url, DomainPolicy, UrlSchemePolicy,
-};
+use super::url_validation::{normalize_allowed_domains, DomainPolicy, UrlSchemePolicy};
 use crate::config::UrlAccessConfig;
-use crate::security::SecurityPolicy;
+use crate::security::{EgressPolicy, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -14,7 +12,7 @@ use std::time::Duration;
 pub struct HttpRequestTool {
     security: Arc<SecurityPolicy>,
     allowed_domains: Vec<String>,
-    url_access: UrlAccessConfig,
+    egress: Arc<EgressPolicy>,
     max_response_size: usize,
     timeout_secs: u64,
     user_agent: String,
@@ -32,15 +30,23 @@ impl HttpRequestTool {
         Self {
             security,
             allowed_domains: normalize_allowed_domains(allowed_domains),
-            url_access,
+            egress: Arc::new(EgressPolicy::from_url_access(url_access)),
             max_response_size,
             timeout_secs,
             user_agent,
         }
     }
 
+    /// Share one [`EgressPolicy`] with the other network tools so rate limits
+    /// and counters are tracked across all of them.
+    pub fn with_egress_policy(mut self, egress: Arc<EgressPolicy>) -> Self {
+        self.egress = egress;
+        self
+    }
+
     fn validate_url(&self, raw_url: &str) -> anyhow::Result<String> {
-        validate_url(
+        self.egress.authorize(
+            "http_request",
             raw_url,
             &DomainPolicy {
                 allowed_domains: &self.allowed_domains,
@@ -50,7 +56,7 @@ impl HttpRequestTool {
                 empty_allowed_message: "HTTP request tool is enabled but no allowed_domains are configured. Add [http_request].allowed_domains in config.toml",
                 scheme_policy: UrlSchemePolicy::HttpOrHttps,
                 ipv6_error_context: "http_request",
-                url_access: Some(&self.url_access),
+                url_access: None,
             },
         )
     }
@@ -136,9 +136,8 @@ impl ImageInfoTool {
             .canonicalize()
             .map_err(|_| format!("File not found: {path_str}"))?;
 
-        if !self.security.is_resolved_path_allowed(&resolved) {
-            return Err(self.security.resolved_path_violation_message(&resolved));
-        }
+        self.security
+            .check_tool_resolved_path(self.name(), &resolved)?;
 
         Ok(resolved)
     }
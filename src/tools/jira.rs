@@ -0,0 +1,560 @@
+//! `jira_search` and `jira_create_issue`: look up and file issues in the
+//! projects listed in `[jira].projects`.
+//!
+//! Every search is wrapped in a `project in (...)` clause, so agent-written
+//! JQL cannot reach other projects. Filing an issue is a high risk tier tool
+//! (see [`super::registry::risk_for`]) and is refused under read-only
+//! autonomy.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::{JiraConfig, JiraDeployment};
+use crate::security::SecurityPolicy;
+use crate::util::truncate_with_ellipsis;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::sync::Arc;
+
+const JIRA_TIMEOUT_SECS: u64 = 30;
+const SEARCH_FIELDS: &str = "summary,status,assignee,priority,issuetype,updated";
+
+fn blocked(security: &SecurityPolicy) -> Option<ToolResult> {
+    let reason = if !security.can_act() {
+        "Action blocked: autonomy is read-only"
+    } else if !security.record_action() {
+        "Action blocked: rate limit exceeded"
+    } else {
+        return None;
+    };
+    Some(ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(reason.into()),
+    })
+}
+
+fn failure(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+/// The project named in `args`, or the default (first) project. `Err` holds
+/// the message for a project outside the allowlist.
+fn resolve_project<'a>(config: &'a JiraConfig, args: &Value) -> Result<&'a str, String> {
+    match args
+        .get("project")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+    {
+        Some(key) => config
+            .projects
+            .iter()
+            .find(|allowed| allowed.eq_ignore_ascii_case(key))
+            .map(String::as_str)
+            .ok_or_else(|| {
+                format!(
+                    "Project '{key}' is not in jira.projects ({})",
+                    config.projects.join(", ")
+                )
+            }),
+        None => config
+            .projects
+            .first()
+            .map(String::as_str)
+            .ok_or_else(|| "No projects configured in jira.projects".to_string()),
+    }
+}
+
+fn api_base(config: &JiraConfig) -> String {
+    let version = match config.deployment {
+        JiraDeployment::Cloud => 3,
+        JiraDeployment::Server => 2,
+    };
+    format!(
+        "{}/rest/api/{version}",
+        config.base_url.trim().trim_end_matches('/')
+    )
+}
+
+fn authorize(config: &JiraConfig, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    let token = config.api_token.as_deref().unwrap_or_default().trim();
+    match config.deployment {
+        JiraDeployment::Cloud => request.basic_auth(
+            config.email.as_deref().unwrap_or_default().trim(),
+            Some(token),
+        ),
+        JiraDeployment::Server => request.bearer_auth(token),
+    }
+}
+
+/// Send `request` and parse the JSON reply, turning Jira's error payload
+/// (`errorMessages` / `errors`) into the error text.
+async fn send(request: reqwest::RequestBuilder) -> Result<Value> {
+    let response = request.send().await.context("failed to reach Jira")?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    let payload: Value = serde_json::from_str(&body).unwrap_or(Value::Null);
+    if status.is_success() {
+        return Ok(payload);
+    }
+    let mut messages: Vec<String> = payload
+        .get("errorMessages")
+        .and_then(Value::as_array)
+        .map(|messages| {
+            messages
+                .iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(errors) = payload.get("errors").and_then(Value::as_object) {
+        for (field, message) in errors {
+            messages.push(format!("{field}: {}", message.as_str().unwrap_or_default()));
+        }
+    }
+    if messages.is_empty() {
+        messages.push(truncate_with_ellipsis(body.trim(), 200));
+    }
+    anyhow::bail!("Jira returned {status}: {}", messages.join("; "))
+}
+
+/// JQL limited to `projects`, optionally narrowed by free `text` and an
+/// agent-written `extra` clause. A trailing `ORDER BY` in `extra` is kept;
+/// otherwise results are newest-updated first.
+fn build_jql(projects: &[&str], text: Option<&str>, extra: Option<&str>) -> Result<String, String> {
+    let mut jql = if let [project] = projects {
+        format!("project = {project}")
+    } else {
+        format!("project in ({})", projects.join(", "))
+    };
+    if let Some(text) = text {
+        let escaped = text.replace('\\', "\\\\").replace('"', "\\\"");
+        let _ = write!(jql, " AND text ~ \"{escaped}\"");
+    }
+    let mut order = "ORDER BY updated DESC".to_string();
+    if let Some(extra) = extra {
+        let (clause, extra_order) = match extra.to_ascii_lowercase().rfind("order by") {
+            Some(at) => (extra[..at].trim(), Some(extra[at..].trim())),
+            None => (extra, None),
+        };
+        if !parens_balanced(clause) {
+            return Err("'jql' has unbalanced parentheses".into());
+        }
+        if !clause.is_empty() {
+            let _ = write!(jql, " AND ({clause})");
+        }
+        if let Some(extra_order) = extra_order {
+            order = extra_order.to_string();
+        }
+    }
+    Ok(format!("{jql} {order}"))
+}
+
+/// Parentheses outside quoted strings close in order, so a clause cannot
+/// break out of the `AND (...)` it is wrapped in.
+fn parens_balanced(clause: &str) -> bool {
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut escaped = false;
+    for c in clause.chars() {
+        if let Some(open) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == open {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '(' => depth += 1,
+            ')' => match depth.checked_sub(1) {
+                Some(next) => depth = next,
+                None => return false,
+            },
+            _ => {}
+        }
+    }
+    depth == 0 && quote.is_none()
+}
+
+/// `fields` of a create-issue request. REST v3 (Cloud) wants the description
+/// as an Atlassian Document, one paragraph per blank-line-separated block.
+fn issue_fields(
+    deployment: JiraDeployment,
+    project: &str,
+    issue_type: &str,
+    summary: &str,
+    description: Option<&str>,
+    labels: &[String],
+    priority: Option<&str>,
+) -> Value {
+    let mut fields = json!({
+        "project": { "key": project },
+        "issuetype": { "name": issue_type },
+        "summary": summary,
+    });
+    if let Some(description) = description {
+        fields["description"] = match deployment {
+            JiraDeployment::Server => json!(description),
+            JiraDeployment::Cloud => {
+                let paragraphs: Vec<Value> = description
+                    .split("\n\n")
+                    .map(str::trim)
+                    .filter(|paragraph| !paragraph.is_empty())
+                    .map(|paragraph| {
+                        json!({
+                            "type": "paragraph",
+                            "content": [{ "type": "text", "text": paragraph }]
+                        })
+                    })
+                    .collect();
+                json!({ "type": "doc", "version": 1, "content": paragraphs })
+            }
+        };
+    }
+    if !labels.is_empty() {
+        fields["labels"] = json!(labels);
+    }
+    if let Some(priority) = priority {
+        fields["priority"] = json!({ "name": priority });
+    }
+    fields
+}
+
+fn format_issue(base_url: &str, issue: &Value) -> String {
+    let key = issue["key"].as_str().unwrap_or("?");
+    let fields = &issue["fields"];
+    let name = |field: &str, attr: &str| fields[field][attr].as_str().map(String::from);
+    let details: Vec<String> = [
+        name("issuetype", "name"),
+        name("priority", "name"),
+        name("assignee", "displayName"),
+        fields["updated"]
+            .as_str()
+            .map(|updated| format!("updated {}", updated.get(..10).unwrap_or(updated))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    format!(
+        "{key} [{}] {}\n  {}\n  {}/browse/{key}",
+        name("status", "name").unwrap_or_else(|| "?".into()),
+        fields["summary"].as_str().unwrap_or_default(),
+        details.join(", "),
+        base_url.trim().trim_end_matches('/')
+    )
+}
+
+fn client() -> reqwest::Client {
+    crate::config::build_runtime_proxy_client_with_timeouts("tool.jira", JIRA_TIMEOUT_SECS, 10)
+}
+
+/// Searches issues in the allowed Jira projects.
+pub struct JiraSearchTool {
+    security: Arc<SecurityPolicy>,
+    config: Arc<JiraConfig>,
+}
+
+impl JiraSearchTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: Arc<JiraConfig>) -> Self {
+        Self { security, config }
+    }
+}
+
+#[async_trait]
+impl Tool for JiraSearchTool {
+    fn name(&self) -> &str {
+        "jira_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search Jira issues in the configured projects by text and/or a JQL clause. Returns key, status, summary, type, priority, assignee and link for each issue."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Free text matched against summary, description and comments"
+                },
+                "jql": {
+                    "type": "string",
+                    "description": "Extra JQL clause, e.g. status = \"In Progress\" AND assignee = currentUser() ORDER BY priority DESC"
+                },
+                "project": {
+                    "type": "string",
+                    "enum": self.config.projects,
+                    "description": "Only this project (default: all configured projects)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": self.config.max_results,
+                    "description": format!("Maximum issues to return (default: {})", self.config.max_results)
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let text_arg = |name: &str| {
+            args.get(name)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let projects: Vec<&str> = if text_arg("project").is_some() {
+            match resolve_project(&self.config, &args) {
+                Ok(project) => vec![project],
+                Err(message) => return Ok(failure(message)),
+            }
+        } else {
+            self.config.projects.iter().map(String::as_str).collect()
+        };
+        let jql = match build_jql(&projects, text_arg("query"), text_arg("jql")) {
+            Ok(jql) => jql,
+            Err(message) => return Ok(failure(message)),
+        };
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .and_then(|n| usize::try_from(n).ok())
+            .unwrap_or(self.config.max_results)
+            .clamp(1, self.config.max_results);
+
+        if let Some(result) = blocked(&self.security) {
+            return Ok(result);
+        }
+
+        let endpoint = match self.config.deployment {
+            JiraDeployment::Cloud => "search/jql",
+            JiraDeployment::Server => "search",
+        };
+        let request = client()
+            .get(format!("{}/{endpoint}", api_base(&self.config)))
+            .query(&[
+                ("jql", jql.as_str()),
+                ("maxResults", limit.to_string().as_str()),
+                ("fields", SEARCH_FIELDS),
+            ]);
+        let payload = match send(authorize(&self.config, request)).await {
+            Ok(payload) => payload,
+            Err(e) => return Ok(failure(format!("{e:#}"))),
+        };
+
+        let issues = payload["issues"].as_array().map_or(&[][..], Vec::as_slice);
+        if issues.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: format!("No issues match: {jql}"),
+                error: None,
+            });
+        }
+        let mut output = format!("{} issue(s) for: {jql}", issues.len());
+        for issue in issues {
+            let _ = write!(output, "\n\n{}", format_issue(&self.config.base_url, issue));
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+/// Files a new issue in one of the allowed Jira projects.
+pub struct JiraCreateIssueTool {
+    security: Arc<SecurityPolicy>,
+    config: Arc<JiraConfig>,
+}
+
+impl JiraCreateIssueTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: Arc<JiraConfig>) -> Self {
+        Self { security, config }
+    }
+}
+
+#[async_trait]
+impl Tool for JiraCreateIssueTool {
+    fn name(&self) -> &str {
+        "jira_create_issue"
+    }
+
+    fn description(&self) -> &str {
+        "Create a Jira issue in one of the configured projects and return its key and link. Only file issues the user asked for."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "summary": {
+                    "type": "string",
+                    "description": "One-line issue title"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "Details: what happened, steps to reproduce, expected vs actual"
+                },
+                "project": {
+                    "type": "string",
+                    "enum": self.config.projects,
+                    "description": format!(
+                        "Project key (default: {})",
+                        self.config.projects.first().map_or("", String::as_str)
+                    )
+                },
+                "issue_type": {
+                    "type": "string",
+                    "description": format!("Issue type name, e.g. Bug or Story (default: {})", self.config.default_issue_type)
+                },
+                "priority": {
+                    "type": "string",
+                    "description": "Priority name as configured in Jira, e.g. High"
+                },
+                "labels": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Labels (no spaces)"
+                }
+            },
+            "required": ["summary"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let text_arg = |name: &str| {
+            args.get(name)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let summary =
+            text_arg("summary").ok_or_else(|| anyhow::anyhow!("Missing 'summary' parameter"))?;
+        let project = match resolve_project(&self.config, &args) {
+            Ok(project) => project,
+            Err(message) => return Ok(failure(message)),
+        };
+        let labels: Vec<String> = args
+            .get("labels")
+            .and_then(Value::as_array)
+            .map(|labels| {
+                labels
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|label| label.trim().replace(char::is_whitespace, "-"))
+                    .filter(|label| !label.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let fields = issue_fields(
+            self.config.deployment,
+            project,
+            text_arg("issue_type").unwrap_or(self.config.default_issue_type.trim()),
+            &truncate_with_ellipsis(summary, 250),
+            text_arg("description"),
+            &labels,
+            text_arg("priority"),
+        );
+
+        if let Some(result) = blocked(&self.security) {
+            return Ok(result);
+        }
+
+        let request = client()
+            .post(format!("{}/issue", api_base(&self.config)))
+            .json(&json!({ "fields": fields }));
+        match send(authorize(&self.config, request)).await {
+            Ok(created) => {
+                let key = created["key"].as_str().unwrap_or("?");
+                Ok(ToolResult {
+                    success: true,
+                    output: format!(
+                        "Created {key}: {}/browse/{key}",
+                        self.config.base_url.trim().trim_end_matches('/')
+                    ),
+                    error: None,
+                })
+            }
+            Err(e) => Ok(failure(format!("{e:#}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jql_stays_inside_allowed_projects() {
+        assert_eq!(
+            build_jql(&["OPS", "WEB"], Some("login \"500\""), None).unwrap(),
+            "project in (OPS, WEB) AND text ~ \"login \\\"500\\\"\" ORDER BY updated DESC"
+        );
+        assert_eq!(
+            build_jql(&["OPS"], None, Some("status = Open order by priority DESC")).unwrap(),
+            "project = OPS AND (status = Open) order by priority DESC"
+        );
+        assert!(build_jql(&["OPS"], None, Some("status = Open) OR (project = HR")).is_err());
+        assert!(build_jql(&["OPS"], None, Some("summary ~ \"a (b\"")).is_ok());
+    }
+
+    #[test]
+    fn cloud_descriptions_use_document_format() {
+        let fields = issue_fields(
+            JiraDeployment::Cloud,
+            "OPS",
+            "Bug",
+            "Login fails",
+            Some("Steps:\n1. open\n\nExpected: works"),
+            &["auth".to_string()],
+            None,
+        );
+        assert_eq!(fields["project"]["key"], "OPS");
+        assert_eq!(
+            fields["description"]["content"].as_array().unwrap().len(),
+            2
+        );
+        assert_eq!(fields["labels"], json!(["auth"]));
+
+        let fields = issue_fields(
+            JiraDeployment::Server,
+            "OPS",
+            "Task",
+            "x",
+            Some("plain"),
+            &[],
+            Some("High"),
+        );
+        assert_eq!(fields["description"], "plain");
+        assert_eq!(fields["priority"]["name"], "High");
+    }
+
+    #[tokio::test]
+    async fn create_rejects_projects_outside_allowlist() {
+        let config = Arc::new(JiraConfig {
+            enabled: true,
+            base_url: "https://example.atlassian.net".into(),
+            projects: vec!["OPS".into()],
+            ..JiraConfig::default()
+        });
+        let tool = JiraCreateIssueTool::new(Arc::new(SecurityPolicy::default()), config);
+        let result = tool
+            .execute(json!({ "summary": "x", "project": "HR" }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("not in jira.projects"));
+        assert!(tool.execute(json!({ "summary": " " })).await.is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use super::jobs::{parse_job_id, JobRegistry};
+use super::traits::{Tool, ToolResult};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Stop a background job started by `shell` with `background = true`.
+pub struct JobKillTool {
+    security: Arc<SecurityPolicy>,
+    jobs: Arc<JobRegistry>,
+}
+
+impl JobKillTool {
+    pub fn new(security: Arc<SecurityPolicy>, jobs: Arc<JobRegistry>) -> Self {
+        Self { security, jobs }
+    }
+}
+
+#[async_trait]
+impl Tool for JobKillTool {
+    fn name(&self) -> &str {
+        "job_kill"
+    }
+
+    fn description(&self) -> &str {
+        "Kill a running background shell job"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "integer",
+                    "description": "Job id returned by shell with background=true"
+                }
+            },
+            "required": ["id"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let id = parse_job_id(&args).ok_or_else(|| anyhow::anyhow!("Missing 'id' parameter"))?;
+        if let Err(e) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "job_kill")
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            });
+        }
+
+        match self.jobs.kill(id) {
+            Ok(()) => Ok(ToolResult {
+                success: true,
+                output: format!("Killing background job {id}; check job_status for the result"),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    #[tokio::test]
+    async fn job_kill_blocked_in_readonly_mode() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let jobs = Arc::new(JobRegistry::new(None));
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg("sleep 30");
+        let (id, _) = jobs.start("sleep 30", cmd).unwrap();
+
+        let tool = JobKillTool::new(security, jobs.clone());
+        let result = tool.execute(json!({"id": id})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+        assert_eq!(jobs.status(id).unwrap()["state"], "running");
+    }
+}
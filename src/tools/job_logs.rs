@@ -0,0 +1,76 @@
+use super::jobs::{parse_job_id, JobRegistry};
+use super::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+const DEFAULT_TAIL_LINES: usize = 100;
+const MAX_TAIL_LINES: usize = 2_000;
+
+/// Output of a background job started by `shell` with `background = true`.
+pub struct JobLogsTool {
+    jobs: Arc<JobRegistry>,
+}
+
+impl JobLogsTool {
+    pub fn new(jobs: Arc<JobRegistry>) -> Self {
+        Self { jobs }
+    }
+}
+
+#[async_trait]
+impl Tool for JobLogsTool {
+    fn name(&self) -> &str {
+        "job_logs"
+    }
+
+    fn description(&self) -> &str {
+        "Read the combined stdout/stderr of a background shell job: the last lines, or only what is new since a previous call's next_offset"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "integer",
+                    "description": "Job id returned by shell with background=true"
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Return output after this byte offset (next_offset from a previous job_logs call)"
+                },
+                "tail_lines": {
+                    "type": "integer",
+                    "description": "Without offset, return this many trailing lines (default 100, max 2000)"
+                }
+            },
+            "required": ["id"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let id = parse_job_id(&args).ok_or_else(|| anyhow::anyhow!("Missing 'id' parameter"))?;
+        let offset = args.get("offset").and_then(serde_json::Value::as_u64);
+        let tail_lines = args
+            .get("tail_lines")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_TAIL_LINES, |v| {
+                usize::try_from(v).unwrap_or(MAX_TAIL_LINES)
+            })
+            .clamp(1, MAX_TAIL_LINES);
+
+        match self.jobs.logs(id, offset, tail_lines) {
+            Some(logs) => Ok(ToolResult {
+                success: true,
+                output: logs.to_string(),
+                error: None,
+            }),
+            None => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("No background job with id {id}")),
+            }),
+        }
+    }
+}
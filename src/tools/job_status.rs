@@ -0,0 +1,60 @@
+use super::jobs::{parse_job_id, JobRegistry};
+use super::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// State of background jobs started by `shell` with `background = true`.
+pub struct JobStatusTool {
+    jobs: Arc<JobRegistry>,
+}
+
+impl JobStatusTool {
+    pub fn new(jobs: Arc<JobRegistry>) -> Self {
+        Self { jobs }
+    }
+}
+
+#[async_trait]
+impl Tool for JobStatusTool {
+    fn name(&self) -> &str {
+        "job_status"
+    }
+
+    fn description(&self) -> &str {
+        "Check a background shell job (running, exited with code, or killed, and its runtime); omit id to list all jobs"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "integer",
+                    "description": "Job id returned by shell with background=true; omit to list all jobs"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let status = match parse_job_id(&args) {
+            Some(id) => match self.jobs.status(id) {
+                Some(status) => status,
+                None => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("No background job with id {id}")),
+                    });
+                }
+            },
+            None => json!(self.jobs.list()),
+        };
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&status).unwrap_or_default(),
+            error: None,
+        })
+    }
+}
@@ -0,0 +1,416 @@
+//! Background jobs started by `shell` with `background = true`.
+//!
+//! A job runs detached from the tool call that started it, so a build or a
+//! test suite neither blocks the turn nor hits the shell timeout. Its
+//! stdout and stderr are interleaved into one bounded buffer. `job_status`,
+//! `job_logs` and `job_kill` look jobs up by id. Each job runs in its own
+//! process group, so a kill also reaches what the command spawned; jobs still
+//! running when the registry is dropped are killed.
+
+use super::process::{append_bounded, snapshot_output_buffer, spawn_reader_task, OutputBuffer};
+use crate::security::SyscallAnomalyDetector;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+/// Jobs allowed to run at once.
+const MAX_RUNNING_JOBS: usize = 8;
+/// Finished jobs kept for status and log lookups.
+const MAX_FINISHED_JOBS: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobState {
+    Running,
+    /// `exit_code` is `None` when the process was ended by a signal.
+    Exited {
+        exit_code: Option<i32>,
+        duration: Duration,
+    },
+    Killed {
+        duration: Duration,
+    },
+}
+
+struct Job {
+    command: String,
+    pid: u32,
+    started_at: chrono::DateTime<chrono::Utc>,
+    started: Instant,
+    output: Arc<Mutex<OutputBuffer>>,
+    state: Arc<Mutex<JobState>>,
+    kill: Mutex<Option<oneshot::Sender<()>>>,
+}
+
+impl Job {
+    fn state(&self) -> JobState {
+        self.state.lock().unwrap().clone()
+    }
+
+    fn summary(&self, id: usize) -> serde_json::Value {
+        let mut summary = json!({
+            "id": id,
+            "command": self.command,
+            "pid": self.pid,
+            "started_at": self.started_at.to_rfc3339(),
+        });
+        match self.state() {
+            JobState::Running => {
+                summary["state"] = json!("running");
+                summary["runtime_secs"] = json!(self.started.elapsed().as_secs());
+            }
+            JobState::Exited {
+                exit_code,
+                duration,
+            } => {
+                summary["state"] = json!("exited");
+                summary["exit_code"] = json!(exit_code);
+                summary["runtime_secs"] = json!(duration.as_secs());
+            }
+            JobState::Killed { duration } => {
+                summary["state"] = json!("killed");
+                summary["runtime_secs"] = json!(duration.as_secs());
+            }
+        }
+        summary
+    }
+}
+
+/// Background jobs shared by `shell` and the `job_*` tools.
+pub struct JobRegistry {
+    jobs: Mutex<BTreeMap<usize, Job>>,
+    next_id: Mutex<usize>,
+    syscall_detector: Option<Arc<SyscallAnomalyDetector>>,
+}
+
+impl JobRegistry {
+    pub fn new(syscall_detector: Option<Arc<SyscallAnomalyDetector>>) -> Self {
+        Self {
+            jobs: Mutex::new(BTreeMap::new()),
+            next_id: Mutex::new(0),
+            syscall_detector,
+        }
+    }
+
+    /// Spawn `cmd` (already checked against the security policy) as a job
+    /// and return its id and pid.
+    pub fn start(
+        &self,
+        command: &str,
+        mut cmd: tokio::process::Command,
+    ) -> Result<(usize, u32), String> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let running = jobs
+            .values()
+            .filter(|job| job.state() == JobState::Running)
+            .count();
+        if running >= MAX_RUNNING_JOBS {
+            return Err(format!(
+                "Maximum running background jobs ({MAX_RUNNING_JOBS}) reached; wait for one to finish or use job_kill"
+            ));
+        }
+
+        cmd.stdin(Stdio::null());
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+        cmd.kill_on_drop(true);
+        // Own process group, so job_kill reaches whatever the shell spawned.
+        #[cfg(unix)]
+        cmd.process_group(0);
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| format!("Failed to start background job: {e}"))?;
+        let pid = child.id().unwrap_or(0);
+
+        let output = Arc::new(Mutex::new(OutputBuffer::default()));
+        if let Some(stdout) = child.stdout.take() {
+            spawn_reader_task(stdout, output.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_reader_task(stderr, output.clone());
+        }
+
+        let id = {
+            let mut next = self.next_id.lock().unwrap();
+            let id = *next;
+            *next += 1;
+            id
+        };
+        let started = Instant::now();
+        let state = Arc::new(Mutex::new(JobState::Running));
+        let (kill_tx, kill_rx) = oneshot::channel();
+
+        let waiter_state = state.clone();
+        let waiter_output = output.clone();
+        let detector = self.syscall_detector.clone();
+        let waiter_command = command.to_string();
+        tokio::spawn(async move {
+            // A dropped sender (registry gone) kills the job too.
+            let status = tokio::select! {
+                status = child.wait() => Some(status),
+                _ = kill_rx => None,
+            };
+            let finished = match status {
+                Some(status) => {
+                    let exit_code = status.ok().and_then(|status| status.code());
+                    if let Some(detector) = &detector {
+                        let output = snapshot_output_buffer(&waiter_output);
+                        let _ = detector.inspect_command_output(
+                            &waiter_command,
+                            &output.data,
+                            "",
+                            exit_code,
+                        );
+                    }
+                    JobState::Exited {
+                        exit_code,
+                        duration: started.elapsed(),
+                    }
+                }
+                None => {
+                    kill_process_group(pid).await;
+                    let _ = child.kill().await;
+                    append_bounded(&waiter_output, "\n[killed]\n");
+                    JobState::Killed {
+                        duration: started.elapsed(),
+                    }
+                }
+            };
+            *waiter_state.lock().unwrap() = finished;
+        });
+
+        jobs.insert(
+            id,
+            Job {
+                command: command.to_string(),
+                pid,
+                started_at: chrono::Utc::now(),
+                started,
+                output,
+                state,
+                kill: Mutex::new(Some(kill_tx)),
+            },
+        );
+
+        // Forget the oldest finished jobs.
+        let finished: Vec<usize> = jobs
+            .iter()
+            .filter(|(_, job)| job.state() != JobState::Running)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in finished
+            .iter()
+            .take(finished.len().saturating_sub(MAX_FINISHED_JOBS))
+        {
+            jobs.remove(id);
+        }
+
+        Ok((id, pid))
+    }
+
+    /// State of job `id`.
+    pub fn status(&self, id: usize) -> Option<serde_json::Value> {
+        let jobs = self.jobs.lock().unwrap();
+        jobs.get(&id).map(|job| job.summary(id))
+    }
+
+    /// State of every job, oldest first.
+    pub fn list(&self) -> Vec<serde_json::Value> {
+        let jobs = self.jobs.lock().unwrap();
+        jobs.iter().map(|(id, job)| job.summary(*id)).collect()
+    }
+
+    /// Output of job `id`: from byte `offset` (a previous `next_offset`)
+    /// when given, otherwise its last `tail_lines` lines.
+    pub fn logs(
+        &self,
+        id: usize,
+        offset: Option<u64>,
+        tail_lines: usize,
+    ) -> Option<serde_json::Value> {
+        let jobs = self.jobs.lock().unwrap();
+        let job = jobs.get(&id)?;
+        let buffer = snapshot_output_buffer(&job.output);
+        let len = u64::try_from(buffer.data.len()).unwrap_or(u64::MAX);
+        let next_offset = buffer.dropped_prefix_bytes.saturating_add(len);
+
+        let (text, truncated) = match offset {
+            Some(offset) => {
+                let mut start = usize::try_from(offset.saturating_sub(buffer.dropped_prefix_bytes))
+                    .unwrap_or(usize::MAX)
+                    .min(buffer.data.len());
+                while !buffer.data.is_char_boundary(start) {
+                    start += 1;
+                }
+                (&buffer.data[start..], offset < buffer.dropped_prefix_bytes)
+            }
+            None => {
+                let text = tail(&buffer.data, tail_lines);
+                (
+                    text,
+                    text.len() < buffer.data.len() || buffer.dropped_prefix_bytes > 0,
+                )
+            }
+        };
+
+        let mut logs = job.summary(id);
+        logs["output"] = json!(text);
+        logs["next_offset"] = json!(next_offset);
+        logs["truncated"] = json!(truncated);
+        Some(logs)
+    }
+
+    /// Kill job `id` if it is still running.
+    pub fn kill(&self, id: usize) -> Result<(), String> {
+        let jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .get(&id)
+            .ok_or_else(|| format!("No background job with id {id}"))?;
+        if job.state() != JobState::Running {
+            return Err(format!("Job {id} is not running"));
+        }
+        let sender = job.kill.lock().unwrap().take();
+        match sender.map(|sender| sender.send(())) {
+            Some(Ok(())) => Ok(()),
+            _ => Err(format!("Job {id} is already stopping")),
+        }
+    }
+}
+
+/// The last `lines` lines of `text`.
+/// SIGKILL every process in the job's group (its pid is the group id).
+#[cfg(unix)]
+async fn kill_process_group(pid: u32) {
+    if pid == 0 {
+        return;
+    }
+    let _ = tokio::process::Command::new("kill")
+        .args(["-KILL", "--", &format!("-{pid}")])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await;
+}
+
+#[cfg(not(unix))]
+async fn kill_process_group(_pid: u32) {}
+
+fn tail(text: &str, lines: usize) -> &str {
+    let body = text.strip_suffix('\n').unwrap_or(text);
+    match body.rmatch_indices('\n').nth(lines.saturating_sub(1)) {
+        Some((index, _)) => &text[index + 1..],
+        None => text,
+    }
+}
+
+/// Parse the `id` argument of a `job_*` tool.
+pub(super) fn parse_job_id(args: &serde_json::Value) -> Option<usize> {
+    args.get("id")
+        .and_then(serde_json::Value::as_u64)
+        .and_then(|id| usize::try_from(id).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(script: &str) -> tokio::process::Command {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(script);
+        cmd
+    }
+
+    async fn wait_until_finished(jobs: &JobRegistry, id: usize) -> serde_json::Value {
+        for _ in 0..100 {
+            let status = jobs.status(id).unwrap();
+            if status["state"] != "running" {
+                return status;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("job {id} did not finish");
+    }
+
+    #[test]
+    fn tail_keeps_last_lines() {
+        assert_eq!(tail("a\nb\nc\n", 2), "b\nc\n");
+        assert_eq!(tail("a\nb\nc", 1), "c");
+        assert_eq!(tail("a\nb\n", 5), "a\nb\n");
+    }
+
+    #[tokio::test]
+    async fn job_runs_in_background_and_reports_exit_code() {
+        let jobs = JobRegistry::new(None);
+        let (id, _) = jobs
+            .start("build", command("echo one; echo two >&2; exit 3"))
+            .unwrap();
+
+        let status = wait_until_finished(&jobs, id).await;
+        assert_eq!(status["state"], "exited");
+        assert_eq!(status["exit_code"], 3);
+
+        // Give the readers a moment to drain the pipes.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let logs = jobs.logs(id, None, 100).unwrap();
+        let output = logs["output"].as_str().unwrap();
+        assert!(output.contains("one") && output.contains("two"));
+
+        let offset = logs["next_offset"].as_u64().unwrap();
+        let rest = jobs.logs(id, Some(offset), 100).unwrap();
+        assert_eq!(rest["output"], "");
+        assert!(jobs.kill(id).unwrap_err().contains("not running"));
+    }
+
+    #[tokio::test]
+    async fn kill_stops_running_job() {
+        let jobs = JobRegistry::new(None);
+        let (id, _) = jobs.start("sleep", command("sleep 30")).unwrap();
+        assert_eq!(jobs.status(id).unwrap()["state"], "running");
+
+        jobs.kill(id).unwrap();
+        let status = wait_until_finished(&jobs, id).await;
+        assert_eq!(status["state"], "killed");
+        assert!(jobs.kill(99).unwrap_err().contains("No background job"));
+        assert_eq!(jobs.list().len(), 1);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn kill_reaches_processes_the_shell_spawned() {
+        // Running, not a zombie waiting to be reaped.
+        fn alive(pid: &str) -> bool {
+            std::fs::read_to_string(format!("/proc/{pid}/stat"))
+                .is_ok_and(|stat| !stat.rsplit(')').next().unwrap_or("").starts_with(" Z"))
+        }
+
+        let jobs = JobRegistry::new(None);
+        let (id, _) = jobs
+            .start("tree", command("sleep 30 & echo $!; wait"))
+            .unwrap();
+        let mut grandchild = String::new();
+        for _ in 0..100 {
+            grandchild = jobs.logs(id, None, 10).unwrap()["output"]
+                .as_str()
+                .unwrap()
+                .trim()
+                .to_string();
+            if !grandchild.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(!grandchild.is_empty() && alive(&grandchild));
+
+        jobs.kill(id).unwrap();
+        wait_until_finished(&jobs, id).await;
+        for _ in 0..50 {
+            if !alive(&grandchild) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("sleep {grandchild} outlived job_kill");
+    }
+}
@@ -0,0 +1,142 @@
+use super::traits::{Tool, ToolResult};
+use crate::kb::KnowledgeBase;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+const MAX_LIMIT: usize = 20;
+
+/// Searches the documents ingested with `zeroclaw kb ingest`.
+pub struct KbSearchTool {
+    kb: Arc<KnowledgeBase>,
+}
+
+impl KbSearchTool {
+    pub fn new(kb: Arc<KnowledgeBase>) -> Self {
+        Self { kb }
+    }
+}
+
+#[async_trait]
+impl Tool for KbSearchTool {
+    fn name(&self) -> &str {
+        "kb_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search the knowledge base of private documents (handbooks, runbooks, notes) the user has ingested. Returns the most relevant passages with their source; quote the source when answering from them."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "What to look for, in natural language or keywords"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": format!(
+                        "Maximum passages to return (default: {}, max: {MAX_LIMIT})",
+                        self.kb.settings().top_k
+                    ),
+                    "minimum": 1,
+                    "maximum": MAX_LIMIT
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let query = args
+            .get("query")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|query| !query.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'query' parameter"))?;
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(self.kb.settings().top_k, |n| {
+                usize::try_from(n).unwrap_or(MAX_LIMIT).clamp(1, MAX_LIMIT)
+            });
+
+        let hits = match self.kb.search(query, limit).await {
+            Ok(hits) => hits,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Knowledge base search failed: {e:#}")),
+                })
+            }
+        };
+        if hits.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: "No matching passages in the knowledge base.".into(),
+                error: None,
+            });
+        }
+        let mut output = String::new();
+        for hit in hits {
+            let _ = write!(output, "[{:.2}] {} ({})", hit.score, hit.title, hit.source);
+            if let Some(heading) = &hit.heading {
+                let _ = write!(output, " › {}", heading.trim_start_matches('#').trim());
+            }
+            let _ = write!(output, "\n{}\n\n", hit.content);
+        }
+        Ok(ToolResult {
+            success: true,
+            output: output.trim_end().to_string(),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::KbConfig;
+    use crate::memory::embeddings::NoopEmbedding;
+
+    #[tokio::test]
+    async fn kb_search_returns_passages_with_sources() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let kb = KnowledgeBase::open(
+            tmp.path(),
+            Arc::new(NoopEmbedding),
+            KbConfig::default(),
+            0.7,
+            0.3,
+        )
+        .unwrap();
+        kb.ingest_text(
+            "runbooks/db.md",
+            "Database",
+            "## Failover\n\nPromote the replica with pg_ctl promote.",
+        )
+        .await
+        .unwrap();
+        let tool = KbSearchTool::new(Arc::new(kb));
+
+        let result = tool
+            .execute(json!({ "query": "replica failover" }))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result
+            .output
+            .starts_with("[1.00] Database (runbooks/db.md) › Failover\n"));
+
+        let result = tool
+            .execute(json!({ "query": "kubernetes" }))
+            .await
+            .unwrap();
+        assert!(result.output.starts_with("No matching passages"));
+        assert!(tool.execute(json!({})).await.is_err());
+    }
+}
@@ -0,0 +1,430 @@
+//! `linear_search` and `linear_create_issue`: look up and file issues in the
+//! teams listed in `[linear].teams`.
+//!
+//! Every search is filtered to the allowed team keys. Filing an issue is a
+//! high risk tier tool (see [`super::registry::risk_for`]) and is refused
+//! under read-only autonomy.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::LinearConfig;
+use crate::security::SecurityPolicy;
+use crate::util::truncate_with_ellipsis;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::sync::Arc;
+
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+const LINEAR_TIMEOUT_SECS: u64 = 30;
+
+const SEARCH_QUERY: &str = "query($filter: IssueFilter, $first: Int) { issues(filter: $filter, first: $first, orderBy: updatedAt) { nodes { identifier title url priorityLabel updatedAt state { name } assignee { name } } } }";
+const TEAM_QUERY: &str =
+    "query($key: String!) { teams(filter: { key: { eq: $key } }) { nodes { id } } }";
+const CREATE_MUTATION: &str = "mutation($input: IssueCreateInput!) { issueCreate(input: $input) { success issue { identifier url } } }";
+
+fn blocked(security: &SecurityPolicy) -> Option<ToolResult> {
+    let reason = if !security.can_act() {
+        "Action blocked: autonomy is read-only"
+    } else if !security.record_action() {
+        "Action blocked: rate limit exceeded"
+    } else {
+        return None;
+    };
+    Some(ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(reason.into()),
+    })
+}
+
+fn failure(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+/// The team named in `args`, or the default (first) team. `Err` holds the
+/// message for a team outside the allowlist.
+fn resolve_team<'a>(config: &'a LinearConfig, args: &Value) -> Result<&'a str, String> {
+    match args
+        .get("team")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+    {
+        Some(key) => config
+            .teams
+            .iter()
+            .find(|allowed| allowed.eq_ignore_ascii_case(key))
+            .map(String::as_str)
+            .ok_or_else(|| {
+                format!(
+                    "Team '{key}' is not in linear.teams ({})",
+                    config.teams.join(", ")
+                )
+            }),
+        None => config
+            .teams
+            .first()
+            .map(String::as_str)
+            .ok_or_else(|| "No teams configured in linear.teams".to_string()),
+    }
+}
+
+/// Run one GraphQL request and return its `data`.
+async fn graphql(config: &LinearConfig, query: &str, variables: Value) -> Result<Value> {
+    let client = crate::config::build_runtime_proxy_client_with_timeouts(
+        "tool.linear",
+        LINEAR_TIMEOUT_SECS,
+        10,
+    );
+    let response = client
+        .post(LINEAR_API_URL)
+        .header(
+            "Authorization",
+            config.api_key.as_deref().unwrap_or_default().trim(),
+        )
+        .json(&json!({ "query": query, "variables": variables }))
+        .send()
+        .await
+        .context("failed to reach Linear")?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    let payload: Value = serde_json::from_str(&body).unwrap_or(Value::Null);
+    if let Some(errors) = payload.get("errors").and_then(Value::as_array) {
+        let messages: Vec<&str> = errors
+            .iter()
+            .filter_map(|error| error.get("message").and_then(Value::as_str))
+            .collect();
+        anyhow::bail!("Linear returned {status}: {}", messages.join("; "));
+    }
+    if !status.is_success() {
+        anyhow::bail!(
+            "Linear returned {status}: {}",
+            truncate_with_ellipsis(body.trim(), 200)
+        );
+    }
+    Ok(payload["data"].clone())
+}
+
+/// `IssueFilter` limited to `teams`, optionally narrowed by text in the title
+/// or description and by workflow state name.
+fn search_filter(teams: &[&str], text: Option<&str>, state: Option<&str>) -> Value {
+    let mut filter = json!({ "team": { "key": { "in": teams } } });
+    if let Some(text) = text {
+        filter["or"] = json!([
+            { "title": { "containsIgnoreCase": text } },
+            { "description": { "containsIgnoreCase": text } },
+        ]);
+    }
+    if let Some(state) = state {
+        filter["state"] = json!({ "name": { "eqIgnoreCase": state } });
+    }
+    filter
+}
+
+/// Linear's priority scale: 0 none, 1 urgent, 2 high, 3 medium, 4 low.
+fn parse_priority(raw: &str) -> Option<u8> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "none" | "no priority" => Some(0),
+        "urgent" => Some(1),
+        "high" => Some(2),
+        "medium" | "normal" => Some(3),
+        "low" => Some(4),
+        _ => None,
+    }
+}
+
+fn format_issue(issue: &Value) -> String {
+    let details: Vec<String> = [
+        issue["priorityLabel"].as_str().map(String::from),
+        issue["assignee"]["name"].as_str().map(String::from),
+        issue["updatedAt"]
+            .as_str()
+            .map(|updated| format!("updated {}", updated.get(..10).unwrap_or(updated))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    format!(
+        "{} [{}] {}\n  {}\n  {}",
+        issue["identifier"].as_str().unwrap_or("?"),
+        issue["state"]["name"].as_str().unwrap_or("?"),
+        issue["title"].as_str().unwrap_or_default(),
+        details.join(", "),
+        issue["url"].as_str().unwrap_or_default()
+    )
+}
+
+/// Searches issues in the allowed Linear teams.
+pub struct LinearSearchTool {
+    security: Arc<SecurityPolicy>,
+    config: Arc<LinearConfig>,
+}
+
+impl LinearSearchTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: Arc<LinearConfig>) -> Self {
+        Self { security, config }
+    }
+}
+
+#[async_trait]
+impl Tool for LinearSearchTool {
+    fn name(&self) -> &str {
+        "linear_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search Linear issues in the configured teams by text and/or workflow state, most recently updated first. Returns identifier, state, title, priority, assignee and link for each issue."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Text matched against title and description"
+                },
+                "state": {
+                    "type": "string",
+                    "description": "Workflow state name, e.g. In Progress"
+                },
+                "team": {
+                    "type": "string",
+                    "enum": self.config.teams,
+                    "description": "Only this team (default: all configured teams)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": self.config.max_results,
+                    "description": format!("Maximum issues to return (default: {})", self.config.max_results)
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let text_arg = |name: &str| {
+            args.get(name)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let teams: Vec<&str> = if text_arg("team").is_some() {
+            match resolve_team(&self.config, &args) {
+                Ok(team) => vec![team],
+                Err(message) => return Ok(failure(message)),
+            }
+        } else {
+            self.config.teams.iter().map(String::as_str).collect()
+        };
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .and_then(|n| usize::try_from(n).ok())
+            .unwrap_or(self.config.max_results)
+            .clamp(1, self.config.max_results);
+
+        if let Some(result) = blocked(&self.security) {
+            return Ok(result);
+        }
+
+        let variables = json!({
+            "filter": search_filter(&teams, text_arg("query"), text_arg("state")),
+            "first": limit,
+        });
+        let data = match graphql(&self.config, SEARCH_QUERY, variables).await {
+            Ok(data) => data,
+            Err(e) => return Ok(failure(format!("{e:#}"))),
+        };
+        let issues = data["issues"]["nodes"]
+            .as_array()
+            .map_or(&[][..], Vec::as_slice);
+        if issues.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: "No issues match.".into(),
+                error: None,
+            });
+        }
+        let mut output = format!("{} issue(s)", issues.len());
+        for issue in issues {
+            let _ = write!(output, "\n\n{}", format_issue(issue));
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+/// Files a new issue in one of the allowed Linear teams.
+pub struct LinearCreateIssueTool {
+    security: Arc<SecurityPolicy>,
+    config: Arc<LinearConfig>,
+}
+
+impl LinearCreateIssueTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: Arc<LinearConfig>) -> Self {
+        Self { security, config }
+    }
+}
+
+#[async_trait]
+impl Tool for LinearCreateIssueTool {
+    fn name(&self) -> &str {
+        "linear_create_issue"
+    }
+
+    fn description(&self) -> &str {
+        "Create a Linear issue in one of the configured teams and return its identifier and link. Only file issues the user asked for."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "title": {
+                    "type": "string",
+                    "description": "One-line issue title"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "Details in Markdown: what happened, steps to reproduce, expected vs actual"
+                },
+                "team": {
+                    "type": "string",
+                    "enum": self.config.teams,
+                    "description": format!(
+                        "Team key (default: {})",
+                        self.config.teams.first().map_or("", String::as_str)
+                    )
+                },
+                "priority": {
+                    "type": "string",
+                    "enum": ["none", "urgent", "high", "medium", "low"],
+                    "description": "Issue priority (default: none)"
+                }
+            },
+            "required": ["title"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let text_arg = |name: &str| {
+            args.get(name)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let title =
+            text_arg("title").ok_or_else(|| anyhow::anyhow!("Missing 'title' parameter"))?;
+        let team = match resolve_team(&self.config, &args) {
+            Ok(team) => team,
+            Err(message) => return Ok(failure(message)),
+        };
+        let priority = match text_arg("priority") {
+            Some(raw) => match parse_priority(raw) {
+                Some(priority) => Some(priority),
+                None => {
+                    return Ok(failure(format!(
+                        "Invalid 'priority': {raw}. Expected none, urgent, high, medium or low"
+                    )))
+                }
+            },
+            None => None,
+        };
+
+        if let Some(result) = blocked(&self.security) {
+            return Ok(result);
+        }
+
+        let team_id = match graphql(&self.config, TEAM_QUERY, json!({ "key": team })).await {
+            Ok(data) => data["teams"]["nodes"][0]["id"].as_str().map(String::from),
+            Err(e) => return Ok(failure(format!("{e:#}"))),
+        };
+        let Some(team_id) = team_id else {
+            return Ok(failure(format!(
+                "Team '{team}' was not found in the Linear workspace"
+            )));
+        };
+
+        let mut input = json!({
+            "teamId": team_id,
+            "title": truncate_with_ellipsis(title, 250),
+        });
+        if let Some(description) = text_arg("description") {
+            input["description"] = json!(description);
+        }
+        if let Some(priority) = priority {
+            input["priority"] = json!(priority);
+        }
+        match graphql(&self.config, CREATE_MUTATION, json!({ "input": input })).await {
+            Ok(data) if data["issueCreate"]["success"].as_bool() == Some(true) => {
+                let issue = &data["issueCreate"]["issue"];
+                Ok(ToolResult {
+                    success: true,
+                    output: format!(
+                        "Created {}: {}",
+                        issue["identifier"].as_str().unwrap_or("?"),
+                        issue["url"].as_str().unwrap_or_default()
+                    ),
+                    error: None,
+                })
+            }
+            Ok(_) => Ok(failure("Linear did not create the issue")),
+            Err(e) => Ok(failure(format!("{e:#}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_filter_stays_inside_allowed_teams() {
+        assert_eq!(
+            search_filter(&["ENG", "OPS"], Some("login"), Some("In Progress")),
+            json!({
+                "team": { "key": { "in": ["ENG", "OPS"] } },
+                "or": [
+                    { "title": { "containsIgnoreCase": "login" } },
+                    { "description": { "containsIgnoreCase": "login" } },
+                ],
+                "state": { "name": { "eqIgnoreCase": "In Progress" } },
+            })
+        );
+        assert_eq!(parse_priority("High"), Some(2));
+        assert_eq!(parse_priority("p1"), None);
+    }
+
+    #[tokio::test]
+    async fn create_rejects_teams_outside_allowlist() {
+        let config = Arc::new(LinearConfig {
+            enabled: true,
+            api_key: Some("lin_api_test".into()),
+            teams: vec!["ENG".into()],
+            ..LinearConfig::default()
+        });
+        let tool = LinearCreateIssueTool::new(Arc::new(SecurityPolicy::default()), config);
+        let result = tool
+            .execute(json!({ "title": "x", "team": "HR" }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("not in linear.teams"));
+
+        let result = tool
+            .execute(json!({ "title": "x", "priority": "p1" }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("Invalid 'priority'"));
+        assert!(tool.execute(json!({})).await.is_err());
+    }
+}
@@ -0,0 +1,451 @@
+//! `email_search` / `email_read`: look things up in the `[mailbox]` IMAP
+//! account without turning it into a chat channel.
+//!
+//! Folders are opened with `EXAMINE` and bodies fetched with `BODY.PEEK[]`,
+//! so nothing in the mailbox changes unless `mailbox.mark_seen` is set.
+
+use super::traits::{Tool, ToolResult};
+use crate::channels::email_channel::{connect_imap, EmailChannel, ImapSession};
+use crate::config::MailboxConfig;
+use crate::security::SecurityPolicy;
+use crate::util::truncate_with_ellipsis;
+use anyhow::Result;
+use async_imap::types::{Fetch, Flag};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use futures_util::TryStreamExt;
+use mail_parser::{MessageParser, MimeHeaders};
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Upper bound for one IMAP round trip, login included.
+const IMAP_TIMEOUT_SECS: u64 = 60;
+const DEFAULT_BODY_CHARS: usize = 8_000;
+const MAX_BODY_CHARS: usize = 50_000;
+
+fn blocked(message: &str) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.to_string()),
+    }
+}
+
+/// The configured folder named by `requested`, or the first folder.
+fn resolve_folder<'a>(config: &'a MailboxConfig, requested: Option<&str>) -> Result<&'a str> {
+    let mut folders = config
+        .folders
+        .iter()
+        .map(|folder| folder.as_str().trim())
+        .filter(|folder| !folder.is_empty());
+    match requested.map(str::trim).filter(|folder| !folder.is_empty()) {
+        None => folders
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("mailbox.folders is empty")),
+        Some(requested) => folders
+            .find(|folder| folder.eq_ignore_ascii_case(requested))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "folder '{requested}' is not in mailbox.folders ({})",
+                    config.folders.join(", ")
+                )
+            }),
+    }
+}
+
+/// Quote `value` as an IMAP string.
+fn quote(value: &str) -> Result<String> {
+    if value.chars().any(char::is_control) {
+        anyhow::bail!("search terms must not contain control characters");
+    }
+    Ok(format!(
+        "\"{}\"",
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    ))
+}
+
+fn imap_date(raw: &str, key: &str) -> Result<String> {
+    let date = NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("'{key}' must be a date like 2026-10-01"))?;
+    Ok(date.format("%d-%b-%Y").to_string())
+}
+
+/// IMAP `SEARCH` criteria for the tool arguments.
+fn search_criteria(args: &serde_json::Value) -> Result<String> {
+    let mut criteria = Vec::new();
+    for (key, imap_key) in [
+        ("from", "FROM"),
+        ("to", "TO"),
+        ("subject", "SUBJECT"),
+        ("text", "TEXT"),
+    ] {
+        if let Some(value) = args
+            .get(key)
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        {
+            criteria.push(format!("{imap_key} {}", quote(value)?));
+        }
+    }
+    for (key, imap_key) in [("since", "SINCE"), ("before", "BEFORE")] {
+        if let Some(value) = args.get(key).and_then(serde_json::Value::as_str) {
+            criteria.push(format!("{imap_key} {}", imap_date(value, key)?));
+        }
+    }
+    if args
+        .get("unread_only")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+    {
+        criteria.push("UNSEEN".into());
+    }
+    if criteria.is_empty() {
+        return Ok("ALL".into());
+    }
+    let joined = criteria.join(" ");
+    Ok(if joined.is_ascii() {
+        joined
+    } else {
+        format!("CHARSET UTF-8 {joined}")
+    })
+}
+
+async fn open_folder(config: &MailboxConfig, folder: &str, writable: bool) -> Result<ImapSession> {
+    let mut session = connect_imap(
+        config.imap_host.trim(),
+        config.imap_port,
+        config.username.trim(),
+        &config.password,
+    )
+    .await?;
+    if writable {
+        session.select(folder).await?;
+    } else {
+        session.examine(folder).await?;
+    }
+    Ok(session)
+}
+
+async fn with_timeout<T>(future: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+    tokio::time::timeout(Duration::from_secs(IMAP_TIMEOUT_SECS), future)
+        .await
+        .map_err(|_| anyhow::anyhow!("mailbox did not answer within {IMAP_TIMEOUT_SECS}s"))?
+}
+
+fn is_unread(message: &Fetch) -> bool {
+    !message.flags().any(|flag| flag == Flag::Seen)
+}
+
+fn format_date(parsed: &mail_parser::Message<'_>) -> String {
+    parsed
+        .date()
+        .map_or_else(|| "unknown date".into(), mail_parser::DateTime::to_rfc3339)
+}
+
+/// Searches the configured mailbox.
+pub struct EmailSearchTool {
+    security: Arc<SecurityPolicy>,
+    config: Arc<MailboxConfig>,
+}
+
+impl EmailSearchTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: Arc<MailboxConfig>) -> Self {
+        Self { security, config }
+    }
+
+    async fn search(&self, folder: &str, criteria: &str, limit: usize) -> Result<String> {
+        let mut session = open_folder(&self.config, folder, false).await?;
+        let mut uids: Vec<u32> = session.uid_search(criteria).await?.into_iter().collect();
+        uids.sort_unstable_by(|a, b| b.cmp(a));
+        let total = uids.len();
+        uids.truncate(limit);
+        if uids.is_empty() {
+            let _ = session.logout().await;
+            return Ok(format!("No messages in {folder} match."));
+        }
+
+        let uid_set = uids
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut messages: Vec<Fetch> = session
+            .uid_fetch(&uid_set, "(UID FLAGS BODY.PEEK[HEADER])")
+            .await?
+            .try_collect()
+            .await?;
+        let _ = session.logout().await;
+        messages.sort_unstable_by(|a, b| b.uid.cmp(&a.uid));
+
+        let mut output = if total > uids.len() {
+            format!("{total} messages in {folder} match; newest {}:", uids.len())
+        } else {
+            format!("{total} message(s) in {folder} match:")
+        };
+        for message in &messages {
+            let Some(parsed) = message
+                .header()
+                .and_then(|header| MessageParser::default().parse_headers(header))
+            else {
+                continue;
+            };
+            let _ = write!(
+                output,
+                "\n- uid {} · {} · {} · {}{}",
+                message.uid.unwrap_or_default(),
+                format_date(&parsed),
+                EmailChannel::extract_sender(&parsed),
+                parsed.subject().unwrap_or("(no subject)"),
+                if is_unread(message) { " [unread]" } else { "" }
+            );
+        }
+        output.push_str("\n\nUse email_read with a uid to read a message.");
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl Tool for EmailSearchTool {
+    fn name(&self) -> &str {
+        "email_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search the configured mailbox by sender, recipient, subject, text or date. Returns uid, date, sender and subject of matching messages, newest first. Read-only."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "from": { "type": "string", "description": "Sender address or name contains" },
+                "to": { "type": "string", "description": "Recipient contains" },
+                "subject": { "type": "string", "description": "Subject contains" },
+                "text": { "type": "string", "description": "Headers or body contain" },
+                "since": { "type": "string", "description": "Received on or after this date (YYYY-MM-DD)" },
+                "before": { "type": "string", "description": "Received before this date (YYYY-MM-DD)" },
+                "unread_only": { "type": "boolean", "description": "Only unread messages" },
+                "folder": {
+                    "type": "string",
+                    "enum": self.config.folders,
+                    "description": "Folder to search (default: the first configured folder)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": self.config.max_results,
+                    "description": "Maximum messages to list"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+        let folder = match resolve_folder(
+            &self.config,
+            args.get("folder").and_then(serde_json::Value::as_str),
+        ) {
+            Ok(folder) => folder,
+            Err(e) => return Ok(blocked(&e.to_string())),
+        };
+        let criteria = match search_criteria(&args) {
+            Ok(criteria) => criteria,
+            Err(e) => return Ok(blocked(&e.to_string())),
+        };
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|n| usize::try_from(n).ok())
+            .unwrap_or(self.config.max_results)
+            .clamp(1, self.config.max_results);
+
+        if !self.security.record_action() {
+            return Ok(blocked("Action blocked: rate limit exceeded"));
+        }
+
+        match with_timeout(self.search(folder, &criteria, limit)).await {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(blocked(&format!("Mailbox search failed: {e}"))),
+        }
+    }
+}
+
+/// Reads one message of the configured mailbox.
+pub struct EmailReadTool {
+    security: Arc<SecurityPolicy>,
+    config: Arc<MailboxConfig>,
+}
+
+impl EmailReadTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: Arc<MailboxConfig>) -> Self {
+        Self { security, config }
+    }
+
+    async fn read(&self, folder: &str, uid: u32, max_chars: usize) -> Result<String> {
+        let mark_seen = self.config.mark_seen;
+        let mut session = open_folder(&self.config, folder, mark_seen).await?;
+        let query = if mark_seen {
+            "(UID FLAGS BODY[])"
+        } else {
+            "(UID FLAGS BODY.PEEK[])"
+        };
+        let messages: Vec<Fetch> = session
+            .uid_fetch(uid.to_string(), query)
+            .await?
+            .try_collect()
+            .await?;
+        let _ = session.logout().await;
+
+        let message = messages
+            .iter()
+            .find(|message| message.uid == Some(uid))
+            .ok_or_else(|| anyhow::anyhow!("no message with uid {uid} in {folder}"))?;
+        let parsed = message
+            .body()
+            .and_then(|body| MessageParser::default().parse(body))
+            .ok_or_else(|| anyhow::anyhow!("message {uid} could not be parsed"))?;
+
+        let recipients = parsed
+            .to()
+            .and_then(|to| to.first())
+            .and_then(|addr| addr.address())
+            .unwrap_or("unknown");
+        let mut output = format!(
+            "From: {}\nTo: {recipients}\nDate: {}\nSubject: {}",
+            EmailChannel::extract_sender(&parsed),
+            format_date(&parsed),
+            parsed.subject().unwrap_or("(no subject)")
+        );
+        let attachments: Vec<&str> = parsed
+            .attachments()
+            .filter_map(MimeHeaders::attachment_name)
+            .collect();
+        if !attachments.is_empty() {
+            let _ = write!(output, "\nAttachments: {}", attachments.join(", "));
+        }
+        let _ = write!(
+            output,
+            "\n\n{}",
+            truncate_with_ellipsis(&EmailChannel::extract_text(&parsed), max_chars)
+        );
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl Tool for EmailReadTool {
+    fn name(&self) -> &str {
+        "email_read"
+    }
+
+    fn description(&self) -> &str {
+        "Read one message of the configured mailbox by uid (from email_search): sender, recipient, date, subject, attachment names and text body."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "uid": { "type": "integer", "minimum": 1, "description": "Message uid from email_search" },
+                "folder": {
+                    "type": "string",
+                    "enum": self.config.folders,
+                    "description": "Folder the uid belongs to (default: the first configured folder)"
+                },
+                "max_chars": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_BODY_CHARS,
+                    "description": "Maximum body characters to return (default: 8000)"
+                }
+            },
+            "required": ["uid"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+        let uid = args
+            .get("uid")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|uid| u32::try_from(uid).ok())
+            .filter(|uid| *uid > 0)
+            .ok_or_else(|| anyhow::anyhow!("Missing or invalid 'uid' parameter"))?;
+        let folder = match resolve_folder(
+            &self.config,
+            args.get("folder").and_then(serde_json::Value::as_str),
+        ) {
+            Ok(folder) => folder,
+            Err(e) => return Ok(blocked(&e.to_string())),
+        };
+        let max_chars = args
+            .get("max_chars")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|n| usize::try_from(n).ok())
+            .unwrap_or(DEFAULT_BODY_CHARS)
+            .clamp(1, MAX_BODY_CHARS);
+
+        if self.config.mark_seen && !self.security.can_act() {
+            return Ok(blocked(
+                "Action blocked: autonomy is read-only and mailbox.mark_seen changes flags",
+            ));
+        }
+        if !self.security.record_action() {
+            return Ok(blocked("Action blocked: rate limit exceeded"));
+        }
+
+        match with_timeout(self.read(folder, uid, max_chars)).await {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(blocked(&format!("Reading the message failed: {e}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_criteria_quote_terms_and_convert_dates() {
+        assert_eq!(search_criteria(&json!({})).unwrap(), "ALL");
+        assert_eq!(
+            search_criteria(&json!({
+                "from": "billing@acme.com",
+                "subject": "Invoice \"Q3\"",
+                "since": "2026-10-01",
+                "unread_only": true
+            }))
+            .unwrap(),
+            "FROM \"billing@acme.com\" SUBJECT \"Invoice \\\"Q3\\\"\" SINCE 01-Oct-2026 UNSEEN"
+        );
+        assert!(search_criteria(&json!({ "text": "Rechnung für Oktober" }))
+            .unwrap()
+            .starts_with("CHARSET UTF-8 TEXT"));
+        assert!(search_criteria(&json!({ "subject": "a\r\nA1 DELETE INBOX" })).is_err());
+        assert!(search_criteria(&json!({ "since": "yesterday" })).is_err());
+    }
+
+    #[test]
+    fn folders_are_limited_to_the_configured_list() {
+        let config = MailboxConfig {
+            folders: vec!["INBOX".into(), "Receipts".into()],
+            ..MailboxConfig::default()
+        };
+        assert_eq!(resolve_folder(&config, None).unwrap(), "INBOX");
+        assert_eq!(
+            resolve_folder(&config, Some("receipts")).unwrap(),
+            "Receipts"
+        );
+        assert!(resolve_folder(&config, Some("Trash")).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use super::traits::{Tool, ToolResult};
+use crate::memory::principal::{self, MemoryScope};
 use crate::memory::Memory;
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
@@ -58,7 +59,43 @@ impl Tool for MemoryForgetTool {
             });
         }
 
-        match self.memory.forget(key).await {
+        // A principal addresses its own entries by bare key first, then its
+        // channel's, then global ones, and may only delete where it may write.
+        let target = match principal::current_principal() {
+            Some(principal) => {
+                let mut found = None;
+                for scope in [
+                    MemoryScope::Principal,
+                    MemoryScope::Channel,
+                    MemoryScope::Global,
+                ] {
+                    let candidate =
+                        principal::scoped_key(principal.scope_namespace(scope).as_deref(), key);
+                    if let Ok(Some(entry)) = self.memory.get(&candidate).await {
+                        if principal::is_visible_to(&entry, Some(&principal)) {
+                            found = Some((candidate, entry));
+                            break;
+                        }
+                    }
+                }
+                match found {
+                    Some((_, entry)) if !principal::is_writable_by(&entry, Some(&principal)) => {
+                        return Ok(ToolResult {
+                            success: false,
+                            output: String::new(),
+                            error: Some(format!(
+                                "Memory '{key}' is shared and cannot be forgotten by this sender"
+                            )),
+                        });
+                    }
+                    Some((candidate, _)) => candidate,
+                    None => principal::scoped_key(Some(&principal.namespace()), key),
+                }
+            }
+            None => key.to_string(),
+        };
+
+        match self.memory.forget(&target).await {
             Ok(true) => Ok(ToolResult {
                 success: true,
                 output: format!("Forgot memory: {key}"),
@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::memory::Memory;
+use crate::memory::{principal, Memory};
 use async_trait::async_trait;
 use serde_json::json;
 use std::fmt::Write;
@@ -55,7 +55,15 @@ impl Tool for MemoryRecallTool {
             .and_then(serde_json::Value::as_u64)
             .map_or(5, |v| v as usize);
 
-        match self.memory.recall(query, limit, None).await {
+        let principal = principal::current_principal();
+        match principal::recall_for_principal(
+            self.memory.as_ref(),
+            query,
+            limit,
+            principal.as_ref(),
+        )
+        .await
+        {
             Ok(entries) if entries.is_empty() => Ok(ToolResult {
                 success: true,
                 output: "No memories found matching that query.".into(),
@@ -70,7 +78,9 @@ impl Tool for MemoryRecallTool {
                     let _ = writeln!(
                         output,
                         "- [{}] {}: {}{score}",
-                        entry.category, entry.key, entry.content
+                        entry.category,
+                        principal::display_key(&entry),
+                        entry.content
                     );
                 }
                 Ok(ToolResult {
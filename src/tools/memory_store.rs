@@ -1,4 +1,5 @@
 use super::traits::{Tool, ToolResult};
+use crate::memory::principal::{self, MemoryScope};
 use crate::memory::{Memory, MemoryCategory};
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
@@ -43,6 +44,11 @@ impl Tool for MemoryStoreTool {
                 "category": {
                     "type": "string",
                     "description": "Memory category: 'core' (permanent), 'daily' (session), 'conversation' (chat), or a custom category name. Defaults to 'core'."
+                },
+                "scope": {
+                    "type": "string",
+                    "enum": ["principal", "channel", "global"],
+                    "description": "Who can recall this memory on channels: 'principal' (only the current user, default), 'channel' (everyone on this channel), or 'global' (everyone)."
                 }
             },
             "required": ["key", "content"]
@@ -78,7 +84,50 @@ impl Tool for MemoryStoreTool {
             });
         }
 
-        match self.memory.store(key, content, category, None).await {
+        let scope = match args.get("scope").and_then(|v| v.as_str()) {
+            None => MemoryScope::Principal,
+            Some(raw) => match MemoryScope::parse(raw) {
+                Some(scope) => scope,
+                None => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!(
+                            "Unknown scope '{raw}'; use principal, channel, or global"
+                        )),
+                    });
+                }
+            },
+        };
+        let namespace = match principal::current_principal() {
+            Some(principal) if !principal.can_write(scope) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Writing {} memory is not allowed for this sender",
+                        scope.as_str()
+                    )),
+                });
+            }
+            Some(principal) => principal.scope_namespace(scope),
+            None => None,
+        };
+
+        let stored_key = principal::scoped_key(namespace.as_deref(), key);
+        let previous = self.memory.get(&stored_key).await.ok().flatten();
+        super::undo::record_memory_write(
+            &stored_key,
+            namespace.as_deref(),
+            previous
+                .as_ref()
+                .map(|entry| (entry.content.as_str(), &entry.category)),
+        );
+        match self
+            .memory
+            .store(&stored_key, content, category, namespace.as_deref())
+            .await
+        {
             Ok(()) => Ok(ToolResult {
                 success: true,
                 output: format!("Stored memory: {key}"),
@@ -221,4 +270,53 @@ mod tests {
             .contains("Rate limit exceeded"));
         assert!(mem.get("lang").await.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn store_respects_principal_scope_access() {
+        use crate::config::MemoryAccessConfig;
+        use crate::memory::principal::{MemoryPrincipal, MEMORY_PRINCIPAL};
+
+        let (_tmp, mem) = test_mem();
+        let tool = MemoryStoreTool::new(mem.clone(), test_security());
+        let alice = MemoryPrincipal::new("telegram", "alice", MemoryAccessConfig::default());
+
+        let (own, shared, global) = MEMORY_PRINCIPAL
+            .scope(Some(alice.clone()), async {
+                (
+                    tool.execute(json!({"key": "lang", "content": "Prefers Rust"}))
+                        .await
+                        .unwrap(),
+                    tool.execute(
+                        json!({"key": "standup", "content": "Daily at 9", "scope": "channel"}),
+                    )
+                    .await
+                    .unwrap(),
+                    tool.execute(json!({"key": "motd", "content": "Hi", "scope": "global"}))
+                        .await
+                        .unwrap(),
+                )
+            })
+            .await;
+
+        assert!(own.success);
+        let entry = mem
+            .get(&principal::scoped_key(Some(&alice.namespace()), "lang"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.session_id, Some(alice.namespace()));
+
+        assert!(shared.success);
+        assert!(mem
+            .get(&principal::scoped_key(
+                Some(&alice.channel_namespace()),
+                "standup"
+            ))
+            .await
+            .unwrap()
+            .is_some());
+
+        assert!(!global.success);
+        assert!(mem.get("motd").await.unwrap().is_none());
+    }
 }
@@ -19,9 +19,13 @@ pub mod agents_ipc;
 pub mod apply_patch;
 pub mod browser;
 pub mod browser_open;
+pub mod calc;
+pub mod cli;
 pub mod cli_discovery;
 pub mod composio;
+pub mod concurrency;
 pub mod content_search;
+pub mod convert_document;
 pub mod cron_add;
 pub mod cron_list;
 pub mod cron_remove;
@@ -30,12 +34,14 @@ pub mod cron_runs;
 pub mod cron_update;
 pub mod delegate;
 pub mod delegate_coordination_status;
+pub mod feed_fetch;
 #[cfg(feature = "channel-lark")]
 pub mod feishu_doc;
 pub mod file_edit;
 pub mod file_read;
 pub mod file_write;
 pub mod git_operations;
+pub mod git_tools;
 pub mod glob_search;
 #[cfg(feature = "hardware")]
 pub mod hardware_board_info;
@@ -45,6 +51,14 @@ pub mod hardware_memory_map;
 pub mod hardware_memory_read;
 pub mod http_request;
 pub mod image_info;
+pub mod jira;
+pub mod job_kill;
+pub mod job_logs;
+pub mod job_status;
+pub mod jobs;
+pub mod kb_search;
+pub mod linear;
+pub mod mailbox;
 pub mod mcp_client;
 pub mod mcp_protocol;
 pub mod mcp_tool;
@@ -53,20 +67,25 @@ pub mod memory_forget;
 pub mod memory_recall;
 pub mod memory_store;
 pub mod model_routing_config;
+pub mod notify;
 pub mod pdf_read;
 pub mod process;
+pub mod prom_query;
 pub mod proxy_config;
 pub mod pushover;
+pub mod registry;
 pub mod schedule;
 pub mod schema;
 pub mod screenshot;
 pub mod shell;
+pub mod shell_session;
 pub mod subagent_list;
 pub mod subagent_manage;
 pub mod subagent_registry;
 pub mod subagent_spawn;
 pub mod task_plan;
 pub mod traits;
+pub mod undo;
 pub mod url_validation;
 pub mod wasm_module;
 pub mod wasm_tool;
@@ -74,12 +93,15 @@ pub mod web_access_config;
 pub mod web_fetch;
 pub mod web_search_config;
 pub mod web_search_tool;
+pub mod workflow_run;
 
 pub use apply_patch::ApplyPatchTool;
 pub use browser::{BrowserTool, ComputerUseConfig};
 pub use browser_open::BrowserOpenTool;
+pub use calc::CalcTool;
 pub use composio::ComposioTool;
 pub use content_search::ContentSearchTool;
+pub use convert_document::ConvertDocumentTool;
 pub use cron_add::CronAddTool;
 pub use cron_list::CronListTool;
 pub use cron_remove::CronRemoveTool;
@@ -88,12 +110,14 @@ pub use cron_runs::CronRunsTool;
 pub use cron_update::CronUpdateTool;
 pub use delegate::DelegateTool;
 pub use delegate_coordination_status::DelegateCoordinationStatusTool;
+pub use feed_fetch::FeedFetchTool;
 #[cfg(feature = "channel-lark")]
 pub use feishu_doc::FeishuDocTool;
 pub use file_edit::FileEditTool;
 pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
 pub use git_operations::GitOperationsTool;
+pub use git_tools::WorkspaceGitTool;
 pub use glob_search::GlobSearchTool;
 #[cfg(feature = "hardware")]
 pub use hardware_board_info::HardwareBoardInfoTool;
@@ -103,14 +127,24 @@ pub use hardware_memory_map::HardwareMemoryMapTool;
 pub use hardware_memory_read::HardwareMemoryReadTool;
 pub use http_request::HttpRequestTool;
 pub use image_info::ImageInfoTool;
+pub use jira::{JiraCreateIssueTool, JiraSearchTool};
+pub use job_kill::JobKillTool;
+pub use job_logs::JobLogsTool;
+pub use job_status::JobStatusTool;
+pub use jobs::JobRegistry;
+pub use kb_search::KbSearchTool;
+pub use linear::{LinearCreateIssueTool, LinearSearchTool};
+pub use mailbox::{EmailReadTool, EmailSearchTool};
 pub use mcp_client::McpRegistry;
 pub use mcp_tool::McpToolWrapper;
 pub use memory_forget::MemoryForgetTool;
 pub use memory_recall::MemoryRecallTool;
 pub use memory_store::MemoryStoreTool;
 pub use model_routing_config::ModelRoutingConfigTool;
+pub use notify::NotifyTool;
 pub use pdf_read::PdfReadTool;
 pub use process::ProcessTool;
+pub use prom_query::PromQueryTool;
 pub use proxy_config::ProxyConfigTool;
 pub use pushover::PushoverTool;
 pub use schedule::ScheduleTool;
@@ -118,6 +152,7 @@ pub use schedule::ScheduleTool;
 pub use schema::{CleaningStrategy, SchemaCleanr};
 pub use screenshot::ScreenshotTool;
 pub use shell::ShellTool;
+pub use shell_session::ShellSessionTool;
 pub use subagent_list::SubAgentListTool;
 pub use subagent_manage::SubAgentManageTool;
 pub use subagent_registry::SubAgentRegistry;
@@ -131,11 +166,12 @@ pub use web_access_config::WebAccessConfigTool;
 pub use web_fetch::WebFetchTool;
 pub use web_search_config::WebSearchConfigTool;
 pub use web_search_tool::WebSearchTool;
+pub use workflow_run::WorkflowRunTool;
 
 use crate::config::{Config, DelegateAgentConfig};
 use crate::memory::Memory;
 use crate::runtime::{NativeRuntime, RuntimeAdapter};
-use crate::security::SecurityPolicy;
+use crate::security::{EgressPolicy, SecurityPolicy};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -281,6 +317,7 @@ pub fn all_tools_with_runtime(
         Arc::new(MemoryForgetTool::new(memory, security.clone())),
         Arc::new(ScheduleTool::new(security.clone(), root_config.clone())),
         Arc::new(TaskPlanTool::new(security.clone())),
+        Arc::new(CalcTool::new()),
         Arc::new(ModelRoutingConfigTool::new(
             config.clone(),
             security.clone(),
@@ -292,14 +329,102 @@ pub fn all_tools_with_runtime(
             security.clone(),
             workspace_dir.to_path_buf(),
         )),
+        Arc::new(WorkflowRunTool::new(config.clone(), security.clone())),
     ];
 
+    if root_config.convert_document.enabled {
+        tool_arcs.push(Arc::new(ConvertDocumentTool::new(
+            security.clone(),
+            root_config.convert_document.clone(),
+        )));
+    }
+
+    if !root_config.feeds.sources.is_empty() {
+        tool_arcs.push(Arc::new(FeedFetchTool::new(
+            security.clone(),
+            root_config.feeds.clone(),
+            workspace_dir.to_path_buf(),
+        )));
+    }
+
+    if root_config.mailbox.enabled {
+        let mailbox = Arc::new(root_config.mailbox.clone());
+        tool_arcs.push(Arc::new(EmailSearchTool::new(
+            security.clone(),
+            mailbox.clone(),
+        )));
+        tool_arcs.push(Arc::new(EmailReadTool::new(security.clone(), mailbox)));
+    }
+
+    if let Some(kb) = crate::kb::KnowledgeBase::if_enabled(root_config) {
+        tool_arcs.push(Arc::new(KbSearchTool::new(kb)));
+    }
+
+    if root_config.jira.enabled {
+        let jira = Arc::new(root_config.jira.clone());
+        tool_arcs.push(Arc::new(JiraSearchTool::new(
+            security.clone(),
+            jira.clone(),
+        )));
+        tool_arcs.push(Arc::new(JiraCreateIssueTool::new(security.clone(), jira)));
+    }
+
+    if root_config.linear.enabled {
+        let linear = Arc::new(root_config.linear.clone());
+        tool_arcs.push(Arc::new(LinearSearchTool::new(
+            security.clone(),
+            linear.clone(),
+        )));
+        tool_arcs.push(Arc::new(LinearCreateIssueTool::new(
+            security.clone(),
+            linear,
+        )));
+    }
+
+    if root_config.prometheus.enabled {
+        tool_arcs.push(Arc::new(PromQueryTool::new(
+            security.clone(),
+            root_config.prometheus.clone(),
+        )));
+    }
+
+    if !root_config.notify.targets.is_empty() {
+        tool_arcs.push(Arc::new(NotifyTool::new(
+            security.clone(),
+            root_config.notify.clone(),
+        )));
+    }
+
     if has_shell_access {
-        tool_arcs.push(Arc::new(ShellTool::new_with_syscall_detector(
+        let shell = ShellTool::new_with_syscall_detector(
             security.clone(),
             runtime.clone(),
             Some(syscall_detector.clone()),
-        )));
+        );
+        if runtime.supports_long_running() {
+            let jobs = Arc::new(JobRegistry::new(Some(syscall_detector.clone())));
+            tool_arcs.push(Arc::new(shell.with_jobs(jobs.clone())));
+            tool_arcs.push(Arc::new(JobStatusTool::new(jobs.clone())));
+            tool_arcs.push(Arc::new(JobLogsTool::new(jobs.clone())));
+            tool_arcs.push(Arc::new(JobKillTool::new(security.clone(), jobs)));
+        } else {
+            tool_arcs.push(Arc::new(shell));
+        }
+        if cfg!(unix) && root_config.shell_session.enabled {
+            let audit = crate::security::AuditLogger::new(
+                root_config.security.audit.clone(),
+                zeroclaw_dir.clone(),
+            )
+            .ok()
+            .map(Arc::new);
+            tool_arcs.push(Arc::new(ShellSessionTool::new(
+                security.clone(),
+                runtime.clone(),
+                root_config.shell_session.clone(),
+                Some(syscall_detector.clone()),
+                audit,
+            )));
+        }
         tool_arcs.push(Arc::new(ProcessTool::new_with_syscall_detector(
             security.clone(),
             runtime.clone(),
@@ -318,6 +443,9 @@ pub fn all_tools_with_runtime(
         tool_arcs.push(Arc::new(ApplyPatchTool::new()));
         tool_arcs.push(Arc::new(GlobSearchTool::new(security.clone())));
         tool_arcs.push(Arc::new(ContentSearchTool::new(security.clone())));
+        for tool in WorkspaceGitTool::all(security, workspace_dir) {
+            tool_arcs.push(Arc::new(tool));
+        }
     }
     if runtime.as_any().is::<crate::runtime::WasmRuntime>() {
         tool_arcs.push(Arc::new(WasmModuleTool::new(
@@ -326,67 +454,86 @@ pub fn all_tools_with_runtime(
         )));
     }
 
+    // One egress policy shared by every network-capable tool, and by every
+    // registry in the process built from the same rules.
+    let egress = EgressPolicy::shared(
+        root_config.security.url_access.clone(),
+        &root_config.security.egress,
+    );
+
     if browser_config.enabled {
         // Add legacy browser_open tool for simple URL opening
         let browser_choice = browser_open::BrowserChoice::from_str(&browser_config.browser_open);
         if browser_choice != browser_open::BrowserChoice::Disable {
-            tool_arcs.push(Arc::new(BrowserOpenTool::new(
+            tool_arcs.push(Arc::new(
+                BrowserOpenTool::new(
+                    security.clone(),
+                    browser_config.allowed_domains.clone(),
+                    root_config.security.url_access.clone(),
+                    browser_choice,
+                )
+                .with_egress_policy(egress.clone()),
+            ));
+        }
+        // Add full browser automation tool (pluggable backend)
+        tool_arcs.push(Arc::new(
+            BrowserTool::new_with_backend_and_url_access(
                 security.clone(),
                 browser_config.allowed_domains.clone(),
                 root_config.security.url_access.clone(),
-                browser_choice,
-            )));
-        }
-        // Add full browser automation tool (pluggable backend)
-        tool_arcs.push(Arc::new(BrowserTool::new_with_backend_and_url_access(
-            security.clone(),
-            browser_config.allowed_domains.clone(),
-            root_config.security.url_access.clone(),
-            browser_config.session_name.clone(),
-            browser_config.backend.clone(),
-            browser_config.auto_backend_priority.clone(),
-            browser_config.agent_browser_command.clone(),
-            browser_config.agent_browser_extra_args.clone(),
-            browser_config.agent_browser_timeout_ms,
-            browser_config.native_headless,
-            browser_config.native_webdriver_url.clone(),
-            browser_config.native_chrome_path.clone(),
-            ComputerUseConfig {
-                endpoint: browser_config.computer_use.endpoint.clone(),
-                api_key: browser_config.computer_use.api_key.clone(),
-                timeout_ms: browser_config.computer_use.timeout_ms,
-                allow_remote_endpoint: browser_config.computer_use.allow_remote_endpoint,
-                window_allowlist: browser_config.computer_use.window_allowlist.clone(),
-                max_coordinate_x: browser_config.computer_use.max_coordinate_x,
-                max_coordinate_y: browser_config.computer_use.max_coordinate_y,
-            },
-        )));
+                browser_config.session_name.clone(),
+                browser_config.backend.clone(),
+                browser_config.auto_backend_priority.clone(),
+                browser_config.agent_browser_command.clone(),
+                browser_config.agent_browser_extra_args.clone(),
+                browser_config.agent_browser_timeout_ms,
+                browser_config.native_headless,
+                browser_config.native_webdriver_url.clone(),
+                browser_config.native_chrome_path.clone(),
+                ComputerUseConfig {
+                    endpoint: browser_config.computer_use.endpoint.clone(),
+                    api_key: browser_config.computer_use.api_key.clone(),
+                    timeout_ms: browser_config.computer_use.timeout_ms,
+                    allow_remote_endpoint: browser_config.computer_use.allow_remote_endpoint,
+                    window_allowlist: browser_config.computer_use.window_allowlist.clone(),
+                    max_coordinate_x: browser_config.computer_use.max_coordinate_x,
+                    max_coordinate_y: browser_config.computer_use.max_coordinate_y,
+                },
+            )
+            .with_egress_policy(egress.clone()),
+        ));
     }
 
     if http_config.enabled {
-        tool_arcs.push(Arc::new(HttpRequestTool::new(
-            security.clone(),
-            http_config.allowed_domains.clone(),
-            root_config.security.url_access.clone(),
-            http_config.max_response_size,
-            http_config.timeout_secs,
-            http_config.user_agent.clone(),
-        )));
+        tool_arcs.push(Arc::new(
+            HttpRequestTool::new(
+                security.clone(),
+                http_config.allowed_domains.clone(),
+                root_config.security.url_access.clone(),
+                http_config.max_response_size,
+                http_config.timeout_secs,
+                http_config.user_agent.clone(),
+            )
+            .with_egress_policy(egress.clone()),
+        ));
     }
 
     if web_fetch_config.enabled {
-        tool_arcs.push(Arc::new(WebFetchTool::new(
-            security.clone(),
-            web_fetch_config.provider.clone(),
-            web_fetch_config.api_key.clone(),
-            web_fetch_config.api_url.clone(),
-            web_fetch_config.allowed_domains.clone(),
-            web_fetch_config.blocked_domains.clone(),
-            root_config.security.url_access.clone(),
-            web_fetch_config.max_response_size,
-            web_fetch_config.timeout_secs,
-            web_fetch_config.user_agent.clone(),
-        )));
+        tool_arcs.push(Arc::new(
+            WebFetchTool::new(
+                security.clone(),
+                web_fetch_config.provider.clone(),
+                web_fetch_config.api_key.clone(),
+                web_fetch_config.api_url.clone(),
+                web_fetch_config.allowed_domains.clone(),
+                web_fetch_config.blocked_domains.clone(),
+                root_config.security.url_access.clone(),
+                web_fetch_config.max_response_size,
+                web_fetch_config.timeout_secs,
+                web_fetch_config.user_agent.clone(),
+            )
+            .with_egress_policy(egress.clone()),
+        ));
     }
 
     // Web search tool (enabled by default for GLM and other models)
@@ -464,6 +611,7 @@ pub fn all_tools_with_runtime(
                 .map(|mode| mode.as_compatible_mode()),
             max_tokens_override: None,
             model_support_vision: root_config.model_support_vision,
+            model_support_tools: root_config.model_support_tools,
         };
         let parent_tools = Arc::new(tool_arcs.clone());
         let mut delegate_tool = DelegateTool::new_with_options(
@@ -659,6 +807,7 @@ mod tests {
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(!names.contains(&"browser_open"));
         assert!(names.contains(&"schedule"));
+        assert!(names.contains(&"calc"));
         assert!(names.contains(&"model_routing_config"));
         assert!(names.contains(&"pushover"));
         assert!(names.contains(&"proxy_config"));
@@ -746,6 +895,7 @@ mod tests {
         assert!(names.contains(&"wasm_module"));
         assert!(!names.contains(&"shell"));
         assert!(!names.contains(&"process"));
+        assert!(!names.contains(&"shell_session"));
         assert!(!names.contains(&"git_operations"));
         assert!(!names.contains(&"file_read"));
         assert!(!names.contains(&"file_write"));
@@ -877,6 +1027,7 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
 
@@ -962,6 +1113,7 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
 
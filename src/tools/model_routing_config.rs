@@ -682,6 +682,7 @@ impl ModelRoutingConfigTool {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: DEFAULT_AGENT_MAX_ITERATIONS,
+                max_tokens: None,
             });
 
         next_agent.provider = provider;
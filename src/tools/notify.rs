@@ -0,0 +1,171 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::NotifyConfig;
+use crate::notify::{self, Notification};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Sends push notifications to the targets in `[notify.targets]`.
+pub struct NotifyTool {
+    security: Arc<SecurityPolicy>,
+    config: NotifyConfig,
+}
+
+impl NotifyTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: NotifyConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn target_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.config.targets.keys().cloned().collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+#[async_trait]
+impl Tool for NotifyTool {
+    fn name(&self) -> &str {
+        "notify"
+    }
+
+    fn description(&self) -> &str {
+        "Send a push notification to the user's phone through a configured ntfy, Pushover or Gotify target. Use for alerts that must reach the user outside the chat."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Notification text (plain text)"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Optional notification title"
+                },
+                "target": {
+                    "type": "string",
+                    "enum": self.target_names(),
+                    "description": "Push target; omit to use the default target"
+                },
+                "priority": {
+                    "type": "string",
+                    "enum": ["low", "normal", "high"],
+                    "description": "Overrides the target's configured priority"
+                }
+            },
+            "required": ["message"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: rate limit exceeded".into()),
+            });
+        }
+
+        let message = args
+            .get("message")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'message' parameter"))?;
+
+        let priority = match args.get("priority").and_then(serde_json::Value::as_str) {
+            Some(raw) => match notify::parse_priority(raw) {
+                Some(priority) => Some(priority),
+                None => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!(
+                            "Invalid 'priority': {raw}. Expected low, normal or high"
+                        )),
+                    })
+                }
+            },
+            None => None,
+        };
+
+        let notification = Notification {
+            title: args
+                .get("title")
+                .and_then(serde_json::Value::as_str)
+                .map(String::from),
+            message: crate::channels::render::render_for_channel("notify", message),
+            priority,
+        };
+        let target = args.get("target").and_then(serde_json::Value::as_str);
+
+        match notify::send(&self.config, target, &notification).await {
+            Ok(name) => Ok(ToolResult {
+                success: true,
+                output: format!("Notification sent to {name}"),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn tool(level: AutonomyLevel) -> NotifyTool {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: level,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        });
+        let config: NotifyConfig = toml::from_str(
+            r#"
+[targets.phone]
+service = "ntfy"
+topic = "alerts"
+"#,
+        )
+        .unwrap();
+        NotifyTool::new(security, config)
+    }
+
+    #[tokio::test]
+    async fn notify_tool_lists_targets_and_checks_arguments() {
+        let tool = tool(AutonomyLevel::Full);
+        let schema = tool.parameters_schema();
+        assert_eq!(schema["properties"]["target"]["enum"], json!(["phone"]));
+
+        let result = tool
+            .execute(json!({ "message": "hi", "priority": "loud" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Invalid 'priority'"));
+        assert!(tool.execute(json!({ "message": "  " })).await.is_err());
+
+        let result = tool(AutonomyLevel::ReadOnly)
+            .execute(json!({ "message": "hi" }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
@@ -19,9 +19,9 @@ const MAX_OUTPUT_BYTES: usize = 524_288;
 const MAX_PROCESSES: usize = 8;
 
 #[derive(Debug, Default, Clone)]
-struct OutputBuffer {
-    data: String,
-    dropped_prefix_bytes: u64,
+pub(super) struct OutputBuffer {
+    pub(super) data: String,
+    pub(super) dropped_prefix_bytes: u64,
 }
 
 struct ProcessEntry {
@@ -397,7 +397,7 @@ fn parse_id(args: &serde_json::Value, action: &str) -> anyhow::Result<usize> {
 }
 
 /// Append data to a bounded buffer, draining oldest bytes when over limit.
-fn append_bounded(buf: &Mutex<OutputBuffer>, new_data: &str) {
+pub(super) fn append_bounded(buf: &Mutex<OutputBuffer>, new_data: &str) {
     let mut guard = buf.lock().unwrap();
     guard.data.push_str(new_data);
     if guard.data.len() > MAX_OUTPUT_BYTES {
@@ -415,7 +415,7 @@ fn append_bounded(buf: &Mutex<OutputBuffer>, new_data: &str) {
 }
 
 /// Spawn a background task that reads from an async reader into a bounded buffer.
-fn spawn_reader_task<R: tokio::io::AsyncRead + Unpin + Send + 'static>(
+pub(super) fn spawn_reader_task<R: tokio::io::AsyncRead + Unpin + Send + 'static>(
     mut reader: R,
     buf: Arc<Mutex<OutputBuffer>>,
 ) {
@@ -433,7 +433,7 @@ fn spawn_reader_task<R: tokio::io::AsyncRead + Unpin + Send + 'static>(
     });
 }
 
-fn snapshot_output_buffer(buf: &Mutex<OutputBuffer>) -> OutputBuffer {
+pub(super) fn snapshot_output_buffer(buf: &Mutex<OutputBuffer>) -> OutputBuffer {
     buf.lock().unwrap().clone()
 }
 
@@ -0,0 +1,450 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::PrometheusConfig;
+use crate::security::SecurityPolicy;
+use crate::util::truncate_with_ellipsis;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Points a range query aims for when no step is given.
+const TARGET_POINTS: u64 = 60;
+
+/// Prometheus rejects range queries with more points per series.
+const MAX_POINTS: u64 = 11_000;
+
+/// Runs PromQL against the endpoint in `[prometheus]` and summarizes the
+/// result compactly.
+pub struct PromQueryTool {
+    security: Arc<SecurityPolicy>,
+    config: PrometheusConfig,
+    allowed: Vec<regex::Regex>,
+}
+
+impl PromQueryTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: PrometheusConfig) -> Self {
+        // Patterns are checked by `Config::validate`; anything invalid that
+        // slips through is dropped rather than allowing every query.
+        let allowed = config
+            .allowed_queries
+            .iter()
+            .filter_map(|pattern| regex::Regex::new(pattern).ok())
+            .collect();
+        Self {
+            security,
+            config,
+            allowed,
+        }
+    }
+
+    fn query_allowed(&self, query: &str) -> bool {
+        if self.config.allowed_queries.is_empty() {
+            return true;
+        }
+        self.allowed.iter().any(|pattern| pattern.is_match(query))
+    }
+
+    fn error(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for PromQueryTool {
+    fn name(&self) -> &str {
+        "prom_query"
+    }
+
+    fn description(&self) -> &str {
+        "Run a PromQL query against the configured Prometheus server. Without 'range' it returns the current value of each series; with 'range' (e.g. \"1h\") it summarizes each series over that window with min/avg/max/last and the change since the start."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "PromQL expression, e.g. histogram_quantile(0.99, sum by (le) (rate(http_request_duration_seconds_bucket[5m])))"
+                },
+                "range": {
+                    "type": "string",
+                    "description": format!(
+                        "Look back this far from now and summarize the series (e.g. 30m, 6h, 1d; max {}h). Omit for an instant query.",
+                        self.config.max_range_hours
+                    )
+                },
+                "step": {
+                    "type": "string",
+                    "description": "Resolution of a range query (e.g. 1m). Defaults to range / 60."
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let query = args
+            .get("query")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|query| !query.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'query' parameter"))?;
+        if !self.query_allowed(query) {
+            return Ok(Self::error(
+                "Query does not match any pattern in prometheus.allowed_queries",
+            ));
+        }
+
+        let window = match args.get("range").and_then(Value::as_str) {
+            Some(raw) => match parse_duration(raw) {
+                Some(range) if range > self.config.max_range_hours.saturating_mul(3600) => {
+                    return Ok(Self::error(format!(
+                        "'range' {raw} exceeds the {}h limit",
+                        self.config.max_range_hours
+                    )))
+                }
+                Some(range) => {
+                    let step = match args.get("step").and_then(Value::as_str) {
+                        Some(raw_step) => match parse_duration(raw_step) {
+                            Some(step) => step,
+                            None => {
+                                return Ok(Self::error(format!(
+                                    "Invalid 'step': {raw_step}. Use a duration like 30s or 5m"
+                                )))
+                            }
+                        },
+                        None => (range / TARGET_POINTS).max(1),
+                    };
+                    if range / step > MAX_POINTS {
+                        return Ok(Self::error(format!(
+                            "'step' {step}s is too small for a {raw} range; use a larger step"
+                        )));
+                    }
+                    Some((range, step))
+                }
+                None => {
+                    return Ok(Self::error(format!(
+                        "Invalid 'range': {raw}. Use a duration like 30m, 6h or 1d"
+                    )))
+                }
+            },
+            None => None,
+        };
+
+        if !self.security.can_act() {
+            return Ok(Self::error("Action blocked: autonomy is read-only"));
+        }
+        if !self.security.record_action() {
+            return Ok(Self::error("Action blocked: rate limit exceeded"));
+        }
+
+        let base = self.config.url.trim().trim_end_matches('/');
+        let now = chrono::Utc::now().timestamp();
+        let mut params = vec![("query", query.to_string())];
+        let url = match window {
+            Some((range, step)) => {
+                let start = now.saturating_sub(i64::try_from(range).unwrap_or(i64::MAX));
+                params.push(("start", start.to_string()));
+                params.push(("end", now.to_string()));
+                params.push(("step", step.to_string()));
+                format!("{base}/api/v1/query_range")
+            }
+            None => {
+                params.push(("time", now.to_string()));
+                format!("{base}/api/v1/query")
+            }
+        };
+
+        let client = crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.prom_query",
+            self.config.timeout_secs,
+            10,
+        );
+        let mut request = client.get(&url).query(&params);
+        if let Some(token) = self
+            .config
+            .bearer_token
+            .as_deref()
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+        {
+            request = request.bearer_auth(token);
+        }
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => return Ok(Self::error(format!("Failed to reach Prometheus: {e}"))),
+        };
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        let Ok(payload) = serde_json::from_str::<Value>(&body) else {
+            return Ok(Self::error(format!(
+                "Prometheus returned {status}: {}",
+                truncate_with_ellipsis(body.trim(), 200)
+            )));
+        };
+        if payload.get("status").and_then(Value::as_str) != Some("success") {
+            let reason = payload
+                .get("error")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Ok(Self::error(format!(
+                "Prometheus returned {status}: {reason}"
+            )));
+        }
+
+        let mut output = summarize(&payload["data"], self.config.max_series);
+        if let Some(warnings) = payload.get("warnings").and_then(Value::as_array) {
+            for warning in warnings.iter().filter_map(Value::as_str) {
+                let _ = write!(output, "\nwarning: {warning}");
+            }
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+/// Parse a Prometheus-style duration (`90s`, `5m`, `6h`, `1d`, `1w`) into
+/// seconds.
+fn parse_duration(raw: &str) -> Option<u64> {
+    let raw = raw.trim();
+    let split = raw.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = raw.split_at(split);
+    let amount: u64 = amount.parse().ok()?;
+    let unit = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86_400,
+        "w" => 604_800,
+        _ => return None,
+    };
+    amount.checked_mul(unit).filter(|secs| *secs > 0)
+}
+
+/// Render the `data` object of a query response as plain text.
+fn summarize(data: &Value, max_series: usize) -> String {
+    let result_type = data
+        .get("resultType")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let series = data
+        .get("result")
+        .and_then(Value::as_array)
+        .map_or(&[][..], Vec::as_slice);
+    match result_type {
+        "scalar" | "string" => format!(
+            "{result_type}: {}",
+            data.get("result")
+                .and_then(|sample| sample.get(1))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+        ),
+        "vector" => {
+            if series.is_empty() {
+                return "No series matched.".into();
+            }
+            let mut output = format!("{} series", series.len());
+            for entry in series.iter().take(max_series) {
+                let value = entry
+                    .get("value")
+                    .and_then(|sample| sample.get(1))
+                    .and_then(sample_value);
+                let _ = write!(
+                    output,
+                    "\n{} {}",
+                    series_name(&entry["metric"]),
+                    value.map_or_else(|| "?".into(), format_value)
+                );
+            }
+            append_omitted(&mut output, series.len(), max_series);
+            output
+        }
+        "matrix" => {
+            if series.is_empty() {
+                return "No series matched.".into();
+            }
+            let mut output = format!("{} series (min / avg / max / last, change)", series.len());
+            for entry in series.iter().take(max_series) {
+                let values: Vec<f64> = entry
+                    .get("values")
+                    .and_then(Value::as_array)
+                    .map(|samples| {
+                        samples
+                            .iter()
+                            .filter_map(|sample| sample.get(1).and_then(sample_value))
+                            .filter(|value| value.is_finite())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let _ = write!(output, "\n{} ", series_name(&entry["metric"]));
+                output.push_str(&series_stats(&values));
+            }
+            append_omitted(&mut output, series.len(), max_series);
+            output
+        }
+        other => format!("Unsupported result type '{other}'"),
+    }
+}
+
+fn series_stats(values: &[f64]) -> String {
+    let (Some(first), Some(last)) = (values.first(), values.last()) else {
+        return "no samples".into();
+    };
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    #[allow(clippy::cast_precision_loss)]
+    let avg = values.iter().sum::<f64>() / values.len() as f64;
+    let change = if *first == 0.0 {
+        let diff = last - first;
+        let sign = if diff > 0.0 { "+" } else { "" };
+        format!("{sign}{}", format_value(diff))
+    } else {
+        format!("{:+.1}%", (last - first) / first.abs() * 100.0)
+    };
+    format!(
+        "{} / {} / {} / {}, {change}",
+        format_value(min),
+        format_value(avg),
+        format_value(max),
+        format_value(*last)
+    )
+}
+
+fn append_omitted(output: &mut String, total: usize, shown: usize) {
+    if total > shown {
+        let _ = write!(output, "\n... {} more series omitted", total - shown);
+    }
+}
+
+fn sample_value(raw: &Value) -> Option<f64> {
+    raw.as_str()?.parse().ok()
+}
+
+/// `metric_name{label="value",...}`, labels in key order.
+fn series_name(metric: &Value) -> String {
+    let Some(labels) = metric.as_object() else {
+        return "{}".into();
+    };
+    let name = labels
+        .get("__name__")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let mut pairs: Vec<(&String, &str)> = labels
+        .iter()
+        .filter(|(key, _)| key.as_str() != "__name__")
+        .map(|(key, value)| (key, value.as_str().unwrap_or_default()))
+        .collect();
+    pairs.sort_unstable();
+    let labels: Vec<String> = pairs
+        .iter()
+        .map(|(key, value)| format!("{key}=\"{value}\""))
+        .collect();
+    if labels.is_empty() && !name.is_empty() {
+        name.to_string()
+    } else {
+        format!("{name}{{{}}}", labels.join(","))
+    }
+}
+
+/// Up to four decimals, without trailing zeros.
+fn format_value(value: f64) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    let formatted = format!("{value:.4}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".into()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_and_values_parse_compactly() {
+        assert_eq!(parse_duration("90s"), Some(90));
+        assert_eq!(parse_duration(" 6h "), Some(21_600));
+        assert_eq!(parse_duration("1d"), Some(86_400));
+        assert_eq!(parse_duration("0m"), None);
+        assert_eq!(parse_duration("1.5h"), None);
+        assert_eq!(parse_duration("h"), None);
+        assert_eq!(format_value(0.250_00), "0.25");
+        assert_eq!(format_value(1200.0), "1200");
+        assert_eq!(format_value(-0.000_01), "0");
+    }
+
+    #[test]
+    fn results_are_summarized_per_series() {
+        let vector = json!({
+            "resultType": "vector",
+            "result": [
+                { "metric": { "__name__": "up", "job": "api", "instance": "a:9100" }, "value": [1, "1"] },
+                { "metric": { "__name__": "up", "job": "db" }, "value": [1, "0"] },
+                { "metric": { "job": "cache" }, "value": [1, "1"] }
+            ]
+        });
+        assert_eq!(
+            summarize(&vector, 2),
+            "3 series\nup{instance=\"a:9100\",job=\"api\"} 1\nup{job=\"db\"} 0\n... 1 more series omitted"
+        );
+
+        let matrix = json!({
+            "resultType": "matrix",
+            "result": [{
+                "metric": { "route": "/login" },
+                "values": [[1, "0.2"], [2, "0.4"], [3, "NaN"], [4, "0.3"]]
+            }]
+        });
+        assert_eq!(
+            summarize(&matrix, 20),
+            "1 series (min / avg / max / last, change)\n{route=\"/login\"} 0.2 / 0.3 / 0.4 / 0.3, +50.0%"
+        );
+        assert_eq!(
+            summarize(&json!({ "resultType": "matrix", "result": [] }), 20),
+            "No series matched."
+        );
+    }
+
+    #[tokio::test]
+    async fn queries_and_ranges_are_checked_before_sending() {
+        let config = PrometheusConfig {
+            enabled: true,
+            url: "http://127.0.0.1:9".into(),
+            allowed_queries: vec![r"^(rate|histogram_quantile)\(".into()],
+            max_range_hours: 6,
+            ..PrometheusConfig::default()
+        };
+        let tool = PromQueryTool::new(Arc::new(SecurityPolicy::default()), config);
+
+        let result = tool
+            .execute(json!({ "query": "count({__name__=~\".+\"})" }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("allowed_queries"));
+
+        let result = tool
+            .execute(json!({ "query": "rate(http_requests_total[5m])", "range": "2d" }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("6h limit"));
+
+        let result = tool
+            .execute(json!({ "query": "rate(x[5m])", "range": "1h", "step": "fast" }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("Invalid 'step'"));
+        assert!(tool.execute(json!({})).await.is_err());
+    }
+}
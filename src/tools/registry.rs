@@ -0,0 +1,307 @@
+//! What the agent is allowed to do, tool by tool.
+//!
+//! [`describe`] lists every registered tool with its risk tier, whether it is
+//! enabled in the current context, the approval policy a call would hit right
+//! now, and the last error it returned in this process. `zeroclaw tool list`,
+//! `GET /api/admin/tools` and the `/tools` chat command all render this list.
+//! In a channel turn the approval column follows the sender's autonomy
+//! override, so `/tools` answers for the person asking.
+
+use super::traits::Tool;
+use crate::approval::ApprovalManager;
+use crate::security::AutonomyLevel;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::LazyLock;
+
+/// How much damage a tool can do when misused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolRisk {
+    /// Reads local state only.
+    Low,
+    /// Writes files, memory or schedules, or talks to the network.
+    Medium,
+    /// Runs arbitrary code, spawns other agents or files tickets in external
+    /// trackers.
+    High,
+}
+
+impl ToolRisk {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+/// Risk tier of a tool by name. Tools not listed here (MCP, WASM plugins,
+/// integrations) are treated as medium.
+pub fn risk_for(name: &str) -> ToolRisk {
+    match name {
+        "file_read"
+        | "content_search"
+        | "glob_search"
+        | "git_status"
+        | "git_diff"
+        | "image_info"
+        | "pdf_read"
+        | "docx_read"
+        | "memory_recall"
+        | "kb_search"
+        | "cron_list"
+        | "cron_runs"
+        | "subagent_list"
+        | "delegate_coordination_status"
+        | "task_plan"
+        | "job_status"
+        | "job_logs"
+        | "calc"
+        | "agents_list"
+        | "agents_inbox"
+        | "sop_list"
+        | "sop_status"
+        | "state_get"
+        | "hardware_board_info"
+        | "hardware_memory_map"
+        | "hardware_memory_read" => ToolRisk::Low,
+        "shell"
+        | "shell_session"
+        | "process"
+        | "delegate"
+        | "subagent_spawn"
+        | "subagent_manage"
+        | "wasm_module"
+        | "browser"
+        | "composio"
+        | "workflow_run"
+        | "jira_create_issue"
+        | "linear_create_issue" => ToolRisk::High,
+        _ => ToolRisk::Medium,
+    }
+}
+
+/// What happens when the agent calls a tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolApprovalPolicy {
+    /// Runs without asking.
+    Auto,
+    /// Asks unless approved for the session.
+    Ask,
+    /// Listed in `always_ask`; asks every time.
+    AlwaysAsk,
+    /// Refused under read-only autonomy.
+    Blocked,
+}
+
+impl ToolApprovalPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Ask => "ask",
+            Self::AlwaysAsk => "always_ask",
+            Self::Blocked => "blocked",
+        }
+    }
+}
+
+/// Approval policy for `tool` on `channel` under `approvals` in the current
+/// turn.
+pub fn approval_policy(
+    approvals: &ApprovalManager,
+    tool: &str,
+    channel: &str,
+) -> ToolApprovalPolicy {
+    if approvals.effective_autonomy_level() == AutonomyLevel::ReadOnly
+        && risk_for(tool) != ToolRisk::Low
+    {
+        return ToolApprovalPolicy::Blocked;
+    }
+    if !approvals.needs_approval(tool, channel) {
+        ToolApprovalPolicy::Auto
+    } else if approvals.is_always_ask(tool, channel) {
+        ToolApprovalPolicy::AlwaysAsk
+    } else {
+        ToolApprovalPolicy::Ask
+    }
+}
+
+/// Most recent failure of a tool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ToolError {
+    pub message: String,
+    pub at: DateTime<Utc>,
+}
+
+/// Longest error message kept per tool.
+const MAX_ERROR_CHARS: usize = 300;
+
+static LAST_ERRORS: LazyLock<Mutex<HashMap<String, ToolError>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Remember `message` as the last error of `tool`. Callers pass text that
+/// has already been scrubbed of credentials.
+pub fn record_error(tool: &str, message: &str) {
+    let message = message.chars().take(MAX_ERROR_CHARS).collect();
+    LAST_ERRORS.lock().insert(
+        tool.to_string(),
+        ToolError {
+            message,
+            at: Utc::now(),
+        },
+    );
+}
+
+/// Last error `tool` returned in this process.
+pub fn last_error(tool: &str) -> Option<ToolError> {
+    LAST_ERRORS.lock().get(tool).cloned()
+}
+
+/// One row of the tool listing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ToolInfo {
+    pub name: String,
+    pub description: String,
+    pub risk: ToolRisk,
+    /// `false` when the tool is excluded in this context
+    /// (`autonomy.non_cli_excluded_tools` on channels).
+    pub enabled: bool,
+    pub approval: ToolApprovalPolicy,
+    pub last_error: Option<ToolError>,
+}
+
+/// Registered tools as seen from the current context, sorted by name.
+pub fn describe(
+    tools: &[Box<dyn Tool>],
+    approvals: &ApprovalManager,
+    excluded: &[String],
+    channel: &str,
+) -> Vec<ToolInfo> {
+    let mut rows: Vec<ToolInfo> = tools
+        .iter()
+        .map(|tool| {
+            let name = tool.name();
+            ToolInfo {
+                name: name.to_string(),
+                description: tool.description().to_string(),
+                risk: risk_for(name),
+                enabled: !excluded.iter().any(|excluded| excluded == name),
+                approval: approval_policy(approvals, name, channel),
+                last_error: last_error(name),
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| a.name.cmp(&b.name));
+    rows
+}
+
+/// Chat rendering of [`describe`].
+pub fn render_for_chat(rows: &[ToolInfo]) -> String {
+    if rows.is_empty() {
+        return "No tools are registered.".to_string();
+    }
+    let mut response = String::from("Tools in this context (risk, approval):");
+    for row in rows {
+        if row.enabled {
+            let _ = write!(
+                response,
+                "\n- `{}` — {}, {}",
+                row.name,
+                row.risk.as_str(),
+                row.approval.as_str()
+            );
+        } else {
+            let _ = write!(response, "\n- `{}` — disabled here", row.name);
+        }
+        if let Some(error) = row.last_error.as_ref() {
+            let _ = write!(
+                response,
+                "\n  last error {}: {}",
+                error.at.format("%Y-%m-%d %H:%M UTC"),
+                error.message
+            );
+        }
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AutonomyConfig;
+    use crate::tools::traits::ToolResult;
+    use async_trait::async_trait;
+
+    struct NamedTool(&'static str);
+
+    #[async_trait]
+    impl Tool for NamedTool {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn description(&self) -> &str {
+            "test tool"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            unreachable!()
+        }
+    }
+
+    #[tokio::test]
+    async fn describe_reports_risk_approval_and_last_error() {
+        let tools: Vec<Box<dyn Tool>> = vec![
+            Box::new(NamedTool("shell")),
+            Box::new(NamedTool("file_read")),
+            Box::new(NamedTool("file_write")),
+        ];
+        let approvals = ApprovalManager::from_config(&AutonomyConfig {
+            auto_approve: vec!["file_read".into()],
+            always_ask: vec!["shell".into()],
+            ..AutonomyConfig::default()
+        });
+        record_error("file_write", "permission denied");
+
+        let rows = describe(&tools, &approvals, &["file_write".to_string()], "cli");
+        let summary: Vec<_> = rows
+            .iter()
+            .map(|row| (row.name.as_str(), row.risk, row.enabled, row.approval))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("file_read", ToolRisk::Low, true, ToolApprovalPolicy::Auto),
+                (
+                    "file_write",
+                    ToolRisk::Medium,
+                    false,
+                    ToolApprovalPolicy::Ask
+                ),
+                ("shell", ToolRisk::High, true, ToolApprovalPolicy::AlwaysAsk),
+            ]
+        );
+        assert_eq!(
+            rows[1].last_error.as_ref().unwrap().message,
+            "permission denied"
+        );
+        assert!(render_for_chat(&rows).contains("`file_write` — disabled here"));
+
+        let read_only = crate::security::with_turn_autonomy(Some(AutonomyLevel::ReadOnly), async {
+            describe(&tools, &approvals, &[], "cli")
+        })
+        .await;
+        assert_eq!(read_only[0].approval, ToolApprovalPolicy::Auto);
+        assert_eq!(read_only[2].approval, ToolApprovalPolicy::Blocked);
+    }
+}
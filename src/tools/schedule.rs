@@ -4,7 +4,6 @@ use crate::cron;
 use crate::security::SecurityPolicy;
 use anyhow::Result;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
 use serde_json::json;
 use std::sync::Arc;
 
@@ -44,7 +43,7 @@ impl Tool for ScheduleTool {
                 },
                 "expression": {
                     "type": "string",
-                    "description": "Cron expression for recurring tasks (e.g. '*/5 * * * *')."
+                    "description": "Cron expression for recurring tasks (e.g. '*/5 * * * *'), in the user's time zone."
                 },
                 "delay": {
                     "type": "string",
@@ -52,7 +51,7 @@ impl Tool for ScheduleTool {
                 },
                 "run_at": {
                     "type": "string",
-                    "description": "Absolute RFC3339 time for one-shot tasks (e.g. '2030-01-01T00:00:00Z')."
+                    "description": "Absolute RFC3339 time for one-shot tasks: RFC3339 (e.g. '2030-01-01T00:00:00Z'), or 'YYYY-MM-DD HH:MM' in the user's time zone."
                 },
                 "command": {
                     "type": "string",
@@ -310,7 +309,11 @@ impl ScheduleTool {
         }
 
         if let Some(value) = expression {
-            let job = cron::add_job(&self.config, value, command)?;
+            let schedule = cron::Schedule::Cron {
+                expr: value.to_string(),
+                tz: crate::i18n::profile::turn_timezone_name(),
+            };
+            let job = cron::add_shell_job(&self.config, None, schedule, command)?;
             return Ok(ToolResult {
                 success: true,
                 output: format!(
@@ -339,9 +342,8 @@ impl ScheduleTool {
         }
 
         let run_at_raw = run_at.ok_or_else(|| anyhow::anyhow!("Missing scheduling parameters"))?;
-        let run_at_parsed: DateTime<Utc> = DateTime::parse_from_rfc3339(run_at_raw)
-            .map_err(|error| anyhow::anyhow!("Invalid run_at timestamp: {error}"))?
-            .with_timezone(&Utc);
+        let run_at_parsed = crate::i18n::profile::parse_user_datetime(run_at_raw)
+            .ok_or_else(|| anyhow::anyhow!("Invalid run_at timestamp: {run_at_raw}"))?;
 
         let job = cron::add_once_at(&self.config, run_at_parsed, command)?;
         Ok(ToolResult {
@@ -56,12 +56,11 @@ impl ScreenshotTool {
             .parent()
             .ok_or_else(|| anyhow::anyhow!("Invalid screenshot output path"))?;
         let resolved_parent = tokio::fs::canonicalize(parent).await?;
-        if !self.security.is_resolved_path_allowed(&resolved_parent) {
-            anyhow::bail!(
-                "{}",
-                self.security
-                    .resolved_path_violation_message(&resolved_parent)
-            );
+        if let Err(reason) = self
+            .security
+            .check_tool_resolved_path(self.name(), &resolved_parent)
+        {
+            anyhow::bail!("{reason}");
         }
 
         match tokio::fs::symlink_metadata(&output_path).await {
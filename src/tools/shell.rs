@@ -1,3 +1,4 @@
+use super::jobs::JobRegistry;
 use super::traits::{Tool, ToolResult};
 use crate::runtime::RuntimeAdapter;
 use crate::security::SecurityPolicy;
@@ -23,6 +24,8 @@ pub struct ShellTool {
     security: Arc<SecurityPolicy>,
     runtime: Arc<dyn RuntimeAdapter>,
     syscall_detector: Option<Arc<SyscallAnomalyDetector>>,
+    /// Set when `background = true` is available (see `job_status`).
+    jobs: Option<Arc<JobRegistry>>,
 }
 
 impl ShellTool {
@@ -39,6 +42,51 @@ impl ShellTool {
             security,
             runtime,
             syscall_detector,
+            jobs: None,
+        }
+    }
+
+    /// Allow `background = true`, starting jobs in `jobs`.
+    pub fn with_jobs(mut self, jobs: Arc<JobRegistry>) -> Self {
+        self.jobs = Some(jobs);
+        self
+    }
+
+    /// Describe what running `command` would do without running it: working
+    /// directory, environment passed through, risk tier and policy verdict.
+    fn preview(&self, command: &str, approved: bool) -> ToolResult {
+        let cwd = self.security.tool_working_dir(self.name());
+        let env: Vec<String> = collect_allowed_shell_env_vars(&self.security)
+            .into_iter()
+            .filter(|var| std::env::var_os(var).is_some())
+            .collect();
+        let risk = format!("{:?}", self.security.command_risk_level(command)).to_ascii_lowercase();
+        let (runnable, verdict) = match self.security.validate_command_execution(command, approved)
+        {
+            Ok(_) => (true, "allowed".to_string()),
+            Err(reason) => match self.security.validate_command_execution(command, true) {
+                Ok(_) => (
+                    true,
+                    format!(
+                        "needs confirmation — {reason}; call again with approved=true to run it"
+                    ),
+                ),
+                Err(reason) => (false, format!("blocked — {reason}")),
+            },
+        };
+
+        ToolResult {
+            success: runnable,
+            output: format!(
+                "Preview, not executed.\ncommand: {command}\ncwd: {}\nenv: {}\nrisk: {risk}\npolicy: {verdict}",
+                cwd.display(),
+                if env.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    env.join(", ")
+                }
+            ),
+            error: None,
         }
     }
 }
@@ -117,7 +165,7 @@ impl Tool for ShellTool {
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
-        json!({
+        let mut schema = json!({
             "type": "object",
             "properties": {
                 "command": {
@@ -126,12 +174,25 @@ impl Tool for ShellTool {
                 },
                 "approved": {
                     "type": "boolean",
-                    "description": "Set true to explicitly approve medium/high-risk commands in supervised mode",
+                    "description": "Set true to explicitly approve medium/high-risk commands in supervised mode, or critical commands (including destructive ones like rm, dd, git push --force) at any autonomy level",
+                    "default": false
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Preview the command (working directory, environment, risk, policy verdict) without running it",
                     "default": false
                 }
             },
             "required": ["command"]
-        })
+        });
+        if self.jobs.is_some() {
+            schema["properties"]["background"] = json!({
+                "type": "boolean",
+                "description": "Start the command as a background job and return its job id at once; follow it with job_status, job_logs and job_kill. Use for builds, test suites and other commands that may run longer than 60 seconds",
+                "default": false
+            });
+        }
+        schema
     }
 
     #[allow(clippy::incompatible_msrv)]
@@ -142,6 +203,28 @@ impl Tool for ShellTool {
             .get("approved")
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
+        if args
+            .get("dry_run")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+        {
+            return Ok(self.preview(&command, approved));
+        }
+        let background = args
+            .get("background")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let jobs = match (background, &self.jobs) {
+            (false, _) => None,
+            (true, Some(jobs)) if self.runtime.supports_long_running() => Some(jobs),
+            (true, _) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("Background jobs are not available here".into()),
+                });
+            }
+        };
 
         if self.security.is_rate_limited() {
             return Ok(ToolResult {
@@ -154,9 +237,21 @@ impl Tool for ShellTool {
         match self.security.validate_command_execution(&command, approved) {
             Ok(_) => {}
             Err(reason) => {
+                // When approval alone would let the command through, show the
+                // model what it is about to confirm.
+                let output = if !approved
+                    && self
+                        .security
+                        .validate_command_execution(&command, true)
+                        .is_ok()
+                {
+                    self.preview(&command, false).output
+                } else {
+                    String::new()
+                };
                 return Ok(ToolResult {
                     success: false,
-                    output: String::new(),
+                    output,
                     error: Some(reason),
                 });
             }
@@ -183,7 +278,7 @@ impl Tool for ShellTool {
         // (CWE-200), then re-add only safe, functional variables.
         let mut cmd = match self
             .runtime
-            .build_shell_command(&command, &self.security.workspace_dir)
+            .build_shell_command(&command, &self.security.tool_working_dir(self.name()))
         {
             Ok(cmd) => cmd,
             Err(e) => {
@@ -202,6 +297,26 @@ impl Tool for ShellTool {
             }
         }
 
+        if let Some(jobs) = jobs {
+            return Ok(match jobs.start(&command, cmd) {
+                Ok((id, pid)) => ToolResult {
+                    success: true,
+                    output: json!({
+                        "job_id": id,
+                        "pid": pid,
+                        "message": format!("Started in the background: {command}. Poll with job_status / job_logs; stop with job_kill.")
+                    })
+                    .to_string(),
+                    error: None,
+                },
+                Err(e) => ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e),
+                },
+            });
+        }
+
         let result =
             tokio::time::timeout(Duration::from_secs(SHELL_TIMEOUT_SECS), cmd.output()).await;
 
@@ -349,6 +464,37 @@ mod tests {
         assert!(result.error.is_none());
     }
 
+    #[tokio::test]
+    async fn shell_background_starts_job_when_enabled() {
+        let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime());
+        assert!(tool.parameters_schema()["properties"]["background"].is_null());
+        let refused = tool
+            .execute(json!({"command": "echo job", "background": true}))
+            .await
+            .unwrap();
+        assert!(!refused.success);
+
+        let jobs = Arc::new(JobRegistry::new(None));
+        let tool = tool.with_jobs(jobs.clone());
+        assert!(tool.parameters_schema()["properties"]["background"].is_object());
+        let started = tool
+            .execute(json!({"command": "echo job", "background": true}))
+            .await
+            .unwrap();
+        assert!(started.success, "{:?}", started.error);
+        let started: serde_json::Value = serde_json::from_str(&started.output).unwrap();
+        let id = usize::try_from(started["job_id"].as_u64().unwrap()).unwrap();
+        assert!(jobs.status(id).is_some());
+
+        // Background jobs go through the same policy checks.
+        let blocked = tool
+            .execute(json!({"command": "rm -rf /", "background": true}))
+            .await
+            .unwrap();
+        assert!(!blocked.success);
+        assert_eq!(jobs.list().len(), 1);
+    }
+
     #[tokio::test]
     async fn shell_executes_command_from_cmd_alias() {
         let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime());
@@ -372,6 +518,37 @@ mod tests {
         assert!(error.contains("not allowed") || error.contains("high-risk"));
     }
 
+    #[tokio::test]
+    async fn shell_dry_run_previews_without_executing() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: tmp.path().to_path_buf(),
+            allowed_commands: vec!["git".into(), "touch".into()],
+            ..SecurityPolicy::default()
+        });
+        let tool = ShellTool::new(security, test_runtime());
+
+        let result = tool
+            .execute(json!({"command": "touch marker.txt", "dry_run": true}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("not executed"));
+        assert!(result.output.contains("cwd: "));
+        assert!(result.output.contains("policy: allowed"));
+        assert!(!tmp.path().join("marker.txt").exists());
+
+        let result = tool
+            .execute(json!({"command": "git push --force origin main"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_deref().unwrap().contains("destructive"));
+        assert!(result.output.contains("risk: critical"));
+        assert!(result.output.contains("approved=true"));
+    }
+
     #[tokio::test]
     async fn shell_blocks_readonly() {
         let tool = ShellTool::new(test_security(AutonomyLevel::ReadOnly), test_runtime());
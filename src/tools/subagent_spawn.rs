@@ -150,7 +150,7 @@ impl Tool for SubAgentSpawnTool {
         }
 
         // Look up agent config
-        let agent_config = match self.agents.get(agent_name) {
+        let mut agent_config = match self.agents.get(agent_name) {
             Some(cfg) => cfg.clone(),
             None => {
                 let available: Vec<&str> =
@@ -170,6 +170,26 @@ impl Tool for SubAgentSpawnTool {
             }
         };
 
+        // Background sub-agents run one level below the caller and get the
+        // same policy-capped budget as `delegate`.
+        let budget = match self.security.delegation_budget(
+            0,
+            agent_config.max_depth,
+            agent_config.max_iterations,
+            agent_config.max_tokens,
+        ) {
+            Ok(budget) => budget,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                });
+            }
+        };
+        agent_config.max_iterations = budget.max_iterations;
+        agent_config.max_tokens = budget.max_tokens;
+
         // Create provider for this agent
         let provider_credential_owned = agent_config
             .api_key
@@ -178,10 +198,15 @@ impl Tool for SubAgentSpawnTool {
         #[allow(clippy::option_as_ref_deref)]
         let provider_credential = provider_credential_owned.as_ref().map(String::as_str);
 
+        let mut provider_runtime_options = self.provider_runtime_options.clone();
+        if budget.max_tokens.is_some() {
+            provider_runtime_options.max_tokens_override = budget.max_tokens;
+        }
+
         let provider: Box<dyn Provider> = match providers::create_provider_with_options(
             &agent_config.provider,
             provider_credential,
-            &self.provider_runtime_options,
+            &provider_runtime_options,
         ) {
             Ok(p) => p,
             Err(e) => {
@@ -525,6 +550,7 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                max_tokens: None,
             },
         );
         agents
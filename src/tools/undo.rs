@@ -0,0 +1,598 @@
+//! Per-turn undo journal for reversible tool actions.
+//!
+//! `file_write`, `file_edit`, git commits and `memory_store` record what
+//! they change in `<workspace>/state/undo.db` under the running turn's id:
+//! the previous file content (or that the file did not exist yet), the
+//! commit they created, or the memory entry they replaced. `/undo` on
+//! channels and `zeroclaw undo last` revert the latest turn that changed
+//! anything, newest action first. A commit is only undone while it is still
+//! `HEAD`, with `git reset --soft` so its changes stay staged. Entries older
+//! than a week are pruned.
+
+use crate::config::AgentConfig;
+use crate::db::{migrate, Migration, Schema, Step};
+use crate::memory::{Memory, MemoryCategory};
+use crate::observability::turn;
+use crate::workspace::Scoped;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Files larger than this are not backed up, so their writes cannot be
+/// undone.
+const MAX_BACKUP_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Journal entries older than this are pruned when the journal opens.
+const RETENTION_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Kind of a reversible action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UndoKind {
+    File,
+    GitCommit,
+    Memory,
+}
+
+impl UndoKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::GitCommit => "git_commit",
+            Self::Memory => "memory",
+        }
+    }
+
+    fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "file" => Some(Self::File),
+            "git_commit" => Some(Self::GitCommit),
+            "memory" => Some(Self::Memory),
+            _ => None,
+        }
+    }
+}
+
+/// One recorded action.
+#[derive(Debug, Clone, Serialize)]
+pub struct UndoEntry {
+    pub id: i64,
+    pub turn_id: String,
+    pub principal: Option<String>,
+    pub kind: UndoKind,
+    /// File path, `<repo>@<commit>`, or stored memory key.
+    pub target: String,
+    /// Previous file or memory content; `None` when it did not exist.
+    #[serde(skip)]
+    pub previous: Option<Vec<u8>>,
+    /// Memory namespace and category as JSON.
+    #[serde(skip)]
+    pub detail: Option<String>,
+    pub recorded_at: i64,
+}
+
+/// Turns with actions that can still be undone.
+#[derive(Debug, Clone, Serialize)]
+pub struct UndoTurn {
+    pub turn_id: String,
+    pub principal: Option<String>,
+    pub actions: u64,
+    pub last_recorded_at: i64,
+}
+
+/// Tables of `state/undo.db`.
+pub static SCHEMA: Schema = Schema {
+    name: "undo_journal",
+    path: "state/undo.db",
+    migrations: &[Migration {
+        version: 1,
+        description: "create undo_journal",
+        step: Step::Sql(
+            "CREATE TABLE IF NOT EXISTS undo_journal (
+                 id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                 turn_id     TEXT NOT NULL,
+                 principal   TEXT,
+                 kind        TEXT NOT NULL,
+                 target      TEXT NOT NULL,
+                 previous    BLOB,
+                 detail      TEXT,
+                 recorded_at INTEGER NOT NULL,
+                 undone_at   INTEGER
+             );
+             CREATE INDEX IF NOT EXISTS idx_undo_journal_turn ON undo_journal(turn_id);",
+        ),
+    }],
+};
+
+/// SQLite-backed undo journal.
+pub struct UndoJournal {
+    conn: Mutex<Connection>,
+}
+
+impl UndoJournal {
+    pub fn open(workspace_dir: &Path) -> Result<Self> {
+        let db_path = SCHEMA.db_path(workspace_dir);
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        migrate(&conn, &SCHEMA)?;
+        conn.execute(
+            "DELETE FROM undo_journal WHERE recorded_at < ?1",
+            params![Utc::now().timestamp() - RETENTION_SECS],
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn insert(
+        &self,
+        turn_id: &str,
+        kind: UndoKind,
+        target: &str,
+        previous: Option<&[u8]>,
+        detail: Option<&str>,
+    ) -> Result<()> {
+        self.conn.lock().execute(
+            "INSERT INTO undo_journal (turn_id, kind, target, previous, detail, recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                turn_id,
+                kind.as_str(),
+                target,
+                previous,
+                detail,
+                Utc::now().timestamp()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Attribute the actions of `turn_id` to `principal` (`<channel>:<sender>`).
+    pub fn claim_turn(&self, turn_id: &str, principal: &str) -> Result<()> {
+        self.conn.lock().execute(
+            "UPDATE undo_journal SET principal = ?2 WHERE turn_id = ?1 AND principal IS NULL",
+            params![turn_id, principal],
+        )?;
+        Ok(())
+    }
+
+    /// Turns with actions not undone yet, newest first, optionally only
+    /// those of `principal`.
+    pub fn turns(&self, principal: Option<&str>, limit: usize) -> Result<Vec<UndoTurn>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT turn_id, principal, COUNT(*), MAX(recorded_at) FROM undo_journal
+             WHERE undone_at IS NULL AND (?1 IS NULL OR principal = ?1)
+             GROUP BY turn_id
+             ORDER BY MAX(id) DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(
+            params![principal, i64::try_from(limit).unwrap_or(i64::MAX)],
+            |row| {
+                Ok(UndoTurn {
+                    turn_id: row.get(0)?,
+                    principal: row.get(1)?,
+                    actions: row.get::<_, i64>(2)?.try_into().unwrap_or_default(),
+                    last_recorded_at: row.get(3)?,
+                })
+            },
+        )?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    /// Actions of `turn_id` not undone yet, newest first.
+    pub fn entries(&self, turn_id: &str) -> Result<Vec<UndoEntry>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, turn_id, principal, kind, target, previous, detail, recorded_at
+             FROM undo_journal
+             WHERE turn_id = ?1 AND undone_at IS NULL
+             ORDER BY id DESC",
+        )?;
+        let rows = stmt.query_map(params![turn_id], |row| {
+            let kind: String = row.get(3)?;
+            let Some(kind) = UndoKind::parse(&kind) else {
+                return Ok(None);
+            };
+            Ok(Some(UndoEntry {
+                id: row.get(0)?,
+                turn_id: row.get(1)?,
+                principal: row.get(2)?,
+                kind,
+                target: row.get(4)?,
+                previous: row.get(5)?,
+                detail: row.get(6)?,
+                recorded_at: row.get(7)?,
+            }))
+        })?;
+        Ok(rows
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    fn mark_undone(&self, id: i64) -> Result<()> {
+        self.conn.lock().execute(
+            "UPDATE undo_journal SET undone_at = ?2 WHERE id = ?1",
+            params![id, Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    /// Revert every action of `turn_id`, newest first, and describe each
+    /// one. Memory entries are skipped when `memory` is `None`.
+    pub async fn undo_turn(
+        &self,
+        turn_id: &str,
+        memory: Option<&dyn Memory>,
+    ) -> Result<Vec<String>> {
+        let mut report = Vec::new();
+        for entry in self.entries(turn_id)? {
+            let outcome = match entry.kind {
+                UndoKind::File => revert_file(&entry).await,
+                UndoKind::GitCommit => revert_commit(&entry).await,
+                UndoKind::Memory => match memory {
+                    Some(memory) => revert_memory(&entry, memory).await,
+                    None => Err(anyhow::anyhow!("memory backend unavailable")),
+                },
+            };
+            match outcome {
+                Ok(done) => {
+                    self.mark_undone(entry.id)?;
+                    report.push(done);
+                }
+                Err(e) => report.push(format!(
+                    "Skipped {} `{}`: {e}",
+                    entry.kind.as_str(),
+                    entry.target
+                )),
+            }
+        }
+        Ok(report)
+    }
+
+    /// The latest turn of `principal` (any principal when `None`) that can
+    /// still be undone.
+    pub fn last_turn(&self, principal: Option<&str>) -> Result<Option<String>> {
+        Ok(self
+            .turns(principal, 1)?
+            .into_iter()
+            .next()
+            .map(|turn| turn.turn_id))
+    }
+
+    fn has_entries(&self, turn_id: &str) -> Result<bool> {
+        Ok(self
+            .conn
+            .lock()
+            .query_row(
+                "SELECT 1 FROM undo_journal WHERE turn_id = ?1 LIMIT 1",
+                params![turn_id],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some())
+    }
+}
+
+async fn revert_file(entry: &UndoEntry) -> Result<String> {
+    let path = Path::new(&entry.target);
+    match entry.previous.as_deref() {
+        Some(previous) => {
+            tokio::fs::write(path, previous).await?;
+            Ok(format!("Restored `{}`", entry.target))
+        }
+        None => match tokio::fs::remove_file(path).await {
+            Ok(()) => Ok(format!("Removed `{}`", entry.target)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Ok(format!("`{}` was already removed", entry.target))
+            }
+            Err(e) => Err(e.into()),
+        },
+    }
+}
+
+async fn revert_commit(entry: &UndoEntry) -> Result<String> {
+    let (repo, commit) = entry
+        .target
+        .rsplit_once('@')
+        .context("malformed commit entry")?;
+    let head = git(repo, &["rev-parse", "HEAD"]).await?;
+    if head.trim() != commit {
+        anyhow::bail!("commit is no longer HEAD");
+    }
+    git(repo, &["reset", "--soft", "HEAD~1"]).await?;
+    Ok(format!(
+        "Reset commit {} in `{repo}` (changes kept staged)",
+        &commit[..commit.len().min(12)]
+    ))
+}
+
+async fn git(repo: &str, args: &[&str]) -> Result<String> {
+    let output = tokio::process::Command::new("git")
+        .args(["-c", "core.hooksPath=/dev/null"])
+        .args(args)
+        .current_dir(repo)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .await?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[derive(serde::Deserialize)]
+struct MemoryDetail {
+    namespace: Option<String>,
+    category: Option<MemoryCategory>,
+}
+
+async fn revert_memory(entry: &UndoEntry, memory: &dyn Memory) -> Result<String> {
+    let detail: MemoryDetail = serde_json::from_str(entry.detail.as_deref().unwrap_or("{}"))?;
+    match (entry.previous.as_deref(), detail.category) {
+        (Some(previous), Some(category)) => {
+            memory
+                .store(
+                    &entry.target,
+                    &String::from_utf8_lossy(previous),
+                    category,
+                    detail.namespace.as_deref(),
+                )
+                .await?;
+            Ok(format!("Restored memory `{}`", entry.target))
+        }
+        _ => {
+            memory.forget(&entry.target).await?;
+            Ok(format!("Forgot memory `{}`", entry.target))
+        }
+    }
+}
+
+static JOURNAL: Scoped<Option<Arc<UndoJournal>>> = Scoped::new(None);
+
+/// Open (or disable) the undo journal per `[agent].undo_journal`.
+pub fn init_from_config(config: &AgentConfig, workspace_dir: &Path) {
+    let journal = if config.undo_journal {
+        match UndoJournal::open(workspace_dir) {
+            Ok(journal) => Some(Arc::new(journal)),
+            Err(e) => {
+                tracing::warn!("Failed to open undo journal: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    JOURNAL.set(journal);
+}
+
+/// The active journal, if enabled.
+pub fn journal() -> Option<Arc<UndoJournal>> {
+    JOURNAL.get()
+}
+
+fn record(kind: UndoKind, target: &str, previous: Option<&[u8]>, detail: Option<&str>) {
+    let (Some(journal), Some(turn_id)) = (journal(), turn::current_turn_id()) else {
+        return;
+    };
+    if let Err(e) = journal.insert(&turn_id, kind, target, previous, detail) {
+        tracing::warn!(target, "Failed to record undo entry: {e}");
+    }
+}
+
+/// Back up `path` before a tool overwrites or creates it. Call right before
+/// the write.
+pub async fn record_file_change(path: &Path) {
+    if journal().is_none() || turn::current_turn_id().is_none() {
+        return;
+    }
+    let target = path.display().to_string();
+    match tokio::fs::metadata(path).await {
+        Ok(meta) if meta.len() > MAX_BACKUP_BYTES => {
+            tracing::info!(%target, "File too large to back up; its write cannot be undone");
+        }
+        Ok(_) => match tokio::fs::read(path).await {
+            Ok(previous) => record(UndoKind::File, &target, Some(&previous), None),
+            Err(e) => tracing::warn!(%target, "Failed to back up file for undo: {e}"),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            record(UndoKind::File, &target, None, None);
+        }
+        Err(e) => tracing::warn!(%target, "Failed to back up file for undo: {e}"),
+    }
+}
+
+/// Record a commit a tool created in `repo`.
+pub fn record_git_commit(repo: &Path, commit: &str) {
+    record(
+        UndoKind::GitCommit,
+        &format!("{}@{}", repo.display(), commit.trim()),
+        None,
+        None,
+    );
+}
+
+/// Record a memory write to `stored_key`; `previous` is the entry it
+/// replaces.
+pub fn record_memory_write(
+    stored_key: &str,
+    namespace: Option<&str>,
+    previous: Option<(&str, &MemoryCategory)>,
+) {
+    let detail = serde_json::json!({
+        "namespace": namespace,
+        "category": previous.map(|(_, category)| category),
+    })
+    .to_string();
+    record(
+        UndoKind::Memory,
+        stored_key,
+        previous.map(|(content, _)| content.as_bytes()),
+        Some(&detail),
+    );
+}
+
+/// Attribute the running turn's actions to `principal`.
+pub fn claim_current_turn(principal: &str) {
+    let (Some(journal), Some(turn_id)) = (journal(), turn::current_turn_id()) else {
+        return;
+    };
+    if let Err(e) = journal.claim_turn(&turn_id, principal) {
+        tracing::warn!("Failed to attribute undo entries: {e}");
+    }
+}
+
+/// Handle `zeroclaw undo <subcommand>` CLI commands.
+pub async fn handle_command(
+    command: crate::UndoCommands,
+    config: &crate::config::Config,
+) -> Result<()> {
+    let journal = UndoJournal::open(&config.workspace_dir)?;
+    let turn_id = match command {
+        crate::UndoCommands::List { limit, json } => {
+            let turns = journal.turns(None, limit)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&turns)?);
+                return Ok(());
+            }
+            if turns.is_empty() {
+                println!("Nothing to undo.");
+                return Ok(());
+            }
+            println!(
+                "{:<38} {:<28} {:>7} RECORDED",
+                "TURN", "PRINCIPAL", "ACTIONS"
+            );
+            for turn in turns {
+                let recorded = chrono::DateTime::from_timestamp(turn.last_recorded_at, 0)
+                    .map(|at| at.to_rfc3339())
+                    .unwrap_or_default();
+                println!(
+                    "{:<38} {:<28} {:>7} {recorded}",
+                    turn.turn_id,
+                    turn.principal.as_deref().unwrap_or("-"),
+                    turn.actions
+                );
+            }
+            return Ok(());
+        }
+        crate::UndoCommands::Last => match journal.last_turn(None)? {
+            Some(turn_id) => turn_id,
+            None => {
+                println!("Nothing to undo.");
+                return Ok(());
+            }
+        },
+        crate::UndoCommands::Turn { id } => {
+            if !journal.has_entries(&id)? {
+                anyhow::bail!("No undo entries recorded for turn {id}");
+            }
+            id
+        }
+    };
+
+    let (_registry, memory) = crate::agent::loop_::build_tool_registry(config).await?;
+    let report = journal.undo_turn(&turn_id, Some(memory.as_ref())).await?;
+    if report.is_empty() {
+        println!("Turn {turn_id} was already undone.");
+    } else {
+        println!("Undid turn {turn_id}:");
+        for line in report {
+            println!("  {line}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn undo_restores_files_and_memory_of_the_latest_turn() {
+        let tmp = TempDir::new().unwrap();
+        let journal = UndoJournal::open(tmp.path()).unwrap();
+        let memory = SqliteMemory::new(tmp.path()).unwrap();
+
+        let edited = tmp.path().join("notes.md");
+        let created = tmp.path().join("new.md");
+        std::fs::write(&edited, "after").unwrap();
+        std::fs::write(&created, "created").unwrap();
+        memory
+            .store("lang", "fr", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        journal
+            .insert(
+                "t1",
+                UndoKind::File,
+                &edited.display().to_string(),
+                Some(b"before"),
+                None,
+            )
+            .unwrap();
+        journal
+            .insert(
+                "t2",
+                UndoKind::File,
+                &created.display().to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        journal
+            .insert(
+                "t2",
+                UndoKind::Memory,
+                "lang",
+                Some(b"en"),
+                Some(r#"{"namespace":null,"category":"core"}"#),
+            )
+            .unwrap();
+        journal.claim_turn("t2", "telegram:alice").unwrap();
+
+        assert_eq!(
+            journal
+                .last_turn(Some("telegram:alice"))
+                .unwrap()
+                .as_deref(),
+            Some("t2")
+        );
+        let report = journal
+            .undo_turn("t2", Some(&memory as &dyn Memory))
+            .await
+            .unwrap();
+        assert_eq!(report.len(), 2);
+        assert!(!created.exists());
+        assert_eq!(memory.get("lang").await.unwrap().unwrap().content, "en");
+        assert_eq!(std::fs::read_to_string(&edited).unwrap(), "after");
+
+        // The undone turn is gone; the earlier one is next.
+        assert_eq!(journal.last_turn(None).unwrap().as_deref(), Some("t1"));
+        journal.undo_turn("t1", None).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&edited).unwrap(), "before");
+        assert!(journal.last_turn(None).unwrap().is_none());
+    }
+}
@@ -1,9 +1,7 @@
 use super::traits::{Tool, ToolResult};
-use super::url_validation::{
-    normalize_allowed_domains, validate_url, DomainPolicy, UrlSchemePolicy,
-};
+use super::url_validation::{normalize_allowed_domains, DomainPolicy, UrlSchemePolicy};
 use crate::config::UrlAccessConfig;
-use crate::security::SecurityPolicy;
+use crate::security::{EgressPolicy, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -24,7 +22,7 @@ pub struct WebFetchTool {
     api_url: Option<String>,
     allowed_domains: Vec<String>,
     blocked_domains: Vec<String>,
-    url_access: UrlAccessConfig,
+    egress: Arc<EgressPolicy>,
     max_response_size: usize,
     timeout_secs: u64,
     user_agent: String,
@@ -67,7 +65,7 @@ impl WebFetchTool {
             api_url,
             allowed_domains: normalize_allowed_domains(allowed_domains),
             blocked_domains: normalize_allowed_domains(blocked_domains),
-            url_access,
+            egress: Arc::new(EgressPolicy::from_url_access(url_access)),
             max_response_size,
             timeout_secs,
             user_agent,
@@ -75,6 +73,13 @@ impl WebFetchTool {
         }
     }
 
+    /// Share one [`EgressPolicy`] with the other network tools so rate limits
+    /// and counters are tracked across all of them.
+    pub fn with_egress_policy(mut self, egress: Arc<EgressPolicy>) -> Self {
+        self.egress = egress;
+        self
+    }
+
     fn get_next_api_key(&self) -> Option<String> {
         if self.api_keys.is_empty() {
             return None;
@@ -84,7 +89,8 @@ impl WebFetchTool {
     }
 
     fn validate_url(&self, raw_url: &str) -> anyhow::Result<String> {
-        validate_url(
+        self.egress.authorize(
+            "web_fetch",
             raw_url,
             &DomainPolicy {
                 allowed_domains: &self.allowed_domains,
@@ -94,7 +100,7 @@ impl WebFetchTool {
                 empty_allowed_message: "web_fetch tool is enabled but no allowed_domains are configured. Add [web_fetch].allowed_domains in config.toml",
                 scheme_policy: UrlSchemePolicy::HttpOrHttps,
                 ipv6_error_context: "web_fetch",
-                url_access: Some(&self.url_access),
+                url_access: None,
             },
         )
     }
@@ -0,0 +1,148 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::Config;
+use crate::security::SecurityPolicy;
+use crate::workflows::{self, WorkflowRunStatus};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Runs a workflow from `<workspace>/workflows/`. Also the entry point for
+/// scheduled workflows (a cron tool job calling `workflow_run`) and for the
+/// `/workflow` chat command.
+pub struct WorkflowRunTool {
+    config: Arc<Config>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl WorkflowRunTool {
+    pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    /// Run `name` and report the result; `trigger` is recorded in the run log.
+    async fn run(&self, name: &str, trigger: &str) -> ToolResult {
+        let failure = |error: String| ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error),
+        };
+
+        if !self.security.can_act() {
+            return failure(
+                "Security policy: read-only mode, cannot perform 'workflow_run'".into(),
+            );
+        }
+        if self.security.is_rate_limited() {
+            return failure("Rate limit exceeded: too many actions in the last hour".into());
+        }
+        let workflow = match workflows::find_workflow(&self.config.workspace_dir, name) {
+            Ok(workflow) => workflow,
+            Err(e) => return failure(e.to_string()),
+        };
+        if !self.security.record_action() {
+            return failure("Rate limit exceeded: action budget exhausted".into());
+        }
+
+        let record = match workflows::run_workflow(&self.config, &workflow, trigger).await {
+            Ok(record) => record,
+            Err(e) => return failure(e.to_string()),
+        };
+        match record.status {
+            WorkflowRunStatus::Succeeded => ToolResult {
+                success: true,
+                output: record.output.unwrap_or_default(),
+                error: None,
+            },
+            WorkflowRunStatus::Failed => failure(format!(
+                "workflow '{}' failed: {}",
+                workflow.name,
+                record.error.unwrap_or_default()
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for WorkflowRunTool {
+    fn name(&self) -> &str {
+        "workflow_run"
+    }
+
+    fn description(&self) -> &str {
+        "Run a named multi-step workflow defined in the workspace workflows/ directory and return its final output"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Workflow name"
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let name = match args.get("name").and_then(serde_json::Value::as_str) {
+            Some(v) if !v.trim().is_empty() => v.trim(),
+            _ => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("Missing 'name' parameter".to_string()),
+                });
+            }
+        };
+        Ok(self.run(name, "tool").await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn tool(tmp: &TempDir, level: AutonomyLevel) -> WorkflowRunTool {
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        config.autonomy.level = level;
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        WorkflowRunTool::new(Arc::new(config), security)
+    }
+
+    #[tokio::test]
+    async fn reports_unknown_workflow() {
+        let tmp = TempDir::new().unwrap();
+        let result = tool(&tmp, AutonomyLevel::Supervised)
+            .execute(json!({ "name": "missing" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("workflow 'missing' not found"));
+    }
+
+    #[tokio::test]
+    async fn blocks_runs_in_read_only_mode() {
+        let tmp = TempDir::new().unwrap();
+        let result = tool(&tmp, AutonomyLevel::ReadOnly)
+            .execute(json!({ "name": "anything" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
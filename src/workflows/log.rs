@@ -0,0 +1,166 @@
+//! Append-only execution log (`<workspace>/state/workflow_runs.jsonl`).
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Step outputs are truncated to this many characters in the log.
+const MAX_LOGGED_OUTPUT_CHARS: usize = 4000;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowRunStatus {
+    Succeeded,
+    Failed,
+}
+
+impl fmt::Display for WorkflowRunStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Succeeded => write!(f, "succeeded"),
+            Self::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowStepRecord {
+    pub name: String,
+    pub status: WorkflowRunStatus,
+    pub attempts: u32,
+    #[serde(default)]
+    pub output: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowRunRecord {
+    pub id: String,
+    pub workflow: String,
+    /// What started the run: `cli`, or `tool` for the agent, chat commands
+    /// and cron jobs.
+    pub trigger: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub status: WorkflowRunStatus,
+    pub steps: Vec<WorkflowStepRecord>,
+    /// Output of the last step when the run succeeded.
+    #[serde(default)]
+    pub output: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+pub struct WorkflowRunLog {
+    path: PathBuf,
+}
+
+impl WorkflowRunLog {
+    pub fn new(workspace_dir: &Path) -> Self {
+        Self {
+            path: workspace_dir.join("state").join("workflow_runs.jsonl"),
+        }
+    }
+
+    pub fn append(&self, record: &WorkflowRunRecord) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut logged = record.clone();
+        for step in &mut logged.steps {
+            step.output = step
+                .output
+                .as_deref()
+                .map(|output| crate::util::truncate_with_ellipsis(output, MAX_LOGGED_OUTPUT_CHARS));
+        }
+        logged.output = logged
+            .output
+            .as_deref()
+            .map(|output| crate::util::truncate_with_ellipsis(output, MAX_LOGGED_OUTPUT_CHARS));
+
+        let mut line = serde_json::to_string(&logged)?;
+        line.push('\n');
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("failed to open {}", self.path.display()))?;
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Most recent runs first, optionally only those of one workflow.
+    pub fn recent(&self, workflow: Option<&str>, limit: usize) -> Result<Vec<WorkflowRunRecord>> {
+        let raw = match std::fs::read_to_string(&self.path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(raw
+            .lines()
+            .rev()
+            .filter_map(|line| serde_json::from_str::<WorkflowRunRecord>(line).ok())
+            .filter(|run| workflow.is_none_or(|name| run.workflow == name))
+            .take(limit)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn record(workflow: &str, status: WorkflowRunStatus) -> WorkflowRunRecord {
+        let now = Utc::now();
+        WorkflowRunRecord {
+            id: uuid::Uuid::new_v4().to_string(),
+            workflow: workflow.into(),
+            trigger: "cli".into(),
+            started_at: now,
+            finished_at: now,
+            status,
+            steps: vec![WorkflowStepRecord {
+                name: "only".into(),
+                status,
+                attempts: 1,
+                output: Some("x".repeat(MAX_LOGGED_OUTPUT_CHARS + 100)),
+                error: None,
+                started_at: now,
+                finished_at: now,
+            }],
+            output: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn recent_returns_newest_first_and_filters_by_workflow() {
+        let tmp = TempDir::new().unwrap();
+        let log = WorkflowRunLog::new(tmp.path());
+        assert!(log.recent(None, 10).unwrap().is_empty());
+
+        log.append(&record("a", WorkflowRunStatus::Succeeded))
+            .unwrap();
+        log.append(&record("b", WorkflowRunStatus::Failed)).unwrap();
+        log.append(&record("a", WorkflowRunStatus::Failed)).unwrap();
+
+        let all = log.recent(None, 10).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].workflow, "a");
+        assert_eq!(all[0].status, WorkflowRunStatus::Failed);
+
+        let only_a = log.recent(Some("a"), 1).unwrap();
+        assert_eq!(only_a.len(), 1);
+        assert_eq!(only_a[0].status, WorkflowRunStatus::Failed);
+
+        let logged_output = all[0].steps[0].output.as_deref().unwrap();
+        assert!(logged_output.chars().count() < MAX_LOGGED_OUTPUT_CHARS + 100);
+    }
+}
@@ -0,0 +1,297 @@
+//! Named multi-step pipelines.
+//!
+//! A workflow is a TOML file in `<workspace>/workflows/` listing steps that
+//! run as separate agent turns, in order. Each step can restrict the tools it
+//! may use, declare success criteria, and retry on failure. The final output
+//! can be delivered to a channel. Workflows run from the CLI
+//! (`zeroclaw workflow run`), from chat (`/workflow <name>`), or on a schedule
+//! through a cron tool job that calls `workflow_run`.
+
+pub mod log;
+pub mod runner;
+
+pub use log::{WorkflowRunLog, WorkflowRunRecord, WorkflowRunStatus, WorkflowStepRecord};
+pub use runner::run_workflow;
+
+use crate::config::Config;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// A workflow definition (`<workspace>/workflows/<file>.toml`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workflow {
+    /// Unique name used to run the workflow.
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Where to deliver the final step's output.
+    #[serde(default)]
+    pub output: Option<WorkflowOutput>,
+    pub steps: Vec<WorkflowStep>,
+}
+
+/// Delivery target for a workflow's final output.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkflowOutput {
+    pub channel: String,
+    pub to: String,
+}
+
+/// One step of a workflow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowStep {
+    pub name: String,
+    /// Prompt for the step. `{{previous}}` expands to the previous step's
+    /// output and `{{steps.<name>}}` to the output of an earlier step.
+    pub prompt: String,
+    /// Tools the step may use; unset allows every tool.
+    #[serde(default)]
+    pub allowed_tools: Option<Vec<String>>,
+    /// Strings that must all appear in the output (case-insensitive) for the
+    /// step to succeed.
+    #[serde(default)]
+    pub success_contains: Vec<String>,
+    /// Extra attempts after a failed one.
+    #[serde(default)]
+    pub retries: u32,
+}
+
+impl Workflow {
+    fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            anyhow::bail!("workflow name must not be empty");
+        }
+        if self.steps.is_empty() {
+            anyhow::bail!("workflow '{}' has no steps", self.name);
+        }
+        let mut seen = std::collections::HashSet::new();
+        for step in &self.steps {
+            if step.name.trim().is_empty() {
+                anyhow::bail!("workflow '{}' has a step without a name", self.name);
+            }
+            if !seen.insert(step.name.as_str()) {
+                anyhow::bail!(
+                    "workflow '{}' has more than one step named '{}'",
+                    self.name,
+                    step.name
+                );
+            }
+            if step.prompt.trim().is_empty() {
+                anyhow::bail!(
+                    "workflow '{}' step '{}' has an empty prompt",
+                    self.name,
+                    step.name
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Directory holding workflow definitions.
+pub fn workflows_dir(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("workflows")
+}
+
+/// Parse a single workflow definition file.
+pub fn load_workflow_file(path: &Path) -> Result<Workflow> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let workflow: Workflow =
+        toml::from_str(&raw).with_context(|| format!("failed to parse {}", path.display()))?;
+    workflow.validate()?;
+    Ok(workflow)
+}
+
+/// Load every valid workflow in `<workspace>/workflows`, sorted by name.
+/// Invalid files are skipped with a warning.
+pub fn load_workflows(workspace_dir: &Path) -> Vec<Workflow> {
+    let dir = workflows_dir(workspace_dir);
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut workflows: Vec<Workflow> = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("toml") {
+            continue;
+        }
+        match load_workflow_file(&path) {
+            Ok(workflow) => {
+                if workflows.iter().any(|w| w.name == workflow.name) {
+                    warn!(
+                        "Skipping {}: workflow '{}' is already defined",
+                        path.display(),
+                        workflow.name
+                    );
+                    continue;
+                }
+                workflows.push(workflow);
+            }
+            Err(e) => warn!("Failed to load workflow from {}: {e:#}", path.display()),
+        }
+    }
+    workflows.sort_by(|a, b| a.name.cmp(&b.name));
+    workflows
+}
+
+/// Find a workflow by name.
+pub fn find_workflow(workspace_dir: &Path, name: &str) -> Result<Workflow> {
+    let name = name.trim();
+    load_workflows(workspace_dir)
+        .into_iter()
+        .find(|workflow| workflow.name == name)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "workflow '{name}' not found in {}",
+                workflows_dir(workspace_dir).display()
+            )
+        })
+}
+
+pub async fn handle_command(command: crate::WorkflowCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::WorkflowCommands::List => {
+            let workflows = load_workflows(&config.workspace_dir);
+            if workflows.is_empty() {
+                println!(
+                    "No workflows found in {}.",
+                    workflows_dir(&config.workspace_dir).display()
+                );
+                return Ok(());
+            }
+            for workflow in workflows {
+                println!(
+                    "{} ({} step{}){}",
+                    workflow.name,
+                    workflow.steps.len(),
+                    if workflow.steps.len() == 1 { "" } else { "s" },
+                    workflow
+                        .description
+                        .as_deref()
+                        .map(|d| format!(" — {d}"))
+                        .unwrap_or_default()
+                );
+            }
+            Ok(())
+        }
+        crate::WorkflowCommands::Run { name } => {
+            let workflow = find_workflow(&config.workspace_dir, &name)?;
+            let record = run_workflow(config, &workflow, "cli").await?;
+            for step in &record.steps {
+                println!(
+                    "[{}] {} ({} attempt{})",
+                    step.status,
+                    step.name,
+                    step.attempts,
+                    if step.attempts == 1 { "" } else { "s" }
+                );
+            }
+            match record.status {
+                WorkflowRunStatus::Succeeded => {
+                    println!("\n{}", record.output.unwrap_or_default());
+                    Ok(())
+                }
+                WorkflowRunStatus::Failed => anyhow::bail!(
+                    "workflow '{}' failed: {}",
+                    workflow.name,
+                    record.error.unwrap_or_default()
+                ),
+            }
+        }
+        crate::WorkflowCommands::Runs { name, limit } => {
+            let log = WorkflowRunLog::new(&config.workspace_dir);
+            let runs = log.recent(name.as_deref(), limit)?;
+            if runs.is_empty() {
+                println!("No workflow runs recorded.");
+                return Ok(());
+            }
+            for run in runs {
+                println!(
+                    "{} {} {} via {} — {}",
+                    run.started_at.to_rfc3339(),
+                    run.workflow,
+                    run.status,
+                    run.trigger,
+                    run.error.as_deref().unwrap_or("ok")
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const DIGEST: &str = r##"
+name = "standup-digest"
+description = "Morning digest of assigned issues"
+
+[output]
+channel = "slack"
+to = "C0STANDUP"
+
+[[steps]]
+name = "collect"
+prompt = "List the GitHub issues assigned to me."
+allowed_tools = ["http_request"]
+success_contains = ["#"]
+retries = 2
+
+[[steps]]
+name = "summarize"
+prompt = "Summarize for standup:\n{{previous}}"
+"##;
+
+    fn write(dir: &Path, file: &str, body: &str) {
+        std::fs::create_dir_all(workflows_dir(dir)).unwrap();
+        std::fs::write(workflows_dir(dir).join(file), body).unwrap();
+    }
+
+    #[test]
+    fn loads_valid_workflows_and_skips_broken_files() {
+        let tmp = TempDir::new().unwrap();
+        write(tmp.path(), "digest.toml", DIGEST);
+        write(tmp.path(), "empty.toml", "name = \"empty\"\nsteps = []\n");
+        write(tmp.path(), "broken.toml", "name = ");
+        write(tmp.path(), "notes.md", "# not a workflow");
+
+        let workflows = load_workflows(tmp.path());
+        assert_eq!(workflows.len(), 1);
+        let digest = &workflows[0];
+        assert_eq!(digest.name, "standup-digest");
+        assert_eq!(
+            digest.output,
+            Some(WorkflowOutput {
+                channel: "slack".into(),
+                to: "C0STANDUP".into(),
+            })
+        );
+        assert_eq!(digest.steps[0].retries, 2);
+        assert_eq!(
+            digest.steps[0].allowed_tools.as_deref(),
+            Some(&["http_request".to_string()][..])
+        );
+        assert!(digest.steps[1].allowed_tools.is_none());
+
+        assert!(find_workflow(tmp.path(), "standup-digest").is_ok());
+        assert!(find_workflow(tmp.path(), "missing").is_err());
+    }
+
+    #[test]
+    fn rejects_duplicate_step_names() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "dup.toml",
+            "name = \"dup\"\n[[steps]]\nname = \"a\"\nprompt = \"x\"\n[[steps]]\nname = \"a\"\nprompt = \"y\"\n",
+        );
+        let err = load_workflow_file(&workflows_dir(tmp.path()).join("dup.toml")).unwrap_err();
+        assert!(err.to_string().contains("more than one step named 'a'"));
+    }
+}
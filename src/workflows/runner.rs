@@ -0,0 +1,318 @@
+use super::log::{WorkflowRunLog, WorkflowRunRecord, WorkflowRunStatus, WorkflowStepRecord};
+use super::{Workflow, WorkflowStep};
+use crate::agent::ProcessMessageOptions;
+use crate::config::Config;
+use anyhow::Result;
+use chrono::Utc;
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+
+/// Names of workflows currently executing in this process. Guards against a
+/// workflow starting itself (directly or through `workflow_run`) and against
+/// overlapping scheduled runs.
+fn running_workflows() -> &'static Mutex<HashSet<String>> {
+    static RUNNING: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    RUNNING.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+struct RunningGuard(String);
+
+impl RunningGuard {
+    fn acquire(name: &str) -> Option<Self> {
+        let mut running = running_workflows()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        running
+            .insert(name.to_string())
+            .then(|| Self(name.to_string()))
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        running_workflows()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.0);
+    }
+}
+
+/// Run `workflow` through the agent, append the run to the execution log and
+/// deliver the final output when the workflow has an `[output]` target.
+pub async fn run_workflow(
+    config: &Config,
+    workflow: &Workflow,
+    trigger: &str,
+) -> Result<WorkflowRunRecord> {
+    let Some(_guard) = RunningGuard::acquire(&workflow.name) else {
+        anyhow::bail!("workflow '{}' is already running", workflow.name);
+    };
+
+    let run_id = uuid::Uuid::new_v4().to_string();
+    let session = format!("workflow:{}:{run_id}", workflow.name);
+    let mut record = execute_steps(workflow, trigger, run_id, |step, prompt| {
+        let config = config.clone();
+        let options = ProcessMessageOptions {
+            session: Some(session.clone()),
+            allowed_tools: step.allowed_tools.clone(),
+            ..ProcessMessageOptions::default()
+        };
+        async move { crate::agent::process_message_with_options(config, &prompt, options).await }
+    })
+    .await;
+
+    if let (WorkflowRunStatus::Succeeded, Some(target)) = (record.status, &workflow.output) {
+        let text = record.output.clone().unwrap_or_default();
+        if let Err(e) =
+            crate::cron::scheduler::deliver_announcement(config, &target.channel, &target.to, &text)
+                .await
+        {
+            record.status = WorkflowRunStatus::Failed;
+            record.error = Some(format!("delivery to {} failed: {e}", target.channel));
+        }
+    }
+
+    if let Err(e) = WorkflowRunLog::new(&config.workspace_dir).append(&record) {
+        tracing::warn!("Failed to record workflow run for '{}': {e}", workflow.name);
+    }
+    Ok(record)
+}
+
+/// Execute the steps in order with per-step retries. `execute` runs one
+/// rendered prompt as an agent turn.
+async fn execute_steps<F, Fut>(
+    workflow: &Workflow,
+    trigger: &str,
+    run_id: String,
+    mut execute: F,
+) -> WorkflowRunRecord
+where
+    F: FnMut(&WorkflowStep, String) -> Fut,
+    Fut: Future<Output = Result<String>>,
+{
+    let started_at = Utc::now();
+    let mut steps: Vec<WorkflowStepRecord> = Vec::with_capacity(workflow.steps.len());
+    let mut outputs: Vec<(&str, String)> = Vec::with_capacity(workflow.steps.len());
+    let mut error = None;
+
+    for step in &workflow.steps {
+        let prompt = render_prompt(step, &outputs);
+        let step_started = Utc::now();
+        let mut attempts = 0;
+        let mut last_error = String::new();
+        let mut output = None;
+
+        while attempts <= step.retries {
+            attempts += 1;
+            match execute(step, prompt.clone()).await {
+                Ok(text) => match check_success(step, &text) {
+                    Ok(()) => {
+                        output = Some(text);
+                        break;
+                    }
+                    Err(reason) => last_error = reason,
+                },
+                Err(e) => last_error = e.to_string(),
+            }
+            tracing::warn!(
+                workflow = workflow.name.as_str(),
+                step = step.name.as_str(),
+                attempt = attempts,
+                "Workflow step failed: {last_error}"
+            );
+        }
+
+        let succeeded = output.is_some();
+        steps.push(WorkflowStepRecord {
+            name: step.name.clone(),
+            status: if succeeded {
+                WorkflowRunStatus::Succeeded
+            } else {
+                WorkflowRunStatus::Failed
+            },
+            attempts,
+            output: output.clone(),
+            error: (!succeeded).then(|| last_error.clone()),
+            started_at: step_started,
+            finished_at: Utc::now(),
+        });
+
+        match output {
+            Some(text) => outputs.push((step.name.as_str(), text)),
+            None => {
+                error = Some(format!("step '{}' failed: {last_error}", step.name));
+                break;
+            }
+        }
+    }
+
+    let status = if error.is_none() {
+        WorkflowRunStatus::Succeeded
+    } else {
+        WorkflowRunStatus::Failed
+    };
+    WorkflowRunRecord {
+        id: run_id,
+        workflow: workflow.name.clone(),
+        trigger: trigger.to_string(),
+        started_at,
+        finished_at: Utc::now(),
+        status,
+        steps,
+        output: if error.is_none() {
+            outputs.pop().map(|(_, text)| text)
+        } else {
+            None
+        },
+        error,
+    }
+}
+
+/// Expand `{{previous}}` and `{{steps.<name>}}`. A step that references
+/// neither still sees the previous output, appended after its prompt.
+fn render_prompt(step: &WorkflowStep, outputs: &[(&str, String)]) -> String {
+    let previous = outputs.last().map(|(_, text)| text.as_str());
+    let mut prompt = step.prompt.clone();
+    let mut referenced = false;
+
+    if prompt.contains("{{previous}}") {
+        referenced = true;
+        prompt = prompt.replace("{{previous}}", previous.unwrap_or(""));
+    }
+    for (name, text) in outputs {
+        let placeholder = format!("{{{{steps.{name}}}}}");
+        if prompt.contains(&placeholder) {
+            referenced = true;
+            prompt = prompt.replace(&placeholder, text);
+        }
+    }
+
+    match previous {
+        Some(previous) if !referenced => {
+            format!("{prompt}\n\nOutput of the previous step:\n{previous}")
+        }
+        _ => prompt,
+    }
+}
+
+fn check_success(step: &WorkflowStep, output: &str) -> std::result::Result<(), String> {
+    let lowered = output.to_lowercase();
+    match step
+        .success_contains
+        .iter()
+        .find(|needle| !lowered.contains(&needle.to_lowercase()))
+    {
+        Some(missing) => Err(format!("output does not contain '{missing}'")),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    fn step(name: &str, prompt: &str) -> WorkflowStep {
+        WorkflowStep {
+            name: name.into(),
+            prompt: prompt.into(),
+            allowed_tools: None,
+            success_contains: Vec::new(),
+            retries: 0,
+        }
+    }
+
+    fn workflow(steps: Vec<WorkflowStep>) -> Workflow {
+        Workflow {
+            name: "test".into(),
+            description: None,
+            output: None,
+            steps,
+        }
+    }
+
+    #[test]
+    fn render_prompt_expands_placeholders_or_appends_previous_output() {
+        let outputs = vec![("collect", "issue #1".to_string())];
+        assert_eq!(
+            render_prompt(&step("s", "Summarize: {{previous}}"), &outputs),
+            "Summarize: issue #1"
+        );
+        assert_eq!(
+            render_prompt(&step("s", "From collect: {{steps.collect}}"), &outputs),
+            "From collect: issue #1"
+        );
+        assert_eq!(
+            render_prompt(&step("s", "Summarize"), &outputs),
+            "Summarize\n\nOutput of the previous step:\nissue #1"
+        );
+        assert_eq!(render_prompt(&step("s", "Start"), &[]), "Start");
+    }
+
+    #[tokio::test]
+    async fn steps_retry_until_success_criteria_are_met() {
+        let mut collect = step("collect", "List issues");
+        collect.success_contains = vec!["#".into()];
+        collect.retries = 2;
+        let summarize = step("summarize", "Summarize: {{previous}}");
+
+        let mut replies = VecDeque::from(vec![
+            Ok("no issues found".to_string()),
+            Ok("issue #7 is open".to_string()),
+            Ok("digest ready".to_string()),
+        ]);
+        let mut prompts = Vec::new();
+        let record = execute_steps(
+            &workflow(vec![collect, summarize]),
+            "cli",
+            "run-1".into(),
+            |_, prompt| {
+                prompts.push(prompt);
+                let reply = replies.pop_front().unwrap();
+                async move { reply }
+            },
+        )
+        .await;
+
+        assert_eq!(record.status, WorkflowRunStatus::Succeeded);
+        assert_eq!(record.steps[0].attempts, 2);
+        assert_eq!(record.steps[1].attempts, 1);
+        assert_eq!(prompts[2], "Summarize: issue #7 is open");
+        assert_eq!(record.output.as_deref(), Some("digest ready"));
+    }
+
+    #[tokio::test]
+    async fn run_stops_at_first_step_that_exhausts_retries() {
+        let mut flaky = step("flaky", "Try");
+        flaky.retries = 1;
+        let mut calls = 0;
+        let record = execute_steps(
+            &workflow(vec![flaky, step("never", "Unreached")]),
+            "tool",
+            "run-2".into(),
+            |_, _| {
+                calls += 1;
+                async { Err(anyhow::anyhow!("provider unavailable")) }
+            },
+        )
+        .await;
+
+        assert_eq!(calls, 2);
+        assert_eq!(record.status, WorkflowRunStatus::Failed);
+        assert_eq!(record.steps.len(), 1);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("step 'flaky' failed: provider unavailable")
+        );
+        assert!(record.output.is_none());
+    }
+
+    #[test]
+    fn running_guard_rejects_reentrant_runs() {
+        let guard = RunningGuard::acquire("guarded").unwrap();
+        assert!(RunningGuard::acquire("guarded").is_none());
+        drop(guard);
+        assert!(RunningGuard::acquire("guarded").is_some());
+    }
+}
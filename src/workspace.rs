@@ -0,0 +1,122 @@
+//! Per-workspace values of process-wide settings.
+//!
+//! Turn reports, the undo journal, personas, reply moderation, provider
+//! capability overrides, `[agent]` plan mode and tool concurrency limits,
+//! and i18n are set up once from the main config and read from anywhere in
+//! a turn. A gateway tenant (`[gateway.tenants]`) has its own config and
+//! workspace, so the gateway registers that config's values with
+//! [`init_tenant`] and runs the tenant's requests inside [`scope`]; there
+//! every [`Scoped`] setting resolves to the tenant's value. Outside a tenant
+//! scope they resolve to the main config's values.
+//!
+//! Like the turn id, the scope is a task-local: work spawned onto another
+//! task has to enter it again with `scope(current(), ..)`.
+
+use crate::config::Config;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+tokio::task_local! {
+    static WORKSPACE: PathBuf;
+}
+
+/// Run `fut` inside the scope of the tenant workspace `workspace_dir`;
+/// `None` runs it for the main workspace.
+pub async fn scope<F: Future>(workspace_dir: Option<PathBuf>, fut: F) -> F::Output {
+    match workspace_dir {
+        Some(workspace_dir) => WORKSPACE.scope(workspace_dir, fut).await,
+        None => fut.await,
+    }
+}
+
+/// Tenant workspace the current task runs in, if any.
+pub fn current() -> Option<PathBuf> {
+    WORKSPACE.try_with(Clone::clone).ok()
+}
+
+/// A setting with one value for the main config and one per tenant
+/// workspace.
+pub struct Scoped<T> {
+    main: RwLock<T>,
+    tenants: RwLock<BTreeMap<PathBuf, T>>,
+}
+
+impl<T: Clone> Scoped<T> {
+    pub const fn new(main: T) -> Self {
+        Self {
+            main: RwLock::new(main),
+            tenants: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Set the value of the current scope.
+    pub fn set(&self, value: T) {
+        match current() {
+            Some(workspace_dir) => {
+                self.tenants
+                    .write()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(workspace_dir, value);
+            }
+            None => *self.main.write().unwrap_or_else(|e| e.into_inner()) = value,
+        }
+    }
+
+    /// Value of the current scope. A tenant that never set one gets the
+    /// main value.
+    pub fn get(&self) -> T {
+        let tenant = WORKSPACE.try_with(|workspace_dir| {
+            self.tenants
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(workspace_dir)
+                .cloned()
+        });
+        match tenant {
+            Ok(Some(value)) => value,
+            _ => self.main.read().unwrap_or_else(|e| e.into_inner()).clone(),
+        }
+    }
+}
+
+/// Apply the workspace-scoped settings of `config` to the current scope.
+pub fn init_from_config(config: &Config) {
+    crate::observability::turn_report::init_from_config(
+        &config.observability,
+        &config.workspace_dir,
+    );
+    crate::i18n::init_from_config(&config.i18n, &config.workspace_dir);
+    crate::tools::concurrency::init_from_config(&config.agent);
+    crate::providers::capabilities::init_from_config(&config.provider_capabilities);
+    crate::channels::plan::init_from_config(&config.agent);
+    crate::channels::persona::init_from_config(&config.personas);
+    crate::tools::undo::init_from_config(&config.agent, &config.workspace_dir);
+    crate::security::moderation::init_from_config(&config.security.moderation);
+}
+
+/// Register the settings of a tenant config for its workspace.
+pub fn init_tenant(config: &Config) {
+    WORKSPACE.sync_scope(config.workspace_dir.clone(), || init_from_config(config));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn tenant_values_stay_inside_their_scope() {
+        static LIMIT: Scoped<u32> = Scoped::new(1);
+        let tenant = PathBuf::from("/tmp/zeroclaw-scoped-tenant");
+
+        scope(Some(tenant.clone()), async { LIMIT.set(5) }).await;
+        assert_eq!(LIMIT.get(), 1);
+        assert_eq!(scope(Some(tenant), async { LIMIT.get() }).await, 5);
+        assert_eq!(
+            scope(Some(PathBuf::from("/tmp/other")), async { LIMIT.get() }).await,
+            1
+        );
+        assert!(current().is_none());
+    }
+}
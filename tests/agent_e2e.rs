@@ -19,6 +19,7 @@ use zeroclaw::memory;
 use zeroclaw::memory::Memory;
 use zeroclaw::observability::{NoopObserver, Observer};
 use zeroclaw::providers::traits::ChatMessage;
+use zeroclaw::providers::vcr::ReplayProvider;
 use zeroclaw::providers::{
     ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderRuntimeOptions, ToolCall,
 };
@@ -659,6 +660,23 @@ async fn e2e_empty_memory_context_passthrough() {
     );
 }
 
+// ═════════════════════════════════════════════════════════════════════════════
+// Cassette replay — recorded provider sessions (tests/fixtures/cassettes)
+// ═════════════════════════════════════════════════════════════════════════════
+
+/// Replays a recorded tool-call turn through the full agent loop.
+#[tokio::test]
+async fn e2e_replayed_cassette_drives_tool_cycle() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/cassettes/echo_tool_turn.json");
+    let provider = ReplayProvider::from_file(&path).unwrap();
+    assert!(provider.supports_native_tools());
+
+    let mut agent = build_agent(Box::new(provider), vec![Box::new(EchoTool)]);
+    let response = agent.turn("Echo the phrase 'cassette ok'").await.unwrap();
+    assert_eq!(response, "The echo tool returned: cassette ok");
+}
+
 // ═════════════════════════════════════════════════════════════════════════════
 // Live integration test — real OpenAI Codex API (requires credentials)
 // ═════════════════════════════════════════════════════════════════════════════
@@ -92,6 +92,16 @@ export class WebSocketClient {
     this.ws.send(JSON.stringify({ type: 'message', content }));
   }
 
+  /** Answer an `approval_required` prompt for a pending tool call. */
+  sendApproval(requestId: string, approved: boolean): void {
+    if (!this.ws || this.ws.readyState !== WebSocket.OPEN) {
+      throw new Error('WebSocket is not connected');
+    }
+    this.ws.send(
+      JSON.stringify({ type: 'approval', request_id: requestId, approved }),
+    );
+  }
+
   /** Close the connection without auto-reconnecting. */
   disconnect(): void {
     this.intentionallyClosed = true;
@@ -1,13 +1,40 @@
 import { useState, useEffect, useRef } from 'react';
-import { Send, Bot, User, AlertCircle } from 'lucide-react';
+import {
+  Send,
+  Bot,
+  User,
+  AlertCircle,
+  Wrench,
+  ShieldAlert,
+  Check,
+  X,
+} from 'lucide-react';
 import type { WsMessage } from '@/types/api';
 import { WebSocketClient } from '@/lib/ws';
 
+interface ToolActivity {
+  callId?: string;
+  name: string;
+  args: unknown;
+  status: 'running' | 'succeeded' | 'failed';
+  output?: string;
+  durationMs?: number;
+}
+
+interface ApprovalPrompt {
+  requestId: string;
+  name: string;
+  args: unknown;
+  status: 'pending' | 'approved' | 'denied';
+}
+
 interface ChatMessage {
   id: string;
-  role: 'user' | 'agent';
+  role: 'user' | 'agent' | 'tool' | 'approval';
   content: string;
   timestamp: Date;
+  tool?: ToolActivity;
+  approval?: ApprovalPrompt;
 }
 
 let fallbackMessageIdCounter = 0;
@@ -24,10 +51,111 @@ function makeMessageId(): string {
     .slice(2, 10)}`;
 }
 
+function findLastIndex<T>(items: T[], predicate: (item: T) => boolean): number {
+  for (let i = items.length - 1; i >= 0; i -= 1) {
+    if (predicate(items[i]!)) return i;
+  }
+  return -1;
+}
+
+function formatArgs(args: unknown): string {
+  try {
+    return JSON.stringify(args ?? {}, null, 2);
+  } catch {
+    return String(args);
+  }
+}
+
+function ToolActivityCard({ tool }: { tool: ToolActivity }) {
+  const statusClass =
+    tool.status === 'running'
+      ? 'text-yellow-400'
+      : tool.status === 'succeeded'
+        ? 'text-green-400'
+        : 'text-red-400';
+  const statusLabel =
+    tool.status === 'running'
+      ? 'running...'
+      : `${tool.status}${tool.durationMs !== undefined ? ` in ${tool.durationMs} ms` : ''}`;
+
+  return (
+    <div className="ml-11 max-w-[75%] rounded-lg border border-gray-800 bg-gray-900/60 px-3 py-2 text-xs">
+      <details>
+        <summary className="flex cursor-pointer items-center gap-2 text-gray-300">
+          <Wrench className="h-3.5 w-3.5 flex-shrink-0 text-gray-500" />
+          <span className="font-mono">{tool.name}</span>
+          <span className={statusClass}>{statusLabel}</span>
+        </summary>
+        <pre className="mt-2 overflow-x-auto whitespace-pre-wrap break-words text-gray-400">
+          {formatArgs(tool.args)}
+        </pre>
+        {tool.output && (
+          <pre className="mt-2 max-h-64 overflow-auto whitespace-pre-wrap break-words border-t border-gray-800 pt-2 text-gray-300">
+            {tool.output}
+          </pre>
+        )}
+      </details>
+    </div>
+  );
+}
+
+function ApprovalCard({
+  approval,
+  disabled,
+  onDecide,
+}: {
+  approval: ApprovalPrompt;
+  disabled: boolean;
+  onDecide: (requestId: string, approved: boolean) => void;
+}) {
+  return (
+    <div className="ml-11 max-w-[75%] rounded-lg border border-amber-700/60 bg-amber-900/20 px-4 py-3 text-sm">
+      <div className="flex items-center gap-2 text-amber-300">
+        <ShieldAlert className="h-4 w-4 flex-shrink-0" />
+        <span>
+          Approval required for <span className="font-mono">{approval.name}</span>
+        </span>
+      </div>
+      <pre className="mt-2 overflow-x-auto whitespace-pre-wrap break-words text-xs text-gray-400">
+        {formatArgs(approval.args)}
+      </pre>
+      {approval.status === 'pending' ? (
+        <div className="mt-3 flex gap-2">
+          <button
+            onClick={() => onDecide(approval.requestId, true)}
+            disabled={disabled}
+            className="flex items-center gap-1 rounded-lg bg-green-700 px-3 py-1.5 text-xs text-white hover:bg-green-600 disabled:opacity-50"
+          >
+            <Check className="h-3.5 w-3.5" />
+            Approve
+          </button>
+          <button
+            onClick={() => onDecide(approval.requestId, false)}
+            disabled={disabled}
+            className="flex items-center gap-1 rounded-lg bg-red-800 px-3 py-1.5 text-xs text-white hover:bg-red-700 disabled:opacity-50"
+          >
+            <X className="h-3.5 w-3.5" />
+            Deny
+          </button>
+        </div>
+      ) : (
+        <p
+          className={`mt-2 text-xs ${
+            approval.status === 'approved' ? 'text-green-400' : 'text-red-400'
+          }`}
+        >
+          {approval.status === 'approved' ? 'Approved' : 'Denied'}
+        </p>
+      )}
+    </div>
+  );
+}
+
 export default function AgentChat() {
   const [messages, setMessages] = useState<ChatMessage[]>([]);
   const [input, setInput] = useState('');
   const [typing, setTyping] = useState(false);
+  const [streamingText, setStreamingText] = useState('');
   const [connected, setConnected] = useState(false);
   const [error, setError] = useState<string | null>(null);
 
@@ -57,6 +185,7 @@ export default function AgentChat() {
         case 'chunk':
           setTyping(true);
           pendingContentRef.current += msg.content ?? '';
+          setStreamingText(pendingContentRef.current);
           break;
 
         case 'message':
@@ -75,6 +204,7 @@ export default function AgentChat() {
           ]);
 
           pendingContentRef.current = '';
+          setStreamingText('');
           setTyping(false);
           break;
         }
@@ -84,21 +214,68 @@ export default function AgentChat() {
             ...prev,
             {
               id: makeMessageId(),
-              role: 'agent',
-              content: `[Tool Call] ${msg.name ?? 'unknown'}(${JSON.stringify(msg.args ?? {})})`,
+              role: 'tool',
+              content: '',
               timestamp: new Date(),
+              tool: {
+                callId: msg.id,
+                name: msg.name ?? 'unknown',
+                args: msg.args ?? {},
+                status: 'running',
+              },
             },
           ]);
           break;
 
         case 'tool_result':
+          setMessages((prev) => {
+            const index = findLastIndex(
+              prev,
+              (m) =>
+                m.tool?.status === 'running' &&
+                (msg.id ? m.tool.callId === msg.id : m.tool.name === msg.name),
+            );
+            const tool: ToolActivity = {
+              ...(index >= 0
+                ? prev[index]!.tool!
+                : { callId: msg.id, name: msg.name ?? 'unknown', args: {} }),
+              status: msg.success === false ? 'failed' : 'succeeded',
+              output: msg.output ?? '',
+              durationMs: msg.duration_ms,
+            };
+            if (index < 0) {
+              return [
+                ...prev,
+                {
+                  id: makeMessageId(),
+                  role: 'tool',
+                  content: '',
+                  timestamp: new Date(),
+                  tool,
+                },
+              ];
+            }
+            const next = [...prev];
+            next[index] = { ...prev[index]!, tool };
+            return next;
+          });
+          break;
+
+        case 'approval_required':
+          if (!msg.request_id) break;
           setMessages((prev) => [
             ...prev,
             {
               id: makeMessageId(),
-              role: 'agent',
-              content: `[Tool Result] ${msg.output ?? ''}`,
+              role: 'approval',
+              content: '',
               timestamp: new Date(),
+              approval: {
+                requestId: msg.request_id!,
+                name: msg.name ?? 'unknown',
+                args: msg.args ?? {},
+                status: 'pending',
+              },
             },
           ]);
           break;
@@ -115,6 +292,7 @@ export default function AgentChat() {
           ]);
           setTyping(false);
           pendingContentRef.current = '';
+          setStreamingText('');
           break;
       }
     };
@@ -129,7 +307,7 @@ export default function AgentChat() {
 
   useEffect(() => {
     messagesEndRef.current?.scrollIntoView({ behavior: 'smooth' });
-  }, [messages, typing]);
+  }, [messages, typing, streamingText]);
 
   const handleSend = () => {
     const trimmed = input.trim();
@@ -149,6 +327,7 @@ export default function AgentChat() {
       wsRef.current.sendMessage(trimmed);
       setTyping(true);
       pendingContentRef.current = '';
+      setStreamingText('');
     } catch {
       setError('Failed to send message. Please try again.');
     }
@@ -157,6 +336,25 @@ export default function AgentChat() {
     inputRef.current?.focus();
   };
 
+  const handleApproval = (requestId: string, approved: boolean) => {
+    try {
+      wsRef.current?.sendApproval(requestId, approved);
+    } catch {
+      setError('Failed to send approval. Please try again.');
+      return;
+    }
+    setMessages((prev) =>
+      prev.map((m): ChatMessage =>
+        m.approval?.requestId === requestId
+          ? {
+              ...m,
+              approval: { ...m.approval, status: approved ? 'approved' : 'denied' },
+            }
+          : m,
+      ),
+    );
+  };
+
   const handleKeyDown = (e: React.KeyboardEvent) => {
     if (e.key === 'Enter' && !e.shiftKey) {
       e.preventDefault();
@@ -184,44 +382,55 @@ export default function AgentChat() {
           </div>
         )}
 
-        {messages.map((msg) => (
-          <div
-            key={msg.id}
-            className={`flex items-start gap-3 ${
-              msg.role === 'user' ? 'flex-row-reverse' : ''
-            }`}
-          >
-            <div
-              className={`flex-shrink-0 w-8 h-8 rounded-full flex items-center justify-center ${
-                msg.role === 'user'
-                  ? 'bg-blue-600'
-                  : 'bg-gray-700'
-              }`}
-            >
-              {msg.role === 'user' ? (
-                <User className="h-4 w-4 text-white" />
-              ) : (
-                <Bot className="h-4 w-4 text-white" />
-              )}
-            </div>
+        {messages.map((msg) =>
+          msg.tool ? (
+            <ToolActivityCard key={msg.id} tool={msg.tool} />
+          ) : msg.approval ? (
+            <ApprovalCard
+              key={msg.id}
+              approval={msg.approval}
+              disabled={!connected}
+              onDecide={handleApproval}
+            />
+          ) : (
             <div
-              className={`max-w-[75%] rounded-xl px-4 py-3 ${
-                msg.role === 'user'
-                  ? 'bg-blue-600 text-white'
-                  : 'bg-gray-800 text-gray-100 border border-gray-700'
+              key={msg.id}
+              className={`flex items-start gap-3 ${
+                msg.role === 'user' ? 'flex-row-reverse' : ''
               }`}
             >
-              <p className="text-sm whitespace-pre-wrap break-words">{msg.content}</p>
-              <p
-                className={`text-xs mt-1 ${
-                  msg.role === 'user' ? 'text-blue-200' : 'text-gray-500'
+              <div
+                className={`flex-shrink-0 w-8 h-8 rounded-full flex items-center justify-center ${
+                  msg.role === 'user'
+                    ? 'bg-blue-600'
+                    : 'bg-gray-700'
+                }`}
+              >
+                {msg.role === 'user' ? (
+                  <User className="h-4 w-4 text-white" />
+                ) : (
+                  <Bot className="h-4 w-4 text-white" />
+                )}
+              </div>
+              <div
+                className={`max-w-[75%] rounded-xl px-4 py-3 ${
+                  msg.role === 'user'
+                    ? 'bg-blue-600 text-white'
+                    : 'bg-gray-800 text-gray-100 border border-gray-700'
                 }`}
               >
-                {msg.timestamp.toLocaleTimeString()}
-              </p>
+                <p className="text-sm whitespace-pre-wrap break-words">{msg.content}</p>
+                <p
+                  className={`text-xs mt-1 ${
+                    msg.role === 'user' ? 'text-blue-200' : 'text-gray-500'
+                  }`}
+                >
+                  {msg.timestamp.toLocaleTimeString()}
+                </p>
+              </div>
             </div>
-          </div>
-        ))}
+          ),
+        )}
 
         {typing && (
           <div className="flex items-start gap-3">
@@ -229,11 +438,15 @@ export default function AgentChat() {
               <Bot className="h-4 w-4 text-white" />
             </div>
             <div className="bg-gray-800 border border-gray-700 rounded-xl px-4 py-3">
-              <div className="flex items-center gap-1">
-                <span className="w-2 h-2 bg-gray-400 rounded-full animate-bounce" style={{ animationDelay: '0ms' }} />
-                <span className="w-2 h-2 bg-gray-400 rounded-full animate-bounce" style={{ animationDelay: '150ms' }} />
-                <span className="w-2 h-2 bg-gray-400 rounded-full animate-bounce" style={{ animationDelay: '300ms' }} />
-              </div>
+              {streamingText ? (
+                <p className="text-sm text-gray-100 whitespace-pre-wrap break-words">{streamingText}</p>
+              ) : (
+                <div className="flex items-center gap-1">
+                  <span className="w-2 h-2 bg-gray-400 rounded-full animate-bounce" style={{ animationDelay: '0ms' }} />
+                  <span className="w-2 h-2 bg-gray-400 rounded-full animate-bounce" style={{ animationDelay: '150ms' }} />
+                  <span className="w-2 h-2 bg-gray-400 rounded-full animate-bounce" style={{ animationDelay: '300ms' }} />
+                </div>
+              )}
               <p className="text-xs text-gray-500 mt-1">Typing...</p>
             </div>
           </div>
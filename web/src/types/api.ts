@@ -123,11 +123,22 @@ export interface SSEEvent {
 }
 
 export interface WsMessage {
-  type: 'message' | 'chunk' | 'tool_call' | 'tool_result' | 'done' | 'error';
+  type:
+    | 'message'
+    | 'chunk'
+    | 'tool_call'
+    | 'tool_result'
+    | 'approval_required'
+    | 'done'
+    | 'error';
   content?: string;
   full_response?: string;
+  id?: string;
+  request_id?: string;
   name?: string;
   args?: any;
   output?: string;
+  success?: boolean;
+  duration_ms?: number;
   message?: string;
 }